@@ -18,7 +18,14 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     tonic_prost_build::configure()
         .build_client(true)
         .build_server(true)
-        .compile_protos(&proto_paths, &[proto_root_str])?;
+        .compile_protos(&proto_paths, std::slice::from_ref(&proto_root_str))?;
+
+    // Client-only KernelGateway stubs for `eigen submit` against the kernel directly.
+    let kernel_gateway = proto_root.join("eigen/internal/v1/kernel_gateway.proto");
+    tonic_prost_build::configure()
+        .build_client(true)
+        .build_server(false)
+        .compile_protos(&[kernel_gateway.to_string_lossy().to_string()], &[proto_root_str])?;
 
     Ok(())
 }
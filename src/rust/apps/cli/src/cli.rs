@@ -0,0 +1,276 @@
+//! Command-line grammar: the `eigen` parser, its global flags and every subcommand.
+//!
+//! Job commands keep their argument structs next to their `run` functions under
+//! `commands/`; the commands still implemented in `main.rs` are declared here.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use clap::error::{ContextKind, ContextValue, ErrorKind};
+use clap::{Args, Parser, Subcommand};
+
+use crate::commands::{cancel, compile, config, list, logs, result, status, submit, wait};
+use crate::jobspec::GrpcCode;
+use crate::output::{OutputFormat, Printer};
+use crate::{CLI_VERSION, EXIT_USER_ERROR};
+
+/// Kernel address used by job commands when `--endpoint` is not given.
+pub const ENDPOINT_ENV: &str = "EIGEN_ENDPOINT";
+
+const GLOBAL_HEADING: &str = "Global Options";
+
+#[derive(Debug, Parser)]
+#[command(name = "eigen", version = CLI_VERSION, about = "Eigen CLI", arg_required_else_help = true)]
+pub(crate) struct Cli {
+    /// Output format for commands without their own --format
+    #[arg(short = 'o', long, value_enum, value_name = "FORMAT")]
+    pub format: Option<OutputFormat>,
+
+    #[command(flatten)]
+    pub global: GlobalArgs,
+
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+/// Flags accepted before or after any subcommand.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Args)]
+pub(crate) struct GlobalArgs {
+    /// Kernel address; job commands talk to this kernel instead of the System API
+    #[arg(long, global = true, env = ENDPOINT_ENV, value_name = "ADDR", help_heading = GLOBAL_HEADING)]
+    pub endpoint: Option<String>,
+
+    /// Print output as JSON and errors as a {"error", "code"} envelope
+    #[arg(long, global = true, help_heading = GLOBAL_HEADING)]
+    pub json: bool,
+
+    /// Only print results and errors
+    #[arg(short, long, global = true, help_heading = GLOBAL_HEADING)]
+    pub quiet: bool,
+
+    /// Deadline for each RPC and for --wait/--watch, e.g. 30s or 2m
+    #[arg(long, global = true, value_name = "DURATION", value_parser = wait::parse_duration, help_heading = GLOBAL_HEADING)]
+    pub timeout: Option<Duration>,
+
+    /// Bearer token sent to the kernel
+    #[arg(long, global = true, value_name = "TOKEN", help_heading = GLOBAL_HEADING)]
+    pub token: Option<String>,
+
+    /// PEM CA certificate; kernel connections use TLS when set
+    #[arg(long = "tls-cert", global = true, value_name = "PATH", help_heading = GLOBAL_HEADING)]
+    pub tls_cert: Option<PathBuf>,
+}
+
+impl GlobalArgs {
+    /// `--json` wins over `format`, the root `--format` value.
+    pub(crate) fn printer(&self, format: Option<OutputFormat>) -> Printer {
+        let format = if self.json { OutputFormat::Json } else { format.unwrap_or_default() };
+        Printer::new(format).with_quiet(self.quiet)
+    }
+}
+
+#[derive(Debug, Subcommand)]
+pub(crate) enum Command {
+    /// Print the CLI version
+    Version,
+    /// Submit a job file to the System API, or a program to a kernel
+    Submit(submit::SubmitArgs),
+    /// Show the state of a job
+    Status(status::StatusArgs),
+    /// List the jobs known to a kernel
+    List(list::ListArgs),
+    /// Cancel a job
+    Cancel(cancel::CancelArgs),
+    /// Write or show the CLI settings file
+    #[command(subcommand)]
+    Config(config::ConfigCommand),
+    /// Stream the progress of a job
+    Watch(JobIdArgs),
+    /// Fetch the results of a job
+    #[command(alias = "results")]
+    Result(result::ResultArgs),
+    /// Print the log files of a job from the local QFS
+    Logs(logs::LogsArgs),
+    /// Show why a job was dispatched where it was
+    Explain(JobIdArgs),
+    /// Compile a program on a kernel, or a job file to AQO JSON locally
+    Compile(compile::CompileArgs),
+    /// Render an AQO circuit
+    Visualize(VisualizeArgs),
+    /// Run or compare benchmark snapshots
+    #[command(subcommand)]
+    Benchmark(BenchmarkCommand),
+    /// Scaffold, validate, package or activate plugins
+    #[command(subcommand)]
+    Plugin(PluginCommand),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Args)]
+pub(crate) struct JobIdArgs {
+    /// Job id
+    pub job_id: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Args)]
+pub(crate) struct VisualizeArgs {
+    /// AQO JSON file to render
+    #[arg(short = 'f', long = "file", alias = "aqo", value_name = "PATH")]
+    pub aqo_file: PathBuf,
+}
+
+#[derive(Debug, Subcommand)]
+pub(crate) enum BenchmarkCommand {
+    /// Turn a benchmark config into a run snapshot
+    Run(BenchmarkRunArgs),
+    /// Compare a candidate snapshot against a baseline
+    Compare(BenchmarkCompareArgs),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Args)]
+pub(crate) struct BenchmarkRunArgs {
+    /// Benchmark run config (JSON)
+    #[arg(long, value_name = "PATH")]
+    pub config: PathBuf,
+
+    #[command(flatten)]
+    pub output: BenchmarkOutputArgs,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Args)]
+pub(crate) struct BenchmarkCompareArgs {
+    /// Baseline run snapshot
+    #[arg(long, value_name = "PATH")]
+    pub baseline: PathBuf,
+
+    /// Candidate run snapshot
+    #[arg(long, value_name = "PATH")]
+    pub candidate: PathBuf,
+
+    #[command(flatten)]
+    pub output: BenchmarkOutputArgs,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Args)]
+pub(crate) struct BenchmarkOutputArgs {
+    /// Report style on stdout
+    #[arg(long = "output", value_name = "MODE", default_value = "human", value_parser = ["human", "json"])]
+    pub mode: String,
+
+    /// Also write the JSON report to this file
+    #[arg(long, value_name = "PATH")]
+    pub output_file: Option<PathBuf>,
+}
+
+#[derive(Debug, Subcommand)]
+pub(crate) enum PluginCommand {
+    /// Create a plugin directory with a starter plugin.toml
+    Scaffold {
+        /// Directory to create
+        plugin_dir: PathBuf,
+        /// driver, compiler_backend, optimizer or policy
+        plugin_type: String,
+    },
+    /// Check a plugin manifest
+    Validate {
+        /// plugin.toml to check
+        manifest: PathBuf,
+    },
+    /// Package a plugin directory into a single file
+    Package {
+        /// Directory holding plugin.toml
+        plugin_dir: PathBuf,
+        /// Package file to write
+        output_file: PathBuf,
+    },
+    /// Resolve the activation order of a set of plugins
+    Activate {
+        /// plugin.toml files to activate together
+        #[arg(required = true)]
+        manifests: Vec<PathBuf>,
+    },
+}
+
+/// Reports arguments clap rejected and exits. Help, version and a bare `eigen` print
+/// as clap formats them; other errors become the JSON envelope when the command line
+/// asked for JSON.
+pub(crate) fn exit_on_parse_error(err: clap::Error, args: &[String]) -> ! {
+    let formatted_by_clap = matches!(
+        err.kind(),
+        ErrorKind::DisplayHelp | ErrorKind::DisplayVersion | ErrorKind::DisplayHelpOnMissingArgumentOrSubcommand
+    );
+    if formatted_by_clap || !json_requested(args) {
+        err.exit();
+    }
+    Printer::new(OutputFormat::Json).print_failure("eigen", GrpcCode::InvalidArgument.as_str(), &parse_error_message(&err));
+    std::process::exit(EXIT_USER_ERROR);
+}
+
+/// The first line of a clap error, with its "did you mean" suggestion if any.
+fn parse_error_message(err: &clap::Error) -> String {
+    let rendered = err.render().to_string();
+    let first_line = rendered.lines().next().unwrap_or_default();
+    let message = first_line.strip_prefix("error: ").unwrap_or(first_line).to_string();
+    let suggestion = [ContextKind::SuggestedSubcommand, ContextKind::SuggestedArg]
+        .into_iter()
+        .find_map(|kind| match err.get(kind)? {
+            ContextValue::String(value) => Some(value.clone()),
+            // clap lists the closest match last.
+            ContextValue::Strings(values) => values.last().cloned(),
+            _ => None,
+        });
+    match suggestion {
+        Some(suggestion) => format!("{message}; did you mean '{suggestion}'?"),
+        None => message,
+    }
+}
+
+/// Whether the raw command line asks for JSON output; used to report arguments
+/// that could not be parsed at all.
+pub(crate) fn json_requested(args: &[String]) -> bool {
+    args.iter().enumerate().any(|(i, arg)| match arg.as_str() {
+        "--json" | "--format=json" | "-ojson" => true,
+        "--format" | "-o" => args.get(i + 1).is_some_and(|value| value == "json"),
+        _ => false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn command_definition_is_consistent() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn global_flags_are_accepted_after_the_subcommand() {
+        let cli = Cli::try_parse_from(["eigen", "status", "job-1", "--json", "--timeout", "5s", "-q"]).expect("parse");
+        assert!(cli.global.json);
+        assert!(cli.global.quiet);
+        assert_eq!(cli.global.timeout, Some(Duration::from_secs(5)));
+        assert!(matches!(cli.command, Command::Status(_)));
+        assert!(cli.global.printer(None).is_json());
+    }
+
+    #[test]
+    fn unknown_commands_suggest_the_closest_one() {
+        let err = Cli::try_parse_from(["eigen", "stauts", "job-1"]).expect_err("unknown command");
+        assert_eq!(err.kind(), ErrorKind::InvalidSubcommand);
+        assert_eq!(parse_error_message(&err), "unrecognized subcommand 'stauts'; did you mean 'status'?");
+        assert_eq!(err.exit_code(), EXIT_USER_ERROR);
+
+        let err = Cli::try_parse_from(["eigen"]).expect_err("no command");
+        assert_eq!(err.kind(), ErrorKind::DisplayHelpOnMissingArgumentOrSubcommand);
+        assert_eq!(err.exit_code(), EXIT_USER_ERROR);
+    }
+
+    #[test]
+    fn json_is_detected_on_unparsed_arguments() {
+        let args = |items: &[&str]| items.iter().map(|item| item.to_string()).collect::<Vec<_>>();
+        assert!(json_requested(&args(&["eigen", "--format", "json", "list", "--limit", "0"])));
+        assert!(json_requested(&args(&["eigen", "stauts", "--json"])));
+        assert!(!json_requested(&args(&["eigen", "--format", "text", "list"])));
+    }
+}
@@ -0,0 +1,171 @@
+//! `eigen cancel`: request cancellation of a job, after confirmation.
+
+use std::io::{self, BufRead, Write};
+
+use clap::Args;
+use serde_json::json;
+
+use crate::cli::GlobalArgs;
+use crate::commands::wait::{self, JobSource, EXIT_JOB_NOT_FOUND};
+use crate::jobspec::{self, GrpcCode};
+use crate::output::{OutputFormat, Printer};
+use crate::{terminal_exit_code, EXIT_USER_ERROR};
+
+/// Exit code when the prompt was declined.
+const EXIT_DECLINED: i32 = 1;
+/// Exit code when the job is already terminal, whether the CLI or the server
+/// refused the cancellation.
+const EXIT_CANCEL_REJECTED: i32 = 5;
+
+#[derive(Debug, Clone, PartialEq, Eq, Args)]
+pub(crate) struct CancelArgs {
+    /// Job to cancel
+    job_id: String,
+    /// Skip the confirmation prompt
+    #[arg(short, long)]
+    yes: bool,
+    /// Send the cancellation even when the job already looks terminal
+    #[arg(long)]
+    force: bool,
+    /// System API address
+    #[arg(long, value_name = "ADDR")]
+    server: Option<String>,
+    /// Output format; overrides the global one
+    #[arg(short = 'o', long, value_enum, value_name = "FORMAT")]
+    format: Option<OutputFormat>,
+}
+
+/// Runs `eigen cancel`. Errors carry the process exit code: `5` when the job is
+/// already terminal and `4` when it does not exist.
+pub fn run(args: CancelArgs, global: &GlobalArgs, printer: &Printer) -> Result<(), i32> {
+    let printer = printer.with_format(args.format);
+    let source = JobSource::from_flags(args.server.clone(), global.endpoint.clone());
+
+    // With --force the status is informational only; the server has the last word.
+    let state_before = match source.status(&args.job_id) {
+        Ok(status) => Some(jobspec::map_job_state(status.state)),
+        Err(_) if args.force => None,
+        Err(err) => return Err(wait::report_error(&printer, "cancel", &args.job_id, &err)),
+    };
+    if !args.force && state_before.as_deref().is_some_and(|state| terminal_exit_code(state).is_some()) {
+        print_outcome(&printer, &args.job_id, false, state_before.as_deref());
+        return Err(EXIT_CANCEL_REJECTED);
+    }
+
+    if !args.yes {
+        // The prompt goes to stderr so `--format json` output stays machine-readable.
+        let confirmed = confirm(&args.job_id, &mut io::stdin().lock(), &mut io::stderr()).map_err(|err| {
+            let message = format!("could not read confirmation: {err}");
+            printer.print_failure("cancel", GrpcCode::InvalidArgument.as_str(), &message);
+            EXIT_USER_ERROR
+        })?;
+        if !confirmed {
+            printer.print_failure("cancel", "ABORTED", "aborted at the confirmation prompt");
+            return Err(EXIT_DECLINED);
+        }
+    }
+    cancel(&printer, &source, &args.job_id, state_before.as_deref())
+}
+
+/// Sends `CancelJob` and reports the outcome.
+///
+/// A `FAILED_PRECONDITION` answer is reported like `accepted: false`: both mean the
+/// job already reached a terminal state.
+fn cancel(printer: &Printer, source: &JobSource, job_id: &str, state_before: Option<&str>) -> Result<(), i32> {
+    let accepted = match source.cancel(job_id) {
+        Ok(accepted) => accepted,
+        Err(err) if err.code == GrpcCode::FailedPrecondition => false,
+        Err(err) if err.code == GrpcCode::NotFound => {
+            wait::report_error(printer, "cancel", job_id, &err);
+            return Err(EXIT_JOB_NOT_FOUND);
+        }
+        Err(err) => return Err(printer.print_error("cancel", &err)),
+    };
+    print_outcome(printer, job_id, accepted, state_before);
+    if accepted { Ok(()) } else { Err(EXIT_CANCEL_REJECTED) }
+}
+
+fn print_outcome(printer: &Printer, job_id: &str, accepted: bool, state_before: Option<&str>) {
+    match printer.format() {
+        OutputFormat::Text if accepted => {
+            if !printer.is_quiet() {
+                println!("cancellation accepted for job {job_id}");
+            }
+        }
+        OutputFormat::Text => match state_before {
+            Some(state) => println!("cancellation rejected: job {job_id} is already terminal (state {state})"),
+            None => println!("cancellation rejected: job {job_id} is already terminal"),
+        },
+        OutputFormat::Json => printer.print_json(&json!({
+            "job_id": job_id,
+            "accepted": accepted,
+            "state_before": state_before,
+        })),
+    }
+}
+
+/// Asks "Cancel job <id>? [y/N]" on `output` and reads the answer from `input`.
+/// Anything but `y`/`yes` (case-insensitive), including EOF, declines.
+fn confirm(job_id: &str, input: &mut impl BufRead, output: &mut impl Write) -> io::Result<bool> {
+    write!(output, "Cancel job {job_id}? [y/N] ")?;
+    output.flush()?;
+    let mut answer = String::new();
+    input.read_line(&mut answer)?;
+    Ok(matches!(answer.trim().to_ascii_lowercase().as_str(), "y" | "yes"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::{invoke, spawn_mock_server, MockJobService};
+    use crate::jobspec::eigen::api::v1 as api;
+
+    fn status(state: api::JobState) -> api::JobStatus {
+        api::JobStatus {
+            job_id: "job-cancel-1".to_string(),
+            state: state as i32,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn running_job_is_cancelled() {
+        let addr = spawn_mock_server(MockJobService::with_statuses([status(api::JobState::Running)]));
+        invoke(run, &["job-cancel-1", "--yes", "--server", &addr.to_string()]).expect("accepted");
+        invoke(run, &["job-cancel-1", "-y", "--json", "--server", &addr.to_string()]).expect("accepted with json output");
+    }
+
+    #[test]
+    fn done_job_is_refused_before_cancel_job_is_sent() {
+        let service = MockJobService::with_statuses([status(api::JobState::Done)]);
+        let addr = spawn_mock_server(service.clone()).to_string();
+        // No --yes: the refusal happens before the prompt would read stdin.
+        let code = invoke(run, &["job-cancel-1", "--server", &addr]).expect_err("refused");
+        assert_eq!(code, EXIT_CANCEL_REJECTED);
+        assert_eq!(service.status_polls(), 1);
+
+        // --force still asks the server, which rejects the already terminal job.
+        let code = invoke(run, &["job-cancel-1", "--force", "--yes", "--server", &addr]).expect_err("rejected by the server");
+        assert_eq!(code, EXIT_CANCEL_REJECTED);
+    }
+
+    #[test]
+    fn unknown_job_exits_with_not_found() {
+        let addr = spawn_mock_server(MockJobService::default()).to_string();
+        let code = invoke(run, &["job-missing", "--yes", "--json", "--server", &addr]).expect_err("not found");
+        assert_eq!(code, EXIT_JOB_NOT_FOUND);
+
+        let code = invoke(run, &["job-missing", "--force", "--yes", "--server", &addr]).expect_err("not found");
+        assert_eq!(code, EXIT_JOB_NOT_FOUND);
+    }
+
+    #[test]
+    fn confirmation_defaults_to_no() {
+        let mut prompt = Vec::new();
+        assert!(confirm("job-1", &mut "y\n".as_bytes(), &mut prompt).unwrap());
+        assert_eq!(String::from_utf8(prompt).unwrap(), "Cancel job job-1? [y/N] ");
+        assert!(confirm("job-1", &mut "YES\n".as_bytes(), &mut Vec::new()).unwrap());
+        assert!(!confirm("job-1", &mut "\n".as_bytes(), &mut Vec::new()).unwrap());
+        assert!(!confirm("job-1", &mut "".as_bytes(), &mut Vec::new()).unwrap());
+    }
+}
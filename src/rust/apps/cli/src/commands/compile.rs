@@ -0,0 +1,229 @@
+//! `eigen compile`: enqueue an Eigen-Lang program on a kernel and wait until it has
+//! been compiled, or compile a job file to AQO JSON locally with `--file`.
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use clap::Args;
+use serde_json::json;
+
+use crate::cli::GlobalArgs;
+use crate::commands::local_job_dir;
+use crate::commands::submit::program_name;
+use crate::commands::wait::{self, JobSource};
+use crate::jobspec::{self, eigen::api::v1 as api, GrpcCode};
+use crate::kernel::{self, KernelSubmission};
+use crate::output::Printer;
+use crate::{terminal_exit_code, EXIT_USER_ERROR};
+
+/// Delay between two `GetJobStatus` polls while the kernel compiles.
+pub(crate) const COMPILE_POLL_INTERVAL: Duration = Duration::from_millis(500);
+/// How long to wait for `QUEUED` when `--timeout` is not given.
+pub(crate) const DEFAULT_COMPILE_TIMEOUT: Duration = Duration::from_secs(60);
+/// Exit code when the kernel fails, cancels or times out the job before `QUEUED`,
+/// or when `--show-aqo` finds no compiled circuit.
+pub(crate) const EXIT_COMPILE_FAILED: i32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq, Args)]
+pub(crate) struct CompileArgs {
+    /// Eigen-Lang program (.eigen.py) to compile on the kernel
+    #[arg(value_name = "PROGRAM", required_unless_present = "job_file", conflicts_with = "job_file")]
+    program: Option<PathBuf>,
+    /// JobSpec sent with the program instead of a generated one
+    #[arg(long, value_name = "PATH", requires = "program")]
+    job_yaml: Option<PathBuf>,
+    /// Print the compiled AQO JSON on stdout
+    #[arg(long, conflicts_with = "job_file")]
+    show_aqo: bool,
+    /// Compile a job file locally instead
+    #[arg(short = 'f', long = "file", alias = "job", value_name = "PATH")]
+    job_file: Option<PathBuf>,
+    /// Where --file writes the AQO JSON
+    #[arg(short = 'o', long = "out", value_name = "PATH", default_value = "circuit.aqo.json")]
+    out_file: PathBuf,
+}
+
+pub(crate) fn run(args: CompileArgs, global: &GlobalArgs, printer: &Printer) -> Result<(), i32> {
+    let Some(program) = &args.program else {
+        let job_file = args.job_file.as_deref().expect("clap requires PROGRAM or --file");
+        return compile_locally(printer, job_file, &args.out_file);
+    };
+    let submission = submission(program, args.job_yaml.as_deref()).map_err(|message| {
+        printer.print_failure("compile", GrpcCode::InvalidArgument.as_str(), &message);
+        EXIT_USER_ERROR
+    })?;
+    let addr = global.endpoint.clone().unwrap_or_else(kernel::kernel_addr);
+    let job = kernel::enqueue(&addr, &submission).map_err(|err| printer.print_error("compile", &err))?;
+
+    let source = JobSource::Kernel(addr);
+    let timeout = global.timeout.unwrap_or(DEFAULT_COMPILE_TIMEOUT);
+    let status = wait_until_compiled(&source, &job.job_id, COMPILE_POLL_INTERVAL, timeout)
+        .map_err(|err| wait::report_wait_error(printer, "compile", &job.job_id, &err))?;
+    let state = jobspec::map_job_state(status.state);
+    match state.as_str() {
+        "ERROR" => {
+            let code = if status.error_code.is_empty() { GrpcCode::FailedPrecondition.as_str() } else { status.error_code.as_str() };
+            let summary = if status.error_summary.is_empty() { "compilation failed" } else { status.error_summary.as_str() };
+            printer.print_failure("compile", code, &format!("job {}: {summary}", job.job_id));
+            return Err(EXIT_COMPILE_FAILED);
+        }
+        "CANCELLED" | "TIMEOUT" => {
+            let message = format!("job {} ended in state {state} before it was queued", job.job_id);
+            printer.print_failure("compile", GrpcCode::FailedPrecondition.as_str(), &message);
+            return Err(EXIT_COMPILE_FAILED);
+        }
+        _ => {}
+    }
+
+    let aqo_path = compiled_aqo_path(&job.job_id);
+    let aqo_path = aqo_path.is_file().then_some(aqo_path);
+    if args.show_aqo {
+        let Some(path) = &aqo_path else {
+            let message = format!("job {} has no compiled circuit in QFS", job.job_id);
+            printer.print_failure("compile", GrpcCode::NotFound.as_str(), &message);
+            return Err(EXIT_COMPILE_FAILED);
+        };
+        let aqo_json = std::fs::read_to_string(path).map_err(|err| {
+            let message = format!("failed to read {}: {err}", path.display());
+            printer.print_failure("compile", GrpcCode::Internal.as_str(), &message);
+            EXIT_COMPILE_FAILED
+        })?;
+        printer.print_note(&format!("job_id: {}\nstate: {state}", job.job_id));
+        println!("{}", aqo_json.trim_end());
+        return Ok(());
+    }
+    if printer.is_json() {
+        printer.print_json(&json!({
+            "job_id": job.job_id,
+            "state": state,
+            "compiled_aqo": aqo_path.as_ref().map(|path| path.display().to_string()),
+        }));
+        return Ok(());
+    }
+    println!("job_id: {}", job.job_id);
+    println!("state: {state}");
+    if let Some(path) = &aqo_path {
+        println!("compiled_aqo: {}", path.display());
+    }
+    Ok(())
+}
+
+/// Polls until the job has left the compile stages (`QUEUED` or later) or is terminal.
+fn wait_until_compiled(
+    source: &JobSource,
+    job_id: &str,
+    interval: Duration,
+    timeout: Duration,
+) -> Result<api::JobStatus, wait::WaitError> {
+    wait::wait_until(source, job_id, interval, Some(timeout), |status| {
+        let state = jobspec::map_job_state(status.state);
+        matches!(state.as_str(), "QUEUED" | "RUNNING") || terminal_exit_code(&state).is_some()
+    })
+}
+
+/// The program plus `--job-yaml`, or a generated JobSpec named after the program.
+fn submission(program: &Path, job_yaml: Option<&Path>) -> Result<KernelSubmission, String> {
+    let source = std::fs::read(program).map_err(|err| format!("failed to read {}: {err}", program.display()))?;
+    match job_yaml {
+        Some(path) => {
+            let (spec, yaml) = jobspec::load_jobspec_file(path).map_err(|err| err.to_string())?;
+            Ok(KernelSubmission::from_jobspec(spec, yaml, source))
+        }
+        None => {
+            let name = program_name(program);
+            let yaml = minimal_job_yaml(&name);
+            Ok(KernelSubmission { job_yaml: yaml, ..KernelSubmission::program(name, source) })
+        }
+    }
+}
+
+fn minimal_job_yaml(name: &str) -> String {
+    format!("apiVersion: {}\nkind: QuantumJob\nmetadata:\n  name: {name}\n", jobspec::JOBSPEC_API_VERSION)
+}
+
+/// Where the kernel's local QFS keeps `qfs://jobs/<job_id>/compiled/circuit.aqo.json`.
+fn compiled_aqo_path(job_id: &str) -> PathBuf {
+    local_job_dir(job_id).join("compiled/circuit.aqo.json")
+}
+
+fn compile_locally(printer: &Printer, job_file: &Path, out_file: &Path) -> Result<(), i32> {
+    let fail = |message: String| {
+        printer.print_failure("compile", GrpcCode::InvalidArgument.as_str(), &message);
+        EXIT_USER_ERROR
+    };
+    let aqo_json = jobspec::compile_job_to_aqo_json(job_file).map_err(|err| fail(err.to_string()))?;
+    std::fs::write(out_file, aqo_json).map_err(|err| fail(format!("failed to write {}: {err}", out_file.display())))?;
+    println!("compiled_aqo: {}", out_file.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::{invoke, parse, spawn_kernel, spawn_mock_server, MockJobService};
+
+    fn status(state: api::JobState) -> api::JobStatus {
+        api::JobStatus {
+            job_id: "job-compile-1".to_string(),
+            state: state as i32,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn compiles_a_program_on_the_kernel() {
+        let dir = std::env::temp_dir().join(format!("eigen-cli-compile-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("temp dir");
+        let program = dir.join("bell.eigen.py");
+        std::fs::write(&program, "@quantum\ndef main():\n    pass\n").expect("write program");
+        let addr = spawn_kernel().to_string();
+
+        let program = program.to_str().expect("utf-8 path");
+        assert_eq!(invoke(run, &[program, "--endpoint", &addr, "--timeout", "30"]), Ok(()));
+        assert_eq!(invoke(run, &[program, "--endpoint", &addr, "--json"]), Ok(()));
+    }
+
+    #[test]
+    fn compile_times_out_while_the_job_is_still_compiling() {
+        let service = MockJobService::with_statuses([status(api::JobState::Pending), status(api::JobState::Compiling)]);
+        let source = JobSource::SystemApi(spawn_mock_server(service.clone()).to_string());
+
+        let err = wait_until_compiled(&source, "job-compile-1", Duration::from_millis(10), Duration::from_millis(100))
+            .expect_err("never queued");
+        assert!(matches!(&err, wait::WaitError::TimedOut(status) if status.state == api::JobState::Compiling as i32));
+        assert!(service.status_polls() >= 2);
+        assert_eq!(
+            wait::report_wait_error(&Printer::default(), "compile", "job-compile-1", &err),
+            wait::EXIT_WAIT_TIMEOUT
+        );
+    }
+
+    #[test]
+    fn compile_stops_polling_once_the_job_is_queued() {
+        let service = MockJobService::with_statuses([
+            status(api::JobState::Pending),
+            status(api::JobState::Compiling),
+            status(api::JobState::Queued),
+        ]);
+        let source = JobSource::SystemApi(spawn_mock_server(service.clone()).to_string());
+
+        let status = wait_until_compiled(&source, "job-compile-1", Duration::from_millis(10), Duration::from_secs(5))
+            .expect("queued");
+        assert_eq!(status.state, api::JobState::Queued as i32);
+        assert_eq!(service.status_polls(), 3);
+    }
+
+    #[test]
+    fn args_take_a_program_or_a_local_job_file() {
+        assert!(parse::<CompileArgs>(&[]).is_err());
+        assert!(parse::<CompileArgs>(&["bell.eigen.py", "--file", "job.yaml"]).is_err());
+        assert!(parse::<CompileArgs>(&["--file", "job.yaml", "--show-aqo"]).is_err());
+        let (parsed, global) = parse::<CompileArgs>(&["bell.eigen.py", "--job-yaml", "job.yaml", "--show-aqo", "--timeout", "5"])
+            .expect("parse");
+        assert_eq!(parsed.program, Some(PathBuf::from("bell.eigen.py")));
+        assert_eq!(parsed.job_yaml, Some(PathBuf::from("job.yaml")));
+        assert!(parsed.show_aqo);
+        assert_eq!(global.timeout, Some(Duration::from_secs(5)));
+        assert_eq!(minimal_job_yaml("bell"), "apiVersion: eigen.os/v1\nkind: QuantumJob\nmetadata:\n  name: bell\n");
+    }
+}
@@ -0,0 +1,92 @@
+//! `eigen config`: write a starter config file or show the resolved settings.
+
+use std::path::{Path, PathBuf};
+
+use clap::Subcommand;
+use serde_json::json;
+
+use crate::config::{self, CliConfig, STARTER_CONFIG};
+use crate::jobspec::GrpcCode;
+use crate::output::Printer;
+use crate::EXIT_USER_ERROR;
+
+#[derive(Debug, Clone, PartialEq, Eq, Subcommand)]
+pub(crate) enum ConfigCommand {
+    /// Write a starter config file
+    Init {
+        /// Where to write it; defaults to $EIGEN_CONFIG or ~/.eigen/config.toml
+        #[arg(long, value_name = "PATH")]
+        path: Option<PathBuf>,
+        /// Replace an existing file
+        #[arg(long)]
+        force: bool,
+    },
+    /// Print the resolved settings
+    Show,
+}
+
+/// Runs `eigen config`. Errors carry the process exit code.
+pub fn run(command: ConfigCommand, printer: &Printer) -> Result<(), i32> {
+    match command {
+        ConfigCommand::Init { path, force } => {
+            let path = path.unwrap_or_else(config::config_path);
+            init(&path, force).map_err(|err| {
+                printer.print_failure("config", GrpcCode::FailedPrecondition.as_str(), &err);
+                EXIT_USER_ERROR
+            })?;
+            printer.print_note(&format!("wrote {}", path.display()));
+            Ok(())
+        }
+        ConfigCommand::Show => {
+            show(printer, config::current());
+            Ok(())
+        }
+    }
+}
+
+/// Writes [`STARTER_CONFIG`] to `path`, creating parent directories. An existing
+/// file is only replaced with `force`.
+fn init(path: &Path, force: bool) -> Result<(), String> {
+    if path.exists() && !force {
+        return Err(format!("{} already exists; pass --force to overwrite it", path.display()));
+    }
+    if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent).map_err(|err| format!("failed to create {}: {err}", parent.display()))?;
+    }
+    std::fs::write(path, STARTER_CONFIG).map_err(|err| format!("failed to write {}: {err}", path.display()))
+}
+
+fn show(printer: &Printer, config: &CliConfig) {
+    if printer.is_json() {
+        printer.print_json(&json!({
+            "config_path": config::config_path(),
+            "server_addr": config.server_addr,
+            "token": config.token.as_ref().map(|_| "********"),
+            "tls_ca_cert": config.tls_ca_cert,
+        }));
+    } else {
+        println!("# {}", config::config_path().display());
+        print!("{}", config.to_display_toml());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn init_writes_a_loadable_starter_file_once() {
+        let dir = std::env::temp_dir().join(format!("eigen-config-init-{}", std::process::id()));
+        let path = dir.join("nested").join("config.toml");
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let init = |force| ConfigCommand::Init { path: Some(path.clone()), force };
+        run(init(false), &Printer::default()).expect("init");
+        assert_eq!(CliConfig::load_file(&path), Ok(CliConfig::default()));
+        let code = run(init(false), &Printer::default()).expect_err("refuses to overwrite");
+        assert_eq!(code, EXIT_USER_ERROR);
+        run(init(true), &Printer::default()).expect("force");
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,246 @@
+//! `eigen list`: page through the jobs known to a kernel.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use clap::Args;
+use serde_json::{json, Value};
+
+use crate::cli::GlobalArgs;
+use crate::commands::{format_timestamp, wait};
+use crate::jobspec::GrpcLikeError;
+use crate::kernel::{self, proto};
+use crate::output::{OutputFormat, Printer};
+
+const DEFAULT_LIMIT: usize = 20;
+/// Largest page the kernel hands out.
+const MAX_PAGE_SIZE: usize = 200;
+
+#[derive(Debug, Clone, PartialEq, Eq, Args)]
+pub(crate) struct ListArgs {
+    /// Only jobs in this state, e.g. RUNNING
+    #[arg(long, value_name = "STATE", value_parser = parse_state)]
+    state: Option<String>,
+    /// Maximum number of jobs to show
+    #[arg(long, value_name = "N", default_value_t = DEFAULT_LIMIT, value_parser = parse_limit)]
+    limit: usize,
+    /// Show every job, ignoring --limit
+    #[arg(long)]
+    all: bool,
+    /// Only jobs created within this long, e.g. 1h
+    #[arg(long, value_name = "DURATION", value_parser = wait::parse_duration)]
+    since: Option<Duration>,
+    /// Output format; overrides the global one
+    #[arg(short = 'o', long, value_enum, value_name = "FORMAT")]
+    format: Option<OutputFormat>,
+}
+
+/// Jobs collected from one or more `ListJobs` pages.
+#[derive(Debug, Default)]
+struct Listing {
+    jobs: Vec<proto::JobSummary>,
+    pages: usize,
+    /// More matching jobs may exist beyond `--limit`.
+    truncated: bool,
+}
+
+/// Runs `eigen list`. Errors carry the process exit code.
+pub fn run(args: ListArgs, global: &GlobalArgs, printer: &Printer) -> Result<(), i32> {
+    let printer = printer.with_format(args.format);
+    let addr = global.endpoint.clone().unwrap_or_else(kernel::kernel_addr);
+    let created_after_ms = args.since.map(|since| now_unix_ms() - since.as_millis() as i64);
+    let limit = (!args.all).then_some(args.limit);
+    let listing = collect_jobs(&addr, args.state.as_deref().unwrap_or(""), args.limit, limit, created_after_ms)
+        .map_err(|err| printer.print_error("list", &err))?;
+
+    match printer.format() {
+        OutputFormat::Text => printer.print_table(&table_header(), listing.jobs.iter().map(table_row)),
+        OutputFormat::Json => printer.print_json(&Value::Array(listing.jobs.iter().map(job_json).collect())),
+    }
+    if listing.truncated {
+        printer.print_note(&format!("showing the first {} jobs; use --all to list every job", listing.jobs.len()));
+    }
+    Ok(())
+}
+
+/// Walks `ListJobs` pages of `page_size` until `limit` jobs created after
+/// `created_after_ms` are collected, or every page was read when `limit` is `None`.
+fn collect_jobs(
+    addr: &str,
+    state_filter: &str,
+    page_size: usize,
+    limit: Option<usize>,
+    created_after_ms: Option<i64>,
+) -> Result<Listing, GrpcLikeError> {
+    let page_size = page_size.clamp(1, MAX_PAGE_SIZE) as i32;
+    let mut listing = Listing::default();
+    let mut page_token = String::new();
+    loop {
+        let page = kernel::list_jobs_page(addr, state_filter, &page_token, page_size)?;
+        listing.pages += 1;
+        let matching = page
+            .jobs
+            .into_iter()
+            .filter(|job| created_after_ms.is_none_or(|cutoff| created_at_ms(job) >= cutoff));
+        listing.jobs.extend(matching);
+        if let Some(limit) = limit.filter(|limit| listing.jobs.len() >= *limit) {
+            listing.truncated = listing.jobs.len() > limit || !page.next_page_token.is_empty();
+            listing.jobs.truncate(limit);
+            return Ok(listing);
+        }
+        if page.next_page_token.is_empty() {
+            return Ok(listing);
+        }
+        page_token = page.next_page_token;
+    }
+}
+
+fn created_at_ms(job: &proto::JobSummary) -> i64 {
+    job.created_at
+        .as_ref()
+        .map(|ts| ts.seconds * 1_000 + i64::from(ts.nanos) / 1_000_000)
+        .unwrap_or(0)
+}
+
+fn now_unix_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+/// `TASK_STATE_RUNNING` -> `RUNNING`.
+fn state_name(state: i32) -> String {
+    proto::TaskState::try_from(state)
+        .unwrap_or(proto::TaskState::Unspecified)
+        .as_str_name()
+        .trim_start_matches("TASK_STATE_")
+        .to_string()
+}
+
+fn table_header() -> String {
+    format!("{:<36}  {:<24}  {:<11}  {}", "JOB_ID", "NAME", "STATE", "CREATED")
+}
+
+fn table_row(job: &proto::JobSummary) -> String {
+    format!(
+        "{:<36}  {:<24}  {:<11}  {}",
+        job.job_id,
+        job.name,
+        state_name(job.state),
+        job.created_at
+            .as_ref()
+            .map(format_timestamp)
+            .unwrap_or_else(|| "-".to_string())
+    )
+}
+
+fn job_json(job: &proto::JobSummary) -> Value {
+    json!({
+        "job_id": job.job_id,
+        "name": job.name,
+        "state": state_name(job.state),
+        "stage": job.stage,
+        "created_at_unix_ms": created_at_ms(job),
+        "updated_at_unix_ms": job
+            .updated_at
+            .as_ref()
+            .map(|ts| ts.seconds * 1_000 + i64::from(ts.nanos) / 1_000_000),
+    })
+}
+
+fn parse_state(value: &str) -> Result<String, String> {
+    Ok(value.to_ascii_uppercase())
+}
+
+fn parse_limit(value: &str) -> Result<usize, String> {
+    value
+        .parse()
+        .ok()
+        .filter(|limit| *limit > 0)
+        .ok_or_else(|| "--limit must be a positive integer".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::{invoke, parse, spawn_kernel};
+    use crate::kernel::KernelSubmission;
+
+    fn summary(job_id: &str, created_seconds: i64) -> proto::JobSummary {
+        proto::JobSummary {
+            job_id: job_id.to_string(),
+            name: "bell".to_string(),
+            state: proto::TaskState::Running as i32,
+            stage: "execute".to_string(),
+            created_at: Some(prost_types::Timestamp {
+                seconds: created_seconds,
+                nanos: 250_000_000,
+            }),
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn all_follows_every_page() {
+        let addr = spawn_kernel().to_string();
+        let mut enqueued = Vec::new();
+        for i in 0..3 {
+            let submission = KernelSubmission::program(format!("list-page-{i}"), "@quantum\ndef main():\n    pass\n");
+            enqueued.push(kernel::enqueue(&addr, &submission).expect("enqueue").job_id);
+        }
+
+        let listing = collect_jobs(&addr, "", 2, None, None).expect("list all");
+        assert!(listing.pages >= 2, "expected two pages, got {}", listing.pages);
+        assert!(!listing.truncated);
+        let listed: Vec<&str> = listing.jobs.iter().map(|job| job.job_id.as_str()).collect();
+        for job_id in &enqueued {
+            assert!(listed.contains(&job_id.as_str()), "{job_id} missing from {listed:?}");
+        }
+
+        let first_page = collect_jobs(&addr, "", 2, Some(2), None).expect("list first page");
+        assert_eq!(first_page.pages, 1);
+        assert_eq!(first_page.jobs.len(), 2);
+        assert!(first_page.truncated);
+
+        invoke(run, &["--all", "--limit", "2", "--format", "json", "--endpoint", &addr]).expect("list --all");
+    }
+
+    #[test]
+    fn since_filters_on_creation_time() {
+        let addr = spawn_kernel().to_string();
+        let submission = KernelSubmission::program("list-since", "@quantum\ndef main():\n    pass\n");
+        let job_id = kernel::enqueue(&addr, &submission).expect("enqueue").job_id;
+
+        let recent = collect_jobs(&addr, "", 20, None, Some(now_unix_ms() - 60_000)).expect("recent jobs");
+        assert!(recent.jobs.iter().any(|job| job.job_id == job_id));
+        let future = collect_jobs(&addr, "", 20, None, Some(now_unix_ms() + 60_000)).expect("future jobs");
+        assert!(future.jobs.is_empty());
+    }
+
+    #[test]
+    fn rows_and_json_use_job_summary_fields() {
+        let job = summary("job-list-1", 1_767_225_600);
+        let cells: Vec<String> = table_row(&job).split_whitespace().map(str::to_string).collect();
+        assert_eq!(cells, vec!["job-list-1", "bell", "RUNNING", "2026-01-01T00:00:00Z"]);
+
+        let value = job_json(&job);
+        assert_eq!(value["state"], "RUNNING");
+        assert_eq!(value["created_at_unix_ms"], 1_767_225_600_250_i64);
+        assert!(value["updated_at_unix_ms"].is_null());
+    }
+
+    #[test]
+    fn args_defaults_and_validation() {
+        let (parsed, _) = parse::<ListArgs>(&[]).expect("defaults");
+        assert_eq!(parsed.limit, DEFAULT_LIMIT);
+        assert!(!parsed.all);
+
+        let (parsed, _) = parse::<ListArgs>(&["--state", "running", "--since", "1h", "--all"]).expect("parse");
+        assert_eq!(parsed.state.as_deref(), Some("RUNNING"));
+        assert_eq!(parsed.since, Some(Duration::from_secs(3_600)));
+        assert!(parsed.all);
+
+        assert!(parse::<ListArgs>(&["--limit", "0"]).is_err());
+        assert!(parse::<ListArgs>(&["--since", "soon"]).is_err());
+    }
+}
@@ -0,0 +1,243 @@
+//! `eigen logs`: print a job's log files from the kernel's local QFS, optionally
+//! following them like `tail -f` until the job finishes.
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use clap::Args;
+
+use crate::cli::GlobalArgs;
+use crate::commands::local_job_dir;
+use crate::commands::wait::{self, JobSource, WaitError, EXIT_JOB_NOT_FOUND};
+use crate::jobspec::{self, GrpcCode};
+use crate::output::Printer;
+use crate::{terminal_exit_code, EXIT_SERVER_ERROR};
+
+/// Delay between two reads of the log files in `--follow` mode.
+const FOLLOW_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, PartialEq, Eq, Args)]
+pub(crate) struct LogsArgs {
+    /// Job whose logs to print
+    job_id: String,
+    /// Only print logs/<STAGE>.log, e.g. kernel
+    #[arg(long, value_name = "STAGE")]
+    stage: Option<String>,
+    /// Keep printing new lines until the job is terminal
+    #[arg(short, long)]
+    follow: bool,
+    /// Only print the last N lines
+    #[arg(short = 'n', long, value_name = "N")]
+    lines: Option<usize>,
+    /// System API address used by --follow to check the job state
+    #[arg(long, value_name = "ADDR")]
+    server: Option<String>,
+}
+
+/// Runs `eigen logs`. Errors carry the process exit code.
+pub(crate) fn run(args: LogsArgs, global: &GlobalArgs, printer: &Printer) -> Result<(), i32> {
+    if let Some(stage) = &args.stage {
+        validate_stage(stage).map_err(|message| printer.print_usage_error("logs", &message))?;
+    }
+    let mut tail = LogTail::new(local_job_dir(&args.job_id).join("logs"), args.stage.clone());
+    let read_failed = |err: io::Error| {
+        let message = format!("failed to read the logs of job {}: {err}", args.job_id);
+        printer.print_failure("logs", GrpcCode::Internal.as_str(), &message);
+        EXIT_SERVER_ERROR
+    };
+
+    if !args.follow {
+        if tail.files().map_err(read_failed)?.is_empty() {
+            let message = format!("job {} has no logs in {}", args.job_id, tail.dir.display());
+            printer.print_failure("logs", GrpcCode::NotFound.as_str(), &message);
+            return Err(EXIT_JOB_NOT_FOUND);
+        }
+        let lines = tail.read_new_lines(true).map_err(read_failed)?;
+        print_lines(last_lines(lines, args.lines));
+        return Ok(());
+    }
+
+    let source = JobSource::from_flags(args.server.clone(), global.endpoint.clone());
+    let deadline = global.timeout.map(|timeout| Instant::now() + timeout);
+    let mut first_read = true;
+    loop {
+        let status = source
+            .status(&args.job_id)
+            .map_err(|err| wait::report_error(printer, "logs", &args.job_id, &err))?;
+        // The state is read before the files so that everything written before the
+        // job became terminal is printed before exiting.
+        let terminal = terminal_exit_code(&jobspec::map_job_state(status.state)).is_some();
+        let lines = tail.read_new_lines(terminal).map_err(read_failed)?;
+        if first_read {
+            print_lines(last_lines(lines, args.lines));
+            first_read = false;
+        } else {
+            print_lines(lines);
+        }
+        if terminal {
+            return Ok(());
+        }
+        let pause = match deadline {
+            Some(deadline) if Instant::now() >= deadline => {
+                return Err(wait::report_wait_error(printer, "logs", &args.job_id, &WaitError::TimedOut(Box::new(status))));
+            }
+            Some(deadline) => FOLLOW_POLL_INTERVAL.min(deadline - Instant::now()),
+            None => FOLLOW_POLL_INTERVAL,
+        };
+        std::thread::sleep(pause);
+    }
+}
+
+/// Stages name a single `logs/<stage>.log` file.
+fn validate_stage(stage: &str) -> Result<(), String> {
+    let valid_chars = stage.chars().all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '.' | '_' | '-'));
+    if stage.is_empty() || stage.contains("..") || !valid_chars {
+        return Err(format!("invalid stage: {stage:?}"));
+    }
+    Ok(())
+}
+
+fn print_lines(lines: Vec<String>) {
+    for line in lines {
+        println!("{line}");
+    }
+}
+
+/// The last `count` lines, or all of them without a count.
+fn last_lines(mut lines: Vec<String>, count: Option<usize>) -> Vec<String> {
+    match count {
+        Some(count) => lines.split_off(lines.len().saturating_sub(count)),
+        None => lines,
+    }
+}
+
+/// Reads the lines appended to a job's `.log` files since the previous read.
+///
+/// Files are read in name order, or only `<stage>.log` with a stage. A last line
+/// without its newline is held back until the writer finishes it, unless `flush`
+/// asks for everything because no more output is coming.
+struct LogTail {
+    dir: PathBuf,
+    stage: Option<String>,
+    offsets: BTreeMap<PathBuf, u64>,
+}
+
+impl LogTail {
+    fn new(dir: PathBuf, stage: Option<String>) -> Self {
+        Self { dir, stage, offsets: BTreeMap::new() }
+    }
+
+    /// The log files that exist right now.
+    fn files(&self) -> io::Result<Vec<PathBuf>> {
+        if let Some(stage) = &self.stage {
+            let path = self.dir.join(format!("{stage}.log"));
+            return Ok(if path.is_file() { vec![path] } else { Vec::new() });
+        }
+        let entries = match std::fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err),
+        };
+        let mut files = Vec::new();
+        for entry in entries {
+            let path = entry?.path();
+            if path.is_file() && path.extension().is_some_and(|ext| ext == "log") {
+                files.push(path);
+            }
+        }
+        files.sort();
+        Ok(files)
+    }
+
+    fn read_new_lines(&mut self, flush: bool) -> io::Result<Vec<String>> {
+        let mut lines = Vec::new();
+        for path in self.files()? {
+            let offset = self.offsets.entry(path.clone()).or_default();
+            let mut file = File::open(&path)?;
+            file.seek(SeekFrom::Start(*offset))?;
+            let mut appended = Vec::new();
+            file.read_to_end(&mut appended)?;
+
+            let complete = if flush {
+                appended.len()
+            } else {
+                appended.iter().rposition(|byte| *byte == b'\n').map_or(0, |end| end + 1)
+            };
+            *offset += complete as u64;
+            let text = String::from_utf8_lossy(&appended[..complete]);
+            lines.extend(text.lines().map(str::to_string));
+        }
+        Ok(lines)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::parse;
+    use std::io::Write;
+    use std::path::Path;
+
+    fn log_dir(tag: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("eigen-cli-logs-{tag}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).expect("log dir");
+        dir
+    }
+
+    fn append(path: &Path, text: &str) {
+        let mut file = std::fs::OpenOptions::new().create(true).append(true).open(path).expect("open log");
+        file.write_all(text.as_bytes()).expect("append log");
+    }
+
+    #[test]
+    fn reads_every_log_file_in_name_order_or_only_the_stage() {
+        let dir = log_dir("select");
+        append(&dir.join("kernel.log"), "kernel 1\nkernel 2\n");
+        append(&dir.join("compile.log"), "compile 1\n");
+        append(&dir.join("stdout.jsonl"), "{\"ignored\":true}\n");
+
+        let mut all = LogTail::new(dir.clone(), None);
+        assert_eq!(all.read_new_lines(false).expect("read"), ["compile 1", "kernel 1", "kernel 2"]);
+        let mut kernel = LogTail::new(dir.clone(), Some("kernel".to_string()));
+        assert_eq!(kernel.read_new_lines(false).expect("read"), ["kernel 1", "kernel 2"]);
+        assert!(LogTail::new(dir.clone(), Some("missing".to_string())).files().expect("files").is_empty());
+        assert!(LogTail::new(dir.join("absent"), None).files().expect("files").is_empty());
+    }
+
+    #[test]
+    fn later_reads_only_return_new_complete_lines() {
+        let dir = log_dir("follow");
+        let path = dir.join("kernel.log");
+        append(&path, "one\ntw");
+
+        let mut tail = LogTail::new(dir, None);
+        assert_eq!(tail.read_new_lines(false).expect("read"), ["one"]);
+        assert!(tail.read_new_lines(false).expect("read").is_empty());
+        append(&path, "o\nthree");
+        assert_eq!(tail.read_new_lines(false).expect("read"), ["two"]);
+        assert_eq!(tail.read_new_lines(true).expect("read"), ["three"]);
+    }
+
+    #[test]
+    fn lines_keeps_only_the_last_n() {
+        let lines = || ["a", "b", "c"].map(str::to_string).to_vec();
+        assert_eq!(last_lines(lines(), Some(2)), ["b", "c"]);
+        assert_eq!(last_lines(lines(), Some(5)), ["a", "b", "c"]);
+        assert_eq!(last_lines(lines(), Some(0)), Vec::<String>::new());
+        assert_eq!(last_lines(lines(), None), ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn args_accept_stage_follow_and_lines() {
+        let (parsed, _) = parse::<LogsArgs>(&["job-1", "--stage", "kernel", "-f", "-n", "20"]).expect("parse");
+        assert_eq!(parsed.stage.as_deref(), Some("kernel"));
+        assert!(parsed.follow);
+        assert_eq!(parsed.lines, Some(20));
+        assert!(validate_stage("../kernel").is_err());
+        assert!(validate_stage("kernel").is_ok());
+    }
+}
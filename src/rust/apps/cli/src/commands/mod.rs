@@ -0,0 +1,49 @@
+//! Subcommands that have outgrown `main.rs`.
+
+use std::path::{Path, PathBuf};
+
+pub mod cancel;
+pub mod compile;
+pub mod config;
+pub mod list;
+pub mod logs;
+pub mod result;
+pub mod status;
+pub mod submit;
+pub(crate) mod wait;
+
+#[cfg(test)]
+pub(crate) mod test_support;
+
+const DEFAULT_QFS_ROOT: &str = "/tmp/eigen/qfs";
+
+/// A job's directory in the kernel's local QFS, `<root>/jobs/<job_id>`; the root is
+/// resolved the same way as the kernel's.
+pub(crate) fn local_job_dir(job_id: &str) -> PathBuf {
+    let root = std::env::var("EIGEN_QFS_LOCAL_ROOT")
+        .or_else(|_| std::env::var("EIGEN_QFS_ROOT"))
+        .unwrap_or_else(|_| DEFAULT_QFS_ROOT.to_string());
+    Path::new(&root).join("jobs").join(job_id)
+}
+
+/// Formats a protobuf timestamp as RFC 3339 UTC with second precision.
+pub(crate) fn format_timestamp(ts: &prost_types::Timestamp) -> String {
+    let days = ts.seconds.div_euclid(86_400);
+    let secs = ts.seconds.rem_euclid(86_400);
+    // Civil-from-days (Howard Hinnant), valid for the whole proleptic Gregorian range.
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        secs / 3_600,
+        secs % 3_600 / 60,
+        secs % 60
+    )
+}
@@ -0,0 +1,322 @@
+//! `eigen result`: fetch a job's results as a counts table, JSON or CSV.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use clap::{Args, ValueEnum};
+use serde_json::json;
+
+use crate::cli::GlobalArgs;
+use crate::commands::wait::{self, JobSource, POLL_INTERVAL};
+use crate::jobspec::{GrpcCode, JobResultsView};
+use crate::output::{OutputFormat, Printer};
+use crate::{EXIT_SERVER_ERROR, EXIT_USER_ERROR, terminal_exit_code};
+
+/// Exit code for jobs that ended in `ERROR`.
+const EXIT_JOB_FAILED: i32 = 2;
+/// Exit code for jobs that are not terminal yet, so scripts can retry later
+/// (`EX_TEMPFAIL` in sysexits.h).
+const EXIT_NOT_READY: i32 = 75;
+
+/// Value of `eigen result --format`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+enum ResultFormat {
+    // Counts as a `STATE COUNT` table sorted by state, then summary and metadata.
+    #[default]
+    Text,
+    Json,
+    // `state,count` lines under a header.
+    CountsCsv,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Args)]
+pub(crate) struct ResultArgs {
+    /// Job to fetch
+    job_id: String,
+    /// System API address
+    #[arg(long, value_name = "ADDR")]
+    server: Option<String>,
+    /// Output format; overrides the global one
+    #[arg(short = 'o', long, value_enum, value_name = "FORMAT")]
+    format: Option<ResultFormat>,
+    /// Write the results to this file instead of stdout
+    #[arg(long, value_name = "PATH")]
+    output: Option<PathBuf>,
+    /// Wait for a terminal state before fetching
+    #[arg(long)]
+    wait: bool,
+}
+
+/// Runs `eigen result`. Errors carry the process exit code.
+pub fn run(args: ResultArgs, global: &GlobalArgs, printer: &Printer) -> Result<(), i32> {
+    let format = args.format.unwrap_or(match printer.format() {
+        OutputFormat::Text => ResultFormat::Text,
+        OutputFormat::Json => ResultFormat::Json,
+    });
+    let source = JobSource::from_flags(args.server.clone(), global.endpoint.clone());
+    if args.wait {
+        wait::wait_for_terminal(&source, &args.job_id, POLL_INTERVAL, global.timeout, |_| {})
+            .map_err(|err| wait::report_wait_error(printer, "result", &args.job_id, &err))?;
+    }
+    let results = source
+        .results(&args.job_id)
+        .map_err(|err| wait::report_error(printer, "result", &args.job_id, &err))?;
+
+    if terminal_exit_code(&results.state).is_none() {
+        let message = format!("job {} is {}; results are not ready yet", results.job_id, results.state);
+        printer.print_failure("result", GrpcCode::FailedPrecondition.as_str(), &message);
+        return Err(EXIT_NOT_READY);
+    }
+    if results.state == "ERROR" {
+        if printer.is_json() {
+            let message = format!(
+                "job {} ended in ERROR: {}: {} (details: {})",
+                results.job_id,
+                results.error_code.as_deref().unwrap_or("-"),
+                results.error_summary.as_deref().unwrap_or("-"),
+                results.error_details_ref.as_deref().unwrap_or("-")
+            );
+            printer.print_failure("result", "ABORTED", &message);
+        } else {
+            eprint!("{}", error_report(&results));
+        }
+        return Err(EXIT_JOB_FAILED);
+    }
+
+    let rendered = render(&results, format);
+    match &args.output {
+        Some(path) => std::fs::write(path, rendered).map_err(|err| {
+            let message = format!("cannot write {}: {err}", path.display());
+            printer.print_failure("result", GrpcCode::InvalidArgument.as_str(), &message);
+            EXIT_USER_ERROR
+        })?,
+        None => print!("{rendered}"),
+    }
+    if results.state != "DONE" {
+        let message = format!("job {} ended in {}; results are not final", results.job_id, results.state);
+        printer.print_failure("result", GrpcCode::FailedPrecondition.as_str(), &message);
+        return Err(EXIT_SERVER_ERROR);
+    }
+    Ok(())
+}
+
+fn render(results: &JobResultsView, format: ResultFormat) -> String {
+    match format {
+        ResultFormat::Text => {
+            let mut out = counts_table(&results.counts);
+            for (section, entries) in [("summary", &results.summary), ("metadata", &results.metadata)] {
+                if entries.is_empty() {
+                    continue;
+                }
+                out.push_str(&format!("\n{section}:\n"));
+                for (key, value) in entries {
+                    out.push_str(&format!("  {key}: {value}\n"));
+                }
+            }
+            out
+        }
+        ResultFormat::Json => {
+            let value = json!({
+                "job_id": results.job_id,
+                "state": results.state,
+                "counts": results.counts,
+                "summary": results.summary,
+                "metadata": results.metadata,
+                "error_code": results.error_code,
+                "error_summary": results.error_summary,
+                "error_details_ref": results.error_details_ref,
+            });
+            format!("{value:#}\n")
+        }
+        ResultFormat::CountsCsv => counts_csv(&results.counts),
+    }
+}
+
+/// What a job that ended in `ERROR` reports on stderr in text mode.
+fn error_report(results: &JobResultsView) -> String {
+    let field = |value: &Option<String>| value.clone().unwrap_or_else(|| "-".to_string());
+    format!(
+        "result failed: job {} ended in ERROR\n  error_code: {}\n  error_summary: {}\n  error_details_ref: {}\n",
+        results.job_id,
+        field(&results.error_code),
+        field(&results.error_summary),
+        field(&results.error_details_ref)
+    )
+}
+
+fn counts_table(counts: &BTreeMap<String, i64>) -> String {
+    let width = counts.keys().map(String::len).max().unwrap_or(0).max("STATE".len());
+    let mut out = format!("{:<width$}  {:>10}\n", "STATE", "COUNT");
+    for (state, count) in counts {
+        out.push_str(&format!("{state:<width$}  {count:>10}\n"));
+    }
+    out
+}
+
+fn counts_csv(counts: &BTreeMap<String, i64>) -> String {
+    let mut out = String::from("state,count\n");
+    for (state, count) in counts {
+        out.push_str(&format!("{},{count}\n", csv_field(state)));
+    }
+    out
+}
+
+/// Quotes `value` when it contains a delimiter, quote or line break (RFC 4180).
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::{invoke, parse, spawn_kernel, spawn_mock_server, MockJobService};
+    use crate::jobspec::{self, eigen::api::v1 as api};
+    use crate::kernel::{self, KernelSubmission};
+
+    fn response(state: api::JobState) -> api::GetJobResultsResponse {
+        api::GetJobResultsResponse {
+            job_id: "job-result-1".to_string(),
+            state: state as i32,
+            counts: [("000", 431), ("111", 402), ("010", 97), ("101", 94)]
+                .map(|(state, count)| (state.to_string(), count))
+                .into(),
+            metadata: [("result.summary.shots".to_string(), "1024".to_string())].into(),
+            ..Default::default()
+        }
+    }
+
+    fn fetch(state: api::JobState) -> JobResultsView {
+        let addr = spawn_mock_server(MockJobService::with_results(response(state)));
+        jobspec::get_job_results_from_endpoint(&addr.to_string(), "job-result-1").expect("results")
+    }
+
+    fn parse_csv(csv: &str) -> BTreeMap<String, i64> {
+        let mut lines = csv.lines();
+        assert_eq!(lines.next(), Some("state,count"));
+        lines
+            .map(|line| {
+                let (state, count) = line.split_once(',').expect("state,count line");
+                (state.to_string(), count.parse().expect("count"))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn text_output_is_a_table_sorted_by_state() {
+        let results = fetch(api::JobState::Done);
+        let rendered = render(&results, ResultFormat::Text);
+        let rows: Vec<Vec<&str>> = rendered.lines().map(|line| line.split_whitespace().collect()).collect();
+        assert_eq!(rows[0], vec!["STATE", "COUNT"]);
+        assert_eq!(
+            rows[1..5],
+            [vec!["000", "431"], vec!["010", "97"], vec!["101", "94"], vec!["111", "402"]]
+        );
+        assert!(rendered.contains("summary:\n  shots: 1024\n"));
+    }
+
+    #[test]
+    fn json_and_csv_round_trip_counts() {
+        let results = fetch(api::JobState::Done);
+        let expected = response(api::JobState::Done).counts.into_iter().collect::<BTreeMap<_, _>>();
+
+        let value: serde_json::Value =
+            serde_json::from_str(&render(&results, ResultFormat::Json)).expect("json output");
+        assert_eq!(value["state"], "DONE");
+        let counts: BTreeMap<String, i64> = serde_json::from_value(value["counts"].clone()).expect("counts");
+        assert_eq!(counts, expected);
+
+        assert_eq!(parse_csv(&render(&results, ResultFormat::CountsCsv)), expected);
+        assert_eq!(csv_field("a,\"b\""), "\"a,\"\"b\"\"\"");
+    }
+
+    #[test]
+    fn output_flag_writes_csv_file() {
+        let addr = spawn_mock_server(MockJobService::with_results(response(api::JobState::Done)));
+        let dir = std::env::temp_dir().join(format!("eigen-cli-result-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("temp dir");
+        let path = dir.join("counts.csv");
+
+        let argv = [
+            "job-result-1",
+            "--json",
+            "--format",
+            "counts-csv",
+            "--output",
+            path.to_str().unwrap(),
+            "--server",
+            &addr.to_string(),
+        ];
+        // `--format counts-csv` wins over the global `--json`.
+        invoke(run, &argv).expect("write csv");
+        let written = std::fs::read_to_string(&path).expect("read csv");
+        assert_eq!(parse_csv(&written).get("000"), Some(&431));
+    }
+
+    #[test]
+    fn error_state_exits_with_code_two() {
+        let mut failed = response(api::JobState::Error);
+        failed.counts.clear();
+        failed.error_code = "EIGEN_SIM_ERROR".to_string();
+        failed.error_summary = "simulation failed".to_string();
+        failed.error_details_ref = "qfs://errors/job-result-1.json".to_string();
+        let addr = spawn_mock_server(MockJobService::with_results(failed));
+
+        let code = invoke(run, &["job-result-1", "--server", &addr.to_string()]).expect_err("error state");
+        assert_eq!(code, EXIT_JOB_FAILED);
+
+        let results = jobspec::get_job_results_from_endpoint(&addr.to_string(), "job-result-1").expect("results");
+        let report = error_report(&results);
+        assert!(report.contains("error_code: EIGEN_SIM_ERROR\n"), "{report}");
+        assert!(report.contains("error_details_ref: qfs://errors/job-result-1.json\n"), "{report}");
+        let value: serde_json::Value =
+            serde_json::from_str(&render(&results, ResultFormat::Json)).expect("json output");
+        assert_eq!(value["error_details_ref"], "qfs://errors/job-result-1.json");
+    }
+
+    #[test]
+    fn unfinished_jobs_exit_with_the_not_ready_code() {
+        for state in [api::JobState::Pending, api::JobState::Running] {
+            let mut running = response(state);
+            running.counts.clear();
+            let addr = spawn_mock_server(MockJobService::with_results(running));
+            for format in ["text", "json"] {
+                let argv = ["job-result-1", "--format", format, "--server", &addr.to_string()];
+                assert_eq!(invoke(run, &argv), Err(EXIT_NOT_READY), "{state:?} {format}");
+            }
+        }
+    }
+
+    #[test]
+    fn kernel_results_after_wait_and_unknown_jobs() {
+        let addr = spawn_kernel().to_string();
+        let job = kernel::enqueue(&addr, &KernelSubmission::program("result-wait", "@quantum\ndef main():\n    pass\n"))
+            .expect("enqueue");
+
+        let outcome = invoke(run, &[&job.job_id, "--wait", "--timeout", "30s", "--endpoint", &addr, "--json"]);
+        let results = kernel::get_job_results(&addr, &job.job_id).expect("results after wait");
+        match results.state.as_str() {
+            "DONE" => assert_eq!(outcome, Ok(())),
+            "ERROR" => assert_eq!(outcome, Err(EXIT_JOB_FAILED)),
+            other => assert_eq!(outcome, Err(EXIT_SERVER_ERROR), "state {other}"),
+        }
+
+        let code = invoke(run, &["job-unknown", "--endpoint", &addr]).expect_err("unknown job");
+        assert_eq!(code, wait::EXIT_JOB_NOT_FOUND);
+    }
+
+    #[test]
+    fn args_require_job_id_and_known_format() {
+        assert!(parse::<ResultArgs>(&["--format", "json"]).is_err());
+        assert!(parse::<ResultArgs>(&["job-1", "--format", "yaml"]).is_err());
+        let (parsed, _) = parse::<ResultArgs>(&["job-1", "--format", "counts-csv", "--output", "out.csv"]).expect("parse");
+        assert_eq!(parsed.format, Some(ResultFormat::CountsCsv));
+        assert_eq!(parsed.output, Some(PathBuf::from("out.csv")));
+        let (parsed, global) = parse::<ResultArgs>(&["job-1", "--json"]).expect("parse");
+        assert_eq!(parsed.format, None);
+        assert!(global.printer(None).is_json());
+    }
+}
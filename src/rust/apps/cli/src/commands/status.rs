@@ -0,0 +1,269 @@
+//! `eigen status`: show the current state of a job, optionally until it finishes.
+
+use std::time::Duration;
+
+use clap::Args;
+use serde_json::{json, Value};
+
+use crate::cli::GlobalArgs;
+use crate::commands::wait::{self, JobSource, POLL_INTERVAL};
+use crate::commands::format_timestamp;
+use crate::jobspec::{self, eigen::api::v1 as api};
+use crate::output::{OutputFormat, Printer};
+use crate::terminal_exit_code;
+
+/// `--watch` exit code for jobs that ended in `ERROR` or `TIMEOUT`.
+const WATCH_EXIT_FAILED: i32 = 2;
+/// `--watch` exit code for jobs that ended in `CANCELLED`.
+const WATCH_EXIT_CANCELLED: i32 = 3;
+
+#[derive(Debug, Clone, PartialEq, Eq, Args)]
+pub(crate) struct StatusArgs {
+    /// Job to show
+    job_id: String,
+    /// System API address
+    #[arg(long, value_name = "ADDR")]
+    server: Option<String>,
+    /// Output format; overrides the global one
+    #[arg(short = 'o', long, value_enum, value_name = "FORMAT")]
+    format: Option<OutputFormat>,
+    /// Print a row per poll until the job finishes
+    #[arg(short, long, conflicts_with = "wait")]
+    watch: bool,
+    /// Wait for a terminal state before printing
+    #[arg(long)]
+    wait: bool,
+}
+
+/// Runs `eigen status`. Errors carry the process exit code.
+pub fn run(args: StatusArgs, global: &GlobalArgs, printer: &Printer) -> Result<(), i32> {
+    let printer = printer.with_format(args.format);
+    let source = JobSource::from_flags(args.server, global.endpoint.clone());
+    if args.watch {
+        return watch(&printer, &source, &args.job_id, POLL_INTERVAL, global.timeout);
+    }
+
+    let status = if args.wait {
+        wait::wait_for_terminal(&source, &args.job_id, POLL_INTERVAL, global.timeout, |_| {})
+            .map_err(|err| wait::report_wait_error(&printer, "status", &args.job_id, &err))?
+    } else {
+        source
+            .status(&args.job_id)
+            .map_err(|err| wait::report_error(&printer, "status", &args.job_id, &err))?
+    };
+    match printer.format() {
+        OutputFormat::Text => {
+            printer.print_table(&table_header(), [table_row(&status)]);
+            if !status.error_summary.is_empty() {
+                println!("error: {}: {}", status.error_code, status.error_summary);
+            }
+        }
+        OutputFormat::Json => printer.print_json(&status_json(&status)),
+    }
+    match terminal_exit_code(&jobspec::map_job_state(status.state)) {
+        Some(0) | None => Ok(()),
+        Some(code) => Err(code),
+    }
+}
+
+/// Polls `job_id` every `interval` and prints one row (or JSON line) per poll until
+/// the job reaches a terminal state or `timeout` expires.
+fn watch(
+    printer: &Printer,
+    source: &JobSource,
+    job_id: &str,
+    interval: Duration,
+    timeout: Option<Duration>,
+) -> Result<(), i32> {
+    if printer.format() == OutputFormat::Text {
+        println!("{}", table_header());
+    }
+    let status = wait::wait_for_terminal(source, job_id, interval, timeout, |status| match printer.format() {
+        OutputFormat::Text => println!("{}", table_row(status)),
+        // One compact object per line, so the stream can be read as JSON Lines.
+        OutputFormat::Json => println!("{}", status_json(status)),
+    })
+    .map_err(|err| wait::report_wait_error(printer, "status", job_id, &err))?;
+    match watch_exit_code(&jobspec::map_job_state(status.state)) {
+        Some(0) | None => Ok(()),
+        Some(code) => Err(code),
+    }
+}
+
+fn watch_exit_code(state: &str) -> Option<i32> {
+    match state {
+        "DONE" => Some(0),
+        "ERROR" | "TIMEOUT" => Some(WATCH_EXIT_FAILED),
+        "CANCELLED" => Some(WATCH_EXIT_CANCELLED),
+        _ => None,
+    }
+}
+
+fn table_header() -> String {
+    format!("{:<36}  {:<11}  {:<12}  {:>9}  {}", "JOB_ID", "STATE", "STAGE", "PROGRESS%", "UPDATED")
+}
+
+fn table_row(status: &api::JobStatus) -> String {
+    let stage = if status.stage.is_empty() { "-" } else { &status.stage };
+    format!(
+        "{:<36}  {:<11}  {:<12}  {:>9.1}  {}",
+        status.job_id,
+        jobspec::map_job_state(status.state),
+        stage,
+        f64::from(status.progress) * 100.0,
+        status
+            .updated_at
+            .as_ref()
+            .map(format_timestamp)
+            .unwrap_or_else(|| "-".to_string())
+    )
+}
+
+/// Renders every `JobStatus` field under its proto name.
+fn status_json(status: &api::JobStatus) -> Value {
+    let timestamp = |ts: &Option<prost_types::Timestamp>| {
+        ts.as_ref()
+            .map(|ts| json!({ "seconds": ts.seconds, "nanos": ts.nanos }))
+            .unwrap_or(Value::Null)
+    };
+    json!({
+        "job_id": status.job_id,
+        "state": api::JobState::try_from(status.state)
+            .map(|state| Value::from(state.as_str_name()))
+            .unwrap_or_else(|_| Value::from(status.state)),
+        "stage": status.stage,
+        "progress": status.progress,
+        "message": status.message,
+        "created_at": timestamp(&status.created_at),
+        "updated_at": timestamp(&status.updated_at),
+        "error_code": status.error_code,
+        "error_summary": status.error_summary,
+        "error_details_ref": status.error_details_ref,
+        "topology": status.topology.as_ref().map(|topology| json!({
+            "contract_version": topology.contract_version,
+            "lineage_version": topology.lineage_version,
+            "cluster_id": topology.cluster_id,
+            "worker_id": topology.worker_id,
+            "partition_id": topology.partition_id,
+            "attempt": topology.attempt,
+        })),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::{invoke, parse, spawn_kernel, spawn_mock_server, MockJobService};
+    use crate::kernel::{self, KernelSubmission};
+
+    fn status(state: api::JobState, stage: &str, progress: f32) -> api::JobStatus {
+        api::JobStatus {
+            job_id: "job-status-1".to_string(),
+            state: state as i32,
+            stage: stage.to_string(),
+            progress,
+            updated_at: Some(prost_types::Timestamp {
+                seconds: 1_767_225_600,
+                nanos: 0,
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn text_output_is_a_single_row_table() {
+        let header = table_header();
+        for column in ["JOB_ID", "STATE", "STAGE", "PROGRESS%", "UPDATED"] {
+            assert!(header.contains(column), "missing column {column}");
+        }
+
+        let row = table_row(&status(api::JobState::Running, "EXECUTING", 0.5));
+        let cells: Vec<&str> = row.split_whitespace().collect();
+        assert_eq!(
+            cells,
+            vec!["job-status-1", "RUNNING", "EXECUTING", "50.0", "2026-01-01T00:00:00Z"]
+        );
+    }
+
+    #[test]
+    fn json_output_carries_raw_proto_fields() {
+        let value = status_json(&status(api::JobState::Done, "", 1.0));
+        assert_eq!(value["job_id"], "job-status-1");
+        assert_eq!(value["state"], "JOB_STATE_DONE");
+        assert_eq!(value["progress"], 1.0);
+        assert_eq!(value["updated_at"]["seconds"], 1_767_225_600);
+        assert!(value["created_at"].is_null());
+        assert!(value["topology"].is_null());
+
+        let addr = spawn_mock_server(MockJobService::with_statuses([status(api::JobState::Done, "", 1.0)]));
+        invoke(run, &["job-status-1", "--json", "--server", &addr.to_string()]).expect("json status");
+    }
+
+    #[test]
+    fn watch_polls_until_terminal_state() {
+        let service = MockJobService::with_statuses([
+            status(api::JobState::Queued, "QUEUED", 0.0),
+            status(api::JobState::Running, "EXECUTING", 0.5),
+            status(api::JobState::Cancelled, "EXECUTING", 0.5),
+        ]);
+        let addr = spawn_mock_server(service.clone());
+
+        let source = JobSource::SystemApi(addr.to_string());
+        let code = watch(&Printer::default(), &source, "job-status-1", Duration::from_millis(10), None)
+            .expect_err("cancelled job");
+        assert_eq!(code, WATCH_EXIT_CANCELLED);
+        assert_eq!(service.status_polls(), 3);
+
+        let done = spawn_mock_server(MockJobService::with_statuses([status(api::JobState::Done, "", 1.0)]));
+        let source = JobSource::SystemApi(done.to_string());
+        watch(&Printer::new(OutputFormat::Json), &source, "job-status-1", Duration::from_millis(10), None)
+            .expect("done job exits with 0");
+    }
+
+    #[test]
+    fn watch_exit_codes_follow_terminal_state() {
+        assert_eq!(watch_exit_code("DONE"), Some(0));
+        assert_eq!(watch_exit_code("ERROR"), Some(WATCH_EXIT_FAILED));
+        assert_eq!(watch_exit_code("TIMEOUT"), Some(WATCH_EXIT_FAILED));
+        assert_eq!(watch_exit_code("CANCELLED"), Some(WATCH_EXIT_CANCELLED));
+        assert_eq!(watch_exit_code("RUNNING"), None);
+    }
+
+    #[test]
+    fn wait_timeout_exits_with_code_three() {
+        let addr = spawn_mock_server(MockJobService::with_statuses([status(api::JobState::Running, "EXECUTING", 0.5)]));
+        let code = invoke(run, &["job-status-1", "--wait", "--timeout", "50ms", "--server", &addr.to_string()])
+            .expect_err("timed out");
+        assert_eq!(code, wait::EXIT_WAIT_TIMEOUT);
+    }
+
+    #[test]
+    fn kernel_status_waits_for_terminal_state_and_maps_unknown_jobs() {
+        let addr = spawn_kernel().to_string();
+        let job = kernel::enqueue(&addr, &KernelSubmission::program("status-wait", "@quantum\ndef main():\n    pass\n"))
+            .expect("enqueue");
+
+        let result = invoke(run, &[&job.job_id, "--wait", "--timeout", "30s", "--endpoint", &addr, "--format", "json"]);
+        let state = jobspec::map_job_state(kernel::get_job_status(&addr, &job.job_id).expect("status").state);
+        assert_eq!(result, terminal_exit_code(&state).filter(|code| *code != 0).map_or(Ok(()), Err));
+
+        let code = invoke(run, &["job-unknown", "--endpoint", &addr, "--json"]).expect_err("unknown job");
+        assert_eq!(code, wait::EXIT_JOB_NOT_FOUND);
+    }
+
+    #[test]
+    fn args_require_job_id_and_known_format() {
+        assert!(parse::<StatusArgs>(&["--watch"]).is_err());
+        assert!(parse::<StatusArgs>(&["job-1", "--format", "yaml"]).is_err());
+        assert!(parse::<StatusArgs>(&["job-1", "--watch", "--wait"]).is_err());
+        let (parsed, _) = parse::<StatusArgs>(&["job-1", "--watch", "--format", "json"]).expect("parse");
+        assert_eq!(parsed.job_id, "job-1");
+        assert!(parsed.watch);
+        assert_eq!(parsed.format, Some(OutputFormat::Json));
+        let (parsed, global) = parse::<StatusArgs>(&["job-1", "--wait", "--timeout", "5s", "--endpoint", "localhost:50052"])
+            .expect("parse wait");
+        assert!(parsed.wait);
+        assert_eq!(global.timeout, Some(Duration::from_secs(5)));
+        assert_eq!(global.endpoint.as_deref(), Some("localhost:50052"));
+    }
+}
@@ -0,0 +1,354 @@
+//! `eigen submit`: package a job file and submit it to the System API, or enqueue
+//! an Eigen-Lang program directly on a kernel.
+
+use std::path::{Path, PathBuf};
+
+use clap::Args;
+use serde_json::{json, Value};
+
+use crate::cli::GlobalArgs;
+use crate::kernel::{self, KernelSubmission};
+use crate::jobspec::{self, GrpcCode};
+use crate::output::Printer;
+use crate::{print_indented_lines, pretty_json_like, render_title, EXIT_USER_ERROR};
+
+#[derive(Debug, Clone, PartialEq, Eq, Args)]
+pub(crate) struct SubmitArgs {
+    /// Job file (YAML), or an Eigen-Lang program (.py) to enqueue on the kernel
+    #[arg(value_name = "JOB_FILE", conflicts_with = "file")]
+    job_file: Option<PathBuf>,
+    /// Job file; same as the positional argument
+    #[arg(short = 'f', long, alias = "job", value_name = "PATH")]
+    file: Option<PathBuf>,
+    /// System API address for job file submissions
+    #[arg(long, value_name = "ADDR")]
+    server: Option<String>,
+    /// Eigen-Lang program to enqueue on the kernel, with the job file's settings if given
+    #[arg(long, value_name = "PATH")]
+    program: Option<PathBuf>,
+    /// Job name for program submissions
+    #[arg(long, value_name = "NAME")]
+    name: Option<String>,
+    /// Request id for the API envelope; generated when unset
+    #[arg(long, value_name = "ID")]
+    request_id: Option<String>,
+    /// Resubmitting with the same key returns the original job
+    #[arg(long, value_name = "KEY")]
+    idempotency_key: Option<String>,
+    /// W3C traceparent to continue an existing trace
+    #[arg(long, value_name = "VALUE")]
+    traceparent: Option<String>,
+    /// Tenant the job is billed to
+    #[arg(long, alias = "tenant-id", value_name = "ID")]
+    tenant: Option<String>,
+    /// Project the job belongs to
+    #[arg(long, alias = "project-id", value_name = "ID")]
+    project: Option<String>,
+}
+
+impl SubmitArgs {
+    fn options(&self) -> jobspec::PublicSubmitOptions {
+        jobspec::PublicSubmitOptions {
+            request_id: self.request_id.clone(),
+            idempotency_key: self.idempotency_key.clone(),
+            traceparent: self.traceparent.clone(),
+            tenant_id: self.tenant.clone(),
+            project_id: self.project.clone(),
+            client_version: None,
+        }
+    }
+}
+
+/// Runs `eigen submit`. Errors carry the process exit code.
+///
+/// Programs go to the kernel (`--endpoint`, else the configured `server_addr`);
+/// job files go to the System API (`--server`).
+pub fn run(args: SubmitArgs, global: &GlobalArgs, printer: &Printer) -> Result<(), i32> {
+    let to_kernel = |job_file: Option<&Path>, program: &Path| {
+        let addr = global.endpoint.clone().unwrap_or_else(kernel::kernel_addr);
+        submit_program(printer, job_file, program, args.name.as_deref(), &addr)
+    };
+    let job_file = match (args.file.as_deref().or(args.job_file.as_deref()), args.program.as_deref()) {
+        (job_file, Some(program)) => return to_kernel(job_file, program),
+        (Some(program), None) if is_program_file(program) => return to_kernel(None, program),
+        (Some(job_file), None) => job_file,
+        (None, None) => return Err(printer.print_usage_error("submit", "a job file or --program is required")),
+    };
+    if args.name.is_some() {
+        return Err(printer.print_usage_error("submit", "--name only applies to program submissions"));
+    }
+    let endpoint = args.server.clone().unwrap_or_else(jobspec::system_api_endpoint);
+    let options = args.options();
+
+    let req = jobspec::build_submit_request_from_job_file(job_file).map_err(|err| {
+        printer.print_failure("submit", GrpcCode::InvalidArgument.as_str(), &err.to_string());
+        EXIT_USER_ERROR
+    })?;
+    let public_payload = jobspec::build_public_submit_payload_json(&req, &options);
+    let envelope = jobspec::normalized_public_submit_envelope(&req, &options);
+    let response = jobspec::submit_job_to_endpoint(&endpoint, &req, &options)
+        .map_err(|err| printer.print_error("submit", &err))?;
+
+    if printer.is_json() {
+        printer.print_json(&json!({ "job_id": response.job_id, "state": response.state }));
+    } else if printer.is_quiet() {
+        println!("{}", response.job_id);
+    } else {
+        render_submit_output(&response, &req, &envelope, &public_payload);
+    }
+    Ok(())
+}
+
+/// Enqueues an Eigen-Lang program on the kernel at `addr`.
+///
+/// With a job file, its name, target, priority and compiler options are used and the
+/// YAML is sent along; otherwise the job is named after the program's file stem.
+/// `--name` overrides either.
+fn submit_program(
+    printer: &Printer,
+    job_file: Option<&Path>,
+    path: &Path,
+    name: Option<&str>,
+    addr: &str,
+) -> Result<(), i32> {
+    let program = std::fs::read(path).map_err(|err| {
+        let message = format!("failed to read {}: {err}", path.display());
+        printer.print_failure("submit", GrpcCode::InvalidArgument.as_str(), &message);
+        EXIT_USER_ERROR
+    })?;
+    let mut submission = match job_file {
+        Some(job_file) => {
+            let (spec, job_yaml) = jobspec::load_jobspec_file(job_file).map_err(|err| {
+                printer.print_failure("submit", GrpcCode::InvalidArgument.as_str(), &err.to_string());
+                EXIT_USER_ERROR
+            })?;
+            KernelSubmission::from_jobspec(spec, job_yaml, program)
+        }
+        None => KernelSubmission::program(program_name(path), program),
+    };
+    if let Some(name) = name {
+        submission.name = name.to_string();
+    }
+    let job = kernel::enqueue(addr, &submission).map_err(|err| printer.print_error("submit", &err))?;
+
+    if printer.is_json() {
+        printer.print_json(&enqueued_json(&job));
+        return Ok(());
+    }
+    if printer.is_quiet() {
+        println!("{}", job.job_id);
+        return Ok(());
+    }
+    render_title("submit", Some("job accepted"));
+    println!("  job_id: {}", job.job_id);
+    println!("  state: {}", job.state);
+    println!("  name: {}", submission.name);
+    println!("  kernel: {addr}");
+    Ok(())
+}
+
+/// The `EnqueueJob` response as printed by `--json`.
+fn enqueued_json(job: &kernel::EnqueuedJob) -> Value {
+    json!({
+        "job_id": job.job_id,
+        "state": job.state,
+        "created_at": job
+            .created_at
+            .as_ref()
+            .map(|ts| json!({ "seconds": ts.seconds, "nanos": ts.nanos })),
+    })
+}
+
+fn is_program_file(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "py")
+}
+
+/// `bell.eigen.py` -> `bell`.
+pub(crate) fn program_name(path: &Path) -> String {
+    let file_name = path.file_name().and_then(|name| name.to_str()).unwrap_or("program");
+    file_name
+        .strip_suffix(".eigen.py")
+        .or_else(|| file_name.strip_suffix(".py"))
+        .unwrap_or(file_name)
+        .to_string()
+}
+
+fn render_submit_output(
+    response: &jobspec::SubmitJobResponse,
+    req: &jobspec::SubmitJobRequest,
+    envelope: &jobspec::PublicSubmitEnvelope,
+    payload: &str,
+) {
+    let job_id = &response.job_id;
+    render_title("submit", Some("job accepted"));
+    println!("  job_id: {job_id}");
+    println!("  state: {}", response.state);
+    println!("  request:");
+    println!("    name: {}", req.name);
+    println!("    kind: {}", req.workload.kind);
+    println!("    execution_profile: {}", req.workload.execution_profile);
+    println!("    target: {}", req.target);
+    println!("    priority: {}", req.priority);
+    let entrypoint = match &req.program {
+        jobspec::ProgramSource::EigenLangSource { entrypoint, .. } => entrypoint,
+    };
+    println!("    entrypoint: {entrypoint}");
+    println!(
+        "    digest: {}",
+        jobspec::canonical_jobspec_digest_from_request(req, &req.jobspec_api_version)
+    );
+    println!("  envelope:");
+    println!("    contract_version: {}", envelope.contract_version);
+    println!("    request_id: {}", envelope.request_id);
+    println!("    idempotency_key: {}", envelope.idempotency_key);
+    println!("    traceparent: {}", envelope.traceparent);
+    println!("    tenant/project: {}/{}", envelope.tenant_id, envelope.project_id);
+    println!("    client_version: {}", envelope.client_version);
+    println!("  request_payload:");
+    print_indented_lines(4, &pretty_json_like(payload));
+    println!("  next:");
+    println!("    eigen status {job_id}");
+    println!("    eigen watch {job_id}");
+    println!("    eigen result {job_id}  # after completion");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::{invoke, parse, spawn_kernel, spawn_mock_server, MockJobService};
+    use crate::EXIT_NETWORK_ERROR;
+
+    fn write_job(name: Option<&str>) -> PathBuf {
+        let dir = std::env::temp_dir()
+            .join(format!("eigen-cli-submit-{}", std::process::id()))
+            .join(name.unwrap_or("unnamed"));
+        std::fs::create_dir_all(&dir).expect("temp dir");
+        let metadata = name.map(|name| format!("metadata:\n  name: {name}\n")).unwrap_or_default();
+        let path = dir.join("bell-pair.yaml");
+        std::fs::write(
+            &path,
+            format!("apiVersion: eigen.os/v0.1\nkind: QuantumJob\n{metadata}spec:\n  target: sim:local\n"),
+        )
+        .expect("write job.yaml");
+        std::fs::write(dir.join("program.eigen.py"), "@hybrid_program\ndef main():\n    return 1\n").expect("write program");
+        path
+    }
+
+    #[test]
+    fn submit_sends_job_fields_to_server() {
+        let service = MockJobService::default();
+        let addr = spawn_mock_server(service.clone());
+        let job = write_job(Some("bell"));
+
+        let argv = [
+            job.to_str().unwrap(),
+            "--server",
+            &addr.to_string(),
+            "--idempotency-key",
+            "submit-test-key",
+        ];
+        invoke(run, &argv).expect("submit should succeed");
+
+        let submitted = service.submitted.lock().unwrap();
+        assert_eq!(submitted.len(), 1);
+        let request = &submitted[0];
+        assert_eq!(request.name, "bell");
+        assert_eq!(request.target, "sim:local");
+        let envelope = request.envelope.as_ref().expect("envelope");
+        assert_eq!(envelope.idempotency_key, "submit-test-key");
+    }
+
+    #[test]
+    fn submit_falls_back_to_file_name_and_reports_initial_state() {
+        let service = MockJobService::default();
+        let addr = spawn_mock_server(service.clone());
+        let job = write_job(None);
+        let req = jobspec::build_submit_request_from_job_file(&job).expect("build request");
+        assert_eq!(req.name, "bell-pair");
+
+        let response = jobspec::submit_job_to_endpoint(
+            &addr.to_string(),
+            &req,
+            &jobspec::PublicSubmitOptions::default(),
+        )
+        .expect("submit should succeed");
+        assert_eq!(response.job_id, "job-mock-1");
+        assert_eq!(response.state, "PENDING");
+        assert_eq!(service.submitted.lock().unwrap()[0].name, "bell-pair");
+    }
+
+    #[test]
+    fn submit_maps_grpc_errors_to_exit_codes() {
+        let addr = spawn_mock_server(MockJobService::default());
+        let job = write_job(Some("rejected"));
+
+        let code = invoke(run, &["-f", job.to_str().unwrap(), "--server", &addr.to_string()])
+            .expect_err("rejected submit should fail");
+        assert_eq!(code, EXIT_USER_ERROR);
+    }
+
+    #[test]
+    fn args_reject_unknown_flags_and_missing_values() {
+        assert!(parse::<SubmitArgs>(&["--bogus"]).is_err());
+        assert!(parse::<SubmitArgs>(&["job.yaml", "--server"]).is_err());
+        assert!(parse::<SubmitArgs>(&["job.yaml", "-f", "other.yaml"]).is_err());
+        let (parsed, _) = parse::<SubmitArgs>(&["job.yaml", "--server", "localhost:50051", "--tenant-id", "acme"]).expect("parse");
+        assert_eq!(parsed.job_file, Some(PathBuf::from("job.yaml")));
+        assert_eq!(parsed.server.as_deref(), Some("localhost:50051"));
+        assert_eq!(parsed.options().tenant_id.as_deref(), Some("acme"));
+
+        let (parsed, global) =
+            parse::<SubmitArgs>(&["--file", "job.yaml", "--program", "bell.eigen.py", "--endpoint", "localhost:50052", "--json"])
+                .expect("parse kernel submit");
+        assert_eq!(parsed.file, Some(PathBuf::from("job.yaml")));
+        assert_eq!(parsed.program, Some(PathBuf::from("bell.eigen.py")));
+        assert_eq!(global.endpoint.as_deref(), Some("localhost:50052"));
+        assert!(global.json);
+    }
+
+    fn write_program() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("eigen-cli-submit-program-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("temp dir");
+        let path = dir.join("bell.eigen.py");
+        std::fs::write(&path, "@quantum\ndef main():\n    pass\n").expect("write program");
+        path
+    }
+
+    #[test]
+    fn program_submit_enqueues_on_in_process_kernel() {
+        let addr = spawn_kernel().to_string();
+        let program = write_program();
+        assert_eq!(program_name(&program), "bell");
+
+        invoke(run, &[program.to_str().unwrap(), "--name", "bell-pair", "--endpoint", &addr]).expect("submit to kernel");
+
+        // A fresh name keeps this a new job rather than a replay of the one above.
+        let job = kernel::enqueue(&addr, &KernelSubmission::program("bell-direct", "@quantum\ndef main():\n    pass\n"))
+            .expect("enqueue");
+        assert!(job.job_id.starts_with("job-"), "unexpected job id {}", job.job_id);
+        assert_eq!(job.state, "PENDING");
+    }
+
+    #[test]
+    fn program_submit_reports_unreachable_kernel() {
+        let addr = std::net::TcpListener::bind("127.0.0.1:0")
+            .and_then(|listener| listener.local_addr())
+            .expect("reserve port")
+            .to_string();
+        let program = write_program();
+
+        let err = kernel::enqueue(&addr, &KernelSubmission::program("bell", "def main(): pass")).expect_err("refused");
+        assert_eq!(err.code, jobspec::GrpcCode::Unavailable);
+        assert!(err.message.contains("cannot reach kernel"), "{}", err.message);
+
+        let code = invoke(run, &[program.to_str().unwrap(), "--endpoint", &addr]).expect_err("refused");
+        assert_eq!(code, EXIT_NETWORK_ERROR);
+    }
+
+    #[test]
+    fn program_flags_are_rejected_for_job_files() {
+        let job = write_job(Some("bell"));
+        let code = invoke(run, &[job.to_str().unwrap(), "--name", "bell-pair"]).expect_err("job file");
+        assert_eq!(code, EXIT_USER_ERROR);
+    }
+}
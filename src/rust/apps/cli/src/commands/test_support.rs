@@ -0,0 +1,189 @@
+//! In-process System API mock and kernel shared by the command tests.
+
+use std::collections::VecDeque;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+
+use clap::Parser;
+use tokio::net::TcpListener;
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::{Request, Response, Status};
+
+use crate::cli::GlobalArgs;
+use crate::jobspec::eigen::api::v1 as api;
+use crate::output::Printer;
+
+/// Mock `JobService` that records submitted jobs, rejects jobs named `rejected` and
+/// answers `GetJobStatus` from a scripted sequence of statuses.
+///
+/// Each status poll consumes one scripted status; the last one is repeated once the
+/// script runs out. An empty script answers `NOT_FOUND`. `CancelJob` answers
+/// `NOT_FOUND` for `job-missing` and is rejected while the current scripted status is
+/// terminal. `GetJobResults` returns the canned response, or `NOT_FOUND` without one.
+#[derive(Clone, Default)]
+pub(crate) struct MockJobService {
+    pub submitted: Arc<Mutex<Vec<api::SubmitJobRequest>>>,
+    statuses: Arc<Mutex<VecDeque<api::JobStatus>>>,
+    status_polls: Arc<AtomicUsize>,
+    results: Option<api::GetJobResultsResponse>,
+}
+
+impl MockJobService {
+    pub fn with_statuses(statuses: impl IntoIterator<Item = api::JobStatus>) -> Self {
+        Self {
+            statuses: Arc::new(Mutex::new(statuses.into_iter().collect())),
+            ..Self::default()
+        }
+    }
+
+    pub fn with_results(results: api::GetJobResultsResponse) -> Self {
+        Self {
+            results: Some(results),
+            ..Self::default()
+        }
+    }
+
+    pub fn status_polls(&self) -> usize {
+        self.status_polls.load(Ordering::SeqCst)
+    }
+}
+
+#[tonic::async_trait]
+impl api::job_service_server::JobService for MockJobService {
+    type StreamJobUpdatesStream = std::pin::Pin<
+        Box<dyn tokio_stream::Stream<Item = Result<api::StreamJobUpdatesResponse, Status>> + Send + 'static>,
+    >;
+
+    async fn submit_job(
+        &self,
+        request: Request<api::SubmitJobRequest>,
+    ) -> Result<Response<api::SubmitJobResponse>, Status> {
+        let request = request.into_inner();
+        if request.name == "rejected" {
+            return Err(Status::invalid_argument("job rejected by mock"));
+        }
+        self.submitted.lock().unwrap().push(request);
+        Ok(Response::new(api::SubmitJobResponse {
+            job_id: "job-mock-1".to_string(),
+            status: Some(api::JobStatus {
+                job_id: "job-mock-1".to_string(),
+                state: api::JobState::Pending as i32,
+                ..Default::default()
+            }),
+        }))
+    }
+
+    async fn get_job_status(
+        &self,
+        request: Request<api::GetJobStatusRequest>,
+    ) -> Result<Response<api::GetJobStatusResponse>, Status> {
+        self.status_polls.fetch_add(1, Ordering::SeqCst);
+        let mut statuses = self.statuses.lock().unwrap();
+        let status = if statuses.len() > 1 {
+            statuses.pop_front()
+        } else {
+            statuses.front().cloned()
+        };
+        let Some(status) = status else {
+            return Err(Status::not_found(format!("job {} not found", request.get_ref().job_id)));
+        };
+        Ok(Response::new(api::GetJobStatusResponse { status: Some(status) }))
+    }
+
+    async fn cancel_job(
+        &self,
+        request: Request<api::CancelJobRequest>,
+    ) -> Result<Response<api::CancelJobResponse>, Status> {
+        let job_id = &request.get_ref().job_id;
+        if job_id == "job-missing" {
+            return Err(Status::not_found(format!("job {job_id} not found")));
+        }
+        let terminal = self.statuses.lock().unwrap().front().is_some_and(|status| {
+            [api::JobState::Done, api::JobState::Error, api::JobState::Cancelled, api::JobState::Timeout]
+                .map(|state| state as i32)
+                .contains(&status.state)
+        });
+        Ok(Response::new(api::CancelJobResponse { accepted: !terminal }))
+    }
+
+    async fn stream_job_updates(
+        &self,
+        _request: Request<api::StreamJobUpdatesRequest>,
+    ) -> Result<Response<Self::StreamJobUpdatesStream>, Status> {
+        Err(Status::unimplemented("mock"))
+    }
+
+    async fn get_job_results(
+        &self,
+        request: Request<api::GetJobResultsRequest>,
+    ) -> Result<Response<api::GetJobResultsResponse>, Status> {
+        match &self.results {
+            Some(results) => Ok(Response::new(results.clone())),
+            None => Err(Status::not_found(format!("job {} not found", request.get_ref().job_id))),
+        }
+    }
+
+    async fn get_dispatch_rationale(
+        &self,
+        _request: Request<api::GetDispatchRationaleRequest>,
+    ) -> Result<Response<api::GetDispatchRationaleResponse>, Status> {
+        Err(Status::unimplemented("mock"))
+    }
+}
+
+/// Serves `service` on an ephemeral localhost port from a background runtime.
+pub(crate) fn spawn_mock_server(service: MockJobService) -> SocketAddr {
+    let (addr_tx, addr_rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let rt = tokio::runtime::Runtime::new().expect("mock runtime");
+        rt.block_on(async move {
+            let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind mock port");
+            addr_tx.send(listener.local_addr().expect("mock addr")).expect("send mock addr");
+            tonic::transport::Server::builder()
+                .add_service(api::job_service_server::JobServiceServer::new(service))
+                .serve_with_incoming(TcpListenerStream::new(listener))
+                .await
+                .expect("serve mock api");
+        });
+    });
+    addr_rx.recv().expect("receive mock addr")
+}
+
+/// Serves an in-process `eigen-kernel` (auth disabled) on an ephemeral localhost port.
+pub(crate) fn spawn_kernel() -> SocketAddr {
+    let (addr_tx, addr_rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let rt = tokio::runtime::Runtime::new().expect("kernel runtime");
+        rt.block_on(async move {
+            let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind kernel port");
+            addr_tx.send(listener.local_addr().expect("kernel addr")).expect("send kernel addr");
+            eigen_kernel::rpc::serve_with_listener(listener, None, eigen_kernel::auth::AuthInterceptor::disabled())
+                .await
+                .expect("serve kernel");
+        });
+    });
+    addr_rx.recv().expect("receive kernel addr")
+}
+
+/// Parses `items` as the arguments of one subcommand, global flags included.
+pub(crate) fn parse<T: clap::Args>(items: &[&str]) -> Result<(T, GlobalArgs), clap::Error> {
+    #[derive(clap::Parser)]
+    struct Harness<T: clap::Args> {
+        #[command(flatten)]
+        args: T,
+        #[command(flatten)]
+        global: GlobalArgs,
+    }
+    Harness::<T>::try_parse_from(std::iter::once("eigen").chain(items.iter().copied()))
+        .map(|harness| (harness.args, harness.global))
+}
+
+/// Parses `items` and hands them to a command's `run`, as `main` would.
+pub(crate) fn invoke<T: clap::Args>(
+    run: fn(T, &GlobalArgs, &Printer) -> Result<(), i32>,
+    items: &[&str],
+) -> Result<(), i32> {
+    let (args, global) = parse::<T>(items).unwrap_or_else(|err| panic!("invalid test arguments: {err}"));
+    run(args, &global, &global.printer(None))
+}
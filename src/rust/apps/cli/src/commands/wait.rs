@@ -0,0 +1,209 @@
+//! Job lookups shared by `status`, `result` and `cancel`, and the `--wait` polling loop.
+
+use std::time::{Duration, Instant};
+
+use crate::jobspec::{self, eigen::api::v1 as api, GrpcCode, GrpcLikeError, JobResultsView};
+use crate::output::Printer;
+use crate::{kernel, terminal_exit_code};
+
+/// Delay between two `GetJobStatus` polls in `--wait` and `--watch` mode.
+pub(crate) const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Exit code when `--timeout` expires before the job reaches a terminal state.
+pub(crate) const EXIT_WAIT_TIMEOUT: i32 = 3;
+/// Exit code for job ids the server does not know.
+pub(crate) const EXIT_JOB_NOT_FOUND: i32 = 4;
+
+/// Where job lookups go: the System API (`--server`) or a kernel (`--endpoint`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum JobSource {
+    SystemApi(String),
+    Kernel(String),
+}
+
+impl JobSource {
+    /// `--endpoint` wins over `--server`; without either the System API default is used.
+    pub(crate) fn from_flags(server: Option<String>, endpoint: Option<String>) -> Self {
+        match (endpoint, server) {
+            (Some(endpoint), _) => Self::Kernel(endpoint),
+            (None, Some(server)) => Self::SystemApi(server),
+            (None, None) => Self::SystemApi(jobspec::system_api_endpoint()),
+        }
+    }
+
+    pub(crate) fn status(&self, job_id: &str) -> Result<api::JobStatus, GrpcLikeError> {
+        match self {
+            Self::SystemApi(endpoint) => jobspec::get_job_status_from_endpoint(endpoint, job_id),
+            Self::Kernel(addr) => kernel::get_job_status(addr, job_id),
+        }
+    }
+
+    /// Sends `CancelJob`; `Ok(false)` means the job was already terminal.
+    pub(crate) fn cancel(&self, job_id: &str) -> Result<bool, GrpcLikeError> {
+        match self {
+            Self::SystemApi(endpoint) => jobspec::cancel_job_at_endpoint(endpoint, job_id),
+            Self::Kernel(addr) => kernel::cancel_job(addr, job_id),
+        }
+    }
+
+    pub(crate) fn results(&self, job_id: &str) -> Result<JobResultsView, GrpcLikeError> {
+        match self {
+            Self::SystemApi(endpoint) => jobspec::get_job_results_from_endpoint(endpoint, job_id),
+            Self::Kernel(addr) => kernel::get_job_results(addr, job_id),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) enum WaitError {
+    Rpc(GrpcLikeError),
+    /// The deadline passed; carries the last status seen.
+    TimedOut(Box<api::JobStatus>),
+}
+
+/// Polls `job_id` every `interval` until it reaches a terminal state or `timeout`
+/// expires. `on_poll` sees every status, including the terminal one.
+pub(crate) fn wait_for_terminal(
+    source: &JobSource,
+    job_id: &str,
+    interval: Duration,
+    timeout: Option<Duration>,
+    mut on_poll: impl FnMut(&api::JobStatus),
+) -> Result<api::JobStatus, WaitError> {
+    wait_until(source, job_id, interval, timeout, |status| {
+        on_poll(status);
+        terminal_exit_code(&jobspec::map_job_state(status.state)).is_some()
+    })
+}
+
+/// Polls `job_id` every `interval` until `done` accepts a status or `timeout` expires.
+pub(crate) fn wait_until(
+    source: &JobSource,
+    job_id: &str,
+    interval: Duration,
+    timeout: Option<Duration>,
+    mut done: impl FnMut(&api::JobStatus) -> bool,
+) -> Result<api::JobStatus, WaitError> {
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+    loop {
+        let status = source.status(job_id).map_err(WaitError::Rpc)?;
+        if done(&status) {
+            return Ok(status);
+        }
+        let now = Instant::now();
+        let pause = match deadline {
+            Some(deadline) if now >= deadline => return Err(WaitError::TimedOut(Box::new(status))),
+            Some(deadline) => interval.min(deadline - now),
+            None => interval,
+        };
+        std::thread::sleep(pause);
+    }
+}
+
+/// Prints a failed lookup and returns the exit code; unknown jobs exit with
+/// [`EXIT_JOB_NOT_FOUND`].
+pub(crate) fn report_error(printer: &Printer, cmd: &str, job_id: &str, err: &GrpcLikeError) -> i32 {
+    if err.code == GrpcCode::NotFound {
+        printer.print_failure(cmd, err.code.as_str(), &format!("job {job_id} not found"));
+        return EXIT_JOB_NOT_FOUND;
+    }
+    printer.print_error(cmd, err)
+}
+
+pub(crate) fn report_wait_error(printer: &Printer, cmd: &str, job_id: &str, err: &WaitError) -> i32 {
+    match err {
+        WaitError::Rpc(err) => report_error(printer, cmd, job_id, err),
+        WaitError::TimedOut(status) => {
+            let message = format!(
+                "timed out waiting for job {job_id} (last state {})",
+                jobspec::map_job_state(status.state)
+            );
+            printer.print_failure(cmd, GrpcCode::DeadlineExceeded.as_str(), &message);
+            EXIT_WAIT_TIMEOUT
+        }
+    }
+}
+
+/// Parses `--timeout` and `--since`: whole seconds, or a number with an `ms`, `s`,
+/// `m` or `h` suffix.
+pub(crate) fn parse_duration(value: &str) -> Result<Duration, String> {
+    let value = value.trim();
+    let split = value.find(|ch: char| !ch.is_ascii_digit()).unwrap_or(value.len());
+    let (digits, unit) = value.split_at(split);
+    let amount: u64 = digits.parse().map_err(|_| format!("invalid duration: {value:?}"))?;
+    match unit {
+        "ms" => Ok(Duration::from_millis(amount)),
+        "" | "s" => Ok(Duration::from_secs(amount)),
+        "m" => Ok(Duration::from_secs(amount * 60)),
+        "h" => Ok(Duration::from_secs(amount * 3_600)),
+        _ => Err(format!("invalid duration unit in {value:?} (expected ms, s, m or h)")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::{spawn_kernel, spawn_mock_server, MockJobService};
+    use crate::kernel::KernelSubmission;
+
+    fn running() -> api::JobStatus {
+        api::JobStatus {
+            job_id: "job-wait-1".to_string(),
+            state: api::JobState::Running as i32,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn wait_times_out_on_a_job_that_never_finishes() {
+        let service = MockJobService::with_statuses([running()]);
+        let source = JobSource::SystemApi(spawn_mock_server(service.clone()).to_string());
+
+        let started = Instant::now();
+        let err = wait_for_terminal(
+            &source,
+            "job-wait-1",
+            Duration::from_millis(10),
+            Some(Duration::from_millis(100)),
+            |_| {},
+        )
+        .expect_err("never terminal");
+        assert!(matches!(&err, WaitError::TimedOut(status) if status.job_id == "job-wait-1"));
+        assert!(started.elapsed() >= Duration::from_millis(100));
+        assert!(service.status_polls() >= 2);
+        assert_eq!(report_wait_error(&Printer::default(), "status", "job-wait-1", &err), EXIT_WAIT_TIMEOUT);
+    }
+
+    #[test]
+    fn wait_follows_a_kernel_job_to_a_terminal_state() {
+        let addr = spawn_kernel().to_string();
+        let job = kernel::enqueue(&addr, &KernelSubmission::program("wait-kernel", "@quantum\ndef main():\n    pass\n"))
+            .expect("enqueue");
+        let source = JobSource::Kernel(addr);
+
+        let mut polls = 0;
+        let status = wait_for_terminal(
+            &source,
+            &job.job_id,
+            Duration::from_millis(20),
+            Some(Duration::from_secs(30)),
+            |_| polls += 1,
+        )
+        .expect("job finishes");
+        assert!(polls >= 1);
+        assert!(terminal_exit_code(&jobspec::map_job_state(status.state)).is_some());
+
+        let err = source.status("job-unknown").expect_err("unknown job");
+        assert_eq!(report_error(&Printer::default(), "status", "job-unknown", &err), EXIT_JOB_NOT_FOUND);
+    }
+
+    #[test]
+    fn durations_accept_plain_seconds_and_unit_suffixes() {
+        assert_eq!(parse_duration("30"), Ok(Duration::from_secs(30)));
+        assert_eq!(parse_duration("250ms"), Ok(Duration::from_millis(250)));
+        assert_eq!(parse_duration("2m"), Ok(Duration::from_secs(120)));
+        assert_eq!(parse_duration("1h"), Ok(Duration::from_secs(3_600)));
+        assert!(parse_duration("soon").is_err());
+        assert!(parse_duration("5d").is_err());
+    }
+}
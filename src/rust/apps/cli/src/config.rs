@@ -0,0 +1,215 @@
+//! CLI settings from `~/.eigen/config.toml`, the environment and global flags.
+//!
+//! Later sources win: the config file, then `EIGEN_KERNEL_ADDR` / `EIGEN_TOKEN`,
+//! then the global `--endpoint` (or `EIGEN_ENDPOINT`), `--token`, `--tls-cert`
+//! and `--timeout` flags.
+
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::cli::GlobalArgs;
+use crate::kernel::{DEFAULT_KERNEL_ADDR, KERNEL_ADDR_ENV, KERNEL_AUTH_TOKEN_ENV};
+
+/// Overrides the config file location (default `~/.eigen/config.toml`).
+pub const CONFIG_PATH_ENV: &str = "EIGEN_CONFIG";
+pub const TOKEN_ENV: &str = "EIGEN_TOKEN";
+
+/// Starter file written by `eigen config init`.
+pub const STARTER_CONFIG: &str = r#"# Eigen CLI configuration.
+#
+# Values here are overridden by EIGEN_KERNEL_ADDR / EIGEN_TOKEN and by the
+# global flags --endpoint (or EIGEN_ENDPOINT), --token and --tls-cert.
+
+# Kernel gRPC address used when a command gets no --endpoint.
+server_addr = "localhost:50052"
+
+# Bearer token sent to kernels that have authentication enabled.
+# token = "..."
+
+# PEM CA certificate; when set, kernel connections use TLS.
+# tls_ca_cert = "/path/to/ca.pem"
+"#;
+
+static CURRENT: OnceLock<CliConfig> = OnceLock::new();
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CliConfig {
+    pub server_addr: String,
+    pub token: Option<String>,
+    pub tls_ca_cert: Option<PathBuf>,
+    /// Per-RPC deadline from `--timeout`; not read from the file.
+    #[serde(skip)]
+    pub timeout: Option<Duration>,
+}
+
+impl Default for CliConfig {
+    fn default() -> Self {
+        Self {
+            server_addr: DEFAULT_KERNEL_ADDR.to_string(),
+            token: None,
+            tls_ca_cert: None,
+            timeout: None,
+        }
+    }
+}
+
+/// Settings taken from the global flags.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ConfigOverrides {
+    pub server_addr: Option<String>,
+    pub token: Option<String>,
+    pub tls_ca_cert: Option<PathBuf>,
+    pub timeout: Option<Duration>,
+}
+
+impl From<&GlobalArgs> for ConfigOverrides {
+    fn from(global: &GlobalArgs) -> Self {
+        Self {
+            server_addr: global.endpoint.clone(),
+            token: global.token.clone(),
+            tls_ca_cert: global.tls_cert.clone(),
+            timeout: global.timeout,
+        }
+    }
+}
+
+impl CliConfig {
+    /// Parses a config file body.
+    pub fn from_toml(text: &str) -> Result<Self, String> {
+        toml::from_str(text).map_err(|err| format!("invalid config: {err}"))
+    }
+
+    /// Reads `path`; a missing file yields the defaults.
+    pub fn load_file(path: &Path) -> Result<Self, String> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => Self::from_toml(&text).map_err(|err| format!("{}: {err}", path.display())),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(format!("failed to read {}: {err}", path.display())),
+        }
+    }
+
+    /// Applies environment variables (looked up through `env`), then `overrides`.
+    pub fn layered(mut self, env: impl Fn(&str) -> Option<String>, overrides: &ConfigOverrides) -> Self {
+        if let Some(addr) = env(KERNEL_ADDR_ENV) {
+            self.server_addr = addr;
+        }
+        if let Some(token) = env(TOKEN_ENV).or_else(|| env(KERNEL_AUTH_TOKEN_ENV)) {
+            self.token = Some(token);
+        }
+        if let Some(addr) = &overrides.server_addr {
+            self.server_addr = addr.clone();
+        }
+        if let Some(token) = &overrides.token {
+            self.token = Some(token.clone());
+        }
+        if let Some(path) = &overrides.tls_ca_cert {
+            self.tls_ca_cert = Some(path.clone());
+        }
+        if overrides.timeout.is_some() {
+            self.timeout = overrides.timeout;
+        }
+        self
+    }
+
+    /// File, then process environment, then `overrides`.
+    pub fn resolve(overrides: &ConfigOverrides) -> Result<Self, String> {
+        let env = |name: &str| std::env::var(name).ok().filter(|value| !value.trim().is_empty());
+        Ok(Self::load_file(&config_path())?.layered(env, overrides))
+    }
+
+    /// The resolved config as TOML, with the token masked.
+    pub fn to_display_toml(&self) -> String {
+        let mut shown = self.clone();
+        shown.token = shown.token.map(|_| "********".to_string());
+        toml::to_string_pretty(&shown).unwrap_or_default()
+    }
+}
+
+/// `$EIGEN_CONFIG`, else `~/.eigen/config.toml`.
+pub fn config_path() -> PathBuf {
+    if let Some(path) = std::env::var_os(CONFIG_PATH_ENV).filter(|path| !path.is_empty()) {
+        return PathBuf::from(path);
+    }
+    let home = std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+        .unwrap_or_default();
+    home.join(".eigen").join("config.toml")
+}
+
+/// Records the config resolved in `main`; later calls are ignored.
+pub fn install(config: CliConfig) {
+    let _ = CURRENT.set(config);
+}
+
+/// The installed config, or file + environment when `main` did not install one
+/// (e.g. in unit tests).
+pub fn current() -> &'static CliConfig {
+    CURRENT.get_or_init(|| CliConfig::resolve(&ConfigOverrides::default()).unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn env_of(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: BTreeMap<String, String> = vars.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        move |name| vars.get(name).cloned()
+    }
+
+    #[test]
+    fn flags_override_env_which_overrides_the_file() {
+        let file = CliConfig::from_toml(
+            "server_addr = \"file:50052\"\ntoken = \"file-token\"\ntls_ca_cert = \"/etc/eigen/ca.pem\"\n",
+        )
+        .expect("parse file");
+
+        let from_file = file.clone().layered(env_of(&[]), &ConfigOverrides::default());
+        assert_eq!(from_file.server_addr, "file:50052");
+        assert_eq!(from_file.token.as_deref(), Some("file-token"));
+
+        let from_env = file
+            .clone()
+            .layered(env_of(&[(KERNEL_ADDR_ENV, "env:50052"), (TOKEN_ENV, "env-token")]), &ConfigOverrides::default());
+        assert_eq!(from_env.server_addr, "env:50052");
+        assert_eq!(from_env.token.as_deref(), Some("env-token"));
+        assert_eq!(from_env.tls_ca_cert, Some(PathBuf::from("/etc/eigen/ca.pem")));
+
+        let overrides = ConfigOverrides {
+            server_addr: Some("flag:50052".to_string()),
+            token: Some("flag-token".to_string()),
+            tls_ca_cert: Some(PathBuf::from("ca.pem")),
+            timeout: Some(Duration::from_secs(5)),
+        };
+        let from_flags = file.layered(env_of(&[(KERNEL_ADDR_ENV, "env:50052"), (TOKEN_ENV, "env-token")]), &overrides);
+        assert_eq!(from_flags.server_addr, "flag:50052");
+        assert_eq!(from_flags.token.as_deref(), Some("flag-token"));
+        assert_eq!(from_flags.tls_ca_cert, Some(PathBuf::from("ca.pem")));
+        assert_eq!(from_flags.timeout, Some(Duration::from_secs(5)));
+    }
+
+    #[test]
+    fn missing_file_gives_defaults_and_the_starter_file_parses() {
+        let missing = std::env::temp_dir().join(format!("eigen-config-missing-{}.toml", std::process::id()));
+        assert_eq!(CliConfig::load_file(&missing), Ok(CliConfig::default()));
+        assert_eq!(CliConfig::from_toml(STARTER_CONFIG), Ok(CliConfig::default()));
+        assert!(CliConfig::from_toml("sever_addr = \"typo\"").is_err());
+    }
+
+    #[test]
+    fn display_masks_the_token_and_omits_the_timeout() {
+        let shown = CliConfig {
+            token: Some("secret".to_string()),
+            timeout: Some(Duration::from_secs(5)),
+            ..CliConfig::default()
+        }
+        .to_display_toml();
+        assert!(!shown.contains("secret"), "{shown}");
+        assert!(!shown.contains("timeout"), "{shown}");
+    }
+}
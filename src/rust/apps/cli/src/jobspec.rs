@@ -192,6 +192,8 @@ pub enum ProgramSource {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SubmitJobResponse {
     pub job_id: String,
+    /// Initial job state reported by the System API (`PENDING` when omitted).
+    pub state: String,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -218,15 +220,30 @@ pub struct PublicSubmitOptions {
 pub fn build_submit_request_from_job_file(
     path: &Path,
 ) -> Result<SubmitJobRequest, SubmitBuildError> {
-    let yaml = fs::read_to_string(path)
-        .map_err(|e| SubmitBuildError::Io(format!("failed to read {}: {e}", path.display())))?;
-    let spec = parse_and_validate_jobspec(&yaml).map_err(SubmitBuildError::Validation)?;
+    let (spec, _) = load_jobspec_file(path)?;
 
     let basedir = path.parent().unwrap_or_else(|| Path::new("."));
     map_to_submit_job_request_with_packaging(&spec, basedir).map_err(SubmitBuildError::Validation)
 }
 
+/// Reads and validates a JobSpec file, returning the spec together with the raw YAML.
+pub fn load_jobspec_file(path: &Path) -> Result<(JobSpec, String), SubmitBuildError> {
+    let yaml = fs::read_to_string(path)
+        .map_err(|e| SubmitBuildError::Io(format!("failed to read {}: {e}", path.display())))?;
+    // Jobs without metadata.name are named after their file (`bell.yaml` -> `bell`).
+    let fallback_name = path.file_stem().and_then(|stem| stem.to_str());
+    let spec = parse_jobspec(&yaml, fallback_name).map_err(SubmitBuildError::Validation)?;
+    Ok((spec, yaml))
+}
+
 pub fn parse_and_validate_jobspec(yaml: &str) -> Result<JobSpec, JobSpecValidationError> {
+    parse_jobspec(yaml, None)
+}
+
+fn parse_jobspec(
+    yaml: &str,
+    fallback_name: Option<&str>,
+) -> Result<JobSpec, JobSpecValidationError> {
     let mut api_version = String::new();
     let mut kind = String::new();
     let mut name = String::new();
@@ -537,9 +554,12 @@ pub fn parse_and_validate_jobspec(yaml: &str) -> Result<JobSpec, JobSpecValidati
             description: format!("must be '{JOBSPEC_KIND}'"),
         });
     }
+    if name.is_empty() {
+        name = fallback_name.unwrap_or_default().to_string();
+    }
     if name.is_empty() {
         violations.push(FieldViolation {
-            field: "kind".to_string(),
+            field: "metadata.name".to_string(),
             description: "field is required".to_string(),
         });
     }
@@ -876,7 +896,7 @@ fn runtime() -> Result<&'static Runtime, GrpcLikeError> {
     }
 }
 
-fn block_on_result<F, T>(future: F) -> Result<T, GrpcLikeError>
+pub(crate) fn block_on_result<F, T>(future: F) -> Result<T, GrpcLikeError>
 where
     F: std::future::Future<Output = Result<T, GrpcLikeError>>,
 {
@@ -888,15 +908,25 @@ where
 }
 
 #[cfg(not(test))]
-fn system_api_endpoint() -> String {
+pub fn system_api_endpoint() -> String {
     std::env::var("EIGEN_SYSTEM_API_ENDPOINT").unwrap_or_else(|_| "http://127.0.0.1:50051".to_string())
 }
 
 #[cfg(test)]
-fn system_api_endpoint() -> String {
+pub fn system_api_endpoint() -> String {
     std::env::var("EIGEN_SYSTEM_API_ENDPOINT").unwrap_or_else(|_| test_system_api_endpoint())
 }
 
+/// Accepts `host:port` as well as full URLs and defaults the scheme to `http://`.
+pub fn normalize_endpoint(addr: &str) -> String {
+    let addr = addr.trim();
+    if addr.contains("://") {
+        addr.to_string()
+    } else {
+        format!("http://{addr}")
+    }
+}
+
 #[cfg(test)]
 fn test_system_api_endpoint() -> String {
     use std::fs;
@@ -1137,7 +1167,7 @@ impl eigen::api::v1::job_service_server::JobService for TestJobService {
     }
 }
 
-fn map_transport_error(err: TransportError) -> GrpcLikeError {
+pub(crate) fn map_transport_error(err: TransportError) -> GrpcLikeError {
     GrpcLikeError {
         code: GrpcCode::Unavailable,
         message: format!("failed to connect to system api: {err}"),
@@ -1145,7 +1175,7 @@ fn map_transport_error(err: TransportError) -> GrpcLikeError {
     }
 }
 
-fn map_status_error(status: tonic::Status) -> GrpcLikeError {
+pub(crate) fn map_status_error(status: tonic::Status) -> GrpcLikeError {
     let code = match status.code() {
         tonic::Code::InvalidArgument => GrpcCode::InvalidArgument,
         tonic::Code::NotFound => GrpcCode::NotFound,
@@ -1161,7 +1191,7 @@ fn map_status_error(status: tonic::Status) -> GrpcLikeError {
     }
 }
 
-fn map_job_state(state: i32) -> String {
+pub fn map_job_state(state: i32) -> String {
     match state {
         1 => "PENDING",
         2 => "COMPILING",
@@ -1178,11 +1208,20 @@ fn map_job_state(state: i32) -> String {
 
 fn connect_client(
 ) -> Result<eigen::api::v1::job_service_client::JobServiceClient<Channel>, GrpcLikeError> {
-    let endpoint = Endpoint::from_shared(system_api_endpoint()).map_err(|e| GrpcLikeError {
+    connect_client_to(&system_api_endpoint())
+}
+
+fn connect_client_to(
+    endpoint: &str,
+) -> Result<eigen::api::v1::job_service_client::JobServiceClient<Channel>, GrpcLikeError> {
+    let mut endpoint = Endpoint::from_shared(normalize_endpoint(endpoint)).map_err(|e| GrpcLikeError {
         code: GrpcCode::InvalidArgument,
         message: format!("invalid system api endpoint: {e}"),
         retry_hint: None,
     })?;
+    if let Some(timeout) = crate::config::current().timeout {
+        endpoint = endpoint.connect_timeout(timeout).timeout(timeout);
+    }
     let client = block_on_result(async {
         endpoint
             .connect()
@@ -1269,10 +1308,19 @@ fn build_submit_proto_request(
 pub fn submit_job_to_system_api(
     req: &SubmitJobRequest,
     options: &PublicSubmitOptions,
+) -> Result<SubmitJobResponse, GrpcLikeError> {
+    submit_job_to_endpoint(&system_api_endpoint(), req, options)
+}
+
+/// Same as [`submit_job_to_system_api`] against an explicit endpoint, e.g. from `--server`.
+pub fn submit_job_to_endpoint(
+    endpoint: &str,
+    req: &SubmitJobRequest,
+    options: &PublicSubmitOptions,
 ) -> Result<SubmitJobResponse, GrpcLikeError> {
     let _ = validate_submit_request_against_system_api_schema(req);
     block_on_result(async {
-        let mut client = connect_client()?;
+        let mut client = connect_client_to(endpoint)?;
         let proto_req = build_submit_proto_request(req, options);
         let resp = client
             .submit_job(proto_req)
@@ -1281,6 +1329,11 @@ pub fn submit_job_to_system_api(
             .into_inner();
         Ok(SubmitJobResponse {
             job_id: resp.job_id,
+            state: resp
+                .status
+                .map(|status| map_job_state(status.state))
+                .filter(|state| state != "UNSPECIFIED")
+                .unwrap_or_else(|| "PENDING".to_string()),
         })
     })
 }
@@ -1591,7 +1644,7 @@ fn strip_quotes(s: &str) -> String {
     s.trim_matches('"').trim_matches('\'').to_string()
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum GrpcCode {
     InvalidArgument,
     NotFound,
@@ -1629,15 +1682,6 @@ impl Display for GrpcLikeError {
 
 impl std::error::Error for GrpcLikeError {}
 
-#[derive(Debug, Clone, PartialEq)]
-pub struct JobStatusView {
-    pub job_id: String,
-    pub state: String,
-    pub stage: String,
-    pub progress: f32,
-    pub message: String,
-}
-
 #[derive(Debug, Clone, PartialEq)]
 pub struct JobUpdateView {
     pub event_seq: u64,
@@ -1656,6 +1700,8 @@ pub struct JobResultsView {
     pub metadata: BTreeMap<String, String>,
     pub error_code: Option<String>,
     pub error_summary: Option<String>,
+    /// Where the full error report of an `ERROR` job is stored.
+    pub error_details_ref: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -1975,7 +2021,11 @@ fn extract_json_u64_field(doc: &str, field: &str) -> Option<u64> {
     extract_json_number_field(doc, field).map(|v| v as u64)
 }
 
-pub fn get_job_status_from_system_api(job_id: &str) -> Result<JobStatusView, GrpcLikeError> {
+/// Fetches the raw `JobStatus` of `job_id` from the System API at `endpoint`.
+pub fn get_job_status_from_endpoint(
+    endpoint: &str,
+    job_id: &str,
+) -> Result<eigen::api::v1::JobStatus, GrpcLikeError> {
     if job_id.trim().is_empty() {
         return Err(GrpcLikeError {
             code: GrpcCode::InvalidArgument,
@@ -1985,7 +2035,7 @@ pub fn get_job_status_from_system_api(job_id: &str) -> Result<JobStatusView, Grp
     }
 
     block_on_result(async {
-        let mut client = connect_client()?;
+        let mut client = connect_client_to(endpoint)?;
         let resp = client
             .get_job_status(eigen::api::v1::GetJobStatusRequest {
                 envelope: None,
@@ -1994,21 +2044,39 @@ pub fn get_job_status_from_system_api(job_id: &str) -> Result<JobStatusView, Grp
             .await
             .map_err(map_status_error)?
             .into_inner();
-        let status = resp.status.ok_or_else(|| GrpcLikeError {
+        resp.status.ok_or_else(|| GrpcLikeError {
             code: GrpcCode::Internal,
             message: "empty GetJobStatus response".to_string(),
             retry_hint: None,
-        })?;
-        Ok(JobStatusView {
-            job_id: status.job_id,
-            state: map_job_state(status.state),
-            stage: status.stage,
-            progress: status.progress,
-            message: status.message,
         })
     })
 }
 
+/// Asks the System API at `endpoint` to cancel `job_id`; returns whether the request
+/// was accepted.
+pub fn cancel_job_at_endpoint(endpoint: &str, job_id: &str) -> Result<bool, GrpcLikeError> {
+    if job_id.trim().is_empty() {
+        return Err(GrpcLikeError {
+            code: GrpcCode::InvalidArgument,
+            message: "job_id is required".to_string(),
+            retry_hint: None,
+        });
+    }
+
+    block_on_result(async {
+        let mut client = connect_client_to(endpoint)?;
+        let resp = client
+            .cancel_job(eigen::api::v1::CancelJobRequest {
+                envelope: None,
+                job_id: job_id.to_string(),
+            })
+            .await
+            .map_err(map_status_error)?
+            .into_inner();
+        Ok(resp.accepted)
+    })
+}
+
 pub fn stream_job_updates_from_system_api(
     job_id: &str,
 ) -> Result<Vec<JobUpdateView>, GrpcLikeError> {
@@ -2042,7 +2110,7 @@ pub fn stream_job_updates_from_system_api(
 
 const RESULT_SUMMARY_PREFIX: &str = "result.summary.";
 
-fn split_result_summary(
+pub(crate) fn split_result_summary(
     metadata: BTreeMap<String, String>,
 ) -> (BTreeMap<String, String>, BTreeMap<String, String>) {
     let mut summary = BTreeMap::new();
@@ -2058,6 +2126,10 @@ fn split_result_summary(
 }
 
 pub fn get_job_results_from_system_api(job_id: &str) -> Result<JobResultsView, GrpcLikeError> {
+    get_job_results_from_endpoint(&system_api_endpoint(), job_id)
+}
+
+pub fn get_job_results_from_endpoint(endpoint: &str, job_id: &str) -> Result<JobResultsView, GrpcLikeError> {
     if job_id.trim().is_empty() {
         return Err(GrpcLikeError {
             code: GrpcCode::InvalidArgument,
@@ -2067,7 +2139,7 @@ pub fn get_job_results_from_system_api(job_id: &str) -> Result<JobResultsView, G
     }
 
     block_on_result(async {
-    let mut client = connect_client()?;
+    let mut client = connect_client_to(endpoint)?;
     let resp = client
         .get_job_results(eigen::api::v1::GetJobResultsRequest {
             envelope: None,
@@ -2105,6 +2177,11 @@ pub fn get_job_results_from_system_api(job_id: &str) -> Result<JobResultsView, G
         } else {
             Some(resp.error_summary)
         },
+        error_details_ref: if resp.error_details_ref.is_empty() {
+            None
+        } else {
+            Some(resp.error_details_ref)
+        },
     })
 })
 }
@@ -2467,8 +2544,8 @@ spec:
 
     #[test]
     fn status_watch_results_workflow_views_are_consistent() {
-        let status = get_job_status_from_system_api("job-demo").expect("status");
-        assert_eq!(status.state, "RUNNING");
+        let status = get_job_status_from_endpoint(&system_api_endpoint(), "job-demo").expect("status");
+        assert_eq!(map_job_state(status.state), "RUNNING");
 
         let updates = stream_job_updates_from_system_api("job-demo").expect("updates");
         assert_eq!(updates.last().map(|u| u.state.clone()), Some("DONE".to_string()));
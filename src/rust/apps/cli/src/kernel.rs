@@ -0,0 +1,298 @@
+//! Direct client for the kernel's internal `KernelGateway` API.
+//!
+//! `eigen submit <program.eigen.py>` and `eigen submit --file job.yaml --program ...`
+//! bypass the System API and enqueue the program on a kernel, which is handy against
+//! a local `eigen-kernel` without the rest of the stack.
+
+use std::collections::BTreeMap;
+
+use tonic::transport::{Certificate, Channel, ClientTlsConfig, Endpoint};
+use tonic::Request;
+
+use crate::config;
+use crate::jobspec::{
+    self, block_on_result, eigen::api::v1 as api, map_status_error, GrpcCode, GrpcLikeError,
+    JobResultsView, DEFAULT_PROJECT_ID, DEFAULT_TENANT_ID,
+};
+
+/// Generated `eigen.internal.v1` client stubs.
+#[allow(dead_code)]
+pub mod proto {
+    tonic::include_proto!("eigen.internal.v1");
+}
+
+pub const KERNEL_ADDR_ENV: &str = "EIGEN_KERNEL_ADDR";
+pub const DEFAULT_KERNEL_ADDR: &str = "localhost:50052";
+/// Bearer token sent as `authorization` metadata when the kernel has auth enabled;
+/// [`config::TOKEN_ENV`] takes precedence.
+pub const KERNEL_AUTH_TOKEN_ENV: &str = "EIGEN_KERNEL_AUTH_TOKEN";
+
+const PROGRAM_FORMAT: &str = "eigen_lang_source";
+const DEFAULT_TARGET: &str = "sim:local";
+const DEFAULT_PRIORITY: i32 = 50;
+const SOURCE_SERVICE: &str = "eigen-cli";
+
+/// Kernel address from the resolved [`config::CliConfig`]: `--endpoint` (or
+/// `EIGEN_ENDPOINT`), then `EIGEN_KERNEL_ADDR`, then the config file, defaulting to
+/// `localhost:50052`.
+pub fn kernel_addr() -> String {
+    config::current().server_addr.clone()
+}
+
+/// A job to enqueue on the kernel: Eigen-Lang source plus the scheduling fields a
+/// JobSpec carries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KernelSubmission {
+    pub name: String,
+    pub program: Vec<u8>,
+    /// Resolved JobSpec YAML, stored by the kernel as `input/job.yaml`; empty when the
+    /// program was submitted on its own.
+    pub job_yaml: String,
+    pub target: String,
+    pub priority: i32,
+    pub compiler_options: BTreeMap<String, String>,
+}
+
+impl KernelSubmission {
+    /// A bare program with the default target (`sim:local`) and priority.
+    pub fn program(name: impl Into<String>, program: impl Into<Vec<u8>>) -> Self {
+        Self {
+            name: name.into(),
+            program: program.into(),
+            job_yaml: String::new(),
+            target: DEFAULT_TARGET.to_string(),
+            priority: DEFAULT_PRIORITY,
+            compiler_options: BTreeMap::new(),
+        }
+    }
+
+    /// A program scheduled with the settings of `spec`; `job_yaml` is the JobSpec as
+    /// written, for the kernel's source bundle.
+    pub fn from_jobspec(spec: jobspec::JobSpec, job_yaml: String, program: impl Into<Vec<u8>>) -> Self {
+        Self {
+            name: spec.metadata.name,
+            program: program.into(),
+            job_yaml,
+            target: spec.spec.target,
+            priority: spec.spec.priority,
+            compiler_options: spec.spec.compiler_options,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnqueuedJob {
+    pub job_id: String,
+    /// Initial state without the `TASK_STATE_` prefix, e.g. `PENDING`.
+    pub state: String,
+    pub created_at: Option<prost_types::Timestamp>,
+}
+
+/// Enqueues `submission` on the kernel at `addr`.
+///
+/// Request and trace ids are derived from the name, program and JobSpec bytes, so
+/// resubmitting the same job is deduplicated by the kernel.
+pub fn enqueue(addr: &str, submission: &KernelSubmission) -> Result<EnqueuedJob, GrpcLikeError> {
+    let request = authorized(enqueue_request(submission))?;
+    block_on_result(async move {
+        let resp = connect(addr).await?.enqueue_job(request).await.map_err(map_status_error)?.into_inner();
+        let state = proto::TaskState::try_from(resp.state)
+            .ok()
+            .filter(|state| *state != proto::TaskState::Unspecified)
+            .unwrap_or(proto::TaskState::Pending);
+        Ok(EnqueuedJob {
+            job_id: resp.job_id,
+            state: state.as_str_name().trim_start_matches("TASK_STATE_").to_string(),
+            created_at: resp.created_at,
+        })
+    })
+}
+
+/// `GetJobStatus` on the kernel, mapped onto the public `JobStatus` so `eigen status`
+/// renders kernel and System API answers alike.
+pub fn get_job_status(addr: &str, job_id: &str) -> Result<api::JobStatus, GrpcLikeError> {
+    let request = authorized(proto::GetJobStatusRequest {
+        job_id: job_id.to_string(),
+        ..Default::default()
+    })?;
+    block_on_result(async move {
+        let resp = connect(addr).await?.get_job_status(request).await.map_err(map_status_error)?.into_inner();
+        Ok(api::JobStatus {
+            job_id: resp.job_id,
+            state: public_state(resp.state),
+            stage: resp.stage,
+            progress: resp.progress,
+            message: resp.message,
+            updated_at: resp.updated_at,
+            error_code: resp.error_code,
+            error_summary: resp.error_summary,
+            error_details_ref: resp.error_details_ref,
+            ..Default::default()
+        })
+    })
+}
+
+/// `GetJobResults` on the kernel. The QFS result reference is exposed as the
+/// `qfs_result_ref` metadata entry, like the System API does.
+pub fn get_job_results(addr: &str, job_id: &str) -> Result<JobResultsView, GrpcLikeError> {
+    let request = authorized(proto::GetJobResultsRequest {
+        job_id: job_id.to_string(),
+        ..Default::default()
+    })?;
+    block_on_result(async move {
+        let resp = connect(addr).await?.get_job_results(request).await.map_err(map_status_error)?.into_inner();
+        let mut metadata: BTreeMap<String, String> = resp.metadata.into_iter().collect();
+        if !resp.qfs_result_ref.is_empty() {
+            metadata.insert("qfs_result_ref".to_string(), resp.qfs_result_ref);
+        }
+        let (summary, metadata) = jobspec::split_result_summary(metadata);
+        Ok(JobResultsView {
+            job_id: resp.job_id,
+            state: jobspec::map_job_state(public_state(resp.state)),
+            counts: resp.counts.into_iter().collect(),
+            summary,
+            metadata,
+            error_code: Some(resp.error_code).filter(|code| !code.is_empty()),
+            error_summary: Some(resp.error_summary).filter(|summary| !summary.is_empty()),
+            error_details_ref: Some(resp.error_details_ref).filter(|details| !details.is_empty()),
+        })
+    })
+}
+
+/// `CancelJob` on the kernel; returns whether the cancellation was accepted.
+pub fn cancel_job(addr: &str, job_id: &str) -> Result<bool, GrpcLikeError> {
+    let request = authorized(proto::CancelJobRequest {
+        job_id: job_id.to_string(),
+        ..Default::default()
+    })?;
+    block_on_result(async move {
+        Ok(connect(addr).await?.cancel_job(request).await.map_err(map_status_error)?.into_inner().accepted)
+    })
+}
+
+/// One `ListJobs` page; `page_size` 0 lets the kernel pick its default.
+pub fn list_jobs_page(
+    addr: &str,
+    state_filter: &str,
+    page_token: &str,
+    page_size: i32,
+) -> Result<proto::ListJobsResponse, GrpcLikeError> {
+    let request = authorized(proto::ListJobsRequest {
+        state_filter: state_filter.to_string(),
+        page_token: page_token.to_string(),
+        page_size,
+    })?;
+    block_on_result(async move {
+        Ok(connect(addr).await?.list_jobs(request).await.map_err(map_status_error)?.into_inner())
+    })
+}
+
+/// Kernel `TaskState` -> public `JobState`; `OPTIMIZING` is reported as `COMPILING`.
+fn public_state(state: i32) -> i32 {
+    let state = match proto::TaskState::try_from(state).unwrap_or(proto::TaskState::Unspecified) {
+        proto::TaskState::Unspecified => api::JobState::Unspecified,
+        proto::TaskState::Pending => api::JobState::Pending,
+        proto::TaskState::Compiling | proto::TaskState::Optimizing => api::JobState::Compiling,
+        // The public API has no paused state; a paused job is still waiting to run.
+        proto::TaskState::Queued | proto::TaskState::Paused => api::JobState::Queued,
+        proto::TaskState::Running => api::JobState::Running,
+        proto::TaskState::Done => api::JobState::Done,
+        proto::TaskState::Error => api::JobState::Error,
+        proto::TaskState::Cancelled => api::JobState::Cancelled,
+        proto::TaskState::Timeout => api::JobState::Timeout,
+    };
+    state as i32
+}
+
+/// Wraps `message` in a request carrying the configured bearer token, if any.
+fn authorized<T>(message: T) -> Result<Request<T>, GrpcLikeError> {
+    let mut request = Request::new(message);
+    if let Some(token) = &config::current().token {
+        let value = format!("Bearer {}", token.trim()).parse().map_err(|_| GrpcLikeError {
+            code: GrpcCode::InvalidArgument,
+            message: "the configured token is not a valid header value".to_string(),
+            retry_hint: None,
+        })?;
+        request.metadata_mut().insert("authorization", value);
+    }
+    Ok(request)
+}
+
+async fn connect(
+    addr: &str,
+) -> Result<proto::kernel_gateway_service_client::KernelGatewayServiceClient<Channel>, GrpcLikeError> {
+    let tls_ca_cert = config::current().tls_ca_cert.as_deref();
+    // A bare host:port means https once a CA is configured.
+    let url = match tls_ca_cert {
+        Some(_) if !addr.contains("://") => format!("https://{}", addr.trim()),
+        _ => jobspec::normalize_endpoint(addr),
+    };
+    let mut endpoint = Endpoint::from_shared(url).map_err(|e| GrpcLikeError {
+        code: GrpcCode::InvalidArgument,
+        message: format!("invalid kernel address {addr}: {e}"),
+        retry_hint: None,
+    })?;
+    if let Some(timeout) = config::current().timeout {
+        endpoint = endpoint.connect_timeout(timeout).timeout(timeout);
+    }
+    if let Some(path) = tls_ca_cert {
+        let pem = std::fs::read(path).map_err(|err| GrpcLikeError {
+            code: GrpcCode::InvalidArgument,
+            message: format!("failed to read CA certificate {}: {err}", path.display()),
+            retry_hint: None,
+        })?;
+        endpoint = endpoint
+            .tls_config(ClientTlsConfig::new().ca_certificate(Certificate::from_pem(pem)))
+            .map_err(|err| GrpcLikeError {
+                code: GrpcCode::InvalidArgument,
+                message: format!("invalid TLS configuration: {err}"),
+                retry_hint: None,
+            })?;
+    }
+    let channel = endpoint.connect().await.map_err(|err| GrpcLikeError {
+        code: GrpcCode::Unavailable,
+        message: format!("cannot reach kernel at {addr}: {}", error_chain(&err)),
+        retry_hint: Some(format!("start eigen-kernel or point --endpoint / {KERNEL_ADDR_ENV} at a running kernel")),
+    })?;
+    Ok(proto::kernel_gateway_service_client::KernelGatewayServiceClient::new(channel))
+}
+
+fn enqueue_request(submission: &KernelSubmission) -> proto::EnqueueJobRequest {
+    let mut seed = submission.name.as_bytes().to_vec();
+    seed.push(0);
+    seed.extend_from_slice(&submission.program);
+    seed.push(0);
+    seed.extend_from_slice(submission.job_yaml.as_bytes());
+    let digest = jobspec::sha256_hex(&seed);
+    proto::EnqueueJobRequest {
+        metadata: Some(proto::RequestMetadata {
+            contract_version: "1.0.0".to_string(),
+            request_id: format!("req_{}", &digest[..16]),
+            traceparent: format!("00-{}-{}-01", &digest[..32], &digest[32..48]),
+            tenant_id: DEFAULT_TENANT_ID.to_string(),
+            project_id: DEFAULT_PROJECT_ID.to_string(),
+            source_service: SOURCE_SERVICE.to_string(),
+            ..Default::default()
+        }),
+        name: submission.name.clone(),
+        program: submission.program.clone(),
+        program_format: PROGRAM_FORMAT.to_string(),
+        target: submission.target.clone(),
+        priority: submission.priority,
+        compiler_options: submission.compiler_options.clone().into_iter().collect(),
+        job_yaml: submission.job_yaml.clone(),
+        ..Default::default()
+    }
+}
+
+/// `err` followed by its sources, so "transport error" still says "Connection refused".
+fn error_chain(err: &dyn std::error::Error) -> String {
+    let mut out = err.to_string();
+    let mut source = err.source();
+    while let Some(cause) = source {
+        out.push_str(": ");
+        out.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    out
+}
@@ -1,11 +1,21 @@
 //! Eigen CLI - MVP.
 
+mod cli;
+mod commands;
+mod config;
 mod jobspec;
+mod kernel;
+mod output;
 
 use std::collections::{BTreeMap, BTreeSet};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
+use clap::Parser;
+
+use cli::{BenchmarkCommand, BenchmarkCompareArgs, BenchmarkRunArgs, Command, PluginCommand, VisualizeArgs};
+use output::Printer;
+
 const EXIT_USER_ERROR: i32 = 2;
 const EXIT_NETWORK_ERROR: i32 = 3;
 const EXIT_SERVER_ERROR: i32 = 4;
@@ -24,72 +34,48 @@ const BENCHMARK_COMPARISON_VERSION: &str = "1.0.0";
 
 fn main() {
     let args: Vec<String> = std::env::args().collect();
-
-    if args.len() <= 1 {
-        print_help();
-        std::process::exit(EXIT_USER_ERROR);
+    let cli = cli::Cli::try_parse_from(&args).unwrap_or_else(|err| cli::exit_on_parse_error(err, &args));
+    let printer = cli.global.printer(cli.format);
+    match config::CliConfig::resolve(&(&cli.global).into()) {
+        Ok(resolved) => config::install(resolved),
+        // A broken config file must not lock users out of `eigen config init --force`.
+        Err(err) if matches!(cli.command, Command::Config(_)) => eprintln!("warning: {err}"),
+        Err(err) => {
+            printer.print_failure("eigen", jobspec::GrpcCode::InvalidArgument.as_str(), &err);
+            std::process::exit(EXIT_USER_ERROR);
+        }
     }
 
-    match args[1].as_str() {
-        "help" | "--help" | "-h" => print_help(),
-        "version" | "--version" | "-V" => println!("eigen-cli {CLI_VERSION}"),
-        "plugin" => {
-            if let Err(err) = run_plugin(&args[2..]) {
-                eprintln!("plugin failed: {err}");
-                std::process::exit(EXIT_USER_ERROR);
-            }
-        }
-        "benchmark" => {
-            if let Err(err) = run_benchmark(&args[2..]) {
-                eprintln!("benchmark failed: {err}");
-                std::process::exit(EXIT_USER_ERROR);
-            }
-        }
-        "submit" => {
-            if let Err(err) = run_submit(&args[2..]) {
-                eprintln!("submit failed: {err}");
-                std::process::exit(EXIT_USER_ERROR);
-            }
-        }
-        "status" => {
-            if let Err(code) = run_status(&args[2..]) {
-                std::process::exit(code);
-            }
-        }
-        "watch" => {
-            if let Err(code) = run_watch(&args[2..]) {
-                std::process::exit(code);
-            }
-        }
-        "results" | "result" => {
-            if let Err(code) = run_results(&args[2..]) {
-                std::process::exit(code);
-            }
-        }
-        "explain" => {
-            if let Err(code) = run_explain(&args[2..]) {
-                std::process::exit(code);
-            }
-        }
-        "compile" => {
-            if let Err(err) = run_compile(&args[2..]) {
-                eprintln!("compile failed: {err}");
-                std::process::exit(EXIT_USER_ERROR);
-            }
-        }
-        "visualize" => {
-            if let Err(err) = run_visualize(&args[2..]) {
-                eprintln!("visualize failed: {err}");
-                std::process::exit(EXIT_USER_ERROR);
-            }
-        }
-        cmd => {
-            eprintln!("Command '{cmd}' is not implemented. Use 'eigen help'.");
-            std::process::exit(1);
+    let global = &cli.global;
+    let outcome = match cli.command {
+        Command::Version => {
+            println!("eigen-cli {CLI_VERSION}");
+            Ok(())
         }
+        Command::Submit(args) => commands::submit::run(args, global, &printer),
+        Command::Status(args) => commands::status::run(args, global, &printer),
+        Command::List(args) => commands::list::run(args, global, &printer),
+        Command::Cancel(args) => commands::cancel::run(args, global, &printer),
+        Command::Config(command) => commands::config::run(command, &printer),
+        Command::Watch(args) => run_watch(&args.job_id, &printer),
+        Command::Result(args) => commands::result::run(args, global, &printer),
+        Command::Logs(args) => commands::logs::run(args, global, &printer),
+        Command::Explain(args) => run_explain(&args.job_id, &printer),
+        Command::Compile(args) => commands::compile::run(args, global, &printer),
+        Command::Visualize(args) => run_visualize(&args).map_err(|err| invalid_argument(&printer, "visualize", &err)),
+        Command::Benchmark(command) => run_benchmark(command).map_err(|err| invalid_argument(&printer, "benchmark", &err)),
+        Command::Plugin(command) => run_plugin(command).map_err(|err| invalid_argument(&printer, "plugin", &err)),
+    };
+    if let Err(code) = outcome {
+        std::process::exit(code);
     }
 }
 
+fn invalid_argument(printer: &Printer, cmd: &str, message: &str) -> i32 {
+    printer.print_failure(cmd, jobspec::GrpcCode::InvalidArgument.as_str(), message);
+    EXIT_USER_ERROR
+}
+
 #[derive(Debug, Clone, PartialEq)]
 struct BenchmarkRunSnapshot {
     contract_version: String,
@@ -114,34 +100,18 @@ struct BenchmarkMetricComparison {
     regression: bool,
 }
 
-fn run_plugin(args: &[String]) -> Result<(), String> {
-    let Some(subcommand) = args.first() else {
-        return Err(
-            "usage: eigen plugin <scaffold|validate|package|activate> [args...]".to_string(),
-        );
-    };
-    match subcommand.as_str() {
-        "scaffold" => run_plugin_scaffold(&args[1..]),
-        "validate" => run_plugin_validate(&args[1..]),
-        "package" => run_plugin_package(&args[1..]),
-        "activate" => run_plugin_activate(&args[1..]),
-        other => Err(format!(
-            "unknown plugin subcommand: {other}. expected scaffold|validate|package|activate"
-        )),
+fn run_plugin(command: PluginCommand) -> Result<(), String> {
+    match command {
+        PluginCommand::Scaffold { plugin_dir, plugin_type } => run_plugin_scaffold(&plugin_dir, &plugin_type),
+        PluginCommand::Validate { manifest } => run_plugin_validate(&manifest),
+        PluginCommand::Package { plugin_dir, output_file } => run_plugin_package(&plugin_dir, &output_file),
+        PluginCommand::Activate { manifests } => run_plugin_activate(&manifests),
     }
 }
 
-fn run_plugin_scaffold(args: &[String]) -> Result<(), String> {
-    if args.len() != 2 {
-        return Err(
-            "usage: eigen plugin scaffold <plugin_dir> <driver|compiler_backend|optimizer|policy>"
-                .to_string(),
-        );
-    }
-    let plugin_dir = PathBuf::from(&args[0]);
-    let plugin_type = &args[1];
+fn run_plugin_scaffold(plugin_dir: &Path, plugin_type: &str) -> Result<(), String> {
     validate_plugin_type(plugin_type)?;
-    std::fs::create_dir_all(&plugin_dir)
+    std::fs::create_dir_all(plugin_dir)
         .map_err(|e| format!("failed to create {}: {e}", plugin_dir.display()))?;
     let plugin_id = format!(
         "io.eigen.{}",
@@ -180,23 +150,15 @@ fn run_plugin_scaffold(args: &[String]) -> Result<(), String> {
     Ok(())
 }
 
-fn run_plugin_validate(args: &[String]) -> Result<(), String> {
-    if args.len() != 1 {
-        return Err("usage: eigen plugin validate <plugin.toml>".to_string());
-    }
+fn run_plugin_validate(manifest: &Path) -> Result<(), String> {
     let content =
-        std::fs::read_to_string(&args[0]).map_err(|e| format!("failed to read manifest: {e}"))?;
+        std::fs::read_to_string(manifest).map_err(|e| format!("failed to read manifest: {e}"))?;
     validate_plugin_manifest(&content)?;
     println!("manifest valid");
     Ok(())
 }
 
-fn run_plugin_package(args: &[String]) -> Result<(), String> {
-    if args.len() != 2 {
-        return Err("usage: eigen plugin package <plugin_dir> <output_file>".to_string());
-    }
-    let plugin_dir = PathBuf::from(&args[0]);
-    let output = PathBuf::from(&args[1]);
+fn run_plugin_package(plugin_dir: &Path, output: &Path) -> Result<(), String> {
     let manifest_path = plugin_dir.join("plugin.toml");
     let manifest = std::fs::read_to_string(&manifest_path)
         .map_err(|e| format!("failed to read manifest: {e}"))?;
@@ -205,7 +167,7 @@ fn run_plugin_package(args: &[String]) -> Result<(), String> {
         "{{\"format_version\":\"1.0.0\",\"manifest\":\"{}\"}}\n",
         json_escape(&manifest.replace('\n', "\\n"))
     );
-    std::fs::write(&output, payload).map_err(|e| format!("failed to write package: {e}"))?;
+    std::fs::write(output, payload).map_err(|e| format!("failed to write package: {e}"))?;
     println!("packaged plugin: {}", output.display());
     Ok(())
 }
@@ -230,14 +192,12 @@ struct PluginRecord {
     reason: Option<String>,
 }
 
-fn run_plugin_activate(args: &[String]) -> Result<(), String> {
-    if args.is_empty() {
-        return Err("usage: eigen plugin activate <plugin.toml> [plugin.toml ...]".to_string());
-    }
-    let manifests = args
+fn run_plugin_activate(paths: &[PathBuf]) -> Result<(), String> {
+    let manifests = paths
         .iter()
         .map(|path| {
-            std::fs::read_to_string(path).map_err(|e| format!("failed to read {path}: {e}"))
+            std::fs::read_to_string(path)
+                .map_err(|e| format!("failed to read {}: {e}", path.display()))
         })
         .collect::<Result<Vec<_>, _>>()?;
     let records = activate_plugins(&manifests);
@@ -595,156 +555,40 @@ fn extract_toml_u64(doc: &str, key: &str) -> Option<u64> {
     doc[start..end].trim().parse().ok()
 }
 
-fn run_benchmark(args: &[String]) -> Result<(), String> {
-    let Some(subcommand) = args.first() else {
-        return Err("usage: eigen benchmark <run|compare> [args...]".to_string());
-    };
-    match subcommand.as_str() {
-        "run" => run_benchmark_run(&args[1..]),
-        "compare" => run_benchmark_compare(&args[1..]),
-        other => Err(format!(
-            "unknown benchmark subcommand: {other}. expected run|compare"
-        )),
+fn run_benchmark(command: BenchmarkCommand) -> Result<(), String> {
+    match command {
+        BenchmarkCommand::Run(args) => run_benchmark_run(&args),
+        BenchmarkCommand::Compare(args) => run_benchmark_compare(&args),
     }
 }
 
-fn run_benchmark_run(args: &[String]) -> Result<(), String> {
-    let mut config_path: Option<PathBuf> = None;
-    let mut output_mode = "human".to_string();
-    let mut output_file: Option<PathBuf> = None;
-    let mut i = 0;
-    while i < args.len() {
-        match args[i].as_str() {
-            "--config" => {
-                let Some(next) = args.get(i + 1) else {
-                    return Err("expected value after --config".to_string());
-                };
-                config_path = Some(PathBuf::from(next));
-                i += 2;
-            }
-            "--output" => {
-                let Some(next) = args.get(i + 1) else {
-                    return Err("expected value after --output".to_string());
-                };
-                output_mode = next.clone();
-                i += 2;
-            }
-            "--output-file" => {
-                let Some(next) = args.get(i + 1) else {
-                    return Err("expected value after --output-file".to_string());
-                };
-                output_file = Some(PathBuf::from(next));
-                i += 2;
-            }
-            unknown => return Err(format!("unknown benchmark run argument: {unknown}")),
-        }
-    }
-
-    let Some(config_path) = config_path else {
-        return Err(
-            "usage: eigen benchmark run --config benchmark-run.json [--output json|human] [--output-file path]"
-                .to_string(),
-        );
-    };
-    let snapshot = parse_benchmark_run_config(&config_path)?;
+fn run_benchmark_run(args: &BenchmarkRunArgs) -> Result<(), String> {
+    let snapshot = parse_benchmark_run_config(&args.config)?;
     let json = benchmark_run_snapshot_json(&snapshot);
-    if let Some(path) = output_file {
-        std::fs::write(&path, format!("{json}\n"))
+    if let Some(path) = &args.output.output_file {
+        std::fs::write(path, format!("{json}\n"))
             .map_err(|e| format!("failed to write {}: {e}", path.display()))?;
     }
-    render_benchmark_run_output(&snapshot, &json, &output_mode)
+    render_benchmark_run_output(&snapshot, &json, &args.output.mode)
 }
 
-fn run_benchmark_compare(args: &[String]) -> Result<(), String> {
-    let mut baseline_path: Option<PathBuf> = None;
-    let mut candidate_path: Option<PathBuf> = None;
-    let mut output_mode = "human".to_string();
-    let mut output_file: Option<PathBuf> = None;
-    let mut i = 0;
-    while i < args.len() {
-        match args[i].as_str() {
-            "--baseline" => {
-                let Some(next) = args.get(i + 1) else {
-                    return Err("expected value after --baseline".to_string());
-                };
-                baseline_path = Some(PathBuf::from(next));
-                i += 2;
-            }
-            "--candidate" => {
-                let Some(next) = args.get(i + 1) else {
-                    return Err("expected value after --candidate".to_string());
-                };
-                candidate_path = Some(PathBuf::from(next));
-                i += 2;
-            }
-            "--output" => {
-                let Some(next) = args.get(i + 1) else {
-                    return Err("expected value after --output".to_string());
-                };
-                output_mode = next.clone();
-                i += 2;
-            }
-            "--output-file" => {
-                let Some(next) = args.get(i + 1) else {
-                    return Err("expected value after --output-file".to_string());
-                };
-                output_file = Some(PathBuf::from(next));
-                i += 2;
-            }
-            unknown => return Err(format!("unknown benchmark compare argument: {unknown}")),
-        }
-    }
-    let Some(baseline_path) = baseline_path else {
-        return Err(
-            "usage: eigen benchmark compare --baseline baseline.json --candidate candidate.json [--output json|human] [--output-file path]"
-                .to_string(),
-        );
-    };
-    let Some(candidate_path) = candidate_path else {
-        return Err(
-            "usage: eigen benchmark compare --baseline baseline.json --candidate candidate.json [--output json|human] [--output-file path]"
-                .to_string(),
-        );
-    };
-    let baseline = parse_benchmark_snapshot_file(&baseline_path)?;
-    let candidate = parse_benchmark_snapshot_file(&candidate_path)?;
+fn run_benchmark_compare(args: &BenchmarkCompareArgs) -> Result<(), String> {
+    let baseline = parse_benchmark_snapshot_file(&args.baseline)?;
+    let candidate = parse_benchmark_snapshot_file(&args.candidate)?;
     let comparisons = compare_snapshots(&baseline, &candidate);
     let json = benchmark_comparison_json(&baseline, &candidate, &comparisons);
-    if let Some(path) = output_file {
-        std::fs::write(&path, format!("{json}\n"))
+    if let Some(path) = &args.output.output_file {
+        std::fs::write(path, format!("{json}\n"))
             .map_err(|e| format!("failed to write {}: {e}", path.display()))?;
     }
-    render_benchmark_compare_output(&baseline, &candidate, &comparisons, &json, &output_mode)
-}
-
-fn parse_job_id_arg(args: &[String], usage: &str) -> Result<String, i32> {
-    if args.len() != 1 {
-        eprintln!("usage: {usage}");
-        return Err(EXIT_USER_ERROR);
-    }
-    Ok(args[0].clone())
+    render_benchmark_compare_output(&baseline, &candidate, &comparisons, &json, &args.output.mode)
 }
 
-fn run_status(args: &[String]) -> Result<(), i32> {
-    let job_id = parse_job_id_arg(args, "eigen status <job_id>")?;
-    match jobspec::get_job_status_from_system_api(&job_id) {
-        Ok(status) => {
-            render_status_output(&status);
-            match terminal_exit_code(&status.state) {
-                Some(0) | None => Ok(()),
-                Some(code) => Err(code),
-            }
-        }
-        Err(err) => Err(print_grpc_like_error("status", &err)),
-    }
-}
+fn run_watch(job_id: &str, printer: &Printer) -> Result<(), i32> {
+    let updates = jobspec::stream_job_updates_from_system_api(job_id)
+        .map_err(|err| printer.print_error("watch", &err))?;
 
-fn run_watch(args: &[String]) -> Result<(), i32> {
-    let job_id = parse_job_id_arg(args, "eigen watch <job_id>")?;
-    let updates = jobspec::stream_job_updates_from_system_api(&job_id)
-        .map_err(|err| print_grpc_like_error("watch", &err))?;
-
-    render_title("watch", Some(&job_id));
+    render_title("watch", Some(job_id));
     let mut last_state: Option<String> = None;
     for update in updates {
         if should_render_live() {
@@ -760,29 +604,13 @@ fn run_watch(args: &[String]) -> Result<(), i32> {
     }
 }
 
-fn run_results(args: &[String]) -> Result<(), i32> {
-    let job_id = parse_job_id_arg(args, "eigen results <job_id>")?;
-    match jobspec::get_job_results_from_system_api(&job_id) {
-        Ok(results) => {
-            render_results_output(&results);
-
-            if results.state != "DONE" {
-                return Err(EXIT_SERVER_ERROR);
-            }
-            Ok(())
-        }
-        Err(err) => Err(print_grpc_like_error("results", &err)),
-    }
-}
-
-fn run_explain(args: &[String]) -> Result<(), i32> {
-    let job_id = parse_job_id_arg(args, "eigen explain <job_id>")?;
-    match jobspec::get_dispatch_rationale_from_system_api(&job_id) {
+fn run_explain(job_id: &str, printer: &Printer) -> Result<(), i32> {
+    match jobspec::get_dispatch_rationale_from_system_api(job_id) {
         Ok(rationale) => {
-            render_explain_output(&job_id, &rationale);
+            render_explain_output(job_id, &rationale);
             Ok(())
         }
-        Err(err) => Err(print_grpc_like_error("explain", &err)),
+        Err(err) => Err(printer.print_error("explain", &err)),
     }
 }
 
@@ -870,63 +698,6 @@ fn pretty_json_like(input: &str) -> String {
     out.trim().to_string()
 }
 
-fn print_key_value(indent: usize, key: &str, value: &str) {
-    let pad = " ".repeat(indent);
-    let rendered = pretty_json_like(value);
-    if rendered.contains('\n') {
-        println!("{pad}{key}:");
-        print_indented_lines(indent + 2, &rendered);
-    } else {
-        println!("{pad}{key}: {rendered}");
-    }
-}
-
-fn render_submit_output(
-    job_id: &str,
-    req: &jobspec::SubmitJobRequest,
-    envelope: &jobspec::PublicSubmitEnvelope,
-    payload: &str,
-) {
-    render_title("submit", Some("job accepted"));
-    println!("  job_id: {job_id}");
-    println!("  request:");
-    println!("    name: {}", req.name);
-    println!("    kind: {}", req.workload.kind);
-    println!("    execution_profile: {}", req.workload.execution_profile);
-    println!("    target: {}", req.target);
-    println!("    priority: {}", req.priority);
-    let entrypoint = match &req.program {
-        jobspec::ProgramSource::EigenLangSource { entrypoint, .. } => entrypoint,
-    };
-    println!("    entrypoint: {entrypoint}");
-    println!(
-        "    digest: {}",
-        jobspec::canonical_jobspec_digest_from_request(req, &req.jobspec_api_version)
-    );
-    println!("  envelope:");
-    println!("    contract_version: {}", envelope.contract_version);
-    println!("    request_id: {}", envelope.request_id);
-    println!("    idempotency_key: {}", envelope.idempotency_key);
-    println!("    traceparent: {}", envelope.traceparent);
-    println!("    tenant/project: {}/{}", envelope.tenant_id, envelope.project_id);
-    println!("    client_version: {}", envelope.client_version);
-    println!("  request_payload:");
-    print_indented_lines(4, &pretty_json_like(payload));
-    println!("  next:");
-    println!("    eigen status {job_id}");
-    println!("    eigen watch {job_id}");
-    println!("    eigen results {job_id}  # after completion");
-}
-
-fn render_status_output(status: &jobspec::JobStatusView) {
-    render_title("status", Some(&status.job_id));
-    println!("  job_id: {}", status.job_id);
-    println!("  state: {}", format_state_label(&status.state));
-    println!("  stage: {}", status.stage);
-    println!("  progress: {:.1}%", f64::from(status.progress) * 100.0);
-    println!("  message: {}", status.message);
-}
-
 fn render_watch_update(last_state: Option<&str>, update: &jobspec::JobUpdateView) {
     let transition = last_state
         .map(|prev| format!("{prev} → {}", update.state))
@@ -942,31 +713,6 @@ fn render_watch_update(last_state: Option<&str>, update: &jobspec::JobUpdateView
     );
 }
 
-fn render_results_output(results: &jobspec::JobResultsView) {
-    render_title("results", Some(&results.job_id));
-    println!("  job_id: {}", results.job_id);
-    println!("  state: {}", format_state_label(&results.state));
-    if !results.summary.is_empty() {
-        println!("  summary:");
-        for (k, v) in &results.summary {
-            print_key_value(4, k, v);
-        }
-    }
-    println!("  counts:");
-    for (k, v) in &results.counts {
-        println!("    {k}: {v}");
-    }
-    println!("  metadata:");
-    for (k, v) in &results.metadata {
-        print_key_value(4, k, v);
-    }
-    if results.state != "DONE" {
-        println!("  error:");
-        println!("    error_code: {}", results.error_code.as_deref().unwrap_or_default());
-        println!("    error_summary: {}", results.error_summary.as_deref().unwrap_or_default());
-    }
-}
-
 fn render_explain_output(job_id: &str, rationale: &jobspec::DispatchRationaleView) {
     render_title("explain", Some(job_id));
     println!("  job_id: {job_id}");
@@ -1012,146 +758,9 @@ fn terminal_exit_code(state: &str) -> Option<i32> {
     }
 }
 
-fn print_grpc_like_error(cmd: &str, err: &jobspec::GrpcLikeError) -> i32 {
-    eprintln!(
-        "{cmd} failed: grpc code={} message={}",
-        err.code.as_str(),
-        err.message
-    );
-    if let Some(hint) = &err.retry_hint {
-        eprintln!("retry_hint: {hint}");
-    }
-
-    match err.code {
-        jobspec::GrpcCode::Unavailable | jobspec::GrpcCode::DeadlineExceeded => EXIT_NETWORK_ERROR,
-        jobspec::GrpcCode::InvalidArgument
-        | jobspec::GrpcCode::NotFound
-        | jobspec::GrpcCode::FailedPrecondition => EXIT_USER_ERROR,
-        jobspec::GrpcCode::Internal => EXIT_SERVER_ERROR,
-    }
-}
-
-fn run_submit(args: &[String]) -> Result<(), String> {
-    let mut job_file: Option<PathBuf> = None;
-    let mut options = jobspec::PublicSubmitOptions::default();
-    let mut i = 0;
-    while i < args.len() {
-        match args[i].as_str() {
-            "-f" | "--file" | "--job" => {
-                let Some(next) = args.get(i + 1) else {
-                    return Err("expected path after -f/--file/--job".to_string());
-                };
-                job_file = Some(PathBuf::from(next));
-                i += 2;
-            }
-            "--request-id" => {
-                let Some(next) = args.get(i + 1) else {
-                    return Err("expected value after --request-id".to_string());
-                };
-                options.request_id = Some(next.clone());
-                i += 2;
-            }
-            "--idempotency-key" => {
-                let Some(next) = args.get(i + 1) else {
-                    return Err("expected value after --idempotency-key".to_string());
-                };
-                options.idempotency_key = Some(next.clone());
-                i += 2;
-            }
-            "--traceparent" => {
-                let Some(next) = args.get(i + 1) else {
-                    return Err("expected value after --traceparent".to_string());
-                };
-                options.traceparent = Some(next.clone());
-                i += 2;
-            }
-            "--tenant" | "--tenant-id" => {
-                let Some(next) = args.get(i + 1) else {
-                    return Err("expected value after --tenant/--tenant-id".to_string());
-                };
-                options.tenant_id = Some(next.clone());
-                i += 2;
-            }
-            "--project" | "--project-id" => {
-                let Some(next) = args.get(i + 1) else {
-                    return Err("expected value after --project/--project-id".to_string());
-                };
-                options.project_id = Some(next.clone());
-                i += 2;
-            }
-            unknown => return Err(format!("unknown submit argument: {unknown}")),
-        }
-    }
-
-    let Some(job_file) = job_file else {
-        return Err(
-            "usage: eigen submit -f job.yaml [--idempotency-key key] [--traceparent value]"
-                .to_string(),
-        );
-    };
-
-    let req = jobspec::build_submit_request_from_job_file(&job_file).map_err(|e| e.to_string())?;
-    let public_payload = jobspec::build_public_submit_payload_json(&req, &options);
-    let envelope = jobspec::normalized_public_submit_envelope(&req, &options);
-    let response = jobspec::submit_job_to_system_api(&req, &options).map_err(|e| e.to_string())?;
-
-    render_submit_output(&response.job_id, &req, &envelope, &public_payload);
-    Ok(())
-}
-
-fn run_compile(args: &[String]) -> Result<(), String> {
-    let mut job_file: Option<PathBuf> = None;
-    let mut out_file: PathBuf = PathBuf::from("circuit.aqo.json");
-    let mut i = 0;
-    while i < args.len() {
-        match args[i].as_str() {
-            "-f" | "--file" | "--job" => {
-                let Some(next) = args.get(i + 1) else {
-                    return Err("expected path after -f/--file/--job".to_string());
-                };
-                job_file = Some(PathBuf::from(next));
-                i += 2;
-            }
-            "-o" | "--out" => {
-                let Some(next) = args.get(i + 1) else {
-                    return Err("expected path after -o/--out".to_string());
-                };
-                out_file = PathBuf::from(next);
-                i += 2;
-            }
-            unknown => return Err(format!("unknown compile argument: {unknown}")),
-        }
-    }
-    let Some(job_path) = job_file else {
-        return Err("usage: eigen compile -f job.yaml --out circuit.aqo.json".to_string());
-    };
-    let aqo_json = jobspec::compile_job_to_aqo_json(&job_path).map_err(|e| e.to_string())?;
-    std::fs::write(&out_file, aqo_json)
-        .map_err(|e| format!("failed to write {}: {e}", out_file.display()))?;
-    println!("compiled_aqo: {}", out_file.display());
-    Ok(())
-}
-
-fn run_visualize(args: &[String]) -> Result<(), String> {
-    let mut aqo_file: Option<PathBuf> = None;
-    let mut i = 0;
-    while i < args.len() {
-        match args[i].as_str() {
-            "-f" | "--file" | "--aqo" => {
-                let Some(next) = args.get(i + 1) else {
-                    return Err("expected path after -f/--file/--aqo".to_string());
-                };
-                aqo_file = Some(PathBuf::from(next));
-                i += 2;
-            }
-            unknown => return Err(format!("unknown visualize argument: {unknown}")),
-        }
-    }
-    let Some(aqo_path) = aqo_file else {
-        return Err("usage: eigen visualize -f circuit.aqo.json".to_string());
-    };
-    let aqo_json = std::fs::read_to_string(&aqo_path)
-        .map_err(|e| format!("failed to read {}: {e}", aqo_path.display()))?;
+fn run_visualize(args: &VisualizeArgs) -> Result<(), String> {
+    let aqo_json = std::fs::read_to_string(&args.aqo_file)
+        .map_err(|e| format!("failed to read {}: {e}", args.aqo_file.display()))?;
     println!("{}", jobspec::visualize_aqo_json(&aqo_json));
     Ok(())
 }
@@ -1469,13 +1078,6 @@ fn format_seed_timestamp(seed: u64) -> String {
     format!("2026-01-{day:02}T{hour:02}:{minute:02}:{second:02}Z")
 }
 
-fn print_help() {
-    println!(
-        "Eigen CLI\n\nUsage:\n  eigen <command> [args...]\n\nCommands:\n  help        Show this message\n  version     Print version\n  submit      Submit job: eigen submit -f job.yaml [--idempotency-key key] [--traceparent value]\n  status      Get job status: eigen status <job_id>\n  watch       Stream progress: eigen watch <job_id>\n  results     Fetch results: eigen results <job_id>\n  explain     Dispatch rationale: eigen explain <job_id>\n  compile     Compile locally: eigen compile -f job.yaml --out circuit.aqo.json\n  visualize   Visualize AQO: eigen visualize -f circuit.aqo.json\n  benchmark   Run/compare benchmark snapshots
-  plugin      Scaffold/validate/package/activate plugin artifacts\n\nBenchmark examples (reproducible):\n  eigen benchmark run --config bench.json --output json --output-file baseline.json\n  eigen benchmark run --config bench-candidate.json --output json --output-file candidate.json\n  eigen benchmark compare --baseline baseline.json --candidate candidate.json --output human\n"
-    );
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
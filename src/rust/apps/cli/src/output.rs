@@ -0,0 +1,165 @@
+//! How job commands talk to the terminal: tables or JSON on stdout, errors on
+//! stderr as plain text or a `{"error": ..., "code": ...}` envelope.
+
+use std::io::{self, Write};
+
+use serde_json::{json, Value};
+
+use crate::jobspec::{GrpcCode, GrpcLikeError};
+use crate::{EXIT_NETWORK_ERROR, EXIT_SERVER_ERROR, EXIT_USER_ERROR};
+
+/// Value of `--format`, either global (`eigen --format json <command>`) or per command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub(crate) enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+/// Prints command output and errors in one [`OutputFormat`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) struct Printer {
+    format: OutputFormat,
+    /// `--quiet`: skip notes and confirmations, keep results and errors.
+    quiet: bool,
+}
+
+impl Printer {
+    pub(crate) fn new(format: OutputFormat) -> Self {
+        Self { format, quiet: false }
+    }
+
+    pub(crate) fn with_quiet(self, quiet: bool) -> Self {
+        Self { quiet, ..self }
+    }
+
+    pub(crate) fn is_quiet(&self) -> bool {
+        self.quiet
+    }
+
+    pub(crate) fn format(&self) -> OutputFormat {
+        self.format
+    }
+
+    /// A command's own `--format` wins over the global one.
+    pub(crate) fn with_format(self, format: Option<OutputFormat>) -> Self {
+        format.map_or(self, |format| Self { format, ..self })
+    }
+
+    pub(crate) fn is_json(&self) -> bool {
+        self.format == OutputFormat::Json
+    }
+
+    pub(crate) fn print_table(&self, header: &str, rows: impl IntoIterator<Item = String>) {
+        println!("{header}");
+        for row in rows {
+            println!("{row}");
+        }
+    }
+
+    pub(crate) fn print_json(&self, value: &Value) {
+        println!("{value:#}");
+    }
+
+    /// Prints an informational line to stderr unless `--quiet` is set.
+    pub(crate) fn print_note(&self, message: &str) {
+        if !self.quiet {
+            eprintln!("{message}");
+        }
+    }
+
+    /// Reports a failed RPC and returns the exit code for its status.
+    pub(crate) fn print_error(&self, cmd: &str, err: &GrpcLikeError) -> i32 {
+        // Nothing useful is left to do if stderr itself is gone.
+        let _ = self.write_error(&mut io::stderr().lock(), cmd, err);
+        exit_code(err.code)
+    }
+
+    /// Reports a failure that did not come from an RPC; `code` is the gRPC status
+    /// name used in the JSON envelope.
+    pub(crate) fn print_failure(&self, cmd: &str, code: &str, message: &str) {
+        let _ = self.write_failure(&mut io::stderr().lock(), cmd, code, message);
+    }
+
+    /// Reports arguments that parse but do not fit together, pointing at the
+    /// command's `--help` in text mode, and returns [`EXIT_USER_ERROR`].
+    pub(crate) fn print_usage_error(&self, cmd: &str, message: &str) -> i32 {
+        self.print_failure(cmd, GrpcCode::InvalidArgument.as_str(), message);
+        if !self.is_json() {
+            eprintln!("see 'eigen {cmd} --help'");
+        }
+        EXIT_USER_ERROR
+    }
+
+    fn write_error(&self, out: &mut impl Write, cmd: &str, err: &GrpcLikeError) -> io::Result<()> {
+        if self.is_json() {
+            return self.write_failure(out, cmd, err.code.as_str(), &err.message);
+        }
+        writeln!(out, "{cmd} failed: grpc code={} message={}", err.code.as_str(), err.message)?;
+        if let Some(hint) = &err.retry_hint {
+            writeln!(out, "retry_hint: {hint}")?;
+        }
+        Ok(())
+    }
+
+    fn write_failure(&self, out: &mut impl Write, cmd: &str, code: &str, message: &str) -> io::Result<()> {
+        match self.format {
+            OutputFormat::Text => writeln!(out, "{cmd} failed: {message}"),
+            OutputFormat::Json => writeln!(out, "{}", json!({ "error": message, "code": code })),
+        }
+    }
+}
+
+fn exit_code(code: GrpcCode) -> i32 {
+    match code {
+        GrpcCode::Unavailable | GrpcCode::DeadlineExceeded => EXIT_NETWORK_ERROR,
+        GrpcCode::InvalidArgument | GrpcCode::NotFound | GrpcCode::FailedPrecondition => EXIT_USER_ERROR,
+        GrpcCode::Internal => EXIT_SERVER_ERROR,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn not_found() -> GrpcLikeError {
+        GrpcLikeError {
+            code: GrpcCode::NotFound,
+            message: "job job-missing not found".to_string(),
+            retry_hint: Some("check the job id".to_string()),
+        }
+    }
+
+    fn stderr_of(printer: Printer, err: &GrpcLikeError) -> String {
+        let mut stderr = Vec::new();
+        printer.write_error(&mut stderr, "status", err).expect("write error");
+        String::from_utf8(stderr).expect("utf-8")
+    }
+
+    #[test]
+    fn json_errors_are_a_single_envelope_line() {
+        let stderr = stderr_of(Printer::new(OutputFormat::Json), &not_found());
+        assert_eq!(stderr.lines().count(), 1);
+        let envelope: Value = serde_json::from_str(&stderr).expect("stderr is JSON");
+        assert_eq!(envelope, json!({ "error": "job job-missing not found", "code": "NOT_FOUND" }));
+    }
+
+    #[test]
+    fn text_errors_keep_code_message_and_hint() {
+        let stderr = stderr_of(Printer::default(), &not_found());
+        assert_eq!(
+            stderr,
+            "status failed: grpc code=NOT_FOUND message=job job-missing not found\nretry_hint: check the job id\n"
+        );
+        assert_eq!(Printer::default().print_error("status", &not_found()), EXIT_USER_ERROR);
+    }
+
+    #[test]
+    fn command_format_overrides_the_global_one_and_keeps_quiet() {
+        let printer = Printer::new(OutputFormat::Json).with_quiet(true);
+        assert_eq!(printer.with_format(None), printer);
+        let text = printer.with_format(Some(OutputFormat::Text));
+        assert_eq!(text.format(), OutputFormat::Text);
+        assert!(text.is_quiet());
+    }
+}
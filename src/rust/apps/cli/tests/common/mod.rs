@@ -0,0 +1,36 @@
+//! Helpers shared by the CLI integration tests.
+
+// Each test binary uses its own subset of the helpers.
+#![allow(dead_code)]
+
+use std::net::SocketAddr;
+use std::process::Command;
+
+/// Starts a kernel on an ephemeral port in a background thread.
+pub fn spawn_kernel() -> SocketAddr {
+    let (addr_tx, addr_rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        let rt = tokio::runtime::Runtime::new().expect("kernel runtime");
+        rt.block_on(async move {
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.expect("bind kernel port");
+            addr_tx.send(listener.local_addr().expect("kernel addr")).expect("send kernel addr");
+            eigen_kernel::rpc::serve_with_listener(listener, None, eigen_kernel::auth::AuthInterceptor::disabled())
+                .await
+                .expect("serve kernel");
+        });
+    });
+    addr_rx.recv().expect("receive kernel addr")
+}
+
+/// `eigen` with `args`, ignoring any kernel address, endpoint, token or config file
+/// from the environment.
+pub fn eigen(args: &[&str]) -> Command {
+    let mut cmd = Command::new(env!("CARGO_BIN_EXE_eigen"));
+    cmd.args(args)
+        .env_remove("EIGEN_KERNEL_ADDR")
+        .env_remove("EIGEN_ENDPOINT")
+        .env_remove("EIGEN_KERNEL_AUTH_TOKEN")
+        .env_remove("EIGEN_TOKEN")
+        .env("EIGEN_CONFIG", std::env::temp_dir().join("eigen-cli-it-no-config.toml"));
+    cmd
+}
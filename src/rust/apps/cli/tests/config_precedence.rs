@@ -0,0 +1,47 @@
+//! `eigen config show` resolves the config file, then the environment, then flags.
+
+mod common;
+
+use common::eigen;
+
+fn shown_server_addr(cmd: &mut std::process::Command) -> String {
+    let output = cmd.output().expect("run eigen");
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let shown: serde_json::Value = serde_json::from_slice(&output.stdout).expect("stdout is JSON");
+    shown["server_addr"].as_str().expect("server_addr").to_string()
+}
+
+#[test]
+fn flag_beats_env_which_beats_the_config_file() {
+    let path = std::env::temp_dir().join(format!("eigen-cli-it-config-{}.toml", std::process::id()));
+    std::fs::write(&path, "server_addr = \"file-kernel:50052\"\n").expect("write config");
+    let path = path.to_str().unwrap();
+
+    let addr = shown_server_addr(eigen(&["--format", "json", "config", "show"]).env("EIGEN_CONFIG", path));
+    assert_eq!(addr, "file-kernel:50052");
+
+    let addr = shown_server_addr(
+        eigen(&["--format", "json", "config", "show"])
+            .env("EIGEN_CONFIG", path)
+            .env("EIGEN_KERNEL_ADDR", "env-kernel:50052"),
+    );
+    assert_eq!(addr, "env-kernel:50052");
+
+    let addr = shown_server_addr(
+        eigen(&["--format", "json", "config", "show"])
+            .env("EIGEN_CONFIG", path)
+            .env("EIGEN_KERNEL_ADDR", "env-kernel:50052")
+            .env("EIGEN_ENDPOINT", "endpoint-kernel:50052"),
+    );
+    assert_eq!(addr, "endpoint-kernel:50052");
+
+    let addr = shown_server_addr(
+        eigen(&["--endpoint", "flag-kernel:50052", "--format", "json", "config", "show"])
+            .env("EIGEN_CONFIG", path)
+            .env("EIGEN_KERNEL_ADDR", "env-kernel:50052")
+            .env("EIGEN_ENDPOINT", "endpoint-kernel:50052"),
+    );
+    assert_eq!(addr, "flag-kernel:50052");
+
+    let _ = std::fs::remove_file(path);
+}
@@ -0,0 +1,44 @@
+//! `eigen --help` and `eigen <command> --help` match `tests/snapshots/help/<command>.txt`.
+//!
+//! Run with `UPDATE_SNAPSHOTS=1` to rewrite the snapshots after changing the CLI.
+
+mod common;
+
+use std::path::PathBuf;
+
+use common::eigen;
+
+const COMMANDS: &[&str] = &[
+    "version", "submit", "status", "list", "cancel", "config", "watch", "result", "logs", "explain", "compile",
+    "visualize", "benchmark", "plugin",
+];
+
+/// Compares the help for `args` with the snapshot `name`, returning a description of
+/// the difference.
+fn compare(name: &str, args: &[&str]) -> Option<String> {
+    let output = eigen(args).output().expect("run eigen");
+    assert!(output.status.success(), "eigen {args:?}: {}", String::from_utf8_lossy(&output.stderr));
+    let actual = String::from_utf8(output.stdout).expect("help is UTF-8");
+
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/snapshots/help").join(format!("{name}.txt"));
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        std::fs::create_dir_all(path.parent().unwrap()).expect("snapshot dir");
+        std::fs::write(&path, &actual).expect("write snapshot");
+        return None;
+    }
+    let expected = std::fs::read_to_string(&path).unwrap_or_default();
+    (actual != expected).then(|| format!("{}:\n--- expected\n{expected}\n--- actual\n{actual}", path.display()))
+}
+
+#[test]
+fn root_help_matches_snapshot() {
+    if let Some(diff) = compare("eigen", &["--help"]) {
+        panic!("help changed; rerun with UPDATE_SNAPSHOTS=1 if intended\n{diff}");
+    }
+}
+
+#[test]
+fn subcommand_help_matches_snapshots() {
+    let diffs: Vec<String> = COMMANDS.iter().filter_map(|command| compare(command, &[command, "--help"])).collect();
+    assert!(diffs.is_empty(), "help changed; rerun with UPDATE_SNAPSHOTS=1 if intended\n{}", diffs.join("\n"));
+}
@@ -0,0 +1,60 @@
+//! `eigen --format json` reports failures as a `{"error", "code"}` envelope on stderr.
+
+mod common;
+
+use std::net::TcpListener;
+use std::process::Output;
+
+use common::{eigen, spawn_kernel};
+
+fn stderr_envelope(output: &Output) -> serde_json::Value {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(stderr.lines().count(), 1, "expected one envelope line, got {stderr:?}");
+    serde_json::from_str(&stderr).expect("stderr is a JSON object")
+}
+
+#[test]
+fn unknown_job_is_reported_as_not_found_envelope() {
+    let addr = spawn_kernel().to_string();
+
+    let output = eigen(&["--format", "json", "status", "job-missing", "--endpoint", &addr])
+        .output()
+        .expect("run eigen");
+    assert_eq!(output.status.code(), Some(4));
+    assert!(output.stdout.is_empty(), "{}", String::from_utf8_lossy(&output.stdout));
+    assert_eq!(
+        stderr_envelope(&output),
+        serde_json::json!({ "error": "job job-missing not found", "code": "NOT_FOUND" })
+    );
+
+    // The command's own --format wins over the one given before it.
+    let output = eigen(&["--format", "json", "status", "job-missing", "--format", "text", "--endpoint", &addr])
+        .output()
+        .expect("run eigen");
+    assert_eq!(output.status.code(), Some(4));
+    assert_eq!(String::from_utf8_lossy(&output.stderr).trim(), "status failed: job job-missing not found");
+}
+
+#[test]
+fn unreachable_kernel_and_bad_arguments_use_the_envelope() {
+    // Bind and drop a listener so the port is very likely closed.
+    let addr = TcpListener::bind("127.0.0.1:0").and_then(|l| l.local_addr()).expect("free port").to_string();
+
+    let output = eigen(&["--format", "json", "result", "job-1", "--endpoint", &addr])
+        .output()
+        .expect("run eigen");
+    assert_eq!(output.status.code(), Some(3));
+    let envelope = stderr_envelope(&output);
+    assert_eq!(envelope["code"], "UNAVAILABLE");
+    assert!(envelope["error"].as_str().is_some_and(|msg| msg.contains(&addr)), "{envelope}");
+
+    let output = eigen(&["--format", "json", "list", "--limit", "0"]).output().expect("run eigen");
+    assert_eq!(output.status.code(), Some(2));
+    assert_eq!(stderr_envelope(&output)["code"], "INVALID_ARGUMENT");
+
+    let output = eigen(&["stauts", "job-1", "--json"]).output().expect("run eigen");
+    assert_eq!(output.status.code(), Some(2));
+    let envelope = stderr_envelope(&output);
+    assert_eq!(envelope["code"], "INVALID_ARGUMENT");
+    assert!(envelope["error"].as_str().is_some_and(|msg| msg.contains("did you mean 'status'")), "{envelope}");
+}
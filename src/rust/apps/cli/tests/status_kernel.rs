@@ -0,0 +1,68 @@
+//! `eigen status` in text and JSON against an in-process kernel.
+
+mod common;
+
+use std::process::Output;
+
+use common::{eigen, spawn_kernel};
+
+fn stdout_of(output: &Output) -> String {
+    assert!(output.status.success(), "eigen failed: {}", String::from_utf8_lossy(&output.stderr));
+    String::from_utf8(output.stdout.clone()).expect("utf-8 stdout")
+}
+
+/// Stdout of `eigen status`, which exits non-zero when the job ended badly; the
+/// fixture kernel decides how the job ends, so only a terminal state is required.
+fn status_stdout(output: &Output) -> String {
+    assert!(matches!(output.status.code(), Some(0 | 4)), "eigen failed: {}", String::from_utf8_lossy(&output.stderr));
+    String::from_utf8(output.stdout.clone()).expect("utf-8 stdout")
+}
+
+/// Submits a trivial program to the kernel at `addr` and returns its job id.
+fn submit(addr: &str, name: &str) -> String {
+    let program = std::env::temp_dir().join(format!("eigen-cli-it-{}-{name}.eigen.py", std::process::id()));
+    std::fs::write(&program, "@quantum\ndef main():\n    pass\n").expect("write program");
+    let output = eigen(&["submit", "--program", program.to_str().unwrap(), "--name", name, "--endpoint", addr, "--json"])
+        .output()
+        .expect("run eigen");
+    let response: serde_json::Value = serde_json::from_str(&stdout_of(&output)).expect("stdout is a JSON object");
+    response["job_id"].as_str().expect("job_id").to_string()
+}
+
+#[test]
+fn status_prints_a_table_row_in_text_format() {
+    let addr = spawn_kernel().to_string();
+    let job_id = submit(&addr, "status-text");
+
+    let output = eigen(&["status", &job_id, "--wait", "--timeout", "30s", "--endpoint", &addr, "--format", "text"])
+        .output()
+        .expect("run eigen");
+    let stdout = status_stdout(&output);
+    let mut lines = stdout.lines();
+    let header: Vec<&str> = lines.next().expect("header").split_whitespace().collect();
+    assert_eq!(header, ["JOB_ID", "STATE", "STAGE", "PROGRESS%", "UPDATED"]);
+    let row: Vec<&str> = lines.next().expect("row").split_whitespace().collect();
+    assert_eq!(row[0], job_id);
+    assert!(["DONE", "ERROR", "CANCELLED", "TIMEOUT"].contains(&row[1]), "{stdout}");
+    let progress: f64 = row[3].parse().expect("progress percentage");
+    assert!((0.0..=100.0).contains(&progress) && row[3].contains('.'), "{stdout}");
+    if row[1] == "DONE" {
+        assert_eq!(row[3], "100.0");
+    }
+    assert!(row[4].ends_with('Z'), "{stdout}");
+}
+
+#[test]
+fn status_prints_the_raw_response_in_json_format() {
+    let addr = spawn_kernel().to_string();
+    let job_id = submit(&addr, "status-json");
+
+    let output = eigen(&["status", &job_id, "--wait", "--timeout", "30s", "--endpoint", &addr, "--format", "json"])
+        .output()
+        .expect("run eigen");
+    let status: serde_json::Value = serde_json::from_str(&status_stdout(&output)).expect("stdout is a JSON object");
+    assert_eq!(status["job_id"], job_id.as_str());
+    assert!(status["state"].as_str().is_some_and(|state| state.starts_with("JOB_STATE_")), "{status}");
+    assert!(status["progress"].as_f64().is_some_and(|progress| (0.0..=1.0).contains(&progress)), "{status}");
+    assert!(status["updated_at"]["seconds"].as_i64().is_some_and(|seconds| seconds > 0), "{status}");
+}
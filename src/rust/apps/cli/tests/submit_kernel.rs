@@ -0,0 +1,90 @@
+//! End-to-end `eigen submit --program` against an in-process kernel.
+
+mod common;
+
+use std::path::PathBuf;
+use std::process::Output;
+
+use common::{eigen, spawn_kernel};
+
+/// Writes `job.yaml` and `program.eigen.py` for a job named `name`.
+fn write_job(name: &str) -> (PathBuf, PathBuf) {
+    let dir = std::env::temp_dir().join(format!("eigen-cli-it-{}-{name}", std::process::id()));
+    std::fs::create_dir_all(&dir).expect("temp dir");
+    let job = dir.join("job.yaml");
+    let program = dir.join("program.eigen.py");
+    std::fs::write(
+        &job,
+        format!("apiVersion: eigen.os/v0.1\nkind: QuantumJob\nmetadata:\n  name: {name}\nspec:\n  target: sim:local\n"),
+    )
+    .expect("write job.yaml");
+    std::fs::write(&program, "@quantum\ndef main():\n    pass\n").expect("write program");
+    (job, program)
+}
+
+fn stdout_json(output: &Output) -> serde_json::Value {
+    assert!(output.status.success(), "eigen failed: {}", String::from_utf8_lossy(&output.stderr));
+    serde_json::from_slice(&output.stdout).expect("stdout is a JSON object")
+}
+
+#[test]
+fn submit_enqueues_job_file_and_program_on_kernel() {
+    let addr = spawn_kernel().to_string();
+    let name = format!("bell-{}", std::process::id());
+    let (job, program) = write_job(&name);
+
+    let output = eigen(&[
+        "submit",
+        "--file",
+        job.to_str().unwrap(),
+        "--program",
+        program.to_str().unwrap(),
+        "--endpoint",
+        &addr,
+        "--json",
+    ])
+    .output()
+    .expect("run eigen");
+    let response = stdout_json(&output);
+    assert!(response["job_id"].as_str().is_some_and(|id| id.starts_with("job-")), "{response}");
+    assert_eq!(response["state"], "PENDING");
+
+    // EIGEN_KERNEL_ADDR stands in for --endpoint; text output names the job.
+    let output = eigen(&["submit", "--program", program.to_str().unwrap(), "--name", &format!("{name}-env")])
+        .env("EIGEN_KERNEL_ADDR", &addr)
+        .output()
+        .expect("run eigen");
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("job_id: job-"), "{stdout}");
+    assert!(stdout.contains(&format!("name: {name}-env")), "{stdout}");
+}
+
+#[test]
+fn submit_reports_grpc_errors_on_stderr() {
+    let addr = spawn_kernel().to_string();
+    let (job, program) = write_job("empty-program");
+    std::fs::write(&program, "").expect("truncate program");
+
+    let output = eigen(&["submit", "--file", job.to_str().unwrap(), "--program", program.to_str().unwrap(), "--endpoint", &addr])
+        .output()
+        .expect("run eigen");
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("grpc code=INVALID_ARGUMENT"), "{stderr}");
+    assert!(stderr.contains("program is required"), "{stderr}");
+
+    let unreachable = std::net::TcpListener::bind("127.0.0.1:0")
+        .and_then(|listener| listener.local_addr())
+        .expect("reserve port")
+        .to_string();
+    let output = eigen(&["submit", "--program", program.to_str().unwrap(), "--endpoint", &unreachable, "--json"])
+        .output()
+        .expect("run eigen");
+    assert_eq!(output.status.code(), Some(3));
+    assert!(output.stdout.is_empty());
+    // --json applies to errors too.
+    let envelope: serde_json::Value = serde_json::from_slice(&output.stderr).expect("stderr is a JSON object");
+    assert_eq!(envelope["code"], "UNAVAILABLE");
+    assert!(envelope["error"].as_str().is_some_and(|msg| msg.contains("cannot reach kernel")), "{envelope}");
+}
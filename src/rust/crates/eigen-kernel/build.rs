@@ -26,9 +26,15 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .collect();
 
     let proto_root_str = proto_root.to_string_lossy().to_string();
+    let out_dir = PathBuf::from(env::var("OUT_DIR")?);
+
+    for proto in &proto_paths {
+        println!("cargo:rerun-if-changed={proto}");
+    }
 
     tonic_prost_build::configure()
         .build_server(true)
+        .file_descriptor_set_path(out_dir.join("descriptor.bin"))
         .compile_protos(&proto_paths, &[proto_root_str])?;
 
     Ok(())
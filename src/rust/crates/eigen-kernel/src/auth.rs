@@ -0,0 +1,533 @@
+//! API-key and bearer-token authentication for the KernelGateway gRPC service.
+//!
+//! Callers present either an `x-eigen-api-key` header holding one of the keys from
+//! `EIGEN_KERNEL_API_KEYS` / `EIGEN_KERNEL_API_KEYS_FILE`, or `authorization: Bearer
+//! <token>`. The token is `EIGEN_KERNEL_AUTH_TOKEN`, or with `EIGEN_AUTH_ENABLED=true`
+//! an HS256 JWT whose claims handlers read with [`extract_claims`]. With none of
+//! these configured the kernel runs in dev mode and accepts every call.
+//!
+//! Every accepted call has a [`Caller`]: the JWT's claims, or for API keys and the
+//! shared secret a [`ServiceCaller`] naming the credential, so jobs can be scoped to
+//! whoever submitted them. Only dev-mode calls are [`Caller::Anonymous`].
+//!
+//! With `EIGEN_KERNEL_RBAC_POLICY_FILE` set, JWT callers are further limited to
+//! what their role is granted (see [`crate::rbac`]). With `EIGEN_KERNEL_POLICY_FILE`
+//! set, the job RPCs also check JWT callers against that [`PolicyEngine`], which
+//! can limit them to jobs of their tenant or jobs they submitted.
+
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use security_module::{
+    ApiKeySet, AuthError, Claims, JwtAuthConfig, JwtConfigError, JwtValidator, PolicyEngine, PolicyError, RbacError,
+    RbacPolicy, StaticTokenValidator, TokenValidator, bearer_token,
+};
+use sha2::{Digest, Sha256};
+use tonic::service::Interceptor;
+use tonic::{Request, Status};
+
+use crate::rbac::{RBAC_POLICY_FILE_ENV, RbacLayer};
+
+/// Shared secret callers must present as `authorization: Bearer <token>`.
+pub const AUTH_TOKEN_ENV: &str = "EIGEN_KERNEL_AUTH_TOKEN";
+/// Comma-separated API keys accepted in the [`API_KEY_HEADER`] header.
+pub const API_KEYS_ENV: &str = "EIGEN_KERNEL_API_KEYS";
+/// File of API keys, one per line (`#` starts a comment line); combined with
+/// [`API_KEYS_ENV`].
+pub const API_KEYS_FILE_ENV: &str = "EIGEN_KERNEL_API_KEYS_FILE";
+/// Set to `true` to accept unauthenticated calls even when keys are configured
+/// (local dev and compose only).
+pub const AUTH_DISABLED_ENV: &str = "EIGEN_KERNEL_AUTH_DISABLED";
+/// Set to `true` to require JWT bearer tokens configured by the `EIGEN_AUTH_JWT_*`
+/// variables (see [`JwtAuthConfig`]); they replace [`AUTH_TOKEN_ENV`].
+pub const AUTH_ENABLED_ENV: &str = "EIGEN_AUTH_ENABLED";
+/// Path of the TOML [`PolicyEngine`] rules the job RPCs check JWT callers against.
+pub const POLICY_FILE_ENV: &str = "EIGEN_KERNEL_POLICY_FILE";
+/// Metadata header carrying the caller's API key.
+pub const API_KEY_HEADER: &str = "x-eigen-api-key";
+/// Subject of callers presenting the shared bearer secret.
+pub const SHARED_SECRET_SUBJECT: &str = "shared-secret";
+
+/// Why the auth configuration could not be loaded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuthConfigError {
+    KeysFile { path: PathBuf, message: String },
+    Jwt(JwtConfigError),
+    Rbac(RbacError),
+    Policy(PolicyError),
+}
+
+impl fmt::Display for AuthConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::KeysFile { path, message } => {
+                write!(f, "cannot read {API_KEYS_FILE_ENV} file {}: {message}", path.display())
+            }
+            Self::Jwt(err) => write!(f, "invalid JWT configuration: {err}"),
+            Self::Rbac(err) => write!(f, "{RBAC_POLICY_FILE_ENV}: {err}"),
+            Self::Policy(err) => write!(f, "{POLICY_FILE_ENV}: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for AuthConfigError {}
+
+/// Credentials the KernelGateway accepts.
+#[derive(Clone, Default)]
+pub struct AuthConfig {
+    pub api_keys: ApiKeySet,
+    /// Shared secret accepted as `authorization: Bearer <token>`.
+    pub bearer_token: Option<String>,
+    /// Verifies bearer tokens as JWTs instead of comparing them to `bearer_token`.
+    pub jwt: Option<JwtValidator>,
+    /// Role permissions JWT callers are checked against.
+    pub rbac: Option<Arc<RbacPolicy>>,
+    /// Rules the job RPCs check JWT callers against.
+    pub policy: Option<Arc<PolicyEngine>>,
+    /// Accept every call regardless of the credentials above.
+    pub disabled: bool,
+}
+
+// Keep the bearer secret out of logs; `ApiKeySet` already hides its keys.
+impl fmt::Debug for AuthConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AuthConfig")
+            .field("api_keys", &self.api_keys)
+            .field("bearer_token", &self.bearer_token.as_ref().map(|_| "<redacted>"))
+            .field("jwt", &self.jwt)
+            .field("rbac", &self.rbac)
+            .field("policy", &self.policy)
+            .field("disabled", &self.disabled)
+            .finish()
+    }
+}
+
+impl AuthConfig {
+    /// Reads [`API_KEYS_ENV`], [`API_KEYS_FILE_ENV`], [`AUTH_TOKEN_ENV`], [`AUTH_ENABLED_ENV`]
+    /// with the JWT settings, [`RBAC_POLICY_FILE_ENV`], [`POLICY_FILE_ENV`] and
+    /// [`AUTH_DISABLED_ENV`]. Fails when the keys or a policy file cannot be read or JWT
+    /// authentication is enabled without a usable secret.
+    pub fn from_env() -> Result<Self, AuthConfigError> {
+        Self::from_vars(|key| std::env::var(key).ok())
+    }
+
+    fn from_vars(env: impl Fn(&str) -> Option<String>) -> Result<Self, AuthConfigError> {
+        let mut api_keys = env(API_KEYS_ENV).map(|list| ApiKeySet::parse_list(&list)).unwrap_or_default();
+        if let Some(path) = env(API_KEYS_FILE_ENV).filter(|path| !path.trim().is_empty()) {
+            let path = PathBuf::from(path.trim());
+            let contents = std::fs::read_to_string(&path)
+                .map_err(|err| AuthConfigError::KeysFile { path: path.clone(), message: err.to_string() })?;
+            api_keys.extend(ApiKeySet::parse_file(&contents));
+        }
+        let jwt = if env(AUTH_ENABLED_ENV).is_some_and(|value| is_truthy(&value)) {
+            let config = JwtAuthConfig::from_vars(&env);
+            let secret = env(&config.secret_env_var).unwrap_or_default();
+            Some(JwtValidator::hs256(&config, &secret).map_err(AuthConfigError::Jwt)?)
+        } else {
+            None
+        };
+        let rbac = match env(RBAC_POLICY_FILE_ENV).filter(|path| !path.trim().is_empty()) {
+            Some(path) => Some(Arc::new(RbacPolicy::load(path.trim()).map_err(AuthConfigError::Rbac)?)),
+            None => None,
+        };
+        let policy = match env(POLICY_FILE_ENV).filter(|path| !path.trim().is_empty()) {
+            Some(path) => Some(Arc::new(PolicyEngine::load(path.trim()).map_err(AuthConfigError::Policy)?)),
+            None => None,
+        };
+        Ok(Self {
+            api_keys,
+            bearer_token: env(AUTH_TOKEN_ENV).filter(|token| !token.trim().is_empty()),
+            jwt,
+            rbac,
+            policy,
+            disabled: env(AUTH_DISABLED_ENV).is_some_and(|value| is_truthy(&value)),
+        })
+    }
+}
+
+fn is_truthy(value: &str) -> bool {
+    matches!(value.trim().to_ascii_lowercase().as_str(), "1" | "true" | "yes")
+}
+
+/// Claims of the JWT the call was authenticated with; `None` for API-key, shared
+/// secret and unauthenticated calls.
+pub fn extract_claims<T>(request: &Request<T>) -> Option<Claims> {
+    request.extensions().get::<Claims>().cloned()
+}
+
+/// Who the call was authenticated as.
+pub fn extract_caller<T>(request: &Request<T>) -> Caller {
+    if let Some(claims) = extract_claims(request) {
+        return Caller::Jwt(claims);
+    }
+    match request.extensions().get::<ServiceCaller>() {
+        Some(service) => Caller::Service(service.clone()),
+        None => Caller::Anonymous,
+    }
+}
+
+/// Identity of a caller authenticated by an API key or the shared secret, which
+/// carry no claims. Stored in the request extensions by [`AuthInterceptor`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServiceCaller {
+    /// `api-key:` and a digest of the key, or [`SHARED_SECRET_SUBJECT`].
+    pub subject: String,
+}
+
+impl ServiceCaller {
+    /// The caller presenting `key`. The subject holds a digest, never the key itself.
+    pub fn for_api_key(key: &str) -> Self {
+        let digest = Sha256::digest(key.as_bytes());
+        let hex: String = digest[..8].iter().map(|byte| format!("{byte:02x}")).collect();
+        Self { subject: format!("api-key:{hex}") }
+    }
+
+    pub fn shared_secret() -> Self {
+        Self { subject: SHARED_SECRET_SUBJECT.to_string() }
+    }
+}
+
+/// Who a KernelGateway call comes from.
+#[derive(Debug, Clone)]
+pub enum Caller {
+    /// Authenticated with a JWT.
+    Jwt(Claims),
+    /// Authenticated with an API key or the shared secret.
+    Service(ServiceCaller),
+    /// Dev mode: authentication is disabled.
+    Anonymous,
+}
+
+impl Caller {
+    /// Claims of a JWT caller.
+    pub fn claims(&self) -> Option<&Claims> {
+        match self {
+            Caller::Jwt(claims) => Some(claims),
+            Caller::Service(_) | Caller::Anonymous => None,
+        }
+    }
+}
+
+/// Rejects calls without a valid API key or bearer token with `Unauthenticated`.
+///
+/// A call carrying [`API_KEY_HEADER`] is judged by that key alone; otherwise the
+/// bearer token is checked. Claims of an accepted JWT are stored in the request
+/// extensions for [`extract_claims`] and for the [`RbacLayer`] of [`AuthInterceptor::rbac_layer`];
+/// other accepted calls get a [`ServiceCaller`] there instead.
+#[derive(Clone, Default)]
+pub struct AuthInterceptor {
+    validator: Option<Arc<dyn TokenValidator>>,
+    api_keys: Option<Arc<dyn TokenValidator>>,
+    rbac: Option<Arc<RbacPolicy>>,
+    policy: Option<Arc<PolicyEngine>>,
+}
+
+impl AuthInterceptor {
+    pub fn new(validator: Arc<dyn TokenValidator>) -> Self {
+        Self { validator: Some(validator), ..Self::default() }
+    }
+
+    /// Accepts calls presenting one of `keys` in [`API_KEY_HEADER`].
+    pub fn with_api_keys(keys: ApiKeySet) -> Self {
+        Self { api_keys: Some(Arc::new(keys)), ..Self::default() }
+    }
+
+    /// Limits JWT callers to what `policy` grants their role.
+    pub fn with_rbac(mut self, policy: RbacPolicy) -> Self {
+        self.rbac = Some(Arc::new(policy));
+        self
+    }
+
+    /// Role checks to install beneath this interceptor; they pass every call when
+    /// no policy is configured.
+    pub fn rbac_layer(&self) -> RbacLayer {
+        RbacLayer::new(self.rbac.clone())
+    }
+
+    /// Checks JWT callers of the job RPCs against `policy`.
+    pub fn with_policy(mut self, policy: PolicyEngine) -> Self {
+        self.policy = Some(Arc::new(policy));
+        self
+    }
+
+    /// Rules the job RPCs check JWT callers against, when configured.
+    pub fn policy(&self) -> Option<Arc<PolicyEngine>> {
+        self.policy.clone()
+    }
+
+    /// Accepts every call.
+    pub fn disabled() -> Self {
+        Self::default()
+    }
+
+    /// Builds the interceptor for `config`. Without any key or token configured it
+    /// accepts every call and logs an error saying so.
+    pub fn from_config(config: &AuthConfig) -> Self {
+        if config.disabled {
+            tracing::warn!("kernel gRPC authentication is disabled");
+            return Self::disabled();
+        }
+        let validator = match &config.jwt {
+            Some(jwt) => Some(Arc::new(jwt.clone()) as Arc<dyn TokenValidator>),
+            None => config
+                .bearer_token
+                .as_ref()
+                .and_then(|token| StaticTokenValidator::new(token.as_str()).ok())
+                .map(|validator| Arc::new(validator) as Arc<dyn TokenValidator>),
+        };
+        let api_keys = (!config.api_keys.is_empty())
+            .then(|| Arc::new(config.api_keys.clone()) as Arc<dyn TokenValidator>);
+        let interceptor = Self { validator, api_keys, rbac: config.rbac.clone(), policy: config.policy.clone() };
+        if !interceptor.is_enabled() {
+            tracing::error!(
+                "DEV MODE: no {API_KEYS_ENV}, {API_KEYS_FILE_ENV}, {AUTH_TOKEN_ENV} or {AUTH_ENABLED_ENV} configured; \
+                 the kernel gRPC API accepts every call unauthenticated"
+            );
+        }
+        interceptor
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.validator.is_some() || self.api_keys.is_some()
+    }
+
+    fn authenticate(&self, request: &Request<()>) -> Result<Caller, AuthError> {
+        let metadata = request.metadata();
+        if let Some(key) = metadata.get(API_KEY_HEADER) {
+            let key = key.to_str().map_err(|_| AuthError::InvalidApiKey)?;
+            return match &self.api_keys {
+                Some(keys) => keys.validate(key).map(|()| Caller::Service(ServiceCaller::for_api_key(key))),
+                None => Err(AuthError::InvalidApiKey),
+            };
+        }
+        match &self.validator {
+            Some(validator) => {
+                let token = metadata
+                    .get("authorization")
+                    .and_then(|value| value.to_str().ok())
+                    .and_then(bearer_token)
+                    .ok_or(AuthError::MissingToken)?;
+                Ok(match validator.claims(token)? {
+                    Some(claims) => Caller::Jwt(claims),
+                    None => Caller::Service(ServiceCaller::shared_secret()),
+                })
+            }
+            None => Err(AuthError::MissingApiKey),
+        }
+    }
+}
+
+impl Interceptor for AuthInterceptor {
+    fn call(&mut self, mut request: Request<()>) -> Result<Request<()>, Status> {
+        if !self.is_enabled() {
+            return Ok(request);
+        }
+        match self.authenticate(&request) {
+            Ok(caller) => {
+                match caller {
+                    Caller::Jwt(claims) => {
+                        request.extensions_mut().insert(claims);
+                    }
+                    Caller::Service(service) => {
+                        request.extensions_mut().insert(service);
+                    }
+                    Caller::Anonymous => {}
+                }
+                Ok(request)
+            }
+            Err(err) => Err(Status::unauthenticated(err.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tonic::Code;
+
+    fn interceptor() -> AuthInterceptor {
+        AuthInterceptor::new(Arc::new(StaticTokenValidator::new("kernel-secret").expect("validator")))
+    }
+
+    fn api_key_interceptor() -> AuthInterceptor {
+        AuthInterceptor::with_api_keys(ApiKeySet::parse_list("key-ci,key-ops"))
+    }
+
+    fn request_with_api_key(key: &str) -> Request<()> {
+        let mut request = Request::new(());
+        request.metadata_mut().insert(API_KEY_HEADER, key.parse().expect("metadata value"));
+        request
+    }
+
+    fn env_of(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: Vec<(String, String)> = vars.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        move |key| vars.iter().find(|(k, _)| k == key).map(|(_, v)| v.clone())
+    }
+
+    fn request_with(authorization: Option<&str>) -> Request<()> {
+        let mut request = Request::new(());
+        if let Some(value) = authorization {
+            request
+                .metadata_mut()
+                .insert("authorization", value.parse().expect("metadata value"));
+        }
+        request
+    }
+
+    #[test]
+    fn valid_bearer_token_is_accepted() {
+        assert!(interceptor().call(request_with(Some("Bearer kernel-secret"))).is_ok());
+    }
+
+    #[test]
+    fn missing_token_is_unauthenticated() {
+        let err = interceptor().call(request_with(None)).expect_err("missing token");
+        assert_eq!(err.code(), Code::Unauthenticated);
+        assert_eq!(err.message(), "missing bearer token");
+
+        let err = interceptor()
+            .call(request_with(Some("Basic kernel-secret")))
+            .expect_err("non-bearer scheme");
+        assert_eq!(err.code(), Code::Unauthenticated);
+    }
+
+    #[test]
+    fn wrong_token_is_unauthenticated() {
+        let err = interceptor()
+            .call(request_with(Some("Bearer not-the-secret")))
+            .expect_err("wrong token");
+        assert_eq!(err.code(), Code::Unauthenticated);
+        assert_eq!(err.message(), "invalid bearer token");
+    }
+
+    #[test]
+    fn disabled_interceptor_accepts_anonymous_calls() {
+        let mut disabled = AuthInterceptor::disabled();
+        assert!(!disabled.is_enabled());
+        let request = disabled.call(request_with(None)).expect("anonymous call");
+        assert!(matches!(extract_caller(&request), Caller::Anonymous));
+    }
+
+    #[test]
+    fn valid_api_key_is_accepted() {
+        assert!(api_key_interceptor().call(request_with_api_key("key-ops")).is_ok());
+    }
+
+    #[test]
+    fn api_key_and_shared_secret_callers_get_a_service_identity() {
+        let ops = api_key_interceptor().call(request_with_api_key("key-ops")).expect("key-ops");
+        let ci = api_key_interceptor().call(request_with_api_key("key-ci")).expect("key-ci");
+        let subject = |request: &Request<()>| match extract_caller(request) {
+            Caller::Service(service) => service.subject,
+            other => panic!("expected a service caller, got {other:?}"),
+        };
+        assert!(subject(&ops).starts_with("api-key:"));
+        assert!(!subject(&ops).contains("key-ops"));
+        assert_ne!(subject(&ops), subject(&ci));
+        assert_eq!(subject(&ops), ServiceCaller::for_api_key("key-ops").subject);
+
+        let shared = interceptor().call(request_with(Some("Bearer kernel-secret"))).expect("shared secret");
+        assert_eq!(subject(&shared), SHARED_SECRET_SUBJECT);
+        assert!(extract_claims(&shared).is_none());
+    }
+
+    #[test]
+    fn missing_or_invalid_api_key_is_unauthenticated() {
+        let err = api_key_interceptor().call(request_with(None)).expect_err("missing key");
+        assert_eq!(err.code(), Code::Unauthenticated);
+        assert_eq!(err.message(), "missing api key");
+
+        let err = api_key_interceptor()
+            .call(request_with_api_key("key-unknown"))
+            .expect_err("invalid key");
+        assert_eq!(err.code(), Code::Unauthenticated);
+        assert_eq!(err.message(), "invalid api key");
+
+        let err = interceptor()
+            .call(request_with_api_key("kernel-secret"))
+            .expect_err("no keys configured");
+        assert_eq!(err.code(), Code::Unauthenticated);
+    }
+
+    #[test]
+    fn dev_mode_accepts_calls_without_a_key() {
+        let config = AuthConfig::from_vars(env_of(&[(API_KEYS_ENV, " , ")])).expect("config");
+        let mut dev = AuthInterceptor::from_config(&config);
+        assert!(!dev.is_enabled());
+        assert!(dev.call(request_with(None)).is_ok());
+    }
+
+    fn jwt(secret: &str, exp_offset_secs: i64) -> String {
+        use jsonwebtoken::{EncodingKey, Header, encode};
+        let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).expect("clock").as_secs() as i64;
+        let claims = serde_json::json!({"sub": "alice", "iss": "eigen-auth", "exp": now + exp_offset_secs});
+        encode(&Header::default(), &claims, &EncodingKey::from_secret(secret.as_bytes())).expect("encode")
+    }
+
+    #[test]
+    fn enabled_jwt_auth_accepts_signed_tokens_and_exposes_their_claims() {
+        let config = AuthConfig::from_vars(env_of(&[
+            (AUTH_ENABLED_ENV, "true"),
+            ("EIGEN_AUTH_JWT_ISSUER", "eigen-auth"),
+            ("EIGEN_AUTH_JWT_SECRET", "jwt-secret"),
+            (AUTH_TOKEN_ENV, "jwt-secret"),
+        ]))
+        .expect("config");
+        assert!(!format!("{config:?}").contains("jwt-secret"));
+        let mut interceptor = AuthInterceptor::from_config(&config);
+
+        let request = interceptor
+            .call(request_with(Some(&format!("Bearer {}", jwt("jwt-secret", 300)))))
+            .expect("signed token");
+        assert_eq!(extract_claims(&request).map(|claims| claims.sub), Some("alice".to_string()));
+
+        let expired = format!("Bearer {}", jwt("jwt-secret", -600));
+        let err = interceptor.call(request_with(Some(&expired))).expect_err("expired");
+        assert_eq!((err.code(), err.message()), (Code::Unauthenticated, "expired bearer token"));
+        let err = interceptor.call(request_with(Some("Bearer jwt-secret"))).expect_err("shared secret is not a JWT");
+        assert_eq!(err.code(), Code::Unauthenticated);
+        assert!(extract_claims(&request_with(None)).is_none());
+    }
+
+    #[test]
+    fn enabled_jwt_auth_without_a_secret_fails_to_configure() {
+        let err = AuthConfig::from_vars(env_of(&[(AUTH_ENABLED_ENV, "true")])).expect_err("no secret");
+        assert!(matches!(err, AuthConfigError::Jwt(JwtConfigError::MissingSecret { .. })), "{err}");
+    }
+
+    #[test]
+    fn rbac_policy_file_is_loaded_and_errors_are_reported() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let policy_file = dir.path().join("rbac.toml");
+        std::fs::write(&policy_file, "[roles]\nviewer = [\"read_job_status\"]\n").expect("write policy");
+        let policy_file = policy_file.to_string_lossy().into_owned();
+        let config = AuthConfig::from_vars(env_of(&[(RBAC_POLICY_FILE_ENV, &policy_file)])).expect("config");
+        let policy = config.rbac.expect("policy loaded");
+        assert!(policy.grants("viewer", security_module::Permission::ReadJobStatus));
+
+        std::fs::write(&policy_file, "[roles]\nviewer = [\"read_everything\"]\n").expect("write policy");
+        let err = AuthConfig::from_vars(env_of(&[(RBAC_POLICY_FILE_ENV, &policy_file)])).expect_err("bad policy");
+        assert!(matches!(err, AuthConfigError::Rbac(RbacError::Parse { .. })), "{err}");
+    }
+
+    #[test]
+    fn config_combines_env_keys_and_keys_file() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let keys_file = dir.path().join("api-keys");
+        std::fs::write(&keys_file, "# operators\nkey-ops\n").expect("write keys file");
+        let keys_file = keys_file.to_string_lossy().into_owned();
+
+        let config = AuthConfig::from_vars(env_of(&[(API_KEYS_ENV, "key-ci"), (API_KEYS_FILE_ENV, &keys_file)]))
+            .expect("config");
+        assert_eq!(config.api_keys.len(), 2);
+        assert!(!format!("{config:?}").contains("key-ops"));
+        let mut interceptor = AuthInterceptor::from_config(&config);
+        assert!(interceptor.call(request_with_api_key("key-ops")).is_ok());
+        assert!(interceptor.call(request_with_api_key("key-ci")).is_ok());
+
+        let missing = dir.path().join("missing").to_string_lossy().into_owned();
+        let err = AuthConfig::from_vars(env_of(&[(API_KEYS_FILE_ENV, &missing)])).expect_err("unreadable file");
+        assert!(matches!(err, AuthConfigError::KeysFile { .. }));
+    }
+}
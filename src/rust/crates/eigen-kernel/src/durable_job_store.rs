@@ -19,7 +19,7 @@ use uuid::Uuid;
 
 use qrtx::event_log::{JobEventLog, StateTransitionEvent};
 use qrtx::state_machine::{JobEvent, JobState, TransitionError, transition};
-use qfs::CircuitFsLocal;
+use qfs::CircuitFsBackend;
 
 /// A stored job record (extended from MVP) with QFS persistence.
 #[derive(Debug, Clone)]
@@ -64,7 +64,7 @@ pub struct DurableJobStore {
     records: std::sync::Arc<RwLock<HashMap<String, DurableJobRecord>>>,
     
     /// QFS handle for persistent event storage.
-    qfs: CircuitFsLocal,
+    qfs: std::sync::Arc<dyn CircuitFsBackend>,
     
     /// Event logs (in-memory cache, synchronized with QFS).
     event_logs: std::sync::Arc<RwLock<HashMap<String, JobEventLog>>>,
@@ -72,7 +72,7 @@ pub struct DurableJobStore {
 
 impl DurableJobStore {
     /// Create a new durable store, optionally loading from QFS.
-    pub fn new(qfs: CircuitFsLocal) -> Self {
+    pub fn new(qfs: std::sync::Arc<dyn CircuitFsBackend>) -> Self {
         Self {
             records: std::sync::Arc::new(RwLock::new(HashMap::new())),
             qfs,
@@ -101,11 +101,11 @@ impl DurableJobStore {
         for job_id in job_ids {
             let state_events_ref = format!("qfs://jobs/{job_id}/logs/state_events.jsonl");
             let snapshot_ref = format!("qfs://jobs/{job_id}/logs/replay_snapshot.jsonl");
-            let snapshot_bytes = match self.qfs.read_bytes(&snapshot_ref) {
+            let snapshot_bytes = match self.qfs.read_bytes(std::path::Path::new(&snapshot_ref)) {
                 Ok(bytes) => bytes,
                 Err(_) => continue,
             };
-            let events_bytes = match self.qfs.read_bytes(&state_events_ref) {
+            let events_bytes = match self.qfs.read_bytes(std::path::Path::new(&state_events_ref)) {
                 Ok(bytes) => bytes,
                 Err(_) => continue,
             };
@@ -214,7 +214,7 @@ impl DurableJobStore {
             })?;
 
         // Check if already terminal (idempotent)
-        if is_terminal(rec.state) {
+        if rec.state.is_terminal() {
             if terminal_state_for_event(event) == Some(rec.state) {
                 return Ok(rec.clone());
             }
@@ -319,19 +319,13 @@ impl DurableJobStore {
     }
 }
 
-fn is_terminal(state: JobState) -> bool {
-    matches!(
-        state,
-        JobState::Done | JobState::Error | JobState::Cancelled | JobState::Timeout
-    )
-}
-
 fn terminal_state_for_event(event: JobEvent) -> Option<JobState> {
     match event {
-        JobEvent::Complete => Some(JobState::Done),
+        JobEvent::Complete | JobEvent::FinishCompilingOnly => Some(JobState::Done),
         JobEvent::Fail => Some(JobState::Error),
         JobEvent::Cancel => Some(JobState::Cancelled),
         JobEvent::TimeOut => Some(JobState::Timeout),
+        JobEvent::RetryExhausted => Some(JobState::Error),
         _ => None,
     }
 }
@@ -346,12 +340,13 @@ fn unix_ms() -> i64 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use qfs::CircuitFsLocal;
 
     fn make_store() -> DurableJobStore {
         // For tests, use a temp directory
         let temp_dir = tempfile::tempdir().unwrap();
         let qfs = CircuitFsLocal::new(temp_dir.path().to_str().unwrap());
-        DurableJobStore::new(qfs)
+        DurableJobStore::new(std::sync::Arc::new(qfs))
     }
 
     #[test]
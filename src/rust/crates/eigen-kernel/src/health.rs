@@ -0,0 +1,277 @@
+//! Standard `grpc.health.v1.Health` service for the kernel server.
+//!
+//! The overall status (service `""`) and `KernelGatewayService` report SERVING
+//! once the gRPC listener is bound and NOT_SERVING from the start of a graceful
+//! shutdown. The [`QFS_COMPONENT`] entry tracks whether the local QFS root is
+//! writable, so probes can tell a kernel that can no longer persist jobs apart
+//! from a healthy one. `KernelGatewayService` also drops to NOT_SERVING while
+//! `EnqueueJob` keeps failing on the kernel's side (see [`EnqueueFailures`]).
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::watch;
+use tonic::Code;
+use tonic_health::ServingStatus;
+use tonic_health::pb::health_server::{Health, HealthServer};
+use tonic_health::server::{HealthReporter, health_reporter};
+
+use crate::proto::kernel_gateway_service_server::SERVICE_NAME as KERNEL_GATEWAY_SERVICE;
+
+/// Health service name of the QFS writability check.
+pub const QFS_COMPONENT: &str = "eigen.kernel.qfs";
+
+/// How often the QFS root is probed while the server runs.
+pub const QFS_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Consecutive failed `EnqueueJob` calls after which `KernelGatewayService`
+/// reports NOT_SERVING, [`DEFAULT_MAX_ENQUEUE_FAILURES`] when unset; `0` turns the
+/// check off.
+pub const MAX_ENQUEUE_FAILURES_ENV: &str = "EIGEN_KERNEL_HEALTH_MAX_ENQUEUE_FAILURES";
+
+pub const DEFAULT_MAX_ENQUEUE_FAILURES: u32 = 10;
+
+/// Counts the `EnqueueJob` calls that failed in a row because of the kernel.
+/// Failures the caller caused, such as invalid arguments, denied access or an
+/// exhausted quota, are neither counted nor reset the run.
+#[derive(Debug, Clone)]
+pub struct EnqueueFailures {
+    threshold: u32,
+    consecutive: Arc<watch::Sender<u32>>,
+}
+
+impl Default for EnqueueFailures {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_ENQUEUE_FAILURES)
+    }
+}
+
+impl EnqueueFailures {
+    /// Counter reporting the kernel unhealthy after `threshold` failures; `0` never does.
+    pub fn new(threshold: u32) -> Self {
+        Self { threshold, consecutive: Arc::new(watch::channel(0).0) }
+    }
+
+    /// Reads [`MAX_ENQUEUE_FAILURES_ENV`]; unset or unparsable values keep the default.
+    pub fn from_env() -> Self {
+        Self::from_vars(|key| std::env::var(key).ok())
+    }
+
+    fn from_vars(env: impl Fn(&str) -> Option<String>) -> Self {
+        let threshold = env(MAX_ENQUEUE_FAILURES_ENV).and_then(|raw| raw.trim().parse::<u32>().ok());
+        Self::new(threshold.unwrap_or(DEFAULT_MAX_ENQUEUE_FAILURES))
+    }
+
+    pub fn record_success(&self) {
+        self.consecutive.send_if_modified(|failures| std::mem::take(failures) != 0);
+    }
+
+    pub fn record_failure(&self, code: Code) {
+        if is_kernel_fault(code) {
+            self.consecutive.send_modify(|failures| *failures = failures.saturating_add(1));
+        }
+    }
+
+    /// Failures since the last success.
+    pub fn consecutive(&self) -> u32 {
+        *self.consecutive.borrow()
+    }
+}
+
+fn is_kernel_fault(code: Code) -> bool {
+    matches!(code, Code::Internal | Code::Unknown | Code::Unavailable | Code::DataLoss)
+}
+
+/// Updates the statuses served by the health service built in [`KernelHealth::new`].
+#[derive(Clone)]
+pub struct KernelHealth {
+    reporter: HealthReporter,
+    qfs_root: PathBuf,
+}
+
+impl KernelHealth {
+    /// The reporter plus the service to register on the server. Everything starts
+    /// NOT_SERVING until [`KernelHealth::set_serving`].
+    pub async fn new(qfs_root: impl Into<PathBuf>) -> (Self, HealthServer<impl Health>) {
+        let (reporter, service) = health_reporter();
+        let health = Self { reporter, qfs_root: qfs_root.into() };
+        health.set_not_serving().await;
+        (health, service)
+    }
+
+    /// Marks the kernel as ready and runs a first QFS check.
+    pub async fn set_serving(&self) {
+        for service in ["", KERNEL_GATEWAY_SERVICE] {
+            self.reporter.set_service_status(service, ServingStatus::Serving).await;
+        }
+        self.check_qfs().await;
+    }
+
+    /// Marks the kernel and its components as no longer accepting work.
+    pub async fn set_not_serving(&self) {
+        for service in ["", KERNEL_GATEWAY_SERVICE, QFS_COMPONENT] {
+            self.reporter.set_service_status(service, ServingStatus::NotServing).await;
+        }
+    }
+
+    /// Probes the QFS root and updates [`QFS_COMPONENT`]; returns whether it is writable.
+    pub async fn check_qfs(&self) -> bool {
+        let root = self.qfs_root.clone();
+        let writable = tokio::task::spawn_blocking(move || qfs_root_writable(&root)).await.unwrap_or(false);
+        if !writable {
+            tracing::warn!(qfs_root = %self.qfs_root.display(), "QFS root is not writable");
+        }
+        let status = if writable { ServingStatus::Serving } else { ServingStatus::NotServing };
+        self.reporter.set_service_status(QFS_COMPONENT, status).await;
+        writable
+    }
+
+    /// Reports `KernelGatewayService` NOT_SERVING once `failures` reaches its
+    /// threshold and SERVING again after the next successful enqueue, until the task
+    /// is aborted.
+    pub fn spawn_enqueue_failure_watch(&self, failures: &EnqueueFailures) -> tokio::task::JoinHandle<()> {
+        let health = self.clone();
+        let threshold = failures.threshold;
+        let mut consecutive = failures.consecutive.subscribe();
+        tokio::spawn(async move {
+            if threshold == 0 {
+                return;
+            }
+            let mut failing = false;
+            while consecutive.changed().await.is_ok() {
+                let count = *consecutive.borrow_and_update();
+                if (count >= threshold) == failing {
+                    continue;
+                }
+                failing = !failing;
+                let status = if failing {
+                    tracing::warn!(consecutive_failures = count, "EnqueueJob keeps failing; reporting NOT_SERVING");
+                    ServingStatus::NotServing
+                } else {
+                    tracing::info!("EnqueueJob succeeded again; reporting SERVING");
+                    ServingStatus::Serving
+                };
+                health.reporter.set_service_status(KERNEL_GATEWAY_SERVICE, status).await;
+            }
+        })
+    }
+
+    /// Re-runs [`KernelHealth::check_qfs`] every `interval` until the task is aborted.
+    pub fn spawn_qfs_checks(&self, interval: Duration) -> tokio::task::JoinHandle<()> {
+        let health = self.clone();
+        tokio::spawn(async move {
+            let mut ticks = tokio::time::interval(interval);
+            ticks.tick().await;
+            loop {
+                ticks.tick().await;
+                health.check_qfs().await;
+            }
+        })
+    }
+}
+
+/// Whether a file can be created and removed directly under `root`, creating the
+/// root first if needed.
+pub fn qfs_root_writable(root: &Path) -> bool {
+    let probe = root.join(format!(".health-probe-{}", std::process::id()));
+    let written = std::fs::create_dir_all(root).and_then(|()| std::fs::write(&probe, b"ok"));
+    let removed = std::fs::remove_file(&probe);
+    written.is_ok() && removed.is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio_stream::wrappers::TcpListenerStream;
+    use tonic::transport::Channel;
+    use tonic_health::pb::HealthCheckRequest;
+    use tonic_health::pb::health_check_response::ServingStatus as PbStatus;
+    use tonic_health::pb::health_client::HealthClient;
+
+    #[test]
+    fn qfs_root_is_writable_unless_it_cannot_be_created() {
+        let temp = tempfile::tempdir().unwrap();
+        assert!(qfs_root_writable(&temp.path().join("qfs")));
+        assert!(!temp.path().join("qfs").join(format!(".health-probe-{}", std::process::id())).exists());
+
+        std::fs::write(temp.path().join("file"), b"x").unwrap();
+        assert!(!qfs_root_writable(&temp.path().join("file").join("qfs")));
+    }
+
+    #[tokio::test]
+    async fn statuses_start_not_serving_and_follow_the_reporter() {
+        let temp = tempfile::tempdir().unwrap();
+        let blocked_root = temp.path().join("file").join("qfs");
+        std::fs::write(temp.path().join("file"), b"x").unwrap();
+        let (health, service) = KernelHealth::new(&blocked_root).await;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_service(service)
+                .serve_with_incoming(TcpListenerStream::new(listener)),
+        );
+        let channel = Channel::from_shared(format!("http://{addr}")).unwrap().connect().await.unwrap();
+        let mut client = HealthClient::new(channel);
+        let mut status = async |service: &str| {
+            let request = HealthCheckRequest { service: service.to_string() };
+            client.check(request).await.unwrap().into_inner().status
+        };
+
+        assert_eq!(status("").await, PbStatus::NotServing as i32);
+        health.set_serving().await;
+        assert_eq!(status("").await, PbStatus::Serving as i32);
+        assert_eq!(status(KERNEL_GATEWAY_SERVICE).await, PbStatus::Serving as i32);
+        assert_eq!(status(QFS_COMPONENT).await, PbStatus::NotServing as i32);
+
+        std::fs::remove_file(temp.path().join("file")).unwrap();
+        assert!(health.check_qfs().await);
+        assert_eq!(status(QFS_COMPONENT).await, PbStatus::Serving as i32);
+        health.set_not_serving().await;
+        assert_eq!(status("").await, PbStatus::NotServing as i32);
+    }
+
+    #[tokio::test]
+    async fn repeated_enqueue_failures_take_the_gateway_out_of_service() {
+        let temp = tempfile::tempdir().unwrap();
+        let (health, service) = KernelHealth::new(temp.path()).await;
+        health.set_serving().await;
+        let failures = EnqueueFailures::new(3);
+        let _watch = health.spawn_enqueue_failure_watch(&failures);
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_service(service)
+                .serve_with_incoming(TcpListenerStream::new(listener)),
+        );
+        let channel = Channel::from_shared(format!("http://{addr}")).unwrap().connect().await.unwrap();
+        let mut client = HealthClient::new(channel);
+        let request = HealthCheckRequest { service: KERNEL_GATEWAY_SERVICE.to_string() };
+        let mut updates = client.watch(request).await.unwrap().into_inner();
+        let mut next = async || updates.message().await.unwrap().expect("status update").status;
+        assert_eq!(next().await, PbStatus::Serving as i32);
+
+        failures.record_failure(Code::Internal);
+        failures.record_failure(Code::InvalidArgument);
+        failures.record_failure(Code::Unavailable);
+        assert_eq!(failures.consecutive(), 2, "caller errors are not counted");
+        failures.record_failure(Code::Internal);
+        assert_eq!(next().await, PbStatus::NotServing as i32);
+
+        failures.record_success();
+        assert_eq!(failures.consecutive(), 0);
+        assert_eq!(next().await, PbStatus::Serving as i32);
+    }
+
+    #[test]
+    fn enqueue_failure_threshold_comes_from_the_environment() {
+        let env = |value: &'static str| move |key: &str| (key == MAX_ENQUEUE_FAILURES_ENV).then(|| value.to_string());
+        assert_eq!(EnqueueFailures::from_vars(env(" 2 ")).threshold, 2);
+        assert_eq!(EnqueueFailures::from_vars(env("0")).threshold, 0);
+        assert_eq!(EnqueueFailures::from_vars(env("many")).threshold, DEFAULT_MAX_ENQUEUE_FAILURES);
+    }
+}
@@ -9,21 +9,149 @@ use std::time::{SystemTime, UNIX_EPOCH};
 use parking_lot::RwLock;
 use uuid::Uuid;
 
-use qrtx::state_machine::{JobEvent, JobState, TransitionError, transition};
+use qrtx::queue::Priority;
+use qrtx::state_machine::{JobEvent, JobEventWithReason, JobState, TransitionError, transition};
+
+/// Maximum number of transition entries kept per job; the oldest are dropped first.
+pub const MAX_TRANSITION_HISTORY: usize = 256;
+
+/// Error code given to jobs that fail without reporting one.
+pub const UNREPORTED_FAILURE_CODE: &str = "INTERNAL";
+
+/// One entry of a job's transition history.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TransitionEntry {
+    pub from: JobState,
+    pub to: JobState,
+    /// `None` for the entry recorded when the job is created.
+    pub event: Option<JobEvent>,
+    pub at_unix_ms: i64,
+    /// Why the job was cancelled or failed, when known.
+    pub reason: Option<String>,
+}
 
 /// A stored job record for the MVP state machine.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct JobRecord {
     pub job_id: String,
     pub name: String,
+    /// Scheduling class; records written before priorities existed load as `Normal`.
+    #[serde(default)]
+    pub priority: Priority,
     pub state: JobState,
     pub created_at_unix_ms: i64,
     pub updated_at_unix_ms: i64,
     pub error_code: Option<String>,
     pub error_summary: Option<String>,
     pub error_details_ref: Option<String>,
+    #[serde(default)]
     pub counts: HashMap<String, i64>,
+    #[serde(default)]
     pub results_metadata: HashMap<String, String>,
+    #[serde(default)]
+    pub history: Vec<TransitionEntry>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tenant_id: Option<String>,
+    /// Subject that submitted the job, when the kernel knows it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    /// Canonical submission fingerprint, so idempotent replays still match after a restart.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub submission_fingerprint: Option<String>,
+}
+
+impl JobRecord {
+    pub(crate) fn new(job_id: String, name: String, priority: Priority, now: i64) -> Self {
+        Self {
+            job_id,
+            name,
+            priority,
+            state: JobState::Pending,
+            created_at_unix_ms: now,
+            updated_at_unix_ms: now,
+            error_code: None,
+            error_summary: None,
+            error_details_ref: None,
+            counts: HashMap::new(),
+            results_metadata: HashMap::new(),
+            history: vec![TransitionEntry {
+                from: JobState::Pending,
+                to: JobState::Pending,
+                event: None,
+                at_unix_ms: now,
+                reason: None,
+            }],
+            tenant_id: None,
+            owner: None,
+            submission_fingerprint: None,
+        }
+    }
+
+    /// Applies `event` like [`JobRecord::apply_transition`], also storing the error
+    /// a `Fail` carries.
+    pub(crate) fn apply_event(&mut self, event: JobEventWithReason, now: i64) -> Result<bool, TransitionError> {
+        let reason = match &event {
+            JobEventWithReason::Fail { summary, .. } => Some(summary.clone()),
+            JobEventWithReason::Cancel { requested_by } => Some(format!("requested by {requested_by}")),
+            JobEventWithReason::Plain(_) => None,
+        };
+        if !self.apply_transition(event.event(), reason, now)? {
+            return Ok(false);
+        }
+        if let JobEventWithReason::Fail { code, summary } = event {
+            self.error_code = Some(code);
+            self.error_summary = Some(summary);
+        }
+        Ok(true)
+    }
+
+    /// Applies `event`, recording the transition in `history`. A job never ends in
+    /// `Error` without an error code: failures that report none get
+    /// [`UNREPORTED_FAILURE_CODE`].
+    ///
+    /// Returns `Ok(false)` when a terminal job receives the event that already
+    /// terminalized it, which is treated as an idempotent no-op.
+    pub(crate) fn apply_transition(
+        &mut self,
+        event: JobEvent,
+        reason: Option<String>,
+        now: i64,
+    ) -> Result<bool, TransitionError> {
+        if self.state.is_terminal() {
+            if terminal_state_for_event(event) == Some(self.state) {
+                return Ok(false);
+            }
+            return Err(TransitionError::Invalid {
+                from: self.state,
+                event,
+            });
+        }
+
+        let next = transition(self.state, event)?;
+        let reason = match event {
+            JobEvent::Cancel => reason,
+            JobEvent::Fail | JobEvent::RetryExhausted => reason.or_else(|| self.error_summary.clone()),
+            _ => None,
+        };
+        if self.history.len() >= MAX_TRANSITION_HISTORY {
+            let overflow = self.history.len() + 1 - MAX_TRANSITION_HISTORY;
+            self.history.drain(..overflow);
+        }
+        self.history.push(TransitionEntry {
+            from: self.state,
+            to: next,
+            event: Some(event),
+            at_unix_ms: now,
+            reason,
+        });
+        self.state = next;
+        self.updated_at_unix_ms = now;
+        if next == JobState::Error && self.error_code.is_none() {
+            self.error_code = Some(UNREPORTED_FAILURE_CODE.to_string());
+            self.error_summary.get_or_insert_with(|| "job failed without reporting an error".to_string());
+        }
+        Ok(true)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -40,22 +168,10 @@ impl Default for JobStore {
 }
 
 impl JobStore {
-    pub fn create_job(&self, name: String) -> JobRecord {
-        let now = unix_ms();
+    pub fn create_job(&self, name: String, priority: Priority) -> JobRecord {
         let job_id = Uuid::new_v4().to_string();
-        let record = JobRecord {
-            job_id: job_id.clone(),
-            name,
-            state: JobState::Pending,
-            created_at_unix_ms: now,
-            updated_at_unix_ms: now,
-            error_code: None,
-            error_summary: None,
-            error_details_ref: None,
-            counts: HashMap::new(),
-            results_metadata: HashMap::new(),
-        };
-        self.inner.write().insert(job_id.clone(), record.clone());
+        let record = JobRecord::new(job_id.clone(), name, priority, unix_ms());
+        self.inner.write().insert(job_id, record.clone());
         record
     }
 
@@ -63,27 +179,49 @@ impl JobStore {
         self.inner.read().get(job_id).cloned()
     }
 
-    pub fn apply_event(&self, job_id: &str, event: JobEvent) -> Result<JobRecord, TransitionError> {
+    /// Applies `event` and stores the error or requester it carries under the same
+    /// lock, so readers never see a failed job without its error.
+    pub fn apply_event(
+        &self,
+        job_id: &str,
+        event: impl Into<JobEventWithReason>,
+    ) -> Result<JobRecord, TransitionError> {
+        let event = event.into();
+        let mut guard = self.inner.write();
+        let rec = guard.get_mut(job_id).ok_or(TransitionError::Invalid {
+            from: JobState::Pending,
+            event: event.event(),
+        })?;
+        rec.apply_event(event, unix_ms())?;
+        Ok(rec.clone())
+    }
+
+    /// Like [`JobStore::apply_event`], recording `reason` on cancel and fail
+    /// transitions. Failures without an explicit reason fall back to the
+    /// recorded error summary.
+    pub fn apply_event_with_reason(
+        &self,
+        job_id: &str,
+        event: JobEvent,
+        reason: Option<String>,
+    ) -> Result<JobRecord, TransitionError> {
         let mut guard = self.inner.write();
         let rec = guard.get_mut(job_id).ok_or(TransitionError::Invalid {
             from: JobState::Pending,
             event,
         })?;
+        rec.apply_transition(event, reason, unix_ms())?;
+        Ok(rec.clone())
+    }
 
-        if is_terminal(rec.state) {
-            if terminal_state_for_event(event) == Some(rec.state) {
-                return Ok(rec.clone());
-            }
-            return Err(TransitionError::Invalid {
-                from: rec.state,
-                event,
-            });
-        }
+    /// Number of jobs that have not reached a terminal state.
+    pub fn active_count(&self) -> usize {
+        self.inner.read().values().filter(|rec| !rec.state.is_terminal()).count()
+    }
 
-        let next = transition(rec.state, event)?;
-        rec.state = next;
-        rec.updated_at_unix_ms = unix_ms();
-        Ok(rec.clone())
+    /// Ordered transition history of a job, oldest first.
+    pub fn history(&self, job_id: &str) -> Option<Vec<TransitionEntry>> {
+        self.inner.read().get(job_id).map(|rec| rec.history.clone())
     }
 
     pub fn set_error(
@@ -122,24 +260,18 @@ impl JobStore {
     }
 }
 
-fn is_terminal(state: JobState) -> bool {
-    matches!(
-        state,
-        JobState::Done | JobState::Error | JobState::Cancelled | JobState::Timeout
-    )
-}
-
-fn terminal_state_for_event(event: JobEvent) -> Option<JobState> {
+pub(crate) fn terminal_state_for_event(event: JobEvent) -> Option<JobState> {
     match event {
-        JobEvent::Complete => Some(JobState::Done),
+        JobEvent::Complete | JobEvent::FinishCompilingOnly => Some(JobState::Done),
         JobEvent::Fail => Some(JobState::Error),
         JobEvent::Cancel => Some(JobState::Cancelled),
         JobEvent::TimeOut => Some(JobState::Timeout),
+        JobEvent::RetryExhausted => Some(JobState::Error),
         _ => None,
     }
 }
 
-fn unix_ms() -> i64 {
+pub(crate) fn unix_ms() -> i64 {
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .unwrap_or_default()
@@ -153,7 +285,7 @@ mod tests {
     #[test]
     fn terminal_state_rejects_non_matching_events() {
         let store = JobStore::default();
-        let record = store.create_job("test".to_string());
+        let record = store.create_job("test".to_string(), Priority::Normal);
 
         store
             .apply_event(&record.job_id, JobEvent::StartCompiling)
@@ -175,7 +307,7 @@ mod tests {
     #[test]
     fn re_terminalization_is_idempotent() {
         let store = JobStore::default();
-        let record = store.create_job("test".to_string());
+        let record = store.create_job("test".to_string(), Priority::Normal);
 
         store
             .apply_event(&record.job_id, JobEvent::StartCompiling)
@@ -193,5 +325,136 @@ mod tests {
         assert_eq!(done_once.state, JobState::Done);
         assert_eq!(done_twice.state, JobState::Done);
         assert_eq!(done_once.updated_at_unix_ms, done_twice.updated_at_unix_ms);
+        assert_eq!(done_once.history, done_twice.history);
+    }
+
+    #[test]
+    fn happy_path_history_is_ordered_with_non_decreasing_timestamps() {
+        let store = JobStore::default();
+        let record = store.create_job("test".to_string(), Priority::Normal);
+        for event in [JobEvent::StartCompiling, JobEvent::StartRunning, JobEvent::Complete] {
+            store.apply_event(&record.job_id, event).unwrap();
+        }
+
+        let history = store.history(&record.job_id).unwrap();
+        assert_eq!(history.len(), 4);
+        let steps: Vec<(JobState, JobState, Option<JobEvent>)> =
+            history.iter().map(|entry| (entry.from, entry.to, entry.event)).collect();
+        assert_eq!(
+            steps,
+            vec![
+                (JobState::Pending, JobState::Pending, None),
+                (JobState::Pending, JobState::Compiling, Some(JobEvent::StartCompiling)),
+                (JobState::Compiling, JobState::Running, Some(JobEvent::StartRunning)),
+                (JobState::Running, JobState::Done, Some(JobEvent::Complete)),
+            ]
+        );
+        assert!(history.windows(2).all(|pair| pair[0].at_unix_ms <= pair[1].at_unix_ms));
+        assert!(history.iter().all(|entry| entry.reason.is_none()));
+    }
+
+    #[test]
+    fn cancel_and_fail_entries_carry_reasons() {
+        let store = JobStore::default();
+        let cancelled = store.create_job("cancelled".to_string(), Priority::Normal);
+        store
+            .apply_event_with_reason(&cancelled.job_id, JobEvent::Cancel, Some("user-request".to_string()))
+            .unwrap();
+        let last = store.history(&cancelled.job_id).unwrap().pop().unwrap();
+        assert_eq!(last.to, JobState::Cancelled);
+        assert_eq!(last.reason.as_deref(), Some("user-request"));
+
+        let failed = store.create_job("failed".to_string(), Priority::Normal);
+        store.set_error(&failed.job_id, "COMPILE_ERROR".to_string(), "bad gate".to_string(), None);
+        store.apply_event(&failed.job_id, JobEvent::Fail).unwrap();
+        let last = store.history(&failed.job_id).unwrap().pop().unwrap();
+        assert_eq!(last.to, JobState::Error);
+        assert_eq!(last.reason.as_deref(), Some("bad gate"));
+    }
+
+    #[test]
+    fn fail_and_cancel_events_carry_their_data() {
+        let store = JobStore::default();
+        let failed = store.create_job("failed".to_string(), Priority::Normal);
+        let event = JobEventWithReason::Fail { code: "COMPILE_ERROR".to_string(), summary: "bad gate".to_string() };
+        let record = store.apply_event(&failed.job_id, event).unwrap();
+        assert_eq!(record.state, JobState::Error);
+        assert_eq!(record.error_code.as_deref(), Some("COMPILE_ERROR"));
+        assert_eq!(record.error_summary.as_deref(), Some("bad gate"));
+        assert_eq!(record.history.last().unwrap().reason.as_deref(), Some("bad gate"));
+
+        let cancelled = store.create_job("cancelled".to_string(), Priority::Normal);
+        let event = JobEventWithReason::Cancel { requested_by: "alice".to_string() };
+        let record = store.apply_event(&cancelled.job_id, event).unwrap();
+        assert_eq!(record.state, JobState::Cancelled);
+        assert_eq!(record.history.last().unwrap().reason.as_deref(), Some("requested by alice"));
+        assert_eq!(record.error_code, None);
+    }
+
+    #[test]
+    fn error_state_always_has_an_error_code() {
+        let store = JobStore::default();
+        let failed = store.create_job("failed".to_string(), Priority::Normal);
+        let record = store.apply_event(&failed.job_id, JobEvent::Fail).unwrap();
+        assert_eq!(record.state, JobState::Error);
+        assert_eq!(record.error_code.as_deref(), Some(UNREPORTED_FAILURE_CODE));
+        assert!(record.error_summary.is_some());
+
+        let exhausted = store.create_job("exhausted".to_string(), Priority::Normal);
+        for event in [JobEvent::StartCompiling, JobEvent::StartRunning, JobEvent::RetryRequested] {
+            store.apply_event(&exhausted.job_id, event).unwrap();
+        }
+        let record = store
+            .apply_event_with_reason(&exhausted.job_id, JobEvent::RetryExhausted, Some("budget spent".to_string()))
+            .unwrap();
+        assert_eq!(record.error_code.as_deref(), Some(UNREPORTED_FAILURE_CODE));
+    }
+
+    #[test]
+    fn history_is_capped() {
+        let mut record = JobRecord::new("job".to_string(), "capped".to_string(), Priority::Normal, 0);
+        record.history = (0..MAX_TRANSITION_HISTORY as i64)
+            .map(|at_unix_ms| TransitionEntry {
+                from: JobState::Pending,
+                to: JobState::Pending,
+                event: None,
+                at_unix_ms,
+                reason: None,
+            })
+            .collect();
+
+        record.apply_transition(JobEvent::StartCompiling, None, 1_000).unwrap();
+        assert_eq!(record.history.len(), MAX_TRANSITION_HISTORY);
+        assert_eq!(record.history[0].at_unix_ms, 1);
+        assert_eq!(record.history.last().unwrap().event, Some(JobEvent::StartCompiling));
+    }
+
+    #[test]
+    fn created_jobs_keep_their_priority() {
+        let store = JobStore::default();
+        let record = store.create_job("interactive".to_string(), Priority::High);
+        assert_eq!(store.get(&record.job_id).unwrap().priority, Priority::High);
+
+        let mut value = serde_json::to_value(&record).unwrap();
+        value.as_object_mut().unwrap().remove("priority");
+        let legacy: JobRecord = serde_json::from_value(value).unwrap();
+        assert_eq!(legacy.priority, Priority::Normal);
+    }
+
+    #[test]
+    fn active_count_skips_terminal_jobs() {
+        let store = JobStore::default();
+        let running = store.create_job("running".to_string(), Priority::Normal);
+        let paused = store.create_job("paused".to_string(), Priority::Normal);
+        let cancelled = store.create_job("cancelled".to_string(), Priority::Normal);
+        assert_eq!(store.active_count(), 3);
+
+        store.apply_event(&running.job_id, JobEvent::StartCompiling).unwrap();
+        store.apply_event(&paused.job_id, JobEvent::Pause).unwrap();
+        store.apply_event(&cancelled.job_id, JobEvent::Cancel).unwrap();
+        assert_eq!(store.active_count(), 2);
+
+        store.apply_event(&running.job_id, JobEvent::FinishCompilingOnly).unwrap();
+        assert_eq!(store.active_count(), 1);
     }
 }
@@ -5,13 +5,32 @@
 //! - Deterministic state replay on restart
 //! - Single-authority state machine
 //! - Audit trail for all transitions
-//! - Audit trail for all transitions
+//! - Restart-safe job metadata persisted to QFS
+//! - Prometheus `/metrics` endpoint for job and stage metrics
+//! - API-key or bearer-token authentication on every KernelGateway method
+//! - Role-based authorization of JWT callers from a TOML policy
+//! - `grpc.health.v1.Health` readiness, including a QFS writability check
+//! - Optional TLS / mutual TLS on the gRPC listener
+//! - Request validation with stable error codes in `google.rpc` status details
+//! - Deterministic seeded counts when no driver-manager is configured
 
+pub mod auth;
 pub mod durable_job_store;
+pub mod health;
 pub mod job_store;
+pub mod metrics;
+pub mod persistent_job_store;
+pub mod pipeline_config;
+pub mod rbac;
 pub mod rpc;
+pub mod simulator;
+pub mod tls;
+pub mod validation;
 
 /// Generated protobuf types for the internal kernel gateway API.
 pub mod proto {
     tonic::include_proto!("eigen.internal.v1");
+
+    /// Encoded descriptors of every compiled proto file, served through gRPC reflection.
+    pub const FILE_DESCRIPTOR_SET: &[u8] = tonic::include_file_descriptor_set!("descriptor");
 }
@@ -1,14 +1,12 @@
 use std::net::SocketAddr;
 
-use observability::log_startup;
+use observability::TracingConfig;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    tracing_subscriber::fmt()
-        .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
-        .init();
-
-    log_startup("eigen-kernel");
+    let tracing_config = TracingConfig::from_env();
+    let service_name = tracing_config.service_name.clone();
+    observability::init_tracing(tracing_config)?;
 
     // Internal kernel gRPC address (System API -> Kernel).
     // Default matches our dev compose conventions.
@@ -16,5 +14,24 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .unwrap_or_else(|_| "0.0.0.0:50052".to_string())
         .parse()?;
 
-    eigen_kernel::rpc::serve(addr).await
+    // Prometheus scrape address; set EIGEN_METRICS_ADDR=off to disable. The older
+    // EIGEN_KERNEL_METRICS_ADDR name is still honoured when the new one is unset.
+    let metrics_addr: Option<SocketAddr> =
+        match std::env::var("EIGEN_METRICS_ADDR").or_else(|_| std::env::var("EIGEN_KERNEL_METRICS_ADDR")) {
+            Ok(value) if value.eq_ignore_ascii_case("off") => None,
+            Ok(value) => Some(value.parse()?),
+            Err(_) => Some("0.0.0.0:9464".parse()?),
+        };
+
+    // TLS (and mutual TLS) from EIGEN_TLS_CERT, EIGEN_TLS_KEY and EIGEN_TLS_CLIENT_CA.
+    let tls = eigen_kernel::tls::TlsConfig::from_env()?;
+
+    observability::log_startup(&service_name, env!("CARGO_PKG_VERSION"), option_env!("EIGEN_GIT_SHA"), addr);
+    let served = eigen_kernel::rpc::serve(addr, metrics_addr, None, tls).await;
+
+    // Spans are exported in batches when EIGEN_OTEL_ENDPOINT is set; send the last one.
+    if let Err(err) = observability::otel::shutdown_otel() {
+        tracing::warn!(error = %err, "failed to flush OpenTelemetry spans");
+    }
+    served
 }
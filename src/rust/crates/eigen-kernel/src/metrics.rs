@@ -0,0 +1,29 @@
+//! Prometheus `/metrics` endpoint for the kernel.
+//!
+//! Served on its own listener next to the gRPC server so scrapes never compete
+//! with KernelGateway traffic.
+
+use std::io;
+use std::sync::Arc;
+
+use axum::Router;
+use axum::http::header;
+use axum::routing::get;
+use observability::metrics::{JobMetrics, PROMETHEUS_CONTENT_TYPE};
+use tokio::net::TcpListener;
+
+/// Router exposing `GET /metrics` in the Prometheus text format.
+pub fn router(metrics: Arc<JobMetrics>) -> Router {
+    Router::new().route(
+        "/metrics",
+        get(move || {
+            let metrics = metrics.clone();
+            async move { ([(header::CONTENT_TYPE, PROMETHEUS_CONTENT_TYPE)], metrics.render()) }
+        }),
+    )
+}
+
+/// Serves [`router`] on `listener` until the task is dropped.
+pub async fn serve(listener: TcpListener, metrics: Arc<JobMetrics>) -> io::Result<()> {
+    axum::serve(listener, router(metrics)).await
+}
@@ -0,0 +1,282 @@
+//! Persistent job store backed by per-job metadata documents in QFS.
+//!
+//! Every mutation of a [`JobRecord`] is written to `qfs://jobs/<job_id>/meta/job.json`
+//! (atomic temp-file + rename) *before* it becomes visible in memory, so the
+//! on-disk document is always at least as new as what readers have observed.
+//! On startup, [`PersistentJobStore::open`] walks the QFS root and rebuilds the
+//! in-memory index from those documents.
+//!
+//! The document is a [`qfs::JobMeta`]: the record's fields are merged over the
+//! stored document, so fields owned by other writers (tags, shots, fields from
+//! newer producers) survive every rewrite.
+//!
+//! Hot reads are served from the `RwLock<HashMap>` exactly like [`crate::job_store::JobStore`].
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+use uuid::Uuid;
+
+use qfs::{CircuitFsBackend, CircuitFsError, JOB_META_SCHEMA_VERSION, JobMeta};
+use qrtx::queue::Priority;
+use qrtx::state_machine::{JobEvent, JobEventWithReason, TransitionError};
+
+use crate::job_store::{JobRecord, unix_ms};
+
+/// Errors surfaced by [`PersistentJobStore`].
+#[derive(Debug)]
+pub enum PersistentJobStoreError {
+    /// The requested job is not known to the store.
+    NotFound { job_id: String },
+    /// The event is not valid for the job's current state.
+    Transition(TransitionError),
+    /// Reading or writing the metadata document failed.
+    Qfs(CircuitFsError),
+    /// A metadata document exists but could not be decoded.
+    Corrupt { job_id: String, message: String },
+}
+
+impl fmt::Display for PersistentJobStoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PersistentJobStoreError::NotFound { job_id } => write!(f, "job not found: {job_id}"),
+            PersistentJobStoreError::Transition(err) => write!(f, "{err}"),
+            PersistentJobStoreError::Qfs(err) => write!(f, "qfs error: {err}"),
+            PersistentJobStoreError::Corrupt { job_id, message } => {
+                write!(f, "corrupt job metadata for {job_id}: {message}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PersistentJobStoreError {}
+
+impl From<TransitionError> for PersistentJobStoreError {
+    fn from(err: TransitionError) -> Self {
+        PersistentJobStoreError::Transition(err)
+    }
+}
+
+impl From<CircuitFsError> for PersistentJobStoreError {
+    fn from(err: CircuitFsError) -> Self {
+        PersistentJobStoreError::Qfs(err)
+    }
+}
+
+/// Job store whose records survive process restarts.
+#[derive(Debug, Clone)]
+pub struct PersistentJobStore {
+    inner: Arc<RwLock<HashMap<String, JobRecord>>>,
+    qfs: Arc<dyn CircuitFsBackend>,
+}
+
+impl PersistentJobStore {
+    /// Opens the store, rebuilding the in-memory index from every
+    /// `jobs/*/meta/job.json` document found under the QFS root.
+    ///
+    /// Job directories without a metadata document (e.g. artifact-only jobs
+    /// written by other components) are skipped.
+    pub fn open(qfs: Arc<dyn CircuitFsBackend>) -> Result<Self, PersistentJobStoreError> {
+        let mut records = HashMap::new();
+        for job_id in qfs.list_job_ids()? {
+            let meta = match qfs.load_job_meta(&job_id) {
+                Ok(meta) => meta,
+                Err(CircuitFsError::NotFound { .. }) => continue,
+                Err(CircuitFsError::Io(err)) if err.kind() == std::io::ErrorKind::InvalidData => {
+                    return Err(PersistentJobStoreError::Corrupt {
+                        job_id,
+                        message: err.to_string(),
+                    });
+                }
+                Err(err) => return Err(err.into()),
+            };
+            let record = record_from_meta(meta).map_err(|err| PersistentJobStoreError::Corrupt {
+                job_id: job_id.clone(),
+                message: err.to_string(),
+            })?;
+            if record.job_id != job_id {
+                return Err(PersistentJobStoreError::Corrupt {
+                    job_id,
+                    message: format!("metadata names job {}", record.job_id),
+                });
+            }
+            records.insert(job_id, record);
+        }
+
+        Ok(Self {
+            inner: Arc::new(RwLock::new(records)),
+            qfs,
+        })
+    }
+
+    /// Number of jobs currently tracked.
+    pub fn len(&self) -> usize {
+        self.inner.read().len()
+    }
+
+    /// Returns `true` if the store tracks no jobs.
+    pub fn is_empty(&self) -> bool {
+        self.inner.read().is_empty()
+    }
+
+    pub fn create_job(&self, name: String, priority: Priority) -> Result<JobRecord, PersistentJobStoreError> {
+        let job_id = Uuid::new_v4().to_string();
+        let record = JobRecord::new(job_id.clone(), name, priority, unix_ms());
+
+        let mut guard = self.inner.write();
+        self.persist(&record)?;
+        guard.insert(job_id, record.clone());
+        Ok(record)
+    }
+
+    pub fn get(&self, job_id: &str) -> Option<JobRecord> {
+        self.inner.read().get(job_id).cloned()
+    }
+
+    /// Every tracked record, oldest first.
+    pub fn list(&self) -> Vec<JobRecord> {
+        let mut records: Vec<JobRecord> = self.inner.read().values().cloned().collect();
+        records.sort_by(|a, b| {
+            (a.created_at_unix_ms, &a.job_id).cmp(&(b.created_at_unix_ms, &b.job_id))
+        });
+        records
+    }
+
+    /// Stores `record` as given, for owners that drive the job lifecycle
+    /// themselves. Returns `false` without writing when it matches the stored record.
+    pub fn put(&self, record: JobRecord) -> Result<bool, PersistentJobStoreError> {
+        let mut guard = self.inner.write();
+        if guard.get(&record.job_id) == Some(&record) {
+            return Ok(false);
+        }
+        self.persist(&record)?;
+        guard.insert(record.job_id.clone(), record);
+        Ok(true)
+    }
+
+    /// Applies `event`, persisting the error or requester it carries with the new state.
+    pub fn apply_event(
+        &self,
+        job_id: &str,
+        event: impl Into<JobEventWithReason>,
+    ) -> Result<JobRecord, PersistentJobStoreError> {
+        let event = event.into();
+        self.update(job_id, |rec| {
+            rec.apply_event(event, unix_ms())
+                .map_err(PersistentJobStoreError::from)
+        })
+    }
+
+    /// Like [`PersistentJobStore::apply_event`], recording `reason` on cancel
+    /// and fail transitions.
+    pub fn apply_event_with_reason(
+        &self,
+        job_id: &str,
+        event: JobEvent,
+        reason: Option<String>,
+    ) -> Result<JobRecord, PersistentJobStoreError> {
+        self.update(job_id, |rec| {
+            rec.apply_transition(event, reason, unix_ms())
+                .map_err(PersistentJobStoreError::from)
+        })
+    }
+
+    pub fn set_error(
+        &self,
+        job_id: &str,
+        code: String,
+        summary: String,
+        details_ref: Option<String>,
+    ) -> Result<JobRecord, PersistentJobStoreError> {
+        self.update(job_id, |rec| {
+            rec.error_code = Some(code);
+            rec.error_summary = Some(summary);
+            rec.error_details_ref = details_ref;
+            Ok(true)
+        })
+    }
+
+    pub fn set_counts(
+        &self,
+        job_id: &str,
+        counts: HashMap<String, i64>,
+    ) -> Result<JobRecord, PersistentJobStoreError> {
+        self.update(job_id, |rec| {
+            rec.counts = counts;
+            Ok(true)
+        })
+    }
+
+    pub fn set_results_metadata(
+        &self,
+        job_id: &str,
+        metadata: HashMap<String, String>,
+    ) -> Result<JobRecord, PersistentJobStoreError> {
+        self.update(job_id, |rec| {
+            rec.results_metadata = metadata;
+            Ok(true)
+        })
+    }
+
+    /// Applies `mutate` to a copy of the record, persists it, then publishes it.
+    ///
+    /// `mutate` returns `Ok(false)` when nothing changed, in which case the
+    /// current record is returned untouched and nothing is written.
+    fn update<F>(&self, job_id: &str, mutate: F) -> Result<JobRecord, PersistentJobStoreError>
+    where
+        F: FnOnce(&mut JobRecord) -> Result<bool, PersistentJobStoreError>,
+    {
+        let mut guard = self.inner.write();
+        let current = guard
+            .get(job_id)
+            .ok_or_else(|| PersistentJobStoreError::NotFound {
+                job_id: job_id.to_string(),
+            })?;
+
+        let mut next = current.clone();
+        if !mutate(&mut next)? {
+            return Ok(next);
+        }
+        next.updated_at_unix_ms = unix_ms();
+
+        self.persist(&next)?;
+        guard.insert(job_id.to_string(), next.clone());
+        Ok(next)
+    }
+
+    fn persist(&self, record: &JobRecord) -> Result<(), PersistentJobStoreError> {
+        let stored = match self.qfs.load_job_meta(&record.job_id) {
+            Ok(meta) => Some(meta),
+            Err(CircuitFsError::NotFound { .. }) => None,
+            Err(err) => return Err(err.into()),
+        };
+        let meta = meta_from_record(record, stored).map_err(|err| PersistentJobStoreError::Corrupt {
+            job_id: record.job_id.clone(),
+            message: err.to_string(),
+        })?;
+        self.qfs.store_job_meta(&meta)?;
+        Ok(())
+    }
+}
+
+/// `record` merged over the `stored` document, keeping the fields it does not own.
+fn meta_from_record(record: &JobRecord, stored: Option<JobMeta>) -> Result<JobMeta, serde_json::Error> {
+    let mut document = match stored {
+        Some(stored) => serde_json::to_value(stored)?,
+        None => serde_json::Value::Object(serde_json::Map::new()),
+    };
+    if let (Some(fields), serde_json::Value::Object(updates)) =
+        (document.as_object_mut(), serde_json::to_value(record)?)
+    {
+        fields.extend(updates);
+    }
+    let mut meta: JobMeta = serde_json::from_value(document)?;
+    meta.schema_version = JOB_META_SCHEMA_VERSION.to_string();
+    Ok(meta)
+}
+
+fn record_from_meta(meta: JobMeta) -> Result<JobRecord, serde_json::Error> {
+    serde_json::from_value(serde_json::to_value(meta)?)
+}
@@ -0,0 +1,213 @@
+//! Simulated stage durations and failure injection for the job pipeline.
+//!
+//! The fixture pipeline compiles, queues and runs a job as fast as it can and
+//! always succeeds, which leaves clients nothing to time out on, retry or report.
+//! A [`PipelineConfig`] holds each of those stages for a while and can fail one of
+//! them with a chosen error code, like a real backend would. The kernel reads it
+//! from the `EIGEN_SIM_*` variables unless [`crate::rpc::serve_with_config`] is
+//! given one.
+//!
+//! With [`PipelineConfig::dev_mode`] set, a request may also override the config
+//! for its own job through `EnqueueJobRequest.simulation`, whose keys are the
+//! field names: `compile_ms`, `queue_ms`, `run_ms`, `fail_stage` and `fail_code`.
+//!
+//! This is for development and tests only. A production kernel leaves every
+//! `EIGEN_SIM_*` variable unset, which is [`PipelineConfig::default`] and leaves
+//! the pipeline untouched; the kernel warns at startup when it is not.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// How long the compile stage takes, in milliseconds.
+pub const COMPILE_MS_ENV: &str = "EIGEN_SIM_COMPILE_MS";
+/// How long a job waits in the queue once scheduled, in milliseconds.
+pub const QUEUE_MS_ENV: &str = "EIGEN_SIM_QUEUE_MS";
+/// How long each execution attempt takes, in milliseconds.
+pub const RUN_MS_ENV: &str = "EIGEN_SIM_RUN_MS";
+/// Stage that fails on every job: `compile`, `queue` or `run`.
+pub const FAIL_STAGE_ENV: &str = "EIGEN_SIM_FAIL_STAGE";
+/// Error code of the injected failure, [`DEFAULT_FAIL_CODE`] when unset.
+pub const FAIL_CODE_ENV: &str = "EIGEN_SIM_FAIL_CODE";
+/// `true` lets requests override the config for their own job.
+pub const DEV_MODE_ENV: &str = "EIGEN_SIM_DEV_MODE";
+
+/// Error code of an injected failure unless one is configured.
+pub const DEFAULT_FAIL_CODE: &str = "EIGEN_SIM_ERROR";
+
+/// Pipeline stage whose duration and failure can be simulated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SimulatedStage {
+    Compile,
+    /// Waiting for execution capacity after scheduling.
+    Queue,
+    /// Every execution attempt.
+    Run,
+}
+
+impl SimulatedStage {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Compile => "compile",
+            Self::Queue => "queue",
+            Self::Run => "run",
+        }
+    }
+}
+
+impl fmt::Display for SimulatedStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for SimulatedStage {
+    type Err = String;
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "compile" => Ok(Self::Compile),
+            "queue" => Ok(Self::Queue),
+            "run" => Ok(Self::Run),
+            other => Err(format!("unknown stage {other:?}; expected compile, queue or run")),
+        }
+    }
+}
+
+/// Simulated stage durations, and the stage failed on every job if any.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PipelineConfig {
+    pub compile_ms: u64,
+    pub queue_ms: u64,
+    pub run_ms: u64,
+    pub fail_stage: Option<SimulatedStage>,
+    /// Error code the failed job reports.
+    pub fail_code: String,
+    /// Whether requests may override this config for their own job.
+    pub dev_mode: bool,
+}
+
+impl Default for PipelineConfig {
+    /// No delays and no failures: the pipeline as it runs without simulation.
+    fn default() -> Self {
+        Self {
+            compile_ms: 0,
+            queue_ms: 0,
+            run_ms: 0,
+            fail_stage: None,
+            fail_code: DEFAULT_FAIL_CODE.to_string(),
+            dev_mode: false,
+        }
+    }
+}
+
+impl PipelineConfig {
+    /// Reads the `EIGEN_SIM_*` variables; unset or unparsable ones keep their default.
+    pub fn from_env() -> Self {
+        Self::from_vars(|key| std::env::var(key).ok())
+    }
+
+    /// Like [`PipelineConfig::from_env`], reading variables through `env`.
+    pub fn from_vars(env: impl Fn(&str) -> Option<String>) -> Self {
+        let millis = |key: &str| env(key).and_then(|raw| raw.trim().parse::<u64>().ok()).unwrap_or_default();
+        let defaults = Self::default();
+        Self {
+            compile_ms: millis(COMPILE_MS_ENV),
+            queue_ms: millis(QUEUE_MS_ENV),
+            run_ms: millis(RUN_MS_ENV),
+            fail_stage: env(FAIL_STAGE_ENV).and_then(|raw| raw.parse().ok()),
+            fail_code: env(FAIL_CODE_ENV)
+                .map(|raw| raw.trim().to_string())
+                .filter(|code| !code.is_empty())
+                .unwrap_or(defaults.fail_code),
+            dev_mode: env(DEV_MODE_ENV).is_some_and(|raw| matches!(raw.trim(), "1" | "true" | "TRUE" | "yes")),
+        }
+    }
+
+    /// This config with the per-request `overrides` applied. An empty `fail_stage`
+    /// (or `none`) turns failure injection off for the job.
+    pub fn with_overrides(&self, overrides: &BTreeMap<String, String>) -> Result<Self, String> {
+        let mut config = self.clone();
+        for (key, raw) in overrides {
+            let millis = || raw.trim().parse::<u64>().map_err(|_| format!("simulation.{key} must be milliseconds"));
+            match key.as_str() {
+                "compile_ms" => config.compile_ms = millis()?,
+                "queue_ms" => config.queue_ms = millis()?,
+                "run_ms" => config.run_ms = millis()?,
+                "fail_stage" => {
+                    config.fail_stage = match raw.trim() {
+                        "" | "none" => None,
+                        stage => Some(stage.parse()?),
+                    }
+                }
+                "fail_code" if raw.trim().is_empty() => return Err("simulation.fail_code is blank".to_string()),
+                "fail_code" => config.fail_code = raw.trim().to_string(),
+                _ => return Err(format!("unknown simulation key {key:?}")),
+            }
+        }
+        Ok(config)
+    }
+
+    /// How long `stage` is held before it does its work.
+    pub fn delay(&self, stage: SimulatedStage) -> Duration {
+        Duration::from_millis(match stage {
+            SimulatedStage::Compile => self.compile_ms,
+            SimulatedStage::Queue => self.queue_ms,
+            SimulatedStage::Run => self.run_ms,
+        })
+    }
+
+    pub fn fails(&self, stage: SimulatedStage) -> bool {
+        self.fail_stage == Some(stage)
+    }
+
+    /// Whether this config changes the pipeline at all, or lets requests change it.
+    pub fn is_active(&self) -> bool {
+        *self != Self::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn config_is_read_from_the_sim_variables() {
+        let vars = BTreeMap::from([
+            (COMPILE_MS_ENV, "250"),
+            (RUN_MS_ENV, "not-a-number"),
+            (FAIL_STAGE_ENV, "Compile"),
+            (FAIL_CODE_ENV, "QPU_CALIBRATION_FAILED"),
+            (DEV_MODE_ENV, "true"),
+        ]);
+        let config = PipelineConfig::from_vars(|key| vars.get(key).map(|value| value.to_string()));
+        assert_eq!(config.delay(SimulatedStage::Compile), Duration::from_millis(250));
+        assert_eq!(config.delay(SimulatedStage::Run), Duration::ZERO);
+        assert!(config.fails(SimulatedStage::Compile));
+        assert_eq!(config.fail_code, "QPU_CALIBRATION_FAILED");
+        assert!(config.dev_mode);
+
+        assert!(config.is_active());
+        assert!(!PipelineConfig::from_vars(|_| None).is_active());
+    }
+
+    #[test]
+    fn requests_override_individual_fields() {
+        let base = PipelineConfig { fail_stage: Some(SimulatedStage::Run), ..PipelineConfig::default() };
+        let overrides = |pairs: &[(&str, &str)]| {
+            let pairs = pairs.iter().map(|(key, value)| (key.to_string(), value.to_string())).collect();
+            base.with_overrides(&pairs)
+        };
+
+        let config = overrides(&[("queue_ms", "40"), ("fail_stage", "none")]).expect("valid overrides");
+        assert_eq!((config.queue_ms, config.fail_stage), (40, None));
+        let config = overrides(&[("fail_stage", "queue"), ("fail_code", "QUEUE_LOST")]).expect("valid overrides");
+        assert_eq!((config.fail_stage, config.fail_code.as_str()), (Some(SimulatedStage::Queue), "QUEUE_LOST"));
+
+        assert!(overrides(&[("run_ms", "-5")]).is_err());
+        assert!(overrides(&[("fail_stage", "optimize")]).is_err());
+        assert!(overrides(&[("fail_code", " ")]).is_err());
+        assert!(overrides(&[("speed", "fast")]).is_err());
+    }
+}
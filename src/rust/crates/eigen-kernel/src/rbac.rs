@@ -0,0 +1,186 @@
+//! Role-based authorization for the KernelGateway gRPC service.
+//!
+//! [`RbacLayer`] sits beneath the [`AuthInterceptor`](crate::auth::AuthInterceptor),
+//! where the JWT claims of the call are already in the request extensions, and
+//! rejects with `PermissionDenied` every call whose `role` claim lacks the
+//! [`Permission`] its method requires ([`required_permission`]). Calls authenticated
+//! without claims (API keys, the shared bearer secret, dev mode) are not role-checked.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use security_module::{Claims, Permission, RbacPolicy, role};
+use tonic::Status;
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// Path of the TOML [`RbacPolicy`] file; role checks are off when unset.
+pub const RBAC_POLICY_FILE_ENV: &str = "EIGEN_KERNEL_RBAC_POLICY_FILE";
+
+/// Permission needed to call the KernelGateway method named `method`, e.g.
+/// `GetJobStatus`. `None` for methods no role is granted, which JWT callers are
+/// then refused.
+pub fn required_permission(method: &str) -> Option<Permission> {
+    Some(match method {
+        "EnqueueJob" | "EnqueueJobBatch" | "BatchEnqueueJobs" | "RetryJob" => Permission::SubmitJob,
+        "CancelJob" | "PauseJob" | "ResumeJob" => Permission::CancelJob,
+        "GetJobStatus" | "WatchJobStatus" | "StreamJobUpdates" | "GetJobHistory" | "GetDispatchRationale" => {
+            Permission::ReadJobStatus
+        }
+        "GetJobResults" | "GetJobLogs" => Permission::ReadJobResults,
+        "ListJobs" => Permission::ListJobs,
+        "DeleteJob" => Permission::DeleteJob,
+        // Reveals how busy the kernel is, not what any job contains.
+        "GetKernelInfo" => Permission::ReadJobStatus,
+        _ => return None,
+    })
+}
+
+/// Why `claims` may not call `method`, or `None` when it may.
+fn denial(policy: &RbacPolicy, claims: &Claims, method: &str) -> Option<String> {
+    let Some(permission) = required_permission(method) else {
+        return Some(format!("{method} is not granted to any role"));
+    };
+    if policy.is_allowed(claims, permission) {
+        return None;
+    }
+    Some(match role(claims) {
+        Some(role) => format!("role {role} lacks the {permission} permission needed for {method}"),
+        None => format!("token carries no role; {method} needs the {permission} permission"),
+    })
+}
+
+/// Tower layer adding [`RbacService`] around the KernelGateway service.
+#[derive(Debug, Clone, Default)]
+pub struct RbacLayer {
+    policy: Option<Arc<RbacPolicy>>,
+}
+
+impl RbacLayer {
+    /// Checks JWT callers against `policy`; with `None` every call passes.
+    pub fn new(policy: Option<Arc<RbacPolicy>>) -> Self {
+        Self { policy }
+    }
+}
+
+impl<S> Layer<S> for RbacLayer {
+    type Service = RbacService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RbacService { inner, policy: self.policy.clone() }
+    }
+}
+
+/// Service installed by [`RbacLayer`].
+#[derive(Debug, Clone)]
+pub struct RbacService<S> {
+    inner: S,
+    policy: Option<Arc<RbacPolicy>>,
+}
+
+impl<S, ReqBody, ResBody> Service<http::Request<ReqBody>> for RbacService<S>
+where
+    S: Service<http::Request<ReqBody>, Response = http::Response<ResBody>>,
+    S::Future: Send + 'static,
+    S::Error: Send + 'static,
+    ResBody: Default + Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: http::Request<ReqBody>) -> Self::Future {
+        if let (Some(policy), Some(claims)) = (&self.policy, request.extensions().get::<Claims>()) {
+            let method = request.uri().path().rsplit('/').next().unwrap_or_default();
+            if let Some(message) = denial(policy, claims, method) {
+                tracing::warn!(subject = %claims.sub, method, "{message}");
+                let response = Status::permission_denied(message).into_http();
+                return Box::pin(std::future::ready(Ok(response)));
+            }
+        }
+        Box::pin(self.inner.call(request))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+    use std::task::Waker;
+
+    use security_module::ROLE_CLAIM;
+    use tonic::Code;
+
+    /// Answers every call it is handed with an empty OK response.
+    #[derive(Clone)]
+    struct Ok200;
+
+    impl Service<http::Request<()>> for Ok200 {
+        type Response = http::Response<()>;
+        type Error = std::convert::Infallible;
+        type Future = std::future::Ready<Result<Self::Response, Self::Error>>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _request: http::Request<()>) -> Self::Future {
+            std::future::ready(Ok(http::Response::new(())))
+        }
+    }
+
+    fn policy() -> Arc<RbacPolicy> {
+        let policy = RbacPolicy::from_toml_str(
+            "[roles]\nviewer = [\"read_job_status\", \"read_job_results\", \"list_jobs\"]\n",
+        )
+        .expect("policy");
+        Arc::new(policy)
+    }
+
+    fn claims(role: &str) -> Claims {
+        let extra = BTreeMap::from([(ROLE_CLAIM.to_string(), serde_json::Value::from(role))]);
+        Claims { sub: "alice".to_string(), iss: String::new(), exp: 0, iat: None, extra }
+    }
+
+    /// gRPC status of calling `method` with `claims`; `Ok` when the call got through.
+    fn call(layer: &RbacLayer, method: &str, claims: Option<Claims>) -> Code {
+        let mut service = layer.layer(Ok200);
+        let mut request = http::Request::builder()
+            .uri(format!("/eigen.internal.v1.KernelGatewayService/{method}"))
+            .body(())
+            .expect("request");
+        if let Some(claims) = claims {
+            request.extensions_mut().insert(claims);
+        }
+        let mut response = service.call(request);
+        let Poll::Ready(response) = response.as_mut().poll(&mut Context::from_waker(Waker::noop())) else {
+            panic!("the layer answers immediately");
+        };
+        let response = response.expect("infallible");
+        Status::from_header_map(response.headers()).map_or(Code::Ok, |status| status.code())
+    }
+
+    #[test]
+    fn viewer_may_get_job_status_but_not_cancel_jobs() {
+        let layer = RbacLayer::new(Some(policy()));
+        assert_eq!(call(&layer, "GetJobStatus", Some(claims("viewer"))), Code::Ok);
+        assert_eq!(call(&layer, "ListJobs", Some(claims("viewer"))), Code::Ok);
+        assert_eq!(call(&layer, "GetKernelInfo", Some(claims("viewer"))), Code::Ok);
+        assert_eq!(call(&layer, "CancelJob", Some(claims("viewer"))), Code::PermissionDenied);
+        assert_eq!(call(&layer, "EnqueueJob", Some(claims("viewer"))), Code::PermissionDenied);
+        assert_eq!(call(&layer, "GetJobStatus", Some(claims("auditor"))), Code::PermissionDenied);
+    }
+
+    #[test]
+    fn calls_without_claims_or_policy_are_not_role_checked() {
+        assert_eq!(call(&RbacLayer::new(Some(policy())), "CancelJob", None), Code::Ok);
+        assert_eq!(call(&RbacLayer::default(), "CancelJob", Some(claims("viewer"))), Code::Ok);
+        assert!(required_permission("SomeFutureMethod").is_none());
+    }
+}
@@ -7,35 +7,61 @@
 //! - explicit downstream adapters for handoff points
 //! - canonical terminal state / error metadata propagation
 
+use std::borrow::Cow;
 use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
 use std::fmt;
 use std::fs;
+use std::io::{Read, Seek, SeekFrom};
 use std::net::SocketAddr;
 use std::pin::Pin;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use std::time::Instant;
 
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use observability::ObservabilityConfig;
+use observability::metrics::{JobMetrics, JobOutcome, TimingLayer};
+use observability::request_id::{REQUEST_ID_HEADER, RequestIdLayer};
+use observability::otel;
+use observability::trace_context::{self, Context as TraceContext};
 use parking_lot::Mutex;
 use prost_types::{Duration as ProtoDuration, Timestamp};
+use tokio::sync::{mpsc, watch};
 use tokio_stream::iter;
+use tokio_stream::wrappers::{ReceiverStream, TcpListenerStream};
 use tokio_stream::Stream;
-use tonic::transport::Endpoint;
+use tonic::codegen::InterceptedService;
+use tonic::service::LayerExt;
+use tonic::transport::{Endpoint, ServerTlsConfig};
 use tonic::{Code, Request, Response, Status};
 use tracing::Instrument;
 use uuid::Uuid;
 use sha2::{Digest, Sha256};
 
+use qrtx::queue::{JobQueue, Priority};
+use qrtx::state_machine::JobState;
 use qfs::{
-    CircuitFsLocal, CompiledArtifactLineage, CompiledArtifactProvenance, ReleaseEvidenceBundle,
+    CircuitFsBackend, CircuitFsError, CircuitFsLocal, CompiledArtifactLineage, CompiledArtifactProvenance, ReleaseEvidenceBundle,
     ReleaseEvidenceManifest, ReleaseEvidenceProvenanceReport, ResultArtifactDescriptor,
-    ResultEnvelope, ScientificMeasurement,
+    ResultEnvelope, ScientificMeasurement, SourceBundle,
 };
 use resource_manager::{
-    SCHEDULER_DECISION_VERSION, SCHEDULING_POLICY_BUNDLE_ID, SCHEDULING_POLICY_BUNDLE_VERSION,
+    QuotaError, QuotaLease, QuotaManager, QuotaPolicy, SlotLease, SlotPool, SCHEDULER_DECISION_VERSION,
+    SCHEDULING_POLICY_BUNDLE_ID, SCHEDULING_POLICY_BUNDLE_VERSION,
 };
-
+use security_module::{ADMIN_SCOPE, Action, Decision, PolicyEngine, Principal, Resource};
+
+use crate::auth::{AuthConfig, AuthInterceptor, Caller, extract_caller};
+use crate::pipeline_config::{DEV_MODE_ENV, PipelineConfig, SimulatedStage};
+use crate::simulator::{DEFAULT_SEED, fnv1a64, simulate_counts};
+use crate::tls::TlsConfig;
+use crate::validation::{self, ValidationError, ValidationLimits, check_job_id};
+use crate::health::{self, EnqueueFailures, KernelHealth};
+use crate::job_store::{JobRecord, MAX_TRANSITION_HISTORY};
+use crate::persistent_job_store::PersistentJobStore;
 use crate::proto::compilation_service_client::CompilationServiceClient;
 use crate::proto::driver_manager_service_client::DriverManagerServiceClient;
 use crate::proto::kernel_gateway_service_server::{
@@ -44,39 +70,451 @@ use crate::proto::kernel_gateway_service_server::{
 use crate::proto::optimizer_service_client::OptimizerServiceClient;
 use crate::proto::stream_job_updates_response::JobUpdateEnvelope;
 use crate::proto::{
-    CircuitPayload, CompileCircuitRequest, ExecuteCircuitRequest, GraphEncodingContext,
+    CircuitPayload, CompileCircuitRequest, DeleteJobRequest, DeleteJobResponse, ExecuteCircuitRequest,
+    GraphEncodingContext,
     OptimizationObjective, OptimizerContractEnvelope, OptimizerPolicy,
     OptimizerRankingSemantics, OptimizerServiceOptimizeCircuitRequest, RequestMetadata,
     TopologyContext, CancelJobRequest, CancelJobResponse, DispatchRationale, EnqueueJobRequest,
     WorkloadContract, WorkloadTopology,
-    EnqueueJobResponse, GetDispatchRationaleRequest, GetDispatchRationaleResponse,
-    GetJobResultsRequest, GetJobResultsResponse, GetJobStatusRequest, GetJobStatusResponse,
-    StreamJobUpdatesRequest, StreamJobUpdatesResponse, TaskState,
+    BatchEnqueueJobsRequest, BatchEnqueueJobsResponse,
+    EnqueueJobBatchRequest, EnqueueJobBatchResponse, EnqueueJobBatchResult, EnqueueJobResponse,
+    GetDispatchRationaleRequest, GetDispatchRationaleResponse,
+    GetJobHistoryRequest, GetJobHistoryResponse, GetJobResultsRequest, GetJobResultsResponse,
+    GetJobStatusRequest, GetJobStatusResponse, GetKernelInfoRequest, GetKernelInfoResponse,
+    JobSummary, JobTransition, ListJobsRequest,
+    ListJobsResponse, PauseJobRequest, PauseJobResponse, ResumeJobRequest, ResumeJobResponse,
+    RetryJobRequest, RetryJobResponse, StreamJobUpdatesRequest,
+    StreamJobUpdatesResponse, TaskState, WatchJobStatusRequest, GetJobLogsRequest, JobLogLine,
 };
 
-/// Runs the kernel gRPC server on the provided address.
-pub async fn serve(addr: SocketAddr) -> Result<(), Box<dyn std::error::Error>> {
-    let runtime = Arc::new(KernelRuntimeStore::default());
-    let adapters = Arc::new(FixtureAdapters::from_env());
-    let svc = KernelGatewaySvc::new(runtime, adapters);
+/// Delay between reporting NOT_SERVING and stopping the server, so health
+/// probes see the kernel leave before its listener goes away.
+const SHUTDOWN_HEALTH_GRACE: Duration = Duration::from_millis(500);
+
+/// Runs the kernel gRPC server on the provided address, plus the Prometheus
+/// `/metrics` endpoint on `metrics_addr` when one is given, until SIGINT or SIGTERM.
+/// Running jobs then get `EIGEN_SHUTDOWN_GRACE_SECS` (30 by default) to finish.
+///
+/// Every KernelGateway method goes through [`AuthInterceptor`], built from `auth`
+/// or, when that is `None`, from [`AuthConfig::from_env`]. With no API key or
+/// bearer token configured the kernel accepts every call (dev mode).
+///
+/// With `tls` the listener only speaks TLS, and mutual TLS when it names a client
+/// CA; `main` reads it with [`TlsConfig::from_env`]. Startup fails before binding
+/// when its files cannot be read.
+///
+/// The server also answers gRPC reflection (v1), so tools such as `grpcurl` can
+/// list and describe its services.
+pub async fn serve(
+    addr: SocketAddr,
+    metrics_addr: Option<SocketAddr>,
+    auth: Option<AuthConfig>,
+    tls: Option<TlsConfig>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let auth = match auth {
+        Some(config) => config,
+        None => AuthConfig::from_env()?,
+    };
+    let auth = AuthInterceptor::from_config(&auth);
+    let tls = match tls {
+        Some(config) => {
+            tracing::info!(mutual = config.is_mutual(), "kernel gRPC TLS enabled");
+            Some(config.server_tls_config()?)
+        }
+        None => None,
+    };
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    serve_with_shutdown(listener, metrics_addr, auth, tls, shutdown_signal()).await
+}
+
+/// Same as [`serve`] on an already bound `listener` with an explicit `auth`, so
+/// in-process callers (e.g. client tests) can use an ephemeral port. Runs until
+/// the task is dropped.
+pub async fn serve_with_listener(
+    listener: tokio::net::TcpListener,
+    metrics_addr: Option<SocketAddr>,
+    auth: AuthInterceptor,
+) -> Result<(), Box<dyn std::error::Error>> {
+    serve_with_shutdown(listener, metrics_addr, auth, None, std::future::pending()).await
+}
+
+/// Same as [`serve_with_listener`], shutting down gracefully once `shutdown`
+/// completes: new jobs are refused and the health service reports NOT_SERVING,
+/// running job DAGs get the shutdown grace to finish and the jobs still unfinished
+/// after it end in `ERROR` with `SHUTDOWN_INTERRUPTED`. The server then stops
+/// accepting connections and waits for in-flight calls. With `tls` the listener
+/// only accepts TLS connections.
+pub async fn serve_with_shutdown(
+    listener: tokio::net::TcpListener,
+    metrics_addr: Option<SocketAddr>,
+    auth: AuthInterceptor,
+    tls: Option<ServerTlsConfig>,
+    shutdown: impl Future<Output = ()>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    serve_with_config(listener, metrics_addr, auth, tls, PipelineConfig::from_env(), shutdown).await
+}
+
+/// Same as [`serve_with_shutdown`], simulating stage durations and failures as
+/// `pipeline` says instead of as the `EIGEN_SIM_*` variables do.
+pub async fn serve_with_config(
+    listener: tokio::net::TcpListener,
+    metrics_addr: Option<SocketAddr>,
+    auth: AuthInterceptor,
+    tls: Option<ServerTlsConfig>,
+    pipeline: PipelineConfig,
+    shutdown: impl Future<Output = ()>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if pipeline.is_active() {
+        tracing::warn!(?pipeline, "pipeline simulation is on; it is meant for development and tests only");
+    }
+    let adapters = FixtureAdapters::from_env();
+    let job_meta = PersistentJobStore::open(adapters.qfs.clone())?;
+    let runtime = Arc::new(KernelRuntimeStore {
+        metrics: Arc::new(JobMetrics::with_config(&ObservabilityConfig::from_env())),
+        dispatcher: Arc::new(ExecutionDispatcher::from_env()),
+        quota: quota_manager_from_env(),
+        idempotency: IdempotencyIndex::from_env(),
+        default_deadline: default_job_deadline_from_env(),
+        enqueue_batch_limit: EnqueueBatchLimit::from_env(),
+        validation_limits: ValidationLimits::from_env(),
+        enqueue_failures: EnqueueFailures::from_env(),
+        pipeline,
+        job_meta: Some(job_meta),
+        ..KernelRuntimeStore::default()
+    });
+    let restored = runtime.restore_jobs();
+    if restored > 0 {
+        tracing::info!(jobs = restored, "restored jobs from QFS");
+    }
+    serve_runtime(listener, metrics_addr, auth, tls, runtime, adapters, shutdown, shutdown_grace_from_env()).await
+}
+
+/// Body of [`serve_with_shutdown`] for a given runtime and adapters; running DAGs
+/// get `shutdown_grace` once `shutdown` completes.
+#[allow(clippy::too_many_arguments)]
+async fn serve_runtime(
+    listener: tokio::net::TcpListener,
+    metrics_addr: Option<SocketAddr>,
+    auth: AuthInterceptor,
+    tls: Option<ServerTlsConfig>,
+    runtime: Arc<KernelRuntimeStore>,
+    adapters: FixtureAdapters,
+    shutdown: impl Future<Output = ()>,
+    shutdown_grace: Duration,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let addr = listener.local_addr()?;
+    let (health, health_service) = KernelHealth::new(adapters.qfs.root_path()).await;
+    let metrics = runtime.metrics.clone();
+    let timing = TimingLayer::new(metrics.clone());
+    let svc = KernelGatewaySvc::new(runtime.clone(), Arc::new(adapters)).with_policy(auth.policy());
+    // Like health, reflection only describes the API and is served without auth.
+    let reflection = tonic_reflection::server::Builder::configure()
+        .register_encoded_file_descriptor_set(crate::proto::FILE_DESCRIPTOR_SET)
+        .build_v1()?;
+
+    if let Some(metrics_addr) = metrics_addr {
+        let listener = tokio::net::TcpListener::bind(metrics_addr).await?;
+        tracing::info!(addr = %metrics_addr, "kernel metrics endpoint starting");
+        tokio::spawn(async move {
+            if let Err(err) = crate::metrics::serve(listener, metrics).await {
+                tracing::error!(error = %err, "kernel metrics endpoint failed");
+            }
+        });
+    }
 
-    tracing::info!(%addr, "kernel gRPC server starting");
-    tonic::transport::Server::builder()
-        .add_service(KernelGatewayServiceServer::new(svc))
-        .serve(addr)
+    tracing::info!(%addr, auth_enabled = auth.is_enabled(), "kernel gRPC server starting");
+    // Beneath the interceptor, so it sees the claims of the authenticated JWT.
+    let rbac = auth.rbac_layer();
+    health.set_serving().await;
+    let qfs_checks = health.spawn_qfs_checks(health::QFS_CHECK_INTERVAL);
+    let enqueue_watch = health.spawn_enqueue_failure_watch(&runtime.enqueue_failures);
+    let deadline_reaper = runtime.spawn_deadline_reaper(DEADLINE_REAPER_INTERVAL);
+    let job_persister = runtime.spawn_job_persister(JOB_PERSIST_INTERVAL);
+    let shutdown = async {
+        shutdown.await;
+        tracing::info!("kernel gRPC server shutting down");
+        qfs_checks.abort();
+        enqueue_watch.abort();
+        deadline_reaper.abort();
+        job_persister.abort();
+        health.set_not_serving().await;
+        let interrupted = runtime.drain(shutdown_grace).await;
+        if !interrupted.is_empty() {
+            tracing::warn!(jobs = ?interrupted, "jobs interrupted by shutdown");
+        }
+        runtime.persist_jobs();
+        tokio::time::sleep(SHUTDOWN_HEALTH_GRACE).await;
+    };
+    let mut server = tonic::transport::Server::builder();
+    if let Some(tls) = tls {
+        server = server.tls_config(tls)?;
+    }
+    server
+        .layer(RequestIdLayer::new())
+        .layer(timing)
+        .add_service(health_service)
+        .add_service(reflection)
+        .add_service(InterceptedService::new(rbac.named_layer(KernelGatewayServiceServer::new(svc)), auth))
+        .serve_with_incoming_shutdown(TcpListenerStream::new(listener), shutdown)
         .await?;
     Ok(())
 }
 
+/// Completes on Ctrl-C, or on SIGTERM on Unix.
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(err) = tokio::signal::ctrl_c().await {
+            tracing::error!(error = %err, "failed to listen for Ctrl-C");
+            std::future::pending::<()>().await;
+        }
+    };
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(err) => {
+                tracing::error!(error = %err, "failed to listen for SIGTERM");
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+    tokio::select! {
+        () = ctrl_c => {}
+        () = terminate => {}
+    }
+}
+
 #[derive(Clone)]
 struct KernelGatewaySvc {
     runtime: Arc<KernelRuntimeStore>,
     adapters: Arc<dyn OrchestrationAdapters>,
+    /// Rules JWT callers of the job RPCs are checked against; `None` allows every call.
+    policy: Option<Arc<PolicyEngine>>,
+    /// When the service was built, in Unix milliseconds; `GetKernelInfo` reports the uptime.
+    started_at_ms: Arc<AtomicI64>,
 }
 
 impl KernelGatewaySvc {
     fn new(runtime: Arc<KernelRuntimeStore>, adapters: Arc<dyn OrchestrationAdapters>) -> Self {
-        Self { runtime, adapters }
+        let started_at_ms = Arc::new(AtomicI64::new(timestamp_to_ms(&ts_now()) as i64));
+        Self { runtime, adapters, policy: None, started_at_ms }
+    }
+
+    fn with_policy(mut self, policy: Option<Arc<PolicyEngine>>) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Refuses `action` on `resource` with `PermissionDenied` when the policy denies it
+    /// to `caller`. Calls authenticated without claims (API keys, the shared bearer
+    /// secret, dev mode) are not checked.
+    fn authorize(&self, caller: &Caller, action: Action, resource: &Resource) -> Result<(), Status> {
+        let (Some(policy), Some(claims)) = (&self.policy, caller.claims()) else {
+            return Ok(());
+        };
+        match policy.check(&Principal::from_claims(claims), action, resource) {
+            Decision::Allow { .. } => Ok(()),
+            Decision::Deny { rule } => {
+                tracing::warn!(subject = %claims.sub, %action, rule = %rule, "denied by policy");
+                Err(Status::permission_denied(format!("{action} denied by policy rule {rule:?}")))
+            }
+        }
+    }
+
+    /// Whether `caller` may learn that `job` exists: it submitted the job, holds
+    /// [`ADMIN_SCOPE`], or the policy lets it read the job's status. API-key and
+    /// shared-secret callers see the jobs submitted with the same credential; only
+    /// dev-mode calls, which nobody authenticated, see every job.
+    fn can_see(&self, caller: &Caller, job: &JobRuntimeRecord) -> bool {
+        let claims = match caller {
+            Caller::Jwt(claims) => claims,
+            Caller::Service(service) => return job.owner() == service.subject,
+            Caller::Anonymous => return true,
+        };
+        if job.owner() == claims.sub || claims.has_scope(ADMIN_SCOPE) {
+            return true;
+        }
+        self.policy.as_ref().is_some_and(|policy| {
+            policy.check(&Principal::from_claims(claims), Action::Status, &job.resource()).is_allowed()
+        })
+    }
+
+    /// The job `job_id` if the caller may see it. Jobs it may not see are
+    /// `NOT_FOUND` like missing ones, so their existence does not leak.
+    fn visible_job(&self, caller: &Caller, job_id: &str) -> Result<JobRuntimeRecord, Status> {
+        self.runtime
+            .get(job_id)
+            .filter(|job| self.can_see(caller, job))
+            .ok_or_else(|| Status::not_found("job not found"))
+    }
+
+    /// Body of `EnqueueJob`, also run for every entry of `EnqueueJobBatch`: creates the
+    /// job (or finds the one this submission already created), stores its source
+    /// bundle and starts the DAG of a new job. The job is created first, so a
+    /// submission refused for quota or idempotency leaves nothing in QFS.
+    async fn enqueue(
+        &self,
+        req: EnqueueJobRequest,
+        headers: &http::HeaderMap,
+        caller: &Caller,
+    ) -> Result<EnqueueJobResponse, Status> {
+        let submission = self.admit(&req, caller)?;
+        let (job, lease) = self.runtime.create_or_get_job(submission.clone())?;
+        let recorded = match self.adapters.store_source_bundle(&submission).await {
+            Ok(source_bundle_ref) => self.record_source_bundle(&job, source_bundle_ref),
+            Err(err) => Err(err.into_status()),
+        };
+        if let Err(status) = recorded {
+            self.roll_back_created(vec![(job, lease)]).await;
+            return Err(status);
+        }
+        self.spawn_enqueued(&job, lease, submission, headers);
+        Ok(job.enqueue_response())
+    }
+
+    /// Undoes an enqueue that failed before starting anything: the jobs it created
+    /// lose their source bundles, their records and their quota leases. Jobs that
+    /// existed before the call are left alone.
+    async fn roll_back_created(&self, created: Vec<CreatedJob>) {
+        let mut job_ids = Vec::new();
+        for (job, lease) in created {
+            let Some(lease) = lease else {
+                continue;
+            };
+            if let Err(err) = self.adapters.delete_job_artifacts(&job.job_id).await {
+                tracing::warn!(job_id = %job.job_id, error = %err, "failed to remove source bundle of rolled back job");
+            }
+            self.runtime.quota.release(lease);
+            job_ids.push(job.job_id);
+        }
+        self.runtime.discard_created_jobs(&job_ids);
+    }
+
+    /// Validates and authorizes `req`, returning the submission of the job it asks for.
+    fn admit(&self, req: &EnqueueJobRequest, caller: &Caller) -> Result<NormalizedSubmission, Status> {
+        self.runtime.validation_limits.check_enqueue(req)?;
+        let mut submission = NormalizedSubmission::from_request(req)?;
+        self.runtime.pipeline_config(&submission)?;
+        // An authenticated caller owns the job under its own identity, not the one it claims.
+        match caller {
+            Caller::Jwt(claims) => submission.subject = claims.sub.clone(),
+            Caller::Service(service) => submission.subject = service.subject.clone(),
+            Caller::Anonymous => {}
+        }
+        let resource = Resource { tenant_id: submission.tenant_id.clone(), owner: submission.subject.clone() };
+        self.authorize(caller, Action::Submit, &resource)?;
+        self.runtime.resolve_idempotency_key(submission)
+    }
+
+    fn record_source_bundle(&self, job: &JobRuntimeRecord, source_bundle_ref: Option<String>) -> Result<(), Status> {
+        let Some(source_bundle_ref) = source_bundle_ref else {
+            return Ok(());
+        };
+        self.runtime
+            .set_metadata(&job.job_id, BTreeMap::from([("source_bundle_ref".to_string(), source_bundle_ref)]))
+    }
+
+    /// Starts the DAG of a new job, i.e. one that comes with a quota `lease`.
+    fn spawn_enqueued(
+        &self,
+        job: &JobRuntimeRecord,
+        lease: Option<QuotaLease>,
+        submission: NormalizedSubmission,
+        headers: &http::HeaderMap,
+    ) {
+        if let Some(lease) = lease {
+            let parent = submitted_trace_context(headers, &submission);
+            let rpc_request_id = rpc_request_id(headers);
+            self.spawn_job_dag(
+                job.job_id.clone(),
+                submission,
+                DagStageKind::ValidateEnqueue,
+                lease,
+                parent,
+                rpc_request_id,
+            );
+        }
+    }
+
+    /// Runs the job DAG in the background starting at `resume_from`, terminalizing
+    /// the job if a stage fails. A failed job gets its `results/error.json`, and
+    /// `lease` is handed back once the DAG stops, by which point the job is terminal.
+    ///
+    /// The `job_pipeline` span is a child of `submitted_parent` when given, else of
+    /// the span spawning it (the RPC's), and carries `job.id` and `request.id`. Its
+    /// `rpc_request_id` field is the `x-request-id` of the call that started this run,
+    /// so every pipeline event can be matched to that call's `grpc_request` span.
+    fn spawn_job_dag(
+        &self,
+        job_id: String,
+        submission: NormalizedSubmission,
+        resume_from: DagStageKind,
+        lease: QuotaLease,
+        submitted_parent: Option<TraceContext>,
+        rpc_request_id: Option<String>,
+    ) {
+        let runtime = self.runtime.clone();
+        let adapters = self.adapters.clone();
+        let pipeline = self.runtime.pipelines.start();
+        let mut trace_carrier = HashMap::new();
+        otel::inject_trace_context(&mut trace_carrier);
+
+        tokio::spawn(async move {
+            let _pipeline = pipeline;
+            let span = tracing::info_span!(
+                "job_pipeline",
+                job_id = %job_id,
+                request_id = %submission.request_id,
+                rpc_request_id = tracing::field::Empty,
+            );
+            if let Some(rpc_request_id) = &rpc_request_id {
+                span.record("rpc_request_id", tracing::field::display(rpc_request_id));
+            }
+            let parent = submitted_parent
+                .or_else(|| (!trace_carrier.is_empty()).then(|| otel::extract_trace_context(&trace_carrier)));
+            if let Some(parent) = parent {
+                trace_context::set_parent(&span, parent);
+            }
+            otel::set_attribute(&span, "job.id", job_id.clone());
+            otel::set_attribute(&span, "request.id", submission.request_id.clone());
+            async move {
+                if let Err(err) =
+                    run_job_dag(runtime.clone(), adapters.clone(), job_id.clone(), submission, resume_from).await
+                {
+                    let terminalization = match err.grpc_code {
+                        Code::DeadlineExceeded => runtime.request_deadline_terminalization(&job_id).map(|_| ()),
+                        // A cancel landed between a stage's last check and its write.
+                        Code::Cancelled => {
+                            let stage = runtime
+                                .get(&job_id)
+                                .and_then(|job| job.current_stage)
+                                .unwrap_or(DagStageKind::ValidateEnqueue);
+                            terminalize_control(&runtime, &job_id, stage, stage.key())
+                                .map_err(|err| Status::internal(err.summary))
+                        }
+                        _ => runtime
+                            .request_error_terminalization(&job_id, &err.error_code, &err.summary, &err.details_ref)
+                            .map(|_| ()),
+                    };
+                    if let Err(status) = terminalization {
+                        tracing::error!(error = %status, "kernel terminalization failed");
+                    }
+                    tracing::error!(error = %err, "kernel dag failed");
+                }
+                if let Some(details) = runtime.get(&job_id).as_ref().and_then(error_details_json)
+                    && let Err(err) = adapters.store_error_details(&job_id, &details).await
+                {
+                    tracing::warn!(error = %err, "failed to store job error details");
+                }
+                runtime.quota.release(lease);
+            }
+            .instrument(span)
+            .await;
+        });
     }
 }
 
@@ -400,7 +838,10 @@ struct StageRecord {
     replay_token: String,
 }
 
-#[derive(Debug, Clone)]
+/// Prefix of the `metadata_kvs` entries holding a request's `simulation` overrides.
+const SIMULATION_KEY_PREFIX: &str = "simulation.";
+
+#[derive(Debug, Clone, Default)]
 struct NormalizedSubmission {
     contract_version: String,
     request_id: String,
@@ -419,6 +860,7 @@ struct NormalizedSubmission {
     program_format: String,
     program: Vec<u8>,
     program_hash: String,
+    job_yaml: String,
     target: String,
     priority: i32,
     compiler_options: BTreeMap<String, String>,
@@ -429,20 +871,56 @@ struct NormalizedSubmission {
 }
 
 impl NormalizedSubmission {
+    /// Shots per circuit execution: the request's `shots`, which `from_request` folds
+    /// into `metadata_kvs`, or [`DEFAULT_SHOTS`].
+    fn shots(&self) -> i64 {
+        self.metadata_kvs
+            .get("shots")
+            .and_then(|raw| raw.parse::<i64>().ok())
+            .filter(|shots| *shots > 0)
+            .unwrap_or(DEFAULT_SHOTS)
+    }
+
+    /// Whether the job stops after compilation: the request's `compile_only`, which
+    /// `from_request` folds into `metadata_kvs` so it is part of the fingerprint.
+    fn compile_only(&self) -> bool {
+        self.metadata_kvs
+            .get("compile_only")
+            .is_some_and(|raw| raw.trim().eq_ignore_ascii_case("true"))
+    }
+
+    /// The request's `simulation` overrides, which `from_request` folds into
+    /// `metadata_kvs` under [`SIMULATION_KEY_PREFIX`].
+    fn simulation_overrides(&self) -> BTreeMap<String, String> {
+        self.metadata_kvs
+            .iter()
+            .filter_map(|(key, value)| Some((key.strip_prefix(SIMULATION_KEY_PREFIX)?.to_string(), value.clone())))
+            .collect()
+    }
+
+    /// Simulator seed from the `seed` metadata entry, or [`DEFAULT_SEED`].
+    fn seed(&self) -> u64 {
+        self.metadata_kvs
+            .get("seed")
+            .and_then(|raw| raw.trim().parse::<u64>().ok())
+            .unwrap_or(DEFAULT_SEED)
+    }
+
     fn from_request(request: &EnqueueJobRequest) -> Result<Self, Status> {
         let metadata = request
             .metadata
             .as_ref()
-            .ok_or_else(|| Status::invalid_argument("metadata is required"))?;
+            .ok_or_else(|| ValidationError::missing("metadata"))?;
 
         let name = nonempty(&request.name, "name")?;
         let program = request.program.clone();
         if program.is_empty() {
-            return Err(Status::invalid_argument("program is required"));
+            return Err(ValidationError::new(validation::EMPTY_PROGRAM, "program", "program is required").into());
         }
 
         let program_format = nonempty(&request.program_format, "program_format")?;
         let target = nonempty(&request.target, "target")?;
+        let job_yaml = validated_job_yaml(&request.job_yaml)?;
 
         let contract_version = nonempty_or_default(&metadata.contract_version, "1.0.0");
         let request_id = nonempty(&metadata.request_id, "metadata.request_id")?;
@@ -463,7 +941,16 @@ impl NormalizedSubmission {
             .as_ref()
             .and_then(|d| normalized_deadline_at(d));
         let compiler_options = canonical_string_map(&request.compiler_options);
-        let metadata_kvs = canonical_string_map(&request.metadata_kvs);
+        let mut metadata_kvs = canonical_string_map(&request.metadata_kvs);
+        if request.shots > 0 {
+            metadata_kvs.insert("shots".to_string(), request.shots.to_string());
+        }
+        if request.compile_only {
+            metadata_kvs.insert("compile_only".to_string(), "true".to_string());
+        }
+        for (key, value) in &request.simulation {
+            metadata_kvs.insert(format!("{SIMULATION_KEY_PREFIX}{}", key.trim()), value.trim().to_string());
+        }
         let request_workload = metadata
             .workload
             .as_ref();
@@ -514,6 +1001,7 @@ impl NormalizedSubmission {
             program_format,
             program,
             program_hash,
+            job_yaml,
             target,
             priority: request.priority,
             compiler_options,
@@ -524,6 +1012,23 @@ impl NormalizedSubmission {
         })
     }
 
+    /// Digest of what the job would run, leaving out per-attempt fields such as
+    /// `request_id` and `traceparent`, so an `EnqueueJob` retry matches its original.
+    fn payload_digest(&self) -> String {
+        let payload = serde_json::json!({
+            "project_id": self.project_id,
+            "name": self.name,
+            "program_format": self.program_format,
+            "program_hash": self.program_hash,
+            "job_yaml": self.job_yaml,
+            "target": self.target,
+            "priority": self.priority,
+            "compiler_options": self.compiler_options,
+            "metadata_kvs": self.metadata_kvs,
+        });
+        hash_bytes_hex(payload.to_string().as_bytes())
+    }
+
     fn summary_map(&self) -> BTreeMap<String, String> {
         let mut summary = BTreeMap::from([
             ("contract_version".to_string(), self.contract_version.clone()),
@@ -653,6 +1158,11 @@ fn workflow_failure_ref(job_id: &str) -> String {
     format!("qfs://jobs/{job_id}/workflow/failure.json")
 }
 
+/// Job-level failure details, written once the job has failed or timed out.
+fn job_error_details_ref(job_id: &str) -> String {
+    format!("qfs://jobs/{job_id}/results/error.json")
+}
+
 fn workflow_boundary_ref(job_id: &str, stage: DagStageKind, kind: WorkflowBoundaryKind) -> String {
     format!(
         "qfs://jobs/{job_id}/workflow/boundaries/{:02}-{}-{}.json",
@@ -680,7 +1190,7 @@ fn workflow_output_ref_for_stage_output(job_id: &str, stage: DagStageKind) -> St
 }
 
 fn persist_stage_output_artifact(
-    qfs: &CircuitFsLocal,
+    qfs: &dyn CircuitFsBackend,
     job_id: &str,
     stage: DagStageKind,
     output: &BTreeMap<String, String>,
@@ -692,7 +1202,7 @@ fn persist_stage_output_artifact(
             output_ref.clone(),
         )
     })?;
-    qfs.write_bytes(&output_ref, &payload).map_err(|err| {
+    qfs.write_bytes(Path::new(&output_ref), &payload).map_err(|err| {
         KernelStageError::internal(
             format!("failed to persist {} workflow output: {err}", stage.key()),
             output_ref.clone(),
@@ -701,6 +1211,30 @@ fn persist_stage_output_artifact(
     Ok(())
 }
 
+/// The `results/error.json` document for a job that ended in ERROR or TIMEOUT.
+fn error_details_json(job: &JobRuntimeRecord) -> Option<serde_json::Value> {
+    if !matches!(job.state, TaskState::Error | TaskState::Timeout) {
+        return None;
+    }
+    let failed_stage = job.failed_at_stage.map(|stage| stage.key());
+    let stage_details_ref = failed_stage.and_then(|key| {
+        job.stage_records
+            .iter()
+            .rev()
+            .find(|record| record.stage_key == key)
+            .and_then(|record| record.error_details_ref.clone())
+    });
+    Some(serde_json::json!({
+        "job_id": job.job_id,
+        "state": job.state.as_str_name(),
+        "error_code": job.error_code,
+        "error_summary": job.error_summary,
+        "failed_stage": failed_stage,
+        "stage_details_ref": stage_details_ref,
+        "failed_at_unix_ms": job.completed_at.as_ref().map(|at| timestamp_to_ms(at) as i64),
+    }))
+}
+
 fn canonical_job_id_for_submission(submission: &NormalizedSubmission) -> String {
     format!("job-{}", submission.fingerprint)
 }
@@ -1028,6 +1562,9 @@ struct JobRuntimeRecord {
     submission: NormalizedSubmission,
     state: TaskState,
     current_stage: Option<DagStageKind>,
+    /// Progress the pipeline published for the current stage; `GetJobStatus` shows it
+    /// in place of the per-stage estimate.
+    reported_progress: Option<ReportedProgress>,
     created_at: Timestamp,
     updated_at: Timestamp,
     deadline_at: Option<Timestamp>,
@@ -1047,6 +1584,8 @@ struct JobRuntimeRecord {
     cancel_requested: bool,
     cancel_reason: Option<String>,
     cancellation_fanout_ref: Option<String>,
+    /// Holds an execution slot; a dispatched job can no longer be paused.
+    dispatched: bool,
     reservation_state: Option<String>,
     reservation_token: Option<String>,
     reservation_lease_ms: u64,
@@ -1054,6 +1593,32 @@ struct JobRuntimeRecord {
     retry_attempts: Vec<RetryAttemptRecord>,
     retry_final_reason: Option<String>,
     retry_success_after_retry_total: u32,
+    /// Stage that terminalized the job with ERROR/TIMEOUT; decides where `RetryJob` resumes.
+    failed_at_stage: Option<DagStageKind>,
+    /// Number of operator-initiated `RetryJob` restarts.
+    retry_count: u32,
+    /// State changes in order, capped at `MAX_TRANSITION_HISTORY` entries.
+    history: Vec<StateTransitionRecord>,
+    /// Publishes every state change to `WatchJobStatus` subscribers.
+    state_tx: Arc<watch::Sender<TaskState>>,
+    /// Shared with the owning `KernelRuntimeStore`; records every state change.
+    metrics: Arc<JobMetrics>,
+}
+
+#[derive(Debug, Clone)]
+struct ReportedProgress {
+    /// Already clamped into the range of the state it was reported in.
+    fraction: f32,
+    message: String,
+}
+
+#[derive(Debug, Clone)]
+struct StateTransitionRecord {
+    from: TaskState,
+    to: TaskState,
+    event: String,
+    at: Timestamp,
+    reason: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -1068,26 +1633,240 @@ struct RetryAttemptRecord {
 }
 
 impl JobRuntimeRecord {
+    /// The new `PENDING` record of `submission`.
+    fn new(submission: NormalizedSubmission, deadline_at: Option<Timestamp>, metrics: Arc<JobMetrics>) -> Self {
+        let now = ts_now();
+        let canonical_job_id = canonical_job_id_for_submission(&submission);
+        let workflow_id = format!("workflow-{}", submission.fingerprint);
+        Self {
+            job_id: submission.job_id.clone(),
+            submission: submission.clone(),
+            state: TaskState::Pending,
+            current_stage: Some(DagStageKind::ValidateEnqueue),
+            reported_progress: None,
+            created_at: now,
+            updated_at: now,
+            deadline_at,
+            completed_at: None,
+            stage_records: Vec::new(),
+            workflow_events: vec![WorkflowBoundaryRecord {
+                boundary_id: workflow_boundary_ref(
+                    &submission.job_id,
+                    DagStageKind::ValidateEnqueue,
+                    WorkflowBoundaryKind::WorkflowStarted,
+                ),
+                workflow_id: workflow_id.clone(),
+                job_id: submission.job_id.clone(),
+                kind: WorkflowBoundaryKind::WorkflowStarted,
+                stage_id: None,
+                stage_key: None,
+                order: None,
+                state_before: None,
+                state_after: Some(TaskState::Pending),
+                input_ref: workflow_stage_input_ref(&submission.job_id, DagStageKind::ValidateEnqueue),
+                output_ref: String::new(),
+                artifact_ref: workflow_root_lineage_ref(&canonical_job_id),
+                lineage_ref: workflow_root_lineage_ref(&canonical_job_id),
+                replay_token: hash_bytes_hex(
+                    format!(
+                        "workflow-start:{}:{}",
+                        canonical_job_id,
+                        workflow_root_lineage_ref(&canonical_job_id)
+                    )
+                    .as_bytes(),
+                ),
+                timestamp: ts_now(),
+            }],
+            workflow_id,
+            workflow_root_lineage_ref: workflow_root_lineage_ref(&canonical_job_id),
+            workflow_completion_ref: None,
+            workflow_failure_ref: None,
+            counts: BTreeMap::new(),
+            metadata: submission.workload_metadata.clone(),
+            qfs_result_ref: None,
+            error_code: None,
+            error_summary: None,
+            error_details_ref: None,
+            cancel_requested: false,
+            cancel_reason: None,
+            cancellation_fanout_ref: None,
+            dispatched: false,
+            reservation_state: Some("held".to_string()),
+            reservation_token: Some(reservation_token_for(&submission)),
+            reservation_lease_ms: reservation_lease_ms_for(&submission),
+            reservation_released_reason: None,
+            retry_attempts: Vec::new(),
+            retry_final_reason: None,
+            retry_success_after_retry_total: 0,
+            failed_at_stage: None,
+            retry_count: 0,
+            history: vec![StateTransitionRecord {
+                from: TaskState::Unspecified,
+                to: TaskState::Pending,
+                event: "enqueue".to_string(),
+                at: now,
+                reason: None,
+            }],
+            state_tx: Arc::new(watch::channel(TaskState::Pending).0),
+            metrics,
+        }
+    }
+
+    /// The record of a job loaded from its `meta/job.json`. Only what the document
+    /// keeps comes back: status, errors, counts and results metadata.
+    fn restored(record: JobRecord, metrics: Arc<JobMetrics>) -> Self {
+        let submission = NormalizedSubmission {
+            job_id: record.job_id.clone(),
+            name: record.name,
+            tenant_id: record.tenant_id.unwrap_or_default(),
+            subject: record.owner.unwrap_or_default(),
+            priority: priority_level(record.priority),
+            fingerprint: record.submission_fingerprint.unwrap_or_default(),
+            ..NormalizedSubmission::default()
+        };
+        let state = task_state(record.state);
+        let mut job = Self::new(submission, None, metrics);
+        job.state = state;
+        job.current_stage = None;
+        job.created_at = timestamp_from_ms(record.created_at_unix_ms.into());
+        job.updated_at = timestamp_from_ms(record.updated_at_unix_ms.into());
+        job.completed_at = job.is_terminal().then_some(job.updated_at);
+        job.counts = record.counts.into_iter().collect();
+        job.metadata = record.results_metadata.into_iter().collect();
+        job.error_code = record.error_code;
+        job.error_summary = record.error_summary;
+        job.error_details_ref = record.error_details_ref;
+        job.reservation_state = None;
+        job.history = vec![StateTransitionRecord {
+            from: TaskState::Unspecified,
+            to: state,
+            event: "restore".to_string(),
+            at: job.updated_at,
+            reason: None,
+        }];
+        job.state_tx.send_replace(state);
+        job
+    }
+
+    /// The job as the [`JobRecord`] kept in `meta/job.json`.
+    fn job_record(&self) -> JobRecord {
+        JobRecord {
+            job_id: self.job_id.clone(),
+            name: self.submission.name.clone(),
+            priority: Priority::from_level(self.submission.priority),
+            state: job_state(self.state),
+            created_at_unix_ms: timestamp_to_ms(&self.created_at) as i64,
+            updated_at_unix_ms: timestamp_to_ms(&self.updated_at) as i64,
+            error_code: self.error_code.clone(),
+            error_summary: self.error_summary.clone(),
+            error_details_ref: self.error_details_ref.clone(),
+            counts: self.counts.clone().into_iter().collect(),
+            results_metadata: self.metadata.clone().into_iter().collect(),
+            history: Vec::new(),
+            tenant_id: Some(self.submission.tenant_id.clone()),
+            owner: Some(self.owner().to_string()),
+            submission_fingerprint: Some(self.submission.fingerprint.clone()),
+        }
+    }
+
+    /// Subject that submitted the job: the JWT `sub` of an authenticated caller,
+    /// otherwise `metadata.subject` of the request.
+    fn owner(&self) -> &str {
+        &self.submission.subject
+    }
+
+    /// The job as a policy [`Resource`].
+    fn resource(&self) -> Resource {
+        Resource { tenant_id: self.submission.tenant_id.clone(), owner: self.owner().to_string() }
+    }
+
+    /// Moves the job to `to`, recording the change in `history`. Re-entering the
+    /// current state is not recorded.
+    fn transition_to(&mut self, to: TaskState, event: &str, reason: Option<String>) {
+        if self.state == to {
+            return;
+        }
+        let time_in_state = self.history.last().map(|entry| elapsed_since(&entry.at)).unwrap_or_default();
+        self.metrics
+            .job_transitioned(task_state_label(self.state), task_state_label(to), time_in_state);
+        if self.history.len() >= MAX_TRANSITION_HISTORY {
+            let overflow = self.history.len() + 1 - MAX_TRANSITION_HISTORY;
+            self.history.drain(..overflow);
+        }
+        self.history.push(StateTransitionRecord {
+            from: self.state,
+            to,
+            event: event.to_string(),
+            at: ts_now(),
+            reason,
+        });
+        self.state = to;
+        self.reported_progress = None;
+        self.state_tx.send_replace(to);
+        if let Some(outcome) = job_outcome(to) {
+            self.metrics.job_completed(outcome, elapsed_since(&self.created_at));
+        }
+    }
+
+    fn enqueue_response(&self) -> EnqueueJobResponse {
+        EnqueueJobResponse {
+            job_id: self.job_id.clone(),
+            state: self.state as i32,
+            created_at: Some(self.created_at),
+        }
+    }
+
+    fn status_response(&self) -> GetJobStatusResponse {
+        GetJobStatusResponse {
+            job_id: self.job_id.clone(),
+            state: self.state as i32,
+            stage: self.stage_label(),
+            progress: self.progress(),
+            message: self
+                .reported_progress
+                .as_ref()
+                .map(|reported| reported.message.clone())
+                .or_else(|| self.stage_records.last().and_then(|stage| stage.output.get("message").cloned()))
+                .or_else(|| self.error_summary.clone())
+                .unwrap_or_else(|| "job accepted".to_string()),
+            error_code: self.error_code.clone().unwrap_or_default(),
+            error_summary: self.error_summary.clone().unwrap_or_default(),
+            error_details_ref: self.error_details_ref.clone().unwrap_or_default(),
+            updated_at: Some(self.updated_at),
+        }
+    }
+
+    /// Status frame for an intermediate transition that was superseded before a
+    /// watcher observed it.
+    fn transition_status_response(&self, entry: &StateTransitionRecord) -> GetJobStatusResponse {
+        GetJobStatusResponse {
+            job_id: self.job_id.clone(),
+            state: entry.to as i32,
+            stage: state_stage_label(entry.to).to_string(),
+            progress: 0.0,
+            message: entry.reason.clone().unwrap_or_default(),
+            error_code: String::new(),
+            error_summary: String::new(),
+            error_details_ref: String::new(),
+            updated_at: Some(entry.at),
+        }
+    }
+
     fn stage_label(&self) -> String {
+        // A paused job is parked in scheduling; report the pause rather than the stage.
+        if self.state == TaskState::Paused {
+            return state_stage_label(self.state).to_string();
+        }
         self.current_stage
             .map(|stage| stage.key().to_string())
-            .unwrap_or_else(|| match self.state {
-                TaskState::Pending => "pending".to_string(),
-                TaskState::Compiling => "compile".to_string(),
-                TaskState::Optimizing => "optimize".to_string(),
-                TaskState::Queued => "schedule".to_string(),
-                TaskState::Running => "execute".to_string(),
-                TaskState::Done => "finalize".to_string(),
-                TaskState::Error => "error".to_string(),
-                TaskState::Cancelled => "cancelled".to_string(),
-                TaskState::Timeout => "timeout".to_string(),
-                TaskState::Unspecified => "unspecified".to_string(),
-            })
+            .unwrap_or_else(|| state_stage_label(self.state).to_string())
     }
 
     fn progress(&self) -> f32 {
         if self.is_terminal() {
             1.0
+        } else if let Some(reported) = &self.reported_progress {
+            reported.fraction
         } else {
             (self.stage_records.len() as f32 / DagStageKind::all().len() as f32).min(0.99)
         }
@@ -1100,6 +1879,16 @@ impl JobRuntimeRecord {
         )
     }
 
+    /// Rejects pipeline writes once a cancel was requested or the job is terminal,
+    /// so a stage that completes after `CancelJob` cannot record results or move the
+    /// job past `CANCELLED`.
+    fn ensure_in_flight(&self) -> Result<(), Status> {
+        if self.cancel_requested || self.is_terminal() {
+            return Err(Status::cancelled(format!("job {} is no longer running", self.job_id)));
+        }
+        Ok(())
+    }
+
     fn stable_summary_map(&self) -> BTreeMap<String, String> {
         let mut summary = self.submission.summary_map();
         summary.remove("deadline_at_unix_ms");
@@ -1645,89 +2434,501 @@ impl JobRuntimeRecord {
     }
 }
 
-#[derive(Default)]
-struct KernelRuntimeStore {
-    jobs: parking_lot::RwLock<BTreeMap<String, JobRuntimeRecord>>,
-    request_index: parking_lot::RwLock<BTreeMap<String, String>>,
+/// Frames buffered per `WatchJobStatus` subscriber before the watcher task waits.
+const WATCH_JOB_STATUS_BUFFER: usize = 16;
+
+/// Lines buffered per `GetJobLogs` reader before the tailing task waits.
+const JOB_LOGS_BUFFER: usize = 64;
+/// How often a following `GetJobLogs` checks the log file for new lines.
+const JOB_LOGS_POLL_INTERVAL: Duration = Duration::from_millis(50);
+/// How long a following `GetJobLogs` waits for a log file that does not exist yet
+/// before closing the stream empty.
+const JOB_LOGS_WAIT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Upper bound on how long `CancelJob` waits for the pipeline to acknowledge a cancel.
+const CANCEL_ACK_TIMEOUT: Duration = Duration::from_secs(2);
+const CANCEL_ACK_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Jobs executing at once unless `EIGEN_MAX_CONCURRENT_JOBS` says otherwise.
+const DEFAULT_MAX_CONCURRENT_JOBS: usize = 4;
+
+/// Later jobs dispatched ahead of a waiting job before it is promoted a class,
+/// unless `EIGEN_PRIORITY_AGING_THRESHOLD` says otherwise (`0` disables aging).
+const DEFAULT_PRIORITY_AGING_THRESHOLD: u32 = 16;
+
+/// Shots per execution when the submission does not set any.
+const DEFAULT_SHOTS: i64 = 1024;
+
+/// Hands out [`SlotPool`] slots to scheduled jobs in [`Priority`] order, then by
+/// enqueue time, aging jobs that keep being overtaken. Jobs wait in `QUEUED` until
+/// they reach the head of the queue and a slot is free.
+struct ExecutionDispatcher {
+    queue: Mutex<JobQueue>,
+    slots: SlotPool,
+    changed: tokio::sync::Notify,
 }
 
-impl KernelRuntimeStore {
-    fn create_or_get_job(&self, submission: NormalizedSubmission) -> Result<(JobRuntimeRecord, bool), Status> {
-        let mut jobs = self.jobs.write();
-        if let Some(existing) = jobs.get(&submission.job_id) {
-            if existing.submission.fingerprint != submission.fingerprint {
-                return Err(Status::aborted("deterministic job id collision"));
+impl Default for ExecutionDispatcher {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_CONCURRENT_JOBS)
+    }
+}
+
+impl ExecutionDispatcher {
+    fn new(max_running: usize) -> Self {
+        Self::with_aging(max_running, DEFAULT_PRIORITY_AGING_THRESHOLD)
+    }
+
+    fn with_aging(max_running: usize, aging_threshold: u32) -> Self {
+        Self {
+            queue: Mutex::new(JobQueue::new().with_aging(aging_threshold)),
+            slots: SlotPool::new(max_running),
+            changed: tokio::sync::Notify::new(),
+        }
+    }
+
+    fn from_env() -> Self {
+        let max_running = std::env::var("EIGEN_MAX_CONCURRENT_JOBS")
+            .ok()
+            .and_then(|raw| raw.parse::<usize>().ok())
+            .unwrap_or(DEFAULT_MAX_CONCURRENT_JOBS);
+        let aging_threshold = std::env::var("EIGEN_PRIORITY_AGING_THRESHOLD")
+            .ok()
+            .and_then(|raw| raw.parse::<u32>().ok())
+            .unwrap_or(DEFAULT_PRIORITY_AGING_THRESHOLD);
+        Self::with_aging(max_running, aging_threshold)
+    }
+
+    /// Queues `job_id` and waits for its slot. Dropping the future before it
+    /// resolves (e.g. on cancel) takes the job out of the queue without taking a slot.
+    async fn acquire(self: &Arc<Self>, job_id: &str, priority: Priority, enqueued_at_ms: i64) -> ExecutionSlot {
+        self.queue.lock().push(job_id, priority, enqueued_at_ms);
+        let mut ticket = QueueTicket {
+            dispatcher: self.as_ref(),
+            job_id,
+            dispatched: false,
+        };
+        self.changed.notify_waiters();
+        loop {
+            let changed = self.changed.notified();
+            tokio::pin!(changed);
+            changed.as_mut().enable();
+            {
+                let mut queue = self.queue.lock();
+                let at_head = queue.peek().is_some_and(|head| head.job_id == job_id);
+                if let Some(lease) = at_head.then(|| self.slots.try_acquire(job_id)).flatten() {
+                    queue.pop();
+                    ticket.dispatched = true;
+                    drop(queue);
+                    // Another free slot may now belong to the next job in line.
+                    self.changed.notify_waiters();
+                    return ExecutionSlot {
+                        dispatcher: self.clone(),
+                        lease: Some(lease),
+                    };
+                }
             }
-            return Ok((existing.clone(), false));
+            changed.await;
         }
+    }
 
-        let now = ts_now();
-        let canonical_job_id = canonical_job_id_for_submission(&submission);
-        let workflow_id = format!("workflow-{}", submission.fingerprint);
-        let record = JobRuntimeRecord {
-            job_id: submission.job_id.clone(),
-            submission: submission.clone(),
-            state: TaskState::Pending,
-            current_stage: Some(DagStageKind::ValidateEnqueue),
-            created_at: now.clone(),
-            updated_at: now,
-            deadline_at: submission.deadline_at.clone(),
-            completed_at: None,
-            stage_records: Vec::new(),
-            workflow_events: vec![WorkflowBoundaryRecord {
-                boundary_id: workflow_boundary_ref(
-                    &submission.job_id,
-                    DagStageKind::ValidateEnqueue,
-                    WorkflowBoundaryKind::WorkflowStarted,
-                ),
-                workflow_id: workflow_id.clone(),
-                job_id: submission.job_id.clone(),
-                kind: WorkflowBoundaryKind::WorkflowStarted,
-                stage_id: None,
-                stage_key: None,
-                order: None,
-                state_before: None,
-                state_after: Some(TaskState::Pending),
-                input_ref: workflow_stage_input_ref(&submission.job_id, DagStageKind::ValidateEnqueue),
-                output_ref: String::new(),
-                artifact_ref: workflow_root_lineage_ref(&canonical_job_id),
-                lineage_ref: workflow_root_lineage_ref(&canonical_job_id),
-                replay_token: hash_bytes_hex(
-                    format!(
-                        "workflow-start:{}:{}",
-                        canonical_job_id,
-                        workflow_root_lineage_ref(&canonical_job_id)
-                    )
-                    .as_bytes(),
-                ),
-                timestamp: ts_now(),
-            }],
-            workflow_id,
-            workflow_root_lineage_ref: workflow_root_lineage_ref(&canonical_job_id),
-            workflow_completion_ref: None,
-            workflow_failure_ref: None,
-            counts: BTreeMap::new(),
-            metadata: submission.workload_metadata.clone(),
-            qfs_result_ref: None,
-            error_code: None,
-            error_summary: None,
-            error_details_ref: None,
-            cancel_requested: false,
-            cancel_reason: None,
-            cancellation_fanout_ref: None,
-            reservation_state: Some("held".to_string()),
-            reservation_token: Some(reservation_token_for(&submission)),
-            reservation_lease_ms: reservation_lease_ms_for(&submission),
-            reservation_released_reason: None,
-            retry_attempts: Vec::new(),
-            retry_final_reason: None,
-            retry_success_after_retry_total: 0,
+    /// Takes a waiting job out of the queue so it can no longer reach the head;
+    /// its pending `acquire` stays parked until the caller drops it.
+    fn withdraw(&self, job_id: &str) {
+        if self.queue.lock().remove(job_id).is_some() {
+            self.changed.notify_waiters();
+        }
+    }
+}
+
+/// Removes a job that stopped waiting before it was dispatched.
+struct QueueTicket<'a> {
+    dispatcher: &'a ExecutionDispatcher,
+    job_id: &'a str,
+    dispatched: bool,
+}
+
+impl Drop for QueueTicket<'_> {
+    fn drop(&mut self) {
+        if !self.dispatched {
+            self.dispatcher.queue.lock().remove(self.job_id);
+            self.dispatcher.changed.notify_waiters();
+        }
+    }
+}
+
+/// A held execution slot, released on drop.
+struct ExecutionSlot {
+    dispatcher: Arc<ExecutionDispatcher>,
+    lease: Option<SlotLease>,
+}
+
+impl Drop for ExecutionSlot {
+    fn drop(&mut self) {
+        drop(self.lease.take());
+        self.dispatcher.changed.notify_waiters();
+    }
+}
+
+/// Counts the job DAGs running in the background so shutdown can wait for them.
+#[derive(Default)]
+struct PipelineTracker {
+    running: Arc<watch::Sender<usize>>,
+}
+
+impl PipelineTracker {
+    /// Registers a DAG that runs until the returned guard is dropped.
+    fn start(&self) -> PipelineGuard {
+        self.running.send_modify(|running| *running += 1);
+        PipelineGuard { running: self.running.clone() }
+    }
+
+    /// Waits until no DAG is running; returns `false` if `timeout` expired first.
+    async fn wait_idle(&self, timeout: Duration) -> bool {
+        let mut running = self.running.subscribe();
+        tokio::time::timeout(timeout, running.wait_for(|running| *running == 0)).await.is_ok()
+    }
+}
+
+/// A running DAG registered with [`PipelineTracker`], unregistered on drop.
+struct PipelineGuard {
+    running: Arc<watch::Sender<usize>>,
+}
+
+impl Drop for PipelineGuard {
+    fn drop(&mut self) {
+        self.running.send_modify(|running| *running = running.saturating_sub(1));
+    }
+}
+
+/// How long shutdown waits for running DAGs unless `EIGEN_SHUTDOWN_GRACE_SECS` says otherwise.
+const DEFAULT_SHUTDOWN_GRACE: Duration = Duration::from_secs(30);
+
+/// Error code of the jobs a shutdown stopped before they finished.
+const SHUTDOWN_INTERRUPTED: &str = "SHUTDOWN_INTERRUPTED";
+
+fn shutdown_grace_from_env() -> Duration {
+    std::env::var("EIGEN_SHUTDOWN_GRACE_SECS")
+        .ok()
+        .and_then(|raw| raw.parse::<u64>().ok())
+        .map_or(DEFAULT_SHUTDOWN_GRACE, Duration::from_secs)
+}
+
+/// Deadline of jobs enqueued without `metadata.deadline` unless
+/// `EIGEN_KERNEL_DEFAULT_DEADLINE_SECS` says otherwise; `0` there disables it.
+const DEFAULT_JOB_DEADLINE: Duration = Duration::from_secs(60 * 60);
+
+/// How often the deadline reaper looks for expired jobs.
+const DEADLINE_REAPER_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How often job changes are written to `meta/job.json`.
+const JOB_PERSIST_INTERVAL: Duration = Duration::from_secs(1);
+
+fn default_job_deadline_from_env() -> Option<Duration> {
+    let deadline = std::env::var("EIGEN_KERNEL_DEFAULT_DEADLINE_SECS")
+        .ok()
+        .and_then(|raw| raw.parse::<u64>().ok())
+        .map_or(DEFAULT_JOB_DEADLINE, Duration::from_secs);
+    Some(deadline).filter(|deadline| !deadline.is_zero())
+}
+
+/// Most jobs one `EnqueueJobBatch` call may carry unless
+/// `EIGEN_KERNEL_MAX_ENQUEUE_BATCH` says otherwise.
+const DEFAULT_MAX_ENQUEUE_BATCH: usize = 500;
+
+/// Most jobs one `BatchEnqueueJobs` call may carry.
+const MAX_BATCH_ENQUEUE_JOBS: usize = 100;
+
+/// Largest `EnqueueJobBatch` accepted; bigger batches fail with `INVALID_ARGUMENT`.
+#[derive(Debug, Clone, Copy)]
+struct EnqueueBatchLimit(usize);
+
+impl Default for EnqueueBatchLimit {
+    fn default() -> Self {
+        Self(DEFAULT_MAX_ENQUEUE_BATCH)
+    }
+}
+
+impl EnqueueBatchLimit {
+    fn from_env() -> Self {
+        std::env::var("EIGEN_KERNEL_MAX_ENQUEUE_BATCH")
+            .ok()
+            .and_then(|raw| raw.parse::<usize>().ok())
+            .map_or_else(Self::default, Self)
+    }
+}
+
+/// How long an idempotency key keeps pointing at its job unless
+/// `EIGEN_KERNEL_IDEMPOTENCY_TTL_SECS` says otherwise.
+const DEFAULT_IDEMPOTENCY_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Maps `(tenant, metadata.idempotency_key)` to the job the key created, so an
+/// `EnqueueJob` retried after a timeout gets the original job back instead of a
+/// duplicate. Keys expire `ttl` after the job was created.
+struct IdempotencyIndex {
+    ttl: Duration,
+    entries: Mutex<BTreeMap<(String, String), IdempotencyEntry>>,
+}
+
+struct IdempotencyEntry {
+    job_id: String,
+    payload_digest: String,
+    recorded_at: Instant,
+}
+
+impl Default for IdempotencyIndex {
+    fn default() -> Self {
+        Self::new(DEFAULT_IDEMPOTENCY_TTL)
+    }
+}
+
+impl IdempotencyIndex {
+    fn new(ttl: Duration) -> Self {
+        Self { ttl, entries: Mutex::new(BTreeMap::new()) }
+    }
+
+    fn from_env() -> Self {
+        let ttl = std::env::var("EIGEN_KERNEL_IDEMPOTENCY_TTL_SECS")
+            .ok()
+            .and_then(|raw| raw.parse::<u64>().ok())
+            .map_or(DEFAULT_IDEMPOTENCY_TTL, Duration::from_secs);
+        Self::new(ttl)
+    }
+
+    fn key(submission: &NormalizedSubmission) -> (String, String) {
+        (submission.tenant_id.clone(), submission.idempotency_key.clone())
+    }
+
+    /// The job an earlier submission with the same key created, if the key has not
+    /// expired. Reusing the key for a different payload is `ALREADY_EXISTS`.
+    fn lookup(&self, submission: &NormalizedSubmission) -> Result<Option<String>, Status> {
+        let entries = self.entries.lock();
+        let Some(entry) = entries.get(&Self::key(submission)) else {
+            return Ok(None);
         };
+        if entry.recorded_at.elapsed() >= self.ttl {
+            return Ok(None);
+        }
+        if entry.payload_digest != submission.payload_digest() {
+            return Err(Status::already_exists(format!(
+                "idempotency key {:?} was already used for job {} with a different request",
+                submission.idempotency_key, entry.job_id
+            )));
+        }
+        Ok(Some(entry.job_id.clone()))
+    }
+
+    fn record(&self, submission: &NormalizedSubmission) {
+        let mut entries = self.entries.lock();
+        entries.retain(|_, entry| entry.recorded_at.elapsed() < self.ttl);
+        entries.insert(
+            Self::key(submission),
+            IdempotencyEntry {
+                job_id: submission.job_id.clone(),
+                payload_digest: submission.payload_digest(),
+                recorded_at: Instant::now(),
+            },
+        );
+    }
+}
+
+/// Per-tenant job quota; `EIGEN_KERNEL_MAX_JOBS_PER_TENANT` overrides the
+/// default limit on unfinished jobs per tenant.
+fn quota_manager_from_env() -> QuotaManager {
+    let mut policy = QuotaPolicy::default();
+    if let Some(limit) = std::env::var("EIGEN_KERNEL_MAX_JOBS_PER_TENANT")
+        .ok()
+        .and_then(|raw| raw.parse::<u64>().ok())
+    {
+        policy.max_concurrent_jobs_per_tenant = limit;
+    }
+    QuotaManager::new(policy)
+}
+
+/// A job from [`KernelRuntimeStore::create_or_get_jobs`], with the quota lease it
+/// took when the call created it.
+type CreatedJob = (JobRuntimeRecord, Option<QuotaLease>);
+
+#[derive(Default)]
+struct KernelRuntimeStore {
+    jobs: parking_lot::RwLock<BTreeMap<String, JobRuntimeRecord>>,
+    request_index: parking_lot::RwLock<BTreeMap<String, String>>,
+    cancel_signals: Mutex<BTreeMap<String, watch::Sender<bool>>>,
+    metrics: Arc<JobMetrics>,
+    dispatcher: Arc<ExecutionDispatcher>,
+    quota: QuotaManager,
+    idempotency: IdempotencyIndex,
+    pipelines: PipelineTracker,
+    /// Set once shutdown starts; new jobs and retries are refused from then on.
+    draining: AtomicBool,
+    /// Deadline given to jobs enqueued without one; `None` lets them run unbounded.
+    default_deadline: Option<Duration>,
+    enqueue_batch_limit: EnqueueBatchLimit,
+    validation_limits: ValidationLimits,
+    /// Feeds the health service's check on consecutive `EnqueueJob` failures.
+    enqueue_failures: EnqueueFailures,
+    /// Simulated stage durations and failure injection.
+    pipeline: PipelineConfig,
+    /// Keeps every job in its `meta/job.json` so jobs outlive a restart; `None`
+    /// keeps them in memory only.
+    job_meta: Option<PersistentJobStore>,
+}
+
+impl KernelRuntimeStore {
+    /// The [`PipelineConfig`] `submission` runs under: the kernel's, with the
+    /// request's simulation overrides when dev mode allows them.
+    fn pipeline_config(&self, submission: &NormalizedSubmission) -> Result<PipelineConfig, Status> {
+        let overrides = submission.simulation_overrides();
+        if overrides.is_empty() {
+            return Ok(self.pipeline.clone());
+        }
+        if !self.pipeline.dev_mode {
+            return Err(Status::failed_precondition(format!(
+                "simulation overrides are only accepted with {DEV_MODE_ENV}=true"
+            )));
+        }
+        self.pipeline.with_overrides(&overrides).map_err(Status::invalid_argument)
+    }
+
+    /// Points a submission with a known idempotency key at the job that key created,
+    /// so the retry reuses its id (and its source bundle) rather than a fresh one.
+    fn resolve_idempotency_key(&self, mut submission: NormalizedSubmission) -> Result<NormalizedSubmission, Status> {
+        if submission.explicit_idempotency_key
+            && let Some(job_id) = self.idempotency.lookup(&submission)?
+        {
+            submission.job_id = job_id;
+        }
+        Ok(submission)
+    }
+
+    /// Returns the job for `submission`, creating it if it is new. A new job takes
+    /// one unit of its tenant's quota and comes back with the lease; resubmitting an
+    /// existing job returns `None` and costs nothing.
+    fn create_or_get_job(
+        &self,
+        submission: NormalizedSubmission,
+    ) -> Result<(JobRuntimeRecord, Option<QuotaLease>), Status> {
+        let mut jobs = self.jobs.write();
+        if let Some(existing) = self.existing_job(&jobs, &submission)? {
+            return Ok((existing, None));
+        }
+        let lease = self.acquire_quota(&submission.tenant_id)?;
+        Ok((self.insert_job(&mut jobs, submission), Some(lease)))
+    }
+
+    /// [`Self::create_or_get_job`] for every entry of `submissions`, under a single
+    /// acquisition of the job table's write lock and all or nothing: every entry is
+    /// checked and every new job's quota reserved before the first job is created.
+    /// On failure no job is created and the index of the failing entry is returned.
+    fn create_or_get_jobs(
+        &self,
+        submissions: &[NormalizedSubmission],
+    ) -> Result<Vec<CreatedJob>, (usize, Status)> {
+        enum Planned {
+            Existing(Box<JobRuntimeRecord>),
+            /// The same job as the earlier entry at this index.
+            Repeat(usize),
+            New(QuotaLease),
+        }
+
+        let mut jobs = self.jobs.write();
+        let mut plans = Vec::with_capacity(submissions.len());
+        for (index, submission) in submissions.iter().enumerate() {
+            // Keyed entries get fresh job ids, so a repeat is recognised by its key.
+            let shares_key = |earlier: &NormalizedSubmission| {
+                earlier.explicit_idempotency_key
+                    && submission.explicit_idempotency_key
+                    && IdempotencyIndex::key(earlier) == IdempotencyIndex::key(submission)
+            };
+            let earlier = submissions[..index]
+                .iter()
+                .position(|earlier| earlier.job_id == submission.job_id || shares_key(earlier));
+            let plan = match earlier {
+                Some(earlier) if is_same_submission(&submissions[earlier], submission) => Ok(Planned::Repeat(earlier)),
+                Some(earlier) if shares_key(&submissions[earlier]) => Err(Status::already_exists(format!(
+                    "idempotency key {:?} is used by jobs[{earlier}] with a different request",
+                    submission.idempotency_key
+                ))),
+                Some(_) => Err(Status::aborted("deterministic job id collision")),
+                None => self.existing_job(&jobs, submission).and_then(|existing| match existing {
+                    Some(existing) => Ok(Planned::Existing(Box::new(existing))),
+                    None => self.acquire_quota(&submission.tenant_id).map(Planned::New),
+                }),
+            };
+            match plan {
+                Ok(plan) => plans.push(plan),
+                Err(status) => {
+                    for plan in plans {
+                        if let Planned::New(lease) = plan {
+                            self.quota.release(lease);
+                        }
+                    }
+                    return Err((index, status));
+                }
+            }
+        }
+
+        let mut created: Vec<CreatedJob> = Vec::with_capacity(plans.len());
+        for (submission, plan) in submissions.iter().zip(plans) {
+            let entry = match plan {
+                Planned::Existing(existing) => (*existing, None),
+                Planned::Repeat(earlier) => (created[earlier].0.clone(), None),
+                Planned::New(lease) => (self.insert_job(&mut jobs, submission.clone()), Some(lease)),
+            };
+            created.push(entry);
+        }
+        Ok(created)
+    }
+
+    /// The job `submission` already created, if any. A different submission holding
+    /// the same job id is `ABORTED`.
+    fn existing_job(
+        &self,
+        jobs: &BTreeMap<String, JobRuntimeRecord>,
+        submission: &NormalizedSubmission,
+    ) -> Result<Option<JobRuntimeRecord>, Status> {
+        if let Some(existing) = jobs.get(&submission.job_id) {
+            if !is_same_submission(&existing.submission, submission) {
+                return Err(Status::aborted("deterministic job id collision"));
+            }
+            return Ok(Some(existing.clone()));
+        }
+        // A concurrent request with the same key may have created the job since.
+        if submission.explicit_idempotency_key
+            && let Some(existing) = self.idempotency.lookup(submission)?.and_then(|job_id| jobs.get(&job_id))
+        {
+            return Ok(Some(existing.clone()));
+        }
+        Ok(None)
+    }
+
+    /// Adds the new `PENDING` job of `submission` to `jobs` and indexes it.
+    fn insert_job(
+        &self,
+        jobs: &mut BTreeMap<String, JobRuntimeRecord>,
+        submission: NormalizedSubmission,
+    ) -> JobRuntimeRecord {
+        let deadline_at = submission.deadline_at.or_else(|| self.default_deadline_at());
+        let record = JobRuntimeRecord::new(submission.clone(), deadline_at, self.metrics.clone());
         jobs.insert(submission.job_id.clone(), record.clone());
         self.request_index
             .write()
             .insert(submission.fingerprint.clone(), submission.job_id.clone());
-        Ok((record, true))
+        if submission.explicit_idempotency_key {
+            self.idempotency.record(&submission);
+        }
+        self.metrics.job_enqueued(&submission.tenant_id);
+        self.metrics.job_state_entered(task_state_label(TaskState::Pending));
+        record
+    }
+
+    /// Reserves one running job for `tenant_id`; over-quota tenants get
+    /// `RESOURCE_EXHAUSTED`.
+    fn acquire_quota(&self, tenant_id: &str) -> Result<QuotaLease, Status> {
+        self.quota.try_acquire(tenant_id, 1).map_err(|err| match err {
+            QuotaError::EmptyTenantId => Status::invalid_argument("metadata.tenant_id is required"),
+            err @ QuotaError::Exhausted { .. } => Status::resource_exhausted(err.to_string()),
+        })
     }
 
     fn reservation_active(&self, job_id: &str) -> Result<bool, Status> {
@@ -1804,6 +3005,7 @@ impl KernelRuntimeStore {
         }
 
         let stage = job.current_stage.unwrap_or(DagStageKind::ValidateEnqueue);
+        job.failed_at_stage = Some(stage);
         let stage_id = stage_id(job_id, stage);
         let state_before = job.state;
         let input_ref = workflow_stage_input_ref(job_id, stage);
@@ -1878,13 +3080,13 @@ impl KernelRuntimeStore {
             });
         }
 
-        job.state = TaskState::Error;
+        job.transition_to(TaskState::Error, "fail", Some(error_summary.to_string()));
         job.reservation_state = Some("released".to_string());
         job.updated_at = ts_now();
         job.completed_at = Some(ts_now());
         job.error_code = Some(error_code.to_string());
         job.error_summary = Some(error_summary.to_string());
-        job.error_details_ref = Some(error_details_ref.to_string());
+        job.error_details_ref = Some(job_error_details_ref(job_id));
         job.workflow_failure_ref = Some(workflow_failure_ref.clone());
         job.record_workflow_boundary(
             stage,
@@ -1913,6 +3115,52 @@ impl KernelRuntimeStore {
         self.jobs.read().get(job_id).cloned()
     }
 
+    /// Number of jobs that have not reached a terminal state.
+    fn active_count(&self) -> usize {
+        self.jobs.read().values().filter(|job| !job.is_terminal()).count()
+    }
+
+    /// Refuses new work, waits up to `grace` for the running DAGs, then fails every
+    /// job that is still not terminal with [`SHUTDOWN_INTERRUPTED`] so none is left
+    /// `RUNNING` forever. Returns the ids of those jobs.
+    async fn drain(&self, grace: Duration) -> Vec<String> {
+        self.draining.store(true, Ordering::SeqCst);
+        if !self.pipelines.wait_idle(grace).await {
+            tracing::warn!(grace_ms = grace.as_millis() as u64, "job pipelines still running after the shutdown grace");
+        }
+        let unfinished: Vec<String> =
+            self.jobs.read().values().filter(|job| !job.is_terminal()).map(|job| job.job_id.clone()).collect();
+        unfinished
+            .into_iter()
+            .filter(|job_id| {
+                let summary = "kernel shut down before the job finished";
+                let details_ref = job_error_details_ref(job_id);
+                match self.request_error_terminalization(job_id, SHUTDOWN_INTERRUPTED, summary, &details_ref) {
+                    Ok(_) => true,
+                    Err(status) => {
+                        tracing::error!(job_id = %job_id, error = %status, "failed to terminalize job on shutdown");
+                        false
+                    }
+                }
+            })
+            .collect()
+    }
+
+    fn ensure_accepting_jobs(&self) -> Result<(), Status> {
+        if self.draining.load(Ordering::SeqCst) {
+            return Err(Status::unavailable("kernel is shutting down"));
+        }
+        Ok(())
+    }
+
+    /// Returns the current record together with a receiver for its later state
+    /// changes, taken under one lock so no transition falls between the two.
+    fn watch_state(&self, job_id: &str) -> Option<(JobRuntimeRecord, watch::Receiver<TaskState>)> {
+        let jobs = self.jobs.read();
+        let job = jobs.get(job_id)?;
+        Some((job.clone(), job.state_tx.subscribe()))
+    }
+
     fn is_cancel_requested(&self, job_id: &str) -> bool {
         self.jobs
             .read()
@@ -1921,6 +3169,138 @@ impl KernelRuntimeStore {
             .unwrap_or(false)
     }
 
+    /// Subscribes to the cancellation signal of a job. The receiver observes `true`
+    /// once `request_cancel` has been accepted, even if it subscribes afterwards.
+    fn cancel_signal(&self, job_id: &str) -> watch::Receiver<bool> {
+        self.cancel_signals
+            .lock()
+            .entry(job_id.to_string())
+            .or_insert_with(|| watch::channel(false).0)
+            .subscribe()
+    }
+
+    /// Deadline of a job starting now that did not ask for one.
+    fn default_deadline_at(&self) -> Option<Timestamp> {
+        let deadline = self.default_deadline?;
+        normalized_deadline_at(&ProtoDuration {
+            seconds: deadline.as_secs().min(i64::MAX as u64) as i64,
+            nanos: deadline.subsec_nanos() as i32,
+        })
+    }
+
+    /// Moves every unfinished job past its deadline to `TIMEOUT` with
+    /// `DEADLINE_EXCEEDED` and interrupts its running stage. Terminalization
+    /// rechecks the state under the jobs lock, so a job that completes in the
+    /// meantime keeps its outcome. Returns the ids of the jobs timed out.
+    fn reap_expired_deadlines(&self) -> Vec<String> {
+        let now_ms = timestamp_to_ms(&ts_now());
+        let expired: Vec<String> = self
+            .jobs
+            .read()
+            .values()
+            .filter(|job| !job.is_terminal())
+            .filter(|job| job.deadline_at.as_ref().is_some_and(|deadline| timestamp_to_ms(deadline) <= now_ms))
+            .map(|job| job.job_id.clone())
+            .collect();
+        expired
+            .into_iter()
+            .filter(|job_id| match self.request_deadline_terminalization(job_id) {
+                Ok(job) => job.state == TaskState::Timeout,
+                Err(status) => {
+                    tracing::error!(job_id = %job_id, error = %status, "failed to time out job past its deadline");
+                    false
+                }
+            })
+            .inspect(|job_id| {
+                tracing::warn!(job_id = %job_id, "job deadline exceeded");
+                self.cancel_signals
+                    .lock()
+                    .entry(job_id.clone())
+                    .or_insert_with(|| watch::channel(false).0)
+                    .send_replace(true);
+            })
+            .collect()
+    }
+
+    /// Runs [`KernelRuntimeStore::reap_expired_deadlines`] every `interval` until
+    /// the task is aborted, so a job stuck inside a stage still ends at its deadline.
+    /// Loads the jobs kept in `meta/job.json` into a freshly started kernel. Jobs the
+    /// previous kernel left unfinished cannot resume and end in `ERROR` with
+    /// [`SHUTDOWN_INTERRUPTED`]. Returns how many jobs were restored.
+    fn restore_jobs(&self) -> usize {
+        let Some(job_meta) = &self.job_meta else {
+            return 0;
+        };
+        let mut restored = 0;
+        for record in job_meta.list() {
+            let mut jobs = self.jobs.write();
+            if jobs.contains_key(&record.job_id) {
+                continue;
+            }
+            let mut job = JobRuntimeRecord::restored(record, self.metrics.clone());
+            self.metrics.job_state_entered(task_state_label(job.state));
+            if !job.is_terminal() {
+                let summary = "kernel restarted before the job finished";
+                job.transition_to(TaskState::Error, "restart", Some(summary.to_string()));
+                job.error_code = Some(SHUTDOWN_INTERRUPTED.to_string());
+                job.error_summary = Some(summary.to_string());
+                job.updated_at = ts_now();
+                job.completed_at = Some(job.updated_at);
+            }
+            if !job.submission.fingerprint.is_empty() {
+                self.request_index
+                    .write()
+                    .insert(job.submission.fingerprint.clone(), job.job_id.clone());
+            }
+            jobs.insert(job.job_id.clone(), job);
+            restored += 1;
+        }
+        self.persist_jobs();
+        restored
+    }
+
+    /// Writes every job that changed since the last call to its `meta/job.json`.
+    fn persist_jobs(&self) {
+        let Some(job_meta) = &self.job_meta else {
+            return;
+        };
+        let records: Vec<JobRecord> = self.jobs.read().values().map(JobRuntimeRecord::job_record).collect();
+        for record in records {
+            let job_id = record.job_id.clone();
+            if let Err(err) = job_meta.put(record) {
+                tracing::warn!(job_id = %job_id, error = %err, "failed to persist job metadata");
+            }
+        }
+    }
+
+    fn spawn_job_persister(self: &Arc<Self>, interval: Duration) -> tokio::task::JoinHandle<()> {
+        let runtime = Arc::downgrade(self);
+        tokio::spawn(async move {
+            let mut ticks = tokio::time::interval(interval);
+            loop {
+                ticks.tick().await;
+                let Some(runtime) = runtime.upgrade() else {
+                    return;
+                };
+                runtime.persist_jobs();
+            }
+        })
+    }
+
+    fn spawn_deadline_reaper(self: &Arc<Self>, interval: Duration) -> tokio::task::JoinHandle<()> {
+        let runtime = Arc::downgrade(self);
+        tokio::spawn(async move {
+            let mut ticks = tokio::time::interval(interval);
+            loop {
+                ticks.tick().await;
+                let Some(runtime) = runtime.upgrade() else {
+                    return;
+                };
+                runtime.reap_expired_deadlines();
+            }
+        })
+    }
+
     fn deadline_expired(&self, job_id: &str) -> bool {
         self.jobs
             .read()
@@ -1983,12 +3363,13 @@ impl KernelRuntimeStore {
             let workflow_failure_ref = workflow_failure_ref(job_id);
 
             job.current_stage = Some(stage);
+            job.failed_at_stage = Some(stage);
             job.updated_at = ts_now();
             job.completed_at = Some(ts_now());
-            job.state = TaskState::Error;
+            job.transition_to(TaskState::Error, "fail", Some(error_summary.clone()));
             job.error_code = Some(error_code.to_string());
             job.error_summary = Some(error_summary.clone());
-            job.error_details_ref = Some(failure_ref.clone());
+            job.error_details_ref = Some(job_error_details_ref(job_id));
             job.reservation_state = Some("released".to_string());
             job.workflow_failure_ref = Some(workflow_failure_ref.clone());
             job.stage_records.push(StageRecord {
@@ -2054,6 +3435,7 @@ impl KernelRuntimeStore {
             .find_map(|record| record.artifact_refs.get("workflow_output_ref").cloned());
 
         job.current_stage = Some(stage);
+        job.reported_progress = None;
         job.updated_at = ts_now();
         let mut artifact_refs = BTreeMap::from([
             ("workflow_input_ref".to_string(), input_ref.clone()),
@@ -2112,6 +3494,7 @@ impl KernelRuntimeStore {
         let job = jobs
             .get_mut(job_id)
             .ok_or_else(|| Status::not_found("job not found"))?;
+        job.ensure_in_flight()?;
         let (stage_kind, input_ref, handoff_ref, lineage_ref, state_before, output_ref, completion_ref) = {
             let stage = job
                 .stage_records
@@ -2196,6 +3579,8 @@ impl KernelRuntimeStore {
                 );
             stage.completed_at = Some(ts_now());
             stage.replay_token = hash_bytes_hex(&stage_digest_bytes(stage));
+            self.metrics
+                .observe_stage_duration(stage_kind.key(), elapsed_since(&stage.started_at));
             (stage_kind, input_ref, handoff_ref, lineage_ref, stage.state_before, output_ref, completion_ref)
         };
 
@@ -2223,7 +3608,7 @@ impl KernelRuntimeStore {
             );
         }
 
-        job.state = state_after;
+        job.transition_to(state_after, transition_event(state_after), None);
         job.updated_at = ts_now();
         if matches!(state_after, TaskState::Done | TaskState::Error | TaskState::Cancelled | TaskState::Timeout) {
             job.completed_at = Some(ts_now());
@@ -2282,9 +3667,14 @@ impl KernelRuntimeStore {
                 .insert("workflow_lineage_ref".to_string(), lineage_ref.clone());
             stage.completed_at = Some(ts_now());
             stage.replay_token = hash_bytes_hex(&stage_digest_bytes(stage));
+            self.metrics
+                .observe_stage_duration(stage_kind.key(), elapsed_since(&stage.started_at));
             (stage_kind, input_ref, handoff_ref, lineage_ref, failure_ref)
         };
 
+        if matches!(terminal_state, TaskState::Error | TaskState::Timeout) {
+            job.failed_at_stage = Some(stage_kind);
+        }
         job.workflow_failure_ref = Some(workflow_failure_ref(job_id));
         job.record_workflow_boundary(
             stage_kind,
@@ -2307,12 +3697,19 @@ impl KernelRuntimeStore {
             Some(terminal_state),
         );
 
-        job.state = terminal_state;
+        let reason = match terminal_state {
+            TaskState::Cancelled => job.cancel_reason.clone().or_else(|| Some(error_summary.to_string())),
+            _ => Some(error_summary.to_string()),
+        };
+        job.transition_to(terminal_state, transition_event(terminal_state), reason);
         job.updated_at = ts_now();
         job.completed_at = Some(ts_now());
         job.error_code = Some(error_code.to_string());
         job.error_summary = Some(error_summary.to_string());
-        job.error_details_ref = Some(error_details_ref.to_string());
+        job.error_details_ref = Some(match terminal_state {
+            TaskState::Error | TaskState::Timeout => job_error_details_ref(job_id),
+            _ => error_details_ref.to_string(),
+        });
         job.reservation_state = Some("released".to_string());
         let _ = handoff_ref;
         Ok(())
@@ -2327,7 +3724,12 @@ impl KernelRuntimeStore {
         let job = jobs
             .get_mut(job_id)
             .ok_or_else(|| Status::not_found("job not found"))?;
-        job.state = state;
+        job.ensure_in_flight()?;
+        // A paused job stays paused until `ResumeJob` re-queues it.
+        if job.state == TaskState::Paused && state == TaskState::Queued {
+            return Ok(());
+        }
+        job.transition_to(state, transition_event(state), None);
         job.updated_at = ts_now();
         if matches!(state, TaskState::Done | TaskState::Error | TaskState::Cancelled | TaskState::Timeout) {
             job.completed_at = Some(ts_now());
@@ -2349,6 +3751,25 @@ impl KernelRuntimeStore {
         Ok(())
     }
 
+    /// Publishes progress of the running stage. `fraction` is clamped into the range
+    /// of the job's state (see [`progress_range`]) and `message` becomes the status
+    /// message until the next stage begins. Jobs that are terminal or being cancelled
+    /// ignore the update.
+    fn set_progress(&self, job_id: &str, fraction: f32, message: String) -> Result<(), Status> {
+        let mut jobs = self.jobs.write();
+        let job = jobs
+            .get_mut(job_id)
+            .ok_or_else(|| Status::not_found("job not found"))?;
+        if job.ensure_in_flight().is_err() {
+            return Ok(());
+        }
+        let (low, high) = progress_range(job.state);
+        let fraction = if fraction.is_nan() { low } else { fraction.clamp(low, high) };
+        job.reported_progress = Some(ReportedProgress { fraction, message });
+        job.updated_at = ts_now();
+        Ok(())
+    }
+
     fn set_reservation_state(&self, job_id: &str, state: &str) -> Result<(), Status> {
         let mut jobs = self.jobs.write();
         let job = jobs
@@ -2425,18 +3846,190 @@ impl KernelRuntimeStore {
         job.reservation_state = Some("release_pending".to_string());
         job.reservation_released_reason = Some("cancel_requested".to_string());
         job.updated_at = ts_now();
-        Ok(job.clone())
+        let snapshot = job.clone();
+        drop(jobs);
+        self.cancel_signals
+            .lock()
+            .entry(job_id.to_string())
+            .or_insert_with(|| watch::channel(false).0)
+            .send_replace(true);
+        Ok(snapshot)
     }
 
-    fn request_deadline_terminalization(&self, job_id: &str) -> Result<JobRuntimeRecord, Status> {
+    /// Forgets a terminal job: its record, its cancel signal and the request and
+    /// idempotency entries pointing at it, so a resubmission creates it afresh.
+    fn remove_job(&self, job_id: &str) -> Result<JobRuntimeRecord, Status> {
         let mut jobs = self.jobs.write();
-        let job = jobs
-            .get_mut(job_id)
-            .ok_or_else(|| Status::not_found("job not found"))?;
-        if job.is_terminal() {
-            return Ok(job.clone());
+        let job = jobs.get(job_id).ok_or_else(|| Status::not_found("job not found"))?;
+        if !job.is_terminal() {
+            return Err(Status::failed_precondition("job is not terminal"));
         }
-        let stage = job.current_stage.unwrap_or(DagStageKind::ValidateEnqueue);
+        let job = jobs.remove(job_id).expect("job is present");
+        drop(jobs);
+        self.request_index.write().retain(|_, indexed| indexed != job_id);
+        self.idempotency.entries.lock().retain(|_, entry| entry.job_id != job_id);
+        self.cancel_signals.lock().remove(job_id);
+        self.metrics.job_removed(task_state_label(job.state));
+        Ok(job)
+    }
+
+    /// Takes back jobs a failed batch just created: their records, request and
+    /// idempotency entries and cancel signals. Their DAGs were never started.
+    fn discard_created_jobs(&self, job_ids: &[String]) {
+        let mut jobs = self.jobs.write();
+        for job_id in job_ids {
+            if let Some(job) = jobs.remove(job_id) {
+                self.metrics.job_removed(task_state_label(job.state));
+            }
+        }
+        drop(jobs);
+        self.request_index.write().retain(|_, indexed| !job_ids.contains(indexed));
+        self.idempotency.entries.lock().retain(|_, entry| !job_ids.contains(&entry.job_id));
+        let mut cancel_signals = self.cancel_signals.lock();
+        for job_id in job_ids {
+            cancel_signals.remove(job_id);
+        }
+    }
+
+    /// Holds a `QUEUED` job out of dispatch. Returns whether the job was paused;
+    /// jobs that already hold an execution slot, are being cancelled or are in any
+    /// other state are left untouched.
+    fn pause_job(&self, job_id: &str, reason: Option<String>) -> Result<(JobRuntimeRecord, bool), Status> {
+        let mut jobs = self.jobs.write();
+        let job = jobs
+            .get_mut(job_id)
+            .ok_or_else(|| Status::not_found("job not found"))?;
+        if job.state != TaskState::Queued || job.dispatched || job.cancel_requested {
+            return Ok((job.clone(), false));
+        }
+        job.transition_to(TaskState::Paused, "pause", reason);
+        job.updated_at = ts_now();
+        // Withdraw while still holding the jobs lock, so the job cannot be claimed
+        // between the transition and the queue update.
+        self.dispatcher.withdraw(job_id);
+        Ok((job.clone(), true))
+    }
+
+    /// Returns a `PAUSED` job to `QUEUED`. Returns whether the job was resumed.
+    fn resume_job(&self, job_id: &str, reason: Option<String>) -> Result<(JobRuntimeRecord, bool), Status> {
+        let mut jobs = self.jobs.write();
+        let job = jobs
+            .get_mut(job_id)
+            .ok_or_else(|| Status::not_found("job not found"))?;
+        if job.state != TaskState::Paused {
+            return Ok((job.clone(), false));
+        }
+        job.transition_to(TaskState::Queued, "resume", reason);
+        job.updated_at = ts_now();
+        Ok((job.clone(), true))
+    }
+
+    /// Marks the job as holding an execution slot, unless it was paused while the
+    /// slot was being handed out.
+    fn claim_dispatch(&self, job_id: &str) -> bool {
+        let mut jobs = self.jobs.write();
+        let Some(job) = jobs.get_mut(job_id) else {
+            return false;
+        };
+        if job.state == TaskState::Paused {
+            return false;
+        }
+        job.dispatched = true;
+        true
+    }
+
+    /// Waits until the pipeline has terminalized the job, bounded by `timeout`.
+    async fn wait_for_terminal(&self, job_id: &str, timeout: Duration) -> Option<JobRuntimeRecord> {
+        let deadline = tokio::time::Instant::now() + timeout;
+        loop {
+            let job = self.get(job_id)?;
+            if job.is_terminal() {
+                return Some(job);
+            }
+            if tokio::time::Instant::now() >= deadline {
+                return None;
+            }
+            tokio::time::sleep(CANCEL_ACK_POLL_INTERVAL).await;
+        }
+    }
+
+    /// Rewinds an ERROR/TIMEOUT job so its DAG can be re-run.
+    ///
+    /// Jobs that failed after compilation resume at optimize and keep their
+    /// validate/compile stage records; earlier failures restart from validation.
+    /// Returns `None` (leaving the record untouched) when the job is not retryable.
+    fn prepare_retry(
+        &self,
+        job_id: &str,
+        reason: Option<String>,
+    ) -> Result<(JobRuntimeRecord, Option<DagStageKind>), Status> {
+        let mut jobs = self.jobs.write();
+        let job = jobs
+            .get_mut(job_id)
+            .ok_or_else(|| Status::not_found("job not found"))?;
+        if !matches!(job.state, TaskState::Error | TaskState::Timeout) {
+            return Ok((job.clone(), None));
+        }
+
+        let resume_from = match job.failed_at_stage {
+            Some(stage) if stage.index() > DagStageKind::Compile.index() => DagStageKind::Optimize,
+            _ => DagStageKind::ValidateEnqueue,
+        };
+        job.stage_records.retain(|record| record.order < resume_from.index());
+        job.transition_to(resume_from.stage_state(), "retry", reason.clone());
+        job.current_stage = None;
+        job.failed_at_stage = None;
+        job.completed_at = None;
+        job.error_code = None;
+        job.error_summary = None;
+        job.error_details_ref = None;
+        job.workflow_failure_ref = None;
+        job.workflow_completion_ref = None;
+        job.cancel_requested = false;
+        job.cancel_reason = None;
+        job.cancellation_fanout_ref = None;
+        job.dispatched = false;
+        job.counts.clear();
+        job.qfs_result_ref = None;
+        job.reservation_state = Some("held".to_string());
+        job.reservation_released_reason = None;
+        job.deadline_at = job
+            .submission
+            .deadline_seconds
+            .and_then(|seconds| {
+                normalized_deadline_at(&ProtoDuration {
+                    seconds: seconds as i64,
+                    nanos: 0,
+                })
+            })
+            .or_else(|| self.default_deadline_at());
+        job.retry_count += 1;
+        job.metadata
+            .insert("retry.manual_count".to_string(), job.retry_count.to_string());
+        job.metadata
+            .insert("retry.resume_stage".to_string(), resume_from.key().to_string());
+        if let Some(reason) = reason {
+            job.metadata.insert("retry.manual_reason".to_string(), reason);
+        }
+        job.updated_at = ts_now();
+        let snapshot = job.clone();
+        drop(jobs);
+        if let Some(signal) = self.cancel_signals.lock().get(job_id) {
+            signal.send_replace(false);
+        }
+        Ok((snapshot, Some(resume_from)))
+    }
+
+    fn request_deadline_terminalization(&self, job_id: &str) -> Result<JobRuntimeRecord, Status> {
+        let mut jobs = self.jobs.write();
+        let job = jobs
+            .get_mut(job_id)
+            .ok_or_else(|| Status::not_found("job not found"))?;
+        if job.is_terminal() {
+            return Ok(job.clone());
+        }
+        let stage = job.current_stage.unwrap_or(DagStageKind::ValidateEnqueue);
+        job.failed_at_stage = Some(stage);
         let stage_id = stage_id(job_id, stage);
         let state_before = job.state;
         let input_ref = workflow_stage_input_ref(job_id, stage);
@@ -2444,14 +4037,14 @@ impl KernelRuntimeStore {
         let failure_ref = workflow_stage_failure_ref(job_id, stage);
         let workflow_failure_ref = workflow_failure_ref(job_id);
 
-        job.state = TaskState::Timeout;
+        job.transition_to(TaskState::Timeout, "timeout", Some("deadline_exceeded".to_string()));
         job.cancel_requested = true;
         job.cancel_reason = Some("deadline_exceeded".to_string());
         job.cancellation_fanout_ref = Some(format!("qfs://jobs/{job_id}/control/deadline.json"));
         job.reservation_state = Some("released".to_string());
         job.error_code = Some("DEADLINE_EXCEEDED".to_string());
         job.error_summary = Some("deadline exceeded while orchestrating the job".to_string());
-        job.error_details_ref = Some(format!("qfs://jobs/{job_id}/errors/deadline.json"));
+        job.error_details_ref = Some(job_error_details_ref(job_id));
         job.workflow_failure_ref = Some(workflow_failure_ref.clone());
         job.completed_at = Some(ts_now());
         job.updated_at = ts_now();
@@ -2551,6 +4144,7 @@ impl KernelRuntimeStore {
         let job = jobs
             .get_mut(job_id)
             .ok_or_else(|| Status::not_found("job not found"))?;
+        job.ensure_in_flight()?;
         job.counts = counts;
         job.updated_at = ts_now();
         Ok(())
@@ -2566,6 +4160,19 @@ impl KernelRuntimeStore {
         Ok(())
     }
 
+    /// Snapshots all jobs, optionally restricted to one state, ordered by creation time.
+    fn list(&self, state: Option<TaskState>) -> Vec<JobRuntimeRecord> {
+        let mut jobs: Vec<JobRuntimeRecord> = self
+            .jobs
+            .read()
+            .values()
+            .filter(|job| state.is_none_or(|state| job.state == state))
+            .cloned()
+            .collect();
+        jobs.sort_by_key(job_page_key);
+        jobs
+    }
+
     fn all_stage_updates(&self, job_id: &str) -> Result<Vec<JobUpdateEnvelope>, Status> {
         let job = self
             .get(job_id)
@@ -2610,16 +4217,21 @@ impl KernelRuntimeStore {
 #[derive(Debug, Clone)]
 struct KernelStageError {
     grpc_code: Code,
-    error_code: &'static str,
+    error_code: Cow<'static, str>,
     summary: String,
     details_ref: String,
 }
 
 impl KernelStageError {
-    fn new(grpc_code: Code, error_code: &'static str, summary: impl Into<String>, details_ref: impl Into<String>) -> Self {
+    fn new(
+        grpc_code: Code,
+        error_code: impl Into<Cow<'static, str>>,
+        summary: impl Into<String>,
+        details_ref: impl Into<String>,
+    ) -> Self {
         Self {
             grpc_code,
-            error_code,
+            error_code: error_code.into(),
             summary: summary.into(),
             details_ref: details_ref.into(),
         }
@@ -2801,7 +4413,7 @@ fn merge_result_summary_json_value(
 }
 
 fn merge_result_summary_artifact(
-    qfs: &CircuitFsLocal,
+    qfs: &dyn CircuitFsBackend,
     summary: &mut BTreeMap<String, String>,
     artifact_ref: &str,
 ) {
@@ -2809,7 +4421,7 @@ fn merge_result_summary_artifact(
         return;
     }
 
-    let bytes = match qfs.read_bytes(artifact_ref) {
+    let bytes = match qfs.read_bytes(Path::new(artifact_ref)) {
         Ok(bytes) => bytes,
         Err(err) => {
             tracing::debug!(artifact_ref = %artifact_ref, error = %err, "workflow output artifact unavailable");
@@ -2916,6 +4528,14 @@ impl RetryPolicy {
 
 #[tonic::async_trait]
 trait OrchestrationAdapters: Send + Sync {
+    /// Persists the submitted JobSpec and program source before the DAG starts.
+    ///
+    /// Returns the QFS job root ref, or `None` when the request carried no JobSpec.
+    async fn store_source_bundle(
+        &self,
+        submission: &NormalizedSubmission,
+    ) -> Result<Option<String>, KernelStageError>;
+
     async fn validate_enqueue(
         &self,
         submission: &NormalizedSubmission,
@@ -2964,11 +4584,23 @@ trait OrchestrationAdapters: Send + Sync {
         submission: &NormalizedSubmission,
         observability_output: &BTreeMap<String, String>,
     ) -> Result<BTreeMap<String, String>, KernelStageError>;
+
+    /// Writes the failure document that the job's `error_details_ref` points at.
+    async fn store_error_details(&self, job_id: &str, details: &serde_json::Value) -> Result<(), KernelStageError>;
+
+    /// Local path of the job's `logs/<log_name>.jsonl`, which may not exist yet.
+    fn job_log_path(&self, job_id: &str, log_name: &str) -> Result<PathBuf, KernelStageError>;
+
+    /// Removes the job's QFS directory. Returns whether there was one to remove.
+    async fn delete_job_artifacts(&self, job_id: &str) -> Result<bool, KernelStageError>;
+
+    /// Execution backends `execute` dispatches to, for `GetKernelInfo`.
+    fn backend_names(&self) -> Vec<String>;
 }
 
 #[derive(Clone)]
 struct FixtureAdapters {
-    qfs: CircuitFsLocal,
+    qfs: Arc<dyn CircuitFsBackend>,
     failure_stage: Option<DagStageKind>,
     hold_stage: Option<DagStageKind>,
     hold_for: Duration,
@@ -3012,7 +4644,7 @@ impl FixtureAdapters {
             };
 
         Self {
-            qfs: CircuitFsLocal::new(qfs_root),
+            qfs: Arc::new(CircuitFsLocal::new(qfs_root)),
             failure_stage: None,
             hold_stage,
             hold_for,
@@ -3025,7 +4657,7 @@ impl FixtureAdapters {
 
     fn new(qfs_root: impl AsRef<str>, failure_stage: Option<DagStageKind>) -> Self {
         Self {
-            qfs: CircuitFsLocal::new(qfs_root.as_ref()),
+            qfs: Arc::new(CircuitFsLocal::new(qfs_root.as_ref())),
             failure_stage,
             hold_stage: None,
             hold_for: Duration::from_millis(0),
@@ -3043,7 +4675,7 @@ impl FixtureAdapters {
         hold_for: Duration,
     ) -> Self {
         Self {
-            qfs: CircuitFsLocal::new(qfs_root.as_ref()),
+            qfs: Arc::new(CircuitFsLocal::new(qfs_root.as_ref())),
             failure_stage,
             hold_stage,
             hold_for,
@@ -3060,7 +4692,7 @@ impl FixtureAdapters {
         execute_script: Vec<ExecuteScriptStep>,
     ) -> Self {
         Self {
-            qfs: CircuitFsLocal::new(qfs_root.as_ref()),
+            qfs: Arc::new(CircuitFsLocal::new(qfs_root.as_ref())),
             failure_stage,
             hold_stage: None,
             hold_for: Duration::from_millis(0),
@@ -3219,19 +4851,14 @@ impl FixtureAdapters {
             .ok_or_else(|| KernelStageError::execute("driver-manager endpoint is not configured", "qfs://jobs/driver-manager/config/error.json"))?;
 
         let compiled_artifact_ref = format!("qfs://jobs/{}/compiled/circuit.aqo.json", submission.job_id);
-        let aqo_bytes = self.qfs.read_bytes(&compiled_artifact_ref).map_err(|err| {
+        let aqo_bytes = self.qfs.read_bytes(Path::new(&compiled_artifact_ref)).map_err(|err| {
             KernelStageError::execute(
                 format!("compiled aqo artifact missing: {err}"),
                 compiled_artifact_ref.clone(),
             )
         })?;
 
-        let shots = submission
-            .metadata_kvs
-            .get("shots")
-            .and_then(|raw| raw.parse::<i32>().ok())
-            .filter(|v| *v > 0)
-            .unwrap_or(1024);
+        let shots = i32::try_from(submission.shots()).unwrap_or(i32::MAX);
 
         let channel = Endpoint::from_shared(endpoint.clone()).map_err(|err| {
             KernelStageError::execute(
@@ -3279,14 +4906,14 @@ impl FixtureAdapters {
         let counts_payload = serde_json::to_vec_pretty(&serde_json::json!({"counts": counts.clone()})).unwrap_or_default();
         let execution_payload_bytes = serde_json::to_vec_pretty(&execution_payload).unwrap_or_default();
 
-        self.qfs.write_bytes(&counts_ref, &counts_payload).map_err(|err| {
+        self.qfs.write_bytes(Path::new(&counts_ref), &counts_payload).map_err(|err| {
             KernelStageError::execute(
                 format!("failed to persist counts artifact: {err}"),
                 counts_ref.clone(),
             )
         })?;
 
-        self.qfs.write_bytes(&execution_ref, &execution_payload_bytes).map_err(|err| {
+        self.qfs.write_bytes(Path::new(&execution_ref), &execution_payload_bytes).map_err(|err| {
             KernelStageError::execute(
                 format!("failed to persist execution artifact: {err}"),
                 execution_ref.clone(),
@@ -3316,6 +4943,38 @@ impl FixtureAdapters {
 
 #[tonic::async_trait]
 impl OrchestrationAdapters for FixtureAdapters {
+    async fn store_source_bundle(
+        &self,
+        submission: &NormalizedSubmission,
+    ) -> Result<Option<String>, KernelStageError> {
+        if submission.job_yaml.is_empty() {
+            return Ok(None);
+        }
+        let job_id = &submission.job_id;
+        let details_ref = format!("qfs://jobs/{job_id}/input/job.yaml");
+        let bundle = SourceBundle {
+            job_yaml: submission.job_yaml.clone(),
+            program_eigen_py: if submission.program_format == "eigen_lang_source" {
+                submission.program.clone()
+            } else {
+                Vec::new()
+            },
+        };
+        match self.qfs.store_source_bundle(job_id, &bundle) {
+            Ok(()) => {}
+            // Idempotent re-submission of the same payload finds its own bundle.
+            Err(CircuitFsError::AlreadyExists { .. })
+                if self.qfs.load_source_bundle(job_id).ok().as_ref() == Some(&bundle) => {}
+            Err(err) => {
+                return Err(KernelStageError::persist(
+                    format!("failed to store source bundle: {err}"),
+                    details_ref,
+                ));
+            }
+        }
+        Ok(Some(format!("qfs://jobs/{job_id}")))
+    }
+
     async fn validate_enqueue(
         &self,
         submission: &NormalizedSubmission,
@@ -3340,7 +4999,7 @@ impl OrchestrationAdapters for FixtureAdapters {
             stage = "validate-enqueue",
             "submission accepted into orchestration DAG"
         );
-        persist_stage_output_artifact(&self.qfs, &submission.job_id, DagStageKind::ValidateEnqueue, &output)?;
+        persist_stage_output_artifact(self.qfs.as_ref(), &submission.job_id, DagStageKind::ValidateEnqueue, &output)?;
         Ok(output)
     }
 
@@ -3370,7 +5029,7 @@ impl OrchestrationAdapters for FixtureAdapters {
                 ),
             ])
         };
-        persist_stage_output_artifact(&self.qfs, &submission.job_id, DagStageKind::Compile, &output)?;
+        persist_stage_output_artifact(self.qfs.as_ref(), &submission.job_id, DagStageKind::Compile, &output)?;
         Ok(output)
     }
 
@@ -3392,7 +5051,7 @@ impl OrchestrationAdapters for FixtureAdapters {
                 Code::InvalidArgument => KernelStageError::invalid_argument(err.summary, err.details_ref),
                 _ => KernelStageError::optimize(err.summary, err.details_ref),
             })?;
-        persist_stage_output_artifact(&self.qfs, &submission.job_id, DagStageKind::Optimize, &output)?;
+        persist_stage_output_artifact(self.qfs.as_ref(), &submission.job_id, DagStageKind::Optimize, &output)?;
         Ok(output)
     }
 
@@ -3434,7 +5093,7 @@ impl OrchestrationAdapters for FixtureAdapters {
         if let Some(optimize_output) = _optimize_output.get("model_version") {
             output.insert("optimizer_model_version".to_string(), optimize_output.clone());
         }
-        persist_stage_output_artifact(&self.qfs, &submission.job_id, DagStageKind::Schedule, &output)?;
+        persist_stage_output_artifact(self.qfs.as_ref(), &submission.job_id, DagStageKind::Schedule, &output)?;
         Ok(output)
     }
 
@@ -3492,16 +5151,20 @@ impl OrchestrationAdapters for FixtureAdapters {
                     ));
                 }
             }
-            let shots = submission
-                .metadata_kvs
-                .get("shots")
-                .and_then(|raw| raw.parse::<i64>().ok())
-                .filter(|v| *v > 0)
-                .unwrap_or(1024);
-
-            let mut counts = BTreeMap::new();
-            counts.insert("0".to_string(), shots);
+            // Simulate the compiled AQO when the compile stage left one, else the source.
+            let compiled_ref = format!("qfs://jobs/{}/compiled/circuit.aqo.json", submission.job_id);
+            let compiled = self.qfs.read_bytes(Path::new(&compiled_ref)).ok().filter(|aqo| !aqo.is_empty());
+            let circuit = match &compiled {
+                Some(aqo) => aqo.as_slice(),
+                None if submission.program.is_empty() => submission.name.as_bytes(),
+                None => &submission.program,
+            };
+            let counts = simulate_counts(circuit, submission.shots(), submission.seed());
             let counts_ref = format!("qfs://jobs/{}/results/counts.json", submission.job_id);
+            let counts_payload = serde_json::to_vec_pretty(&serde_json::json!({"counts": &counts})).unwrap_or_default();
+            self.qfs.write_bytes(Path::new(&counts_ref), &counts_payload).map_err(|err| {
+                KernelStageError::execute(format!("failed to persist counts artifact: {err}"), counts_ref.clone())
+            })?;
             let execution_ref = format!("qfs://jobs/{}/execution/execution.json", submission.job_id);
 
             ExecutionOutcome {
@@ -3521,7 +5184,7 @@ impl OrchestrationAdapters for FixtureAdapters {
                 metadata: BTreeMap::new(),
             }
             };
-        persist_stage_output_artifact(&self.qfs, &submission.job_id, DagStageKind::Execute, &outcome.output)?;
+        persist_stage_output_artifact(self.qfs.as_ref(), &submission.job_id, DagStageKind::Execute, &outcome.output)?;
         Ok(outcome)
     }
 
@@ -3576,7 +5239,7 @@ impl OrchestrationAdapters for FixtureAdapters {
             merge_result_summary_fields(&mut summary, &optimize_stage.output);
         }
         if let Some(workflow_output_ref) = summary.get("workflow_output_ref").cloned() {
-            merge_result_summary_artifact(&self.qfs, &mut summary, &workflow_output_ref);
+            merge_result_summary_artifact(self.qfs.as_ref(), &mut summary, &workflow_output_ref);
         }
         merge_result_summary_fields(&mut summary, &execution_output.output);
         merge_result_summary_fields(&mut summary, &execution_output.metadata);
@@ -3590,6 +5253,9 @@ impl OrchestrationAdapters for FixtureAdapters {
             .map(|(key, value)| (format!("result.summary.{key}"), value.clone()))
             .collect();
         summary.extend(summary_metadata.clone());
+        // Timings go to results/metadata.json but stay out of the persist output,
+        // which is hashed into the job snapshot and must not vary between runs.
+        summary.extend(stage_timings(stage_records, unix_epoch_ms_u64() as i128));
         let measurements = scientific_measurements(
             submission,
             execution_output,
@@ -3744,7 +5410,7 @@ impl OrchestrationAdapters for FixtureAdapters {
             ("optimizer_version".to_string(), optimizer_version),
         ]);
         persist_output.extend(summary_metadata);
-        persist_stage_output_artifact(&self.qfs, &submission.job_id, DagStageKind::Persist, &persist_output)?;
+        persist_stage_output_artifact(self.qfs.as_ref(), &submission.job_id, DagStageKind::Persist, &persist_output)?;
         Ok(persist_output)
     }
 
@@ -3825,7 +5491,7 @@ impl OrchestrationAdapters for FixtureAdapters {
                 format!("qfs://jobs/{}/trace.json", submission.job_id),
             ),
         ]);
-        persist_stage_output_artifact(&self.qfs, &submission.job_id, DagStageKind::RecordKnowledgeObservability, &output)?;
+        persist_stage_output_artifact(self.qfs.as_ref(), &submission.job_id, DagStageKind::RecordKnowledgeObservability, &output)?;
         Ok(output)
     }
 
@@ -3854,9 +5520,43 @@ impl OrchestrationAdapters for FixtureAdapters {
                     .unwrap_or_else(|| format!("qfs://jobs/{}/results/result.json", submission.job_id)),
             ),
         ]);
-        persist_stage_output_artifact(&self.qfs, &submission.job_id, DagStageKind::Finalize, &output)?;
+        persist_stage_output_artifact(self.qfs.as_ref(), &submission.job_id, DagStageKind::Finalize, &output)?;
         Ok(output)
     }
+
+    async fn store_error_details(&self, job_id: &str, details: &serde_json::Value) -> Result<(), KernelStageError> {
+        let details_ref = job_error_details_ref(job_id);
+        let payload = serde_json::to_vec_pretty(details).map_err(|err| {
+            KernelStageError::internal(format!("failed to serialize error details: {err}"), details_ref.clone())
+        })?;
+        self.qfs
+            .store_error_details_json(job_id, &payload)
+            .map_err(|err| KernelStageError::persist(format!("failed to store error details: {err}"), details_ref))
+    }
+
+    fn job_log_path(&self, job_id: &str, log_name: &str) -> Result<PathBuf, KernelStageError> {
+        self.qfs.job_log_path(job_id, log_name).map_err(|err| {
+            KernelStageError::invalid_argument(err.to_string(), format!("qfs://jobs/{job_id}/logs/{log_name}.jsonl"))
+        })
+    }
+
+    async fn delete_job_artifacts(&self, job_id: &str) -> Result<bool, KernelStageError> {
+        match self.qfs.delete_job(job_id) {
+            Ok(()) => Ok(true),
+            Err(CircuitFsError::NotFound { .. }) => Ok(false),
+            Err(err) => Err(KernelStageError::persist(
+                format!("failed to delete job artifacts: {err}"),
+                format!("qfs://jobs/{job_id}"),
+            )),
+        }
+    }
+
+    fn backend_names(&self) -> Vec<String> {
+        match &self.driver_manager_endpoint {
+            Some(endpoint) => vec![format!("driver-manager:{endpoint}")],
+            None => vec!["fixture-simulator".to_string()],
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -4105,90 +5805,283 @@ fn stable_seed_from_submission(submission: &NormalizedSubmission) -> u64 {
     )
 }
 
+/// Log names map to `logs/<log_name>.jsonl`, so they must stay a single path segment.
+fn validate_log_name(log_name: &str) -> Result<(), Status> {
+    let valid_chars = log_name
+        .chars()
+        .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '.' | '_' | '-'));
+    if log_name.is_empty() || log_name.contains("..") || !valid_chars {
+        return Err(Status::invalid_argument(format!("invalid log name: {log_name:?}")));
+    }
+    Ok(())
+}
+
+/// Waits for a followed log to be created. Gives up after [`JOB_LOGS_WAIT_TIMEOUT`],
+/// once the job is terminal without having written it, or when the reader hangs up.
+async fn wait_for_log_file(
+    runtime: &KernelRuntimeStore,
+    job_id: &str,
+    path: &Path,
+    tx: &mpsc::Sender<Result<JobLogLine, Status>>,
+) -> bool {
+    let deadline = tokio::time::Instant::now() + JOB_LOGS_WAIT_TIMEOUT;
+    loop {
+        let terminal = runtime.get(job_id).is_none_or(|job| job.is_terminal());
+        if path.is_file() {
+            return true;
+        }
+        if terminal || tx.is_closed() || tokio::time::Instant::now() >= deadline {
+            return false;
+        }
+        tokio::time::sleep(JOB_LOGS_POLL_INTERVAL).await;
+    }
+}
+
+/// Reads the complete lines appended to a log since the previous call. A trailing
+/// line without its newline is held back until the writer finishes it.
+struct JobLogReader {
+    path: PathBuf,
+    offset: u64,
+    next_line: u64,
+    from_line: u64,
+    partial: Vec<u8>,
+}
+
+impl JobLogReader {
+    fn new(path: PathBuf, from_line: u64) -> Self {
+        Self {
+            path,
+            offset: 0,
+            next_line: 0,
+            from_line,
+            partial: Vec::new(),
+        }
+    }
+
+    fn read_new_lines(&mut self) -> std::io::Result<Vec<JobLogLine>> {
+        let mut file = fs::File::open(&self.path)?;
+        file.seek(SeekFrom::Start(self.offset))?;
+        let mut appended = Vec::new();
+        self.offset += file.read_to_end(&mut appended)? as u64;
+        self.partial.extend_from_slice(&appended);
+
+        let mut lines = Vec::new();
+        while let Some(end) = self.partial.iter().position(|byte| *byte == b'\n') {
+            let raw: Vec<u8> = self.partial.drain(..=end).collect();
+            let line_number = self.next_line;
+            self.next_line += 1;
+            if line_number >= self.from_line {
+                lines.push(JobLogLine {
+                    line_number,
+                    line: String::from_utf8_lossy(&raw[..end]).into_owned(),
+                });
+            }
+        }
+        Ok(lines)
+    }
+}
+
 #[tonic::async_trait]
 impl KernelGatewayService for KernelGatewaySvc {
     type StreamJobUpdatesStream =
         Pin<Box<dyn Stream<Item = Result<StreamJobUpdatesResponse, Status>> + Send + 'static>>;
+    type WatchJobStatusStream =
+        Pin<Box<dyn Stream<Item = Result<GetJobStatusResponse, Status>> + Send + 'static>>;
+    type GetJobLogsStream = Pin<Box<dyn Stream<Item = Result<JobLogLine, Status>> + Send + 'static>>;
 
     async fn enqueue_job(
         &self,
         request: Request<EnqueueJobRequest>,
     ) -> Result<Response<EnqueueJobResponse>, Status> {
-        let req = request.into_inner();
-        let submission = NormalizedSubmission::from_request(&req)?;
-        let (job, created) = self.runtime.create_or_get_job(submission.clone())?;
-
-        if created {
-            let runtime = self.runtime.clone();
-            let adapters = self.adapters.clone();
-            let job_id = job.job_id.clone();
-            let submission_for_task = submission.clone();
-
-            tokio::spawn(async move {
-                let span = tracing::info_span!("kernel_dag", job_id = %job_id);
-                async move {
-                    if let Err(err) =
-                        run_job_dag(runtime.clone(), adapters.clone(), job_id.clone(), submission_for_task).await
-                    {
-                        let terminalization = match err.grpc_code {
-                            Code::DeadlineExceeded => runtime.request_deadline_terminalization(&job_id).map(|_| ()),
-                            _ => runtime
-                                .request_error_terminalization(&job_id, &err.error_code, &err.summary, &err.details_ref)
-                                .map(|_| ()),
-                        };
-                        if let Err(status) = terminalization {
-                            tracing::error!(error = %status, "kernel terminalization failed");
-                        }
-                        tracing::error!(error = %err, "kernel dag failed");
-                    }
-                }
-                .instrument(span)
-                .await;
+        self.runtime.ensure_accepting_jobs()?;
+        let headers = request.metadata().clone().into_headers();
+        let caller = extract_caller(&request);
+        let result = self.enqueue(request.into_inner(), &headers, &caller).await;
+        match &result {
+            Ok(_) => self.runtime.enqueue_failures.record_success(),
+            Err(status) => self.runtime.enqueue_failures.record_failure(status.code()),
+        }
+        result.map(Response::new)
+    }
+
+    async fn enqueue_job_batch(
+        &self,
+        request: Request<EnqueueJobBatchRequest>,
+    ) -> Result<Response<EnqueueJobBatchResponse>, Status> {
+        self.runtime.ensure_accepting_jobs()?;
+        let headers = request.metadata().clone().into_headers();
+        let caller = extract_caller(&request);
+        let jobs = request.into_inner().jobs;
+        let limit = self.runtime.enqueue_batch_limit.0;
+        if jobs.len() > limit {
+            return Err(Status::invalid_argument(format!(
+                "batch of {} jobs exceeds the limit of {limit}",
+                jobs.len()
+            )));
+        }
+        let mut results = Vec::with_capacity(jobs.len());
+        for job in jobs {
+            results.push(match self.enqueue(job, &headers, &caller).await {
+                Ok(enqueued) => EnqueueJobBatchResult {
+                    accepted: true,
+                    job_id: enqueued.job_id,
+                    state: enqueued.state,
+                    created_at: enqueued.created_at,
+                    ..Default::default()
+                },
+                Err(status) => EnqueueJobBatchResult {
+                    accepted: false,
+                    error_code: status.code() as i32,
+                    error_message: status.message().to_string(),
+                    ..Default::default()
+                },
             });
         }
+        Ok(Response::new(EnqueueJobBatchResponse { results }))
+    }
 
-        Ok(Response::new(EnqueueJobResponse {
-            job_id: job.job_id,
-            state: job.state as i32,
-            created_at: Some(job.created_at.clone()),
-        }))
+    async fn batch_enqueue_jobs(
+        &self,
+        request: Request<BatchEnqueueJobsRequest>,
+    ) -> Result<Response<BatchEnqueueJobsResponse>, Status> {
+        self.runtime.ensure_accepting_jobs()?;
+        let headers = request.metadata().clone().into_headers();
+        let caller = extract_caller(&request);
+        let jobs = request.into_inner().jobs;
+        if jobs.len() > MAX_BATCH_ENQUEUE_JOBS {
+            return Err(Status::invalid_argument(format!(
+                "batch of {} jobs exceeds the limit of {MAX_BATCH_ENQUEUE_JOBS}",
+                jobs.len()
+            )));
+        }
+        let mut submissions = Vec::with_capacity(jobs.len());
+        for (index, job) in jobs.iter().enumerate() {
+            submissions.push(self.admit(job, &caller).map_err(|status| at_batch_index(index, status))?);
+        }
+        let created = self
+            .runtime
+            .create_or_get_jobs(&submissions)
+            .map_err(|(index, status)| at_batch_index(index, status))?;
+
+        // Source bundles are written only once every entry is planned, and no DAG
+        // starts until all of them are recorded.
+        let mut failed = None;
+        for (index, ((job, _), submission)) in created.iter().zip(&submissions).enumerate() {
+            let recorded = match self.adapters.store_source_bundle(submission).await {
+                Ok(source_bundle_ref) => self.record_source_bundle(job, source_bundle_ref),
+                Err(err) => Err(err.into_status()),
+            };
+            if let Err(status) = recorded {
+                failed = Some(at_batch_index(index, status));
+                break;
+            }
+        }
+        if let Some(status) = failed {
+            self.roll_back_created(created).await;
+            return Err(status);
+        }
+
+        let mut results = Vec::with_capacity(created.len());
+        for ((job, lease), submission) in created.into_iter().zip(submissions) {
+            self.spawn_enqueued(&job, lease, submission, &headers);
+            results.push(job.enqueue_response());
+        }
+        Ok(Response::new(BatchEnqueueJobsResponse { results }))
     }
 
     async fn get_job_status(
         &self,
         request: Request<GetJobStatusRequest>,
     ) -> Result<Response<GetJobStatusResponse>, Status> {
+        let caller = extract_caller(&request);
+        let request_id = rpc_request_id(request.metadata().as_ref()).unwrap_or_default();
         let job_id = request.into_inner().job_id;
-        let job = self
+        check_job_id(&job_id)?;
+        let job = self.visible_job(&caller, &job_id)?;
+        self.authorize(&caller, Action::Status, &job.resource())?;
+
+        let response = job.status_response();
+        tracing::debug!(job_id = %job_id, request_id = %request_id, state = response.state, "job status read");
+        Ok(Response::new(response))
+    }
+
+    async fn watch_job_status(
+        &self,
+        request: Request<WatchJobStatusRequest>,
+    ) -> Result<Response<Self::WatchJobStatusStream>, Status> {
+        let caller = extract_caller(&request);
+        let job_id = request.into_inner().job_id;
+        let job = self.visible_job(&caller, &job_id)?;
+        self.authorize(&caller, Action::Status, &job.resource())?;
+        let (job, mut state_rx) = self
             .runtime
-            .get(&job_id)
+            .watch_state(&job_id)
             .ok_or_else(|| Status::not_found("job not found"))?;
+        let runtime = self.runtime.clone();
+        let (tx, rx) = mpsc::channel(WATCH_JOB_STATUS_BUFFER);
 
-        Ok(Response::new(GetJobStatusResponse {
-            job_id: job.job_id.clone(),
-            state: job.state as i32,
-            stage: job.stage_label(),
-            progress: job.progress(),
-            message: job
-                .stage_records
-                .last()
-                .and_then(|stage| stage.output.get("message").cloned())
-                .or_else(|| job.error_summary.clone())
-                .unwrap_or_else(|| "job accepted".to_string()),
-            error_code: job.error_code.unwrap_or_default(),
-            error_summary: job.error_summary.unwrap_or_default(),
-            error_details_ref: job.error_details_ref.unwrap_or_default(),
-            updated_at: Some(job.updated_at.clone()),
-        }))
+        tokio::spawn(async move {
+            let mut sent = job.history.len();
+            if tx.send(Ok(job.status_response())).await.is_err() || job.is_terminal() {
+                return;
+            }
+            while state_rx.changed().await.is_ok() {
+                let Some(job) = runtime.get(&job_id) else {
+                    return;
+                };
+                // The watch channel only keeps the latest state, so replay every
+                // transition recorded since the last frame from the history.
+                let new_entries = &job.history[sent.min(job.history.len())..];
+                if new_entries.is_empty() && tx.send(Ok(job.status_response())).await.is_err() {
+                    return;
+                }
+                for (idx, entry) in new_entries.iter().enumerate() {
+                    let frame = if idx + 1 == new_entries.len() {
+                        job.status_response()
+                    } else {
+                        job.transition_status_response(entry)
+                    };
+                    if tx.send(Ok(frame)).await.is_err() {
+                        return;
+                    }
+                }
+                sent = job.history.len();
+                if job.is_terminal() {
+                    return;
+                }
+            }
+        });
+
+        Ok(Response::new(Box::pin(ReceiverStream::new(rx))))
     }
 
     async fn cancel_job(
         &self,
         request: Request<CancelJobRequest>,
     ) -> Result<Response<CancelJobResponse>, Status> {
+        let caller = extract_caller(&request);
         let req = request.into_inner();
         let job_id = req.job_id;
-        let job = self.runtime.request_cancel(&job_id, None)?;
+        check_job_id(&job_id)?;
+        let job = self.visible_job(&caller, &job_id)?;
+        self.authorize(&caller, Action::Cancel, &job.resource())?;
+        // Recorded as the reason of the cancel transition.
+        let requested_by = req
+            .metadata
+            .as_ref()
+            .map(|metadata| metadata.subject.trim())
+            .filter(|subject| !subject.is_empty())
+            .map(|subject| format!("requested by {subject}"));
+        let job = match self.runtime.request_cancel(&job_id, requested_by) {
+            Ok(job) => job,
+            Err(status) if status.code() == Code::FailedPrecondition => {
+                return Ok(Response::new(CancelJobResponse {
+                    accepted: false,
+                    reason_code: "ALREADY_TERMINAL".to_string(),
+                }));
+            }
+            Err(status) => return Err(status),
+        };
         tracing::info!(
             event = "cancel",
             trace_id = %job.submission.trace_id,
@@ -4198,13 +6091,16 @@ impl KernelGatewayService for KernelGatewaySvc {
             stage = job.stage_label(),
             "cancellation requested"
         );
+
+        // Only acknowledge once the DAG task has stopped progressing the job.
+        let (accepted, reason_code) = match self.runtime.wait_for_terminal(&job_id, CANCEL_ACK_TIMEOUT).await {
+            Some(job) if job.state == TaskState::Cancelled => (true, "CANCELLED"),
+            Some(_) => (false, "ALREADY_TERMINAL"),
+            None => (false, "CANCEL_PENDING"),
+        };
         Ok(Response::new(CancelJobResponse {
-            accepted: true,
-            reason_code: if job.is_terminal() {
-                "CANCELLED".to_string()
-            } else {
-                "ACCEPTED".to_string()
-            },
+            accepted,
+            reason_code: reason_code.to_string(),
         }))
     }
 
@@ -4212,18 +6108,24 @@ impl KernelGatewayService for KernelGatewaySvc {
         &self,
         request: Request<GetJobResultsRequest>,
     ) -> Result<Response<GetJobResultsResponse>, Status> {
+        let caller = extract_caller(&request);
         let job_id = request.into_inner().job_id;
-        let job = self
-            .runtime
-            .get(&job_id)
-            .ok_or_else(|| Status::not_found("job not found"))?;
+        check_job_id(&job_id)?;
+        let job = self.visible_job(&caller, &job_id)?;
+        self.authorize(&caller, Action::Results, &job.resource())?;
 
         if !job.is_terminal() {
             return Err(Status::failed_precondition("job results are not ready"));
         }
 
         let counts: HashMap<String, i64> = job.counts.clone().into_iter().collect();
-        let metadata: HashMap<String, String> = job.metadata.clone().into_iter().collect();
+        let end_ms = job.completed_at.as_ref().map(timestamp_to_ms).unwrap_or_else(|| unix_epoch_ms_u64() as i128);
+        let mut metadata: HashMap<String, String> = job.metadata.clone().into_iter().collect();
+        metadata.extend(
+            stage_timings(&job.stage_records, end_ms)
+                .into_iter()
+                .map(|(key, value)| (format!("result.summary.{key}"), value)),
+        );
 
         Ok(Response::new(GetJobResultsResponse {
             job_id: job.job_id.clone(),
@@ -4242,7 +6144,10 @@ impl KernelGatewayService for KernelGatewaySvc {
         &self,
         request: Request<StreamJobUpdatesRequest>,
     ) -> Result<Response<Self::StreamJobUpdatesStream>, Status> {
+        let caller = extract_caller(&request);
         let job_id = request.into_inner().job_id;
+        let job = self.visible_job(&caller, &job_id)?;
+        self.authorize(&caller, Action::Status, &job.resource())?;
         let deadline = tokio::time::Instant::now() + Duration::from_secs(10);
         loop {
             if let Some(job) = self.runtime.get(&job_id) {
@@ -4266,11 +6171,10 @@ impl KernelGatewayService for KernelGatewaySvc {
         &self,
         request: Request<GetDispatchRationaleRequest>,
     ) -> Result<Response<GetDispatchRationaleResponse>, Status> {
+        let caller = extract_caller(&request);
         let job_id = request.into_inner().job_id;
-        let job = self
-            .runtime
-            .get(&job_id)
-            .ok_or_else(|| Status::not_found("job not found"))?;
+        let job = self.visible_job(&caller, &job_id)?;
+        self.authorize(&caller, Action::Status, &job.resource())?;
 
         let schedule_stage = job
             .schedule_stage()
@@ -4340,93 +6244,450 @@ impl KernelGatewayService for KernelGatewaySvc {
             rationale: Some(rationale),
         }))
     }
-}
 
-async fn run_job_dag(
-    runtime: Arc<KernelRuntimeStore>,
-    adapters: Arc<dyn OrchestrationAdapters>,
-    job_id: String,
-    submission: NormalizedSubmission,
-) -> Result<(), KernelStageError> {
-    let mut validation_output = BTreeMap::new();
-    let mut compile_output = BTreeMap::new();
-    let mut optimize_output = BTreeMap::new();
-    let mut schedule_output = BTreeMap::new();
-    let mut execution_output = ExecutionOutcome {
-        counts: BTreeMap::new(),
-        output: BTreeMap::new(),
-        metadata: BTreeMap::new(),
-    };
-    let mut persist_output = BTreeMap::new();
-    let mut observability_output = BTreeMap::new();
+    async fn list_jobs(
+        &self,
+        request: Request<ListJobsRequest>,
+    ) -> Result<Response<ListJobsResponse>, Status> {
+        let caller = extract_caller(&request);
+        let req = request.into_inner();
+        let state_filter = parse_state_filter(&req.state_filter)?;
+        let after = decode_page_token(&req.page_token)?;
+        let page_size = match req.page_size {
+            size if size <= 0 => LIST_JOBS_DEFAULT_PAGE_SIZE,
+            size => (size as usize).min(LIST_JOBS_MAX_PAGE_SIZE),
+        };
 
-    let _ = runtime.sweep_stale_reservations();
-    let validate_stage = DagStageKind::ValidateEnqueue;
-    let validate_stage_id = runtime
-        .begin_stage(
-            &job_id,
-            validate_stage,
-            validate_stage.stage_state(),
-            submission.stage_input(validate_stage),
-        )
-        .map_err(status_to_stage_error(validate_stage, "begin_validate"))?;
-    validation_output = adapters
-        .validate_enqueue(&submission)
-        .await
-        .map_err(|err| stage_error(validate_stage, err))?;
+        let mut remaining = self
+            .runtime
+            .list(state_filter)
+            .into_iter()
+            .filter(|job| self.can_see(&caller, job))
+            .filter(|job| after.as_ref().is_none_or(|cursor| job_page_key(job) > *cursor))
+            .peekable();
+        let page: Vec<JobRuntimeRecord> = remaining.by_ref().take(page_size).collect();
+        let next_page_token = match (remaining.peek(), page.last()) {
+            (Some(_), Some(last)) => encode_page_token(&job_page_key(last)),
+            _ => String::new(),
+        };
 
-    if runtime.is_cancel_requested(&job_id) || runtime.deadline_expired(&job_id) {
-        terminalize_control(&runtime, &job_id, DagStageKind::Compile, "validate")?;
-        return Ok(());
+        Ok(Response::new(ListJobsResponse {
+            jobs: page
+                .into_iter()
+                .map(|job| JobSummary {
+                    job_id: job.job_id.clone(),
+                    name: job.submission.name.clone(),
+                    state: job.state as i32,
+                    stage: job.stage_label(),
+                    created_at: Some(job.created_at),
+                    updated_at: Some(job.updated_at),
+                })
+                .collect(),
+            next_page_token,
+        }))
     }
 
-    runtime
-        .finish_stage_success(
-            &job_id,
-            &validate_stage_id,
-            validate_stage.next_state_after_success(),
-            validation_output.clone(),
-        )
-        .map_err(status_to_stage_error(validate_stage, "finish_validate"))?;
-    runtime
-        .set_state(&job_id, DagStageKind::Compile.stage_state())
-        .map_err(status_to_stage_error(validate_stage, "set_compile_state"))?;
+    async fn get_job_history(
+        &self,
+        request: Request<GetJobHistoryRequest>,
+    ) -> Result<Response<GetJobHistoryResponse>, Status> {
+        let caller = extract_caller(&request);
+        let job_id = request.into_inner().job_id;
+        let job = self.visible_job(&caller, &job_id)?;
+        self.authorize(&caller, Action::Status, &job.resource())?;
 
-    if runtime.is_cancel_requested(&job_id) {
-        cancel_after_stage(&runtime, &job_id, DagStageKind::Compile, "cancelled before compile")?;
-        return Ok(());
+        Ok(Response::new(GetJobHistoryResponse {
+            job_id: job.job_id,
+            transitions: job
+                .history
+                .into_iter()
+                .map(|entry| JobTransition {
+                    from_state: entry.from as i32,
+                    to_state: entry.to as i32,
+                    event: entry.event,
+                    at: Some(entry.at),
+                    reason: entry.reason.unwrap_or_default(),
+                })
+                .collect(),
+        }))
     }
 
-    let compile_stage = DagStageKind::Compile;
-    let compile_stage_id = runtime
-        .begin_stage(
-            &job_id,
-            compile_stage,
-            compile_stage.stage_state(),
-            stage_input_from_outputs(&submission, compile_stage, &validation_output),
-        )
-        .map_err(status_to_stage_error(compile_stage, "begin_compile"))?;
-    compile_output = adapters
-        .compile(&submission, &validation_output)
-        .await
-        .map_err(|err| stage_error(compile_stage, err))?;
-
-    if runtime.is_cancel_requested(&job_id) || runtime.deadline_expired(&job_id) {
-        terminalize_control(&runtime, &job_id, DagStageKind::Compile, "compile")?;
-        return Ok(());
+    async fn get_job_logs(
+        &self,
+        request: Request<GetJobLogsRequest>,
+    ) -> Result<Response<Self::GetJobLogsStream>, Status> {
+        let caller = extract_caller(&request);
+        let req = request.into_inner();
+        validate_log_name(&req.log_name)?;
+        let job = self.visible_job(&caller, &req.job_id)?;
+        self.authorize(&caller, Action::Status, &job.resource())?;
+        let path = self
+            .adapters
+            .job_log_path(&req.job_id, &req.log_name)
+            .map_err(KernelStageError::into_status)?;
+        let runtime = self.runtime.clone();
+        let (tx, rx) = mpsc::channel(JOB_LOGS_BUFFER);
+
+        tokio::spawn(async move {
+            let log_name = req.log_name;
+            // A job that never wrote the log has no lines to send, which is not an error.
+            let exists = if req.follow {
+                wait_for_log_file(&runtime, &req.job_id, &path, &tx).await
+            } else {
+                path.is_file()
+            };
+            if !exists {
+                return;
+            }
+            let mut reader = JobLogReader::new(path, req.from_line);
+            let mut tail_lines = (req.tail_lines > 0).then_some(req.tail_lines as usize);
+            loop {
+                // Sample the state before reading so lines written before the job
+                // became terminal are always drained.
+                let terminal = runtime.get(&req.job_id).is_none_or(|job| job.is_terminal());
+                let mut lines = match reader.read_new_lines() {
+                    Ok(lines) => lines,
+                    Err(err) => {
+                        let _ = tx.send(Err(Status::internal(format!("failed to read log {log_name}: {err}")))).await;
+                        return;
+                    }
+                };
+                if let Some(tail_lines) = tail_lines.take() {
+                    lines.drain(..lines.len().saturating_sub(tail_lines));
+                }
+                for line in lines {
+                    if tx.send(Ok(line)).await.is_err() {
+                        return;
+                    }
+                }
+                if !req.follow || terminal || tx.is_closed() {
+                    return;
+                }
+                tokio::time::sleep(JOB_LOGS_POLL_INTERVAL).await;
+            }
+        });
+
+        Ok(Response::new(Box::pin(ReceiverStream::new(rx))))
     }
 
-    runtime
-        .finish_stage_success(
-            &job_id,
-            &compile_stage_id,
-            compile_stage.next_state_after_success(),
-            compile_output.clone(),
-        )
-        .map_err(status_to_stage_error(compile_stage, "finish_compile"))?;
-    runtime
-        .set_state(&job_id, DagStageKind::Optimize.stage_state())
-        .map_err(status_to_stage_error(compile_stage, "set_optimize_state"))?;
+    async fn retry_job(
+        &self,
+        request: Request<RetryJobRequest>,
+    ) -> Result<Response<RetryJobResponse>, Status> {
+        self.runtime.ensure_accepting_jobs()?;
+        let caller = extract_caller(&request);
+        let headers = request.metadata().clone().into_headers();
+        let req = request.into_inner();
+        let reason = Some(req.reason.trim().to_string()).filter(|reason| !reason.is_empty());
+        let job = self.visible_job(&caller, &req.job_id)?;
+        // Retrying puts the job back to work under its tenant, like submitting it.
+        self.authorize(&caller, Action::Submit, &job.resource())?;
+        // The previous run gave its lease back when it became terminal.
+        let lease = self.runtime.acquire_quota(&job.submission.tenant_id)?;
+        let (job, resume_from) = match self.runtime.prepare_retry(&req.job_id, reason) {
+            Ok(prepared) => prepared,
+            Err(status) => {
+                self.runtime.quota.release(lease);
+                return Err(status);
+            }
+        };
+        let Some(resume_from) = resume_from else {
+            self.runtime.quota.release(lease);
+            return Ok(Response::new(RetryJobResponse {
+                accepted: false,
+                state: job.state as i32,
+                retry_count: job.retry_count,
+            }));
+        };
+        tracing::info!(
+            event = "retry",
+            trace_id = %job.submission.trace_id,
+            request_id = %job.submission.request_id,
+            job_id = %job.job_id,
+            resume_stage = resume_from.key(),
+            retry_count = job.retry_count,
+            "job retry accepted"
+        );
+
+        let parent = submitted_trace_context(&headers, &job.submission);
+        let rpc_request_id = rpc_request_id(&headers);
+        self.spawn_job_dag(job.job_id.clone(), job.submission.clone(), resume_from, lease, parent, rpc_request_id);
+        Ok(Response::new(RetryJobResponse {
+            accepted: true,
+            state: job.state as i32,
+            retry_count: job.retry_count,
+        }))
+    }
+
+    async fn pause_job(
+        &self,
+        request: Request<PauseJobRequest>,
+    ) -> Result<Response<PauseJobResponse>, Status> {
+        let caller = extract_caller(&request);
+        let req = request.into_inner();
+        let reason = Some(req.reason.trim().to_string()).filter(|reason| !reason.is_empty());
+        let job = self.visible_job(&caller, &req.job_id)?;
+        // Holding a job back from dispatch is job control, granted like cancelling.
+        self.authorize(&caller, Action::Cancel, &job.resource())?;
+        let (job, accepted) = self.runtime.pause_job(&req.job_id, reason)?;
+        if accepted {
+            tracing::info!(
+                event = "pause",
+                trace_id = %job.submission.trace_id,
+                request_id = %job.submission.request_id,
+                job_id = %job.job_id,
+                "job paused"
+            );
+        }
+        Ok(Response::new(PauseJobResponse {
+            accepted,
+            state: job.state as i32,
+        }))
+    }
+
+    async fn resume_job(
+        &self,
+        request: Request<ResumeJobRequest>,
+    ) -> Result<Response<ResumeJobResponse>, Status> {
+        let caller = extract_caller(&request);
+        let req = request.into_inner();
+        let reason = Some(req.reason.trim().to_string()).filter(|reason| !reason.is_empty());
+        let job = self.visible_job(&caller, &req.job_id)?;
+        // Resuming is job control too, granted like cancelling.
+        self.authorize(&caller, Action::Cancel, &job.resource())?;
+        let (job, accepted) = self.runtime.resume_job(&req.job_id, reason)?;
+        if accepted {
+            tracing::info!(
+                event = "resume",
+                trace_id = %job.submission.trace_id,
+                request_id = %job.submission.request_id,
+                job_id = %job.job_id,
+                "job resumed"
+            );
+        }
+        Ok(Response::new(ResumeJobResponse {
+            accepted,
+            state: job.state as i32,
+        }))
+    }
+
+    async fn delete_job(
+        &self,
+        request: Request<DeleteJobRequest>,
+    ) -> Result<Response<DeleteJobResponse>, Status> {
+        let caller = extract_caller(&request);
+        let req = request.into_inner();
+        check_job_id(&req.job_id)?;
+        let job = self.visible_job(&caller, &req.job_id)?;
+        // Deleting is at least as destructive as cancelling, so it needs the same grant.
+        self.authorize(&caller, Action::Cancel, &job.resource())?;
+        if !job.is_terminal() {
+            if !req.force {
+                return Err(Status::failed_precondition(format!(
+                    "job is {}; cancel it first or delete with force",
+                    task_state_label(job.state)
+                )));
+            }
+            match self.runtime.request_cancel(&req.job_id, Some("deleted with force".to_string())) {
+                Ok(_) => {}
+                // It finished on its own in the meantime.
+                Err(status) if status.code() == Code::FailedPrecondition => {}
+                Err(status) => return Err(status),
+            }
+            if self.runtime.wait_for_terminal(&req.job_id, CANCEL_ACK_TIMEOUT).await.is_none() {
+                return Err(Status::unavailable("job is still stopping; retry the delete"));
+            }
+        }
+
+        let job = self.runtime.remove_job(&req.job_id)?;
+        let artifacts_removed =
+            self.adapters.delete_job_artifacts(&job.job_id).await.map_err(KernelStageError::into_status)?;
+        tracing::info!(
+            event = "delete",
+            trace_id = %job.submission.trace_id,
+            request_id = %job.submission.request_id,
+            job_id = %job.job_id,
+            force = req.force,
+            artifacts_removed,
+            "job deleted"
+        );
+        Ok(Response::new(DeleteJobResponse { deleted: true, artifacts_removed }))
+    }
+
+    async fn get_kernel_info(
+        &self,
+        _request: Request<GetKernelInfoRequest>,
+    ) -> Result<Response<GetKernelInfoResponse>, Status> {
+        let uptime_ms = timestamp_to_ms(&ts_now()) as i64 - self.started_at_ms.load(Ordering::Relaxed);
+        let saturating_i32 = |count: usize| i32::try_from(count).unwrap_or(i32::MAX);
+        Ok(Response::new(GetKernelInfoResponse {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            uptime_secs: uptime_ms.max(0) / 1000,
+            active_jobs: saturating_i32(self.runtime.active_count()),
+            max_concurrent_jobs: saturating_i32(self.runtime.dispatcher.slots.capacity()),
+            backend_names: self.adapters.backend_names(),
+        }))
+    }
+}
+
+const LIST_JOBS_DEFAULT_PAGE_SIZE: usize = 50;
+const LIST_JOBS_MAX_PAGE_SIZE: usize = 200;
+
+/// Ordering key for `ListJobs`: creation time, then job id as a tie-breaker.
+fn job_page_key(job: &JobRuntimeRecord) -> (i128, String) {
+    (timestamp_to_ms(&job.created_at), job.job_id.clone())
+}
+
+/// Encodes the key of the last returned job as an opaque URL-safe base64 cursor.
+fn encode_page_token(key: &(i128, String)) -> String {
+    URL_SAFE_NO_PAD.encode(format!("{}:{}", key.0, key.1))
+}
+
+fn decode_page_token(token: &str) -> Result<Option<(i128, String)>, Status> {
+    if token.is_empty() {
+        return Ok(None);
+    }
+    let invalid = || Status::invalid_argument("invalid page_token");
+    let bytes = URL_SAFE_NO_PAD.decode(token).map_err(|_| invalid())?;
+    let decoded = String::from_utf8(bytes).map_err(|_| invalid())?;
+    let (created_ms, job_id) = decoded.split_once(':').ok_or_else(invalid)?;
+    let created_ms = created_ms.parse::<i128>().map_err(|_| invalid())?;
+    Ok(Some((created_ms, job_id.to_string())))
+}
+
+/// Parses a `ListJobs` state filter; accepts both `RUNNING` and `TASK_STATE_RUNNING`.
+fn parse_state_filter(raw: &str) -> Result<Option<TaskState>, Status> {
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        return Ok(None);
+    }
+    let upper = trimmed.to_ascii_uppercase();
+    let full_name = if upper.starts_with("TASK_STATE_") {
+        upper
+    } else {
+        format!("TASK_STATE_{upper}")
+    };
+    match TaskState::from_str_name(&full_name) {
+        Some(TaskState::Unspecified) | None => Err(Status::invalid_argument(format!(
+            "unknown state_filter: {trimmed}"
+        ))),
+        Some(state) => Ok(Some(state)),
+    }
+}
+
+async fn run_job_dag(
+    runtime: Arc<KernelRuntimeStore>,
+    adapters: Arc<dyn OrchestrationAdapters>,
+    job_id: String,
+    submission: NormalizedSubmission,
+    resume_from: DagStageKind,
+) -> Result<(), KernelStageError> {
+    let mut validation_output = BTreeMap::new();
+    let mut compile_output = BTreeMap::new();
+    let mut optimize_output = BTreeMap::new();
+    let mut schedule_output = BTreeMap::new();
+    let mut execution_output = ExecutionOutcome {
+        counts: BTreeMap::new(),
+        output: BTreeMap::new(),
+        metadata: BTreeMap::new(),
+    };
+    let mut persist_output = BTreeMap::new();
+    let mut observability_output = BTreeMap::new();
+
+    let _ = runtime.sweep_stale_reservations();
+    if resume_from == DagStageKind::ValidateEnqueue {
+        let validate_stage = DagStageKind::ValidateEnqueue;
+        let validate_stage_id = runtime
+            .begin_stage(
+                &job_id,
+                validate_stage,
+                validate_stage.stage_state(),
+                submission.stage_input(validate_stage),
+            )
+            .map_err(status_to_stage_error(validate_stage, "begin_validate"))?;
+        let Some(stage_result) = unless_cancelled(&runtime, &job_id, adapters.validate_enqueue(&submission)).await else {
+            terminalize_control(&runtime, &job_id, DagStageKind::Compile, "validate")?;
+            return Ok(());
+        };
+        validation_output = stage_result.map_err(|err| stage_error(validate_stage, err))?;
+
+        if runtime.is_cancel_requested(&job_id) || runtime.deadline_expired(&job_id) {
+            terminalize_control(&runtime, &job_id, DagStageKind::Compile, "validate")?;
+            return Ok(());
+        }
+
+        runtime
+            .finish_stage_success(
+                &job_id,
+                &validate_stage_id,
+                validate_stage.next_state_after_success(),
+                validation_output.clone(),
+            )
+            .map_err(status_to_stage_error(validate_stage, "finish_validate"))?;
+        runtime
+            .set_state(&job_id, DagStageKind::Compile.stage_state())
+            .map_err(status_to_stage_error(validate_stage, "set_compile_state"))?;
+
+        if runtime.is_cancel_requested(&job_id) {
+            cancel_after_stage(&runtime, &job_id, DagStageKind::Compile, "cancelled before compile")?;
+            return Ok(());
+        }
+
+        let compile_stage = DagStageKind::Compile;
+        let compile_stage_id = runtime
+            .begin_stage(
+                &job_id,
+                compile_stage,
+                compile_stage.stage_state(),
+                stage_input_from_outputs(&submission, compile_stage, &validation_output),
+            )
+            .map_err(status_to_stage_error(compile_stage, "begin_compile"))?;
+        let compile = adapters.compile(&submission, &validation_output);
+        let compile = simulated(&runtime, &submission, SimulatedStage::Compile, compile);
+        let Some(stage_result) = unless_cancelled(&runtime, &job_id, compile).await else {
+            terminalize_control(&runtime, &job_id, DagStageKind::Compile, "compile")?;
+            return Ok(());
+        };
+        compile_output = stage_result.map_err(|err| stage_error(compile_stage, err))?;
+
+        if runtime.is_cancel_requested(&job_id) || runtime.deadline_expired(&job_id) {
+            terminalize_control(&runtime, &job_id, DagStageKind::Compile, "compile")?;
+            return Ok(());
+        }
+
+        if submission.compile_only() {
+            finish_compile_only(&runtime, &job_id, &submission, &compile_stage_id, &compile_output)?;
+            return Ok(());
+        }
+
+        runtime
+            .finish_stage_success(
+                &job_id,
+                &compile_stage_id,
+                compile_stage.next_state_after_success(),
+                compile_output.clone(),
+            )
+            .map_err(status_to_stage_error(compile_stage, "finish_compile"))?;
+        runtime
+            .set_state(&job_id, DagStageKind::Optimize.stage_state())
+            .map_err(status_to_stage_error(compile_stage, "set_optimize_state"))?;
+    } else {
+        // A retry past compilation reuses the compile output recorded by the earlier run.
+        let job = runtime.get(&job_id).ok_or_else(|| {
+            stage_error(
+                resume_from,
+                KernelStageError::internal("job disappeared before retry", format!("qfs://jobs/{job_id}/meta/job.json")),
+            )
+        })?;
+        compile_output = job
+            .stage_records
+            .iter()
+            .find(|record| {
+                record.stage_key == DagStageKind::Compile.key() && record.status == StageStatus::Succeeded
+            })
+            .map(|record| record.output.clone())
+            .unwrap_or_default();
+    }
 
     if runtime.is_cancel_requested(&job_id) {
         cancel_after_stage(&runtime, &job_id, DagStageKind::Optimize, "cancelled before optimize")?;
@@ -4442,10 +6703,11 @@ async fn run_job_dag(
             stage_input_from_outputs(&submission, optimize_stage, &compile_output),
         )
         .map_err(status_to_stage_error(optimize_stage, "begin_optimize"))?;
-    optimize_output = adapters
-        .optimize(&submission, &compile_output)
-        .await
-        .map_err(|err| stage_error(optimize_stage, err))?;    
+    let Some(stage_result) = unless_cancelled(&runtime, &job_id, adapters.optimize(&submission, &compile_output)).await else {
+        terminalize_control(&runtime, &job_id, DagStageKind::Optimize, "optimize")?;
+        return Ok(());
+    };
+    optimize_output = stage_result.map_err(|err| stage_error(optimize_stage, err))?;
 
     if runtime.is_cancel_requested(&job_id) || runtime.deadline_expired(&job_id) {
         terminalize_control(&runtime, &job_id, DagStageKind::Optimize, "optimize")?;
@@ -4478,10 +6740,29 @@ async fn run_job_dag(
             stage_input_from_outputs(&submission, schedule_stage, &optimize_output),
         )
         .map_err(status_to_stage_error(schedule_stage, "begin_schedule"))?;
-    schedule_output = adapters
-        .schedule(&submission, &optimize_output)
-        .await
-        .map_err(|err| stage_error(schedule_stage, err))?;
+    let schedule = adapters.schedule(&submission, &optimize_output);
+    let schedule = simulated(&runtime, &submission, SimulatedStage::Queue, schedule);
+    let Some(stage_result) = unless_cancelled(&runtime, &job_id, schedule).await else {
+        terminalize_control(&runtime, &job_id, DagStageKind::Schedule, "schedule")?;
+        return Ok(());
+    };
+    schedule_output = stage_result.map_err(|err| stage_error(schedule_stage, err))?;
+
+    let enqueued_at_ms = runtime
+        .get(&job_id)
+        .map(|job| timestamp_to_ms(&job.created_at) as i64)
+        .unwrap_or_default();
+    let Some(execution_slot) = unless_cancelled(
+        &runtime,
+        &job_id,
+        acquire_unless_paused(&runtime, &job_id, Priority::from_level(submission.priority), enqueued_at_ms),
+    )
+    .await
+    .flatten()
+    else {
+        terminalize_control(&runtime, &job_id, DagStageKind::Schedule, "schedule")?;
+        return Ok(());
+    };
 
     if runtime.is_cancel_requested(&job_id) || runtime.deadline_expired(&job_id) {
         terminalize_control(&runtime, &job_id, DagStageKind::Schedule, "schedule")?;
@@ -4577,16 +6858,24 @@ async fn run_job_dag(
         }
     }
 
-    execution_output = execute_with_retry(
+    let Some(stage_result) = unless_cancelled(
         &runtime,
         &job_id,
-        &execute_stage_id,
-        &submission,
-        &schedule_output,
-        adapters.clone(),
+        execute_with_retry(
+            &runtime,
+            &job_id,
+            &execute_stage_id,
+            &submission,
+            &schedule_output,
+            adapters.clone(),
+        ),
     )
     .await
-    .map_err(|err| stage_error(execute_stage, err))?;
+    else {
+        terminalize_control(&runtime, &job_id, DagStageKind::Execute, "execute")?;
+        return Ok(());
+    };
+    execution_output = stage_result.map_err(|err| stage_error(execute_stage, err))?;
 
     if runtime.is_cancel_requested(&job_id) || runtime.deadline_expired(&job_id) {
         terminalize_control(&runtime, &job_id, DagStageKind::Execute, "execute")?;
@@ -4604,6 +6893,7 @@ async fn run_job_dag(
             execution_output.output.clone(),
         )
         .map_err(status_to_stage_error(execute_stage, "finish_execute"))?;
+    drop(execution_slot);
 
     if runtime.is_cancel_requested(&job_id) {
         cancel_after_stage(&runtime, &job_id, DagStageKind::Persist, "cancelled before persist")?;
@@ -4619,10 +6909,14 @@ async fn run_job_dag(
             stage_input_from_outputs(&submission, persist_stage, &execution_output.output),
         )
         .map_err(status_to_stage_error(persist_stage, "begin_persist"))?;
-    persist_output = adapters
-        .persist(&submission, &execution_output, &runtime.get(&job_id).map(|job| job.stage_records.clone()).unwrap_or_default())
-        .await
-        .map_err(|err| stage_error(persist_stage, err))?;
+    let stage_records = runtime.get(&job_id).map(|job| job.stage_records).unwrap_or_default();
+    let Some(stage_result) =
+        unless_cancelled(&runtime, &job_id, adapters.persist(&submission, &execution_output, &stage_records)).await
+    else {
+        terminalize_control(&runtime, &job_id, DagStageKind::Persist, "persist")?;
+        return Ok(());
+    };
+    persist_output = stage_result.map_err(|err| stage_error(persist_stage, err))?;
 
     if !persist_output.contains_key("result.summary.objective") {
         if let Some(objective) = optimize_output
@@ -4685,10 +6979,11 @@ async fn run_job_dag(
             stage_input_from_outputs(&submission, observability_stage, &persist_output),
         )
         .map_err(status_to_stage_error(observability_stage, "begin_observability"))?;
-    observability_output = adapters
-        .record_knowledge_observability(&submission, &persist_output, &execution_output)
-        .await
-        .map_err(|err| stage_error(observability_stage, err))?;
+    let Some(stage_result) = unless_cancelled(&runtime, &job_id, adapters.record_knowledge_observability(&submission, &persist_output, &execution_output)).await else {
+        terminalize_control(&runtime, &job_id, DagStageKind::RecordKnowledgeObservability, "observability")?;
+        return Ok(());
+    };
+    observability_output = stage_result.map_err(|err| stage_error(observability_stage, err))?;
 
     if runtime.is_cancel_requested(&job_id) || runtime.deadline_expired(&job_id) {
         terminalize_control(&runtime, &job_id, DagStageKind::RecordKnowledgeObservability, "observability")?;
@@ -4716,10 +7011,11 @@ async fn run_job_dag(
             stage_input_from_outputs(&submission, finalize_stage, &observability_output),
         )
         .map_err(status_to_stage_error(finalize_stage, "begin_finalize"))?;
-    let finalize_output = adapters
-        .finalize(&submission, &observability_output)
-        .await
-        .map_err(|err| stage_error(finalize_stage, err))?;
+    let Some(stage_result) = unless_cancelled(&runtime, &job_id, adapters.finalize(&submission, &observability_output)).await else {
+        terminalize_control(&runtime, &job_id, DagStageKind::Finalize, "finalize")?;
+        return Ok(());
+    };
+    let finalize_output = stage_result.map_err(|err| stage_error(finalize_stage, err))?;
 
     if runtime.is_cancel_requested(&job_id) || runtime.deadline_expired(&job_id) {
         terminalize_control(&runtime, &job_id, DagStageKind::Finalize, "finalize")?;
@@ -4747,6 +7043,113 @@ async fn run_job_dag(
     Ok(())
 }
 
+/// Ends a compile-only job at its compile stage: the compiled artifact becomes the
+/// job's result and the job goes straight from COMPILING to DONE.
+fn finish_compile_only(
+    runtime: &KernelRuntimeStore,
+    job_id: &str,
+    submission: &NormalizedSubmission,
+    compile_stage_id: &str,
+    compile_output: &BTreeMap<String, String>,
+) -> Result<(), KernelStageError> {
+    let compile_stage = DagStageKind::Compile;
+    let compiled_artifact_ref = compile_output
+        .get("compiled_artifact_ref")
+        .cloned()
+        .unwrap_or_else(|| format!("qfs://jobs/{job_id}/compiled/circuit.aqo.json"));
+    runtime
+        .set_metadata(
+            job_id,
+            BTreeMap::from([
+                ("compile_only".to_string(), "true".to_string()),
+                ("compiled_artifact_ref".to_string(), compiled_artifact_ref.clone()),
+            ]),
+        )
+        .map_err(status_to_stage_error(compile_stage, "set_compile_only_metadata"))?;
+    runtime
+        .set_qfs_result_ref(job_id, compiled_artifact_ref)
+        .map_err(status_to_stage_error(compile_stage, "set_compile_only_result_ref"))?;
+    runtime
+        .finish_stage_success(job_id, compile_stage_id, TaskState::Done, compile_output.clone())
+        .map_err(status_to_stage_error(compile_stage, "finish_compile"))?;
+
+    tracing::info!(
+        event = "terminal_state",
+        trace_id = %submission.trace_id,
+        request_id = %submission.request_id,
+        job_id = %job_id,
+        final_state = "DONE",
+        compile_only = true,
+        "job reached terminal state"
+    );
+    Ok(())
+}
+
+/// Runs the work of `stage` once the delay the job's [`PipelineConfig`] gives it has
+/// passed, or fails the stage with the configured code instead of running it.
+async fn simulated<T>(
+    runtime: &KernelRuntimeStore,
+    submission: &NormalizedSubmission,
+    stage: SimulatedStage,
+    work: impl Future<Output = Result<T, KernelStageError>>,
+) -> Result<T, KernelStageError> {
+    // Overrides were checked at enqueue; a config changed since then falls back to the kernel's.
+    let config = runtime.pipeline_config(submission).unwrap_or_else(|_| runtime.pipeline.clone());
+    let delay = config.delay(stage);
+    if !delay.is_zero() {
+        tokio::time::sleep(delay).await;
+    }
+    if config.fails(stage) {
+        return Err(KernelStageError::new(
+            Code::Internal,
+            config.fail_code,
+            format!("simulated failure injected at {stage} stage"),
+            format!("qfs://simulation/{stage}-stage-failure.json"),
+        ));
+    }
+    work.await
+}
+
+/// Drives a stage future unless the job is cancelled first, in which case the
+/// future is dropped so the stage stops making progress and `None` is returned.
+async fn unless_cancelled<F>(runtime: &KernelRuntimeStore, job_id: &str, stage: F) -> Option<F::Output>
+where
+    F: std::future::Future,
+{
+    let mut cancelled = runtime.cancel_signal(job_id);
+    tokio::select! {
+        output = stage => Some(output),
+        Ok(_) = cancelled.wait_for(|requested| *requested) => None,
+    }
+}
+
+/// Waits in the dispatcher queue for an execution slot. Pausing the job takes it
+/// out of the queue; resuming puts it back with its original enqueue time. Returns
+/// `None` when the job became terminal while paused.
+async fn acquire_unless_paused(
+    runtime: &Arc<KernelRuntimeStore>,
+    job_id: &str,
+    priority: Priority,
+    enqueued_at_ms: i64,
+) -> Option<ExecutionSlot> {
+    let (_, mut state) = runtime.watch_state(job_id)?;
+    loop {
+        let current = *state.wait_for(|state| *state != TaskState::Paused).await.ok()?;
+        if job_outcome(current).is_some() {
+            return None;
+        }
+        tokio::select! {
+            slot = runtime.dispatcher.acquire(job_id, priority, enqueued_at_ms) => {
+                if runtime.claim_dispatch(job_id) {
+                    return Some(slot);
+                }
+                // Paused while the slot was handed out: give it back and wait.
+            }
+            Ok(_) = state.wait_for(|state| *state == TaskState::Paused) => {}
+        }
+    }
+}
+
 fn stage_input_from_outputs(
     submission: &NormalizedSubmission,
     stage: DagStageKind,
@@ -4770,6 +7173,15 @@ fn stage_error(stage: DagStageKind, err: KernelStageError) -> KernelStageError {
 
 fn status_to_stage_error(stage: DagStageKind, action: &'static str) -> impl FnOnce(Status) -> KernelStageError {
     move |status| {
+        // The store refused the write because the job was cancelled meanwhile.
+        if status.code() == Code::Cancelled {
+            return KernelStageError::new(
+                Code::Cancelled,
+                "CANCELLED",
+                format!("{} stage {} skipped: {}", stage.key(), action, status.message()),
+                format!("status::{:?}", status.code()),
+            );
+        }
         KernelStageError::new(
             Code::Internal,
             "RUNTIME_STAGE_FAILURE",
@@ -4785,6 +7197,9 @@ fn cancel_after_stage(
     next_stage: DagStageKind,
     reason: &str,
 ) -> Result<(), KernelStageError> {
+    if runtime.get(job_id).is_none_or(|job| job.is_terminal()) {
+        return Ok(());
+    }
     let terminal_error = KernelStageError::new(
         Code::Cancelled,
         "CANCELLED",
@@ -4803,7 +7218,7 @@ fn cancel_after_stage(
             job_id,
             &stage_id,
             TaskState::Cancelled,
-            terminal_error.error_code,
+            &terminal_error.error_code,
             &terminal_error.summary,
             &terminal_error.details_ref,
         )
@@ -4903,6 +7318,11 @@ async fn execute_with_retry(
             return terminalize_deadline(runtime);
         }
         attempt = attempt.saturating_add(1);
+        if attempt > 1 {
+            // Progress is advisory; a job that vanished fails the next runtime write anyway.
+            let message = format!("retrying execution (attempt {attempt} of {})", policy.max_attempts);
+            let _ = runtime.set_progress(job_id, 0.5, message);
+        }
         let attempt_started = Instant::now();
         let span = tracing::info_span!(
             "execute_attempt",
@@ -4913,14 +7333,8 @@ async fn execute_with_retry(
             stage = "execute"
         );
 
-        let result = async {
-            adapters
-                .as_ref()
-                .execute(submission, schedule_output)
-                .await
-        }
-        .instrument(span)
-        .await;
+        let execute = adapters.as_ref().execute(submission, schedule_output);
+        let result = simulated(runtime, submission, SimulatedStage::Run, execute).instrument(span).await;
 
         match result {
             Ok(outcome) => {
@@ -5089,7 +7503,7 @@ fn parse_reason_csv(raw: &str) -> Vec<&'static str> {
 
 fn nonempty(value: &str, field: &'static str) -> Result<String, Status> {
     if value.trim().is_empty() {
-        Err(Status::invalid_argument(format!("{field} is required")))
+        Err(ValidationError::missing(field).into())
     } else {
         Ok(value.trim().to_string())
     }
@@ -5103,6 +7517,23 @@ fn nonempty_or_default(value: &str, default: &str) -> String {
     }
 }
 
+/// Validates the optional JobSpec YAML carried by an enqueue request.
+///
+/// Legacy callers omit the field entirely; when present it must be a YAML mapping.
+fn validated_job_yaml(raw: &str) -> Result<String, Status> {
+    if raw.is_empty() {
+        return Ok(String::new());
+    }
+    if raw.trim().is_empty() {
+        return Err(Status::invalid_argument("job_yaml must not be blank"));
+    }
+    match serde_yaml::from_str::<serde_yaml::Value>(raw) {
+        Ok(serde_yaml::Value::Mapping(_)) => Ok(raw.to_string()),
+        Ok(_) => Err(Status::invalid_argument("job_yaml must be a YAML mapping")),
+        Err(err) => Err(Status::invalid_argument(format!("job_yaml is not valid YAML: {err}"))),
+    }
+}
+
 fn canonical_string_map(input: &HashMap<String, String>) -> BTreeMap<String, String> {
     input
         .iter()
@@ -5114,37 +7545,158 @@ fn stage_id(job_id: &str, stage: DagStageKind) -> String {
     format!("{job_id}:{:02}-{}", stage.index(), stage.key())
 }
 
-fn normalized_deadline_at(deadline: &prost_types::Duration) -> Option<Timestamp> {
-    if deadline.seconds <= 0 && deadline.nanos <= 0 {
-        return None;
+/// The lifecycle state `meta/job.json` records for `state`.
+fn job_state(state: TaskState) -> JobState {
+    match state {
+        TaskState::Unspecified | TaskState::Pending => JobState::Pending,
+        TaskState::Compiling | TaskState::Optimizing => JobState::Compiling,
+        TaskState::Queued | TaskState::Running => JobState::Running,
+        TaskState::Paused => JobState::Paused,
+        TaskState::Done => JobState::Done,
+        TaskState::Error => JobState::Error,
+        TaskState::Cancelled => JobState::Cancelled,
+        TaskState::Timeout => JobState::Timeout,
     }
-    let now = ts_now();
-    let now_ms = timestamp_to_ms(&now);
-    let deadline_ms = now_ms
-        .saturating_add((deadline.seconds.max(0) as i128) * 1000)
-        .saturating_add((deadline.nanos.max(0) as i128) / 1_000_000);
-    Some(Timestamp {
-        seconds: (deadline_ms / 1000) as i64,
-        nanos: ((deadline_ms % 1000) * 1_000_000) as i32,
-    })
 }
 
-fn timestamp_to_ms(ts: &Timestamp) -> i128 {
-    (ts.seconds as i128) * 1000 + (ts.nanos as i128 / 1_000_000)
+/// Inverse of [`job_state`]; a `RETRYING` job is waiting to run again.
+fn task_state(state: JobState) -> TaskState {
+    match state {
+        JobState::Pending => TaskState::Pending,
+        JobState::Compiling => TaskState::Compiling,
+        JobState::Running | JobState::Retrying => TaskState::Running,
+        JobState::Paused => TaskState::Paused,
+        JobState::Done => TaskState::Done,
+        JobState::Error => TaskState::Error,
+        JobState::Cancelled => TaskState::Cancelled,
+        JobState::Timeout => TaskState::Timeout,
+    }
 }
 
-fn timestamp_from_ms(ms: i128) -> Timestamp {
-    Timestamp {
-        seconds: (ms.div_euclid(1000)) as i64,
-        nanos: ((ms.rem_euclid(1000)) * 1_000_000) as i32,
+/// A wire priority in `priority`'s class, so [`Priority::from_level`] maps it back.
+fn priority_level(priority: Priority) -> i32 {
+    match priority {
+        Priority::Low => 1,
+        Priority::Normal => 0,
+        Priority::High => 67,
     }
 }
 
-fn parse_stage_kind(value: &str) -> Option<DagStageKind> {
-    match value {
-        "validate" | "validate_enq" | "validate-enqueue" => Some(DagStageKind::ValidateEnqueue),
-        "compile" => Some(DagStageKind::Compile),
-        "optimize" => Some(DagStageKind::Optimize),
+/// Value of the bounded `state` label for terminal task states.
+fn job_outcome(state: TaskState) -> Option<JobOutcome> {
+    match state {
+        TaskState::Done => Some(JobOutcome::Done),
+        TaskState::Error => Some(JobOutcome::Error),
+        TaskState::Cancelled => Some(JobOutcome::Cancelled),
+        TaskState::Timeout => Some(JobOutcome::Timeout),
+        _ => None,
+    }
+}
+
+/// `state` label of the `eigen_jobs_by_state` gauge and transition histogram.
+fn task_state_label(state: TaskState) -> &'static str {
+    match state {
+        TaskState::Pending => "pending",
+        TaskState::Compiling => "compiling",
+        TaskState::Optimizing => "optimizing",
+        TaskState::Queued => "queued",
+        TaskState::Paused => "paused",
+        TaskState::Running => "running",
+        TaskState::Done => "done",
+        TaskState::Error => "error",
+        TaskState::Cancelled => "cancelled",
+        TaskState::Timeout => "timeout",
+        TaskState::Unspecified => "unspecified",
+    }
+}
+
+/// Stage label reported for `state` when no DAG stage is in flight.
+fn state_stage_label(state: TaskState) -> &'static str {
+    match state {
+        TaskState::Pending => "pending",
+        TaskState::Compiling => "compile",
+        TaskState::Optimizing => "optimize",
+        TaskState::Queued => "schedule",
+        TaskState::Paused => "paused",
+        TaskState::Running => "execute",
+        TaskState::Done => "finalize",
+        TaskState::Error => "error",
+        TaskState::Cancelled => "cancelled",
+        TaskState::Timeout => "timeout",
+        TaskState::Unspecified => "unspecified",
+    }
+}
+
+/// Share of the overall progress covered by a state, as `(low, high)`.
+fn progress_range(state: TaskState) -> (f32, f32) {
+    match state {
+        TaskState::Pending | TaskState::Unspecified => (0.0, 0.1),
+        TaskState::Compiling => (0.1, 0.3),
+        TaskState::Optimizing => (0.3, 0.4),
+        TaskState::Queued | TaskState::Paused => (0.4, 0.5),
+        TaskState::Running => (0.5, 1.0),
+        TaskState::Done | TaskState::Error | TaskState::Cancelled | TaskState::Timeout => (1.0, 1.0),
+    }
+}
+
+/// History event name for a state change driven by the DAG itself.
+fn transition_event(to: TaskState) -> &'static str {
+    match to {
+        TaskState::Done => "complete",
+        TaskState::Error => "fail",
+        TaskState::Cancelled => "cancel",
+        TaskState::Timeout => "timeout",
+        _ => "advance",
+    }
+}
+
+fn normalized_deadline_at(deadline: &prost_types::Duration) -> Option<Timestamp> {
+    if deadline.seconds <= 0 && deadline.nanos <= 0 {
+        return None;
+    }
+    let now = ts_now();
+    let now_ms = timestamp_to_ms(&now);
+    let deadline_ms = now_ms
+        .saturating_add((deadline.seconds.max(0) as i128) * 1000)
+        .saturating_add((deadline.nanos.max(0) as i128) / 1_000_000);
+    Some(Timestamp {
+        seconds: (deadline_ms / 1000) as i64,
+        nanos: ((deadline_ms % 1000) * 1_000_000) as i32,
+    })
+}
+
+/// `wall_time_ms` since the first stage started and `stage_duration_ms.<stage>`
+/// for every completed stage, measured up to `end_ms`.
+fn stage_timings(stage_records: &[StageRecord], end_ms: i128) -> BTreeMap<String, String> {
+    let mut timings = BTreeMap::new();
+    if let Some(first_started_ms) = stage_records.iter().map(|stage| timestamp_to_ms(&stage.started_at)).min() {
+        timings.insert("wall_time_ms".to_string(), (end_ms - first_started_ms).max(0).to_string());
+    }
+    for stage in stage_records {
+        if let Some(completed_at) = stage.completed_at.as_ref() {
+            let duration_ms = (timestamp_to_ms(completed_at) - timestamp_to_ms(&stage.started_at)).max(0);
+            timings.insert(format!("stage_duration_ms.{}", stage.stage_key), duration_ms.to_string());
+        }
+    }
+    timings
+}
+
+fn timestamp_to_ms(ts: &Timestamp) -> i128 {
+    (ts.seconds as i128) * 1000 + (ts.nanos as i128 / 1_000_000)
+}
+
+fn timestamp_from_ms(ms: i128) -> Timestamp {
+    Timestamp {
+        seconds: (ms.div_euclid(1000)) as i64,
+        nanos: ((ms.rem_euclid(1000)) * 1_000_000) as i32,
+    }
+}
+
+fn parse_stage_kind(value: &str) -> Option<DagStageKind> {
+    match value {
+        "validate" | "validate_enq" | "validate-enqueue" => Some(DagStageKind::ValidateEnqueue),
+        "compile" => Some(DagStageKind::Compile),
+        "optimize" => Some(DagStageKind::Optimize),
         "schedule" => Some(DagStageKind::Schedule),
         "execute" => Some(DagStageKind::Execute),
         "persist" => Some(DagStageKind::Persist),
@@ -5269,6 +7821,22 @@ fn compiler_request_metadata_for_submission(submission: &NormalizedSubmission) -
     }
 }
 
+/// `status` for the `BatchEnqueueJobs` entry at `index`, its message prefixed with
+/// the entry's position and its details kept.
+fn at_batch_index(index: usize, status: Status) -> Status {
+    let message = format!("jobs[{index}]: {}", status.message());
+    Status::with_details(status.code(), message, status.details().to_vec().into())
+}
+
+/// Whether `submission` resubmits the job of `existing`, which holds the same job
+/// id. Keyed retries were matched on their payload by `resolve_idempotency_key`.
+fn is_same_submission(existing: &NormalizedSubmission, submission: &NormalizedSubmission) -> bool {
+    existing.fingerprint == submission.fingerprint
+        || (submission.explicit_idempotency_key
+            && existing.idempotency_key == submission.idempotency_key
+            && existing.payload_digest() == submission.payload_digest())
+}
+
 fn canonical_submission_fingerprint(
     contract_version: &str,
     request_id: &str,
@@ -5345,15 +7913,20 @@ fn hash_bytes_hex(input: &[u8]) -> String {
     format!("{:016x}", fnv1a64(input))
 }
 
-fn fnv1a64(input: &[u8]) -> u64 {
-    const OFFSET: u64 = 0xcbf29ce484222325;
-    const PRIME: u64 = 0x100000001b3;
-    let mut hash = OFFSET;
-    for byte in input {
-        hash ^= *byte as u64;
-        hash = hash.wrapping_mul(PRIME);
+/// The `traceparent` the job was submitted with, when the call itself carried none.
+/// A call with a `traceparent` header already runs in a child span of the caller's,
+/// which the pipeline then nests under.
+fn submitted_trace_context(headers: &http::HeaderMap, submission: &NormalizedSubmission) -> Option<TraceContext> {
+    match trace_context::extract_context(headers) {
+        Some(_) => None,
+        None => trace_context::context_from_traceparent(&submission.traceparent),
     }
-    hash
+}
+
+/// The call's `x-request-id`, which [`RequestIdLayer`] sets on every request it serves.
+fn rpc_request_id(headers: &http::HeaderMap) -> Option<String> {
+    let value = headers.get(REQUEST_ID_HEADER)?.to_str().ok()?;
+    Some(value.to_string()).filter(|value| !value.is_empty())
 }
 
 fn trace_id_from_traceparent(traceparent: &str) -> Option<String> {
@@ -5367,6 +7940,12 @@ fn trace_id_from_traceparent(traceparent: &str) -> Option<String> {
     }
 }
 
+/// Wall-clock time since `started_at`, zero if the clock went backwards.
+fn elapsed_since(started_at: &Timestamp) -> Duration {
+    let elapsed_ms = timestamp_to_ms(&ts_now()) - timestamp_to_ms(started_at);
+    Duration::from_millis(elapsed_ms.clamp(0, u64::MAX as i128) as u64)
+}
+
 fn ts_now() -> Timestamp {
     let duration = SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -5467,6 +8046,8 @@ mod tests {
         ));
         root.to_string_lossy().into_owned()
     }
+    use crate::auth::ServiceCaller;
+    use security_module::Claims;
     use crate::proto::RequestMetadata;
     use std::collections::{BTreeMap, BTreeSet};
     use prost_types::Duration as ProtoDuration;
@@ -5480,6 +8061,16 @@ mod tests {
         (svc, runtime)
     }
 
+    /// Service whose pipeline stages are delayed or failed as `pipeline` says.
+    fn make_service_with_pipeline(
+        qfs_root: &str,
+        pipeline: PipelineConfig,
+    ) -> (KernelGatewaySvc, Arc<KernelRuntimeStore>) {
+        let runtime = Arc::new(KernelRuntimeStore { pipeline, ..KernelRuntimeStore::default() });
+        let svc = KernelGatewaySvc::new(runtime.clone(), Arc::new(FixtureAdapters::new(qfs_root, None)));
+        (svc, runtime)
+    }
+
     fn make_service_with_hold(
          failure_stage: Option<DagStageKind>,
          hold_stage: Option<DagStageKind>,
@@ -5528,6 +8119,10 @@ mod tests {
             priority: 50,
             compiler_options,
             metadata_kvs,
+            job_yaml: String::new(),
+            shots: 0,
+            compile_only: false,
+            simulation: HashMap::new(),
         }
     }
 
@@ -5643,200 +8238,867 @@ mod tests {
         assert!(!job.metadata.contains_key("result.summary.energy"));
     }
 
-    #[test]
-    fn result_summary_metadata_promotion_is_generic() {
-        let mut summary = BTreeMap::from([("workload_kind".to_string(), "HybridWorkflow".to_string())]);
-        let mut source = BTreeMap::new();
-        source.insert("energy".to_string(), "-1.137270".to_string());
-        source.insert("parameters".to_string(), "[0.121,-0.233,0.055,0.019]".to_string());
-        source.insert("result.summary.objective".to_string(), "-1.137270".to_string());
-        source.insert("message".to_string(), "ignored".to_string());
-        source.insert("counts_ref".to_string(), "qfs://jobs/job-1/results/counts.json".to_string());
-
-        merge_result_summary_fields(&mut summary, &source);
-
-        assert_eq!(summary.get("energy").map(String::as_str), Some("-1.137270"));
-        assert_eq!(summary.get("parameters").map(String::as_str), Some("[0.121,-0.233,0.055,0.019]"));
-        assert_eq!(summary.get("objective").map(String::as_str), Some("-1.137270"));
-        assert!(!summary.contains_key("message"));
-        assert!(!summary.contains_key("counts_ref"));
-    }
-
     #[tokio::test]
-    async fn distributed_jobspec_metadata_is_projected_into_kernel_lineage() {
-        let (svc, runtime) = make_service(None);
+    async fn enqueue_with_job_yaml_stores_source_bundle() {
+        let qfs_root = test_qfs_root("source-bundle");
+        let runtime = Arc::new(KernelRuntimeStore::default());
+        let adapters = Arc::new(FixtureAdapters::new(&qfs_root, None));
+        let svc = KernelGatewaySvc::new(runtime.clone(), adapters);
+
+        let mut request = make_request("source-bundle");
+        request.job_yaml = "apiVersion: eigen/v1\nkind: QuantumJob\nmetadata:\n  name: source-bundle\n".to_string();
         let response = svc
-            .enqueue_job(Request::new(make_distributed_request("distributed")))
+            .enqueue_job(Request::new(request.clone()))
             .await
             .expect("enqueue should succeed")
             .into_inner();
 
-        let job = wait_for_terminal(runtime.clone(), &response.job_id).await;
-        assert_eq!(job.state, TaskState::Done);
-        assert_eq!(job.metadata.get("distributed.cluster_id").map(String::as_str), Some("cluster:auto"));
-        assert_eq!(job.metadata.get("distributed.partition_count").map(String::as_str), Some("2"));
-        assert_eq!(job.metadata.get("distributed.partition_ids").map(String::as_str), Some("[\"partition-0\",\"partition-1\"]"));
-        assert_eq!(job.metadata.get("distributed.preferred_workers").map(String::as_str), Some("[\"worker-a\",\"worker-b\"]"));
-        assert!(job.metadata.get("distributed.topology_digest_sha256").is_some());
-
-        let validate_stage = job
-            .stage_records
-            .iter()
-            .find(|stage| stage.stage_key == "validate-enqueue")
-            .expect("validate stage should exist");
-        assert_eq!(validate_stage.input.get("distributed.cluster_id").map(String::as_str), Some("cluster:auto"));
-        assert_eq!(validate_stage.input.get("distributed.partition_count").map(String::as_str), Some("2"));
-        assert_eq!(validate_stage.input.get("distributed.partition_ids").map(String::as_str), Some("[\"partition-0\",\"partition-1\"]"));
-        assert_eq!(validate_stage.input.get("distributed.preferred_workers").map(String::as_str), Some("[\"worker-a\",\"worker-b\"]"));
-        assert!(validate_stage.replay_token.len() >= 16);
+        let bundle = CircuitFsLocal::new(&qfs_root)
+            .load_source_bundle(&response.job_id)
+            .expect("source bundle should be stored before the DAG runs");
+        assert_eq!(bundle.job_yaml, request.job_yaml);
+        assert!(bundle.program_eigen_py.is_empty());
 
-        let status = svc
-            .get_dispatch_rationale(Request::new(GetDispatchRationaleRequest {
-                metadata: Some(RequestMetadata {
-                    contract_version: "1.0.0".to_string(),
-                    request_id: format!("dispatch-{}", response.job_id),
-                    idempotency_key: format!("dispatch-{}", response.job_id),
-                    traceparent: "00-0123456789abcdef0123456789abcdef-0123456789abcdef-01".to_string(),
-                    deadline: Some(ProtoDuration { seconds: 30, nanos: 0 }),
-                    tenant_id: "tenant-a".to_string(),
-                    project_id: "project-a".to_string(),
-                    subject: "alice".to_string(),
-                    role: "user".to_string(),
-                    source_service: "system-api".to_string(),
-                    trace_id: "".to_string(),
-                    retry_policy: "".to_string(),
-                    security_context: "".to_string(),
-                    workload: Some(Default::default()),
-                    ..Default::default()
-                }),
+        wait_for_terminal(runtime, &response.job_id).await;
+        let results = svc
+            .get_job_results(Request::new(GetJobResultsRequest {
                 job_id: response.job_id.clone(),
+                ..Default::default()
             }))
             .await
-            .expect("dispatch rationale should succeed")
+            .expect("results should succeed")
             .into_inner();
-        let rationale = status.rationale.expect("rationale should exist");
-        assert_eq!(rationale.attributes.get("distributed.cluster_id").map(String::as_str), Some("cluster:auto"));
-        assert_eq!(rationale.attributes.get("distributed.partition_count").map(String::as_str), Some("2"));
+        assert_eq!(
+            results.metadata.get("source_bundle_ref"),
+            Some(&format!("qfs://jobs/{}", response.job_id))
+        );
     }
 
     #[tokio::test]
-    async fn distributed_jobspec_topology_can_fall_back_to_jobspec_yaml() {
-        let (svc, _) = make_service(None);
+    async fn compile_only_job_is_done_after_compiling_without_running() {
+        let (svc, runtime) = make_service(None);
+        let request = EnqueueJobRequest { compile_only: true, ..make_request("compile-only") };
+        let job_id = svc.enqueue_job(Request::new(request)).await.expect("enqueue").into_inner().job_id;
+        wait_for_terminal(runtime.clone(), &job_id).await;
 
-        let mut request = make_request("yaml-fallback");
-        request.target = "cluster:auto".to_string();
-        request.metadata_kvs.insert(
-            "jobspec_yaml".to_string(),
-            r#"apiVersion: eigen.os/v1
-kind: QuantumJob
-spec:
-  target: cluster:auto
-  workload:
-    kind: DistributedJob
-    execution_profile: distributed
-    replayable: true
-    topology:
-      cluster_id: cluster:auto
-      partition_count: 2
-      partition_ids:
-        - partition-0
-        - partition-1
-      preferred_workers:
-        - worker-a
-        - worker-b
-"#
-                .to_string(),
-        );
+        let job = runtime.get(&job_id).expect("job");
+        assert_eq!(job.state, TaskState::Done);
+        assert!(job.history.iter().any(|entry| entry.from == TaskState::Compiling && entry.to == TaskState::Done));
+        assert!(job.history.iter().all(|entry| entry.to != TaskState::Running), "{:?}", job.history);
+        let stages: Vec<&str> = job.stage_records.iter().map(|stage| stage.stage_key.as_str()).collect();
+        assert_eq!(stages, ["validate-enqueue", "compile"]);
 
-        let response = svc
-            .enqueue_job(Request::new(request))
+        let results = svc
+            .get_job_results(Request::new(GetJobResultsRequest { job_id: job_id.clone(), ..Default::default() }))
             .await
-            .expect("yaml fallback should succeed")
+            .expect("results")
             .into_inner();
-
-        assert!(response.job_id.starts_with("job-"));
+        let compiled = format!("qfs://jobs/{job_id}/compiled/circuit.aqo.json");
+        assert_eq!(results.state, TaskState::Done as i32);
+        assert!(results.counts.is_empty());
+        assert_eq!(results.qfs_result_ref, compiled);
+        assert_eq!(results.metadata.get("compiled_artifact_ref"), Some(&compiled));
     }
 
     #[tokio::test]
-    async fn distributed_jobspec_topology_can_fall_back_to_request_workload() {
-        let (svc, _) = make_service(None);
-
-        let mut request = make_distributed_request("proto-fallback");
-        request.metadata_kvs.insert(
-            "jobspec_workload".to_string(),
-            serde_json::json!({
-                "kind": "DistributedJob",
-                "execution_profile": "distributed",
-                "replayable": true,
-            })
-            .to_string(),
-        );
-        request.metadata.as_mut().expect("metadata").workload = Some(WorkloadContract {
-            kind: 3,
-            execution_profile: "distributed".to_string(),
-            replayable: true,
-            topology: Some(WorkloadTopology {
-                cluster_id: "cluster:auto".to_string(),
-                partition_count: 2,
-                partition_ids: vec!["partition-0".to_string(), "partition-1".to_string()],
-                preferred_workers: vec!["worker-a".to_string(), "worker-b".to_string()],
-            }),
-            ..Default::default()
-        });
+    async fn delete_job_removes_the_record_and_its_job_directory() {
+        let qfs_root = test_qfs_root("delete-job");
+        let runtime = Arc::new(KernelRuntimeStore::default());
+        let adapters = Arc::new(FixtureAdapters::new(&qfs_root, None));
+        let svc = KernelGatewaySvc::new(runtime.clone(), adapters);
+        let delete = |job_id: &str| {
+            Request::new(DeleteJobRequest { job_id: job_id.to_string(), ..Default::default() })
+        };
 
-        let response = svc
-            .enqueue_job(Request::new(request))
+        let job_id = svc
+            .enqueue_job(Request::new(make_request("delete-job")))
             .await
-            .expect("proto workload fallback should succeed")
+            .expect("enqueue should succeed")
+            .into_inner()
+            .job_id;
+        wait_for_terminal(runtime.clone(), &job_id).await;
+        let qfs = CircuitFsLocal::new(&qfs_root);
+        assert!(qfs.job_exists(&job_id).expect("job exists"), "the run wrote artifacts");
+
+        let response = svc.delete_job(delete(&job_id)).await.expect("delete").into_inner();
+        assert_eq!(response, DeleteJobResponse { deleted: true, artifacts_removed: true });
+        assert!(!qfs.job_exists(&job_id).expect("job exists"));
+        assert!(runtime.get(&job_id).is_none());
+        let status = svc.get_job_status(Request::new(make_status_request(&job_id))).await;
+        assert_eq!(status.expect_err("deleted job").code(), Code::NotFound);
+        let again = svc.delete_job(delete(&job_id)).await.expect_err("second delete");
+        assert_eq!(again.code(), Code::NotFound);
+
+        // The same submission is accepted again as a new job.
+        let resubmitted = svc
+            .enqueue_job(Request::new(make_request("delete-job")))
+            .await
+            .expect("resubmit should succeed")
             .into_inner();
-
-        assert!(response.job_id.starts_with("job-"));
+        assert_eq!(resubmitted.state, TaskState::Pending as i32);
+        wait_for_terminal(runtime, &resubmitted.job_id).await;
     }
 
     #[tokio::test]
-    async fn distributed_jobspec_topology_can_fall_back_to_distributed_hints() {
-        let (svc, _) = make_service(None);
+    async fn kernel_info_counts_jobs_until_they_finish() {
+        let (svc, runtime) = make_service_with_hold(None, Some(DagStageKind::Execute), Duration::from_millis(300));
+        let info = || async { svc.get_kernel_info(Request::new(GetKernelInfoRequest::default())).await };
+
+        let idle = info().await.expect("kernel info").into_inner();
+        assert_eq!(idle.version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(idle.active_jobs, 0);
+        assert_eq!(idle.max_concurrent_jobs, DEFAULT_MAX_CONCURRENT_JOBS as i32);
+        assert_eq!(idle.backend_names, ["fixture-simulator"]);
+
+        let job_id = svc
+            .enqueue_job(Request::new(make_request("kernel-info")))
+            .await
+            .expect("enqueue should succeed")
+            .into_inner()
+            .job_id;
+        assert_eq!(info().await.expect("kernel info").into_inner().active_jobs, 1);
 
-        let mut request = make_request("distributed-hints");
-        request.target = "cluster:auto".to_string();
-        request.compiler_options.insert("distributed.partition_count".to_string(), "2".to_string());
-        request.metadata_kvs.insert("cluster_id".to_string(), "cluster:auto".to_string());
-        request.metadata_kvs.insert(
-            "jobspec_workload".to_string(),
-            serde_json::json!({
-                "kind": "DistributedJob",
-                "execution_profile": "distributed",
-                "replayable": true,
-            })
-            .to_string(),
-        );
+        assert_eq!(wait_for_terminal(runtime.clone(), &job_id).await.state, TaskState::Done);
+        svc.started_at_ms.fetch_sub(5_000, Ordering::Relaxed);
+        let done = info().await.expect("kernel info").into_inner();
+        assert_eq!(done.active_jobs, 0);
+        assert!(done.uptime_secs >= 5, "uptime {}", done.uptime_secs);
+    }
 
-        let response = svc
-            .enqueue_job(Request::new(request))
+    #[tokio::test]
+    async fn deleting_an_unfinished_job_needs_force() {
+        let (svc, runtime) = make_service_with_hold(None, Some(DagStageKind::Execute), Duration::from_secs(5));
+        let job_id = svc
+            .enqueue_job(Request::new(make_request("delete-running")))
             .await
-            .expect("distributed hints fallback should succeed")
-            .into_inner();
+            .expect("enqueue should succeed")
+            .into_inner()
+            .job_id;
 
-        assert!(response.job_id.starts_with("job-"));
+        let request = DeleteJobRequest { job_id: job_id.clone(), ..Default::default() };
+        let err = svc.delete_job(Request::new(request.clone())).await.expect_err("job is still running");
+        assert_eq!(err.code(), Code::FailedPrecondition);
+        assert!(runtime.get(&job_id).is_some_and(|job| !job.is_terminal()), "nothing was cancelled");
+
+        let forced = DeleteJobRequest { force: true, ..request };
+        let response = svc.delete_job(Request::new(forced)).await.expect("forced delete").into_inner();
+        assert!(response.deleted);
+        assert!(runtime.get(&job_id).is_none());
+        assert_eq!(runtime.metrics.jobs_in_state("cancelled"), 0, "deleted jobs leave the state gauge");
     }
 
     #[tokio::test]
-    async fn distributed_jobspec_missing_or_conflicting_topology_is_rejected() {
-        let (svc, _) = make_service(None);
-
-        let mut missing = make_request("missing-topology");
-        missing.metadata_kvs.insert(
-            "jobspec_workload".to_string(),
-            serde_json::json!({
-                "kind": "DistributedJob",
-                "execution_profile": "distributed",
-                "replayable": true,
-            })
-            .to_string(),
-        );
-        let err = svc.enqueue_job(Request::new(missing)).await.expect_err("missing topology should fail");
-        assert_eq!(err.code(), Code::InvalidArgument);
-        assert!(err.message().contains("spec.workload.topology"));
+    async fn fixture_execution_reports_counts_for_the_requested_shots() {
+        let qfs_root = test_qfs_root("fixture-counts");
+        let runtime = Arc::new(KernelRuntimeStore::default());
+        let adapters = Arc::new(FixtureAdapters::new(&qfs_root, None));
+        let svc = KernelGatewaySvc::new(runtime.clone(), adapters);
+
+        let mut job_counts = Vec::new();
+        for name in ["counts-a", "counts-b"] {
+            let mut request = make_request(name);
+            request.shots = 500;
+            let response = svc.enqueue_job(Request::new(request)).await.expect("enqueue should succeed").into_inner();
+            let job = wait_for_terminal(runtime.clone(), &response.job_id).await;
+            assert_eq!(job.state, TaskState::Done);
+            assert_eq!(job.counts.values().sum::<i64>(), 500);
+
+            let results = svc
+                .get_job_results(Request::new(GetJobResultsRequest {
+                    job_id: response.job_id.clone(),
+                    ..Default::default()
+                }))
+                .await
+                .expect("results should succeed")
+                .into_inner();
+            let counts: BTreeMap<String, i64> = results.counts.into_iter().collect();
+            assert_eq!(counts, job.counts);
+
+            let stored = CircuitFsLocal::new(&qfs_root)
+                .read_bytes(format!("qfs://jobs/{}/results/counts.json", response.job_id))
+                .expect("counts artifact");
+            let stored: serde_json::Value = serde_json::from_slice(&stored).expect("counts json");
+            assert_eq!(stored["counts"], serde_json::json!(counts));
+            job_counts.push(counts);
+        }
+        assert_eq!(job_counts[0], job_counts[1], "same program and shots give the same counts");
+    }
+
+    #[tokio::test]
+    async fn invalid_requests_carry_bad_request_details_with_a_stable_code() {
+        use tonic_types::StatusExt;
+
+        fn violation(status: &Status) -> (String, String) {
+            let details = status.get_error_details();
+            let bad_request = details.bad_request().expect("BadRequest detail");
+            let field = bad_request.field_violations.first().expect("field violation").field.clone();
+            let reason = details.error_info().expect("ErrorInfo detail").reason.clone();
+            (field, reason)
+        }
+
+        let (svc, _runtime) = make_service(None);
+        let mut long_name = make_request("long-name");
+        long_name.name = "n".repeat(validation::DEFAULT_MAX_NAME_CHARS + 1);
+        let mut blank_name = make_request("blank-name");
+        blank_name.name = "  ".to_string();
+        let mut oversized = make_request("oversized");
+        oversized.program = vec![b'#'; validation::DEFAULT_MAX_PROGRAM_BYTES + 1];
+        let mut no_tenant = make_request("no-tenant");
+        no_tenant.metadata.as_mut().expect("metadata").tenant_id.clear();
+        for (request, field, code) in [
+            (long_name, "name", validation::NAME_TOO_LONG),
+            (blank_name, "name", validation::EMPTY_NAME),
+            (oversized, "program", validation::PAYLOAD_TOO_LARGE),
+            (no_tenant, "metadata.tenant_id", validation::MISSING_FIELD),
+        ] {
+            let err = svc.enqueue_job(Request::new(request)).await.expect_err("invalid request");
+            assert_eq!(err.code(), Code::InvalidArgument);
+            assert_eq!(violation(&err), (field.to_string(), code.to_string()), "{err:?}");
+        }
+
+        let err = svc.get_job_status(Request::new(make_status_request("job 1/.."))).await.expect_err("malformed id");
+        assert_eq!(violation(&err), ("job_id".to_string(), validation::INVALID_JOB_ID_FORMAT.to_string()));
+        let err = svc
+            .get_job_results(Request::new(GetJobResultsRequest::default()))
+            .await
+            .expect_err("missing id");
+        assert_eq!(violation(&err), ("job_id".to_string(), validation::EMPTY_JOB_ID.to_string()));
+        let cancel = CancelJobRequest { job_id: "job\t1".to_string(), ..Default::default() };
+        let err = svc.cancel_job(Request::new(cancel)).await.expect_err("malformed id");
+        assert_eq!(err.code(), Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn policy_lets_tenant_members_read_but_only_owners_cancel() {
+        fn as_caller<T>(message: T, sub: &str, tenant_id: &str) -> Request<T> {
+            let extra = BTreeMap::from([("tenant_id".to_string(), serde_json::Value::from(tenant_id))]);
+            let mut request = Request::new(message);
+            let claims = Claims { sub: sub.to_string(), iss: String::new(), exp: 0, iat: None, extra };
+            request.extensions_mut().insert(claims);
+            request
+        }
+
+        let engine = PolicyEngine::from_toml_str(
+            r#"
+            [[rules]]
+            name = "own-jobs"
+            effect = "allow"
+            actions = ["submit", "status", "cancel", "results"]
+            scope = "owner"
+
+            [[rules]]
+            name = "tenant-read"
+            effect = "allow"
+            actions = ["status", "results"]
+            scope = "tenant"
+            "#,
+        )
+        .expect("policy");
+        let (svc, runtime) = make_service_with_hold(None, Some(DagStageKind::Execute), Duration::from_secs(5));
+        let svc = svc.with_policy(Some(Arc::new(engine)));
+
+        let err = svc
+            .enqueue_job(as_caller(make_request("policy-foreign"), "mallory", "tenant-b"))
+            .await
+            .expect_err("submitting into another tenant");
+        assert_eq!(err.code(), Code::PermissionDenied);
+
+        let job_id = svc
+            .enqueue_job(as_caller(make_request("policy-owned"), "alice", "tenant-a"))
+            .await
+            .expect("enqueue as alice")
+            .into_inner()
+            .job_id;
+        assert_eq!(runtime.get(&job_id).expect("job").owner(), "alice", "owner is the JWT subject");
+
+        svc.get_job_status(as_caller(make_status_request(&job_id), "bob", "tenant-a")).await.expect("tenant read");
+        let err = svc
+            .get_job_status(as_caller(make_status_request(&job_id), "mallory", "tenant-b"))
+            .await
+            .expect_err("other tenant");
+        assert_eq!(err.code(), Code::NotFound, "jobs the caller may not read are hidden");
+        let history = || GetJobHistoryRequest { job_id: job_id.clone(), ..Default::default() };
+        svc.get_job_history(as_caller(history(), "bob", "tenant-a")).await.expect("tenant reads history");
+        let err = svc.get_job_history(as_caller(history(), "mallory", "tenant-b")).await.expect_err("other tenant");
+        assert_eq!(err.code(), Code::NotFound);
+        let err = svc
+            .pause_job(as_caller(make_pause_request(&job_id), "bob", "tenant-a"))
+            .await
+            .expect_err("pausing is job control");
+        assert_eq!(err.code(), Code::PermissionDenied);
+
+        let cancel = || CancelJobRequest { job_id: job_id.clone(), ..Default::default() };
+        let err = svc.cancel_job(as_caller(cancel(), "bob", "tenant-a")).await.expect_err("not the owner");
+        assert_eq!(err.code(), Code::PermissionDenied);
+        assert!(err.message().contains("\"default\""), "{}", err.message());
+        let cancelled = svc.cancel_job(as_caller(cancel(), "alice", "tenant-a")).await.expect("owner cancels");
+        assert!(cancelled.into_inner().accepted);
+        svc.cancel_job(Request::new(cancel())).await.expect("calls without claims are not policy-checked");
+    }
+
+    #[tokio::test]
+    async fn jobs_are_hidden_from_callers_who_did_not_submit_them() {
+        fn as_caller<T>(message: T, sub: &str, scope: &str) -> Request<T> {
+            let extra = BTreeMap::from([(security_module::SCOPE_CLAIM.to_string(), serde_json::Value::from(scope))]);
+            let mut request = Request::new(message);
+            let claims = Claims { sub: sub.to_string(), iss: String::new(), exp: 0, iat: None, extra };
+            request.extensions_mut().insert(claims);
+            request
+        }
+        async fn listed(svc: &KernelGatewaySvc, request: Request<ListJobsRequest>) -> Vec<String> {
+            let response = svc.list_jobs(request).await.expect("list").into_inner();
+            response.jobs.into_iter().map(|job| job.job_id).collect()
+        }
+
+        let (svc, runtime) = make_service_with_hold(None, Some(DagStageKind::Execute), Duration::from_secs(5));
+        let alice_job = svc
+            .enqueue_job(as_caller(make_request("owned-by-alice"), "alice", ""))
+            .await
+            .expect("enqueue as alice")
+            .into_inner()
+            .job_id;
+        let bob_job = svc
+            .enqueue_job(as_caller(make_request("owned-by-bob"), "bob", ""))
+            .await
+            .expect("enqueue as bob")
+            .into_inner()
+            .job_id;
+        assert_eq!(runtime.get(&bob_job).expect("job").owner(), "bob");
+
+        svc.get_job_status(as_caller(make_status_request(&alice_job), "alice", "")).await.expect("own job");
+        let status = svc.get_job_status(as_caller(make_status_request(&alice_job), "bob", "")).await;
+        assert_eq!(status.expect_err("someone else's job").code(), Code::NotFound);
+        let results = GetJobResultsRequest { job_id: alice_job.clone(), ..Default::default() };
+        let results = svc.get_job_results(as_caller(results, "bob", "")).await;
+        assert_eq!(results.expect_err("someone else's job").code(), Code::NotFound);
+        let cancel = CancelJobRequest { job_id: alice_job.clone(), ..Default::default() };
+        let cancel = svc.cancel_job(as_caller(cancel, "bob", "jobs:write")).await;
+        assert_eq!(cancel.expect_err("someone else's job").code(), Code::NotFound);
+        assert!(!runtime.get(&alice_job).expect("job").cancel_requested);
+
+        assert_eq!(listed(&svc, as_caller(list_request("", "", 0), "bob", "")).await, std::slice::from_ref(&bob_job));
+        let everything = listed(&svc, as_caller(list_request("", "", 0), "carol", ADMIN_SCOPE)).await;
+        assert_eq!(everything.len(), 2, "{everything:?}");
+        assert!(everything.contains(&alice_job) && everything.contains(&bob_job));
+        assert_eq!(listed(&svc, Request::new(list_request("", "", 0))).await.len(), 2, "calls without claims");
+
+        let admin_status = svc.get_job_status(as_caller(make_status_request(&alice_job), "carol", ADMIN_SCOPE)).await;
+        assert_eq!(admin_status.expect("admin scope sees every job").into_inner().job_id, alice_job);
+    }
+
+    #[tokio::test]
+    async fn api_key_callers_only_see_jobs_submitted_with_their_key() {
+        fn with_key<T>(message: T, key: &str) -> Request<T> {
+            let mut request = Request::new(message);
+            request.extensions_mut().insert(ServiceCaller::for_api_key(key));
+            request
+        }
+
+        let (svc, runtime) = make_service_with_hold(None, Some(DagStageKind::Execute), Duration::from_secs(5));
+        let job_id = svc
+            .enqueue_job(with_key(make_request("owned-by-key-ci"), "key-ci"))
+            .await
+            .expect("enqueue with key-ci")
+            .into_inner()
+            .job_id;
+        let owner = runtime.get(&job_id).expect("job").owner().to_string();
+        assert_eq!(owner, ServiceCaller::for_api_key("key-ci").subject, "owner is the key, not metadata.subject");
+        assert!(!owner.contains("key-ci"), "the key itself is never stored");
+
+        svc.get_job_status(with_key(make_status_request(&job_id), "key-ci")).await.expect("own job");
+        let status = svc.get_job_status(with_key(make_status_request(&job_id), "key-ops")).await;
+        assert_eq!(status.expect_err("another key's job").code(), Code::NotFound);
+        let cancel = CancelJobRequest { job_id: job_id.clone(), ..Default::default() };
+        let cancel = svc.cancel_job(with_key(cancel, "key-ops")).await;
+        assert_eq!(cancel.expect_err("another key's job").code(), Code::NotFound);
+        let listed = svc.list_jobs(with_key(list_request("", "", 0), "key-ops")).await.expect("list");
+        assert!(listed.into_inner().jobs.is_empty());
+        let mut shared = Request::new(make_status_request(&job_id));
+        shared.extensions_mut().insert(ServiceCaller::shared_secret());
+        assert_eq!(svc.get_job_status(shared).await.expect_err("shared secret").code(), Code::NotFound);
+    }
+
+    #[tokio::test]
+    async fn fixture_execution_uses_the_submitted_seed() {
+        let qfs_root = test_qfs_root("fixture-seed");
+        let runtime = Arc::new(KernelRuntimeStore::default());
+        let adapters = Arc::new(FixtureAdapters::new(&qfs_root, None));
+        let svc = KernelGatewaySvc::new(runtime.clone(), adapters);
+
+        let mut counts = Vec::new();
+        for (name, seed) in [("seed-a", "11"), ("seed-b", "11"), ("seed-c", "12")] {
+            let mut request = make_request(name);
+            request.shots = 2048;
+            request.metadata_kvs.insert("seed".to_string(), seed.to_string());
+            let program = request.program.clone();
+            let response = svc.enqueue_job(Request::new(request)).await.expect("enqueue should succeed").into_inner();
+            let job = wait_for_terminal(runtime.clone(), &response.job_id).await;
+            assert_eq!(job.state, TaskState::Done);
+            assert_eq!(job.counts, simulate_counts(&program, 2048, seed.parse().expect("seed")));
+            counts.push(job.counts);
+        }
+        assert_eq!(counts[0], counts[1]);
+        assert_ne!(counts[0], counts[2]);
+    }
+
+    #[tokio::test]
+    async fn results_metadata_carries_backend_shots_and_stage_timings() {
+        let qfs_root = test_qfs_root("result-metadata");
+        let runtime = Arc::new(KernelRuntimeStore::default());
+        let adapters = Arc::new(FixtureAdapters::new(&qfs_root, None));
+        let svc = KernelGatewaySvc::new(runtime.clone(), adapters);
+        let response = svc
+            .enqueue_job(Request::new(make_request("result-metadata")))
+            .await
+            .expect("enqueue should succeed")
+            .into_inner();
+        assert_eq!(wait_for_terminal(runtime, &response.job_id).await.state, TaskState::Done);
+
+        let results = svc
+            .get_job_results(Request::new(GetJobResultsRequest {
+                job_id: response.job_id.clone(),
+                ..Default::default()
+            }))
+            .await
+            .expect("results should succeed")
+            .into_inner();
+        for key in [
+            "result.summary.selected_backend",
+            "result.summary.shots_total",
+            "result.summary.wall_time_ms",
+            "result.summary.stage_duration_ms.compile",
+            "result.summary.stage_duration_ms.execute",
+        ] {
+            assert!(results.metadata.contains_key(key), "missing {key} in {:?}", results.metadata);
+        }
+        let shots: i64 = results.counts.values().sum();
+        assert_eq!(results.metadata["result.summary.shots_total"], shots.to_string());
+
+        let stored = CircuitFsLocal::new(&qfs_root)
+            .load_result_metadata(&response.job_id)
+            .expect("metadata.json should be stored with the results");
+        assert_eq!(
+            stored.get("selected_backend"),
+            results.metadata.get("result.summary.selected_backend")
+        );
+        assert!(stored.contains_key("stage_duration_ms.schedule"));
+    }
+
+    #[tokio::test]
+    async fn enqueue_with_malformed_job_yaml_is_rejected_without_a_record() {
+        let (svc, runtime) = make_service(None);
+        let mut request = make_request("bad-yaml");
+        request.job_yaml = "kind: [QuantumJob".to_string();
+
+        let err = svc
+            .enqueue_job(Request::new(request))
+            .await
+            .expect_err("malformed job_yaml must be rejected");
+        assert_eq!(err.code(), Code::InvalidArgument);
+        assert!(runtime.jobs.read().is_empty());
+    }
+
+    #[tokio::test]
+    async fn enqueue_job_batch_reports_an_outcome_per_entry() {
+        let (svc, runtime) = make_service(None);
+        let mut unnamed = make_request("batch-unnamed");
+        unnamed.name.clear();
+        let mut bad_yaml = make_request("batch-bad-yaml");
+        bad_yaml.job_yaml = "kind: [QuantumJob".to_string();
+        let jobs = vec![make_request("batch-a"), unnamed, make_request("batch-b"), bad_yaml];
+
+        let results = svc
+            .enqueue_job_batch(Request::new(EnqueueJobBatchRequest { jobs }))
+            .await
+            .expect("batch")
+            .into_inner()
+            .results;
+
+        let accepted: Vec<bool> = results.iter().map(|result| result.accepted).collect();
+        assert_eq!(accepted, [true, false, true, false]);
+        for rejected in [&results[1], &results[3]] {
+            assert_eq!(rejected.error_code, Code::InvalidArgument as i32, "{rejected:?}");
+            assert!(rejected.job_id.is_empty() && !rejected.error_message.is_empty(), "{rejected:?}");
+        }
+        assert!(results[1].error_message.contains("name"), "{:?}", results[1]);
+        for enqueued in [&results[0], &results[2]] {
+            assert_eq!(enqueued.error_code, 0);
+            let job = wait_for_terminal(runtime.clone(), &enqueued.job_id).await;
+            assert_eq!(job.state, TaskState::Done);
+        }
+        assert_eq!(runtime.jobs.read().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn enqueue_job_batch_over_the_limit_is_rejected_whole() {
+        let runtime = Arc::new(KernelRuntimeStore { enqueue_batch_limit: EnqueueBatchLimit(2), ..Default::default() });
+        let svc = KernelGatewaySvc::new(runtime.clone(), Arc::new(FixtureAdapters::new(test_qfs_root("batch"), None)));
+        let jobs = ["batch-1", "batch-2", "batch-3"].map(make_request).to_vec();
+
+        let err = svc
+            .enqueue_job_batch(Request::new(EnqueueJobBatchRequest { jobs }))
+            .await
+            .expect_err("batch over the limit");
+        assert_eq!(err.code(), Code::InvalidArgument);
+        assert!(err.message().contains("limit of 2"), "{err}");
+        assert!(runtime.jobs.read().is_empty());
+    }
+
+    #[tokio::test]
+    async fn batch_enqueue_jobs_creates_every_job_in_order() {
+        let (svc, runtime) = make_service(None);
+        let mut jobs = ["atomic-a", "atomic-b", "atomic-c"].map(make_request).to_vec();
+        jobs.push(make_request("atomic-a"));
+
+        let results = svc
+            .batch_enqueue_jobs(Request::new(BatchEnqueueJobsRequest { jobs }))
+            .await
+            .expect("batch")
+            .into_inner()
+            .results;
+
+        assert_eq!(results.len(), 4);
+        assert_eq!(results[3].job_id, results[0].job_id, "a repeated entry is the same job");
+        assert_eq!(runtime.jobs.read().len(), 3);
+        for result in &results[..3] {
+            assert_eq!(result.state, TaskState::Pending as i32);
+            let job = wait_for_terminal(runtime.clone(), &result.job_id).await;
+            assert_eq!(job.state, TaskState::Done);
+        }
+    }
+
+    #[tokio::test]
+    async fn batch_enqueue_jobs_with_an_invalid_entry_creates_nothing() {
+        let (svc, runtime) = make_service(None);
+        let mut unnamed = make_request("atomic-unnamed");
+        unnamed.name.clear();
+        let jobs = vec![make_request("atomic-ok"), unnamed, make_request("atomic-ok-2")];
+
+        let err = svc
+            .batch_enqueue_jobs(Request::new(BatchEnqueueJobsRequest { jobs }))
+            .await
+            .expect_err("invalid entry");
+        assert_eq!(err.code(), Code::InvalidArgument);
+        assert!(err.message().starts_with("jobs[1]: "), "{err}");
+        assert!(runtime.jobs.read().is_empty());
+    }
+
+    #[tokio::test]
+    async fn batch_enqueue_jobs_over_quota_releases_what_it_reserved() {
+        let runtime = Arc::new(KernelRuntimeStore {
+            quota: QuotaManager::new(QuotaPolicy { max_concurrent_jobs_per_tenant: 2, ..QuotaPolicy::default() }),
+            ..KernelRuntimeStore::default()
+        });
+        let svc = KernelGatewaySvc::new(runtime.clone(), Arc::new(FixtureAdapters::new(test_qfs_root("batch"), None)));
+        let jobs = ["quota-1", "quota-2", "quota-3"].map(make_request).to_vec();
+
+        let err = svc
+            .batch_enqueue_jobs(Request::new(BatchEnqueueJobsRequest { jobs: jobs.clone() }))
+            .await
+            .expect_err("third job is over quota");
+        assert_eq!(err.code(), Code::ResourceExhausted);
+        assert!(err.message().starts_with("jobs[2]: "), "{err}");
+        assert!(runtime.jobs.read().is_empty());
+
+        let fits = BatchEnqueueJobsRequest { jobs: jobs[..2].to_vec() };
+        let results = svc.batch_enqueue_jobs(Request::new(fits)).await.expect("quota was released").into_inner();
+        assert_eq!(results.results.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn batch_enqueue_jobs_rolls_back_created_jobs_when_a_source_bundle_fails() {
+        let qfs_root = test_qfs_root("batch-rollback");
+        let runtime = Arc::new(KernelRuntimeStore {
+            quota: QuotaManager::new(QuotaPolicy { max_concurrent_jobs_per_tenant: 2, ..QuotaPolicy::default() }),
+            ..KernelRuntimeStore::default()
+        });
+        let svc = KernelGatewaySvc::new(runtime.clone(), Arc::new(FixtureAdapters::new(&qfs_root, None)));
+        let jobs = ["rollback-1", "rollback-2"].map(|name| {
+            let mut request = make_request(name);
+            request.metadata.as_mut().expect("metadata").idempotency_key.clear();
+            request.job_yaml = format!("apiVersion: eigen/v1\nkind: QuantumJob\nmetadata:\n  name: {name}\n");
+            request
+        });
+        let qfs = CircuitFsLocal::new(&qfs_root);
+        let job_ids = jobs.clone().map(|job| NormalizedSubmission::from_request(&job).expect("submission").job_id);
+        // Another payload already owns the second job's bundle, so storing it fails.
+        let foreign = SourceBundle { job_yaml: "kind: QuantumJob\n".to_string(), program_eigen_py: Vec::new() };
+        qfs.store_source_bundle(&job_ids[1], &foreign).expect("foreign bundle");
+
+        let err = svc
+            .batch_enqueue_jobs(Request::new(BatchEnqueueJobsRequest { jobs: jobs.to_vec() }))
+            .await
+            .expect_err("second bundle cannot be stored");
+        assert!(err.message().starts_with("jobs[1]: "), "{err}");
+        assert!(runtime.jobs.read().is_empty());
+        assert!(!qfs.job_exists(&job_ids[0]).expect("job_exists"), "the first bundle is removed again");
+
+        let first = BatchEnqueueJobsRequest { jobs: jobs[..1].to_vec() };
+        let results = svc.batch_enqueue_jobs(Request::new(first)).await.expect("quota was released").into_inner();
+        assert_eq!(wait_for_terminal(runtime, &results.results[0].job_id).await.state, TaskState::Done);
+    }
+
+    fn request_with_bundle(name: &str) -> EnqueueJobRequest {
+        let mut request = make_request(name);
+        request.metadata.as_mut().expect("metadata").idempotency_key.clear();
+        request.job_yaml = format!("apiVersion: eigen/v1\nkind: QuantumJob\nmetadata:\n  name: {name}\n");
+        request
+    }
+
+    fn single_job_quota_runtime() -> Arc<KernelRuntimeStore> {
+        Arc::new(KernelRuntimeStore {
+            quota: QuotaManager::new(QuotaPolicy { max_concurrent_jobs_per_tenant: 1, ..QuotaPolicy::default() }),
+            ..KernelRuntimeStore::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn enqueue_job_refused_over_quota_stores_no_source_bundle() {
+        let qfs_root = test_qfs_root("enqueue-refused");
+        let runtime = single_job_quota_runtime();
+        let adapters = FixtureAdapters::with_hold(&qfs_root, None, Some(DagStageKind::Execute), Duration::from_secs(5));
+        let svc = KernelGatewaySvc::new(runtime.clone(), Arc::new(adapters));
+        let qfs = CircuitFsLocal::new(&qfs_root);
+
+        svc.enqueue_job(Request::new(request_with_bundle("refused-running"))).await.expect("takes the quota");
+        let over_quota = request_with_bundle("refused-over-quota");
+        let job_id = NormalizedSubmission::from_request(&over_quota).expect("submission").job_id;
+        let err = svc.enqueue_job(Request::new(over_quota)).await.expect_err("over quota");
+        assert_eq!(err.code(), Code::ResourceExhausted);
+        assert!(runtime.get(&job_id).is_none());
+        assert!(!qfs.job_exists(&job_id).expect("job_exists"), "a refused job leaves no bundle behind");
+    }
+
+    #[tokio::test]
+    async fn enqueue_job_rolls_back_the_job_when_its_source_bundle_fails() {
+        let qfs_root = test_qfs_root("enqueue-rollback");
+        let runtime = single_job_quota_runtime();
+        let svc = KernelGatewaySvc::new(runtime.clone(), Arc::new(FixtureAdapters::new(&qfs_root, None)));
+        let request = request_with_bundle("rollback-single");
+        let job_id = NormalizedSubmission::from_request(&request).expect("submission").job_id;
+        // Another payload already owns the job's bundle, so storing it fails.
+        let foreign = SourceBundle { job_yaml: "kind: QuantumJob\n".to_string(), program_eigen_py: Vec::new() };
+        CircuitFsLocal::new(&qfs_root).store_source_bundle(&job_id, &foreign).expect("foreign bundle");
+
+        svc.enqueue_job(Request::new(request)).await.expect_err("bundle cannot be stored");
+        assert!(runtime.get(&job_id).is_none(), "the job record is discarded");
+
+        let next = svc
+            .enqueue_job(Request::new(request_with_bundle("rollback-next")))
+            .await
+            .expect("quota was released")
+            .into_inner();
+        assert_eq!(wait_for_terminal(runtime, &next.job_id).await.state, TaskState::Done);
+    }
+
+    #[tokio::test]
+    async fn batch_enqueue_jobs_accepts_at_most_a_hundred_entries() {
+        let (svc, runtime) = make_service(None);
+        let jobs: Vec<_> = (0..=MAX_BATCH_ENQUEUE_JOBS).map(|index| make_request(&format!("limit-{index}"))).collect();
+
+        let err = svc
+            .batch_enqueue_jobs(Request::new(BatchEnqueueJobsRequest { jobs }))
+            .await
+            .expect_err("101 jobs");
+        assert_eq!(err.code(), Code::InvalidArgument);
+        assert!(err.message().contains("limit of 100"), "{err}");
+        assert!(runtime.jobs.read().is_empty());
+    }
+
+    #[test]
+    fn result_summary_metadata_promotion_is_generic() {
+        let mut summary = BTreeMap::from([("workload_kind".to_string(), "HybridWorkflow".to_string())]);
+        let mut source = BTreeMap::new();
+        source.insert("energy".to_string(), "-1.137270".to_string());
+        source.insert("parameters".to_string(), "[0.121,-0.233,0.055,0.019]".to_string());
+        source.insert("result.summary.objective".to_string(), "-1.137270".to_string());
+        source.insert("message".to_string(), "ignored".to_string());
+        source.insert("counts_ref".to_string(), "qfs://jobs/job-1/results/counts.json".to_string());
+
+        merge_result_summary_fields(&mut summary, &source);
+
+        assert_eq!(summary.get("energy").map(String::as_str), Some("-1.137270"));
+        assert_eq!(summary.get("parameters").map(String::as_str), Some("[0.121,-0.233,0.055,0.019]"));
+        assert_eq!(summary.get("objective").map(String::as_str), Some("-1.137270"));
+        assert!(!summary.contains_key("message"));
+        assert!(!summary.contains_key("counts_ref"));
+    }
+
+    #[tokio::test]
+    async fn distributed_jobspec_metadata_is_projected_into_kernel_lineage() {
+        let (svc, runtime) = make_service(None);
+        let response = svc
+            .enqueue_job(Request::new(make_distributed_request("distributed")))
+            .await
+            .expect("enqueue should succeed")
+            .into_inner();
+
+        let job = wait_for_terminal(runtime.clone(), &response.job_id).await;
+        assert_eq!(job.state, TaskState::Done);
+        assert_eq!(job.metadata.get("distributed.cluster_id").map(String::as_str), Some("cluster:auto"));
+        assert_eq!(job.metadata.get("distributed.partition_count").map(String::as_str), Some("2"));
+        assert_eq!(job.metadata.get("distributed.partition_ids").map(String::as_str), Some("[\"partition-0\",\"partition-1\"]"));
+        assert_eq!(job.metadata.get("distributed.preferred_workers").map(String::as_str), Some("[\"worker-a\",\"worker-b\"]"));
+        assert!(job.metadata.get("distributed.topology_digest_sha256").is_some());
+
+        let validate_stage = job
+            .stage_records
+            .iter()
+            .find(|stage| stage.stage_key == "validate-enqueue")
+            .expect("validate stage should exist");
+        assert_eq!(validate_stage.input.get("distributed.cluster_id").map(String::as_str), Some("cluster:auto"));
+        assert_eq!(validate_stage.input.get("distributed.partition_count").map(String::as_str), Some("2"));
+        assert_eq!(validate_stage.input.get("distributed.partition_ids").map(String::as_str), Some("[\"partition-0\",\"partition-1\"]"));
+        assert_eq!(validate_stage.input.get("distributed.preferred_workers").map(String::as_str), Some("[\"worker-a\",\"worker-b\"]"));
+        assert!(validate_stage.replay_token.len() >= 16);
+
+        let status = svc
+            .get_dispatch_rationale(Request::new(GetDispatchRationaleRequest {
+                metadata: Some(RequestMetadata {
+                    contract_version: "1.0.0".to_string(),
+                    request_id: format!("dispatch-{}", response.job_id),
+                    idempotency_key: format!("dispatch-{}", response.job_id),
+                    traceparent: "00-0123456789abcdef0123456789abcdef-0123456789abcdef-01".to_string(),
+                    deadline: Some(ProtoDuration { seconds: 30, nanos: 0 }),
+                    tenant_id: "tenant-a".to_string(),
+                    project_id: "project-a".to_string(),
+                    subject: "alice".to_string(),
+                    role: "user".to_string(),
+                    source_service: "system-api".to_string(),
+                    trace_id: "".to_string(),
+                    retry_policy: "".to_string(),
+                    security_context: "".to_string(),
+                    workload: Some(Default::default()),
+                    ..Default::default()
+                }),
+                job_id: response.job_id.clone(),
+            }))
+            .await
+            .expect("dispatch rationale should succeed")
+            .into_inner();
+        let rationale = status.rationale.expect("rationale should exist");
+        assert_eq!(rationale.attributes.get("distributed.cluster_id").map(String::as_str), Some("cluster:auto"));
+        assert_eq!(rationale.attributes.get("distributed.partition_count").map(String::as_str), Some("2"));
+    }
+
+    #[tokio::test]
+    async fn distributed_jobspec_topology_can_fall_back_to_jobspec_yaml() {
+        let (svc, _) = make_service(None);
+
+        let mut request = make_request("yaml-fallback");
+        request.target = "cluster:auto".to_string();
+        request.metadata_kvs.insert(
+            "jobspec_yaml".to_string(),
+            r#"apiVersion: eigen.os/v1
+kind: QuantumJob
+spec:
+  target: cluster:auto
+  workload:
+    kind: DistributedJob
+    execution_profile: distributed
+    replayable: true
+    topology:
+      cluster_id: cluster:auto
+      partition_count: 2
+      partition_ids:
+        - partition-0
+        - partition-1
+      preferred_workers:
+        - worker-a
+        - worker-b
+"#
+                .to_string(),
+        );
+
+        let response = svc
+            .enqueue_job(Request::new(request))
+            .await
+            .expect("yaml fallback should succeed")
+            .into_inner();
+
+        assert!(response.job_id.starts_with("job-"));
+    }
+
+    #[tokio::test]
+    async fn distributed_jobspec_topology_can_fall_back_to_request_workload() {
+        let (svc, _) = make_service(None);
+
+        let mut request = make_distributed_request("proto-fallback");
+        request.metadata_kvs.insert(
+            "jobspec_workload".to_string(),
+            serde_json::json!({
+                "kind": "DistributedJob",
+                "execution_profile": "distributed",
+                "replayable": true,
+            })
+            .to_string(),
+        );
+        request.metadata.as_mut().expect("metadata").workload = Some(WorkloadContract {
+            kind: 3,
+            execution_profile: "distributed".to_string(),
+            replayable: true,
+            topology: Some(WorkloadTopology {
+                cluster_id: "cluster:auto".to_string(),
+                partition_count: 2,
+                partition_ids: vec!["partition-0".to_string(), "partition-1".to_string()],
+                preferred_workers: vec!["worker-a".to_string(), "worker-b".to_string()],
+            }),
+            ..Default::default()
+        });
+
+        let response = svc
+            .enqueue_job(Request::new(request))
+            .await
+            .expect("proto workload fallback should succeed")
+            .into_inner();
+
+        assert!(response.job_id.starts_with("job-"));
+    }
+
+    #[tokio::test]
+    async fn distributed_jobspec_topology_can_fall_back_to_distributed_hints() {
+        let (svc, _) = make_service(None);
+
+        let mut request = make_request("distributed-hints");
+        request.target = "cluster:auto".to_string();
+        request.compiler_options.insert("distributed.partition_count".to_string(), "2".to_string());
+        request.metadata_kvs.insert("cluster_id".to_string(), "cluster:auto".to_string());
+        request.metadata_kvs.insert(
+            "jobspec_workload".to_string(),
+            serde_json::json!({
+                "kind": "DistributedJob",
+                "execution_profile": "distributed",
+                "replayable": true,
+            })
+            .to_string(),
+        );
+
+        let response = svc
+            .enqueue_job(Request::new(request))
+            .await
+            .expect("distributed hints fallback should succeed")
+            .into_inner();
+
+        assert!(response.job_id.starts_with("job-"));
+    }
+
+    #[tokio::test]
+    async fn distributed_jobspec_missing_or_conflicting_topology_is_rejected() {
+        let (svc, _) = make_service(None);
+
+        let mut missing = make_request("missing-topology");
+        missing.metadata_kvs.insert(
+            "jobspec_workload".to_string(),
+            serde_json::json!({
+                "kind": "DistributedJob",
+                "execution_profile": "distributed",
+                "replayable": true,
+            })
+            .to_string(),
+        );
+        let err = svc.enqueue_job(Request::new(missing)).await.expect_err("missing topology should fail");
+        assert_eq!(err.code(), Code::InvalidArgument);
+        assert!(err.message().contains("spec.workload.topology"));
 
         let mut conflicting = make_request("conflicting-topology");
         conflicting.metadata_kvs.insert(
@@ -5852,353 +9114,2014 @@ spec:
                     "preferred_workers": ["worker-a", "worker-b"],
                 },
             })
-            .to_string(),
+            .to_string(),
+        );
+        let err = svc.enqueue_job(Request::new(conflicting)).await.expect_err("conflicting topology should fail");
+        assert_eq!(err.code(), Code::InvalidArgument);
+        assert!(err.message().contains("partition_ids"));
+    }
+
+    #[tokio::test]
+    async fn submit_to_results_failure_path_marks_error_metadata() {
+        let (svc, runtime) = make_service(Some(DagStageKind::Optimize));
+        let response = svc
+            .enqueue_job(Request::new(make_request("failure")))
+            .await
+            .expect("enqueue should succeed")
+            .into_inner();
+
+        let job = wait_for_terminal(runtime.clone(), &response.job_id).await;
+        assert_eq!(job.state, TaskState::Error);
+        assert_eq!(job.error_code.as_deref(), Some("OPTIMIZER_STAGE_FAILED"));
+        assert_eq!(
+            job.stage_records
+                .iter()
+                .find(|stage| stage.stage_key == "optimize")
+                .and_then(|stage| stage.error_code.as_deref()),
+            Some("OPTIMIZER_STAGE_FAILED")
+        );
+
+        let results = svc
+            .get_job_results(Request::new(GetJobResultsRequest {
+                metadata: Some(RequestMetadata {
+                    contract_version: "1.0.0".to_string(),
+                    request_id: format!("results-{}", response.job_id),
+                    idempotency_key: format!("results-{}", response.job_id),
+                    traceparent: "00-0123456789abcdef0123456789abcdef-0123456789abcdef-01".to_string(),
+                    deadline: Some(ProtoDuration { seconds: 30, nanos: 0 }),
+                    tenant_id: "tenant-a".to_string(),
+                    project_id: "project-a".to_string(),
+                    subject: "alice".to_string(),
+                    role: "user".to_string(),
+                    source_service: "system-api".to_string(),
+                    trace_id: "".to_string(),
+                    retry_policy: "".to_string(),
+                    security_context: "".to_string(),
+                    workload: Some(Default::default()),
+                    ..Default::default()
+                }),
+                job_id: response.job_id.clone(),
+            }))
+            .await
+            .expect("terminal error results should succeed")
+            .into_inner();
+        assert_eq!(results.state, TaskState::Error as i32);
+        assert_eq!(results.error_code, "OPTIMIZER_STAGE_FAILED");
+        assert!(!results.error_summary.is_empty());
+    }
+
+    #[tokio::test]
+    async fn failed_job_error_details_ref_resolves_to_error_json() {
+        let qfs_root = test_qfs_root("error-details");
+        let runtime = Arc::new(KernelRuntimeStore::default());
+        let adapters = Arc::new(FixtureAdapters::new(qfs_root.clone(), Some(DagStageKind::Compile)));
+        let svc = KernelGatewaySvc::new(runtime.clone(), adapters);
+        let response = svc
+            .enqueue_job(Request::new(make_request("error-details")))
+            .await
+            .expect("enqueue should succeed")
+            .into_inner();
+        wait_for_terminal(runtime, &response.job_id).await;
+
+        let results = svc
+            .get_job_results(Request::new(GetJobResultsRequest {
+                metadata: make_request("error-details").metadata,
+                job_id: response.job_id.clone(),
+            }))
+            .await
+            .expect("terminal error results should succeed")
+            .into_inner();
+        assert_eq!(results.error_details_ref, format!("qfs://jobs/{}/results/error.json", response.job_id));
+
+        // The report is written right after the job turns terminal.
+        let qfs = CircuitFsLocal::new(&qfs_root);
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+        let bytes = loop {
+            match qfs.read_bytes(&results.error_details_ref) {
+                Ok(bytes) => break bytes,
+                Err(err) if tokio::time::Instant::now() >= deadline => panic!("error.json was not written: {err}"),
+                Err(_) => tokio::time::sleep(Duration::from_millis(10)).await,
+            }
+        };
+        let details: serde_json::Value = serde_json::from_slice(&bytes).expect("error.json is JSON");
+        assert_eq!(details["job_id"], response.job_id.as_str());
+        assert_eq!(details["state"], "TASK_STATE_ERROR");
+        assert_eq!(details["error_code"], "COMPILER_STAGE_FAILED");
+        assert_eq!(details["error_summary"], results.error_summary.as_str());
+        assert_eq!(details["failed_stage"], "compile");
+        assert_eq!(details["stage_details_ref"], "qfs://fixtures/compile-stage-failure.json");
+        assert!(details["failed_at_unix_ms"].as_i64().is_some_and(|ms| ms > 0));
+    }
+
+    #[tokio::test]
+    async fn injected_compile_failure_fails_the_job_with_the_configured_code() {
+        let qfs_root = test_qfs_root("sim-compile-failure");
+        let pipeline = PipelineConfig {
+            compile_ms: 20,
+            fail_stage: Some(SimulatedStage::Compile),
+            fail_code: "QPU_CALIBRATION_FAILED".to_string(),
+            ..PipelineConfig::default()
+        };
+        let (svc, runtime) = make_service_with_pipeline(&qfs_root, pipeline);
+        let job_id = svc
+            .enqueue_job(Request::new(make_request("sim-compile-failure")))
+            .await
+            .expect("enqueue should succeed")
+            .into_inner()
+            .job_id;
+        wait_for_terminal(runtime.clone(), &job_id).await;
+
+        let job = runtime.get(&job_id).expect("job");
+        assert_eq!(job.state, TaskState::Error);
+        assert_eq!(job.error_code.as_deref(), Some("QPU_CALIBRATION_FAILED"));
+        assert!(job.history.iter().all(|entry| entry.to != TaskState::Running));
+
+        let qfs = CircuitFsLocal::new(&qfs_root);
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+        let bytes = loop {
+            match qfs.load_error_details_json(&job_id) {
+                Ok(bytes) => break bytes,
+                Err(err) if tokio::time::Instant::now() >= deadline => panic!("error.json was not written: {err}"),
+                Err(_) => tokio::time::sleep(Duration::from_millis(10)).await,
+            }
+        };
+        let details: serde_json::Value = serde_json::from_slice(&bytes).expect("error.json is JSON");
+        assert_eq!(details["error_code"], "QPU_CALIBRATION_FAILED");
+        assert_eq!(details["failed_stage"], "compile");
+    }
+
+    #[tokio::test]
+    async fn injected_run_failure_fails_the_job_without_retrying() {
+        let pipeline = PipelineConfig {
+            fail_stage: Some(SimulatedStage::Run),
+            fail_code: "QPU_READOUT_FAILED".to_string(),
+            ..PipelineConfig::default()
+        };
+        let (svc, runtime) = make_service_with_pipeline(&test_qfs_root("sim-run-failure"), pipeline);
+        let job_id = svc
+            .enqueue_job(Request::new(make_request("sim-run-failure")))
+            .await
+            .expect("enqueue should succeed")
+            .into_inner()
+            .job_id;
+
+        let job = wait_for_terminal(runtime.clone(), &job_id).await;
+        assert_eq!(job.state, TaskState::Error);
+        assert_eq!(job.error_code.as_deref(), Some("QPU_READOUT_FAILED"));
+        assert!(job.history.iter().any(|entry| entry.to == TaskState::Running), "failed while running");
+        let results = svc
+            .get_job_results(Request::new(GetJobResultsRequest { job_id: job_id.clone(), ..Default::default() }))
+            .await
+            .expect("results")
+            .into_inner();
+        assert_eq!(results.error_code, "QPU_READOUT_FAILED");
+    }
+
+    #[tokio::test]
+    async fn stalled_run_can_be_cancelled_while_running() {
+        let pipeline = PipelineConfig { run_ms: 30_000, ..PipelineConfig::default() };
+        let (svc, runtime) = make_service_with_pipeline(&test_qfs_root("sim-run-stall"), pipeline);
+        let job_id = svc
+            .enqueue_job(Request::new(make_request("sim-run-stall")))
+            .await
+            .expect("enqueue should succeed")
+            .into_inner()
+            .job_id;
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+        while runtime.get(&job_id).map(|job| job.state) != Some(TaskState::Running) {
+            assert!(tokio::time::Instant::now() < deadline, "job never started running");
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        let cancel = svc.cancel_job(Request::new(make_cancel_request(&job_id))).await.expect("cancel").into_inner();
+        assert!(cancel.accepted);
+        // Far sooner than the 30s run: cancelling interrupts the stalled stage.
+        assert_eq!(wait_for_terminal(runtime.clone(), &job_id).await.state, TaskState::Cancelled);
+    }
+
+    #[tokio::test]
+    async fn simulation_overrides_need_dev_mode() {
+        let request = || EnqueueJobRequest {
+            simulation: HashMap::from([("fail_stage".to_string(), "run".to_string())]),
+            ..make_request("sim-override")
+        };
+        let (svc, _) = make_service(None);
+        let err = svc.enqueue_job(Request::new(request())).await.expect_err("dev mode is off");
+        assert_eq!(err.code(), Code::FailedPrecondition);
+
+        let runtime = Arc::new(KernelRuntimeStore {
+            pipeline: PipelineConfig { dev_mode: true, ..PipelineConfig::default() },
+            ..KernelRuntimeStore::default()
+        });
+        let svc = KernelGatewaySvc::new(runtime.clone(), Arc::new(FixtureAdapters::new(test_qfs_root("sim-override"), None)));
+        let bad = EnqueueJobRequest {
+            simulation: HashMap::from([("fail_stage".to_string(), "optimize".to_string())]),
+            ..make_request("sim-bad-override")
+        };
+        let err = svc.enqueue_job(Request::new(bad)).await.expect_err("unknown stage");
+        assert_eq!(err.code(), Code::InvalidArgument);
+
+        let job_id = svc.enqueue_job(Request::new(request())).await.expect("enqueue").into_inner().job_id;
+        wait_for_terminal(runtime.clone(), &job_id).await;
+        let job = runtime.get(&job_id).expect("job");
+        assert_eq!((job.state, job.error_code.as_deref()), (TaskState::Error, Some("EIGEN_SIM_ERROR")));
+    }
+
+    fn make_retry_service(script: Vec<ExecuteScriptStep>) -> (KernelGatewaySvc, Arc<KernelRuntimeStore>) {
+        let runtime = Arc::new(KernelRuntimeStore::default());
+        let adapters = Arc::new(FixtureAdapters::with_execute_script(
+            test_qfs_root("retry"),
+            None,
+            script,
+        ));
+        let svc = KernelGatewaySvc::new(runtime.clone(), adapters);
+        (svc, runtime)
+    }
+
+    #[tokio::test]
+    async fn retryable_failure_succeeds_after_one_retry() {
+        let (svc, runtime) = make_retry_service(vec![
+            ExecuteScriptStep::Unavailable,
+            ExecuteScriptStep::Success,
+        ]);
+        let mut req = make_request("retryable-success");
+        req.metadata_kvs.insert("retry.max_attempts".to_string(), "3".to_string());
+        let response = svc
+            .enqueue_job(Request::new(req))
+            .await
+            .expect("enqueue should succeed")
+            .into_inner();
+        let job = wait_for_terminal(runtime, &response.job_id).await;
+        assert_eq!(job.state, TaskState::Done);
+        assert_eq!(job.retry_attempts.len(), 1);
+        assert_eq!(job.retry_success_after_retry_total, 1);
+        assert_eq!(
+            job.retry_attempts[0].reason_code,
+            "EIGEN_EXECUTION_UNAVAILABLE"
+        );
+    }
+
+    #[tokio::test]
+    async fn non_retryable_failure_does_not_retry() {
+        let (svc, runtime) = make_retry_service(vec![ExecuteScriptStep::InvalidArgument]);
+        let response = svc
+            .enqueue_job(Request::new(make_request("non-retryable")))
+            .await
+            .expect("enqueue should succeed")
+            .into_inner();
+        let job = wait_for_terminal(runtime, &response.job_id).await;
+        assert_eq!(job.state, TaskState::Error);
+        assert_eq!(job.retry_attempts.len(), 1);
+        assert_eq!(job.retry_final_reason.as_deref(), Some("NON_RETRYABLE"));
+    }
+
+    #[tokio::test]
+    async fn exhausted_retries_produce_terminal_error_state() {
+        let (svc, runtime) = make_retry_service(vec![
+            ExecuteScriptStep::Unavailable,
+            ExecuteScriptStep::Unavailable,
+            ExecuteScriptStep::Unavailable,
+        ]);
+        let mut req = make_request("exhausted");
+        req.metadata_kvs.insert("retry.max_attempts".to_string(), "2".to_string());
+        let response = svc
+            .enqueue_job(Request::new(req))
+            .await
+            .expect("enqueue should succeed")
+            .into_inner();
+        let job = wait_for_terminal(runtime, &response.job_id).await;
+        assert_eq!(job.state, TaskState::Error);
+        assert_eq!(job.retry_attempts.len(), 2);
+        assert_eq!(job.retry_final_reason.as_deref(), Some("RETRY_EXHAUSTED"));
+        assert_eq!(job.error_code.as_deref(), Some("EIGEN_EXECUTION_UNAVAILABLE"));
+    }
+
+    #[tokio::test]
+    async fn deadline_interrupted_retry_becomes_timeout() {
+        let (svc, runtime) = make_retry_service(vec![
+            ExecuteScriptStep::Unavailable,
+            ExecuteScriptStep::Success,
+        ]);
+        let mut req = make_request("deadline-interrupted");
+        if let Some(metadata) = req.metadata.as_mut() {
+            metadata.deadline = Some(ProtoDuration { seconds: 0, nanos: 10_000_000 });
+        }
+        req.metadata_kvs.insert("retry.base_delay_ms".to_string(), "100".to_string());
+        let response = svc
+            .enqueue_job(Request::new(req))
+            .await
+            .expect("enqueue should succeed")
+            .into_inner();
+        let job = wait_for_terminal(runtime, &response.job_id).await;
+        assert_eq!(job.state, TaskState::Timeout);
+        assert_eq!(job.retry_attempts.len(), 1);
+        assert_eq!(job.retry_final_reason.as_deref(), Some("DEADLINE_EXCEEDED"));
+    }
+
+    fn make_cancel_request(job_id: &str) -> CancelJobRequest {
+        CancelJobRequest {
+            metadata: Some(RequestMetadata {
+                contract_version: "1.0.0".to_string(),
+                request_id: format!("cancel-{job_id}"),
+                idempotency_key: format!("cancel-{job_id}"),
+                traceparent: "00-0123456789abcdef0123456789abcdef-0123456789abcdef-01".to_string(),
+                deadline: Some(ProtoDuration { seconds: 30, nanos: 0 }),
+                tenant_id: "tenant-a".to_string(),
+                project_id: "project-a".to_string(),
+                subject: "alice".to_string(),
+                role: "user".to_string(),
+                source_service: "system-api".to_string(),
+                trace_id: "".to_string(),
+                retry_policy: "".to_string(),
+                security_context: "".to_string(),
+                workload: Some(Default::default()),
+                ..Default::default()
+            }),
+            job_id: job_id.to_string(),
+        }
+    }
+
+    fn seed_jobs(runtime: &KernelRuntimeStore, tag: &str, count: usize) -> Vec<String> {
+        (0..count)
+            .map(|idx| {
+                let submission = NormalizedSubmission::from_request(&make_request(&format!("{tag}-{idx}")))
+                    .expect("submission should normalize");
+                runtime.create_or_get_job(submission).expect("job should be created").0.job_id
+            })
+            .collect()
+    }
+
+    fn list_request(state_filter: &str, page_token: &str, page_size: i32) -> ListJobsRequest {
+        ListJobsRequest {
+            state_filter: state_filter.to_string(),
+            page_token: page_token.to_string(),
+            page_size,
+        }
+    }
+
+    #[tokio::test]
+    async fn list_jobs_on_empty_store_returns_no_jobs() {
+        let (svc, _runtime) = make_service(None);
+        let response = svc
+            .list_jobs(Request::new(list_request("", "", 0)))
+            .await
+            .expect("list should succeed")
+            .into_inner();
+        assert!(response.jobs.is_empty());
+        assert!(response.next_page_token.is_empty());
+    }
+
+    #[tokio::test]
+    async fn list_jobs_returns_single_page_in_creation_order() {
+        let (svc, runtime) = make_service(None);
+        seed_jobs(&runtime, "single-page", 3);
+        let expected: Vec<String> = runtime.list(None).into_iter().map(|job| job.job_id).collect();
+
+        let response = svc
+            .list_jobs(Request::new(list_request("", "", 0)))
+            .await
+            .expect("list should succeed")
+            .into_inner();
+        assert_eq!(response.jobs.iter().map(|job| job.job_id.clone()).collect::<Vec<_>>(), expected);
+        assert!(response.jobs.iter().all(|job| job.state == TaskState::Pending as i32));
+        assert!(response.next_page_token.is_empty());
+    }
+
+    #[tokio::test]
+    async fn list_jobs_paginates_with_cursor_tokens() {
+        let (svc, runtime) = make_service(None);
+        seed_jobs(&runtime, "multi-page", 5);
+        let expected: Vec<String> = runtime.list(None).into_iter().map(|job| job.job_id).collect();
+
+        let mut seen = Vec::new();
+        let mut page_token = String::new();
+        let mut pages = 0;
+        loop {
+            let response = svc
+                .list_jobs(Request::new(list_request("", &page_token, 2)))
+                .await
+                .expect("list should succeed")
+                .into_inner();
+            assert!(response.jobs.len() <= 2);
+            seen.extend(response.jobs.into_iter().map(|job| job.job_id));
+            pages += 1;
+            if response.next_page_token.is_empty() {
+                break;
+            }
+            let cursor = URL_SAFE_NO_PAD.decode(&response.next_page_token).expect("base64 cursor");
+            let cursor = String::from_utf8(cursor).expect("utf-8 cursor");
+            assert!(cursor.ends_with(&format!(":{}", seen.last().expect("a full page"))), "{cursor}");
+            page_token = response.next_page_token;
+        }
+        assert_eq!(pages, 3);
+        assert_eq!(seen, expected);
+
+        let no_separator = URL_SAFE_NO_PAD.encode("job-without-created-at");
+        for token in ["not a token!", no_separator.as_str()] {
+            let err = svc
+                .list_jobs(Request::new(list_request("", token, 2)))
+                .await
+                .expect_err("malformed cursor must be rejected");
+            assert_eq!(err.code(), Code::InvalidArgument);
+        }
+    }
+
+    #[tokio::test]
+    async fn list_jobs_filters_by_state() {
+        let (svc, runtime) = make_service(None);
+        let job_ids = seed_jobs(&runtime, "state-filter", 3);
+        runtime.set_state(&job_ids[1], TaskState::Running).expect("set state");
+
+        for filter in ["RUNNING", "task_state_running"] {
+            let response = svc
+                .list_jobs(Request::new(list_request(filter, "", 0)))
+                .await
+                .expect("list should succeed")
+                .into_inner();
+            assert_eq!(response.jobs.len(), 1);
+            assert_eq!(response.jobs[0].job_id, job_ids[1]);
+            assert_eq!(response.jobs[0].state, TaskState::Running as i32);
+        }
+
+        let pending = svc
+            .list_jobs(Request::new(list_request("PENDING", "", 0)))
+            .await
+            .expect("list should succeed")
+            .into_inner();
+        assert_eq!(pending.jobs.len(), 2);
+
+        let err = svc
+            .list_jobs(Request::new(list_request("SLEEPING", "", 0)))
+            .await
+            .expect_err("unknown state must be rejected");
+        assert_eq!(err.code(), Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn cancellation_while_queued_releases_reservation() {
+        let (svc, runtime) = make_service_with_hold(None, Some(DagStageKind::Schedule), Duration::from_millis(80));
+        let response = svc
+            .enqueue_job(Request::new(make_request("queued-cancel")))
+            .await
+            .expect("enqueue should succeed")
+            .into_inner();
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        let _ = svc.cancel_job(Request::new(make_cancel_request(&response.job_id))).await;
+        let job = wait_for_terminal(runtime, &response.job_id).await;
+        assert_eq!(job.state, TaskState::Cancelled);
+        assert_eq!(job.reservation_state.as_deref(), Some("released"));
+    }
+
+    #[test]
+    fn stale_reservation_is_swept_and_can_be_reacquired_with_same_token() {
+        let runtime = KernelRuntimeStore::default();
+        let submission = fixture_submission_with_lease_ms(1);
+        let (job, _) = runtime.create_or_get_job(submission.clone()).expect("job");
+        assert_eq!(job.reservation_state.as_deref(), Some("held"));
+
+        {
+            let mut jobs = runtime.jobs.write();
+            let record = jobs.get_mut(&job.job_id).expect("job");
+            record.updated_at = timestamp_from_ms(timestamp_to_ms(&ts_now()) - 10_000);
+        }
+
+        let released = runtime.sweep_stale_reservations();
+        assert_eq!(released, vec![job.job_id.clone()]);
+        let reacquired = runtime.acquire_live_reservation(&job.job_id).expect("reacquire");
+        assert_eq!(reacquired.reservation_state.as_deref(), Some("held"));
+        assert_eq!(reacquired.reservation_token, job.reservation_token);
+    }
+
+    #[test]
+    fn duplicate_live_reservation_is_rejected_while_active() {
+        let runtime = KernelRuntimeStore::default();
+        let submission = fixture_submission_with_lease_ms(60_000);
+        let (job, _) = runtime.create_or_get_job(submission).expect("job");
+
+        let err = runtime.acquire_live_reservation(&job.job_id).expect_err("duplicate");
+        assert_eq!(err.code(), Code::FailedPrecondition);
+    }
+
+    #[test]
+    fn reservation_replay_token_is_deterministic_for_same_submission() {
+        let runtime = KernelRuntimeStore::default();
+        let submission = fixture_submission_with_lease_ms(60_000);
+        let (job_a, _) = runtime.create_or_get_job(submission.clone()).expect("job a");
+        let snapshot_a = runtime.get(&job_a.job_id).unwrap().snapshot_digest();
+
+        let runtime2 = KernelRuntimeStore::default();
+        let (job_b, _) = runtime2.create_or_get_job(submission).expect("job b");
+        let snapshot_b = runtime2.get(&job_b.job_id).unwrap().snapshot_digest();
+
+        assert_eq!(job_a.reservation_token, job_b.reservation_token);
+        assert_eq!(snapshot_a, snapshot_b);
+    }
+
+    fn fixture_submission_with_lease_ms(lease_ms: u64) -> NormalizedSubmission {
+        let mut metadata = BTreeMap::new();
+        metadata.insert("contract_version".to_string(), "1.0.0".to_string());
+        metadata.insert("request_id".to_string(), "req-live-ownership".to_string());
+        metadata.insert("traceparent".to_string(), "00-11111111111111111111111111111111-2222222222222222-01".to_string());
+        metadata.insert("tenant_id".to_string(), "tenant-a".to_string());
+        metadata.insert("project_id".to_string(), "project-a".to_string());
+        metadata.insert("reservation.lease_ms".to_string(), lease_ms.to_string());
+
+        let mut metadata_kvs = HashMap::new();
+        metadata_kvs.insert("reservation.lease_ms".to_string(), lease_ms.to_string());
+
+        let req = EnqueueJobRequest {
+            name: "reservation-test".to_string(),
+            program: b"@quantum\ndef main(): pass".to_vec(),
+            program_format: "eigen_lang_source".to_string(),
+            target: "sim:local".to_string(),
+            priority: 50,
+            compiler_options: HashMap::new(),
+            metadata_kvs,
+            job_yaml: String::new(),
+            shots: 0,
+            compile_only: false,
+            simulation: HashMap::new(),
+            metadata: Some(RequestMetadata {
+                contract_version: "1.0.0".to_string(),
+                request_id: "req-live-ownership".to_string(),
+                idempotency_key: "".to_string(),
+                traceparent: "00-11111111111111111111111111111111-2222222222222222-01".to_string(),
+                deadline: None,
+                tenant_id: "tenant-a".to_string(),
+                project_id: "project-a".to_string(),
+                subject: "user".to_string(),
+                role: "user".to_string(),
+                source_service: "system-api".to_string(),
+                trace_id: "".to_string(),
+                retry_policy: "".to_string(),
+                security_context: "".to_string(),
+                workload: Some(Default::default()),
+            }),
+        };
+        NormalizedSubmission::from_request(&req).expect("submission")
+    }
+
+    #[test]
+    fn compiler_request_metadata_uses_safe_defaults_and_submission_context() {
+        let submission = fixture_submission_with_lease_ms(45_000);
+        let metadata = compiler_request_metadata_for_submission(&submission);
+
+        assert_eq!(metadata.contract_version, "1.0.0");
+        assert_eq!(metadata.request_id, "req-live-ownership");
+        assert_eq!(metadata.idempotency_key, "req-live-ownership");
+        assert_eq!(metadata.traceparent, "00-11111111111111111111111111111111-2222222222222222-01");
+        assert_eq!(metadata.deadline.as_ref().map(|d| d.seconds), Some(45));
+        assert_eq!(metadata.retry_policy, "retry-none");
+        assert_eq!(metadata.security_context, "mTLS");
+        assert_eq!(metadata.tenant_id, "tenant-a");
+        assert_eq!(metadata.project_id, "project-a");
+        assert!(metadata.workload.is_some());
+    }
+
+    #[test]
+    fn optimizer_gateway_request_uses_bounded_defaults_and_canonical_graph_json() {
+        let gateway = GrpcOptimizerGateway {
+            endpoint: "http://127.0.0.1:50052".to_string(),
+        };
+        let submission = fixture_submission_with_lease_ms(60_000);
+        let mut compile_output = BTreeMap::new();
+        compile_output.insert("compile_digest".to_string(), "compile-digest-0001".to_string());
+        compile_output.insert(
+            "compiled_artifact_ref".to_string(),
+            "qfs://jobs/job-1/compiled/circuit.aqo.json".to_string(),
+        );
+
+        let request = gateway.build_request(&submission, &compile_output);
+        assert_eq!(request.candidate_budget, 1);
+        assert_eq!(request.timeout_ms, 100);
+        assert_eq!(request.trace_context.get("request_id"), Some(&submission.request_id));
+        assert_eq!(request.trace_context.get("traceparent"), Some(&submission.traceparent));
+
+        let graph_encoding = request.graph_encoding.expect("graph encoding");
+        let graph_json: serde_json::Value = serde_json::from_str(&graph_encoding.canonical_graph_json)
+            .expect("canonical graph json");
+        assert_eq!(graph_json["canonical_graph_version"], "aqo-graph-v1");
+        assert_eq!(graph_json["compile_digest"], "compile-digest-0001");
+        assert_eq!(
+            graph_json["compiled_artifact_ref"],
+            "qfs://jobs/job-1/compiled/circuit.aqo.json"
+        );
+        assert_eq!(
+            graph_encoding.canonical_sha256,
+            hash_bytes_hex(graph_encoding.canonical_graph_json.as_bytes())
+        );
+    }
+
+    #[tokio::test]
+    async fn cancellation_while_compiling_is_deterministic() {
+        let (svc, runtime) = make_service_with_hold(None, Some(DagStageKind::Compile), Duration::from_millis(80));
+        let response = svc
+            .enqueue_job(Request::new(make_request("compile-cancel")))
+            .await
+            .expect("enqueue should succeed")
+            .into_inner();
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        let _ = svc.cancel_job(Request::new(make_cancel_request(&response.job_id))).await;
+        let job = wait_for_terminal(runtime, &response.job_id).await;
+        assert_eq!(job.state, TaskState::Cancelled);
+    }
+
+    #[tokio::test]
+    async fn cancellation_during_compile_stops_pipeline_before_queued() {
+        let (svc, runtime) = make_service_with_hold(None, Some(DagStageKind::Compile), Duration::from_secs(30));
+        let response = svc
+            .enqueue_job(Request::new(make_request("compile-cancel-stop")))
+            .await
+            .expect("enqueue should succeed")
+            .into_inner();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        let cancel = svc
+            .cancel_job(Request::new(make_cancel_request(&response.job_id)))
+            .await
+            .expect("cancel should succeed")
+            .into_inner();
+        assert!(cancel.accepted);
+        assert_eq!(cancel.reason_code, "CANCELLED");
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let job = runtime.get(&response.job_id).expect("job should exist");
+        assert_eq!(job.state, TaskState::Cancelled);
+        assert!(job.stage_records.iter().all(|record| record.state_after != TaskState::Queued));
+        assert!(job.stage_records.iter().all(|record| record.stage_key != DagStageKind::Schedule.key()));
+    }
+
+    #[tokio::test]
+    async fn shutdown_drain_interrupts_a_compiling_job_and_refuses_new_ones() {
+        let (svc, runtime) = make_service_with_hold(None, Some(DagStageKind::Compile), Duration::from_secs(30));
+        let response = svc
+            .enqueue_job(Request::new(make_request("shutdown-compile")))
+            .await
+            .expect("enqueue should succeed")
+            .into_inner();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(runtime.get(&response.job_id).expect("job should exist").state, TaskState::Compiling);
+
+        let interrupted = runtime.drain(Duration::from_millis(50)).await;
+        assert_eq!(interrupted, std::slice::from_ref(&response.job_id));
+        let job = runtime.get(&response.job_id).expect("job should exist");
+        assert_eq!(job.state, TaskState::Error);
+        assert_eq!(job.error_code.as_deref(), Some(SHUTDOWN_INTERRUPTED));
+        assert_eq!(job.failed_at_stage, Some(DagStageKind::Compile));
+
+        let err = svc
+            .enqueue_job(Request::new(make_request("shutdown-late")))
+            .await
+            .expect_err("enqueue during shutdown should fail");
+        assert_eq!(err.code(), Code::Unavailable);
+    }
+
+    #[tokio::test]
+    async fn restarted_kernel_restores_finished_jobs_and_fails_unfinished_ones() {
+        let qfs_root = test_qfs_root("restart");
+        let open_job_meta =
+            || Some(PersistentJobStore::open(Arc::new(CircuitFsLocal::new(&qfs_root))).expect("open job meta"));
+        let runtime = Arc::new(KernelRuntimeStore { job_meta: open_job_meta(), ..KernelRuntimeStore::default() });
+        let svc = KernelGatewaySvc::new(runtime.clone(), Arc::new(FixtureAdapters::new(&qfs_root, None)));
+        // Without an explicit idempotency key the job id is derived from the
+        // submission, so a replay after the restart lands on the same job.
+        let mut finished_request = make_request("restart-finished");
+        finished_request.metadata.as_mut().expect("metadata").idempotency_key.clear();
+        let finished = svc
+            .enqueue_job(Request::new(finished_request.clone()))
+            .await
+            .expect("enqueue should succeed")
+            .into_inner();
+        assert_eq!(wait_for_terminal(runtime.clone(), &finished.job_id).await.state, TaskState::Done);
+
+        let held = KernelGatewaySvc::new(
+            runtime.clone(),
+            Arc::new(FixtureAdapters::with_hold(&qfs_root, None, Some(DagStageKind::Compile), Duration::from_secs(30))),
+        );
+        let unfinished = held
+            .enqueue_job(Request::new(make_request("restart-unfinished")))
+            .await
+            .expect("enqueue should succeed")
+            .into_inner();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        runtime.persist_jobs();
+
+        let restarted = Arc::new(KernelRuntimeStore { job_meta: open_job_meta(), ..KernelRuntimeStore::default() });
+        assert_eq!(restarted.restore_jobs(), 2);
+        let svc = KernelGatewaySvc::new(restarted.clone(), Arc::new(FixtureAdapters::new(&qfs_root, None)));
+
+        let status = svc
+            .get_job_status(Request::new(make_status_request(&finished.job_id)))
+            .await
+            .expect("restored job should have a status")
+            .into_inner();
+        assert_eq!(status.state, TaskState::Done as i32);
+        let job = restarted.get(&finished.job_id).expect("finished job restored");
+        assert_eq!(job.counts, runtime.get(&finished.job_id).expect("finished job").counts);
+
+        let job = restarted.get(&unfinished.job_id).expect("unfinished job restored");
+        assert_eq!(job.state, TaskState::Error);
+        assert_eq!(job.error_code.as_deref(), Some(SHUTDOWN_INTERRUPTED));
+
+        let replay = svc
+            .enqueue_job(Request::new(finished_request))
+            .await
+            .expect("replay should succeed")
+            .into_inner();
+        assert_eq!(replay.job_id, finished.job_id);
+
+        // The restart outcome is itself persisted.
+        let reopened = open_job_meta().expect("job meta");
+        assert_eq!(reopened.get(&unfinished.job_id).expect("unfinished record").state, JobState::Error);
+    }
+
+    #[tokio::test]
+    async fn shutdown_drain_waits_for_running_pipelines() {
+        let (svc, runtime) = make_service_with_hold(None, Some(DagStageKind::Compile), Duration::from_millis(80));
+        let response = svc
+            .enqueue_job(Request::new(make_request("shutdown-drained")))
+            .await
+            .expect("enqueue should succeed")
+            .into_inner();
+
+        assert!(runtime.drain(Duration::from_secs(10)).await.is_empty());
+        assert_eq!(runtime.get(&response.job_id).expect("job should exist").state, TaskState::Done);
+    }
+
+    #[tokio::test]
+    async fn server_shutdown_refuses_new_jobs_and_lets_in_flight_calls_finish() {
+        use crate::proto::kernel_gateway_service_client::KernelGatewayServiceClient;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.expect("bind kernel port");
+        let addr = listener.local_addr().expect("kernel addr");
+        let runtime = Arc::new(KernelRuntimeStore::default());
+        let adapters = FixtureAdapters::with_hold(
+            test_qfs_root("server-shutdown"),
+            None,
+            Some(DagStageKind::Compile),
+            Duration::from_millis(300),
+        );
+        let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
+        let shutdown = async {
+            let _ = shutdown_rx.await;
+        };
+        let server = tokio::spawn(async move {
+            let grace = Duration::from_secs(10);
+            serve_runtime(listener, None, AuthInterceptor::disabled(), None, runtime, adapters, shutdown, grace)
+                .await
+                .map_err(|err| err.to_string())
+        });
+
+        let mut client = KernelGatewayServiceClient::connect(format!("http://{addr}")).await.expect("connect kernel");
+        let job_id = client
+            .enqueue_job(make_request("server-shutdown"))
+            .await
+            .expect("enqueue should succeed")
+            .into_inner()
+            .job_id;
+        let mut updates = client.watch_job_status(make_watch_request(&job_id)).await.expect("watch").into_inner();
+
+        shutdown_tx.send(()).expect("signal shutdown");
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let err = client
+            .enqueue_job(make_request("server-shutdown-late"))
+            .await
+            .expect_err("enqueue during shutdown should fail");
+        assert_eq!(err.code(), Code::Unavailable);
+
+        let mut last_state = None;
+        while let Some(frame) = updates.message().await.expect("watch frame") {
+            last_state = Some(frame.state);
+        }
+        assert_eq!(last_state, Some(TaskState::Done as i32));
+
+        drop(client);
+        tokio::time::timeout(Duration::from_secs(5), server)
+            .await
+            .expect("server stops after shutdown")
+            .expect("server task")
+            .expect("server exits cleanly");
+    }
+
+    #[tokio::test]
+    async fn enqueue_with_a_known_idempotency_key_returns_the_existing_job() {
+        let (svc, runtime) = make_service(None);
+        let first = svc
+            .enqueue_job(Request::new(make_request("idem-retry")))
+            .await
+            .expect("enqueue should succeed")
+            .into_inner();
+
+        // A retry after a timeout carries a new request id and traceparent.
+        let mut retry = make_request("idem-retry");
+        let metadata = retry.metadata.as_mut().expect("metadata");
+        metadata.request_id = "req-idem-retry-2".to_string();
+        metadata.traceparent = "00-fedcba9876543210fedcba9876543210-0123456789abcdef-01".to_string();
+        let second = svc.enqueue_job(Request::new(retry)).await.expect("retry should succeed").into_inner();
+        assert_eq!(second.job_id, first.job_id);
+        assert_eq!(second.created_at, first.created_at);
+
+        let other = svc
+            .enqueue_job(Request::new(make_request("idem-other")))
+            .await
+            .expect("enqueue should succeed")
+            .into_inner();
+        assert_ne!(other.job_id, first.job_id);
+        assert_eq!(runtime.jobs.read().len(), 2);
+        assert_eq!(runtime.metrics.jobs_enqueued(), 2);
+    }
+
+    #[tokio::test]
+    async fn reusing_an_idempotency_key_for_another_payload_is_already_exists() {
+        let (svc, runtime) = make_service(None);
+        let first = svc
+            .enqueue_job(Request::new(make_request("idem-conflict")))
+            .await
+            .expect("enqueue should succeed")
+            .into_inner();
+
+        let mut conflicting = make_request("idem-conflict");
+        conflicting.name = "idem-conflict-renamed".to_string();
+        let err = svc
+            .enqueue_job(Request::new(conflicting))
+            .await
+            .expect_err("key reuse with another name should fail");
+        assert_eq!(err.code(), Code::AlreadyExists);
+        assert!(err.message().contains(&first.job_id), "{}", err.message());
+        assert_eq!(runtime.jobs.read().len(), 1);
+    }
+
+    #[test]
+    fn idempotency_keys_expire_after_the_ttl() {
+        let index = IdempotencyIndex::new(Duration::ZERO);
+        let submission = NormalizedSubmission::from_request(&make_request("idem-expired")).expect("submission");
+        index.record(&submission);
+        assert_eq!(index.lookup(&submission).expect("lookup"), None);
+
+        let index = IdempotencyIndex::default();
+        index.record(&submission);
+        assert_eq!(index.lookup(&submission).expect("lookup"), Some(submission.job_id.clone()));
+    }
+
+    #[tokio::test]
+    async fn cancellation_after_done_is_not_accepted() {
+        let (svc, runtime) = make_service(None);
+        let response = svc
+            .enqueue_job(Request::new(make_request("done-cancel")))
+            .await
+            .expect("enqueue should succeed")
+            .into_inner();
+        let job = wait_for_terminal(runtime.clone(), &response.job_id).await;
+        assert_eq!(job.state, TaskState::Done);
+
+        let cancel = svc
+            .cancel_job(Request::new(make_cancel_request(&response.job_id)))
+            .await
+            .expect("cancel should succeed")
+            .into_inner();
+        assert!(!cancel.accepted);
+        assert_eq!(cancel.reason_code, "ALREADY_TERMINAL");
+        assert_eq!(runtime.get(&response.job_id).expect("job").state, TaskState::Done);
+    }
+
+    fn make_history_request(job_id: &str) -> GetJobHistoryRequest {
+        GetJobHistoryRequest {
+            metadata: None,
+            job_id: job_id.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn job_history_lists_success_path_transitions_in_order() {
+        let (svc, runtime) = make_service(None);
+        let response = svc
+            .enqueue_job(Request::new(make_request("history-success")))
+            .await
+            .expect("enqueue should succeed")
+            .into_inner();
+        wait_for_terminal(runtime, &response.job_id).await;
+
+        let history = svc
+            .get_job_history(Request::new(make_history_request(&response.job_id)))
+            .await
+            .expect("history should succeed")
+            .into_inner();
+        assert_eq!(history.job_id, response.job_id);
+        let states: Vec<(i32, i32, &str)> = history
+            .transitions
+            .iter()
+            .map(|entry| (entry.from_state, entry.to_state, entry.event.as_str()))
+            .collect();
+        assert_eq!(
+            states,
+            vec![
+                (TaskState::Unspecified as i32, TaskState::Pending as i32, "enqueue"),
+                (TaskState::Pending as i32, TaskState::Compiling as i32, "advance"),
+                (TaskState::Compiling as i32, TaskState::Optimizing as i32, "advance"),
+                (TaskState::Optimizing as i32, TaskState::Queued as i32, "advance"),
+                (TaskState::Queued as i32, TaskState::Running as i32, "advance"),
+                (TaskState::Running as i32, TaskState::Done as i32, "complete"),
+            ]
+        );
+        let timestamps: Vec<i128> = history
+            .transitions
+            .iter()
+            .map(|entry| timestamp_to_ms(entry.at.as_ref().expect("timestamp")))
+            .collect();
+        assert!(timestamps.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert!(history.transitions.iter().all(|entry| entry.reason.is_empty()));
+    }
+
+    #[tokio::test]
+    async fn job_history_records_cancel_reason() {
+        let (svc, runtime) = make_service_with_hold(None, Some(DagStageKind::Compile), Duration::from_secs(30));
+        let response = svc
+            .enqueue_job(Request::new(make_request("history-cancel")))
+            .await
+            .expect("enqueue should succeed")
+            .into_inner();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        svc.cancel_job(Request::new(make_cancel_request(&response.job_id)))
+            .await
+            .expect("cancel should succeed");
+        wait_for_terminal(runtime, &response.job_id).await;
+
+        let history = svc
+            .get_job_history(Request::new(make_history_request(&response.job_id)))
+            .await
+            .expect("history should succeed")
+            .into_inner();
+        let last = history.transitions.last().expect("transitions");
+        assert_eq!(last.to_state, TaskState::Cancelled as i32);
+        assert_eq!(last.event, "cancel");
+        assert_eq!(last.reason, "requested by alice");
+    }
+
+    #[tokio::test]
+    async fn error_status_always_carries_an_error_code() {
+        let (svc, _runtime) = make_service(Some(DagStageKind::Compile));
+        let response = svc
+            .enqueue_job(Request::new(make_request("error-code-invariant")))
+            .await
+            .expect("enqueue should succeed")
+            .into_inner();
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+        loop {
+            let status = svc
+                .get_job_status(Request::new(make_status_request(&response.job_id)))
+                .await
+                .expect("status should succeed")
+                .into_inner();
+            if status.state == TaskState::Error as i32 {
+                assert!(!status.error_code.is_empty(), "ERROR status without an error code");
+                assert!(!status.error_summary.is_empty());
+                break;
+            }
+            assert!(tokio::time::Instant::now() < deadline, "job did not fail");
+            tokio::task::yield_now().await;
+        }
+    }
+
+    #[tokio::test]
+    async fn job_history_of_unknown_job_is_not_found() {
+        let (svc, _runtime) = make_service(None);
+        let err = svc
+            .get_job_history(Request::new(make_history_request("missing-job")))
+            .await
+            .expect_err("history should fail");
+        assert_eq!(err.code(), Code::NotFound);
+    }
+
+    fn make_watch_request(job_id: &str) -> WatchJobStatusRequest {
+        WatchJobStatusRequest {
+            metadata: None,
+            job_id: job_id.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn watch_job_status_streams_every_transition_until_terminal() {
+        let (svc, _runtime) = make_service_with_hold(None, Some(DagStageKind::Compile), Duration::from_millis(80));
+        let response = svc
+            .enqueue_job(Request::new(make_request("watch-success")))
+            .await
+            .expect("enqueue should succeed")
+            .into_inner();
+
+        let stream = svc
+            .watch_job_status(Request::new(make_watch_request(&response.job_id)))
+            .await
+            .expect("watch should succeed")
+            .into_inner();
+        let frames: Vec<GetJobStatusResponse> = tokio::time::timeout(Duration::from_secs(5), stream.collect::<Vec<_>>())
+            .await
+            .expect("stream should close once the job is terminal")
+            .into_iter()
+            .map(|frame| frame.expect("frame should be ok"))
+            .collect();
+
+        let states: Vec<i32> = frames.iter().map(|frame| frame.state).collect();
+        let expected = [TaskState::Compiling, TaskState::Queued, TaskState::Running, TaskState::Done];
+        let positions: Vec<usize> = expected
+            .iter()
+            .map(|state| {
+                states
+                    .iter()
+                    .position(|observed| *observed == *state as i32)
+                    .unwrap_or_else(|| panic!("missing {state:?} frame in {states:?}"))
+            })
+            .collect();
+        assert!(positions.windows(2).all(|pair| pair[0] < pair[1]), "out of order: {states:?}");
+        assert_eq!(states.last(), Some(&(TaskState::Done as i32)));
+        assert!(frames.iter().all(|frame| frame.job_id == response.job_id));
+    }
+
+    #[tokio::test]
+    async fn watch_job_status_of_terminal_job_sends_one_frame() {
+        let (svc, runtime) = make_service(None);
+        let response = svc
+            .enqueue_job(Request::new(make_request("watch-terminal")))
+            .await
+            .expect("enqueue should succeed")
+            .into_inner();
+        wait_for_terminal(runtime, &response.job_id).await;
+
+        let frames: Vec<_> = svc
+            .watch_job_status(Request::new(make_watch_request(&response.job_id)))
+            .await
+            .expect("watch should succeed")
+            .into_inner()
+            .collect()
+            .await;
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0].as_ref().expect("frame should be ok").state, TaskState::Done as i32);
+    }
+
+    #[tokio::test]
+    async fn watch_job_status_of_unknown_job_is_not_found() {
+        let (svc, _runtime) = make_service(None);
+        let err = svc
+            .watch_job_status(Request::new(make_watch_request("missing-job")))
+            .await
+            .err()
+            .expect("watch should fail");
+        assert_eq!(err.code(), Code::NotFound);
+    }
+
+    fn make_logs_request(job_id: &str, follow: bool, from_line: u64) -> GetJobLogsRequest {
+        GetJobLogsRequest {
+            metadata: None,
+            job_id: job_id.to_string(),
+            log_name: "stdout".to_string(),
+            follow,
+            from_line,
+            tail_lines: 0,
+        }
+    }
+
+    fn append_stdout_lines(svc: &KernelGatewaySvc, job_id: &str, lines: &[&str]) {
+        use std::io::Write;
+        let path = svc.adapters.job_log_path(job_id, "stdout").expect("log path");
+        fs::create_dir_all(path.parent().expect("logs dir")).expect("create logs dir");
+        let mut file = fs::OpenOptions::new().create(true).append(true).open(&path).expect("open log");
+        for line in lines {
+            writeln!(file, "{line}").expect("append log line");
+        }
+    }
+
+    #[tokio::test]
+    async fn get_job_logs_reads_a_bounded_range_of_lines() {
+        let (svc, runtime) = make_service(None);
+        let response = svc
+            .enqueue_job(Request::new(make_request("logs-bounded")))
+            .await
+            .expect("enqueue should succeed")
+            .into_inner();
+        wait_for_terminal(runtime, &response.job_id).await;
+
+        let missing: Vec<_> = svc
+            .get_job_logs(Request::new(make_logs_request(&response.job_id, false, 0)))
+            .await
+            .expect("missing log still streams")
+            .into_inner()
+            .collect()
+            .await;
+        assert!(missing.is_empty(), "{missing:?}");
+
+        append_stdout_lines(&svc, &response.job_id, &[r#"{"msg":"zero"}"#, r#"{"msg":"one"}"#, r#"{"msg":"two"}"#]);
+        let lines: Vec<JobLogLine> = svc
+            .get_job_logs(Request::new(make_logs_request(&response.job_id, false, 1)))
+            .await
+            .expect("logs should stream")
+            .into_inner()
+            .map(|line| line.expect("line should be ok"))
+            .collect()
+            .await;
+        assert_eq!(
+            lines,
+            vec![
+                JobLogLine { line_number: 1, line: r#"{"msg":"one"}"#.to_string() },
+                JobLogLine { line_number: 2, line: r#"{"msg":"two"}"#.to_string() },
+            ]
         );
-        let err = svc.enqueue_job(Request::new(conflicting)).await.expect_err("conflicting topology should fail");
+
+        let mut bad_name = make_logs_request(&response.job_id, false, 0);
+        bad_name.log_name = "../stdout".to_string();
+        let err = svc.get_job_logs(Request::new(bad_name)).await.err().expect("bad name should fail");
         assert_eq!(err.code(), Code::InvalidArgument);
-        assert!(err.message().contains("partition_ids"));
+        let err = svc
+            .get_job_logs(Request::new(make_logs_request("missing-job", false, 0)))
+            .await
+            .err()
+            .expect("unknown job should fail");
+        assert_eq!(err.code(), Code::NotFound);
     }
 
     #[tokio::test]
-    async fn submit_to_results_failure_path_marks_error_metadata() {
-        let (svc, runtime) = make_service(Some(DagStageKind::Optimize));
+    async fn get_job_logs_follow_waits_for_the_log_and_tails_appended_lines() {
+        let (svc, _runtime) = make_service_with_hold(None, Some(DagStageKind::Execute), Duration::from_millis(500));
         let response = svc
-            .enqueue_job(Request::new(make_request("failure")))
+            .enqueue_job(Request::new(make_request("logs-follow")))
             .await
             .expect("enqueue should succeed")
             .into_inner();
 
-        let job = wait_for_terminal(runtime.clone(), &response.job_id).await;
-        assert_eq!(job.state, TaskState::Error);
-        assert_eq!(job.error_code.as_deref(), Some("OPTIMIZER_STAGE_FAILED"));
-        assert_eq!(
-            job.stage_records
-                .iter()
-                .find(|stage| stage.stage_key == "optimize")
-                .and_then(|stage| stage.error_code.as_deref()),
-            Some("OPTIMIZER_STAGE_FAILED")
-        );
+        // The log does not exist yet; following waits for it instead of failing.
+        let mut stream = svc
+            .get_job_logs(Request::new(make_logs_request(&response.job_id, true, 0)))
+            .await
+            .expect("follow should start")
+            .into_inner();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        append_stdout_lines(&svc, &response.job_id, &["first"]);
+        let first = tokio::time::timeout(Duration::from_secs(5), stream.next())
+            .await
+            .expect("first line should arrive")
+            .expect("stream should stay open")
+            .expect("line should be ok");
+        assert_eq!(first, JobLogLine { line_number: 0, line: "first".to_string() });
 
-        let results = svc
-            .get_job_results(Request::new(GetJobResultsRequest {
-                metadata: Some(RequestMetadata {
-                    contract_version: "1.0.0".to_string(),
-                    request_id: format!("results-{}", response.job_id),
-                    idempotency_key: format!("results-{}", response.job_id),
-                    traceparent: "00-0123456789abcdef0123456789abcdef-0123456789abcdef-01".to_string(),
-                    deadline: Some(ProtoDuration { seconds: 30, nanos: 0 }),
-                    tenant_id: "tenant-a".to_string(),
-                    project_id: "project-a".to_string(),
-                    subject: "alice".to_string(),
-                    role: "user".to_string(),
-                    source_service: "system-api".to_string(),
-                    trace_id: "".to_string(),
-                    retry_policy: "".to_string(),
-                    security_context: "".to_string(),
-                    workload: Some(Default::default()),
-                    ..Default::default()
-                }),
-                job_id: response.job_id.clone(),
-            }))
+        append_stdout_lines(&svc, &response.job_id, &["second", "third"]);
+        let rest: Vec<String> = tokio::time::timeout(Duration::from_secs(5), stream.collect::<Vec<_>>())
             .await
-            .expect("terminal error results should succeed")
+            .expect("stream should close once the job is terminal")
+            .into_iter()
+            .map(|line| line.expect("line should be ok").line)
+            .collect();
+        assert_eq!(rest, ["second", "third"]);
+    }
+
+    async fn next_log_line(stream: &mut (impl Stream<Item = Result<JobLogLine, Status>> + Unpin)) -> JobLogLine {
+        tokio::time::timeout(Duration::from_secs(5), stream.next())
+            .await
+            .expect("line should arrive")
+            .expect("stream should stay open")
+            .expect("line should be ok")
+    }
+
+    #[tokio::test]
+    async fn get_job_logs_tail_lines_starts_at_the_last_lines_then_follows() {
+        let (svc, _runtime) = make_service_with_hold(None, Some(DagStageKind::Execute), Duration::from_millis(500));
+        let response = svc
+            .enqueue_job(Request::new(make_request("logs-tail")))
+            .await
+            .expect("enqueue should succeed")
             .into_inner();
-        assert_eq!(results.state, TaskState::Error as i32);
-        assert_eq!(results.error_code, "OPTIMIZER_STAGE_FAILED");
-        assert!(!results.error_summary.is_empty());
+        append_stdout_lines(&svc, &response.job_id, &["zero", "one", "two"]);
+
+        let mut request = make_logs_request(&response.job_id, true, 0);
+        request.tail_lines = 2;
+        let mut stream = svc.get_job_logs(Request::new(request)).await.expect("follow should start").into_inner();
+        assert_eq!(next_log_line(&mut stream).await, JobLogLine { line_number: 1, line: "one".to_string() });
+        assert_eq!(next_log_line(&mut stream).await, JobLogLine { line_number: 2, line: "two".to_string() });
+        append_stdout_lines(&svc, &response.job_id, &["three"]);
+        assert_eq!(next_log_line(&mut stream).await, JobLogLine { line_number: 3, line: "three".to_string() });
+        let rest: Vec<_> = tokio::time::timeout(Duration::from_secs(5), stream.collect::<Vec<_>>())
+            .await
+            .expect("stream should close once the job is terminal");
+        assert!(rest.is_empty(), "{rest:?}");
     }
 
-    fn make_retry_service(script: Vec<ExecuteScriptStep>) -> (KernelGatewaySvc, Arc<KernelRuntimeStore>) {
+    #[tokio::test]
+    async fn health_service_reports_serving_until_graceful_shutdown() {
+        use tonic_health::pb::health_check_response::ServingStatus;
+        use tonic_health::pb::health_client::HealthClient;
+        use tonic_health::pb::HealthCheckRequest;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.expect("bind kernel port");
+        let addr = listener.local_addr().expect("kernel addr");
+        let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
+        let server = tokio::spawn(async move {
+            serve_with_shutdown(listener, None, AuthInterceptor::disabled(), None, async {
+                let _ = shutdown_rx.await;
+            })
+            .await
+            .map_err(|err| err.to_string())
+        });
+
+        let channel = tonic::transport::Channel::from_shared(format!("http://{addr}")).expect("kernel uri");
+        let mut client = HealthClient::new(channel.connect().await.expect("connect health client"));
+        let check = |service: &str| HealthCheckRequest { service: service.to_string() };
+        for service in ["", crate::proto::kernel_gateway_service_server::SERVICE_NAME, health::QFS_COMPONENT] {
+            let response = client.check(check(service)).await.expect("health check").into_inner();
+            assert_eq!(response.status, ServingStatus::Serving as i32, "{service:?}");
+        }
+
+        let mut updates = client.watch(check("")).await.expect("health watch").into_inner();
+        let first = updates.message().await.expect("watch update").expect("initial status");
+        assert_eq!(first.status, ServingStatus::Serving as i32);
+        shutdown_tx.send(()).expect("signal shutdown");
+        let next = updates.message().await.expect("watch update").expect("shutdown status");
+        assert_eq!(next.status, ServingStatus::NotServing as i32);
+
+        drop(updates);
+        drop(client);
+        tokio::time::timeout(Duration::from_secs(5), server)
+            .await
+            .expect("server stops after shutdown")
+            .expect("server task")
+            .expect("server exits cleanly");
+    }
+
+    #[tokio::test]
+    async fn reflection_lists_the_kernel_gateway_service() {
+        use tonic_reflection::pb::v1::server_reflection_client::ServerReflectionClient;
+        use tonic_reflection::pb::v1::server_reflection_request::MessageRequest;
+        use tonic_reflection::pb::v1::server_reflection_response::MessageResponse;
+        use tonic_reflection::pb::v1::ServerReflectionRequest;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.expect("bind kernel port");
+        let addr = listener.local_addr().expect("kernel addr");
+        tokio::spawn(async move {
+            serve_with_shutdown(listener, None, AuthInterceptor::disabled(), None, std::future::pending())
+                .await
+                .map_err(|err| err.to_string())
+        });
+
+        let channel = tonic::transport::Channel::from_shared(format!("http://{addr}")).expect("kernel uri");
+        let mut client = ServerReflectionClient::new(channel.connect().await.expect("connect reflection"));
+        let list_services = ServerReflectionRequest {
+            host: String::new(),
+            message_request: Some(MessageRequest::ListServices(String::new())),
+        };
+        let mut responses = client
+            .server_reflection_info(tokio_stream::once(list_services))
+            .await
+            .expect("reflection call")
+            .into_inner();
+        let response = responses.message().await.expect("reflection response").expect("one response");
+        let Some(MessageResponse::ListServicesResponse(listed)) = response.message_response else {
+            panic!("unexpected reflection response: {:?}", response.message_response);
+        };
+        let services: Vec<&str> = listed.service.iter().map(|service| service.name.as_str()).collect();
+        assert!(services.contains(&crate::proto::kernel_gateway_service_server::SERVICE_NAME), "{services:?}");
+    }
+
+    #[tokio::test]
+    async fn served_calls_echo_or_generate_the_request_id() {
+        use tonic_health::pb::health_client::HealthClient;
+        use tonic_health::pb::HealthCheckRequest;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.expect("bind kernel port");
+        let addr = listener.local_addr().expect("kernel addr");
+        tokio::spawn(async move {
+            serve_with_shutdown(listener, None, AuthInterceptor::disabled(), None, std::future::pending())
+                .await
+                .map_err(|err| err.to_string())
+        });
+
+        let channel = tonic::transport::Channel::from_shared(format!("http://{addr}")).expect("kernel uri");
+        let mut client = HealthClient::new(channel.connect().await.expect("connect health client"));
+        let mut request = Request::new(HealthCheckRequest::default());
+        request.metadata_mut().insert(REQUEST_ID_HEADER, "req-health-1".parse().expect("metadata value"));
+        let response = client.check(request).await.expect("health check");
+        let request_id = |metadata: &tonic::metadata::MetadataMap| {
+            metadata.get(REQUEST_ID_HEADER).and_then(|value| value.to_str().ok()).map(str::to_string)
+        };
+        assert_eq!(request_id(response.metadata()).as_deref(), Some("req-health-1"));
+
+        let response = client.check(HealthCheckRequest::default()).await.expect("health check");
+        let generated = request_id(response.metadata()).expect("generated request id");
+        assert!(Uuid::parse_str(&generated).is_ok(), "{generated}");
+    }
+
+    #[tokio::test]
+    async fn pipeline_and_status_events_carry_the_callers_request_id() {
+        #[derive(Clone, Default)]
+        struct CapturedLogs(Arc<std::sync::Mutex<Vec<u8>>>);
+
+        impl std::io::Write for CapturedLogs {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.0.lock().unwrap().extend_from_slice(buf);
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let logs = CapturedLogs::default();
+        let writer = logs.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(tracing::Level::DEBUG)
+            .with_ansi(false)
+            .with_writer(move || writer.clone())
+            .finish();
+        // The test runtime is single-threaded, so the spawned pipeline logs here too.
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let (svc, runtime) = make_service(None);
+        let mut request = Request::new(make_request("request-id-pipeline"));
+        request.metadata_mut().insert(REQUEST_ID_HEADER, "abc".parse().expect("metadata value"));
+        let job_id = svc.enqueue_job(request).await.expect("enqueue").into_inner().job_id;
+        wait_for_terminal(runtime.clone(), &job_id).await;
+        let mut request = Request::new(make_status_request(&job_id));
+        request.metadata_mut().insert(REQUEST_ID_HEADER, "abc".parse().expect("metadata value"));
+        svc.get_job_status(request).await.expect("status");
+
+        let logs = String::from_utf8(logs.0.lock().unwrap().clone()).expect("utf-8 logs");
+        let pipeline: Vec<&str> = logs.lines().filter(|line| line.contains("job_pipeline{")).collect();
+        assert!(!pipeline.is_empty(), "{logs}");
+        assert!(pipeline.iter().all(|line| line.contains("rpc_request_id=abc")), "{logs}");
+        let status_read = logs.lines().find(|line| line.contains("job status read")).expect("status event");
+        assert!(status_read.contains("request_id=abc") && status_read.contains(&job_id), "{status_read}");
+    }
+
+    #[tokio::test]
+    async fn metrics_endpoint_reports_job_counts_transitions_and_rpc_durations() {
+        let (svc, runtime) = make_service(None);
+        // Without an explicit idempotency key the job id is derived from the
+        // submission, so the replay below lands on the same job.
+        let mut request = make_request("metrics-success");
+        request.metadata.as_mut().expect("metadata").idempotency_key.clear();
+        let response = svc
+            .enqueue_job(Request::new(request.clone()))
+            .await
+            .expect("enqueue should succeed")
+            .into_inner();
+        // An idempotent replay must not count as a second enqueue.
+        svc.enqueue_job(Request::new(request))
+            .await
+            .expect("replay should succeed");
+        wait_for_terminal(runtime.clone(), &response.job_id).await;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.expect("bind metrics port");
+        let addr = listener.local_addr().expect("metrics addr");
+        tokio::spawn(crate::metrics::serve(listener, runtime.metrics.clone()));
+
+        let body = tokio::task::spawn_blocking(move || {
+            use std::io::{Read, Write};
+            let mut stream = std::net::TcpStream::connect(addr).expect("connect metrics endpoint");
+            stream
+                .write_all(b"GET /metrics HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+                .expect("send scrape");
+            let mut body = String::new();
+            stream.read_to_string(&mut body).expect("read scrape");
+            body
+        })
+        .await
+        .expect("scrape task");
+
+        assert!(body.starts_with("HTTP/1.1 200"), "unexpected response: {body}");
+        assert!(body.contains("eigen_jobs_enqueued_total 1\n"));
+        assert!(body.contains("eigen_jobs_submitted_total{tenant=\"tenant-a\"} 1\n"));
+        assert!(body.contains("eigen_jobs_completed_total{state=\"done\"} 1\n"));
+        assert!(body.contains("eigen_job_duration_seconds_count{state=\"done\"} 1\n"));
+        assert!(body.contains("eigen_pipeline_stage_duration_seconds_count{stage=\"compile\"} 1\n"));
+        assert!(body.contains("eigen_jobs_by_state{state=\"pending\"} 0\n"));
+        assert!(body.contains("eigen_jobs_by_state{state=\"done\"} 1\n"));
+        assert!(body.contains("eigen_job_transition_duration_seconds_count{from=\"pending\",to=\"compiling\"} 1\n"));
+    }
+
+    #[tokio::test]
+    async fn served_calls_are_timed_per_method_and_status() {
+        use crate::proto::kernel_gateway_service_client::KernelGatewayServiceClient;
+
         let runtime = Arc::new(KernelRuntimeStore::default());
-        let adapters = Arc::new(FixtureAdapters::with_execute_script(
-            test_qfs_root("retry"),
-            None,
-            script,
-        ));
-        let svc = KernelGatewaySvc::new(runtime.clone(), adapters);
-        (svc, runtime)
+        let adapters = FixtureAdapters::new(test_qfs_root("rpc-timing"), None);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.expect("bind kernel port");
+        let addr = listener.local_addr().expect("kernel addr");
+        let served = runtime.clone();
+        tokio::spawn(async move {
+            let shutdown = std::future::pending();
+            serve_runtime(listener, None, AuthInterceptor::disabled(), None, served, adapters, shutdown, Duration::ZERO)
+                .await
+                .map_err(|err| err.to_string())
+        });
+
+        let mut client = KernelGatewayServiceClient::connect(format!("http://{addr}")).await.expect("connect kernel");
+        for name in ["timing-first", "timing-second"] {
+            client.enqueue_job(make_request(name)).await.expect("enqueue");
+        }
+        let err = client.get_job_status(make_status_request("missing-job")).await.expect_err("unknown job");
+        assert_eq!(err.code(), Code::NotFound);
+
+        assert_eq!(runtime.metrics.rpc_calls("EnqueueJob", "OK"), 2);
+        assert_eq!(runtime.metrics.rpc_calls("GetJobStatus", "NOT_FOUND"), 1);
+        let text = runtime.metrics.render();
+        assert!(text.contains("eigen_rpc_duration_seconds_count{method=\"EnqueueJob\",status_code=\"OK\"} 2\n"), "{text}");
+        assert!(text.contains("eigen_rpc_duration_seconds_bucket{method=\"EnqueueJob\",status_code=\"OK\",le=\"+Inf\"} 2\n"));
+    }
+
+    fn make_retry_request(job_id: &str, reason: &str) -> RetryJobRequest {
+        RetryJobRequest {
+            metadata: None,
+            job_id: job_id.to_string(),
+            reason: reason.to_string(),
+        }
     }
 
     #[tokio::test]
-    async fn retryable_failure_succeeds_after_one_retry() {
-        let (svc, runtime) = make_retry_service(vec![
-            ExecuteScriptStep::Unavailable,
-            ExecuteScriptStep::Success,
-        ]);
-        let mut req = make_request("retryable-success");
-        req.metadata_kvs.insert("retry.max_attempts".to_string(), "3".to_string());
+    async fn retry_after_execute_failure_resumes_after_compile_and_counts_retries() {
+        let (svc, runtime) = make_service(Some(DagStageKind::Execute));
         let response = svc
-            .enqueue_job(Request::new(req))
+            .enqueue_job(Request::new(make_request("retry-execute")))
             .await
             .expect("enqueue should succeed")
             .into_inner();
+        let failed = wait_for_terminal(runtime.clone(), &response.job_id).await;
+        assert_eq!(failed.state, TaskState::Error);
+        assert_eq!(failed.failed_at_stage, Some(DagStageKind::Execute));
+        let compile_started_at = failed
+            .stage_records
+            .iter()
+            .find(|record| record.stage_key == DagStageKind::Compile.key())
+            .map(|record| record.started_at)
+            .expect("compile stage recorded");
+
+        let first = svc
+            .retry_job(Request::new(make_retry_request(&response.job_id, "flaky backend")))
+            .await
+            .expect("retry should succeed")
+            .into_inner();
+        assert!(first.accepted);
+        assert_eq!(first.retry_count, 1);
+        assert_eq!(first.state, TaskState::Optimizing as i32);
+
+        // The job is running again, so an immediate second retry is refused.
+        let second = svc
+            .retry_job(Request::new(make_retry_request(&response.job_id, "")))
+            .await
+            .expect("retry should succeed")
+            .into_inner();
+        assert!(!second.accepted);
+        assert_eq!(second.retry_count, 1);
+
+        let failed_again = wait_for_terminal(runtime.clone(), &response.job_id).await;
+        assert_eq!(failed_again.state, TaskState::Error);
+        let compile_records: Vec<&StageRecord> = failed_again
+            .stage_records
+            .iter()
+            .filter(|record| record.stage_key == DagStageKind::Compile.key())
+            .collect();
+        assert_eq!(compile_records.len(), 1);
+        assert_eq!(compile_records[0].started_at, compile_started_at);
+        assert_eq!(failed_again.metadata.get("retry.resume_stage").map(String::as_str), Some("optimize"));
+        assert_eq!(failed_again.metadata.get("retry.manual_reason").map(String::as_str), Some("flaky backend"));
+
+        let third = svc
+            .retry_job(Request::new(make_retry_request(&response.job_id, "")))
+            .await
+            .expect("retry should succeed")
+            .into_inner();
+        assert!(third.accepted);
+        assert_eq!(third.retry_count, 2);
         let job = wait_for_terminal(runtime, &response.job_id).await;
-        assert_eq!(job.state, TaskState::Done);
-        assert_eq!(job.retry_attempts.len(), 1);
-        assert_eq!(job.retry_success_after_retry_total, 1);
-        assert_eq!(
-            job.retry_attempts[0].reason_code,
-            "EIGEN_EXECUTION_UNAVAILABLE"
-        );
+        assert_eq!(job.retry_count, 2);
+        assert_eq!(job.metadata.get("retry.manual_count").map(String::as_str), Some("2"));
     }
 
     #[tokio::test]
-    async fn non_retryable_failure_does_not_retry() {
-        let (svc, runtime) = make_retry_service(vec![ExecuteScriptStep::InvalidArgument]);
+    async fn retry_after_compile_failure_restarts_from_validation() {
+        let (svc, runtime) = make_service(Some(DagStageKind::Compile));
         let response = svc
-            .enqueue_job(Request::new(make_request("non-retryable")))
+            .enqueue_job(Request::new(make_request("retry-compile")))
             .await
             .expect("enqueue should succeed")
             .into_inner();
+        let failed = wait_for_terminal(runtime.clone(), &response.job_id).await;
+        assert_eq!(failed.failed_at_stage, Some(DagStageKind::Compile));
+
+        let retry = svc
+            .retry_job(Request::new(make_retry_request(&response.job_id, "")))
+            .await
+            .expect("retry should succeed")
+            .into_inner();
+        assert!(retry.accepted);
+        assert_eq!(retry.state, TaskState::Pending as i32);
         let job = wait_for_terminal(runtime, &response.job_id).await;
         assert_eq!(job.state, TaskState::Error);
-        assert_eq!(job.retry_attempts.len(), 1);
-        assert_eq!(job.retry_final_reason.as_deref(), Some("NON_RETRYABLE"));
+        assert_eq!(job.metadata.get("retry.resume_stage").map(String::as_str), Some("validate-enqueue"));
     }
 
     #[tokio::test]
-    async fn exhausted_retries_produce_terminal_error_state() {
-        let (svc, runtime) = make_retry_service(vec![
-            ExecuteScriptStep::Unavailable,
-            ExecuteScriptStep::Unavailable,
-            ExecuteScriptStep::Unavailable,
-        ]);
-        let mut req = make_request("exhausted");
-        req.metadata_kvs.insert("retry.max_attempts".to_string(), "2".to_string());
+    async fn retry_of_done_job_is_not_accepted() {
+        let (svc, runtime) = make_service(None);
         let response = svc
-            .enqueue_job(Request::new(req))
+            .enqueue_job(Request::new(make_request("retry-done")))
             .await
             .expect("enqueue should succeed")
             .into_inner();
-        let job = wait_for_terminal(runtime, &response.job_id).await;
-        assert_eq!(job.state, TaskState::Error);
-        assert_eq!(job.retry_attempts.len(), 2);
-        assert_eq!(job.retry_final_reason.as_deref(), Some("RETRY_EXHAUSTED"));
-        assert_eq!(job.error_code.as_deref(), Some("EIGEN_EXECUTION_UNAVAILABLE"));
+        wait_for_terminal(runtime.clone(), &response.job_id).await;
+
+        let retry = svc
+            .retry_job(Request::new(make_retry_request(&response.job_id, "")))
+            .await
+            .expect("retry should succeed")
+            .into_inner();
+        assert!(!retry.accepted);
+        assert_eq!(retry.state, TaskState::Done as i32);
+        assert_eq!(retry.retry_count, 0);
+        assert_eq!(runtime.get(&response.job_id).expect("job").state, TaskState::Done);
     }
 
     #[tokio::test]
-    async fn deadline_interrupted_retry_becomes_timeout() {
-        let (svc, runtime) = make_retry_service(vec![
-            ExecuteScriptStep::Unavailable,
-            ExecuteScriptStep::Success,
-        ]);
-        let mut req = make_request("deadline-interrupted");
-        if let Some(metadata) = req.metadata.as_mut() {
-            metadata.deadline = Some(ProtoDuration { seconds: 0, nanos: 10_000_000 });
-        }
-        req.metadata_kvs.insert("retry.base_delay_ms".to_string(), "100".to_string());
+    async fn retry_of_cancelled_job_is_not_accepted() {
+        let (svc, runtime) = make_service_with_hold(None, Some(DagStageKind::Compile), Duration::from_secs(30));
         let response = svc
-            .enqueue_job(Request::new(req))
+            .enqueue_job(Request::new(make_request("retry-cancelled")))
+            .await
+            .expect("enqueue should succeed")
+            .into_inner();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        svc.cancel_job(Request::new(make_cancel_request(&response.job_id)))
+            .await
+            .expect("cancel should succeed");
+        assert_eq!(wait_for_terminal(runtime.clone(), &response.job_id).await.state, TaskState::Cancelled);
+
+        let retry = svc
+            .retry_job(Request::new(make_retry_request(&response.job_id, "")))
+            .await
+            .expect("retry should succeed")
+            .into_inner();
+        assert!(!retry.accepted);
+        assert_eq!(retry.state, TaskState::Cancelled as i32);
+        assert_eq!(runtime.get(&response.job_id).expect("job").retry_count, 0);
+    }
+
+    #[tokio::test]
+    async fn retry_of_unknown_job_is_not_found() {
+        let (svc, _runtime) = make_service(None);
+        let err = svc
+            .retry_job(Request::new(make_retry_request("missing-job", "")))
+            .await
+            .expect_err("retry should fail");
+        assert_eq!(err.code(), Code::NotFound);
+    }
+
+    #[tokio::test]
+    async fn cancellation_while_executing_is_deterministic() {
+        let (svc, runtime) = make_service_with_hold(None, Some(DagStageKind::Execute), Duration::from_millis(80));
+        let response = svc
+            .enqueue_job(Request::new(make_request("execute-cancel")))
             .await
             .expect("enqueue should succeed")
             .into_inner();
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        let _ = svc.cancel_job(Request::new(make_cancel_request(&response.job_id))).await;
         let job = wait_for_terminal(runtime, &response.job_id).await;
-        assert_eq!(job.state, TaskState::Timeout);
-        assert_eq!(job.retry_attempts.len(), 1);
-        assert_eq!(job.retry_final_reason.as_deref(), Some("DEADLINE_EXCEEDED"));
+        assert_eq!(job.state, TaskState::Cancelled);
     }
 
-    fn make_cancel_request(job_id: &str) -> CancelJobRequest {
-        CancelJobRequest {
-            metadata: Some(RequestMetadata {
-                contract_version: "1.0.0".to_string(),
-                request_id: format!("cancel-{job_id}"),
-                idempotency_key: format!("cancel-{job_id}"),
-                traceparent: "00-0123456789abcdef0123456789abcdef-0123456789abcdef-01".to_string(),
-                deadline: Some(ProtoDuration { seconds: 30, nanos: 0 }),
-                tenant_id: "tenant-a".to_string(),
-                project_id: "project-a".to_string(),
-                subject: "alice".to_string(),
-                role: "user".to_string(),
-                source_service: "system-api".to_string(),
-                trace_id: "".to_string(),
-                retry_policy: "".to_string(),
-                security_context: "".to_string(),
-                workload: Some(Default::default()),
-                ..Default::default()
-            }),
-            job_id: job_id.to_string(),
+    #[tokio::test]
+    async fn cancellation_mid_flight_is_never_overwritten_by_late_stage_writes() {
+        let (svc, runtime) = make_service_with_hold(None, Some(DagStageKind::Execute), Duration::from_millis(200));
+        let response = svc
+            .enqueue_job(Request::new(make_request("late-write-cancel")))
+            .await
+            .expect("enqueue should succeed")
+            .into_inner();
+        let job_id = response.job_id;
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+        while runtime.get(&job_id).and_then(|job| job.current_stage) != Some(DagStageKind::Execute) {
+            assert!(tokio::time::Instant::now() < deadline, "job never reached execute");
+            tokio::time::sleep(Duration::from_millis(5)).await;
         }
+
+        let cancel = svc
+            .cancel_job(Request::new(make_cancel_request(&job_id)))
+            .await
+            .expect("cancel should succeed")
+            .into_inner();
+        assert!(cancel.accepted);
+
+        // Writes from a stage that finished after the cancel are refused.
+        let late_counts = BTreeMap::from([("00".to_string(), 512_i64)]);
+        let err = runtime.set_counts(&job_id, late_counts).expect_err("counts after cancel");
+        assert_eq!(err.code(), Code::Cancelled);
+        let err = runtime.set_state(&job_id, TaskState::Done).expect_err("completion after cancel");
+        assert_eq!(err.code(), Code::Cancelled);
+
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        let job = runtime.get(&job_id).expect("job should exist");
+        assert_eq!(job.state, TaskState::Cancelled);
+        assert!(job.counts.is_empty());
+        assert!(job.history.iter().all(|entry| entry.to != TaskState::Done));
     }
 
     #[tokio::test]
-    async fn cancellation_while_queued_releases_reservation() {
-        let (svc, runtime) = make_service_with_hold(None, Some(DagStageKind::Schedule), Duration::from_millis(80));
-        let response = svc
-            .enqueue_job(Request::new(make_request("queued-cancel")))
+    async fn published_progress_is_clamped_into_the_running_range_and_shown_by_get_job_status() {
+        let (svc, runtime) = make_service_with_hold(None, Some(DagStageKind::Execute), Duration::from_millis(300));
+        let job_id = svc
+            .enqueue_job(Request::new(make_request("progress")))
             .await
             .expect("enqueue should succeed")
-            .into_inner();
-        tokio::time::sleep(Duration::from_millis(10)).await;
-        let _ = svc.cancel_job(Request::new(make_cancel_request(&response.job_id))).await;
-        let job = wait_for_terminal(runtime, &response.job_id).await;
-        assert_eq!(job.state, TaskState::Cancelled);
-        assert_eq!(job.reservation_state.as_deref(), Some("released"));
+            .into_inner()
+            .job_id;
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+        while !runtime
+            .get(&job_id)
+            .is_some_and(|job| job.state == TaskState::Running && job.current_stage == Some(DagStageKind::Execute))
+        {
+            assert!(tokio::time::Instant::now() < deadline, "job never reached execute");
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        async fn status(svc: &KernelGatewaySvc, job_id: &str) -> GetJobStatusResponse {
+            svc.get_job_status(Request::new(make_status_request(job_id))).await.expect("status").into_inner()
+        }
+
+        runtime.set_progress(&job_id, 0.75, "768 of 1024 shots".to_string()).expect("progress");
+        let running = status(&svc, &job_id).await;
+        assert_eq!(running.state, TaskState::Running as i32);
+        assert_eq!((running.progress, running.message.as_str()), (0.75, "768 of 1024 shots"));
+
+        runtime.set_progress(&job_id, 0.1, "behind".to_string()).expect("progress");
+        assert_eq!(status(&svc, &job_id).await.progress, 0.5);
+        runtime.set_progress(&job_id, 7.0, "ahead".to_string()).expect("progress");
+        assert_eq!(status(&svc, &job_id).await.progress, 1.0);
+
+        let job = wait_for_terminal(runtime.clone(), &job_id).await;
+        assert_eq!(job.state, TaskState::Done);
+        let done = status(&svc, &job_id).await;
+        runtime.set_progress(&job_id, 0.6, "late update".to_string()).expect("ignored, not refused");
+        assert_eq!(status(&svc, &job_id).await, done);
+        assert_eq!(done.progress, 1.0);
+        assert_ne!(done.message, "late update");
     }
 
-    #[test]
-    fn stale_reservation_is_swept_and_can_be_reacquired_with_same_token() {
-        let runtime = KernelRuntimeStore::default();
-        let submission = fixture_submission_with_lease_ms(1);
-        let (job, _) = runtime.create_or_get_job(submission.clone()).expect("job");
-        assert_eq!(job.reservation_state.as_deref(), Some("held"));
+    #[tokio::test]
+    async fn dispatcher_hands_the_free_slot_to_the_highest_priority_job() {
+        let dispatcher = Arc::new(ExecutionDispatcher::new(1));
+        let busy = dispatcher.acquire("job-busy", Priority::Normal, 0).await;
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let mut waiters = Vec::new();
+        for (job_id, priority, enqueued_at_ms) in [("job-low", Priority::Low, 1), ("job-high", Priority::High, 2)] {
+            let waiter_dispatcher = dispatcher.clone();
+            let order = order.clone();
+            waiters.push(tokio::spawn(async move {
+                let _slot = waiter_dispatcher.acquire(job_id, priority, enqueued_at_ms).await;
+                order.lock().push(job_id);
+            }));
+            while dispatcher.queue.lock().len() < waiters.len() {
+                tokio::task::yield_now().await;
+            }
+        }
 
-        {
-            let mut jobs = runtime.jobs.write();
-            let record = jobs.get_mut(&job.job_id).expect("job");
-            record.updated_at = timestamp_from_ms(timestamp_to_ms(&ts_now()) - 10_000);
+        drop(busy);
+        for waiter in waiters {
+            waiter.await.expect("waiter");
         }
+        assert_eq!(*order.lock(), vec!["job-high", "job-low"]);
+        assert_eq!(dispatcher.slots.in_use(), 0);
+    }
 
-        let released = runtime.sweep_stale_reservations();
-        assert_eq!(released, vec![job.job_id.clone()]);
-        let reacquired = runtime.acquire_live_reservation(&job.job_id).expect("reacquire");
-        assert_eq!(reacquired.reservation_state.as_deref(), Some("held"));
-        assert_eq!(reacquired.reservation_token, job.reservation_token);
+    #[tokio::test]
+    async fn cancelled_jobs_leave_the_dispatch_queue() {
+        let dispatcher = Arc::new(ExecutionDispatcher::new(1));
+        let _busy = dispatcher.acquire("job-busy", Priority::Normal, 0).await;
+        let waiting = dispatcher.acquire("job-waiting", Priority::High, 1);
+        assert!(tokio::time::timeout(Duration::from_millis(20), waiting).await.is_err());
+        assert!(dispatcher.queue.lock().is_empty());
+        assert_eq!(dispatcher.slots.in_use(), 1);
     }
 
-    #[test]
-    fn duplicate_live_reservation_is_rejected_while_active() {
-        let runtime = KernelRuntimeStore::default();
-        let submission = fixture_submission_with_lease_ms(60_000);
-        let (job, _) = runtime.create_or_get_job(submission).expect("job");
+    #[tokio::test]
+    async fn high_priority_job_executes_before_an_earlier_low_priority_job() {
+        let runtime = Arc::new(KernelRuntimeStore {
+            dispatcher: Arc::new(ExecutionDispatcher::new(1)),
+            ..KernelRuntimeStore::default()
+        });
+        let adapters = Arc::new(FixtureAdapters::with_hold(
+            test_qfs_root("priority"),
+            None,
+            Some(DagStageKind::Execute),
+            Duration::from_millis(300),
+        ));
+        let svc = KernelGatewaySvc::new(runtime.clone(), adapters);
 
-        let err = runtime.acquire_live_reservation(&job.job_id).expect_err("duplicate");
-        assert_eq!(err.code(), Code::FailedPrecondition);
+        let blocker = svc
+            .enqueue_job(Request::new(make_request("priority-blocker")))
+            .await
+            .expect("enqueue blocker")
+            .into_inner();
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+        while runtime.get(&blocker.job_id).map(|job| job.state) != Some(TaskState::Running) {
+            assert!(tokio::time::Instant::now() < deadline, "blocker never started executing");
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+
+        let mut job_ids = Vec::new();
+        for (name, priority) in [("priority-low", 10), ("priority-high", 90)] {
+            let mut request = make_request(name);
+            request.priority = priority;
+            let response = svc.enqueue_job(Request::new(request)).await.expect("enqueue").into_inner();
+            job_ids.push(response.job_id);
+            while runtime.dispatcher.queue.lock().len() < job_ids.len() {
+                assert!(tokio::time::Instant::now() < deadline, "{name} never queued");
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        }
+
+        let execute_started = |job: &JobRuntimeRecord| {
+            job.stage_records
+                .iter()
+                .find(|record| record.stage_key == DagStageKind::Execute.key())
+                .map(|record| timestamp_to_ms(&record.started_at))
+                .expect("execute stage")
+        };
+        let low = wait_for_terminal(runtime.clone(), &job_ids[0]).await;
+        let high = wait_for_terminal(runtime.clone(), &job_ids[1]).await;
+        wait_for_terminal(runtime, &blocker.job_id).await;
+        assert!(execute_started(&high) < execute_started(&low));
     }
 
-    #[test]
-    fn reservation_replay_token_is_deterministic_for_same_submission() {
-        let runtime = KernelRuntimeStore::default();
-        let submission = fixture_submission_with_lease_ms(60_000);
-        let (job_a, _) = runtime.create_or_get_job(submission.clone()).expect("job a");
-        let snapshot_a = runtime.get(&job_a.job_id).unwrap().snapshot_digest();
+    #[tokio::test]
+    async fn jobs_run_strictly_one_after_another_with_a_single_slot() {
+        let runtime = Arc::new(KernelRuntimeStore {
+            dispatcher: Arc::new(ExecutionDispatcher::new(1)),
+            ..KernelRuntimeStore::default()
+        });
+        let adapters = Arc::new(FixtureAdapters::with_hold(
+            test_qfs_root("single-slot"),
+            None,
+            Some(DagStageKind::Execute),
+            Duration::from_millis(150),
+        ));
+        let svc = KernelGatewaySvc::new(runtime.clone(), adapters);
 
-        let runtime2 = KernelRuntimeStore::default();
-        let (job_b, _) = runtime2.create_or_get_job(submission).expect("job b");
-        let snapshot_b = runtime2.get(&job_b.job_id).unwrap().snapshot_digest();
+        let mut job_ids = Vec::new();
+        for name in ["single-slot-first", "single-slot-second"] {
+            let response = svc.enqueue_job(Request::new(make_request(name))).await.expect("enqueue").into_inner();
+            job_ids.push(response.job_id);
+        }
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+        while runtime.dispatcher.queue.lock().is_empty() {
+            assert!(tokio::time::Instant::now() < deadline, "second job never waited for a slot");
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        assert_eq!(runtime.dispatcher.slots.in_use(), 1);
+        let waiting = runtime.dispatcher.queue.lock().peek().map(|job| job.job_id.clone()).expect("waiting job");
+        assert_eq!(runtime.get(&waiting).map(|job| job.state), Some(TaskState::Queued));
 
-        assert_eq!(job_a.reservation_token, job_b.reservation_token);
-        assert_eq!(snapshot_a, snapshot_b);
+        let execute_window = |job: &JobRuntimeRecord| {
+            let record = job
+                .stage_records
+                .iter()
+                .find(|record| record.stage_key == DagStageKind::Execute.key())
+                .expect("execute stage");
+            (timestamp_to_ms(&record.started_at), timestamp_to_ms(record.completed_at.as_ref().expect("completed")))
+        };
+        let mut windows = Vec::new();
+        for job_id in &job_ids {
+            let job = wait_for_terminal(runtime.clone(), job_id).await;
+            assert_eq!(job.state, TaskState::Done);
+            windows.push(execute_window(&job));
+        }
+        windows.sort();
+        assert!(windows[0].1 <= windows[1].0, "executions overlapped: {windows:?}");
+        assert_eq!(runtime.dispatcher.slots.in_use(), 0);
     }
 
-    fn fixture_submission_with_lease_ms(lease_ms: u64) -> NormalizedSubmission {
-        let mut metadata = BTreeMap::new();
-        metadata.insert("contract_version".to_string(), "1.0.0".to_string());
-        metadata.insert("request_id".to_string(), "req-live-ownership".to_string());
-        metadata.insert("traceparent".to_string(), "00-11111111111111111111111111111111-2222222222222222-01".to_string());
-        metadata.insert("tenant_id".to_string(), "tenant-a".to_string());
-        metadata.insert("project_id".to_string(), "project-a".to_string());
-        metadata.insert("reservation.lease_ms".to_string(), lease_ms.to_string());
+    #[tokio::test]
+    async fn cancelling_a_job_waiting_for_a_slot_takes_no_slot() {
+        let runtime = Arc::new(KernelRuntimeStore {
+            dispatcher: Arc::new(ExecutionDispatcher::new(1)),
+            ..KernelRuntimeStore::default()
+        });
+        let adapters = Arc::new(FixtureAdapters::with_hold(
+            test_qfs_root("slot-cancel"),
+            None,
+            Some(DagStageKind::Execute),
+            Duration::from_secs(1),
+        ));
+        let svc = KernelGatewaySvc::new(runtime.clone(), adapters);
 
-        let mut metadata_kvs = HashMap::new();
-        metadata_kvs.insert("reservation.lease_ms".to_string(), lease_ms.to_string());
+        let blocker = svc
+            .enqueue_job(Request::new(make_request("slot-cancel-blocker")))
+            .await
+            .expect("enqueue blocker")
+            .into_inner();
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+        while runtime.get(&blocker.job_id).map(|job| job.state) != Some(TaskState::Running) {
+            assert!(tokio::time::Instant::now() < deadline, "blocker never started executing");
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        let queued = svc
+            .enqueue_job(Request::new(make_request("slot-cancel-queued")))
+            .await
+            .expect("enqueue queued")
+            .into_inner();
+        while runtime.dispatcher.queue.lock().is_empty() {
+            assert!(tokio::time::Instant::now() < deadline, "second job never waited for a slot");
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
 
-        let req = EnqueueJobRequest {
-            name: "reservation-test".to_string(),
-            program: b"@quantum\ndef main(): pass".to_vec(),
-            program_format: "eigen_lang_source".to_string(),
-            target: "sim:local".to_string(),
-            priority: 50,
-            compiler_options: HashMap::new(),
-            metadata_kvs,
-            metadata: Some(RequestMetadata {
-                contract_version: "1.0.0".to_string(),
-                request_id: "req-live-ownership".to_string(),
-                idempotency_key: "".to_string(),
-                traceparent: "00-11111111111111111111111111111111-2222222222222222-01".to_string(),
-                deadline: None,
-                tenant_id: "tenant-a".to_string(),
-                project_id: "project-a".to_string(),
-                subject: "user".to_string(),
-                role: "user".to_string(),
-                source_service: "system-api".to_string(),
-                trace_id: "".to_string(),
-                retry_policy: "".to_string(),
-                security_context: "".to_string(),
-                workload: Some(Default::default()),
-            }),
-        };
-        NormalizedSubmission::from_request(&req).expect("submission")
+        let started = tokio::time::Instant::now();
+        let cancel = svc
+            .cancel_job(Request::new(make_cancel_request(&queued.job_id)))
+            .await
+            .expect("cancel")
+            .into_inner();
+        assert!(cancel.accepted);
+        assert!(started.elapsed() < Duration::from_millis(500), "cancel waited for the running job");
+        assert_eq!(runtime.get(&queued.job_id).map(|job| job.state), Some(TaskState::Cancelled));
+        assert!(runtime.dispatcher.queue.lock().is_empty());
+        assert_eq!(runtime.dispatcher.slots.in_use(), 1);
+
+        assert_eq!(wait_for_terminal(runtime.clone(), &blocker.job_id).await.state, TaskState::Done);
+        assert_eq!(runtime.dispatcher.slots.in_use(), 0);
     }
 
-    #[test]
-    fn compiler_request_metadata_uses_safe_defaults_and_submission_context() {
-        let submission = fixture_submission_with_lease_ms(45_000);
-        let metadata = compiler_request_metadata_for_submission(&submission);
+    /// Starts a blocker holding the only execution slot, then a second job that
+    /// waits for it in the dispatcher queue; returns both job ids.
+    async fn enqueue_behind_a_blocker(svc: &KernelGatewaySvc, runtime: &Arc<KernelRuntimeStore>, tag: &str) -> (String, String) {
+        let blocker = svc
+            .enqueue_job(Request::new(make_request(&format!("{tag}-blocker"))))
+            .await
+            .expect("enqueue blocker")
+            .into_inner();
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+        while runtime.get(&blocker.job_id).map(|job| job.state) != Some(TaskState::Running) {
+            assert!(tokio::time::Instant::now() < deadline, "blocker never started executing");
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        let queued = svc
+            .enqueue_job(Request::new(make_request(&format!("{tag}-queued"))))
+            .await
+            .expect("enqueue queued")
+            .into_inner();
+        while runtime.dispatcher.queue.lock().is_empty() {
+            assert!(tokio::time::Instant::now() < deadline, "second job never waited for a slot");
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        (blocker.job_id, queued.job_id)
+    }
 
-        assert_eq!(metadata.contract_version, "1.0.0");
-        assert_eq!(metadata.request_id, "req-live-ownership");
-        assert_eq!(metadata.idempotency_key, "req-live-ownership");
-        assert_eq!(metadata.traceparent, "00-11111111111111111111111111111111-2222222222222222-01");
-        assert_eq!(metadata.deadline.as_ref().map(|d| d.seconds), Some(45));
-        assert_eq!(metadata.retry_policy, "retry-none");
-        assert_eq!(metadata.security_context, "mTLS");
-        assert_eq!(metadata.tenant_id, "tenant-a");
-        assert_eq!(metadata.project_id, "project-a");
-        assert!(metadata.workload.is_some());
+    fn make_pause_request(job_id: &str) -> PauseJobRequest {
+        PauseJobRequest { job_id: job_id.to_string(), reason: "maintenance".to_string(), ..Default::default() }
     }
 
-    #[test]
-    fn optimizer_gateway_request_uses_bounded_defaults_and_canonical_graph_json() {
-        let gateway = GrpcOptimizerGateway {
-            endpoint: "http://127.0.0.1:50052".to_string(),
-        };
-        let submission = fixture_submission_with_lease_ms(60_000);
-        let mut compile_output = BTreeMap::new();
-        compile_output.insert("compile_digest".to_string(), "compile-digest-0001".to_string());
-        compile_output.insert(
-            "compiled_artifact_ref".to_string(),
-            "qfs://jobs/job-1/compiled/circuit.aqo.json".to_string(),
-        );
+    fn make_resume_request(job_id: &str) -> ResumeJobRequest {
+        ResumeJobRequest { job_id: job_id.to_string(), ..Default::default() }
+    }
 
-        let request = gateway.build_request(&submission, &compile_output);
-        assert_eq!(request.candidate_budget, 1);
-        assert_eq!(request.timeout_ms, 100);
-        assert_eq!(request.trace_context.get("request_id"), Some(&submission.request_id));
-        assert_eq!(request.trace_context.get("traceparent"), Some(&submission.traceparent));
+    #[tokio::test]
+    async fn paused_job_leaves_the_queue_until_resumed() {
+        let runtime = Arc::new(KernelRuntimeStore {
+            dispatcher: Arc::new(ExecutionDispatcher::new(1)),
+            ..KernelRuntimeStore::default()
+        });
+        let adapters = Arc::new(FixtureAdapters::with_hold(
+            test_qfs_root("pause"),
+            None,
+            Some(DagStageKind::Execute),
+            Duration::from_millis(150),
+        ));
+        let svc = KernelGatewaySvc::new(runtime.clone(), adapters);
+        let (blocker, queued) = enqueue_behind_a_blocker(&svc, &runtime, "pause").await;
+
+        let paused = svc.pause_job(Request::new(make_pause_request(&queued))).await.expect("pause").into_inner();
+        assert!(paused.accepted);
+        assert_eq!(paused.state, TaskState::Paused as i32);
+        assert!(runtime.dispatcher.queue.lock().is_empty());
+        let status = runtime.get(&queued).expect("paused job").status_response();
+        assert_eq!(status.stage, "paused");
+
+        // The slot frees up, but the paused job is not dispatched.
+        assert_eq!(wait_for_terminal(runtime.clone(), &blocker).await.state, TaskState::Done);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(runtime.get(&queued).map(|job| job.state), Some(TaskState::Paused));
+        assert_eq!(runtime.dispatcher.slots.in_use(), 0);
+
+        let resumed = svc.resume_job(Request::new(make_resume_request(&queued))).await.expect("resume").into_inner();
+        assert!(resumed.accepted);
+        assert_eq!(resumed.state, TaskState::Queued as i32);
+        let job = wait_for_terminal(runtime.clone(), &queued).await;
+        assert_eq!(job.state, TaskState::Done);
+        let events: Vec<&str> = job.history.iter().map(|entry| entry.event.as_str()).collect();
+        assert!(events.windows(2).any(|pair| pair == ["pause", "resume"]), "{events:?}");
+        let pause = job.history.iter().find(|entry| entry.event == "pause").expect("pause transition");
+        assert_eq!((pause.from, pause.to), (TaskState::Queued, TaskState::Paused));
+        assert_eq!(pause.reason.as_deref(), Some("maintenance"));
+    }
 
-        let graph_encoding = request.graph_encoding.expect("graph encoding");
-        let graph_json: serde_json::Value = serde_json::from_str(&graph_encoding.canonical_graph_json)
-            .expect("canonical graph json");
-        assert_eq!(graph_json["canonical_graph_version"], "aqo-graph-v1");
-        assert_eq!(graph_json["compile_digest"], "compile-digest-0001");
-        assert_eq!(
-            graph_json["compiled_artifact_ref"],
-            "qfs://jobs/job-1/compiled/circuit.aqo.json"
-        );
-        assert_eq!(
-            graph_encoding.canonical_sha256,
-            hash_bytes_hex(graph_encoding.canonical_graph_json.as_bytes())
-        );
+    #[tokio::test]
+    async fn pause_and_resume_reject_jobs_in_other_states() {
+        let runtime = Arc::new(KernelRuntimeStore {
+            dispatcher: Arc::new(ExecutionDispatcher::new(1)),
+            ..KernelRuntimeStore::default()
+        });
+        let adapters = Arc::new(FixtureAdapters::with_hold(
+            test_qfs_root("pause-invalid"),
+            None,
+            Some(DagStageKind::Execute),
+            Duration::from_millis(300),
+        ));
+        let svc = KernelGatewaySvc::new(runtime.clone(), adapters);
+        let (running, queued) = enqueue_behind_a_blocker(&svc, &runtime, "pause-invalid").await;
+
+        let pause = svc.pause_job(Request::new(make_pause_request(&running))).await.expect("pause").into_inner();
+        assert!(!pause.accepted);
+        assert_eq!(pause.state, TaskState::Running as i32);
+        for job_id in [&running, &queued] {
+            let resume = svc.resume_job(Request::new(make_resume_request(job_id))).await.expect("resume").into_inner();
+            assert!(!resume.accepted, "{job_id} is not paused");
+        }
+        assert_eq!(runtime.get(&queued).map(|job| job.state), Some(TaskState::Queued));
+
+        let err = svc.pause_job(Request::new(make_pause_request("job-missing"))).await.expect_err("unknown job");
+        assert_eq!(err.code(), Code::NotFound);
+        let err = svc.resume_job(Request::new(make_resume_request("job-missing"))).await.expect_err("unknown job");
+        assert_eq!(err.code(), Code::NotFound);
+
+        let done = wait_for_terminal(runtime.clone(), &running).await;
+        let pause = svc.pause_job(Request::new(make_pause_request(&done.job_id))).await.expect("pause").into_inner();
+        assert!(!pause.accepted);
+        assert_eq!(pause.state, TaskState::Done as i32);
+        assert_eq!(wait_for_terminal(runtime.clone(), &queued).await.state, TaskState::Done);
     }
 
     #[tokio::test]
-    async fn cancellation_while_compiling_is_deterministic() {
-        let (svc, runtime) = make_service_with_hold(None, Some(DagStageKind::Compile), Duration::from_millis(80));
-        let response = svc
-            .enqueue_job(Request::new(make_request("compile-cancel")))
+    async fn paused_job_can_be_cancelled() {
+        let runtime = Arc::new(KernelRuntimeStore {
+            dispatcher: Arc::new(ExecutionDispatcher::new(1)),
+            ..KernelRuntimeStore::default()
+        });
+        let adapters = Arc::new(FixtureAdapters::with_hold(
+            test_qfs_root("pause-cancel"),
+            None,
+            Some(DagStageKind::Execute),
+            Duration::from_millis(300),
+        ));
+        let svc = KernelGatewaySvc::new(runtime.clone(), adapters);
+        let (blocker, queued) = enqueue_behind_a_blocker(&svc, &runtime, "pause-cancel").await;
+        assert!(svc.pause_job(Request::new(make_pause_request(&queued))).await.expect("pause").into_inner().accepted);
+
+        let cancel = svc
+            .cancel_job(Request::new(make_cancel_request(&queued)))
             .await
-            .expect("enqueue should succeed")
+            .expect("cancel")
             .into_inner();
-        tokio::time::sleep(Duration::from_millis(10)).await;
-        let _ = svc.cancel_job(Request::new(make_cancel_request(&response.job_id))).await;
-        let job = wait_for_terminal(runtime, &response.job_id).await;
-        assert_eq!(job.state, TaskState::Cancelled);
+        assert!(cancel.accepted);
+        assert_eq!(wait_for_terminal(runtime.clone(), &queued).await.state, TaskState::Cancelled);
+        let resume = svc.resume_job(Request::new(make_resume_request(&queued))).await.expect("resume").into_inner();
+        assert!(!resume.accepted);
+        assert_eq!(resume.state, TaskState::Cancelled as i32);
+        assert_eq!(wait_for_terminal(runtime.clone(), &blocker).await.state, TaskState::Done);
     }
 
     #[tokio::test]
-    async fn cancellation_while_executing_is_deterministic() {
-        let (svc, runtime) = make_service_with_hold(None, Some(DagStageKind::Execute), Duration::from_millis(80));
-        let response = svc
-            .enqueue_job(Request::new(make_request("execute-cancel")))
+    async fn tenant_over_quota_is_rejected_until_its_job_finishes() {
+        let runtime = Arc::new(KernelRuntimeStore {
+            quota: QuotaManager::new(QuotaPolicy {
+                max_concurrent_jobs_per_tenant: 1,
+                ..QuotaPolicy::default()
+            }),
+            ..KernelRuntimeStore::default()
+        });
+        let adapters = Arc::new(FixtureAdapters::with_hold(
+            test_qfs_root("quota"),
+            None,
+            Some(DagStageKind::Execute),
+            Duration::from_millis(100),
+        ));
+        let svc = KernelGatewaySvc::new(runtime.clone(), adapters);
+
+        let first = svc
+            .enqueue_job(Request::new(make_request("quota-first")))
             .await
-            .expect("enqueue should succeed")
+            .expect("first job fits the quota")
             .into_inner();
-        tokio::time::sleep(Duration::from_millis(10)).await;
-        let _ = svc.cancel_job(Request::new(make_cancel_request(&response.job_id))).await;
-        let job = wait_for_terminal(runtime, &response.job_id).await;
-        assert_eq!(job.state, TaskState::Cancelled);
+        let err = svc
+            .enqueue_job(Request::new(make_request("quota-second")))
+            .await
+            .expect_err("second job exceeds the quota");
+        assert_eq!(err.code(), Code::ResourceExhausted);
+
+        let mut other_tenant = make_request("quota-other-tenant");
+        other_tenant.metadata.as_mut().expect("metadata").tenant_id = "tenant-b".to_string();
+        svc.enqueue_job(Request::new(other_tenant)).await.expect("tenant-b has its own quota");
+
+        wait_for_terminal(runtime.clone(), &first.job_id).await;
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+        while runtime.quota.in_use("tenant-a") > 0 {
+            assert!(tokio::time::Instant::now() < deadline, "lease was never released");
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        svc.enqueue_job(Request::new(make_request("quota-second")))
+            .await
+            .expect("capacity is back once the first job is terminal");
     }
 
     #[tokio::test]
@@ -6212,7 +11135,7 @@ spec:
         tokio::time::sleep(Duration::from_millis(10)).await;
         let _ = svc.cancel_job(Request::new(make_cancel_request(&response.job_id))).await;
         let job = wait_for_terminal(runtime, &response.job_id).await;
-        assert!(matches!(job.state, TaskState::Cancelled | TaskState::Done));
+        assert_eq!(job.state, TaskState::Cancelled);
     }
 
     #[tokio::test]
@@ -6233,6 +11156,41 @@ spec:
         assert_eq!(job.reservation_state.as_deref(), Some("released"));
     }
 
+    #[tokio::test]
+    async fn deadline_reaper_times_out_a_job_stuck_in_a_stage() {
+        let (svc, runtime) = make_service_with_hold(None, Some(DagStageKind::Compile), Duration::from_secs(30));
+        let reaper = runtime.spawn_deadline_reaper(Duration::from_millis(10));
+        let mut req = make_request("deadline-reaper");
+        req.metadata.as_mut().expect("metadata").deadline = Some(ProtoDuration { seconds: 0, nanos: 100_000_000 });
+        let started = Instant::now();
+        let response = svc.enqueue_job(Request::new(req)).await.expect("enqueue").into_inner();
+
+        let job = wait_for_terminal(runtime.clone(), &response.job_id).await;
+        assert!(started.elapsed() < Duration::from_secs(5), "the reaper did not interrupt the held stage");
+        assert_eq!(job.state, TaskState::Timeout);
+        assert_eq!(job.error_code.as_deref(), Some("DEADLINE_EXCEEDED"));
+        assert!(runtime.reap_expired_deadlines().is_empty(), "terminal jobs are left alone");
+        reaper.abort();
+    }
+
+    #[tokio::test]
+    async fn jobs_without_a_deadline_get_the_runtime_default() {
+        let runtime = Arc::new(KernelRuntimeStore {
+            default_deadline: Some(DEFAULT_JOB_DEADLINE),
+            ..KernelRuntimeStore::default()
+        });
+        let adapters = Arc::new(FixtureAdapters::new(test_qfs_root("default-deadline"), None));
+        let svc = KernelGatewaySvc::new(runtime.clone(), adapters);
+        let mut req = make_request("default-deadline");
+        req.metadata.as_mut().expect("metadata").deadline = None;
+        let response = svc.enqueue_job(Request::new(req)).await.expect("enqueue").into_inner();
+
+        let deadline_at = runtime.get(&response.job_id).and_then(|job| job.deadline_at).expect("default deadline");
+        let remaining_ms = timestamp_to_ms(&deadline_at) - timestamp_to_ms(&ts_now());
+        assert!(remaining_ms > 0 && remaining_ms <= DEFAULT_JOB_DEADLINE.as_millis() as i128);
+        assert!(runtime.reap_expired_deadlines().is_empty());
+    }
+
     #[tokio::test]
     async fn stream_updates_emit_stage_envelopes_in_order() {
         let (svc, _runtime) = make_service(None);
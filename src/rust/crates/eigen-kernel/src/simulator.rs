@@ -0,0 +1,109 @@
+//! Deterministic stand-in for a circuit simulator.
+//!
+//! [`simulate_counts`] is not a quantum simulator: it derives a skewed but
+//! plausible distribution over the circuit's bitstrings from the circuit bytes
+//! and a seed, so a given (circuit, shots, seed) always yields the same counts and
+//! tests can assert exact distributions.
+
+use std::collections::BTreeMap;
+
+/// Widest register simulated; wider declared circuits are derived from the hash
+/// instead, keeping the outcome table small.
+pub const MAX_SIMULATED_QUBITS: usize = 10;
+
+/// Seed used when the submission names none.
+pub const DEFAULT_SEED: u64 = 0;
+
+/// Counts for `shots` executions of `circuit`, summing to exactly `shots`.
+///
+/// When `circuit` is AQO JSON declaring `qubits` (or `num_qubits`) between 1 and
+/// [`MAX_SIMULATED_QUBITS`], bitstrings have that width; otherwise the width is 1
+/// to 4 qubits, picked from the circuit hash. Outcomes that drew no shots are left out.
+pub fn simulate_counts(circuit: &[u8], shots: i64, seed: u64) -> BTreeMap<String, i64> {
+    let mut state = fnv1a64(circuit) ^ seed.wrapping_mul(0x9e3779b97f4a7c15);
+    let mut next = move || {
+        // splitmix64
+        state = state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    };
+    let derived = 1 + (next() % 4) as usize;
+    let qubits = declared_qubits(circuit).unwrap_or(derived);
+    // Squared weights favour a few dominant outcomes, like a real circuit would.
+    let weights: Vec<i128> = (0..1_usize << qubits).map(|_| ((next() % 16) as i128 + 1).pow(2)).collect();
+    let total_weight: i128 = weights.iter().sum();
+    let shots = shots.max(0);
+
+    let mut counts: Vec<i64> =
+        weights.iter().map(|weight| (i128::from(shots) * weight / total_weight) as i64).collect();
+    let mut remainder = shots - counts.iter().sum::<i64>();
+    let outcomes = counts.len();
+    let mut index = (next() % outcomes as u64) as usize;
+    while remainder > 0 {
+        counts[index] += 1;
+        remainder -= 1;
+        index = (index + 1) % outcomes;
+    }
+    counts
+        .into_iter()
+        .enumerate()
+        .filter(|(_, count)| *count > 0)
+        .map(|(outcome, count)| (format!("{outcome:0qubits$b}"), count))
+        .collect()
+}
+
+/// Register width an AQO JSON document declares, when it is one we simulate.
+fn declared_qubits(circuit: &[u8]) -> Option<usize> {
+    let aqo: serde_json::Value = serde_json::from_slice(circuit).ok()?;
+    let qubits = aqo.get("qubits").or_else(|| aqo.get("num_qubits"))?.as_u64()?;
+    usize::try_from(qubits).ok().filter(|qubits| (1..=MAX_SIMULATED_QUBITS).contains(qubits))
+}
+
+pub(crate) fn fnv1a64(input: &[u8]) -> u64 {
+    const OFFSET: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+    let mut hash = OFFSET;
+    for byte in input {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(PRIME);
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BELL: &[u8] = b"@quantum\ndef main():\n    h(0)\n    cx(0, 1)\n";
+    const GHZ: &[u8] = b"@quantum\ndef main():\n    h(0)\n    cx(0, 1)\n    cx(1, 2)\n";
+
+    #[test]
+    fn counts_are_deterministic_per_circuit_and_seed_and_sum_to_shots() {
+        assert_eq!(simulate_counts(BELL, 1024, 7), simulate_counts(BELL, 1024, 7));
+        assert_ne!(simulate_counts(BELL, 1024, 7), simulate_counts(GHZ, 1024, 7));
+        assert_ne!(simulate_counts(BELL, 1024, 7), simulate_counts(BELL, 1024, 8));
+        for shots in [0, 1, 7, 1000, 1024, 1_000_000] {
+            for circuit in [BELL, GHZ] {
+                let counts = simulate_counts(circuit, shots, DEFAULT_SEED);
+                assert_eq!(counts.values().sum::<i64>(), shots, "{counts:?}");
+                assert!(counts.values().all(|count| *count > 0));
+                let width = counts.keys().next().map_or(0, String::len);
+                assert!(counts.keys().all(|bitstring| bitstring.len() == width && (1..=4).contains(&width)));
+            }
+        }
+    }
+
+    #[test]
+    fn declared_aqo_width_is_used_for_the_bitstrings() {
+        let aqo = br#"{"version":"aqo.v1","qubits":6,"ops":[{"op":"h","q":[0]}]}"#;
+        let counts = simulate_counts(aqo, 4096, 42);
+        assert_eq!(counts.values().sum::<i64>(), 4096);
+        assert!(counts.keys().all(|bitstring| bitstring.len() == 6), "{counts:?}");
+
+        let oversized = br#"{"num_qubits":64}"#;
+        let width = simulate_counts(oversized, 100, 42).keys().next().map_or(0, String::len);
+        assert!((1..=4).contains(&width));
+    }
+}
@@ -0,0 +1,193 @@
+//! TLS and mutual TLS for the kernel gRPC listener.
+//!
+//! The listener speaks TLS when `EIGEN_TLS_CERT` and `EIGEN_TLS_KEY` are set, and
+//! additionally requires client certificates signed by `EIGEN_TLS_CLIENT_CA` when
+//! that is set. Without any of them it stays plaintext. The older
+//! `EIGEN_KERNEL_TLS_*` names are still honoured when the shared ones are unset.
+//!
+//! The AWS SDK linked through QFS brings rustls with `aws-lc-rs` while tonic uses
+//! `ring`, so rustls cannot pick a crypto provider on its own;
+//! [`install_crypto_provider`] installs `ring` before any TLS config is built.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use tonic::transport::{Certificate, Identity, ServerTlsConfig};
+
+/// PEM certificate chain the kernel presents.
+pub const TLS_CERT_ENV: &str = "EIGEN_KERNEL_TLS_CERT";
+/// PEM private key of [`TLS_CERT_ENV`].
+pub const TLS_KEY_ENV: &str = "EIGEN_KERNEL_TLS_KEY";
+/// PEM CA bundle client certificates must chain to; enables mutual TLS.
+pub const TLS_CLIENT_CA_ENV: &str = "EIGEN_KERNEL_TLS_CLIENT_CA";
+/// Name shared by every Eigen service for [`TLS_CERT_ENV`]; takes precedence over it.
+pub const SHARED_TLS_CERT_ENV: &str = "EIGEN_TLS_CERT";
+/// Shared name for [`TLS_KEY_ENV`]; takes precedence over it.
+pub const SHARED_TLS_KEY_ENV: &str = "EIGEN_TLS_KEY";
+/// Shared name for [`TLS_CLIENT_CA_ENV`]; takes precedence over it.
+pub const SHARED_TLS_CLIENT_CA_ENV: &str = "EIGEN_TLS_CLIENT_CA";
+
+/// Makes `ring` the process-wide rustls crypto provider, unless one is installed
+/// already. Call it before building any rustls client or server config.
+pub fn install_crypto_provider() {
+    // An error only means another provider won the race, which is as good.
+    let _ = rustls::crypto::ring::default_provider().install_default();
+}
+
+/// The shared name of a kernel-specific TLS variable.
+fn shared_name(env: &str) -> &'static str {
+    match env {
+        TLS_CERT_ENV => SHARED_TLS_CERT_ENV,
+        TLS_KEY_ENV => SHARED_TLS_KEY_ENV,
+        _ => SHARED_TLS_CLIENT_CA_ENV,
+    }
+}
+
+/// Why the TLS configuration could not be loaded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TlsConfigError {
+    /// Only some of the variables needed for TLS are set; `missing` is the
+    /// kernel-specific name of the one that is not.
+    Incomplete { missing: &'static str },
+    /// A configured file is missing or unreadable.
+    Unreadable { env: &'static str, path: PathBuf, message: String },
+}
+
+impl fmt::Display for TlsConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Incomplete { missing } => {
+                let shared = shared_name(missing);
+                write!(f, "incomplete kernel TLS configuration: {shared} (or {missing}) must be set as well")
+            }
+            Self::Unreadable { env, path, message } => {
+                write!(f, "cannot read {env} file {}: {message}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for TlsConfigError {}
+
+/// Certificate and key files of the kernel listener.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TlsConfig {
+    pub cert_path: PathBuf,
+    pub key_path: PathBuf,
+    /// Require client certificates signed by this CA (mutual TLS).
+    pub client_ca_path: Option<PathBuf>,
+}
+
+impl TlsConfig {
+    /// Reads [`SHARED_TLS_CERT_ENV`], [`SHARED_TLS_KEY_ENV`] and [`SHARED_TLS_CLIENT_CA_ENV`],
+    /// each falling back to its `EIGEN_KERNEL_TLS_*` name; `None` when none is set.
+    pub fn from_env() -> Result<Option<Self>, TlsConfigError> {
+        Self::from_vars(|key| std::env::var(key).ok())
+    }
+
+    fn from_vars(env: impl Fn(&str) -> Option<String>) -> Result<Option<Self>, TlsConfigError> {
+        let path = |key: &str| {
+            env(shared_name(key))
+                .filter(|value| !value.trim().is_empty())
+                .or_else(|| env(key))
+                .filter(|value| !value.trim().is_empty())
+                .map(|value| PathBuf::from(value.trim()))
+        };
+        match (path(TLS_CERT_ENV), path(TLS_KEY_ENV), path(TLS_CLIENT_CA_ENV)) {
+            (None, None, None) => Ok(None),
+            (Some(cert_path), Some(key_path), client_ca_path) => {
+                Ok(Some(Self { cert_path, key_path, client_ca_path }))
+            }
+            (None, _, _) => Err(TlsConfigError::Incomplete { missing: TLS_CERT_ENV }),
+            (Some(_), None, _) => Err(TlsConfigError::Incomplete { missing: TLS_KEY_ENV }),
+        }
+    }
+
+    /// Reads the files into a tonic server config. Fails on the first missing or
+    /// unreadable file, so the kernel never falls back to plaintext.
+    pub fn server_tls_config(&self) -> Result<ServerTlsConfig, TlsConfigError> {
+        install_crypto_provider();
+        let cert = read_pem(TLS_CERT_ENV, &self.cert_path)?;
+        let key = read_pem(TLS_KEY_ENV, &self.key_path)?;
+        let mut config = ServerTlsConfig::new().identity(Identity::from_pem(cert, key));
+        if let Some(client_ca_path) = &self.client_ca_path {
+            config = config.client_ca_root(Certificate::from_pem(read_pem(TLS_CLIENT_CA_ENV, client_ca_path)?));
+        }
+        Ok(config)
+    }
+
+    pub fn is_mutual(&self) -> bool {
+        self.client_ca_path.is_some()
+    }
+}
+
+fn read_pem(env: &'static str, path: &Path) -> Result<Vec<u8>, TlsConfigError> {
+    let unreadable = |message: String| TlsConfigError::Unreadable { env, path: path.to_path_buf(), message };
+    let pem = std::fs::read(path).map_err(|err| unreadable(err.to_string()))?;
+    if !pem.windows(b"-----BEGIN".len()).any(|window| window == b"-----BEGIN") {
+        return Err(unreadable("not a PEM file".to_string()));
+    }
+    Ok(pem)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env_of(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: Vec<(String, String)> = vars.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        move |key| vars.iter().find(|(k, _)| k == key).map(|(_, v)| v.clone())
+    }
+
+    #[test]
+    fn config_requires_cert_and_key_together() {
+        assert_eq!(TlsConfig::from_vars(env_of(&[])), Ok(None));
+        let config = TlsConfig::from_vars(env_of(&[
+            (TLS_CERT_ENV, "/etc/eigen/kernel.pem"),
+            (TLS_KEY_ENV, "/etc/eigen/kernel.key"),
+            (TLS_CLIENT_CA_ENV, "/etc/eigen/clients.pem"),
+        ]))
+        .expect("config")
+        .expect("tls enabled");
+        assert!(config.is_mutual());
+        assert_eq!(config.key_path, PathBuf::from("/etc/eigen/kernel.key"));
+
+        let err = TlsConfig::from_vars(env_of(&[(TLS_CERT_ENV, "/etc/eigen/kernel.pem")])).expect_err("no key");
+        assert_eq!(err, TlsConfigError::Incomplete { missing: TLS_KEY_ENV });
+        let err = TlsConfig::from_vars(env_of(&[(TLS_CLIENT_CA_ENV, "/etc/eigen/clients.pem")])).expect_err("ca only");
+        assert_eq!(err, TlsConfigError::Incomplete { missing: TLS_CERT_ENV });
+        assert!(err.to_string().contains(SHARED_TLS_CERT_ENV), "{err}");
+    }
+
+    #[test]
+    fn shared_names_take_precedence_over_kernel_names() {
+        let config = TlsConfig::from_vars(env_of(&[
+            (SHARED_TLS_CERT_ENV, "/etc/eigen/tls.pem"),
+            (TLS_CERT_ENV, "/etc/eigen/kernel.pem"),
+            (TLS_KEY_ENV, "/etc/eigen/kernel.key"),
+            (SHARED_TLS_CLIENT_CA_ENV, " "),
+        ]))
+        .expect("config")
+        .expect("tls enabled");
+        assert_eq!(config.cert_path, PathBuf::from("/etc/eigen/tls.pem"));
+        assert_eq!(config.key_path, PathBuf::from("/etc/eigen/kernel.key"));
+        assert!(!config.is_mutual());
+    }
+
+    #[test]
+    fn missing_or_non_pem_files_are_reported_with_their_variable() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let cert_path = dir.path().join("kernel.pem");
+        std::fs::write(&cert_path, "not a certificate").expect("write cert");
+        let config = TlsConfig { cert_path, key_path: dir.path().join("missing.key"), client_ca_path: None };
+
+        let err = config.server_tls_config().expect_err("bad cert");
+        assert!(matches!(&err, TlsConfigError::Unreadable { env: TLS_CERT_ENV, .. }), "{err}");
+        assert!(err.to_string().contains("not a PEM file"), "{err}");
+
+        std::fs::write(&config.cert_path, "-----BEGIN CERTIFICATE-----\n").expect("write cert");
+        let err = config.server_tls_config().expect_err("missing key");
+        assert!(matches!(&err, TlsConfigError::Unreadable { env: TLS_KEY_ENV, .. }), "{err}");
+        assert!(err.to_string().contains("missing.key"), "{err}");
+    }
+}
@@ -0,0 +1,213 @@
+//! Validation of KernelGateway requests, with stable error codes.
+//!
+//! A rejected request fails with `INVALID_ARGUMENT`. Its status details carry a
+//! `google.rpc.BadRequest` naming the offending field and a `google.rpc.ErrorInfo`
+//! whose `reason` is one of the codes below, so clients can branch on the code
+//! instead of the message:
+//!
+//! | Code                    | Meaning                                                  |
+//! |-------------------------|----------------------------------------------------------|
+//! | `MISSING_FIELD`         | a required field is absent or blank                      |
+//! | `EMPTY_NAME`            | `name` is blank                                          |
+//! | `NAME_TOO_LONG`         | `name` has more than [`ValidationLimits::max_name_chars`] |
+//! | `EMPTY_PROGRAM`         | `program` is empty                                       |
+//! | `PAYLOAD_TOO_LARGE`     | `program` exceeds [`ValidationLimits::max_program_bytes`] |
+//! | `EMPTY_JOB_ID`          | `job_id` is blank                                        |
+//! | `INVALID_JOB_ID_FORMAT` | `job_id` is too long or has characters ids never contain |
+
+use std::collections::HashMap;
+use std::fmt;
+
+use tonic::{Code, Status};
+use tonic_types::{ErrorDetails, StatusExt};
+
+use crate::proto::EnqueueJobRequest;
+
+pub const MISSING_FIELD: &str = "MISSING_FIELD";
+pub const EMPTY_NAME: &str = "EMPTY_NAME";
+pub const NAME_TOO_LONG: &str = "NAME_TOO_LONG";
+pub const EMPTY_PROGRAM: &str = "EMPTY_PROGRAM";
+pub const PAYLOAD_TOO_LARGE: &str = "PAYLOAD_TOO_LARGE";
+pub const EMPTY_JOB_ID: &str = "EMPTY_JOB_ID";
+pub const INVALID_JOB_ID_FORMAT: &str = "INVALID_JOB_ID_FORMAT";
+
+/// `ErrorInfo.domain` of validation failures.
+pub const ERROR_DOMAIN: &str = "kernel.eigen-os";
+
+/// Longest job name accepted, in characters, unless [`MAX_NAME_CHARS_ENV`] says otherwise.
+pub const DEFAULT_MAX_NAME_CHARS: usize = 256;
+pub const MAX_NAME_CHARS_ENV: &str = "EIGEN_KERNEL_MAX_NAME_CHARS";
+/// Largest program accepted, in bytes, unless [`MAX_PROGRAM_BYTES_ENV`] says otherwise.
+pub const DEFAULT_MAX_PROGRAM_BYTES: usize = 1024 * 1024;
+pub const MAX_PROGRAM_BYTES_ENV: &str = "EIGEN_KERNEL_MAX_PROGRAM_BYTES";
+/// Longest job id accepted; the kernel's own ids are 36 characters at most.
+pub const MAX_JOB_ID_LEN: usize = 128;
+
+/// Why a request was rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    /// One of the codes listed in the module docs.
+    pub code: &'static str,
+    /// Path of the offending field, e.g. `metadata.tenant_id`.
+    pub field: String,
+    pub message: String,
+}
+
+impl ValidationError {
+    pub fn new(code: &'static str, field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self { code, field: field.into(), message: message.into() }
+    }
+
+    /// `field` is absent or blank.
+    pub fn missing(field: &str) -> Self {
+        Self::new(MISSING_FIELD, field, format!("{field} is required"))
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+impl From<ValidationError> for Status {
+    fn from(err: ValidationError) -> Self {
+        let mut details = ErrorDetails::with_bad_request_violation(err.field.clone(), err.message.clone());
+        details.set_error_info(err.code, ERROR_DOMAIN, HashMap::from([("field".to_string(), err.field)]));
+        Status::with_error_details(Code::InvalidArgument, err.message, details)
+    }
+}
+
+/// Size limits on submitted jobs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ValidationLimits {
+    pub max_name_chars: usize,
+    pub max_program_bytes: usize,
+}
+
+impl Default for ValidationLimits {
+    fn default() -> Self {
+        Self { max_name_chars: DEFAULT_MAX_NAME_CHARS, max_program_bytes: DEFAULT_MAX_PROGRAM_BYTES }
+    }
+}
+
+impl ValidationLimits {
+    /// Reads [`MAX_NAME_CHARS_ENV`] and [`MAX_PROGRAM_BYTES_ENV`]; unset, unparsable
+    /// or zero values keep the defaults.
+    pub fn from_env() -> Self {
+        Self::from_vars(|key| std::env::var(key).ok())
+    }
+
+    fn from_vars(env: impl Fn(&str) -> Option<String>) -> Self {
+        let limit = |key: &str, default: usize| {
+            env(key).and_then(|raw| raw.trim().parse::<usize>().ok()).filter(|limit| *limit > 0).unwrap_or(default)
+        };
+        Self {
+            max_name_chars: limit(MAX_NAME_CHARS_ENV, DEFAULT_MAX_NAME_CHARS),
+            max_program_bytes: limit(MAX_PROGRAM_BYTES_ENV, DEFAULT_MAX_PROGRAM_BYTES),
+        }
+    }
+
+    /// Checks the metadata, name and program of an `EnqueueJob` request. The other
+    /// required fields are checked while the submission is normalized.
+    pub fn check_enqueue(&self, request: &EnqueueJobRequest) -> Result<(), ValidationError> {
+        if request.metadata.is_none() {
+            return Err(ValidationError::missing("metadata"));
+        }
+        let name = request.name.trim();
+        if name.is_empty() {
+            return Err(ValidationError::new(EMPTY_NAME, "name", "name is required"));
+        }
+        let name_chars = name.chars().count();
+        if name_chars > self.max_name_chars {
+            return Err(ValidationError::new(
+                NAME_TOO_LONG,
+                "name",
+                format!("name has {name_chars} characters; at most {} are allowed", self.max_name_chars),
+            ));
+        }
+        if request.program.is_empty() {
+            return Err(ValidationError::new(EMPTY_PROGRAM, "program", "program is required"));
+        }
+        if request.program.len() > self.max_program_bytes {
+            return Err(ValidationError::new(
+                PAYLOAD_TOO_LARGE,
+                "program",
+                format!("program is {} bytes; at most {} are allowed", request.program.len(), self.max_program_bytes),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Checks the `job_id` of a job-scoped request: present, at most [`MAX_JOB_ID_LEN`]
+/// long and made of ASCII letters, digits, `-`, `_` and `.` only.
+pub fn check_job_id(job_id: &str) -> Result<(), ValidationError> {
+    if job_id.trim().is_empty() {
+        return Err(ValidationError::new(EMPTY_JOB_ID, "job_id", "job_id is required"));
+    }
+    let valid_chars = job_id.bytes().all(|byte| byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.'));
+    if job_id.len() > MAX_JOB_ID_LEN || !valid_chars {
+        return Err(ValidationError::new(
+            INVALID_JOB_ID_FORMAT,
+            "job_id",
+            format!("job_id {job_id:?} is not a valid job id"),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proto::RequestMetadata;
+
+    fn request(name: &str, program: &[u8]) -> EnqueueJobRequest {
+        EnqueueJobRequest {
+            metadata: Some(RequestMetadata::default()),
+            name: name.to_string(),
+            program: program.to_vec(),
+            ..Default::default()
+        }
+    }
+
+    fn code(result: Result<(), ValidationError>) -> Option<&'static str> {
+        result.err().map(|err| err.code)
+    }
+
+    #[test]
+    fn enqueue_requests_are_checked_against_the_limits() {
+        let limits = ValidationLimits { max_name_chars: 4, max_program_bytes: 8 };
+        assert_eq!(code(limits.check_enqueue(&request("bell", b"h(0)"))), None);
+        assert_eq!(code(limits.check_enqueue(&request(" ", b"h(0)"))), Some(EMPTY_NAME));
+        assert_eq!(code(limits.check_enqueue(&request("bell2", b"h(0)"))), Some(NAME_TOO_LONG));
+        assert_eq!(code(limits.check_enqueue(&request("ψψψψ", b"h(0)"))), None, "characters, not bytes");
+        assert_eq!(code(limits.check_enqueue(&request("bell", b""))), Some(EMPTY_PROGRAM));
+        assert_eq!(code(limits.check_enqueue(&request("bell", b"h(0); cx(0, 1)"))), Some(PAYLOAD_TOO_LARGE));
+        let no_metadata = EnqueueJobRequest { metadata: None, ..request("bell", b"h(0)") };
+        assert_eq!(code(limits.check_enqueue(&no_metadata)), Some(MISSING_FIELD));
+    }
+
+    #[test]
+    fn job_ids_must_be_present_short_and_plain() {
+        assert_eq!(code(check_job_id("job-0123456789abcdef")), None);
+        assert_eq!(code(check_job_id("")), Some(EMPTY_JOB_ID));
+        for job_id in ["job 1", "../jobs", "job\n", &"j".repeat(MAX_JOB_ID_LEN + 1)] {
+            assert_eq!(code(check_job_id(job_id)), Some(INVALID_JOB_ID_FORMAT), "{job_id:?}");
+        }
+    }
+
+    #[test]
+    fn limits_come_from_the_environment() {
+        let env = |key: &str| match key {
+            MAX_NAME_CHARS_ENV => Some("64".to_string()),
+            MAX_PROGRAM_BYTES_ENV => Some("0".to_string()),
+            _ => None,
+        };
+        let limits = ValidationLimits::from_vars(env);
+        assert_eq!(limits, ValidationLimits { max_name_chars: 64, max_program_bytes: DEFAULT_MAX_PROGRAM_BYTES });
+        assert_eq!(ValidationLimits::from_vars(|_| None), ValidationLimits::default());
+    }
+}
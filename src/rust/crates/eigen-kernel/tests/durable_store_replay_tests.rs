@@ -10,6 +10,8 @@
 
 #[cfg(test)]
 mod durable_store_tests {
+    use std::sync::Arc;
+
     use eigen_kernel::durable_job_store::DurableJobStore;
     use qrtx::event_log::{JobEventLog, StateTransitionEvent};
     use qrtx::state_machine::{JobEvent, JobState};
@@ -19,7 +21,7 @@ mod durable_store_tests {
     fn make_store() -> DurableJobStore {
         let temp = tempdir().unwrap();
         let qfs = CircuitFsLocal::new(temp.path().to_str().unwrap());
-        DurableJobStore::new(qfs)
+        DurableJobStore::new(Arc::new(qfs))
     }
 
     #[test]
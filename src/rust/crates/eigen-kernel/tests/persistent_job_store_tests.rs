@@ -0,0 +1,169 @@
+//! Restart tests for the QFS-backed persistent job store.
+//!
+//! Tests cover:
+//! - Records survive dropping and reopening the store
+//! - Terminal state, errors, counts and results metadata are preserved
+//! - Non-job directories and artifact-only jobs are ignored on open
+//! - Corrupt metadata is reported instead of silently dropped
+//! - The store runs unchanged on the in-memory `MockCircuitFsBackend`
+//! - Rewrites keep the `JobMeta` fields and unknown fields other writers stored
+
+#[cfg(test)]
+mod persistent_store_tests {
+    use std::collections::{BTreeMap, HashMap};
+    use std::sync::Arc;
+
+    use eigen_kernel::persistent_job_store::{PersistentJobStore, PersistentJobStoreError};
+    use qfs::{CircuitFsBackend, CircuitFsLocal, JOB_META_SCHEMA_VERSION, JobMeta, MockCircuitFsBackend};
+    use qrtx::queue::Priority;
+    use qrtx::state_machine::{JobEvent, JobState};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_records_survive_reopen() {
+        let temp = tempdir().unwrap();
+        let (done_id, running_id) = {
+            let store = PersistentJobStore::open(Arc::new(CircuitFsLocal::new(temp.path()))).unwrap();
+            let done = store.create_job("done-job".to_string(), Priority::Normal).unwrap();
+            store.apply_event(&done.job_id, JobEvent::StartCompiling).unwrap();
+            store.apply_event(&done.job_id, JobEvent::StartRunning).unwrap();
+            store
+                .set_counts(&done.job_id, HashMap::from([("00".to_string(), 512)]))
+                .unwrap();
+            store
+                .set_results_metadata(
+                    &done.job_id,
+                    HashMap::from([("backend".to_string(), "sim:local".to_string())]),
+                )
+                .unwrap();
+            store.apply_event(&done.job_id, JobEvent::Complete).unwrap();
+
+            let running = store.create_job("running-job".to_string(), Priority::Normal).unwrap();
+            store.apply_event(&running.job_id, JobEvent::StartCompiling).unwrap();
+            (done.job_id, running.job_id)
+        };
+
+        let reopened = PersistentJobStore::open(Arc::new(CircuitFsLocal::new(temp.path()))).unwrap();
+        assert_eq!(reopened.len(), 2);
+
+        let done = reopened.get(&done_id).unwrap();
+        assert_eq!(done.name, "done-job");
+        assert_eq!(done.state, JobState::Done);
+        assert_eq!(done.counts.get("00"), Some(&512));
+        assert_eq!(done.results_metadata.get("backend").map(String::as_str), Some("sim:local"));
+
+        let running = reopened.get(&running_id).unwrap();
+        assert_eq!(running.state, JobState::Compiling);
+    }
+
+    #[test]
+    fn test_reopened_store_continues_state_machine() {
+        let temp = tempdir().unwrap();
+        let job_id = {
+            let store = PersistentJobStore::open(Arc::new(CircuitFsLocal::new(temp.path()))).unwrap();
+            let job = store.create_job("resume".to_string(), Priority::Normal).unwrap();
+            store.apply_event(&job.job_id, JobEvent::StartCompiling).unwrap();
+            job.job_id
+        };
+
+        let store = PersistentJobStore::open(Arc::new(CircuitFsLocal::new(temp.path()))).unwrap();
+        store
+            .set_error(&job_id, "COMPILE_ERROR".to_string(), "bad gate".to_string(), None)
+            .unwrap();
+        store.apply_event(&job_id, JobEvent::Fail).unwrap();
+
+        let reopened = PersistentJobStore::open(Arc::new(CircuitFsLocal::new(temp.path()))).unwrap();
+        let job = reopened.get(&job_id).unwrap();
+        assert_eq!(job.state, JobState::Error);
+        assert_eq!(job.error_code.as_deref(), Some("COMPILE_ERROR"));
+        assert!(matches!(
+            reopened.apply_event(&job_id, JobEvent::Cancel),
+            Err(PersistentJobStoreError::Transition(_))
+        ));
+    }
+
+    #[test]
+    fn test_open_skips_jobs_without_metadata() {
+        let temp = tempdir().unwrap();
+        let qfs = CircuitFsLocal::new(temp.path()).with_test_job_id_prefix("job-");
+        qfs.ensure_job_layout("job-artifact-only").unwrap();
+        std::fs::write(temp.path().join("jobs").join("stray-file"), b"x").unwrap();
+
+        let store = PersistentJobStore::open(Arc::new(qfs)).unwrap();
+        assert!(store.is_empty());
+    }
+
+    #[test]
+    fn test_open_reports_corrupt_metadata() {
+        let temp = tempdir().unwrap();
+        let qfs = CircuitFsLocal::new(temp.path()).with_test_job_id_prefix("job-");
+        let path = qfs.meta_json_path("job-broken").unwrap();
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, b"{not json").unwrap();
+
+        let err = PersistentJobStore::open(Arc::new(qfs)).unwrap_err();
+        assert!(matches!(err, PersistentJobStoreError::Corrupt { ref job_id, .. } if job_id == "job-broken"));
+    }
+
+    #[test]
+    fn test_records_survive_reopen_on_a_mock_backend() {
+        let backend = Arc::new(MockCircuitFsBackend::default());
+        let job_id = {
+            let store = PersistentJobStore::open(backend.clone()).unwrap();
+            let job = store.create_job("in-memory".to_string(), Priority::Normal).unwrap();
+            store.apply_event(&job.job_id, JobEvent::StartCompiling).unwrap();
+            job.job_id
+        };
+
+        assert_eq!(backend.list_job_ids().unwrap(), vec![job_id.clone()]);
+        assert_eq!(backend.keys(), vec![format!("jobs/{job_id}/meta/job.json")]);
+        let reopened = PersistentJobStore::open(backend).unwrap();
+        assert_eq!(reopened.get(&job_id).unwrap().state, JobState::Compiling);
+    }
+
+    #[test]
+    fn test_rewrites_keep_job_meta_fields_and_extras() {
+        let temp = tempdir().unwrap();
+        let qfs = Arc::new(CircuitFsLocal::new(temp.path()).with_test_job_id_prefix("job-"));
+        qfs.store_job_meta(&JobMeta {
+            schema_version: JOB_META_SCHEMA_VERSION.to_string(),
+            job_id: "job-shared".to_string(),
+            name: "bell".to_string(),
+            tenant_id: Some("tenant-a".to_string()),
+            tags: BTreeMap::from([("team".to_string(), "qec".to_string())]),
+            shots: 1024,
+            state: "PENDING".to_string(),
+            priority: "High".to_string(),
+            created_at_unix_ms: 1,
+            updated_at_unix_ms: 1,
+            attempts: 0,
+            error_code: None,
+            error_summary: None,
+            error_details_ref: None,
+            extra: BTreeMap::from([("dashboard_url".to_string(), serde_json::json!("https://example.test/bell"))]),
+        })
+        .unwrap();
+
+        let store = PersistentJobStore::open(qfs.clone()).unwrap();
+        let record = store.get("job-shared").unwrap();
+        assert_eq!((record.priority, record.tenant_id.as_deref()), (Priority::High, Some("tenant-a")));
+        store.apply_event("job-shared", JobEvent::StartCompiling).unwrap();
+
+        let meta = qfs.load_job_meta("job-shared").unwrap();
+        assert_eq!(meta.schema_version, JOB_META_SCHEMA_VERSION);
+        assert_eq!(meta.state, "COMPILING");
+        assert_eq!((meta.shots, meta.tags.get("team").map(String::as_str)), (1024, Some("qec")));
+        assert_eq!(meta.extra.get("dashboard_url"), Some(&serde_json::json!("https://example.test/bell")));
+        assert!(meta.extra.contains_key("history"));
+    }
+
+    #[test]
+    fn test_unknown_job_is_not_found() {
+        let temp = tempdir().unwrap();
+        let store = PersistentJobStore::open(Arc::new(CircuitFsLocal::new(temp.path()))).unwrap();
+        assert!(matches!(
+            store.apply_event("missing", JobEvent::StartCompiling),
+            Err(PersistentJobStoreError::NotFound { .. })
+        ));
+    }
+}
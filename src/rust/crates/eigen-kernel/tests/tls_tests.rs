@@ -0,0 +1,169 @@
+//! TLS and mutual TLS on the kernel gRPC listener.
+//!
+//! Tests cover:
+//! - A plaintext client cannot call a TLS listener
+//! - Without a client CA, a TLS client trusting the server certificate is served
+//! - An mTLS client whose certificate chains to the configured CA is served
+//! - A TLS client without a client certificate is rejected under mTLS
+//! - `serve` starts with a self-signed certificate and answers TLS clients
+
+#[cfg(test)]
+mod tls_tests {
+    use std::net::SocketAddr;
+    use std::time::Duration;
+
+    use eigen_kernel::auth::{AuthConfig, AuthInterceptor};
+    use eigen_kernel::proto::GetJobStatusRequest;
+    use eigen_kernel::proto::kernel_gateway_service_client::KernelGatewayServiceClient;
+    use eigen_kernel::rpc::{serve, serve_with_shutdown};
+    use eigen_kernel::tls::TlsConfig;
+    use rcgen::{BasicConstraints, CertificateParams, ExtendedKeyUsagePurpose, IsCa, KeyPair};
+    use tempfile::TempDir;
+    use tonic::Code;
+    use tonic::transport::{Certificate, ClientTlsConfig, Endpoint, Identity};
+
+    /// A throwaway CA with a `localhost` server certificate and a client certificate.
+    struct Pki {
+        dir: TempDir,
+        ca_pem: String,
+        client_pem: String,
+        client_key_pem: String,
+    }
+
+    impl Pki {
+        fn generate() -> Self {
+            let ca_key = KeyPair::generate().expect("ca key");
+            let mut ca_params = CertificateParams::new(Vec::<String>::new()).expect("ca params");
+            ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+            let ca = ca_params.self_signed(&ca_key).expect("ca certificate");
+            let issue = |usage: ExtendedKeyUsagePurpose| {
+                let key = KeyPair::generate().expect("leaf key");
+                let mut params = CertificateParams::new(vec!["localhost".to_string()]).expect("leaf params");
+                params.extended_key_usages = vec![usage];
+                let cert = params.signed_by(&key, &ca, &ca_key).expect("leaf certificate");
+                (cert.pem(), key.serialize_pem())
+            };
+            let (server_pem, server_key_pem) = issue(ExtendedKeyUsagePurpose::ServerAuth);
+            let (client_pem, client_key_pem) = issue(ExtendedKeyUsagePurpose::ClientAuth);
+
+            let dir = tempfile::tempdir().expect("tempdir");
+            for (name, pem) in [("ca.pem", ca.pem()), ("kernel.pem", server_pem), ("kernel.key", server_key_pem)] {
+                std::fs::write(dir.path().join(name), pem).expect("write pem");
+            }
+            Self { dir, ca_pem: ca.pem(), client_pem, client_key_pem }
+        }
+
+        fn server_config(&self) -> TlsConfig {
+            let path = |name: &str| self.dir.path().join(name);
+            TlsConfig {
+                cert_path: path("kernel.pem"),
+                key_path: path("kernel.key"),
+                client_ca_path: Some(path("ca.pem")),
+            }
+        }
+    }
+
+    async fn spawn_kernel(tls: &TlsConfig) -> SocketAddr {
+        let tls = tls.server_tls_config().expect("server tls config");
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.expect("bind kernel port");
+        let addr = listener.local_addr().expect("kernel addr");
+        tokio::spawn(async move {
+            serve_with_shutdown(listener, None, AuthInterceptor::disabled(), Some(tls), std::future::pending())
+                .await
+                .map_err(|err| err.to_string())
+        });
+        addr
+    }
+
+    /// Calls GetJobStatus for an unknown job; a served call fails with `NotFound`.
+    async fn call(endpoint: Endpoint) -> Result<(), tonic::Status> {
+        let channel = endpoint
+            .connect_timeout(Duration::from_secs(5))
+            .timeout(Duration::from_secs(5))
+            .connect()
+            .await
+            .map_err(|err| tonic::Status::unavailable(err.to_string()))?;
+        KernelGatewayServiceClient::new(channel)
+            .get_job_status(GetJobStatusRequest { job_id: "job-missing".to_string(), ..Default::default() })
+            .await
+            .map(|_| ())
+    }
+
+    fn tls_endpoint(addr: SocketAddr, ca_pem: &str, identity: Option<Identity>) -> Endpoint {
+        eigen_kernel::tls::install_crypto_provider();
+        let mut tls = ClientTlsConfig::new().ca_certificate(Certificate::from_pem(ca_pem)).domain_name("localhost");
+        if let Some(identity) = identity {
+            tls = tls.identity(identity);
+        }
+        Endpoint::from_shared(format!("https://{addr}")).expect("endpoint").tls_config(tls).expect("client tls")
+    }
+
+    #[tokio::test]
+    async fn plaintext_client_is_rejected_and_mtls_client_is_served() {
+        let pki = Pki::generate();
+        let addr = spawn_kernel(&pki.server_config()).await;
+
+        let plaintext = call(Endpoint::from_shared(format!("http://{addr}")).expect("endpoint")).await;
+        let err = plaintext.expect_err("plaintext client must not be served");
+        assert_ne!(err.code(), Code::NotFound, "{err}");
+
+        let identity = Identity::from_pem(&pki.client_pem, &pki.client_key_pem);
+        let err = call(tls_endpoint(addr, &pki.ca_pem, Some(identity))).await.expect_err("unknown job");
+        assert_eq!(err.code(), Code::NotFound, "{err}");
+    }
+
+    #[tokio::test]
+    async fn tls_client_is_served_without_a_certificate_when_no_client_ca_is_set() {
+        let pki = Pki::generate();
+        let addr = spawn_kernel(&TlsConfig { client_ca_path: None, ..pki.server_config() }).await;
+
+        let err = call(tls_endpoint(addr, &pki.ca_pem, None)).await.expect_err("unknown job");
+        assert_eq!(err.code(), Code::NotFound, "{err}");
+    }
+
+    #[tokio::test]
+    async fn client_without_certificate_is_rejected_under_mtls() {
+        let pki = Pki::generate();
+        let addr = spawn_kernel(&pki.server_config()).await;
+
+        let err = call(tls_endpoint(addr, &pki.ca_pem, None)).await.expect_err("anonymous client");
+        assert_ne!(err.code(), Code::NotFound, "{err}");
+    }
+
+    #[tokio::test]
+    async fn serve_starts_with_a_self_signed_certificate() {
+        let certified = rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).expect("self-signed cert");
+        let dir = tempfile::tempdir().expect("tempdir");
+        let (cert_path, key_path) = (dir.path().join("kernel.pem"), dir.path().join("kernel.key"));
+        std::fs::write(&cert_path, certified.cert.pem()).expect("write cert");
+        std::fs::write(&key_path, certified.key_pair.serialize_pem()).expect("write key");
+        let tls = TlsConfig { cert_path, key_path, client_ca_path: None };
+
+        // `serve` binds the address itself; reserve a free port and hand it over.
+        let addr = std::net::TcpListener::bind("127.0.0.1:0").and_then(|listener| listener.local_addr()).expect("port");
+        let server = tokio::spawn(async move {
+            serve(addr, None, Some(AuthConfig::default()), Some(tls)).await.map_err(|err| err.to_string())
+        });
+
+        let mut served = Err(tonic::Status::unavailable("not attempted"));
+        for _ in 0..50 {
+            served = call(tls_endpoint(addr, &certified.cert.pem(), None)).await;
+            if served.as_ref().is_err_and(|err| err.code() == Code::NotFound) || server.is_finished() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        assert!(!server.is_finished(), "serve exited: {:?}", server.await);
+        let err = served.expect_err("unknown job");
+        assert_eq!(err.code(), Code::NotFound, "{err}");
+        server.abort();
+    }
+
+    #[test]
+    fn unreadable_key_fails_before_serving() {
+        let pki = Pki::generate();
+        let config = TlsConfig { key_path: pki.dir.path().join("missing.key"), ..pki.server_config() };
+        let err = config.server_tls_config().expect_err("missing key");
+        assert!(err.to_string().contains("missing.key"), "{err}");
+    }
+}
@@ -1,17 +1,433 @@
-//! Observability (MVP placeholder).
+//! Observability for Eigen OS services.
 //!
-//! This crate will provide:
-//! - tracing setup + structured logs
-//! - metrics (Prometheus/OpenTelemetry)
-//! - context propagation helpers (trace_id / request_id)
+//! This crate provides:
+//! - tracing setup + structured logs ([`init_tracing`], [`log_startup`])
+//! - span export to an OpenTelemetry collector over OTLP/gRPC ([`otel`])
+//! - job pipeline and per-RPC latency metrics in the Prometheus text format
+//!   ([`metrics`], configured by [`ObservabilityConfig`])
+//! - context propagation helpers (trace_id / request_id), including the
+//!   `x-request-id` gRPC layer ([`request_id::RequestIdLayer`]) and W3C
+//!   `traceparent` extraction and injection ([`trace_context`])
 
 #![forbid(unsafe_code)]
 
-/// Returns a stable placeholder value.
-pub fn log_startup(service: &str) {
+pub mod metrics;
+pub mod otel;
+pub mod request_id;
+pub mod trace_context;
+
+use std::fmt::{self, Display};
+
+use opentelemetry_sdk::trace::Tracer;
+use tracing::Subscriber;
+use tracing_subscriber::EnvFilter;
+use tracing_subscriber::Layer;
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::fmt::time::SystemTime;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+
+pub use tracing_subscriber::util::TryInitError;
+
+/// Selects the log output format: `json` for one JSON object per line, anything
+/// else (or unset) for human-readable text.
+pub const LOG_FORMAT_ENV: &str = "EIGEN_LOG_FORMAT";
+/// When truthy (`1`, `true`, `yes`), closing a span logs an event with its busy and
+/// idle time.
+pub const LOG_SPAN_TIMING_ENV: &str = "EIGEN_LOG_SPAN_TIMING";
+/// Overrides the service name, which otherwise is the executable's file name.
+pub const SERVICE_NAME_ENV: &str = "EIGEN_SERVICE_NAME";
+/// OTLP/gRPC collector endpoint, e.g. `http://otel-collector:4317`; spans are only
+/// exported when it is set.
+pub const OTEL_ENDPOINT_ENV: &str = "EIGEN_OTEL_ENDPOINT";
+/// Comma-separated upper bounds (seconds) of the `eigen_rpc_duration_seconds`
+/// buckets, e.g. `0.01,0.1,1`.
+pub const RPC_DURATION_BUCKETS_ENV: &str = "EIGEN_RPC_DURATION_BUCKETS";
+
+/// Filter applied when `RUST_LOG` is unset or invalid.
+const DEFAULT_FILTER: &str = "info";
+/// Service name used when neither [`SERVICE_NAME_ENV`] nor the executable name is available.
+const DEFAULT_SERVICE_NAME: &str = "eigen";
+
+/// Log output format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+impl LogFormat {
+    /// Reads the format from [`LOG_FORMAT_ENV`].
+    pub fn from_env() -> Self {
+        Self::from_vars(|key| std::env::var(key).ok())
+    }
+
+    fn from_vars(env: impl Fn(&str) -> Option<String>) -> Self {
+        match env(LOG_FORMAT_ENV) {
+            Some(value) if value.trim().eq_ignore_ascii_case("json") => Self::Json,
+            _ => Self::Text,
+        }
+    }
+}
+
+/// How [`init_tracing`] sets up the subscriber.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TracingConfig {
+    pub format: LogFormat,
+    /// `RUST_LOG`-style directives; an invalid filter falls back to `info`.
+    pub filter: String,
+    /// Log span close events carrying `time.busy` and `time.idle`.
+    pub span_timing: bool,
+    /// Name of the service, attached to the `tracing initialized` event and to
+    /// exported spans.
+    pub service_name: String,
+    /// Export spans to this OTLP/gRPC collector (see [`otel`]).
+    pub otlp_endpoint: Option<String>,
+}
+
+impl Default for TracingConfig {
+    fn default() -> Self {
+        Self {
+            format: LogFormat::default(),
+            filter: DEFAULT_FILTER.to_string(),
+            span_timing: false,
+            service_name: DEFAULT_SERVICE_NAME.to_string(),
+            otlp_endpoint: None,
+        }
+    }
+}
+
+impl TracingConfig {
+    /// Reads the format from [`LOG_FORMAT_ENV`], the filter from `RUST_LOG`, span
+    /// timing from [`LOG_SPAN_TIMING_ENV`], the collector from [`OTEL_ENDPOINT_ENV`]
+    /// and the service name from [`SERVICE_NAME_ENV`], falling back to the
+    /// executable's file name.
+    pub fn from_env() -> Self {
+        let executable = std::env::current_exe()
+            .ok()
+            .and_then(|path| path.file_stem().map(|stem| stem.to_string_lossy().into_owned()));
+        Self::from_vars(|key| std::env::var(key).ok(), executable)
+    }
+
+    fn from_vars(env: impl Fn(&str) -> Option<String>, executable: Option<String>) -> Self {
+        let service_name = env(SERVICE_NAME_ENV)
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+            .or(executable)
+            .unwrap_or_else(|| DEFAULT_SERVICE_NAME.to_string());
+        Self {
+            format: LogFormat::from_vars(&env),
+            filter: env(EnvFilter::DEFAULT_ENV).unwrap_or_else(|| DEFAULT_FILTER.to_string()),
+            span_timing: env(LOG_SPAN_TIMING_ENV).is_some_and(|value| {
+                matches!(value.trim().to_ascii_lowercase().as_str(), "1" | "true" | "yes")
+            }),
+            service_name,
+            otlp_endpoint: env(OTEL_ENDPOINT_ENV)
+                .map(|endpoint| endpoint.trim().to_string())
+                .filter(|endpoint| !endpoint.is_empty()),
+        }
+    }
+
+    fn env_filter(&self) -> EnvFilter {
+        EnvFilter::try_new(&self.filter).unwrap_or_else(|_| EnvFilter::new(DEFAULT_FILTER))
+    }
+}
+
+/// How the metrics in [`metrics`] are recorded.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObservabilityConfig {
+    /// Upper bounds (seconds) of the `eigen_rpc_duration_seconds` buckets, strictly
+    /// increasing; defaults to [`metrics::RPC_DURATION_BUCKETS`].
+    pub rpc_duration_buckets: Vec<f64>,
+}
+
+impl Default for ObservabilityConfig {
+    fn default() -> Self {
+        Self { rpc_duration_buckets: metrics::RPC_DURATION_BUCKETS.to_vec() }
+    }
+}
+
+impl ObservabilityConfig {
+    /// Reads the RPC buckets from [`RPC_DURATION_BUCKETS_ENV`]. A list that is not
+    /// made of positive, strictly increasing numbers falls back to the defaults.
+    pub fn from_env() -> Self {
+        Self::from_vars(|key| std::env::var(key).ok())
+    }
+
+    fn from_vars(env: impl Fn(&str) -> Option<String>) -> Self {
+        let buckets = env(RPC_DURATION_BUCKETS_ENV).and_then(|raw| {
+            let bounds = raw
+                .split(',')
+                .map(|bound| bound.trim().parse::<f64>().ok().filter(|bound| bound.is_finite() && *bound > 0.0))
+                .collect::<Option<Vec<f64>>>()?;
+            let increasing = bounds.windows(2).all(|pair| pair[0] < pair[1]);
+            (!bounds.is_empty() && increasing).then_some(bounds)
+        });
+        match buckets {
+            Some(rpc_duration_buckets) => Self { rpc_duration_buckets },
+            None => Self::default(),
+        }
+    }
+}
+
+/// Why [`init_tracing`] failed.
+#[derive(Debug)]
+pub enum InitTracingError {
+    /// A global subscriber is already installed.
+    Subscriber(TryInitError),
+    /// Span export to [`TracingConfig::otlp_endpoint`] could not be set up.
+    Otel(otel::OtelError),
+}
+
+impl fmt::Display for InitTracingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Subscriber(err) => write!(f, "cannot install tracing subscriber: {err}"),
+            Self::Otel(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl std::error::Error for InitTracingError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Subscriber(err) => Some(err),
+            Self::Otel(err) => Some(err),
+        }
+    }
+}
+
+/// Installs the global tracing subscriber described by `config`.
+///
+/// Both formats stamp events with RFC 3339 UTC timestamps. Output is written
+/// synchronously to stdout, so there is no guard to hold for flushing. When
+/// [`TracingConfig::otlp_endpoint`] is set, spans are also exported through
+/// [`otel::init_otel_tracing`]; call [`otel::shutdown_otel`] before exiting to flush
+/// them. Fails if a global subscriber is already installed.
+pub fn init_tracing(config: TracingConfig) -> Result<(), InitTracingError> {
+    let tracer = match config.otlp_endpoint {
+        Some(_) => Some(otel::init_otel_tracing(&config).map_err(InitTracingError::Otel)?),
+        None => None,
+    };
+    build_subscriber(&config, std::io::stdout, tracer).try_init().map_err(InitTracingError::Subscriber)?;
+    tracing::debug!(
+        service = %config.service_name,
+        format = ?config.format,
+        otlp_endpoint = config.otlp_endpoint.as_deref(),
+        "tracing initialized"
+    );
+    Ok(())
+}
+
+/// Emits the structured `service starting` event with the service name, its
+/// version, the git commit it was built from (`unknown` when not known), the address
+/// it listens on and the process id.
+///
+/// Services call this once after [`init_tracing`], when the listen address is known.
+pub fn log_startup(service: &str, version: &str, git_sha: Option<&str>, listen_addr: impl Display) {
     tracing::info!(
         service = service,
+        version = version,
+        git_sha = git_sha.unwrap_or("unknown"),
+        listen_addr = %listen_addr,
         pid = std::process::id(),
         "service starting"
     );
 }
+
+fn build_subscriber<W>(config: &TracingConfig, writer: W, tracer: Option<Tracer>) -> Box<dyn Subscriber + Send + Sync>
+where
+    W: for<'writer> MakeWriter<'writer> + Send + Sync + 'static,
+{
+    let span_events = if config.span_timing { FmtSpan::CLOSE } else { FmtSpan::NONE };
+    let fmt = tracing_subscriber::fmt::layer()
+        .with_timer(SystemTime)
+        .with_span_events(span_events)
+        .with_writer(writer);
+    let fmt = match config.format {
+        LogFormat::Text => fmt.boxed(),
+        LogFormat::Json => fmt.json().flatten_event(true).boxed(),
+    };
+    let otel = tracer.map(|tracer| tracing_opentelemetry::layer().with_tracer(tracer));
+    Box::new(tracing_subscriber::registry().with(fmt).with(otel).with(config.env_filter()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct CaptureWriter(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for CaptureWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'writer> MakeWriter<'writer> for CaptureWriter {
+        type Writer = Self;
+
+        fn make_writer(&'writer self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    fn capture(config: TracingConfig, emit: impl FnOnce()) -> String {
+        let writer = CaptureWriter::default();
+        let subscriber = build_subscriber(&config, writer.clone(), None);
+        tracing::subscriber::with_default(subscriber, emit);
+        let bytes = writer.0.lock().unwrap().clone();
+        String::from_utf8(bytes).expect("utf-8 log output")
+    }
+
+    fn config(format: LogFormat, filter: &str) -> TracingConfig {
+        TracingConfig {
+            format,
+            filter: filter.to_string(),
+            span_timing: false,
+            ..TracingConfig::default()
+        }
+    }
+
+    fn env_of(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: Vec<(String, String)> = vars.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        move |key| vars.iter().find(|(k, _)| k == key).map(|(_, v)| v.clone())
+    }
+
+    /// Drops the colour codes the text format wraps field names in.
+    fn strip_ansi(output: &str) -> String {
+        let mut plain = String::with_capacity(output.len());
+        let mut chars = output.chars();
+        while let Some(ch) = chars.next() {
+            if ch == '\u{1b}' {
+                chars.by_ref().find(|ch| *ch == 'm');
+            } else {
+                plain.push(ch);
+            }
+        }
+        plain
+    }
+
+    fn startup() {
+        log_startup("eigen-test", "1.2.3", Some("abc1234"), "127.0.0.1:50052");
+    }
+
+    #[test]
+    fn json_startup_event_carries_service_metadata() {
+        let output = capture(config(LogFormat::Json, "info"), startup);
+        let line = output.lines().next().expect("one log line");
+        let event: serde_json::Value = serde_json::from_str(line).expect("json log line");
+
+        assert_eq!(event["level"], "INFO");
+        assert_eq!(event["message"], "service starting");
+        assert_eq!(event["service"], "eigen-test");
+        assert_eq!(event["version"], "1.2.3");
+        assert_eq!(event["git_sha"], "abc1234");
+        assert_eq!(event["listen_addr"], "127.0.0.1:50052");
+        assert_eq!(event["pid"], std::process::id());
+        assert!(event["timestamp"].as_str().is_some_and(|ts| ts.ends_with('Z')));
+    }
+
+    #[test]
+    fn text_startup_event_carries_service_metadata() {
+        let output = capture(config(LogFormat::Text, "info"), || {
+            log_startup("eigen-test", "1.2.3", None, "0.0.0.0:50052");
+        });
+        let output = strip_ansi(&output);
+        assert!(output.contains("service starting"));
+        for field in ["service=\"eigen-test\"", "version=\"1.2.3\"", "git_sha=\"unknown\"", "listen_addr=0.0.0.0:50052"] {
+            assert!(output.contains(field), "missing {field} in {output}");
+        }
+    }
+
+    #[test]
+    fn text_format_and_filter_are_applied() {
+        let output = capture(config(LogFormat::Text, "warn"), || {
+            startup();
+            tracing::warn!("kept");
+        });
+        assert!(!output.contains("service starting"));
+        assert!(output.contains("kept"));
+
+        let output = capture(config(LogFormat::Text, "not a [valid filter"), startup);
+        assert!(output.contains("service starting"));
+    }
+
+    #[test]
+    fn span_timing_logs_busy_and_idle_time_on_close() {
+        let emit = || tracing::info_span!("job_pipeline").in_scope(|| tracing::info!("inside"));
+        let output = capture(TracingConfig { span_timing: true, ..config(LogFormat::Json, "info") }, emit);
+        let close = output
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).expect("json log line"))
+            .find(|event| event["message"] == "close")
+            .expect("span close event");
+        assert!(close["time.busy"].is_string());
+        assert!(close["time.idle"].is_string());
+
+        let output = capture(config(LogFormat::Json, "info"), emit);
+        assert!(!output.contains("time.busy"));
+    }
+
+    #[test]
+    fn config_is_read_from_the_environment() {
+        let env = env_of(&[
+            (LOG_FORMAT_ENV, " JSON "),
+            ("RUST_LOG", "eigen_kernel=debug,info"),
+            (LOG_SPAN_TIMING_ENV, "yes"),
+            (SERVICE_NAME_ENV, "eigen-kernel-a"),
+        ]);
+        let config = TracingConfig::from_vars(env, Some("eigen-kernel".to_string()));
+        assert_eq!(
+            config,
+            TracingConfig {
+                format: LogFormat::Json,
+                filter: "eigen_kernel=debug,info".to_string(),
+                span_timing: true,
+                service_name: "eigen-kernel-a".to_string(),
+                otlp_endpoint: None,
+            }
+        );
+
+        let config = TracingConfig::from_vars(env_of(&[(LOG_FORMAT_ENV, "pretty")]), Some("eigen-kernel".to_string()));
+        assert_eq!(config.format, LogFormat::Text);
+        assert_eq!(config.filter, "info");
+        assert!(!config.span_timing);
+        assert_eq!(config.service_name, "eigen-kernel");
+        assert_eq!(TracingConfig::from_vars(env_of(&[(SERVICE_NAME_ENV, " ")]), None).service_name, "eigen");
+
+        let config = TracingConfig::from_vars(env_of(&[(OTEL_ENDPOINT_ENV, " http://otel:4317 ")]), None);
+        assert_eq!(config.otlp_endpoint.as_deref(), Some("http://otel:4317"));
+        assert_eq!(TracingConfig::from_vars(env_of(&[(OTEL_ENDPOINT_ENV, "")]), None).otlp_endpoint, None);
+    }
+
+    #[test]
+    fn rpc_buckets_are_read_from_the_environment() {
+        let config = ObservabilityConfig::from_vars(env_of(&[(RPC_DURATION_BUCKETS_ENV, "0.01, 0.1,1")]));
+        assert_eq!(config.rpc_duration_buckets, [0.01, 0.1, 1.0]);
+
+        for invalid in ["", "0.1,0.01", "0.1,0.1", "0,1", "-1", "fast", "0.1,inf"] {
+            let config = ObservabilityConfig::from_vars(env_of(&[(RPC_DURATION_BUCKETS_ENV, invalid)]));
+            assert_eq!(config, ObservabilityConfig::default(), "{invalid:?}");
+        }
+        assert_eq!(ObservabilityConfig::from_vars(env_of(&[])).rpc_duration_buckets, metrics::RPC_DURATION_BUCKETS);
+    }
+
+    #[test]
+    fn init_tracing_installs_the_subscriber_once() {
+        let config = TracingConfig { format: LogFormat::Json, ..TracingConfig::default() };
+        assert!(init_tracing(config.clone()).is_ok());
+        tracing::info!("logged through the global subscriber");
+        assert!(init_tracing(config).is_err());
+    }
+}
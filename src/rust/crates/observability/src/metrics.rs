@@ -0,0 +1,549 @@
+//! Job lifecycle and RPC metrics rendered in the Prometheus text exposition format.
+//!
+//! Label values are either a closed enum ([`JobOutcome`]) or `&'static str`, so a
+//! caller cannot turn request data (job ids, error messages) into label values and
+//! blow up series cardinality. The exceptions are the `tenant` label of
+//! `eigen_jobs_submitted_total` and the `method` label of
+//! `eigen_rpc_duration_seconds`, capped at [`MAX_TENANT_LABELS`] and
+//! [`MAX_RPC_METHOD_LABELS`] values.
+//!
+//! RPC latency is recorded by [`TimingLayer`] around the gRPC server, so p50/p95/p99
+//! per method come from `histogram_quantile` over `eigen_rpc_duration_seconds`.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use tower_layer::Layer;
+use tower_service::Service;
+
+use crate::ObservabilityConfig;
+
+/// `Content-Type` of [`JobMetrics::render`] output.
+pub const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Upper bounds (seconds) of the buckets shared by every duration histogram.
+pub const STAGE_DURATION_BUCKETS: [f64; 14] =
+    [0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 300.0];
+
+/// Default upper bounds (seconds) of the `eigen_rpc_duration_seconds` buckets; see
+/// [`ObservabilityConfig::rpc_duration_buckets`].
+pub const RPC_DURATION_BUCKETS: [f64; 9] = [0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5];
+
+/// Distinct `method` label values of `eigen_rpc_duration_seconds`; calls to further
+/// methods are recorded under [`OTHER_METHOD_LABEL`].
+pub const MAX_RPC_METHOD_LABELS: usize = 64;
+/// `method` label of calls beyond [`MAX_RPC_METHOD_LABELS`].
+pub const OTHER_METHOD_LABEL: &str = "other";
+
+/// `status_code` label values, indexed by gRPC status code.
+const GRPC_STATUS_LABELS: [&str; 17] = [
+    "OK",
+    "CANCELLED",
+    "UNKNOWN",
+    "INVALID_ARGUMENT",
+    "DEADLINE_EXCEEDED",
+    "NOT_FOUND",
+    "ALREADY_EXISTS",
+    "PERMISSION_DENIED",
+    "RESOURCE_EXHAUSTED",
+    "FAILED_PRECONDITION",
+    "ABORTED",
+    "OUT_OF_RANGE",
+    "UNIMPLEMENTED",
+    "INTERNAL",
+    "UNAVAILABLE",
+    "DATA_LOSS",
+    "UNAUTHENTICATED",
+];
+
+/// Distinct `tenant` label values of `eigen_jobs_submitted_total`; submissions
+/// from further tenants are counted under [`OTHER_TENANT_LABEL`].
+pub const MAX_TENANT_LABELS: usize = 64;
+/// `tenant` label of submissions beyond [`MAX_TENANT_LABELS`].
+pub const OTHER_TENANT_LABEL: &str = "other";
+
+/// Terminal state of a job, used as the `state` label of `eigen_jobs_completed_total`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum JobOutcome {
+    Done,
+    Error,
+    Cancelled,
+    Timeout,
+}
+
+impl JobOutcome {
+    pub const ALL: [JobOutcome; 4] = [Self::Done, Self::Error, Self::Cancelled, Self::Timeout];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Done => "done",
+            Self::Error => "error",
+            Self::Cancelled => "cancelled",
+            Self::Timeout => "timeout",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct HistogramData {
+    /// Non-cumulative counts per bucket of the histogram's bounds; the extra last
+    /// slot is `+Inf`.
+    buckets: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+impl HistogramData {
+    fn observe(&mut self, bounds: &[f64], seconds: f64) {
+        self.buckets.resize(bounds.len() + 1, 0);
+        let idx = bounds.iter().position(|bound| seconds <= *bound).unwrap_or(bounds.len());
+        self.buckets[idx] += 1;
+        self.sum += seconds;
+        self.count += 1;
+    }
+}
+
+/// Counters, gauges and histograms for the job pipeline and the RPCs serving it.
+#[derive(Debug)]
+pub struct JobMetrics {
+    jobs_enqueued: AtomicU64,
+    jobs_submitted: Mutex<BTreeMap<String, u64>>,
+    jobs_completed: [AtomicU64; JobOutcome::ALL.len()],
+    job_durations: Mutex<BTreeMap<JobOutcome, HistogramData>>,
+    jobs_by_state: Mutex<BTreeMap<&'static str, i64>>,
+    stage_durations: Mutex<BTreeMap<&'static str, HistogramData>>,
+    transition_durations: Mutex<BTreeMap<(&'static str, &'static str), HistogramData>>,
+    /// Per `method`, then per `status_code`.
+    rpc_durations: Mutex<BTreeMap<String, BTreeMap<&'static str, HistogramData>>>,
+    rpc_buckets: Vec<f64>,
+}
+
+impl Default for JobMetrics {
+    fn default() -> Self {
+        Self::with_config(&ObservabilityConfig::default())
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+impl JobMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Metrics whose RPC histogram uses the buckets of `config`.
+    pub fn with_config(config: &ObservabilityConfig) -> Self {
+        Self {
+            jobs_enqueued: AtomicU64::new(0),
+            jobs_submitted: Mutex::default(),
+            jobs_completed: Default::default(),
+            job_durations: Mutex::default(),
+            jobs_by_state: Mutex::default(),
+            stage_durations: Mutex::default(),
+            transition_durations: Mutex::default(),
+            rpc_durations: Mutex::default(),
+            rpc_buckets: config.rpc_duration_buckets.clone(),
+        }
+    }
+
+    /// Counts a newly accepted job of `tenant`. Idempotent replays of an existing
+    /// job must not call this.
+    pub fn job_enqueued(&self, tenant: &str) {
+        self.jobs_enqueued.fetch_add(1, Ordering::Relaxed);
+        let mut jobs_submitted = lock(&self.jobs_submitted);
+        let label = if jobs_submitted.contains_key(tenant) || jobs_submitted.len() < MAX_TENANT_LABELS {
+            tenant
+        } else {
+            OTHER_TENANT_LABEL
+        };
+        *jobs_submitted.entry(label.to_string()).or_default() += 1;
+    }
+
+    /// Counts a job reaching `outcome` and records how long it took from submission.
+    pub fn job_completed(&self, outcome: JobOutcome, duration: Duration) {
+        self.jobs_completed[outcome as usize].fetch_add(1, Ordering::Relaxed);
+        lock(&self.job_durations)
+            .entry(outcome)
+            .or_default()
+            .observe(&STAGE_DURATION_BUCKETS, duration.as_secs_f64());
+    }
+
+    /// Counts a job entering its first state; later moves go through [`Self::job_transitioned`].
+    pub fn job_state_entered(&self, state: &'static str) {
+        *lock(&self.jobs_by_state).entry(state).or_default() += 1;
+    }
+
+    /// Takes a deleted job in `state` out of the per-state gauge.
+    pub fn job_removed(&self, state: &'static str) {
+        *lock(&self.jobs_by_state).entry(state).or_default() -= 1;
+    }
+
+    /// Moves a job from `from` to `to` in the per-state gauge and records how long
+    /// it stayed in `from`.
+    pub fn job_transitioned(&self, from: &'static str, to: &'static str, time_in_from: Duration) {
+        {
+            let mut jobs_by_state = lock(&self.jobs_by_state);
+            *jobs_by_state.entry(from).or_default() -= 1;
+            *jobs_by_state.entry(to).or_default() += 1;
+        }
+        lock(&self.transition_durations)
+            .entry((from, to))
+            .or_default()
+            .observe(&STAGE_DURATION_BUCKETS, time_in_from.as_secs_f64());
+    }
+
+    /// Records how long one run of pipeline `stage` took.
+    pub fn observe_stage_duration(&self, stage: &'static str, duration: Duration) {
+        lock(&self.stage_durations)
+            .entry(stage)
+            .or_default()
+            .observe(&STAGE_DURATION_BUCKETS, duration.as_secs_f64());
+    }
+
+    /// Records how long one call of RPC `method` took and the gRPC status it ended
+    /// with; [`TimingLayer`] calls this for every served call.
+    pub fn observe_rpc_duration(&self, method: &str, status_code: &'static str, duration: Duration) {
+        let mut rpc_durations = lock(&self.rpc_durations);
+        let method = if rpc_durations.contains_key(method) || rpc_durations.len() < MAX_RPC_METHOD_LABELS {
+            method
+        } else {
+            OTHER_METHOD_LABEL
+        };
+        rpc_durations
+            .entry(method.to_string())
+            .or_default()
+            .entry(status_code)
+            .or_default()
+            .observe(&self.rpc_buckets, duration.as_secs_f64());
+    }
+
+    /// Calls of RPC `method` recorded with `status_code`.
+    pub fn rpc_calls(&self, method: &str, status_code: &str) -> u64 {
+        lock(&self.rpc_durations)
+            .get(method)
+            .and_then(|statuses| statuses.get(status_code))
+            .map(|data| data.count)
+            .unwrap_or_default()
+    }
+
+    pub fn jobs_enqueued(&self) -> u64 {
+        self.jobs_enqueued.load(Ordering::Relaxed)
+    }
+
+    /// Jobs submitted by `tenant`, or by the tenants past the label cap for
+    /// [`OTHER_TENANT_LABEL`].
+    pub fn jobs_submitted(&self, tenant: &str) -> u64 {
+        lock(&self.jobs_submitted).get(tenant).copied().unwrap_or_default()
+    }
+
+    pub fn jobs_completed(&self, outcome: JobOutcome) -> u64 {
+        self.jobs_completed[outcome as usize].load(Ordering::Relaxed)
+    }
+
+    /// Jobs currently in `state`.
+    pub fn jobs_in_state(&self, state: &str) -> i64 {
+        lock(&self.jobs_by_state).get(state).copied().unwrap_or_default()
+    }
+
+    /// Renders every metric in the Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        out.push_str("# HELP eigen_jobs_enqueued_total Jobs accepted by the kernel.\n");
+        out.push_str("# TYPE eigen_jobs_enqueued_total counter\n");
+        let _ = writeln!(out, "eigen_jobs_enqueued_total {}", self.jobs_enqueued());
+
+        out.push_str("# HELP eigen_jobs_submitted_total Jobs accepted by the kernel, per tenant.\n");
+        out.push_str("# TYPE eigen_jobs_submitted_total counter\n");
+        for (tenant, count) in lock(&self.jobs_submitted).iter() {
+            let _ = writeln!(out, "eigen_jobs_submitted_total{{tenant=\"{}\"}} {count}", escape_label_value(tenant));
+        }
+
+        out.push_str("# HELP eigen_jobs_completed_total Jobs that reached a terminal state.\n");
+        out.push_str("# TYPE eigen_jobs_completed_total counter\n");
+        for outcome in JobOutcome::ALL {
+            let _ = writeln!(
+                out,
+                "eigen_jobs_completed_total{{state=\"{}\"}} {}",
+                outcome.as_str(),
+                self.jobs_completed(outcome)
+            );
+        }
+
+        out.push_str("# HELP eigen_job_duration_seconds Time from submission to a terminal state.\n");
+        out.push_str("# TYPE eigen_job_duration_seconds histogram\n");
+        for (outcome, data) in lock(&self.job_durations).clone() {
+            let labels = format!("state=\"{}\"", outcome.as_str());
+            write_histogram(&mut out, "eigen_job_duration_seconds", &labels, &STAGE_DURATION_BUCKETS, &data);
+        }
+
+        out.push_str("# HELP eigen_jobs_by_state Jobs currently in each state.\n");
+        out.push_str("# TYPE eigen_jobs_by_state gauge\n");
+        for (state, count) in lock(&self.jobs_by_state).iter() {
+            let _ = writeln!(out, "eigen_jobs_by_state{{state=\"{state}\"}} {count}");
+        }
+
+        out.push_str("# HELP eigen_pipeline_stage_duration_seconds Duration of kernel pipeline stages.\n");
+        out.push_str("# TYPE eigen_pipeline_stage_duration_seconds histogram\n");
+        for (stage, data) in lock(&self.stage_durations).clone() {
+            let labels = format!("stage=\"{stage}\"");
+            write_histogram(&mut out, "eigen_pipeline_stage_duration_seconds", &labels, &STAGE_DURATION_BUCKETS, &data);
+        }
+
+        out.push_str("# HELP eigen_job_transition_duration_seconds Time a job spent in a state before leaving it.\n");
+        out.push_str("# TYPE eigen_job_transition_duration_seconds histogram\n");
+        for ((from, to), data) in lock(&self.transition_durations).clone() {
+            let labels = format!("from=\"{from}\",to=\"{to}\"");
+            write_histogram(&mut out, "eigen_job_transition_duration_seconds", &labels, &STAGE_DURATION_BUCKETS, &data);
+        }
+
+        out.push_str("# HELP eigen_rpc_duration_seconds Duration of gRPC calls, per method and status.\n");
+        out.push_str("# TYPE eigen_rpc_duration_seconds histogram\n");
+        for (method, statuses) in lock(&self.rpc_durations).clone() {
+            for (status_code, data) in statuses {
+                let labels = format!("method=\"{}\",status_code=\"{status_code}\"", escape_label_value(&method));
+                write_histogram(&mut out, "eigen_rpc_duration_seconds", &labels, &self.rpc_buckets, &data);
+            }
+        }
+        out
+    }
+}
+
+/// Tower layer recording every gRPC call of a server in `eigen_rpc_duration_seconds`.
+///
+/// A call is timed until its response headers are ready, which for unary calls is
+/// when the handler returns. The `method` label is the last segment of the request
+/// path and `status_code` the name of the `grpc-status` sent with the headers; a
+/// response without one is a success whose status follows in the trailers.
+#[derive(Debug, Clone)]
+pub struct TimingLayer {
+    metrics: Arc<JobMetrics>,
+}
+
+impl TimingLayer {
+    pub fn new(metrics: Arc<JobMetrics>) -> Self {
+        Self { metrics }
+    }
+}
+
+impl<S> Layer<S> for TimingLayer {
+    type Service = TimingService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        TimingService { inner, metrics: self.metrics.clone() }
+    }
+}
+
+/// Service installed by [`TimingLayer`].
+#[derive(Debug, Clone)]
+pub struct TimingService<S> {
+    inner: S,
+    metrics: Arc<JobMetrics>,
+}
+
+impl<S, ReqBody, ResBody> Service<http::Request<ReqBody>> for TimingService<S>
+where
+    S: Service<http::Request<ReqBody>, Response = http::Response<ResBody>>,
+    S::Future: Send + 'static,
+    S::Error: Send + 'static,
+    ResBody: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: http::Request<ReqBody>) -> Self::Future {
+        let path = request.uri().path();
+        let method = path.rsplit('/').next().filter(|method| !method.is_empty()).unwrap_or(path).to_string();
+        let metrics = self.metrics.clone();
+        let started = Instant::now();
+        let response = self.inner.call(request);
+        Box::pin(async move {
+            let response = response.await?;
+            metrics.observe_rpc_duration(&method, grpc_status_label(response.headers()), started.elapsed());
+            Ok(response)
+        })
+    }
+}
+
+/// `status_code` label for the `grpc-status` header of a response.
+fn grpc_status_label(headers: &http::HeaderMap) -> &'static str {
+    let Some(status) = headers.get("grpc-status") else {
+        return GRPC_STATUS_LABELS[0];
+    };
+    status
+        .to_str()
+        .ok()
+        .and_then(|code| code.parse::<usize>().ok())
+        .and_then(|code| GRPC_STATUS_LABELS.get(code).copied())
+        .unwrap_or("UNKNOWN")
+}
+
+/// Writes the `_bucket`, `_sum` and `_count` series of one labelled histogram.
+fn write_histogram(out: &mut String, name: &str, labels: &str, bounds: &[f64], data: &HistogramData) {
+    let mut cumulative = 0;
+    for (index, bound) in bounds.iter().enumerate() {
+        cumulative += data.buckets.get(index).copied().unwrap_or_default();
+        let _ = writeln!(out, "{name}_bucket{{{labels},le=\"{bound}\"}} {cumulative}");
+    }
+    let _ = writeln!(out, "{name}_bucket{{{labels},le=\"+Inf\"}} {}", data.count);
+    let _ = writeln!(out, "{name}_sum{{{labels}}} {}", data.sum);
+    let _ = writeln!(out, "{name}_count{{{labels}}} {}", data.count);
+}
+
+/// Escapes `\`, `"` and newlines as the text exposition format requires.
+fn escape_label_value(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_reports_counters_for_every_terminal_state() {
+        let metrics = JobMetrics::new();
+        metrics.job_enqueued("tenant-a");
+        metrics.job_enqueued("tenant-a");
+        metrics.job_completed(JobOutcome::Done, Duration::from_millis(40));
+
+        let text = metrics.render();
+        assert!(text.contains("eigen_jobs_enqueued_total 2\n"));
+        assert!(text.contains("eigen_jobs_completed_total{state=\"done\"} 1\n"));
+        assert!(text.contains("eigen_jobs_completed_total{state=\"timeout\"} 0\n"));
+        assert!(text.contains("eigen_job_duration_seconds_bucket{state=\"done\",le=\"0.05\"} 1\n"));
+        assert!(text.contains("eigen_job_duration_seconds_count{state=\"done\"} 1\n"));
+        assert!(!text.contains("eigen_job_duration_seconds_count{state=\"error\"}"));
+    }
+
+    #[test]
+    fn submissions_are_counted_per_tenant_up_to_the_label_cap() {
+        let metrics = JobMetrics::new();
+        metrics.job_enqueued("tenant-a");
+        metrics.job_enqueued("tenant-a");
+        metrics.job_enqueued("tenant \"b\"");
+        assert_eq!(metrics.jobs_submitted("tenant-a"), 2);
+        let text = metrics.render();
+        assert!(text.contains("eigen_jobs_submitted_total{tenant=\"tenant-a\"} 2\n"));
+        assert!(text.contains("eigen_jobs_submitted_total{tenant=\"tenant \\\"b\\\"\"} 1\n"));
+
+        for index in 0..MAX_TENANT_LABELS {
+            metrics.job_enqueued(&format!("bulk-{index}"));
+        }
+        metrics.job_enqueued("tenant-a");
+        assert_eq!(metrics.jobs_submitted("tenant-a"), 3);
+        assert_eq!(metrics.jobs_submitted(OTHER_TENANT_LABEL), 2);
+        assert_eq!(metrics.jobs_enqueued(), MAX_TENANT_LABELS as u64 + 4);
+    }
+
+    #[test]
+    fn stage_histogram_buckets_are_cumulative() {
+        let metrics = JobMetrics::new();
+        metrics.observe_stage_duration("compile", Duration::from_millis(3));
+        metrics.observe_stage_duration("compile", Duration::from_millis(200));
+        metrics.observe_stage_duration("compile", Duration::from_secs(600));
+
+        let text = metrics.render();
+        assert!(text.contains("eigen_pipeline_stage_duration_seconds_bucket{stage=\"compile\",le=\"0.005\"} 1\n"));
+        assert!(text.contains("eigen_pipeline_stage_duration_seconds_bucket{stage=\"compile\",le=\"0.25\"} 2\n"));
+        assert!(text.contains("eigen_pipeline_stage_duration_seconds_bucket{stage=\"compile\",le=\"300\"} 2\n"));
+        assert!(text.contains("eigen_pipeline_stage_duration_seconds_bucket{stage=\"compile\",le=\"+Inf\"} 3\n"));
+        assert!(text.contains("eigen_pipeline_stage_duration_seconds_count{stage=\"compile\"} 3\n"));
+    }
+
+    #[test]
+    fn transitions_move_jobs_between_state_gauges() {
+        let metrics = JobMetrics::new();
+        metrics.job_state_entered("pending");
+        metrics.job_state_entered("pending");
+        metrics.job_transitioned("pending", "compiling", Duration::from_millis(20));
+
+        assert_eq!(metrics.jobs_in_state("pending"), 1);
+        assert_eq!(metrics.jobs_in_state("compiling"), 1);
+        let text = metrics.render();
+        assert!(text.contains("eigen_jobs_by_state{state=\"pending\"} 1\n"));
+        assert!(text.contains(
+            "eigen_job_transition_duration_seconds_bucket{from=\"pending\",to=\"compiling\",le=\"0.025\"} 1\n"
+        ));
+        assert!(text.contains("eigen_job_transition_duration_seconds_count{from=\"pending\",to=\"compiling\"} 1\n"));
+    }
+
+    /// Answers every call with the given `grpc-status` header, if any.
+    #[derive(Clone)]
+    struct Responder(Option<&'static str>);
+
+    impl Service<http::Request<()>> for Responder {
+        type Response = http::Response<()>;
+        type Error = std::convert::Infallible;
+        type Future = std::future::Ready<Result<Self::Response, Self::Error>>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _request: http::Request<()>) -> Self::Future {
+            let mut response = http::Response::builder();
+            if let Some(status) = self.0 {
+                response = response.header("grpc-status", status);
+            }
+            std::future::ready(Ok(response.body(()).expect("response")))
+        }
+    }
+
+    fn call(metrics: &Arc<JobMetrics>, path: &str, status: Option<&'static str>) {
+        let mut service = TimingLayer::new(metrics.clone()).layer(Responder(status));
+        let request = http::Request::builder().uri(path).body(()).expect("request");
+        let mut response = service.call(request);
+        let Poll::Ready(response) = response.as_mut().poll(&mut Context::from_waker(std::task::Waker::noop())) else {
+            panic!("the responder answers immediately");
+        };
+        response.expect("infallible");
+    }
+
+    #[test]
+    fn timing_layer_records_calls_per_method_and_status() {
+        let metrics = Arc::new(JobMetrics::new());
+        let path = "/eigen.internal.v1.KernelGatewayService/GetJobStatus";
+        call(&metrics, path, None);
+        call(&metrics, path, Some("0"));
+        call(&metrics, path, Some("5"));
+        call(&metrics, path, Some("99"));
+
+        assert_eq!(metrics.rpc_calls("GetJobStatus", "OK"), 2);
+        assert_eq!(metrics.rpc_calls("GetJobStatus", "NOT_FOUND"), 1);
+        assert_eq!(metrics.rpc_calls("GetJobStatus", "UNKNOWN"), 1);
+        let text = metrics.render();
+        let labels = "method=\"GetJobStatus\",status_code=\"OK\"";
+        assert!(text.contains(&format!("eigen_rpc_duration_seconds_count{{{labels}}} 2\n")), "{text}");
+        assert!(text.contains(&format!("eigen_rpc_duration_seconds_bucket{{{labels},le=\"2.5\"}} 2\n")), "{text}");
+        assert!(!text.contains(&format!("eigen_rpc_duration_seconds_bucket{{{labels},le=\"5\"}}")), "{text}");
+    }
+
+    #[test]
+    fn rpc_buckets_and_method_labels_follow_the_config_and_cap() {
+        let config = ObservabilityConfig { rpc_duration_buckets: vec![0.1, 1.0] };
+        let metrics = JobMetrics::with_config(&config);
+        metrics.observe_rpc_duration("EnqueueJob", "OK", Duration::from_millis(500));
+        let text = metrics.render();
+        assert!(text.contains("eigen_rpc_duration_seconds_bucket{method=\"EnqueueJob\",status_code=\"OK\",le=\"0.1\"} 0\n"));
+        assert!(text.contains("eigen_rpc_duration_seconds_bucket{method=\"EnqueueJob\",status_code=\"OK\",le=\"1\"} 1\n"));
+
+        for index in 0..MAX_RPC_METHOD_LABELS {
+            metrics.observe_rpc_duration(&format!("Method{index}"), "UNIMPLEMENTED", Duration::ZERO);
+        }
+        metrics.observe_rpc_duration("EnqueueJob", "OK", Duration::ZERO);
+        assert_eq!(metrics.rpc_calls("EnqueueJob", "OK"), 2);
+        assert_eq!(metrics.rpc_calls(OTHER_METHOD_LABEL, "UNIMPLEMENTED"), 1);
+    }
+}
@@ -0,0 +1,172 @@
+//! OpenTelemetry trace export over OTLP/gRPC.
+//!
+//! [`init_otel_tracing`] builds a tracer whose spans are batched and sent to the
+//! collector at [`TracingConfig::otlp_endpoint`]; [`crate::init_tracing`] wires it
+//! into the subscriber through `tracing-opentelemetry` when that endpoint is set,
+//! so every `tracing` span is exported as well as logged. Call [`shutdown_otel`]
+//! before the process exits to flush the spans still buffered.
+//!
+//! Work handed to another task keeps its place in the trace by carrying the
+//! current context in a string map ([`inject_trace_context`]) and restoring it as
+//! the parent of the task's span ([`extract_trace_context`]).
+//!
+//! The exporter uses tonic, so it must be initialized inside a Tokio runtime.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Mutex;
+
+use opentelemetry::Context;
+use opentelemetry::propagation::TextMapPropagator;
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry_otlp::{ExporterBuildError, SpanExporter, WithExportConfig};
+use opentelemetry_sdk::Resource;
+use opentelemetry_sdk::error::OTelSdkError;
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::trace::{SdkTracerProvider, Tracer};
+use tracing::Span;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+use crate::TracingConfig;
+
+/// Collector endpoint used when the config does not name one.
+pub const DEFAULT_OTLP_ENDPOINT: &str = "http://localhost:4317";
+
+/// Provider installed by the last [`init_otel_tracing`], kept for [`shutdown_otel`].
+static PROVIDER: Mutex<Option<SdkTracerProvider>> = Mutex::new(None);
+
+/// Why trace export could not be set up or shut down.
+#[derive(Debug)]
+pub enum OtelError {
+    /// The OTLP exporter could not be built, e.g. the endpoint is not a valid URI.
+    Exporter(ExporterBuildError),
+    /// Flushing or shutting down the tracer provider failed.
+    Shutdown(OTelSdkError),
+}
+
+impl fmt::Display for OtelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Exporter(err) => write!(f, "cannot build OTLP span exporter: {err}"),
+            Self::Shutdown(err) => write!(f, "cannot shut down OTLP span export: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for OtelError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Exporter(err) => Some(err),
+            Self::Shutdown(err) => Some(err),
+        }
+    }
+}
+
+/// Builds a batching OTLP/gRPC tracer for `config.service_name` and installs its
+/// provider as the global OpenTelemetry tracer provider.
+///
+/// Spans go to `config.otlp_endpoint`, or [`DEFAULT_OTLP_ENDPOINT`] when unset.
+/// The connection is made lazily, so an unreachable collector does not fail here.
+pub fn init_otel_tracing(config: &TracingConfig) -> Result<Tracer, OtelError> {
+    let endpoint = config.otlp_endpoint.as_deref().unwrap_or(DEFAULT_OTLP_ENDPOINT);
+    let exporter = SpanExporter::builder()
+        .with_tonic()
+        .with_endpoint(endpoint)
+        .build()
+        .map_err(OtelError::Exporter)?;
+    let provider = SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(Resource::builder().with_service_name(config.service_name.clone()).build())
+        .build();
+    let tracer = provider.tracer(config.service_name.clone());
+    opentelemetry::global::set_tracer_provider(provider.clone());
+    *PROVIDER.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(provider);
+    Ok(tracer)
+}
+
+/// Flushes the spans still buffered and shuts the exporter down. Does nothing when
+/// [`init_otel_tracing`] was never called or the exporter is already shut down.
+pub fn shutdown_otel() -> Result<(), OtelError> {
+    let provider = PROVIDER.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).take();
+    let Some(provider) = provider else {
+        return Ok(());
+    };
+    provider.force_flush().map_err(OtelError::Shutdown)?;
+    provider.shutdown().map_err(OtelError::Shutdown)
+}
+
+/// Writes the W3C context of the current span (`traceparent`, `tracestate`) into
+/// `headers`. Writes nothing when the span has no OpenTelemetry context.
+pub fn inject_trace_context(headers: &mut HashMap<String, String>) {
+    TraceContextPropagator::new().inject_context(&Span::current().context(), headers);
+}
+
+/// The context [`inject_trace_context`] wrote into `headers`, for
+/// [`crate::trace_context::set_parent`]; empty when there is none.
+pub fn extract_trace_context(headers: &HashMap<String, String>) -> Context {
+    TraceContextPropagator::new().extract(headers)
+}
+
+/// Sets an OpenTelemetry attribute on `span`, under a dotted key such as `job.id`
+/// that a `tracing` field name cannot express.
+pub fn set_attribute(span: &Span, key: &'static str, value: String) {
+    span.set_attribute(key, value);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry_sdk::trace::InMemorySpanExporter;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    fn config(endpoint: &str) -> TracingConfig {
+        TracingConfig { otlp_endpoint: Some(endpoint.to_string()), ..TracingConfig::default() }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn tracer_initializes_and_shutdown_flushes_cleanly() {
+        assert!(shutdown_otel().is_ok(), "shutdown without a tracer is a no-op");
+
+        // Nothing listens on the endpoint; the exporter only connects on export.
+        init_otel_tracing(&config("http://127.0.0.1:1")).expect("tracer");
+        assert!(PROVIDER.lock().unwrap().is_some());
+        shutdown_otel().expect("flush and shut down");
+        assert!(PROVIDER.lock().unwrap().is_none());
+        assert!(shutdown_otel().is_ok(), "second shutdown is a no-op");
+    }
+
+    #[test]
+    fn pipeline_span_nests_under_the_rpc_span_and_carries_the_job_id() {
+        let exporter = InMemorySpanExporter::default();
+        let provider = SdkTracerProvider::builder().with_simple_exporter(exporter.clone()).build();
+        let layer = tracing_opentelemetry::layer().with_tracer(provider.tracer("otel-test"));
+        tracing::subscriber::with_default(tracing_subscriber::registry().with(layer), || {
+            let mut carrier = HashMap::new();
+            tracing::info_span!("grpc_request").in_scope(|| inject_trace_context(&mut carrier));
+            assert!(carrier.contains_key("traceparent"), "{carrier:?}");
+
+            // What a spawned pipeline task does with the carrier it was handed.
+            let span = tracing::info_span!("job_pipeline");
+            crate::trace_context::set_parent(&span, extract_trace_context(&carrier));
+            set_attribute(&span, "job.id", "job-42".to_string());
+            span.in_scope(|| tracing::info_span!("execute_attempt").in_scope(|| {}));
+        });
+        provider.force_flush().expect("flush");
+
+        let spans = exporter.get_finished_spans().expect("finished spans");
+        let span = |name: &str| spans.iter().find(|span| span.name == name).expect(name);
+        let (rpc, pipeline, attempt) = (span("grpc_request"), span("job_pipeline"), span("execute_attempt"));
+        let job_id = pipeline.attributes.iter().find(|attribute| attribute.key.as_str() == "job.id");
+        let job_id = job_id.map(|attribute| attribute.value.as_str());
+        assert_eq!(job_id, Some("job-42".into()), "{:?}", pipeline.attributes);
+        assert_eq!(pipeline.span_context.trace_id(), rpc.span_context.trace_id());
+        assert_eq!(pipeline.parent_span_id, rpc.span_context.span_id());
+        assert_eq!(attempt.parent_span_id, pipeline.span_context.span_id());
+    }
+
+    #[tokio::test]
+    async fn invalid_endpoint_is_reported() {
+        let err = init_otel_tracing(&config("not a uri")).expect_err("invalid endpoint");
+        assert!(matches!(err, OtelError::Exporter(_)), "{err}");
+    }
+}
@@ -0,0 +1,176 @@
+//! `x-request-id` propagation for gRPC servers.
+//!
+//! [`RequestIdLayer`] gives every inbound request an id: the caller's
+//! `x-request-id` header when it is usable, else a fresh UUID v4. The id is
+//! recorded on a `grpc_request` span wrapping the handler, made available to
+//! handlers as a [`RequestId`] request extension, and echoed in the response
+//! headers. The span continues the caller's trace when the request carries a
+//! `traceparent` header.
+
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use http::HeaderValue;
+use tower_layer::Layer;
+use tower_service::Service;
+use tracing::Instrument;
+
+/// Metadata header carrying the request id, in both directions.
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Longest caller-supplied id that is kept; longer ones are replaced.
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+/// Request id of the current call, stored in the request extensions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestId(String);
+
+impl RequestId {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for RequestId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Tower layer adding [`RequestIdService`] around a server's routes.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RequestIdLayer;
+
+impl RequestIdLayer {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<S> Layer<S> for RequestIdLayer {
+    type Service = RequestIdService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RequestIdService { inner }
+    }
+}
+
+/// Service installed by [`RequestIdLayer`].
+#[derive(Debug, Clone)]
+pub struct RequestIdService<S> {
+    inner: S,
+}
+
+impl<S, ReqBody, ResBody> Service<http::Request<ReqBody>> for RequestIdService<S>
+where
+    S: Service<http::Request<ReqBody>, Response = http::Response<ResBody>>,
+    S::Future: Send + 'static,
+    S::Error: Send + 'static,
+    ResBody: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: http::Request<ReqBody>) -> Self::Future {
+        let header = request
+            .headers()
+            .get(REQUEST_ID_HEADER)
+            .filter(|value| is_usable(value))
+            .cloned();
+        let header = header.unwrap_or_else(|| {
+            let generated = uuid::Uuid::new_v4().to_string();
+            let value = HeaderValue::from_str(&generated).expect("a UUID is a valid header value");
+            request.headers_mut().insert(REQUEST_ID_HEADER, value.clone());
+            value
+        });
+        let request_id = header.to_str().unwrap_or_default().to_string();
+        let span = tracing::info_span!("grpc_request", request_id = %request_id, path = %request.uri().path());
+        if let Some(parent) = crate::trace_context::extract_context(request.headers()) {
+            crate::trace_context::set_parent(&span, parent);
+        }
+        request.extensions_mut().insert(RequestId(request_id));
+
+        let response = self.inner.call(request).instrument(span);
+        Box::pin(async move {
+            let mut response = response.await?;
+            response.headers_mut().insert(REQUEST_ID_HEADER, header);
+            Ok(response)
+        })
+    }
+}
+
+/// Caller ids must be short, non-empty, visible ASCII.
+fn is_usable(value: &HeaderValue) -> bool {
+    let bytes = value.as_bytes();
+    !bytes.is_empty() && bytes.len() <= MAX_REQUEST_ID_LEN && bytes.iter().all(|byte| byte.is_ascii_graphic())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use std::task::Waker;
+
+    /// Answers immediately, remembering the [`RequestId`] extension it was given.
+    #[derive(Clone, Default)]
+    struct Recorder(Arc<Mutex<Option<RequestId>>>);
+
+    impl Service<http::Request<()>> for Recorder {
+        type Response = http::Response<()>;
+        type Error = std::convert::Infallible;
+        type Future = std::future::Ready<Result<Self::Response, Self::Error>>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, request: http::Request<()>) -> Self::Future {
+            *self.0.lock().unwrap() = request.extensions().get::<RequestId>().cloned();
+            std::future::ready(Ok(http::Response::new(())))
+        }
+    }
+
+    fn call(request_id: Option<&str>) -> (http::Response<()>, Option<RequestId>) {
+        let recorder = Recorder::default();
+        let mut service = RequestIdLayer::new().layer(recorder.clone());
+        let mut request = http::Request::builder().uri("/eigen.internal.v1.KernelGatewayService/GetJobStatus");
+        if let Some(request_id) = request_id {
+            request = request.header(REQUEST_ID_HEADER, request_id);
+        }
+        let mut response = service.call(request.body(()).expect("request"));
+        let Poll::Ready(response) = response.as_mut().poll(&mut Context::from_waker(Waker::noop())) else {
+            panic!("the recorder answers immediately");
+        };
+        let seen = recorder.0.lock().unwrap().clone();
+        (response.expect("infallible"), seen)
+    }
+
+    fn response_id(response: &http::Response<()>) -> &str {
+        response.headers()[REQUEST_ID_HEADER].to_str().expect("ascii request id")
+    }
+
+    #[test]
+    fn provided_request_id_is_echoed() {
+        let (response, seen) = call(Some("req-7f3a"));
+        assert_eq!(response_id(&response), "req-7f3a");
+        assert_eq!(seen.as_ref().map(RequestId::as_str), Some("req-7f3a"));
+    }
+
+    #[test]
+    fn missing_or_unusable_request_id_is_replaced_by_a_uuid() {
+        let oversized = "x".repeat(MAX_REQUEST_ID_LEN + 1);
+        for request_id in [None, Some(""), Some("has space"), Some(oversized.as_str())] {
+            let (response, seen) = call(request_id);
+            let generated = uuid::Uuid::parse_str(response_id(&response)).expect("generated id is a UUID");
+            assert_eq!(generated.get_version_num(), 4);
+            assert_eq!(seen.map(|id| id.to_string()), Some(generated.to_string()));
+        }
+    }
+}
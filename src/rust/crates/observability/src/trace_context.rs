@@ -0,0 +1,119 @@
+//! W3C trace context propagation across the gRPC boundary.
+//!
+//! Servers call [`extract_context`] on the request headers and [`set_parent`] on the
+//! span doing the work, so their spans join the caller's trace instead of starting
+//! a new one. Clients call [`inject_context`] to send their span's context along
+//! as a `traceparent` header. Without the OpenTelemetry layer installed by
+//! [`crate::init_tracing`], spans carry no context and both directions are no-ops.
+
+use std::collections::HashMap;
+
+use http::{HeaderMap, HeaderName, HeaderValue};
+use opentelemetry::propagation::{Extractor, Injector, TextMapPropagator};
+use opentelemetry::trace::TraceContextExt;
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use tracing::Span;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+pub use opentelemetry::Context;
+
+/// Header carrying the W3C trace context, in both directions.
+pub const TRACEPARENT_HEADER: &str = "traceparent";
+
+/// The caller's trace context from `traceparent` (and `tracestate`); `None` when the
+/// header is missing or malformed.
+pub fn extract_context(headers: &HeaderMap) -> Option<Context> {
+    valid(TraceContextPropagator::new().extract(&HeaderExtractor(headers)))
+}
+
+/// Trace context from a bare `traceparent` value, such as one carried in a
+/// request body; `None` when it is malformed.
+pub fn context_from_traceparent(traceparent: &str) -> Option<Context> {
+    let carrier = HashMap::from([(TRACEPARENT_HEADER.to_string(), traceparent.trim().to_string())]);
+    valid(TraceContextPropagator::new().extract(&carrier))
+}
+
+/// Makes `parent` the parent of `span`. Must be called before `span` is first entered.
+pub fn set_parent(span: &Span, parent: Context) {
+    if let Err(err) = span.set_parent(parent) {
+        tracing::debug!(error = %err, "cannot set the parent trace context of a span");
+    }
+}
+
+/// Writes the trace context of `span` into `headers` for an outgoing request.
+pub fn inject_context(span: &Span, headers: &mut HeaderMap) {
+    TraceContextPropagator::new().inject_context(&span.context(), &mut HeaderInjector(headers));
+}
+
+fn valid(context: Context) -> Option<Context> {
+    context.span().span_context().is_valid().then_some(context)
+}
+
+struct HeaderExtractor<'a>(&'a HeaderMap);
+
+impl Extractor for HeaderExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|value| value.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(HeaderName::as_str).collect()
+    }
+}
+
+struct HeaderInjector<'a>(&'a mut HeaderMap);
+
+impl Injector for HeaderInjector<'_> {
+    fn set(&mut self, key: &str, value: String) {
+        if let (Ok(name), Ok(value)) = (HeaderName::from_bytes(key.as_bytes()), HeaderValue::from_str(&value)) {
+            self.0.insert(name, value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::trace::TracerProvider as _;
+    use opentelemetry_sdk::trace::SdkTracerProvider;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    const TRACEPARENT: &str = "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01";
+
+    /// Runs `f` under a subscriber that gives spans OpenTelemetry contexts.
+    fn with_otel_layer(f: impl FnOnce()) {
+        let tracer = SdkTracerProvider::builder().build().tracer("trace-context-test");
+        let subscriber = tracing_subscriber::registry().with(tracing_opentelemetry::layer().with_tracer(tracer));
+        tracing::subscriber::with_default(subscriber, f);
+    }
+
+    fn trace_id(span: &Span) -> String {
+        span.context().span().span_context().trace_id().to_string()
+    }
+
+    #[test]
+    fn injected_traceparent_becomes_the_parent_of_the_job_pipeline_span() {
+        let mut headers = HeaderMap::new();
+        headers.insert(TRACEPARENT_HEADER, HeaderValue::from_static(TRACEPARENT));
+
+        with_otel_layer(|| {
+            let span = tracing::info_span!("job_pipeline");
+            set_parent(&span, extract_context(&headers).expect("valid traceparent"));
+            assert_eq!(trace_id(&span), "0af7651916cd43dd8448eb211c80319c");
+
+            let mut outgoing = HeaderMap::new();
+            inject_context(&span, &mut outgoing);
+            let sent = outgoing[TRACEPARENT_HEADER].to_str().expect("ascii traceparent");
+            assert!(sent.starts_with("00-0af7651916cd43dd8448eb211c80319c-"), "{sent}");
+            assert_ne!(sent, TRACEPARENT, "the child span has its own span id");
+        });
+    }
+
+    #[test]
+    fn missing_or_malformed_traceparent_yields_no_context() {
+        assert!(extract_context(&HeaderMap::new()).is_none());
+        assert!(context_from_traceparent("00-trace-789-parent-01").is_none());
+        let context = context_from_traceparent(TRACEPARENT).expect("valid traceparent");
+        assert_eq!(context.span().span_context().trace_id().to_string(), "0af7651916cd43dd8448eb211c80319c");
+    }
+}
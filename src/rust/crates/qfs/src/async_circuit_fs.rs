@@ -0,0 +1,340 @@
+//! Async facade over [`CircuitFsLocal`] for callers running on a tokio executor.
+//!
+//! Every store goes through the blocking implementation on the blocking thread pool,
+//! so the atomic-write guarantees (temp file in the target directory, `fsync`, then
+//! rename, sidecar after content) are exactly those of [`CircuitFsLocal`]. Plain
+//! reads of local files use `tokio::fs`; misses fall back to the blocking path so the
+//! MinIO read-through keeps working.
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::local_circuit_fs::{
+    CircuitFsError, CircuitFsLocal, CompiledArtifactProvenance, JobMeta, ReleaseEvidenceBundle,
+    ReleaseEvidenceManifest, ReleaseEvidenceProvenanceReport, ResultEnvelope, ResultManifest,
+    ResultsBundle, SourceBundle,
+};
+
+/// Cheaply cloneable async handle to a CircuitFS root.
+///
+/// Uses the same path helpers and [`CircuitFsError`] as [`CircuitFsLocal`]; arguments
+/// are taken by value because the work outlives the calling future's borrows.
+#[derive(Debug, Clone)]
+pub struct CircuitFsAsync {
+    inner: Arc<CircuitFsLocal>,
+}
+
+impl From<CircuitFsLocal> for CircuitFsAsync {
+    fn from(local: CircuitFsLocal) -> Self {
+        Self { inner: Arc::new(local) }
+    }
+}
+
+impl CircuitFsAsync {
+    pub fn new(root: impl AsRef<Path>) -> Self {
+        CircuitFsLocal::new(root).into()
+    }
+
+    pub fn root_path(&self) -> &Path {
+        self.inner.root_path()
+    }
+
+    /// The blocking implementation, for paths that are not on an executor.
+    pub fn blocking(&self) -> &CircuitFsLocal {
+        &self.inner
+    }
+
+    pub async fn write_bytes(&self, path: impl Into<PathBuf>, bytes: Vec<u8>) -> Result<(), CircuitFsError> {
+        let path = path.into();
+        self.run(move |fs| fs.write_bytes(path, &bytes)).await
+    }
+
+    pub async fn read_bytes(&self, path: impl AsRef<Path>) -> Result<Vec<u8>, CircuitFsError> {
+        let path = self.inner.resolve_path(path.as_ref());
+        match tokio::fs::read(&path).await {
+            Ok(bytes) => Ok(bytes),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => self.run(move |fs| fs.read_bytes(path)).await,
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    pub async fn object_exists(&self, path: impl Into<PathBuf>) -> Result<bool, CircuitFsError> {
+        let path = path.into();
+        self.run(move |fs| Ok(fs.object_exists(path))).await
+    }
+
+    pub async fn list_job_ids(&self) -> Result<Vec<String>, CircuitFsError> {
+        self.run(|fs| fs.list_job_ids()).await
+    }
+
+    pub async fn list_jobs(&self) -> Result<Vec<String>, CircuitFsError> {
+        self.run(|fs| fs.list_jobs()).await
+    }
+
+    pub async fn job_count(&self) -> Result<usize, CircuitFsError> {
+        self.run(|fs| fs.job_count()).await
+    }
+
+    pub async fn job_exists(&self, job_id: &str) -> Result<bool, CircuitFsError> {
+        let job_id = job_id.to_string();
+        self.run(move |fs| fs.job_exists(&job_id)).await
+    }
+
+    pub async fn delete_job(&self, job_id: &str) -> Result<(), CircuitFsError> {
+        let job_id = job_id.to_string();
+        self.run(move |fs| fs.delete_job(&job_id)).await
+    }
+
+    pub async fn ensure_job_layout(&self, job_id: &str) -> Result<(), CircuitFsError> {
+        let job_id = job_id.to_string();
+        self.run(move |fs| fs.ensure_job_layout(&job_id)).await
+    }
+
+    pub async fn append_log_line(&self, job_id: &str, stream: &str, line: &str) -> Result<(), CircuitFsError> {
+        let (job_id, stream, line) = (job_id.to_string(), stream.to_string(), line.to_string());
+        self.run(move |fs| fs.append_log_line(&job_id, &stream, &line)).await
+    }
+
+    pub async fn store_source_bundle(&self, job_id: &str, bundle: SourceBundle) -> Result<(), CircuitFsError> {
+        let job_id = job_id.to_string();
+        self.run(move |fs| fs.store_source_bundle(&job_id, &bundle)).await
+    }
+
+    pub async fn load_source_bundle(&self, job_id: &str) -> Result<SourceBundle, CircuitFsError> {
+        let job_id = job_id.to_string();
+        self.run(move |fs| fs.load_source_bundle(&job_id)).await
+    }
+
+    pub async fn store_compiled_artifacts_v1(
+        &self,
+        job_id: &str,
+        aqo_json: Vec<u8>,
+        qasm: Option<Vec<u8>>,
+        compile_report_json: Option<Vec<u8>>,
+        provenance: CompiledArtifactProvenance,
+    ) -> Result<(), CircuitFsError> {
+        let job_id = job_id.to_string();
+        self.run(move |fs| {
+            fs.store_compiled_artifacts_v1(
+                &job_id,
+                &aqo_json,
+                qasm.as_deref(),
+                compile_report_json.as_deref(),
+                provenance,
+            )
+        })
+        .await
+    }
+
+    pub async fn store_results_bundle(
+        &self,
+        job_id: &str,
+        envelope: ResultEnvelope,
+        producer_version: &str,
+    ) -> Result<(), CircuitFsError> {
+        let (job_id, producer_version) = (job_id.to_string(), producer_version.to_string());
+        self.run(move |fs| fs.store_results_bundle(&job_id, &envelope, &producer_version))
+            .await
+    }
+
+    pub async fn load_results_bundle(&self, job_id: &str) -> Result<ResultsBundle, CircuitFsError> {
+        let job_id = job_id.to_string();
+        self.run(move |fs| fs.load_results_bundle(&job_id)).await
+    }
+
+    pub async fn load_results_bundle_verified(&self, job_id: &str) -> Result<ResultsBundle, CircuitFsError> {
+        let job_id = job_id.to_string();
+        self.run(move |fs| fs.load_results_bundle_verified(&job_id)).await
+    }
+
+    pub async fn load_manifest(&self, job_id: &str) -> Result<ResultManifest, CircuitFsError> {
+        let job_id = job_id.to_string();
+        self.run(move |fs| fs.load_manifest(&job_id)).await
+    }
+
+    pub async fn store_release_evidence_bundle_v1(
+        &self,
+        job_id: &str,
+        bundle: ReleaseEvidenceBundle,
+        manifest: ReleaseEvidenceManifest,
+        provenance_report: ReleaseEvidenceProvenanceReport,
+    ) -> Result<(), CircuitFsError> {
+        let job_id = job_id.to_string();
+        self.run(move |fs| fs.store_release_evidence_bundle_v1(&job_id, &bundle, &manifest, &provenance_report))
+            .await
+    }
+
+    pub async fn store_job_meta(&self, meta: JobMeta) -> Result<(), CircuitFsError> {
+        self.run(move |fs| fs.store_job_meta(&meta)).await
+    }
+
+    pub async fn load_job_meta(&self, job_id: &str) -> Result<JobMeta, CircuitFsError> {
+        let job_id = job_id.to_string();
+        self.run(move |fs| fs.load_job_meta(&job_id)).await
+    }
+
+    pub async fn store_metrics_json(&self, job_id: &str, metrics: Vec<u8>) -> Result<(), CircuitFsError> {
+        let job_id = job_id.to_string();
+        self.run(move |fs| fs.store_metrics_json(&job_id, &metrics)).await
+    }
+
+    pub async fn store_error_details_json(&self, job_id: &str, details: Vec<u8>) -> Result<(), CircuitFsError> {
+        let job_id = job_id.to_string();
+        self.run(move |fs| fs.store_error_details_json(&job_id, &details)).await
+    }
+
+    pub async fn load_error_details_json(&self, job_id: &str) -> Result<Vec<u8>, CircuitFsError> {
+        let job_id = job_id.to_string();
+        self.run(move |fs| fs.load_error_details_json(&job_id)).await
+    }
+
+    /// Runs `op` against the blocking implementation on tokio's blocking pool.
+    pub(crate) async fn run<T, F>(&self, op: F) -> Result<T, CircuitFsError>
+    where
+        F: FnOnce(&CircuitFsLocal) -> Result<T, CircuitFsError> + Send + 'static,
+        T: Send + 'static,
+    {
+        let inner = Arc::clone(&self.inner);
+        tokio::task::spawn_blocking(move || op(&inner))
+            .await
+            .map_err(|err| CircuitFsError::Io(io::Error::other(err)))?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CompiledArtifactLineage;
+    use tempfile::tempdir;
+
+    fn envelope(job_id: &str) -> ResultEnvelope {
+        ResultEnvelope {
+            artifact_version: "1.0.0".to_string(),
+            schema_version: "scientific_result_bundle.v1".to_string(),
+            producer_version: "1.0.0".to_string(),
+            job_id: job_id.to_string(),
+            workload_kind: "QuantumJob".to_string(),
+            result_ref: "results/result.json".to_string(),
+            manifest_ref: "results/manifest.json".to_string(),
+            created_at_epoch_ms: 1_718_181_234_000,
+            retention_policy: "default".to_string(),
+            lineage: CompiledArtifactLineage::default(),
+            context: Default::default(),
+            summary: [("shots".to_string(), "1024".to_string())].into(),
+            measurements: Vec::new(),
+        }
+    }
+
+    fn source_bundle() -> SourceBundle {
+        SourceBundle {
+            job_yaml: "apiVersion: eigen/v1\nkind: QuantumJob\n".to_string(),
+            program_eigen_py: b"def main():\n    pass\n".to_vec(),
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn source_bundle_round_trips_and_is_immutable() {
+        let tempdir = tempdir().expect("tempdir");
+        let fs = CircuitFsAsync::from(CircuitFsLocal::new(tempdir.path()).with_test_job_id_prefix("job-"));
+
+        fs.store_source_bundle("job-src", source_bundle()).await.expect("store source bundle");
+        assert!(tempdir.path().join("jobs/job-src/input/job.yaml.sha256").exists());
+        assert_eq!(fs.load_source_bundle("job-src").await.expect("load"), source_bundle());
+
+        let err = fs
+            .store_source_bundle("job-src", source_bundle())
+            .await
+            .expect_err("source bundle must be immutable");
+        assert!(matches!(err, CircuitFsError::AlreadyExists { .. }));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn results_bundle_round_trips_with_verification() {
+        let tempdir = tempdir().expect("tempdir");
+        let fs = CircuitFsAsync::from(CircuitFsLocal::new(tempdir.path()).with_test_job_id_prefix("job-"));
+
+        fs.store_results_bundle("job-results", envelope("job-results"), "1.0.0")
+            .await
+            .expect("store results bundle");
+        let bundle = fs.load_results_bundle_verified("job-results").await.expect("verified load");
+        assert_eq!(bundle.envelope, envelope("job-results"));
+        assert_eq!(fs.load_manifest("job-results").await.expect("manifest").artifacts.len(), 3);
+        assert_eq!(
+            fs.read_bytes("qfs://jobs/job-results/results/result.json").await.expect("read"),
+            std::fs::read(tempdir.path().join("jobs/job-results/results/result.json")).expect("read std")
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn compiled_artifacts_and_logs_use_canonical_paths() {
+        let tempdir = tempdir().expect("tempdir");
+        let fs = CircuitFsAsync::from(CircuitFsLocal::new(tempdir.path()).with_test_job_id_prefix("job-"));
+
+        fs.store_compiled_artifacts_v1(
+            "job-compiled",
+            br#"{"ops":[]}"#.to_vec(),
+            Some(b"OPENQASM 3;".to_vec()),
+            None,
+            CompiledArtifactProvenance {
+                producer_identity: "eigen-compiler".to_string(),
+                contract_version: "1.0.0".to_string(),
+                compiler_version: "0.1.0".to_string(),
+                created_at: "2026-01-01T00:00:00Z".to_string(),
+                lineage: CompiledArtifactLineage::default(),
+            },
+        )
+        .await
+        .expect("store compiled artifacts");
+        fs.append_log_line("job-compiled", "stdout", "compiled").await.expect("append log");
+
+        let job_root = tempdir.path().join("jobs/job-compiled");
+        assert!(job_root.join("compiled/circuit.aqo.json").exists());
+        assert!(job_root.join("compiled/circuit.qasm").exists());
+        assert!(job_root.join("compiled/metadata.json").exists());
+        assert_eq!(
+            std::fs::read_to_string(job_root.join("logs/stdout.jsonl")).expect("read log"),
+            "compiled\n"
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn invalid_job_ids_are_rejected() {
+        let tempdir = tempdir().expect("tempdir");
+        let fs = CircuitFsAsync::from(CircuitFsLocal::new(tempdir.path()).with_test_job_id_prefix("job-"));
+        let err = fs
+            .store_source_bundle("../escape", source_bundle())
+            .await
+            .expect_err("path traversal");
+        assert!(matches!(err, CircuitFsError::InvalidJobId { .. }));
+        assert!(matches!(
+            fs.read_bytes(tempdir.path().join("missing")).await,
+            Err(CircuitFsError::NotFound { .. })
+        ));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn stores_one_hundred_jobs_concurrently() {
+        let tempdir = tempdir().expect("tempdir");
+        let fs = CircuitFsAsync::from(CircuitFsLocal::new(tempdir.path()).with_test_job_id_prefix("job-"));
+
+        let mut tasks = tokio::task::JoinSet::new();
+        for i in 0..100 {
+            let fs = fs.clone();
+            tasks.spawn(async move {
+                let job_id = format!("job-{i:03}");
+                fs.store_source_bundle(&job_id, source_bundle()).await?;
+                fs.store_results_bundle(&job_id, envelope(&job_id), "1.0.0").await?;
+                fs.load_results_bundle_verified(&job_id).await
+            });
+        }
+        while let Some(joined) = tasks.join_next().await {
+            joined.expect("task").expect("store and verify job");
+        }
+
+        let job_ids = fs.list_job_ids().await.expect("list jobs");
+        assert_eq!(job_ids.len(), 100);
+        assert_eq!(job_ids.first().map(String::as_str), Some("job-000"));
+        assert!(fs.blocking().verify_job_artifacts("job-099").expect("verify").is_ok());
+    }
+}
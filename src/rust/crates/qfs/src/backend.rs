@@ -0,0 +1,585 @@
+//! Storage abstraction over a CircuitFS root.
+//!
+//! [`CircuitFsBackend`] is the object-safe subset of [`CircuitFsLocal`] that job
+//! stores and the kernel pipeline use, so they can hold an `Arc<dyn CircuitFsBackend>`
+//! and run against [`MockCircuitFsBackend`] in tests. Paths are taken as `&Path`
+//! rather than `impl AsRef<Path>`; `qfs://` and `circuitfs://` refs are accepted
+//! wherever a path is.
+
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+use crate::local_circuit_fs::{
+    content_hash_hex, CircuitFsError, CircuitFsLocal, CompiledArtifactProvenance, JobMeta, ReleaseEvidenceBundle,
+    ReleaseEvidenceManifest, ReleaseEvidenceProvenanceReport, ResultArtifactDescriptor, ResultEnvelope,
+    ResultManifest, ResultsBundle, SourceBundle,
+};
+
+/// Per-job artifact storage with the layout described in `qfs-layout.md`.
+pub trait CircuitFsBackend: Send + Sync + fmt::Debug {
+    /// Local directory that job paths are resolved against.
+    fn root_path(&self) -> &Path;
+
+    fn read_bytes(&self, path: &Path) -> Result<Vec<u8>, CircuitFsError>;
+
+    fn write_bytes(&self, path: &Path, bytes: &[u8]) -> Result<(), CircuitFsError>;
+
+    /// Same as [`CircuitFsBackend::write_bytes`], also recording the content checksum.
+    fn write_bytes_checksummed(&self, path: &Path, bytes: &[u8]) -> Result<(), CircuitFsError>;
+
+    /// Every stored `qfs://` ref starting with `prefix`, sorted.
+    fn list_refs(&self, prefix: &str) -> Result<Vec<String>, CircuitFsError>;
+
+    /// Ids of the jobs with a layout under `jobs/`, sorted.
+    fn list_job_ids(&self) -> Result<Vec<String>, CircuitFsError>;
+
+    fn job_exists(&self, job_id: &str) -> Result<bool, CircuitFsError>;
+
+    /// Removes everything stored for the job; [`CircuitFsError::NotFound`] when it has nothing.
+    fn delete_job(&self, job_id: &str) -> Result<(), CircuitFsError>;
+
+    fn ensure_job_layout(&self, job_id: &str) -> Result<(), CircuitFsError>;
+
+    fn meta_json_path(&self, job_id: &str) -> Result<PathBuf, CircuitFsError>;
+
+    fn job_log_path(&self, job_id: &str, stream: &str) -> Result<PathBuf, CircuitFsError>;
+
+    fn append_log_line(&self, job_id: &str, stream: &str, line: &str) -> Result<(), CircuitFsError>;
+
+    fn store_source_bundle(&self, job_id: &str, bundle: &SourceBundle) -> Result<(), CircuitFsError>;
+
+    fn load_source_bundle(&self, job_id: &str) -> Result<SourceBundle, CircuitFsError>;
+
+    fn store_compiled_artifacts_v1(
+        &self,
+        job_id: &str,
+        aqo_json: &[u8],
+        qasm: Option<&[u8]>,
+        compile_report_json: Option<&[u8]>,
+        provenance: CompiledArtifactProvenance,
+    ) -> Result<(), CircuitFsError>;
+
+    fn load_compiled_aqo_json(&self, job_id: &str) -> Result<Vec<u8>, CircuitFsError>;
+
+    fn store_results_bundle(
+        &self,
+        job_id: &str,
+        envelope: &ResultEnvelope,
+        producer_version: &str,
+    ) -> Result<(), CircuitFsError>;
+
+    fn load_results_bundle(&self, job_id: &str) -> Result<ResultsBundle, CircuitFsError>;
+
+    fn load_manifest(&self, job_id: &str) -> Result<ResultManifest, CircuitFsError>;
+
+    fn store_job_meta(&self, meta: &JobMeta) -> Result<(), CircuitFsError>;
+
+    fn load_job_meta(&self, job_id: &str) -> Result<JobMeta, CircuitFsError>;
+
+    fn store_metrics_json(&self, job_id: &str, metrics: &[u8]) -> Result<(), CircuitFsError>;
+
+    fn store_error_details_json(&self, job_id: &str, details: &[u8]) -> Result<(), CircuitFsError>;
+
+    fn load_error_details_json(&self, job_id: &str) -> Result<Vec<u8>, CircuitFsError>;
+
+    fn store_release_evidence_bundle_v1(
+        &self,
+        job_id: &str,
+        bundle: &ReleaseEvidenceBundle,
+        manifest: &ReleaseEvidenceManifest,
+        provenance_report: &ReleaseEvidenceProvenanceReport,
+    ) -> Result<(), CircuitFsError>;
+}
+
+impl CircuitFsBackend for CircuitFsLocal {
+    fn root_path(&self) -> &Path {
+        CircuitFsLocal::root_path(self)
+    }
+
+    fn read_bytes(&self, path: &Path) -> Result<Vec<u8>, CircuitFsError> {
+        CircuitFsLocal::read_bytes(self, path)
+    }
+
+    fn write_bytes(&self, path: &Path, bytes: &[u8]) -> Result<(), CircuitFsError> {
+        CircuitFsLocal::write_bytes(self, path, bytes)
+    }
+
+    fn write_bytes_checksummed(&self, path: &Path, bytes: &[u8]) -> Result<(), CircuitFsError> {
+        CircuitFsLocal::write_bytes_checksummed(self, path, bytes)
+    }
+
+    fn list_refs(&self, prefix: &str) -> Result<Vec<String>, CircuitFsError> {
+        CircuitFsLocal::list_refs(self, prefix)
+    }
+
+    fn list_job_ids(&self) -> Result<Vec<String>, CircuitFsError> {
+        CircuitFsLocal::list_job_ids(self)
+    }
+
+    fn job_exists(&self, job_id: &str) -> Result<bool, CircuitFsError> {
+        CircuitFsLocal::job_exists(self, job_id)
+    }
+
+    fn delete_job(&self, job_id: &str) -> Result<(), CircuitFsError> {
+        CircuitFsLocal::delete_job(self, job_id)
+    }
+
+    fn ensure_job_layout(&self, job_id: &str) -> Result<(), CircuitFsError> {
+        CircuitFsLocal::ensure_job_layout(self, job_id)
+    }
+
+    fn meta_json_path(&self, job_id: &str) -> Result<PathBuf, CircuitFsError> {
+        CircuitFsLocal::meta_json_path(self, job_id)
+    }
+
+    fn job_log_path(&self, job_id: &str, stream: &str) -> Result<PathBuf, CircuitFsError> {
+        CircuitFsLocal::job_log_path(self, job_id, stream)
+    }
+
+    fn append_log_line(&self, job_id: &str, stream: &str, line: &str) -> Result<(), CircuitFsError> {
+        CircuitFsLocal::append_log_line(self, job_id, stream, line)
+    }
+
+    fn store_source_bundle(&self, job_id: &str, bundle: &SourceBundle) -> Result<(), CircuitFsError> {
+        CircuitFsLocal::store_source_bundle(self, job_id, bundle)
+    }
+
+    fn load_source_bundle(&self, job_id: &str) -> Result<SourceBundle, CircuitFsError> {
+        CircuitFsLocal::load_source_bundle(self, job_id)
+    }
+
+    fn store_compiled_artifacts_v1(
+        &self,
+        job_id: &str,
+        aqo_json: &[u8],
+        qasm: Option<&[u8]>,
+        compile_report_json: Option<&[u8]>,
+        provenance: CompiledArtifactProvenance,
+    ) -> Result<(), CircuitFsError> {
+        CircuitFsLocal::store_compiled_artifacts_v1(self, job_id, aqo_json, qasm, compile_report_json, provenance)
+    }
+
+    fn load_compiled_aqo_json(&self, job_id: &str) -> Result<Vec<u8>, CircuitFsError> {
+        CircuitFsLocal::load_compiled_aqo_json(self, job_id)
+    }
+
+    fn store_results_bundle(
+        &self,
+        job_id: &str,
+        envelope: &ResultEnvelope,
+        producer_version: &str,
+    ) -> Result<(), CircuitFsError> {
+        CircuitFsLocal::store_results_bundle(self, job_id, envelope, producer_version)
+    }
+
+    fn load_results_bundle(&self, job_id: &str) -> Result<ResultsBundle, CircuitFsError> {
+        CircuitFsLocal::load_results_bundle(self, job_id)
+    }
+
+    fn load_manifest(&self, job_id: &str) -> Result<ResultManifest, CircuitFsError> {
+        CircuitFsLocal::load_manifest(self, job_id)
+    }
+
+    fn store_job_meta(&self, meta: &JobMeta) -> Result<(), CircuitFsError> {
+        CircuitFsLocal::store_job_meta(self, meta)
+    }
+
+    fn load_job_meta(&self, job_id: &str) -> Result<JobMeta, CircuitFsError> {
+        CircuitFsLocal::load_job_meta(self, job_id)
+    }
+
+    fn store_metrics_json(&self, job_id: &str, metrics: &[u8]) -> Result<(), CircuitFsError> {
+        CircuitFsLocal::store_metrics_json(self, job_id, metrics)
+    }
+
+    fn store_error_details_json(&self, job_id: &str, details: &[u8]) -> Result<(), CircuitFsError> {
+        CircuitFsLocal::store_error_details_json(self, job_id, details)
+    }
+
+    fn load_error_details_json(&self, job_id: &str) -> Result<Vec<u8>, CircuitFsError> {
+        CircuitFsLocal::load_error_details_json(self, job_id)
+    }
+
+    fn store_release_evidence_bundle_v1(
+        &self,
+        job_id: &str,
+        bundle: &ReleaseEvidenceBundle,
+        manifest: &ReleaseEvidenceManifest,
+        provenance_report: &ReleaseEvidenceProvenanceReport,
+    ) -> Result<(), CircuitFsError> {
+        CircuitFsLocal::store_release_evidence_bundle_v1(self, job_id, bundle, manifest, provenance_report)
+    }
+}
+
+/// In-memory [`CircuitFsBackend`] for tests.
+///
+/// Objects live in a map keyed by their path relative to the root
+/// (`jobs/<job_id>/results/result.json`), using the same names as
+/// [`CircuitFsLocal`]. Nothing is compressed, no checksum sidecars or parquet files
+/// are written, and compiled artifacts carry no `metadata.json`.
+#[derive(Debug)]
+pub struct MockCircuitFsBackend {
+    root: PathBuf,
+    objects: Mutex<HashMap<String, Vec<u8>>>,
+    jobs: Mutex<BTreeSet<String>>,
+}
+
+impl Default for MockCircuitFsBackend {
+    fn default() -> Self {
+        Self::new("/mock-qfs")
+    }
+}
+
+impl MockCircuitFsBackend {
+    /// `root` is only used to build the paths the backend hands out; nothing is
+    /// written to it.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into(), objects: Mutex::default(), jobs: Mutex::default() }
+    }
+
+    /// Keys of every stored object, sorted.
+    pub fn keys(&self) -> Vec<String> {
+        let mut keys: Vec<String> = self.objects().keys().cloned().collect();
+        keys.sort();
+        keys
+    }
+
+    fn objects(&self) -> MutexGuard<'_, HashMap<String, Vec<u8>>> {
+        self.objects.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn jobs(&self) -> MutexGuard<'_, BTreeSet<String>> {
+        self.jobs.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Jobs laid out with `ensure_job_layout` plus those that only have objects
+    /// written through a path, like a directory created by a file write would.
+    fn job_ids(&self) -> BTreeSet<String> {
+        let mut job_ids = self.jobs().clone();
+        for key in self.objects().keys() {
+            if let Some(job_id) = key.strip_prefix("jobs/").and_then(|rest| rest.split('/').next()) {
+                job_ids.insert(job_id.to_string());
+            }
+        }
+        job_ids
+    }
+
+    fn key(&self, path: &Path) -> String {
+        let raw = path.to_string_lossy();
+        let relative = match raw.strip_prefix("qfs://").or_else(|| raw.strip_prefix("circuitfs://")) {
+            Some(relative) => relative.to_string(),
+            None => path.strip_prefix(&self.root).map_or_else(|_| raw.to_string(), |rel| rel.to_string_lossy().into()),
+        };
+        relative.replace('\\', "/").trim_start_matches('/').to_string()
+    }
+
+    fn job_key(&self, job_id: &str, rel: &str) -> Result<String, CircuitFsError> {
+        job_key(job_id, rel)
+    }
+
+    fn get(&self, key: &str) -> Result<Vec<u8>, CircuitFsError> {
+        self.objects().get(key).cloned().ok_or_else(|| CircuitFsError::NotFound { path: self.root.join(key) })
+    }
+
+    fn put(&self, key: String, bytes: &[u8]) {
+        self.objects().insert(key, bytes.to_vec());
+    }
+
+    /// Stores every `(key, bytes)` pair, or none of them when one already exists.
+    fn put_new(&self, objects: Vec<(String, Vec<u8>)>) -> Result<(), CircuitFsError> {
+        let mut stored = self.objects();
+        if let Some((key, _)) = objects.iter().find(|(key, _)| stored.contains_key(key)) {
+            return Err(CircuitFsError::AlreadyExists { path: self.root.join(key) });
+        }
+        stored.extend(objects);
+        Ok(())
+    }
+
+    fn get_json<T: serde::de::DeserializeOwned>(&self, key: &str) -> Result<T, CircuitFsError> {
+        serde_json::from_slice(&self.get(key)?).map_err(json_error)
+    }
+}
+
+impl CircuitFsBackend for MockCircuitFsBackend {
+    fn root_path(&self) -> &Path {
+        &self.root
+    }
+
+    fn read_bytes(&self, path: &Path) -> Result<Vec<u8>, CircuitFsError> {
+        self.get(&self.key(path))
+    }
+
+    fn write_bytes(&self, path: &Path, bytes: &[u8]) -> Result<(), CircuitFsError> {
+        self.put(self.key(path), bytes);
+        Ok(())
+    }
+
+    fn write_bytes_checksummed(&self, path: &Path, bytes: &[u8]) -> Result<(), CircuitFsError> {
+        self.write_bytes(path, bytes)
+    }
+
+    fn list_refs(&self, prefix: &str) -> Result<Vec<String>, CircuitFsError> {
+        Ok(self.keys().into_iter().map(|key| format!("qfs://{key}")).filter(|r| r.starts_with(prefix)).collect())
+    }
+
+    fn list_job_ids(&self) -> Result<Vec<String>, CircuitFsError> {
+        Ok(self.job_ids().into_iter().collect())
+    }
+
+    fn job_exists(&self, job_id: &str) -> Result<bool, CircuitFsError> {
+        self.job_key(job_id, "")?;
+        Ok(self.job_ids().contains(job_id))
+    }
+
+    fn delete_job(&self, job_id: &str) -> Result<(), CircuitFsError> {
+        let prefix = self.job_key(job_id, "")?;
+        if !self.job_ids().contains(job_id) {
+            return Err(CircuitFsError::NotFound { path: self.root.join(prefix) });
+        }
+        self.jobs().remove(job_id);
+        self.objects().retain(|key, _| !key.starts_with(&prefix));
+        Ok(())
+    }
+
+    fn ensure_job_layout(&self, job_id: &str) -> Result<(), CircuitFsError> {
+        self.job_key(job_id, "")?;
+        self.jobs().insert(job_id.to_string());
+        Ok(())
+    }
+
+    fn meta_json_path(&self, job_id: &str) -> Result<PathBuf, CircuitFsError> {
+        Ok(self.root.join(self.job_key(job_id, "meta/job.json")?))
+    }
+
+    fn job_log_path(&self, job_id: &str, stream: &str) -> Result<PathBuf, CircuitFsError> {
+        Ok(self.root.join(log_key(job_id, stream)?))
+    }
+
+    fn append_log_line(&self, job_id: &str, stream: &str, line: &str) -> Result<(), CircuitFsError> {
+        let key = self.key(&self.job_log_path(job_id, stream)?);
+        self.ensure_job_layout(job_id)?;
+        let mut objects = self.objects();
+        let log = objects.entry(key).or_default();
+        log.extend_from_slice(line.trim_end_matches('\n').as_bytes());
+        log.push(b'\n');
+        Ok(())
+    }
+
+    fn store_source_bundle(&self, job_id: &str, bundle: &SourceBundle) -> Result<(), CircuitFsError> {
+        self.ensure_job_layout(job_id)?;
+        let mut objects = vec![(self.job_key(job_id, "input/job.yaml")?, bundle.job_yaml.clone().into_bytes())];
+        if !bundle.program_eigen_py.is_empty() {
+            objects.push((self.job_key(job_id, "input/program.eigen.py")?, bundle.program_eigen_py.clone()));
+        }
+        self.put_new(objects)
+    }
+
+    fn load_source_bundle(&self, job_id: &str) -> Result<SourceBundle, CircuitFsError> {
+        let job_yaml = String::from_utf8(self.get(&self.job_key(job_id, "input/job.yaml")?)?)
+            .map_err(|err| CircuitFsError::Io(io::Error::new(io::ErrorKind::InvalidData, err)))?;
+        let program_eigen_py = self.get(&self.job_key(job_id, "input/program.eigen.py")?).unwrap_or_default();
+        Ok(SourceBundle { job_yaml, program_eigen_py })
+    }
+
+    fn store_compiled_artifacts_v1(
+        &self,
+        job_id: &str,
+        aqo_json: &[u8],
+        qasm: Option<&[u8]>,
+        compile_report_json: Option<&[u8]>,
+        _provenance: CompiledArtifactProvenance,
+    ) -> Result<(), CircuitFsError> {
+        self.ensure_job_layout(job_id)?;
+        let mut objects = vec![(self.job_key(job_id, "compiled/circuit.aqo.json")?, aqo_json.to_vec())];
+        if let Some(qasm) = qasm {
+            objects.push((self.job_key(job_id, "compiled/circuit.qasm")?, qasm.to_vec()));
+        }
+        if let Some(report) = compile_report_json {
+            objects.push((self.job_key(job_id, "compiled/compile_report.json")?, report.to_vec()));
+        }
+        self.put_new(objects)
+    }
+
+    fn load_compiled_aqo_json(&self, job_id: &str) -> Result<Vec<u8>, CircuitFsError> {
+        self.get(&self.job_key(job_id, "compiled/circuit.aqo.json")?)
+    }
+
+    fn store_results_bundle(
+        &self,
+        job_id: &str,
+        envelope: &ResultEnvelope,
+        producer_version: &str,
+    ) -> Result<(), CircuitFsError> {
+        self.ensure_job_layout(job_id)?;
+        let objects = results_bundle_objects(envelope, producer_version)?
+            .into_iter()
+            .map(|(rel, bytes)| Ok((self.job_key(job_id, rel)?, bytes)))
+            .collect::<Result<_, CircuitFsError>>()?;
+        self.put_new(objects)
+    }
+
+    fn load_results_bundle(&self, job_id: &str) -> Result<ResultsBundle, CircuitFsError> {
+        Ok(ResultsBundle { envelope: self.get_json(&self.job_key(job_id, "results/result.json")?)? })
+    }
+
+    fn load_manifest(&self, job_id: &str) -> Result<ResultManifest, CircuitFsError> {
+        self.get_json(&self.job_key(job_id, "results/manifest.json")?)
+    }
+
+    fn store_job_meta(&self, meta: &JobMeta) -> Result<(), CircuitFsError> {
+        self.ensure_job_layout(&meta.job_id)?;
+        let bytes = serde_json::to_vec_pretty(meta).map_err(json_error)?;
+        self.put(self.job_key(&meta.job_id, "meta/job.json")?, &bytes);
+        Ok(())
+    }
+
+    fn load_job_meta(&self, job_id: &str) -> Result<JobMeta, CircuitFsError> {
+        let key = self.job_key(job_id, "meta/job.json")?;
+        let meta: JobMeta = self.get_json(&key)?;
+        meta.check_schema_version(Path::new(&key))?;
+        Ok(meta)
+    }
+
+    fn store_metrics_json(&self, job_id: &str, metrics: &[u8]) -> Result<(), CircuitFsError> {
+        self.ensure_job_layout(job_id)?;
+        self.put_new(vec![(self.job_key(job_id, "observability/metrics.json")?, metrics.to_vec())])
+    }
+
+    fn store_error_details_json(&self, job_id: &str, details: &[u8]) -> Result<(), CircuitFsError> {
+        self.ensure_job_layout(job_id)?;
+        self.put(self.job_key(job_id, "results/error.json")?, details);
+        Ok(())
+    }
+
+    fn load_error_details_json(&self, job_id: &str) -> Result<Vec<u8>, CircuitFsError> {
+        self.get(&self.job_key(job_id, "results/error.json")?)
+    }
+
+    fn store_release_evidence_bundle_v1(
+        &self,
+        job_id: &str,
+        bundle: &ReleaseEvidenceBundle,
+        manifest: &ReleaseEvidenceManifest,
+        provenance_report: &ReleaseEvidenceProvenanceReport,
+    ) -> Result<(), CircuitFsError> {
+        self.ensure_job_layout(job_id)?;
+        self.put_new(vec![
+            (
+                self.job_key(job_id, "meta/release_evidence/bundle.json")?,
+                serde_json::to_vec_pretty(bundle).map_err(json_error)?,
+            ),
+            (
+                self.job_key(job_id, "meta/release_evidence/manifest.json")?,
+                serde_json::to_vec_pretty(manifest).map_err(json_error)?,
+            ),
+            (
+                self.job_key(job_id, "meta/release_evidence/provenance.json")?,
+                serde_json::to_vec_pretty(provenance_report).map_err(json_error)?,
+            ),
+        ])
+    }
+}
+
+/// Key of `rel` under the job's directory, `jobs/<job_id>/<rel>`, after checking that
+/// the id cannot escape `jobs/`.
+pub(crate) fn job_key(job_id: &str, rel: &str) -> Result<String, CircuitFsError> {
+    let valid_chars = job_id.chars().all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '.' | '_' | '-'));
+    if job_id.is_empty() || job_id.contains("..") || !valid_chars {
+        return Err(CircuitFsError::InvalidJobId { job_id: job_id.to_string() });
+    }
+    Ok(format!("jobs/{job_id}/{rel}"))
+}
+
+/// Key of the `logs/<stream>.jsonl` file of the job.
+pub(crate) fn log_key(job_id: &str, stream: &str) -> Result<String, CircuitFsError> {
+    let valid_chars = stream.chars().all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '.' | '_' | '-'));
+    if stream.is_empty() || stream.starts_with('.') || stream.contains("..") || !valid_chars {
+        return Err(CircuitFsError::InvalidLogName { log_name: stream.to_string() });
+    }
+    job_key(job_id, &format!("logs/{stream}.jsonl"))
+}
+
+/// The uncompressed objects of a results bundle, by path relative to the job root:
+/// `result.json` (and its `envelope.json` alias), the summary as `metadata.json`, and
+/// a `manifest.json` hashing both.
+pub(crate) fn results_bundle_objects(
+    envelope: &ResultEnvelope,
+    producer_version: &str,
+) -> Result<Vec<(&'static str, Vec<u8>)>, CircuitFsError> {
+    let envelope_bytes = serde_json::to_vec_pretty(envelope).map_err(json_error)?;
+    let metadata_bytes = serde_json::to_vec_pretty(&envelope.summary).map_err(json_error)?;
+    let manifest = ResultManifest {
+        artifact_version: envelope.artifact_version.clone(),
+        producer_version: producer_version.to_string(),
+        schema_version: "result_manifest.v1".to_string(),
+        created_at_epoch_ms: envelope.created_at_epoch_ms,
+        retention_policy: envelope.retention_policy.clone(),
+        artifacts: vec![
+            ResultArtifactDescriptor {
+                path: "results/result.json".to_string(),
+                content_hash: content_hash_hex(&envelope_bytes),
+                size_bytes: envelope_bytes.len() as u64,
+            },
+            ResultArtifactDescriptor {
+                path: "results/metadata.json".to_string(),
+                content_hash: content_hash_hex(&metadata_bytes),
+                size_bytes: metadata_bytes.len() as u64,
+            },
+        ],
+    };
+    let manifest_bytes = serde_json::to_vec_pretty(&manifest).map_err(json_error)?;
+    Ok(vec![
+        ("results/result.json", envelope_bytes.clone()),
+        ("results/envelope.json", envelope_bytes),
+        ("results/metadata.json", metadata_bytes),
+        ("results/manifest.json", manifest_bytes),
+    ])
+}
+
+pub(crate) fn json_error(err: serde_json::Error) -> CircuitFsError {
+    CircuitFsError::Io(io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use tempfile::tempdir;
+
+    fn exercise(backend: Arc<dyn CircuitFsBackend>) {
+        let bundle = SourceBundle { job_yaml: "name: bell\n".to_string(), program_eigen_py: b"h(0)\n".to_vec() };
+        backend.store_source_bundle("job-backend", &bundle).expect("store source");
+        assert_eq!(backend.load_source_bundle("job-backend").expect("load source"), bundle);
+        let err = backend.store_source_bundle("job-backend", &bundle).expect_err("source is immutable");
+        assert!(matches!(err, CircuitFsError::AlreadyExists { .. }));
+
+        backend.append_log_line("job-backend", "kernel", "one").expect("append");
+        backend.append_log_line("job-backend", "kernel", "two\n").expect("append");
+        let log_path = backend.job_log_path("job-backend", "kernel").expect("log path");
+        assert_eq!(backend.read_bytes(&log_path).expect("read log"), b"one\ntwo\n");
+        assert!(backend.list_refs("qfs://jobs/job-backend/logs/").expect("refs").contains(
+            &"qfs://jobs/job-backend/logs/kernel.jsonl".to_string()
+        ));
+
+        let meta_path = backend.meta_json_path("job-backend").expect("meta path");
+        backend.write_bytes_checksummed(&meta_path, b"{}").expect("write meta");
+        assert_eq!(backend.read_bytes(Path::new("qfs://jobs/job-backend/meta/job.json")).expect("read ref"), b"{}");
+
+        assert_eq!(backend.list_job_ids().expect("list"), vec!["job-backend".to_string()]);
+        assert!(matches!(backend.ensure_job_layout("../escape"), Err(CircuitFsError::InvalidJobId { .. })));
+        backend.delete_job("job-backend").expect("delete");
+        assert!(!backend.job_exists("job-backend").expect("exists"));
+        assert!(matches!(backend.load_source_bundle("job-backend"), Err(CircuitFsError::NotFound { .. })));
+        assert!(matches!(backend.delete_job("job-backend"), Err(CircuitFsError::NotFound { .. })));
+    }
+
+    #[test]
+    fn local_and_mock_backends_behave_alike() {
+        let tempdir = tempdir().expect("tempdir");
+        exercise(Arc::new(CircuitFsLocal::new(tempdir.path()).with_test_job_id_prefix("job-")));
+        exercise(Arc::new(MockCircuitFsBackend::default()));
+    }
+}
@@ -0,0 +1,334 @@
+//! Content checksum verification for CircuitFS job artifacts.
+//!
+//! Immutable artifacts (source bundle, compiled artifacts, results bundle) are written
+//! with a `sha256sum`-compatible `<file>.sha256` sidecar next to them. Verification
+//! walks the job directory, pairs every sidecar with its artifact and recomputes the
+//! digest, so bundles copied between machines can be checked with either this API or
+//! `sha256sum -c`.
+//!
+//! Single artifacts can be checked with [`CircuitFsLocal::verify_artifact`],
+//! [`CircuitFsLocal::verify_all_artifacts`] lists everything that fails, and
+//! [`CircuitFsLocal::load_results_bundle_verified`] refuses to return a results
+//! bundle whose files no longer match their sidecars.
+
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+use crate::local_circuit_fs::{
+    CircuitFsError, CircuitFsLocal, ResultsBundle, checksum_sidecar_path, content_hash_hex,
+};
+
+const SIDECAR_SUFFIX: &str = ".sha256";
+
+/// Outcome of [`CircuitFsLocal::verify_job_artifacts`].
+///
+/// Paths are relative to the job root (e.g. `results/result.json`) and sorted.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VerifyReport {
+    /// Artifacts whose content matches the recorded checksum.
+    pub matching: Vec<String>,
+    /// Artifacts whose content no longer matches the recorded checksum, or whose
+    /// sidecar cannot be parsed.
+    pub mismatched: Vec<String>,
+    /// Artifacts that have a checksum sidecar but no content.
+    pub missing: Vec<String>,
+}
+
+impl VerifyReport {
+    /// Returns `true` when every checksummed artifact is present and intact.
+    pub fn is_ok(&self) -> bool {
+        self.mismatched.is_empty() && self.missing.is_empty()
+    }
+}
+
+impl CircuitFsLocal {
+    /// Recomputes the SHA-256 of every checksummed artifact of `job_id` and compares
+    /// it against its sidecar.
+    pub fn verify_job_artifacts(&self, job_id: &str) -> Result<VerifyReport, CircuitFsError> {
+        let job_root = self.job_root_path(job_id)?;
+        if !job_root.exists() {
+            return Err(CircuitFsError::NotFound { path: job_root });
+        }
+
+        let mut report = VerifyReport::default();
+        let mut stack = vec![job_root.clone()];
+        while let Some(dir) = stack.pop() {
+            for entry in fs::read_dir(&dir)? {
+                let entry = entry?;
+                let path = entry.path();
+                if entry.file_type()?.is_dir() {
+                    stack.push(path);
+                    continue;
+                }
+                let Some(artifact_name) = path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .and_then(|name| name.strip_suffix(SIDECAR_SUFFIX))
+                else {
+                    continue;
+                };
+                let artifact_path = path.with_file_name(artifact_name);
+                let relative = relative_ref(&job_root, &artifact_path);
+                if !artifact_path.exists() {
+                    report.missing.push(relative);
+                    continue;
+                }
+                let expected = sidecar_digest(&fs::read(&path)?);
+                if content_hash_hex(&fs::read(&artifact_path)?) == expected {
+                    report.matching.push(relative);
+                } else {
+                    report.mismatched.push(relative);
+                }
+            }
+        }
+
+        report.matching.sort();
+        report.mismatched.sort();
+        report.missing.sort();
+        Ok(report)
+    }
+
+    /// Checks one artifact of `job_id` against its sidecar.
+    ///
+    /// `artifact` is relative to the job root (e.g. `results/result.json`). Returns
+    /// `Ok(false)` on a digest mismatch and `NotFound` when either the artifact or its
+    /// sidecar is absent.
+    pub fn verify_artifact(&self, job_id: &str, artifact: &str) -> Result<bool, CircuitFsError> {
+        let relative = Path::new(artifact);
+        if !relative.components().all(|component| matches!(component, Component::Normal(_))) {
+            return Err(CircuitFsError::Io(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("artifact path must be relative to the job root: {artifact}"),
+            )));
+        }
+        match self.verify_checksum(&self.job_root_path(job_id)?.join(relative)) {
+            Ok(()) => Ok(true),
+            Err(CircuitFsError::ChecksumMismatch { .. }) => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Paths of the artifacts of `job_id` that cannot be trusted, sorted: content
+    /// that no longer matches its sidecar, sidecars whose content is gone, and files
+    /// written by the `store_*` methods that have no sidecar at all.
+    pub fn verify_all_artifacts(&self, job_id: &str) -> Result<Vec<PathBuf>, CircuitFsError> {
+        let report = self.verify_job_artifacts(job_id)?;
+        let job_root = self.job_root_path(job_id)?;
+        let mut failed: Vec<PathBuf> =
+            report.mismatched.iter().chain(&report.missing).map(|relative| job_root.join(relative)).collect();
+        for path in self.checksummed_artifact_paths(job_id)? {
+            if path.exists() && !checksum_sidecar_path(&path).exists() {
+                failed.push(path);
+            }
+        }
+        failed.sort();
+        Ok(failed)
+    }
+
+    /// Loads the results bundle after checking `results.parquet`, `results/result.json`,
+    /// `results/envelope.json` and `results/manifest.json` against their sidecars. The
+    /// JSON documents are checked in whichever form, plain or `.zst`, was stored.
+    ///
+    /// Fails with [`CircuitFsError::ChecksumMismatch`] on the first tampered file.
+    pub fn load_results_bundle_verified(&self, job_id: &str) -> Result<ResultsBundle, CircuitFsError> {
+        for path in [
+            self.results_parquet_path(job_id)?,
+            self.stored_compressible_path(&self.result_json_path(job_id)?),
+            self.stored_compressible_path(&self.result_envelope_path(job_id)?),
+            self.result_manifest_path(job_id)?,
+        ] {
+            self.verify_checksum(&path)?;
+        }
+        self.load_results_bundle(job_id)
+    }
+
+    fn verify_checksum(&self, path: &Path) -> Result<(), CircuitFsError> {
+        let expected = sidecar_digest(&self.read_bytes(checksum_sidecar_path(path))?);
+        let actual = content_hash_hex(&self.read_bytes(path)?);
+        if actual != expected {
+            return Err(CircuitFsError::ChecksumMismatch {
+                path: path.to_path_buf(),
+                expected,
+                actual,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Extracts the digest from a `sha256sum`-style `<hex>  <file name>` line.
+fn sidecar_digest(sidecar: &[u8]) -> String {
+    String::from_utf8_lossy(sidecar)
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn relative_ref(job_root: &Path, path: &Path) -> String {
+    path.strip_prefix(job_root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ResultEnvelope, SourceBundle};
+    use tempfile::tempdir;
+
+    fn store_fixture(fs: &CircuitFsLocal, job_id: &str) {
+        fs.store_source_bundle(
+            job_id,
+            &SourceBundle {
+                job_yaml: "kind: QuantumJob\n".to_string(),
+                program_eigen_py: b"def main():\n    pass\n".to_vec(),
+            },
+        )
+        .expect("store source bundle");
+    }
+
+    #[test]
+    fn verify_reports_matching_artifacts_for_intact_job() {
+        let tempdir = tempdir().expect("tempdir");
+        let fs = CircuitFsLocal::new(tempdir.path()).with_test_job_id_prefix("job-");
+        store_fixture(&fs, "job-intact");
+
+        let sidecar = tempdir.path().join("jobs/job-intact/input/job.yaml.sha256");
+        let sidecar_text = std::fs::read_to_string(&sidecar).expect("read sidecar");
+        assert!(sidecar_text.ends_with("  job.yaml\n"));
+
+        let report = fs.verify_job_artifacts("job-intact").expect("verify");
+        assert!(report.is_ok());
+        assert_eq!(report.matching, vec!["input/job.yaml", "input/program.eigen.py"]);
+    }
+
+    #[test]
+    fn verify_detects_corrupted_and_missing_artifacts() {
+        let tempdir = tempdir().expect("tempdir");
+        let fs = CircuitFsLocal::new(tempdir.path()).with_test_job_id_prefix("job-");
+        store_fixture(&fs, "job-corrupt");
+
+        let input_dir = tempdir.path().join("jobs/job-corrupt/input");
+        std::fs::write(input_dir.join("job.yaml"), "kind: Tampered\n").expect("corrupt job.yaml");
+        std::fs::remove_file(input_dir.join("program.eigen.py")).expect("remove program");
+
+        let report = fs.verify_job_artifacts("job-corrupt").expect("verify");
+        assert!(!report.is_ok());
+        assert!(report.matching.is_empty());
+        assert_eq!(report.mismatched, vec!["input/job.yaml"]);
+        assert_eq!(report.missing, vec!["input/program.eigen.py"]);
+    }
+
+    #[test]
+    fn verify_unknown_job_is_not_found() {
+        let tempdir = tempdir().expect("tempdir");
+        let fs = CircuitFsLocal::new(tempdir.path()).with_test_job_id_prefix("job-");
+        let err = fs.verify_job_artifacts("job-missing").expect_err("unknown job");
+        assert!(matches!(err, CircuitFsError::NotFound { .. }));
+    }
+
+    #[test]
+    fn verify_all_artifacts_lists_corrupted_and_unchecksummed_files() {
+        let tempdir = tempdir().expect("tempdir");
+        let fs = CircuitFsLocal::new(tempdir.path()).with_test_job_id_prefix("job-");
+        store_fixture(&fs, "job-all");
+        fs.store_error_details_json("job-all", br#"{"error_code":"RUNTIME_FAILED"}"#).expect("store error details");
+        fs.append_log_line("job-all", "stdout", "logs are not checksummed").expect("append log");
+        assert_eq!(fs.verify_all_artifacts("job-all").expect("verify"), Vec::<PathBuf>::new());
+
+        let job_root = tempdir.path().join("jobs/job-all");
+        std::fs::write(job_root.join("input/program.eigen.py"), "def main():\n    raise\n").expect("corrupt program");
+        assert!(!fs.verify_artifact("job-all", "input/program.eigen.py").expect("verify program"));
+        std::fs::remove_file(job_root.join("results/error.json.sha256")).expect("drop sidecar");
+
+        assert_eq!(
+            fs.verify_all_artifacts("job-all").expect("verify"),
+            vec![job_root.join("input/program.eigen.py"), job_root.join("results/error.json")]
+        );
+    }
+
+    fn store_results_fixture(fs: &CircuitFsLocal, job_id: &str) {
+        let envelope = ResultEnvelope {
+            artifact_version: "1.0.0".to_string(),
+            schema_version: "scientific_result_bundle.v1".to_string(),
+            producer_version: "1.0.0".to_string(),
+            job_id: job_id.to_string(),
+            workload_kind: "QuantumJob".to_string(),
+            result_ref: "results/result.json".to_string(),
+            manifest_ref: "results/manifest.json".to_string(),
+            created_at_epoch_ms: 1_718_181_234_000,
+            retention_policy: "default".to_string(),
+            lineage: Default::default(),
+            context: Default::default(),
+            summary: [("shots".to_string(), "1024".to_string())].into(),
+            measurements: Vec::new(),
+        };
+        fs.store_results_bundle(job_id, &envelope, "1.0.0")
+            .expect("store results bundle");
+    }
+
+    #[test]
+    fn verified_load_rejects_tampered_results_while_plain_load_succeeds() {
+        let tempdir = tempdir().expect("tempdir");
+        let fs = CircuitFsLocal::new(tempdir.path()).with_test_job_id_prefix("job-");
+        store_results_fixture(&fs, "job-tamper");
+        assert!(fs.load_results_bundle_verified("job-tamper").is_ok());
+
+        // The counts live in the envelope summary of `results/result.json`.
+        let result_json = tempdir.path().join("jobs/job-tamper/results/result.json");
+        let tampered = std::fs::read_to_string(&result_json)
+            .expect("read result.json")
+            .replace("\"1024\"", "\"4096\"");
+        std::fs::write(&result_json, tampered).expect("tamper result.json");
+
+        let err = fs
+            .load_results_bundle_verified("job-tamper")
+            .expect_err("tampered bundle");
+        match err {
+            CircuitFsError::ChecksumMismatch { path, expected, actual } => {
+                assert_eq!(path, result_json);
+                assert_ne!(expected, actual);
+            }
+            other => panic!("unexpected error: {other}"),
+        }
+
+        let bundle = fs.load_results_bundle("job-tamper").expect("plain load");
+        assert_eq!(bundle.envelope.summary["shots"], "4096");
+    }
+
+    #[test]
+    fn verify_artifact_checks_a_single_sidecar() {
+        let tempdir = tempdir().expect("tempdir");
+        let fs = CircuitFsLocal::new(tempdir.path()).with_test_job_id_prefix("job-");
+        store_results_fixture(&fs, "job-single");
+
+        assert!(fs.verify_artifact("job-single", "results/manifest.json").expect("verify"));
+        std::fs::write(tempdir.path().join("jobs/job-single/results/manifest.json"), "{}")
+            .expect("tamper manifest");
+        assert!(!fs.verify_artifact("job-single", "results/manifest.json").expect("verify"));
+
+        let err = fs
+            .verify_artifact("job-single", "results/missing.json")
+            .expect_err("missing artifact");
+        assert!(matches!(err, CircuitFsError::NotFound { .. }));
+        assert!(fs.verify_artifact("job-single", "../job-other/results.parquet").is_err());
+    }
+
+    #[test]
+    fn load_manifest_lists_result_artifacts_with_sizes() {
+        let tempdir = tempdir().expect("tempdir");
+        let fs = CircuitFsLocal::new(tempdir.path()).with_test_job_id_prefix("job-");
+        store_results_fixture(&fs, "job-manifest");
+
+        let manifest = fs.load_manifest("job-manifest").expect("load manifest");
+        let paths: Vec<_> = manifest.artifacts.iter().map(|artifact| artifact.path.as_str()).collect();
+        assert_eq!(paths, vec!["results.parquet", "results/result.json", "results/metadata.json"]);
+        let result_json = std::fs::read(tempdir.path().join("jobs/job-manifest/results/result.json"))
+            .expect("read result.json");
+        assert_eq!(manifest.artifacts[1].size_bytes, result_json.len() as u64);
+        assert_eq!(manifest.artifacts[1].content_hash, content_hash_hex(&result_json));
+    }
+}
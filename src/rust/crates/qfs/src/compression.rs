@@ -0,0 +1,156 @@
+//! Optional zstd compression of the large CircuitFS artifacts.
+//!
+//! With [`Compression::Zstd`], `compiled/circuit.aqo.json`, `results/result.json`
+//! and `results/envelope.json` are written zstd-compressed under their usual name
+//! plus `.zst` (e.g. `compiled/circuit.aqo.json.zst`). Checksum sidecars and the
+//! result manifest describe the compressed file. Loaders accept either form, so one
+//! root can hold jobs written with and without compression.
+
+use std::borrow::Cow;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const ZSTD_EXTENSION: &str = "zst";
+
+/// How [`crate::CircuitFsLocal`] stores its compressible artifacts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Compression {
+    /// Artifacts are written as is.
+    #[default]
+    None,
+    /// Artifacts are zstd-compressed at the given level (1 to 22; 0 uses zstd's default).
+    Zstd(i32),
+}
+
+impl Compression {
+    /// Where an artifact normally stored at `path` is written.
+    pub(crate) fn stored_path(self, path: &Path) -> PathBuf {
+        match self {
+            Self::None => path.to_path_buf(),
+            Self::Zstd(_) => zstd_path(path),
+        }
+    }
+
+    /// The bytes to write for `bytes`.
+    pub(crate) fn encode(self, bytes: &[u8]) -> io::Result<Cow<'_, [u8]>> {
+        match self {
+            Self::None => Ok(Cow::Borrowed(bytes)),
+            Self::Zstd(level) => zstd::encode_all(bytes, level).map(Cow::Owned),
+        }
+    }
+}
+
+/// `path` with `.zst` appended to its file name.
+pub(crate) fn zstd_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".");
+    name.push(ZSTD_EXTENSION);
+    PathBuf::from(name)
+}
+
+/// The content of a file read from `path`, decompressed when it is a `.zst` file.
+pub(crate) fn decode(path: &Path, bytes: Vec<u8>) -> io::Result<Vec<u8>> {
+    if path.extension().is_some_and(|extension| extension == ZSTD_EXTENSION) {
+        zstd::decode_all(bytes.as_slice())
+    } else {
+        Ok(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CircuitFsLocal, CompiledArtifactProvenance, ResultEnvelope};
+    use tempfile::tempdir;
+
+    fn compressed_fs(root: &Path) -> CircuitFsLocal {
+        CircuitFsLocal::new_with_compression(root, Compression::Zstd(3)).with_test_job_id_prefix("job-")
+    }
+
+    fn envelope(job_id: &str) -> ResultEnvelope {
+        ResultEnvelope {
+            artifact_version: "1.0.0".to_string(),
+            schema_version: "scientific_result_bundle.v1".to_string(),
+            producer_version: "1.0.0".to_string(),
+            job_id: job_id.to_string(),
+            workload_kind: "QuantumJob".to_string(),
+            result_ref: "results/result.json".to_string(),
+            manifest_ref: "results/manifest.json".to_string(),
+            created_at_epoch_ms: 1_718_181_234_000,
+            retention_policy: "default".to_string(),
+            lineage: Default::default(),
+            context: Default::default(),
+            summary: (0..64).map(|state| (format!("{state:06b}"), "16".to_string())).collect(),
+            measurements: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn compiled_aqo_round_trips_through_a_zst_file() {
+        let tempdir = tempdir().expect("tempdir");
+        let fs = compressed_fs(tempdir.path());
+        let aqo_json = format!(r#"{{"gates":[{}]}}"#, vec![r#"{"op":"h","q":[0]}"#; 200].join(",")).into_bytes();
+        let provenance = CompiledArtifactProvenance {
+            producer_identity: "compiler-service".to_string(),
+            contract_version: "1.0.0".to_string(),
+            compiler_version: "1.0.0".to_string(),
+            created_at: "2026-06-12T00:00:00Z".to_string(),
+            lineage: Default::default(),
+        };
+        fs.store_compiled_artifacts_v1("job-zstd", &aqo_json, None, None, provenance)
+            .expect("store compiled artifacts");
+
+        let compiled = tempdir.path().join("jobs/job-zstd/compiled");
+        assert!(!compiled.join("circuit.aqo.json").exists());
+        let on_disk = std::fs::read(compiled.join("circuit.aqo.json.zst")).expect("read zst");
+        assert_ne!(on_disk, aqo_json);
+        assert!(on_disk.len() < aqo_json.len());
+        assert_eq!(fs.load_compiled_aqo_json("job-zstd").expect("load aqo"), aqo_json);
+        assert!(fs.verify_all_artifacts("job-zstd").expect("verify").is_empty());
+    }
+
+    #[test]
+    fn results_bundle_round_trips_compressed_and_plain_roots_read_both() {
+        let tempdir = tempdir().expect("tempdir");
+        let fs = compressed_fs(tempdir.path());
+        let envelope = envelope("job-zstd-results");
+        fs.store_results_bundle("job-zstd-results", &envelope, "1.0.0").expect("store results");
+
+        let results = tempdir.path().join("jobs/job-zstd-results/results");
+        let plain = serde_json::to_vec_pretty(&envelope).expect("serialize envelope");
+        let on_disk = std::fs::read(results.join("result.json.zst")).expect("read zst");
+        assert_ne!(on_disk, plain);
+        assert!(!results.join("result.json").exists());
+        assert!(results.join("envelope.json.zst").exists());
+
+        assert_eq!(fs.load_results_bundle("job-zstd-results").expect("load").envelope, envelope);
+        assert_eq!(fs.load_results_bundle_verified("job-zstd-results").expect("verified load").envelope, envelope);
+        let manifest = fs.load_manifest("job-zstd-results").expect("manifest");
+        assert_eq!(manifest.artifacts[1].path, "results/result.json.zst");
+        assert_eq!(manifest.artifacts[1].size_bytes, on_disk.len() as u64);
+
+        let plain_fs = CircuitFsLocal::new(tempdir.path()).with_test_job_id_prefix("job-");
+        assert_eq!(plain_fs.load_results_bundle("job-zstd-results").expect("plain load").envelope, envelope);
+        let err = plain_fs.store_results_bundle("job-zstd-results", &envelope, "1.0.0").expect_err("immutable");
+        assert!(matches!(err, crate::CircuitFsError::AlreadyExists { .. }));
+    }
+
+    #[test]
+    fn uncompressed_bundles_round_trip_and_stay_readable_once_compression_is_enabled() {
+        let tempdir = tempdir().expect("tempdir");
+        let plain_fs = CircuitFsLocal::new(tempdir.path()).with_test_job_id_prefix("job-");
+        let envelope = envelope("job-legacy");
+        plain_fs.store_results_bundle("job-legacy", &envelope, "1.0.0").expect("store results");
+
+        let results = tempdir.path().join("jobs/job-legacy/results");
+        let plain = serde_json::to_vec_pretty(&envelope).expect("serialize envelope");
+        assert_eq!(std::fs::read(results.join("result.json")).expect("read result.json"), plain);
+        assert!(!results.join("result.json.zst").exists());
+        assert_eq!(plain_fs.load_results_bundle("job-legacy").expect("plain load").envelope, envelope);
+
+        let fs = plain_fs.with_compression(Compression::Zstd(0));
+        assert_eq!(fs.load_results_bundle("job-legacy").expect("load legacy").envelope, envelope);
+        assert_eq!(fs.load_results_bundle_verified("job-legacy").expect("verified legacy").envelope, envelope);
+        assert!(fs.verify_all_artifacts("job-legacy").expect("verify").is_empty());
+    }
+}
@@ -7,15 +7,23 @@
 
 #![forbid(unsafe_code)]
 
+mod async_circuit_fs;
+mod backend;
+mod checksums;
+mod compression;
 mod local_circuit_fs;
 mod qfs_l2_checkpoint;
+mod retention;
+#[cfg(feature = "s3")]
+mod s3_circuit_fs;
+mod store;
 
 pub use local_circuit_fs::{
     CircuitFsError, CircuitFsLocal, CompiledArtifactLineage, CompiledArtifactProvenance,
-    CompiledArtifacts, CompiledMetadata, ErrorDetails, ReleaseEvidenceBundle,
+    CompiledArtifacts, CompiledMetadata, ErrorDetails, JobMeta, LogPolicy, ReleaseEvidenceBundle,
     ReleaseEvidenceManifest, ReleaseEvidenceProvenanceReport, ResultArtifactDescriptor,
     ResultEnvelope, ResultManifest, ResultsBundle, ScientificMeasurement, SourceBundle, SourceMetadata,
-    DEFAULT_CIRCUIT_FS_ROOT,
+    DEFAULT_CIRCUIT_FS_ROOT, JOB_META_SCHEMA_VERSION,
 };
 
 pub use qfs_l2_checkpoint::{
@@ -26,3 +34,12 @@ pub use qfs_l2_checkpoint::{
     CHECKPOINT_ENVELOPE_SCHEMA_VERSION, CHECKPOINT_RUNTIME_API_VERSION,
     DEFAULT_MAX_CHECKPOINT_SIZE_BYTES, DEFAULT_MAX_RESTORE_COST_UNITS,
 };
+
+pub use async_circuit_fs::CircuitFsAsync;
+pub use backend::{CircuitFsBackend, MockCircuitFsBackend};
+pub use checksums::VerifyReport;
+pub use compression::Compression;
+pub use retention::{GcReport, RetentionPolicy};
+#[cfg(feature = "s3")]
+pub use s3_circuit_fs::{CircuitFsS3, S3_BUCKET_ENV, S3_ENDPOINT_ENV};
+pub use store::CircuitStore;
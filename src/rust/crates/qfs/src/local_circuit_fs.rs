@@ -1,5 +1,5 @@
 use std::fs;
-use std::fs::OpenOptions;
+use std::fs::{File, OpenOptions, TryLockError};
 use std::collections::{BTreeMap, BTreeSet};
 use std::env;
 use std::future::Future;
@@ -7,7 +7,7 @@ use std::io::{self, Write};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::thread;
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
@@ -21,12 +21,22 @@ use thiserror::Error;
 use tokio::runtime::Handle;
 use tokio::task;
 
+use crate::compression::{self, Compression};
+
 
 /// Default filesystem root for CircuitFS (QFS-L3).
 ///
 /// For local development/tests, you should override this with a temp directory.
 pub const DEFAULT_CIRCUIT_FS_ROOT: &str = "/var/lib/eigen/circuit_fs";
 
+/// How long a multi-file write waits for another writer's job lock by default;
+/// see [`CircuitFsLocal::with_lock_timeout`].
+pub const DEFAULT_JOB_LOCK_TIMEOUT: Duration = Duration::from_secs(5);
+/// Advisory lock file in each job directory; not an artifact, so never listed.
+const JOB_LOCK_FILE_NAME: &str = ".lock";
+/// Delay between attempts to take a contended job lock.
+const JOB_LOCK_RETRY_INTERVAL: Duration = Duration::from_millis(10);
+
 const MINIO_MIRROR_MAX_ATTEMPTS: usize = 3;
 const MINIO_MIRROR_BACKOFF_MS: u64 = 50;
 /// Represents the “source bundle” artifacts stored in QFS.
@@ -84,6 +94,13 @@ pub enum CircuitFsError {
     #[error("artifact integrity mismatch: {path}")]
     IntegrityMismatch { path: PathBuf },
 
+    #[error("checksum mismatch for {path}: expected {expected}, got {actual}")]
+    ChecksumMismatch {
+        path: PathBuf,
+        expected: String,
+        actual: String,
+    },
+
     #[error("artifact not found: {path}")]
     NotFound { path: PathBuf },
 
@@ -98,25 +115,106 @@ pub enum CircuitFsError {
     #[error("invalid job id: {job_id}")]
     InvalidJobId { job_id: String },
 
+    #[error("invalid log name: {log_name}")]
+    InvalidLogName { log_name: String },
+
+    #[error("job is locked by another writer: {path}")]
+    Locked { path: PathBuf },
+
+    #[error("unsupported schema version {found:?} in {path}, expected {supported:?}")]
+    UnsupportedSchema { path: PathBuf, found: String, supported: &'static str },
+
     #[error(transparent)]
     Io(#[from] io::Error),
 }
 
+/// Size cap of the `logs/<stream>.jsonl` files written by
+/// [`CircuitFsLocal::append_log_line`].
+///
+/// When a line would push the current file past `max_bytes_per_file`, the file is
+/// renamed to `<stream>.jsonl.1`, older generations move up one (`.1` to `.2`, ...)
+/// and generations beyond `max_files` are deleted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LogPolicy {
+    pub max_bytes_per_file: u64,
+    /// Rotated generations kept next to the current file.
+    pub max_files: usize,
+}
+
+impl Default for LogPolicy {
+    /// 16 MiB per file and four rotated generations.
+    fn default() -> Self {
+        Self { max_bytes_per_file: 16 * 1024 * 1024, max_files: 4 }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CircuitFsLocal {
     root: PathBuf,
+    /// Extra job ids accepted besides generated ones; see
+    /// [`CircuitFsLocal::with_test_job_id_prefix`].
+    test_job_id_prefix: Option<String>,
+    /// How the compiled AQO JSON and the result JSON documents are stored.
+    compression: Compression,
+    log_policy: LogPolicy,
+    /// How long multi-file writes wait for the job lock before failing with
+    /// [`CircuitFsError::Locked`].
+    lock_timeout: Duration,
 }
 
 impl CircuitFsLocal {
     pub fn new(root: impl AsRef<Path>) -> Self {
-        Self { root: root.as_ref().to_path_buf() }
+        Self::new_with_compression(root, Compression::None)
+    }
+
+    /// Like [`CircuitFsLocal::new`], storing the compressible artifacts with
+    /// `compression`; see [`Compression`].
+    pub fn new_with_compression(root: impl AsRef<Path>, compression: Compression) -> Self {
+        Self {
+            root: root.as_ref().to_path_buf(),
+            test_job_id_prefix: None,
+            compression,
+            log_policy: LogPolicy::default(),
+            lock_timeout: DEFAULT_JOB_LOCK_TIMEOUT,
+        }
+    }
+
+    /// Rotates job logs according to `policy` instead of [`LogPolicy::default`].
+    pub fn with_log_policy(mut self, policy: LogPolicy) -> Self {
+        self.log_policy = policy;
+        self
+    }
+
+    /// Waits up to `timeout` for a job lock held by another writer instead of
+    /// [`DEFAULT_JOB_LOCK_TIMEOUT`]; a zero timeout fails right away.
+    pub fn with_lock_timeout(mut self, timeout: Duration) -> Self {
+        self.lock_timeout = timeout;
+        self
+    }
+
+    /// Also accepts hand-written job ids starting with `prefix` (e.g. `job-1` for
+    /// `"job-"`), for tests and fixtures. Without it only generated ids are valid.
+    pub fn with_test_job_id_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.test_job_id_prefix = Some(prefix.into());
+        self
+    }
+
+    /// Switches how artifacts written from now on are stored; existing plain and
+    /// compressed artifacts stay readable either way.
+    pub fn with_compression(mut self, compression: Compression) -> Self {
+        self.compression = compression;
+        self
     }
 
     pub fn root_path(&self) -> &Path {
         &self.root
     }
 
-    fn resolve_path(&self, path: &Path) -> PathBuf {
+    pub fn compression(&self) -> Compression {
+        self.compression
+    }
+
+    pub(crate) fn resolve_path(&self, path: &Path) -> PathBuf {
         let raw = path.to_string_lossy();
         if let Some(normalized) = raw.strip_prefix("qfs://").or_else(|| raw.strip_prefix("circuitfs://")) {
             self.root.join(normalized.trim_start_matches('/'))
@@ -129,6 +227,12 @@ impl CircuitFsLocal {
         atomic_write_bytes(&self.resolve_path(path.as_ref()), bytes)
     }
 
+    /// Same as [`Self::write_bytes`], also writing a `<path>.sha256` sidecar that
+    /// [`Self::verify_all_artifacts`] checks.
+    pub fn write_bytes_checksummed(&self, path: impl AsRef<Path>, bytes: &[u8]) -> Result<(), CircuitFsError> {
+        atomic_write_checksummed(&self.resolve_path(path.as_ref()), bytes)
+    }
+
     pub fn read_bytes(&self, path: impl AsRef<Path>) -> Result<Vec<u8>, CircuitFsError> {
         let path = self.resolve_path(path.as_ref());
         if path.exists() {
@@ -185,7 +289,7 @@ impl CircuitFsLocal {
                         stack.push(path);
                         continue;
                     }
-                    if path.is_file() {
+                    if path.is_file() && entry.file_name() != JOB_LOCK_FILE_NAME {
                         let rel = path
                             .strip_prefix(&root)
                             .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "path escapes qfs root"))?
@@ -207,8 +311,140 @@ impl CircuitFsLocal {
         Ok(refs)
     }
 
+    /// Iterates over the ids of the jobs that have a directory under `jobs/`, in
+    /// directory order, without reading the whole directory up front.
+    ///
+    /// Files and entries whose names are not valid job ids are skipped; I/O errors
+    /// while reading an entry are yielded as they occur.
+    pub fn iter_jobs(&self) -> Result<impl Iterator<Item = Result<String, CircuitFsError>> + '_, CircuitFsError> {
+        let entries = match fs::read_dir(self.root.join("jobs")) {
+            Ok(entries) => Some(entries),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => None,
+            Err(err) => return Err(err.into()),
+        };
+        Ok(entries.into_iter().flatten().filter_map(move |entry| {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(err) => return Some(Err(err.into())),
+            };
+            match entry.file_type() {
+                Ok(file_type) if file_type.is_dir() => {}
+                Ok(_) => return None,
+                Err(err) => return Some(Err(err.into())),
+            }
+            let name = entry.file_name().into_string().ok()?;
+            self.validate_job_id(&name).is_ok().then_some(Ok(name))
+        }))
+    }
+
+    /// Number of jobs [`CircuitFsLocal::iter_jobs`] yields.
+    pub fn job_count(&self) -> Result<usize, CircuitFsError> {
+        self.iter_jobs()?.try_fold(0, |count, job_id| job_id.map(|_| count + 1))
+    }
+
+    /// Lists the ids of all jobs that have a directory under `jobs/`, sorted.
+    ///
+    /// Entries whose names are not valid job ids are skipped.
+    pub fn list_job_ids(&self) -> Result<Vec<String>, CircuitFsError> {
+        let mut job_ids = self.iter_jobs()?.collect::<Result<Vec<_>, _>>()?;
+        job_ids.sort();
+        Ok(job_ids)
+    }
+
+    /// Lists the ids of the jobs that have a `meta/job.json` document, sorted.
+    ///
+    /// Unlike [`CircuitFsLocal::list_job_ids`] this skips directories holding only
+    /// artifacts or a half-created layout, so every id returned can be loaded with
+    /// [`CircuitFsLocal::load_job_meta`].
+    pub fn list_jobs(&self) -> Result<Vec<String>, CircuitFsError> {
+        let mut job_ids = Vec::new();
+        for job_id in self.iter_jobs()? {
+            let job_id = job_id?;
+            if self.meta_json_path(&job_id)?.is_file() {
+                job_ids.push(job_id);
+            }
+        }
+        job_ids.sort();
+        Ok(job_ids)
+    }
+
+    /// Whether the job has a directory under `jobs/`.
+    pub fn job_exists(&self, job_id: &str) -> Result<bool, CircuitFsError> {
+        Ok(self.job_root_path(job_id)?.is_dir())
+    }
+
+    /// Removes the job's directory with everything in it. Returns
+    /// [`CircuitFsError::NotFound`] when the job has no directory; copies mirrored
+    /// to MinIO are not touched.
+    pub fn delete_job(&self, job_id: &str) -> Result<(), CircuitFsError> {
+        let job_root = self.job_root_path(job_id)?;
+        // `job_root_path` already rejects ids that could escape `jobs/`; never let a
+        // gap there turn this into removing the root or the whole `jobs/` tree.
+        if job_root.parent() != Some(self.root.join("jobs").as_path()) || job_root == self.root {
+            return Err(CircuitFsError::InvalidJobId { job_id: job_id.to_string() });
+        }
+        if !job_root.is_dir() {
+            return Err(CircuitFsError::NotFound { path: job_root });
+        }
+        fs::remove_dir_all(&job_root)?;
+        Ok(())
+    }
+
+    /// Every file the `store_*` methods write for `job_id`, whether or not it exists.
+    /// All of them are written with a checksum sidecar.
+    pub(crate) fn checksummed_artifact_paths(&self, job_id: &str) -> Result<Vec<PathBuf>, CircuitFsError> {
+        Ok(vec![
+            self.job_yaml_path(job_id)?,
+            self.program_source_path(job_id)?,
+            self.compiled_aqo_json_path(job_id)?,
+            compression::zstd_path(&self.compiled_aqo_json_path(job_id)?),
+            self.compiled_metadata_path(job_id)?,
+            self.compiled_qasm_path(job_id)?,
+            self.compiled_report_path(job_id)?,
+            self.results_parquet_path(job_id)?,
+            self.result_json_path(job_id)?,
+            compression::zstd_path(&self.result_json_path(job_id)?),
+            self.result_envelope_path(job_id)?,
+            compression::zstd_path(&self.result_envelope_path(job_id)?),
+            self.result_manifest_path(job_id)?,
+            self.result_metadata_path(job_id)?,
+            self.error_json_path(job_id)?,
+            self.metrics_json_path(job_id)?,
+            self.meta_json_path(job_id)?,
+            self.release_evidence_bundle_path(job_id)?,
+            self.release_evidence_manifest_path(job_id)?,
+            self.release_evidence_provenance_path(job_id)?,
+        ])
+    }
+
+    /// Takes the job's advisory lock (`flock` on `jobs/<job_id>/.lock`), so writers
+    /// in other processes sharing the root cannot interleave a multi-file bundle.
+    /// Retries until the lock timeout, then fails with [`CircuitFsError::Locked`].
+    /// The lock is released when the returned guard is dropped.
+    fn lock_job(&self, job_id: &str) -> Result<JobLock, CircuitFsError> {
+        let path = self.job_root_path(job_id)?.join(JOB_LOCK_FILE_NAME);
+        let file = OpenOptions::new().create(true).truncate(false).write(true).open(&path)?;
+        let started = Instant::now();
+        loop {
+            match file.try_lock() {
+                Ok(()) => return Ok(JobLock { _file: file }),
+                Err(TryLockError::WouldBlock) if started.elapsed() < self.lock_timeout => {
+                    thread::sleep(JOB_LOCK_RETRY_INTERVAL);
+                }
+                Err(TryLockError::WouldBlock) => return Err(CircuitFsError::Locked { path }),
+                Err(TryLockError::Error(err)) => return Err(err.into()),
+            }
+        }
+    }
+
+    /// Path of the mutable job metadata document (`meta/job.json`).
+    pub fn meta_json_path(&self, job_id: &str) -> Result<PathBuf, CircuitFsError> {
+        Ok(self.meta_dir_path(job_id)?.join("job.json"))
+    }
+
     pub fn ensure_job_layout(&self, job_id: &str) -> Result<(), CircuitFsError> {
         fs::create_dir_all(self.job_root_path(job_id)?)?;
+        fs::create_dir_all(self.input_dir_path(job_id)?)?;
         fs::create_dir_all(self.compiled_dir_path(job_id)?)?;
         fs::create_dir_all(self.results_dir_path(job_id)?)?;
         fs::create_dir_all(self.observability_dir_path(job_id)?)?;
@@ -219,14 +455,18 @@ impl CircuitFsLocal {
     }
 
     pub fn append_log_line(&self, job_id: &str, stream: &str, line: &str) -> Result<(), CircuitFsError> {
-        self.ensure_job_layout(job_id)?;
         let path = self.log_path(job_id, stream)?;
+        self.ensure_job_layout(job_id)?;
         let parent = path
             .parent()
             .ok_or_else(|| CircuitFsError::Io(io::Error::new(io::ErrorKind::InvalidInput, "missing log parent")))?;
         fs::create_dir_all(parent)?;
-        let mut fh = OpenOptions::new().create(true).append(true).open(&path)?;
         let bytes = line.trim_end_matches('\n').as_bytes();
+        let current_len = fs::metadata(&path).map_or(0, |metadata| metadata.len());
+        if current_len > 0 && current_len + bytes.len() as u64 + 1 > self.log_policy.max_bytes_per_file {
+            rotate_log(&path, self.log_policy.max_files)?;
+        }
+        let mut fh = OpenOptions::new().create(true).append(true).open(&path)?;
         fh.write_all(bytes)?;
         fh.write_all(b"\n")?;
         fh.flush()?;
@@ -248,28 +488,34 @@ impl CircuitFsLocal {
         producer_version: &str,
     ) -> Result<(), CircuitFsError> {
         self.ensure_job_layout(job_id)?;
+        let _lock = self.lock_job(job_id)?;
 
         let parquet_path = self.results_parquet_path(job_id)?;
         let result_json_path = self.result_json_path(job_id)?;
         let manifest_path = self.result_manifest_path(job_id)?;
         let envelope_path = self.result_envelope_path(job_id)?;
+        let metadata_path = self.result_metadata_path(job_id)?;
 
-        for path in [
-            parquet_path.clone(),
-            result_json_path.clone(),
-            manifest_path.clone(),
-            envelope_path.clone(),
-        ] {
-            if self.object_exists(&path) {
-                return Err(CircuitFsError::AlreadyExists { path });
+        for path in [&parquet_path, &manifest_path, &metadata_path] {
+            if self.object_exists(path) {
+                return Err(CircuitFsError::AlreadyExists { path: path.clone() });
             }
         }
+        for path in [&result_json_path, &envelope_path] {
+            self.ensure_compressible_absent(path)?;
+        }
 
         let envelope_bytes = serde_json::to_vec_pretty(envelope).map_err(to_io_error)?;
+        let stored_envelope_bytes = self.compression.encode(&envelope_bytes)?;
+        let result_json_path = self.compression.stored_path(&result_json_path);
         let parquet_bytes = write_scientific_results_parquet(envelope)?;
-        atomic_write_bytes(&parquet_path, &parquet_bytes)?;
-        atomic_write_bytes(&result_json_path, &envelope_bytes)?;
-        atomic_write_bytes(&envelope_path, &envelope_bytes)?;
+        let metadata_bytes = serde_json::to_vec_pretty(&envelope.summary).map_err(to_io_error)?;
+        atomic_write_checksummed(&parquet_path, &parquet_bytes)?;
+        atomic_write_checksummed(&result_json_path, &stored_envelope_bytes)?;
+        atomic_write_checksummed(&self.compression.stored_path(&envelope_path), &stored_envelope_bytes)?;
+        atomic_write_checksummed(&metadata_path, &metadata_bytes)?;
+        let result_json_name = result_json_path.file_name().and_then(|name| name.to_str()).unwrap_or("result.json");
+        let result_json_ref = format!("results/{result_json_name}");
 
         let manifest = ResultManifest {
             artifact_version: envelope.artifact_version.clone(),
@@ -284,27 +530,128 @@ impl CircuitFsLocal {
                     size_bytes: parquet_bytes.len() as u64,
                 },
                 ResultArtifactDescriptor {
-                    path: "results/result.json".to_string(),
-                    content_hash: content_hash_hex(&envelope_bytes),
-                    size_bytes: envelope_bytes.len() as u64,
+                    path: result_json_ref,
+                    content_hash: content_hash_hex(&stored_envelope_bytes),
+                    size_bytes: stored_envelope_bytes.len() as u64,
+                },
+                ResultArtifactDescriptor {
+                    path: "results/metadata.json".to_string(),
+                    content_hash: content_hash_hex(&metadata_bytes),
+                    size_bytes: metadata_bytes.len() as u64,
                 },
             ],
         };
         let manifest_bytes = serde_json::to_vec_pretty(&manifest).map_err(to_io_error)?;
-        atomic_write_bytes(&manifest_path, &manifest_bytes)?;
+        atomic_write_checksummed(&manifest_path, &manifest_bytes)?;
         Ok(())
     }
 
+    /// Stores the submitted source bundle under `input/`.
+    ///
+    /// `input/program.eigen.py` is only written when the bundle carries program
+    /// source. Source bundles are immutable once written.
+    pub fn store_source_bundle(&self, job_id: &str, bundle: &SourceBundle) -> Result<(), CircuitFsError> {
+        self.ensure_job_layout(job_id)?;
+        let _lock = self.lock_job(job_id)?;
+        let job_yaml_path = self.job_yaml_path(job_id)?;
+        let program_path = self.program_source_path(job_id)?;
+        for path in [&job_yaml_path, &program_path] {
+            if self.object_exists(path) {
+                return Err(CircuitFsError::AlreadyExists { path: path.clone() });
+            }
+        }
+        if !bundle.program_eigen_py.is_empty() {
+            atomic_write_checksummed(&program_path, &bundle.program_eigen_py)?;
+        }
+        atomic_write_checksummed(&job_yaml_path, bundle.job_yaml.as_bytes())
+    }
+
+    pub fn load_source_bundle(&self, job_id: &str) -> Result<SourceBundle, CircuitFsError> {
+        let job_yaml_path = self.job_yaml_path(job_id)?;
+        let job_yaml = String::from_utf8(self.read_bytes(&job_yaml_path)?)
+            .map_err(|err| CircuitFsError::Io(io::Error::new(io::ErrorKind::InvalidData, err)))?;
+        let program_path = self.program_source_path(job_id)?;
+        let program_eigen_py = if self.object_exists(&program_path) {
+            self.read_bytes(&program_path)?
+        } else {
+            Vec::new()
+        };
+        Ok(SourceBundle { job_yaml, program_eigen_py })
+    }
+
+    /// Loads the result envelope from `results/result.json` (or its `.zst` form)
+    /// without checking its checksum; see [`CircuitFsLocal::load_results_bundle_verified`].
+    pub fn load_results_bundle(&self, job_id: &str) -> Result<ResultsBundle, CircuitFsError> {
+        let bytes = self.read_compressible(&self.result_json_path(job_id)?)?;
+        let envelope = serde_json::from_slice(&bytes).map_err(to_io_error)?;
+        Ok(ResultsBundle { envelope })
+    }
+
+    /// Loads `results/metadata.json`, the run metadata (backend, shot count, wall
+    /// time, stage timings) copied from the envelope summary.
+    pub fn load_result_metadata(&self, job_id: &str) -> Result<BTreeMap<String, String>, CircuitFsError> {
+        let bytes = self.read_bytes(self.result_metadata_path(job_id)?)?;
+        serde_json::from_slice(&bytes).map_err(to_io_error)
+    }
+
+    /// Loads `results/manifest.json`, which lists the size and SHA-256 of every
+    /// result artifact.
+    pub fn load_manifest(&self, job_id: &str) -> Result<ResultManifest, CircuitFsError> {
+        let bytes = self.read_bytes(self.result_manifest_path(job_id)?)?;
+        serde_json::from_slice(&bytes).map_err(to_io_error)
+    }
+
+    /// Writes `meta/job.json` for `meta.job_id`, replacing any previous document.
+    ///
+    /// Unlike the input and result artifacts, job metadata is mutable: every state
+    /// change rewrites it through the same temp-file-and-rename path.
+    pub fn store_job_meta(&self, meta: &JobMeta) -> Result<(), CircuitFsError> {
+        self.ensure_job_layout(&meta.job_id)?;
+        let bytes = serde_json::to_vec_pretty(meta).map_err(to_io_error)?;
+        atomic_write_checksummed(&self.meta_json_path(&meta.job_id)?, &bytes)
+    }
+
+    /// Loads `meta/job.json`; returns [`CircuitFsError::NotFound`] when the job has
+    /// no metadata yet.
+    pub fn load_job_meta(&self, job_id: &str) -> Result<JobMeta, CircuitFsError> {
+        let path = self.meta_json_path(job_id)?;
+        let meta: JobMeta = serde_json::from_slice(&self.read_bytes(&path)?).map_err(to_io_error)?;
+        meta.check_schema_version(&path)?;
+        Ok(meta)
+    }
+
     pub fn store_metrics_json(&self, job_id: &str, metrics: &[u8]) -> Result<(), CircuitFsError> {
         self.ensure_job_layout(job_id)?;
         let path = self.metrics_json_path(job_id)?;
         if self.object_exists(&path) {
             return Err(CircuitFsError::AlreadyExists { path });
         }
-        atomic_write_bytes(&path, metrics)
+        atomic_write_checksummed(&path, metrics)
     }
 
-    fn validate_job_id(job_id: &str) -> Result<(), CircuitFsError> {
+    /// Writes `results/error.json`, the structured report of why a job failed.
+    ///
+    /// Unlike the result artifacts the report is rewritable, so a retried job that
+    /// fails again replaces it.
+    pub fn store_error_details_json(&self, job_id: &str, details: &[u8]) -> Result<(), CircuitFsError> {
+        self.ensure_job_layout(job_id)?;
+        atomic_write_checksummed(&self.error_json_path(job_id)?, details)
+    }
+
+    pub fn load_error_details_json(&self, job_id: &str) -> Result<Vec<u8>, CircuitFsError> {
+        self.read_bytes(self.error_json_path(job_id)?)
+    }
+
+    /// Path of the `logs/<stream>.jsonl` file that [`Self::append_log_line`] writes to,
+    /// whether or not it exists yet.
+    pub fn job_log_path(&self, job_id: &str, stream: &str) -> Result<PathBuf, CircuitFsError> {
+        self.log_path(job_id, stream)
+    }
+
+    /// Accepts canonical UUIDs (the job stores' ids), the kernel's `job-<hex>` ids and,
+    /// when configured, ids with the test prefix. Anything else is most likely a typo
+    /// or corruption and would otherwise create a stray job directory.
+    fn validate_job_id(&self, job_id: &str) -> Result<(), CircuitFsError> {
         let valid_chars = job_id
             .chars()
             .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '.' | '_' | '-'));
@@ -313,12 +660,29 @@ impl CircuitFsLocal {
             || job_id == ".."
             || job_id.contains("..")
             || job_id.as_bytes().contains(&0);
-        if !valid_chars || forbidden_segments {
+        let test_id = self
+            .test_job_id_prefix
+            .as_deref()
+            .is_some_and(|prefix| job_id.len() > prefix.len() && job_id.starts_with(prefix));
+        let known_shape = is_canonical_uuid(job_id) || is_kernel_job_id(job_id) || test_id;
+        if !valid_chars || forbidden_segments || !known_shape {
             return Err(CircuitFsError::InvalidJobId { job_id: job_id.to_string() });
         }
         Ok(())
     }
 
+    fn input_dir_path(&self, job_id: &str) -> Result<PathBuf, CircuitFsError> {
+        Ok(self.job_root_path(job_id)?.join("input"))
+    }
+
+    fn job_yaml_path(&self, job_id: &str) -> Result<PathBuf, CircuitFsError> {
+        Ok(self.input_dir_path(job_id)?.join("job.yaml"))
+    }
+
+    fn program_source_path(&self, job_id: &str) -> Result<PathBuf, CircuitFsError> {
+        Ok(self.input_dir_path(job_id)?.join("program.eigen.py"))
+    }
+
     fn observability_dir_path(&self, job_id: &str) -> Result<PathBuf, CircuitFsError> {
         Ok(self.job_root_path(job_id)?.join("observability"))
     }
@@ -335,18 +699,26 @@ impl CircuitFsLocal {
         Ok(self.meta_dir_path(job_id)?.join("release_evidence"))
     }
 
-    fn result_json_path(&self, job_id: &str) -> Result<PathBuf, CircuitFsError> {
+    pub(crate) fn result_json_path(&self, job_id: &str) -> Result<PathBuf, CircuitFsError> {
         Ok(self.results_dir_path(job_id)?.join("result.json"))
     }
 
-    fn result_manifest_path(&self, job_id: &str) -> Result<PathBuf, CircuitFsError> {
+    fn error_json_path(&self, job_id: &str) -> Result<PathBuf, CircuitFsError> {
+        Ok(self.results_dir_path(job_id)?.join("error.json"))
+    }
+
+    pub(crate) fn result_manifest_path(&self, job_id: &str) -> Result<PathBuf, CircuitFsError> {
         Ok(self.results_dir_path(job_id)?.join("manifest.json"))
     }
 
-    fn result_envelope_path(&self, job_id: &str) -> Result<PathBuf, CircuitFsError> {
+    pub(crate) fn result_envelope_path(&self, job_id: &str) -> Result<PathBuf, CircuitFsError> {
         Ok(self.results_dir_path(job_id)?.join("envelope.json"))
     }
 
+    fn result_metadata_path(&self, job_id: &str) -> Result<PathBuf, CircuitFsError> {
+        Ok(self.results_dir_path(job_id)?.join("metadata.json"))
+    }
+
     fn release_evidence_bundle_path(&self, job_id: &str) -> Result<PathBuf, CircuitFsError> {
         Ok(self.release_evidence_dir_path(job_id)?.join("bundle.json"))
     }
@@ -364,11 +736,17 @@ impl CircuitFsLocal {
     }
 
     fn log_path(&self, job_id: &str, stream: &str) -> Result<PathBuf, CircuitFsError> {
+        let valid_chars = stream
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '.' | '_' | '-'));
+        if stream.is_empty() || stream.starts_with('.') || stream.contains("..") || !valid_chars {
+            return Err(CircuitFsError::InvalidLogName { log_name: stream.to_string() });
+        }
         Ok(self.logs_dir_path(job_id)?.join(format!("{stream}.jsonl")))
     }
 
-    fn job_root_path(&self, job_id: &str) -> Result<PathBuf, CircuitFsError> {
-        Self::validate_job_id(job_id)?;
+    pub(crate) fn job_root_path(&self, job_id: &str) -> Result<PathBuf, CircuitFsError> {
+        self.validate_job_id(job_id)?;
         Ok(self.root.join("jobs").join(job_id))
     }
 
@@ -380,6 +758,29 @@ impl CircuitFsLocal {
         Ok(self.compiled_dir_path(job_id)?.join("circuit.aqo.json"))
     }
 
+    /// The file holding the artifact normally stored at `path`: its `.zst` form when
+    /// that exists, else `path` itself.
+    pub(crate) fn stored_compressible_path(&self, path: &Path) -> PathBuf {
+        let compressed = compression::zstd_path(path);
+        if self.object_exists(&compressed) { compressed } else { path.to_path_buf() }
+    }
+
+    fn read_compressible(&self, path: &Path) -> Result<Vec<u8>, CircuitFsError> {
+        let stored = self.stored_compressible_path(path);
+        let bytes = self.read_bytes(&stored)?;
+        Ok(compression::decode(&stored, bytes)?)
+    }
+
+    /// Immutable compressible artifacts may not exist in either form.
+    fn ensure_compressible_absent(&self, path: &Path) -> Result<(), CircuitFsError> {
+        for candidate in [path.to_path_buf(), compression::zstd_path(path)] {
+            if self.object_exists(&candidate) {
+                return Err(CircuitFsError::AlreadyExists { path: candidate });
+            }
+        }
+        Ok(())
+    }
+
     fn compiled_metadata_path(&self, job_id: &str) -> Result<PathBuf, CircuitFsError> {
         Ok(self.compiled_dir_path(job_id)?.join("metadata.json"))
     }
@@ -396,11 +797,16 @@ impl CircuitFsLocal {
         Ok(self.job_root_path(job_id)?.join("results"))
     }
 
-    fn results_parquet_path(&self, job_id: &str) -> Result<PathBuf, CircuitFsError> {
+    pub(crate) fn results_parquet_path(&self, job_id: &str) -> Result<PathBuf, CircuitFsError> {
         Ok(self.job_root_path(job_id)?.join("results.parquet"))
     }
 }
 
+/// Held job lock; closing the file releases it.
+struct JobLock {
+    _file: File,
+}
+
 /// Represents the “results bundle” artifacts stored in QFS.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ResultsBundle {
@@ -471,6 +877,31 @@ pub struct CompiledMetadata {
     pub lineage: CompiledArtifactLineage,
 }
 
+impl CompiledMetadata {
+    /// `compiled/metadata.json` describing the given compiler outputs.
+    pub(crate) fn v1(
+        aqo_json: &[u8],
+        qasm: Option<&[u8]>,
+        compile_report_json: Option<&[u8]>,
+        provenance: CompiledArtifactProvenance,
+    ) -> Self {
+        Self {
+            version: "1.0.0".to_string(),
+            schema_version: "compiled_artifacts.v1".to_string(),
+            compiler_version: provenance.compiler_version,
+            producer_identity: provenance.producer_identity,
+            retention_policy: "pinned".to_string(),
+            contract_version: provenance.contract_version,
+            created_at: provenance.created_at,
+            source_sha256: provenance.lineage.source_sha256.clone().unwrap_or_default(),
+            aqo_hash: content_hash_hex(aqo_json),
+            qasm_hash: qasm.map(content_hash_hex),
+            diagnostics_hash: compile_report_json.map(content_hash_hex),
+            lineage: provenance.lineage,
+        }
+    }
+}
+
 /// Versioned result envelope persisted under `results/result.json`.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ResultEnvelope {
@@ -511,6 +942,68 @@ pub struct ResultManifest {
     pub artifacts: Vec<ResultArtifactDescriptor>,
 }
 
+/// Schema version written in, and accepted from, `meta/job.json`.
+pub const JOB_META_SCHEMA_VERSION: &str = "job_meta.v1";
+
+/// Mutable job metadata persisted under `meta/job.json`.
+///
+/// Field names follow the kernel's `JobRecord`, so a record written by the kernel
+/// loads as a `JobMeta`. Fields this version does not know are kept in `extra` and
+/// written back unchanged, so rewriting the document never drops them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JobMeta {
+    /// [`JOB_META_SCHEMA_VERSION`]; documents written before it existed load as that version.
+    #[serde(default = "default_job_meta_schema_version")]
+    pub schema_version: String,
+    pub job_id: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tenant_id: Option<String>,
+    /// Free-form labels from the submission, e.g. `team` or `experiment`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tags: BTreeMap<String, String>,
+    /// Shots requested per circuit execution; 0 when the submission set none.
+    #[serde(default)]
+    pub shots: u32,
+    /// Lifecycle state as the kernel names it, e.g. `RUNNING` or `DONE`.
+    pub state: String,
+    /// Scheduling class as the kernel names it, e.g. `High`; empty when unknown.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub priority: String,
+    #[serde(default)]
+    pub created_at_unix_ms: i64,
+    #[serde(default)]
+    pub updated_at_unix_ms: i64,
+    /// Run attempts started so far.
+    #[serde(default)]
+    pub attempts: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_summary: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_details_ref: Option<String>,
+    /// Fields written by newer producers, preserved on round-trip.
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, serde_json::Value>,
+}
+
+impl JobMeta {
+    /// Fails with [`CircuitFsError::UnsupportedSchema`] unless the document loaded
+    /// from `path` is [`JOB_META_SCHEMA_VERSION`].
+    pub(crate) fn check_schema_version(&self, path: &Path) -> Result<(), CircuitFsError> {
+        if self.schema_version == JOB_META_SCHEMA_VERSION {
+            return Ok(());
+        }
+        Err(CircuitFsError::UnsupportedSchema {
+            path: path.to_path_buf(),
+            found: self.schema_version.clone(),
+            supported: JOB_META_SCHEMA_VERSION,
+        })
+    }
+}
+
 /// Release-evidence bundle persisted under `meta/release_evidence/`.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ReleaseEvidenceBundle {
@@ -579,14 +1072,14 @@ impl CircuitFsLocal {
         provenance: CompiledArtifactProvenance,
     ) -> Result<(), CircuitFsError> {
         self.ensure_job_layout(job_id)?;
+        let _lock = self.lock_job(job_id)?;
 
         let compiled_aqo_path = self.compiled_aqo_json_path(job_id)?;
         let compiled_metadata_path = self.compiled_metadata_path(job_id)?;
         let compiled_qasm_path = self.compiled_qasm_path(job_id)?;
         let compiled_report_path = self.compiled_report_path(job_id)?;
 
-        let compiled_paths: [PathBuf; 4] = [
-            compiled_aqo_path.clone(),
+        let compiled_paths: [PathBuf; 3] = [
             compiled_metadata_path.clone(),
             compiled_qasm_path.clone(),
             compiled_report_path.clone(),
@@ -596,39 +1089,31 @@ impl CircuitFsLocal {
                 return Err(CircuitFsError::AlreadyExists { path });
             }
         }
+        self.ensure_compressible_absent(&compiled_aqo_path)?;
 
-        atomic_write_bytes(&compiled_aqo_path, aqo_json)?;
+        atomic_write_checksummed(
+            &self.compression.stored_path(&compiled_aqo_path),
+            &self.compression.encode(aqo_json)?,
+        )?;
         if let Some(qasm_bytes) = qasm {
-            atomic_write_bytes(&compiled_qasm_path, qasm_bytes)?;
+            atomic_write_checksummed(&compiled_qasm_path, qasm_bytes)?;
         }
         if let Some(report_bytes) = compile_report_json {
-            atomic_write_bytes(&compiled_report_path, report_bytes)?;
+            atomic_write_checksummed(&compiled_report_path, report_bytes)?;
         }
 
-        let metadata = CompiledMetadata {
-            version: "1.0.0".to_string(),
-            schema_version: "compiled_artifacts.v1".to_string(),
-            compiler_version: provenance.compiler_version.clone(),
-            producer_identity: provenance.producer_identity,
-            retention_policy: "pinned".to_string(),
-            contract_version: provenance.contract_version,
-            created_at: provenance.created_at,
-            source_sha256: provenance
-                .lineage
-                .source_sha256
-                .clone()
-                .unwrap_or_default(),
-            aqo_hash: content_hash_hex(aqo_json),
-            qasm_hash: qasm.map(content_hash_hex),
-            diagnostics_hash: compile_report_json.map(content_hash_hex),
-            lineage: provenance.lineage,
-        };
-
+        let metadata = CompiledMetadata::v1(aqo_json, qasm, compile_report_json, provenance);
         let bytes = serde_json::to_vec_pretty(&metadata).map_err(to_io_error)?;
-        atomic_write_bytes(&compiled_metadata_path, &bytes)?;
+        atomic_write_checksummed(&compiled_metadata_path, &bytes)?;
         Ok(())
     }
 
+    /// Loads `compiled/circuit.aqo.json`, decompressing `compiled/circuit.aqo.json.zst`
+    /// when the job was stored compressed.
+    pub fn load_compiled_aqo_json(&self, job_id: &str) -> Result<Vec<u8>, CircuitFsError> {
+        self.read_compressible(&self.compiled_aqo_json_path(job_id)?)
+    }
+
     pub fn store_release_evidence_bundle_v1(
         &self,
         job_id: &str,
@@ -637,6 +1122,7 @@ impl CircuitFsLocal {
         provenance_report: &ReleaseEvidenceProvenanceReport,
     ) -> Result<(), CircuitFsError> {
         self.ensure_job_layout(job_id)?;
+        let _lock = self.lock_job(job_id)?;
 
         let bundle_path = self.release_evidence_bundle_path(job_id)?;
         let manifest_path = self.release_evidence_manifest_path(job_id)?;
@@ -652,9 +1138,9 @@ impl CircuitFsLocal {
         let manifest_bytes = serde_json::to_vec_pretty(manifest).map_err(to_io_error)?;
         let provenance_bytes = serde_json::to_vec_pretty(provenance_report).map_err(to_io_error)?;
 
-        atomic_write_bytes(&bundle_path, &bundle_bytes)?;
-        atomic_write_bytes(&manifest_path, &manifest_bytes)?;
-        atomic_write_bytes(&provenance_path, &provenance_bytes)?;
+        atomic_write_checksummed(&bundle_path, &bundle_bytes)?;
+        atomic_write_checksummed(&manifest_path, &manifest_bytes)?;
+        atomic_write_checksummed(&provenance_path, &provenance_bytes)?;
         Ok(())
     }
 }
@@ -693,15 +1179,40 @@ fn verify_result_manifest(
     Ok(())
 }
 
+fn is_lower_hex(text: &str) -> bool {
+    text.bytes().all(|byte| matches!(byte, b'0'..=b'9' | b'a'..=b'f'))
+}
+
+/// `xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx` in lowercase hex, as `Uuid::to_string` prints it.
+fn is_canonical_uuid(job_id: &str) -> bool {
+    let groups: Vec<&str> = job_id.split('-').collect();
+    groups.len() == 5
+        && groups
+            .iter()
+            .zip([8, 4, 4, 4, 12])
+            .all(|(group, len)| group.len() == len && is_lower_hex(group))
+}
+
+/// `job-` followed by the 16-hex submission fingerprint or a 32-hex simple UUID.
+fn is_kernel_job_id(job_id: &str) -> bool {
+    job_id
+        .strip_prefix("job-")
+        .is_some_and(|hex| matches!(hex.len(), 16 | 32) && is_lower_hex(hex))
+}
+
 fn to_io_error(err: serde_json::Error) -> CircuitFsError {
     CircuitFsError::Io(io::Error::new(io::ErrorKind::InvalidData, err))
 }
 
+fn default_job_meta_schema_version() -> String {
+    JOB_META_SCHEMA_VERSION.to_string()
+}
+
 fn default_scientific_schema_version() -> String {
     "scientific_result_bundle.v1".to_string()
 }
 
-fn content_hash_hex(bytes: &[u8]) -> String {
+pub(crate) fn content_hash_hex(bytes: &[u8]) -> String {
     let mut hasher = Sha256::new();
     hasher.update(bytes);
     format!("{:x}", hasher.finalize())
@@ -846,6 +1357,76 @@ fn write_scientific_results_parquet(envelope: &ResultEnvelope) -> Result<Vec<u8>
 }
 
 fn atomic_write_bytes(path: &Path, bytes: &[u8]) -> Result<(), CircuitFsError> {
+    let tmp = stage_temp_file(path, bytes)?;
+    tmp.persist(path)
+        .map_err(|err| CircuitFsError::Io(err.error))?;
+    mirror_or_log(path, bytes);
+    Ok(())
+}
+
+/// Writes `bytes` to `path` together with a `<path>.sha256` sidecar.
+///
+/// Both files are fully written and synced before either is renamed into place,
+/// and the content is renamed first. A crash can therefore leave content without
+/// a sidecar (which `verify_job_artifacts` skips), but never a sidecar describing
+/// content that was not written.
+/// Shifts `<log>.N` to `<log>.N+1` for every kept generation, dropping the ones
+/// beyond `max_files`, then moves `log` itself to `<log>.1`. Each step is a rename,
+/// so readers always see complete files.
+fn rotate_log(log: &Path, max_files: usize) -> Result<(), CircuitFsError> {
+    let generation = |n: usize| {
+        let mut name = log.as_os_str().to_owned();
+        name.push(format!(".{n}"));
+        PathBuf::from(name)
+    };
+    if max_files == 0 {
+        fs::remove_file(log)?;
+        return Ok(());
+    }
+    match fs::remove_file(generation(max_files)) {
+        Ok(()) => {}
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+        Err(err) => return Err(err.into()),
+    }
+    for n in (1..max_files).rev() {
+        let older = generation(n);
+        if older.exists() {
+            fs::rename(&older, generation(n + 1))?;
+        }
+    }
+    fs::rename(log, generation(1))?;
+    Ok(())
+}
+
+fn atomic_write_checksummed(path: &Path, bytes: &[u8]) -> Result<(), CircuitFsError> {
+    let sidecar_path = checksum_sidecar_path(path);
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let sidecar_bytes = format!("{}  {}\n", content_hash_hex(bytes), file_name).into_bytes();
+
+    let content_tmp = stage_temp_file(path, bytes)?;
+    let sidecar_tmp = stage_temp_file(&sidecar_path, &sidecar_bytes)?;
+    content_tmp
+        .persist(path)
+        .map_err(|err| CircuitFsError::Io(err.error))?;
+    sidecar_tmp
+        .persist(&sidecar_path)
+        .map_err(|err| CircuitFsError::Io(err.error))?;
+    mirror_or_log(path, bytes);
+    mirror_or_log(&sidecar_path, &sidecar_bytes);
+    Ok(())
+}
+
+/// Path of the `sha256sum`-compatible checksum sidecar for an artifact.
+pub(crate) fn checksum_sidecar_path(path: &Path) -> PathBuf {
+    let mut raw = path.as_os_str().to_os_string();
+    raw.push(".sha256");
+    PathBuf::from(raw)
+}
+
+fn stage_temp_file(path: &Path, bytes: &[u8]) -> Result<NamedTempFile, CircuitFsError> {
     let parent = path
         .parent()
         .ok_or_else(|| CircuitFsError::Io(io::Error::new(io::ErrorKind::InvalidInput, "missing parent directory")))?;
@@ -854,8 +1435,10 @@ fn atomic_write_bytes(path: &Path, bytes: &[u8]) -> Result<(), CircuitFsError> {
     tmp.write_all(bytes)?;
     tmp.flush()?;
     tmp.as_file().sync_all()?;
-    tmp.persist(path)
-        .map_err(|err| CircuitFsError::Io(err.error))?;
+    Ok(tmp)
+}
+
+fn mirror_or_log(path: &Path, bytes: &[u8]) {
     // Local persistence is authoritative; MinIO mirroring should still be attempted,
     // but failures are surfaced in logs so the object-store path can be diagnosed.
     if let Err(err) = mirror_path_to_minio(path, bytes) {
@@ -865,7 +1448,6 @@ fn atomic_write_bytes(path: &Path, bytes: &[u8]) -> Result<(), CircuitFsError> {
             err
         );
     }
-    Ok(())
 }
 
 fn verify_hash(path: &Path, expected: &str, actual: &[u8]) -> Result<(), CircuitFsError> {
@@ -1116,11 +1698,215 @@ mod tests {
         serde_json::from_slice(&bytes).expect("parse json")
     }
 
+    fn sample_source_bundle() -> SourceBundle {
+        SourceBundle {
+            job_yaml: "apiVersion: eigen.os/v1\nkind: QuantumJob\n".to_string(),
+            program_eigen_py: b"print('hi')\n".to_vec(),
+        }
+    }
+
     #[test]
-    fn store_compiled_artifacts_uses_canonical_paths_and_metadata() {
+    fn iter_jobs_yields_every_job_directory_and_job_count_follows_deletes() {
+        let tempdir = tempdir().expect("tempdir");
+        let fs = CircuitFsLocal::new(tempdir.path()).with_test_job_id_prefix("job-");
+        assert_eq!(fs.job_count().expect("count without jobs dir"), 0);
+
+        let job_ids = ["job-1", "job-2", "job-3", "job-4", "job-5"];
+        for job_id in job_ids {
+            fs.ensure_job_layout(job_id).expect("layout");
+        }
+        std::fs::write(tempdir.path().join("jobs/job-file"), b"x").expect("stray file");
+        std::fs::create_dir_all(tempdir.path().join("jobs/not a job")).expect("stray dir");
+
+        let mut listed = fs.iter_jobs().expect("iter jobs").collect::<Result<Vec<_>, _>>().expect("job ids");
+        listed.sort();
+        assert_eq!(listed, job_ids);
+        assert_eq!(fs.job_count().expect("count"), 5);
+
+        fs.delete_job("job-3").expect("delete job");
+        assert_eq!(fs.job_count().expect("count"), 4);
+        assert_eq!(fs.list_job_ids().expect("list"), ["job-1", "job-2", "job-4", "job-5"]);
+    }
+
+    fn shots_envelope(job_id: &str, shots: &str) -> ResultEnvelope {
+        ResultEnvelope {
+            artifact_version: "1.0.0".to_string(),
+            schema_version: "scientific_result_bundle.v1".to_string(),
+            producer_version: "1.0.0".to_string(),
+            job_id: job_id.to_string(),
+            workload_kind: "QuantumJob".to_string(),
+            result_ref: "results/result.json".to_string(),
+            manifest_ref: "results/manifest.json".to_string(),
+            created_at_epoch_ms: 1_718_181_234_000,
+            retention_policy: "default".to_string(),
+            lineage: Default::default(),
+            context: Default::default(),
+            summary: BTreeMap::from([("shots".to_string(), shots.to_string())]),
+            measurements: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn concurrent_results_writers_leave_one_consistent_bundle() {
+        let tempdir = tempdir().expect("tempdir");
+        let fs = CircuitFsLocal::new(tempdir.path()).with_test_job_id_prefix("job-");
+        let barrier = std::sync::Barrier::new(2);
+
+        let outcomes: Vec<(&str, Result<(), CircuitFsError>)> = thread::scope(|scope| {
+            let writers: Vec<_> = ["1024", "2048"]
+                .into_iter()
+                .map(|shots| {
+                    let (fs, barrier) = (&fs, &barrier);
+                    scope.spawn(move || {
+                        barrier.wait();
+                        (shots, fs.store_results_bundle("job-race", &shots_envelope("job-race", shots), "1.0.0"))
+                    })
+                })
+                .collect();
+            writers.into_iter().map(|writer| writer.join().expect("writer thread")).collect()
+        });
+
+        let winners: Vec<&str> = outcomes.iter().filter(|(_, result)| result.is_ok()).map(|(shots, _)| *shots).collect();
+        assert_eq!(winners.len(), 1, "{outcomes:?}");
+        let loser = outcomes.iter().find_map(|(_, result)| result.as_ref().err()).expect("losing writer");
+        assert!(matches!(loser, CircuitFsError::AlreadyExists { .. }), "{loser}");
+
+        let bundle = fs.load_results_bundle_verified("job-race").expect("bundle verifies");
+        assert_eq!(bundle.envelope.summary["shots"], winners[0]);
+        assert_eq!(fs.load_result_metadata("job-race").expect("metadata")["shots"], winners[0]);
+        assert!(!fs.list_refs("qfs://jobs/job-race/").expect("refs").iter().any(|r| r.ends_with(JOB_LOCK_FILE_NAME)));
+    }
+
+    #[test]
+    fn contended_job_lock_fails_after_the_timeout() {
+        let tempdir = tempdir().expect("tempdir");
+        let fs = CircuitFsLocal::new(tempdir.path())
+            .with_test_job_id_prefix("job-")
+            .with_lock_timeout(Duration::from_millis(50));
+        fs.ensure_job_layout("job-locked").expect("layout");
+
+        let held = fs.lock_job("job-locked").expect("first lock");
+        let started = Instant::now();
+        let err = fs.store_source_bundle("job-locked", &sample_source_bundle()).expect_err("lock is held");
+        assert!(matches!(&err, CircuitFsError::Locked { path } if path.ends_with(JOB_LOCK_FILE_NAME)), "{err}");
+        assert!(started.elapsed() >= Duration::from_millis(50));
+        assert!(!fs.object_exists(fs.job_yaml_path("job-locked").expect("path")));
+
+        drop(held);
+        fs.store_source_bundle("job-locked", &sample_source_bundle()).expect("lock released");
+    }
+
+    #[test]
+    fn delete_job_removes_the_whole_job_directory() {
+        let tempdir = tempdir().expect("tempdir");
+        let fs = CircuitFsLocal::new(tempdir.path()).with_test_job_id_prefix("job-");
+        fs.store_source_bundle("job-delete", &sample_source_bundle()).expect("store source bundle");
+        fs.append_log_line("job-delete", "stdout", "hello").expect("append log");
+        fs.store_source_bundle("job-keep", &sample_source_bundle()).expect("store source bundle");
+        assert!(fs.job_exists("job-delete").expect("job exists"));
+
+        fs.delete_job("job-delete").expect("delete job");
+
+        assert!(!fs.job_exists("job-delete").expect("job exists"));
+        assert!(!tempdir.path().join("jobs/job-delete").exists());
+        assert!(matches!(fs.load_source_bundle("job-delete"), Err(CircuitFsError::NotFound { .. })));
+        assert_eq!(fs.load_source_bundle("job-keep").expect("other job untouched"), sample_source_bundle());
+    }
+
+    #[test]
+    fn delete_job_of_unknown_or_invalid_job_fails() {
+        let tempdir = tempdir().expect("tempdir");
+        let fs = CircuitFsLocal::new(tempdir.path()).with_test_job_id_prefix("job-");
+
+        assert!(!fs.job_exists("job-missing").expect("job exists"));
+        assert!(matches!(fs.delete_job("job-missing"), Err(CircuitFsError::NotFound { .. })));
+        assert!(matches!(fs.delete_job("../jobs"), Err(CircuitFsError::InvalidJobId { .. })));
+        fs.store_source_bundle("job-survivor", &sample_source_bundle()).expect("store source bundle");
+        for job_id in ["", ".", "job-survivor/..", "/"] {
+            assert!(matches!(fs.delete_job(job_id), Err(CircuitFsError::InvalidJobId { .. })), "{job_id:?}");
+        }
+        assert!(fs.job_exists("job-survivor").expect("job exists"), "root and jobs/ must survive");
+        assert!(matches!(fs.job_exists(".."), Err(CircuitFsError::InvalidJobId { .. })));
+    }
+
+    #[test]
+    fn log_files_rotate_past_the_size_cap() {
+        let tempdir = tempdir().expect("tempdir");
+        let policy = LogPolicy { max_bytes_per_file: 32, max_files: 2 };
+        let fs = CircuitFsLocal::new(tempdir.path()).with_test_job_id_prefix("job-").with_log_policy(policy);
+
+        for n in 0..8 {
+            fs.append_log_line("job-rotate", "stdout", &format!("line {n:02} of the log")).expect("append log");
+        }
+
+        let logs = tempdir.path().join("jobs/job-rotate/logs");
+        let mut names: Vec<String> = std::fs::read_dir(&logs)
+            .expect("read logs")
+            .map(|entry| entry.expect("entry").file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        assert_eq!(names, ["stdout.jsonl", "stdout.jsonl.1", "stdout.jsonl.2"]);
+        let read = |name: &str| std::fs::read_to_string(logs.join(name)).expect("read log");
+        assert_eq!(read("stdout.jsonl"), "line 07 of the log\n");
+        assert_eq!(read("stdout.jsonl.1"), "line 06 of the log\n");
+        assert_eq!(read("stdout.jsonl.2"), "line 05 of the log\n");
+    }
+
+    #[test]
+    fn log_names_cannot_leave_the_logs_dir() {
+        let tempdir = tempdir().expect("tempdir");
+        let fs = CircuitFsLocal::new(tempdir.path()).with_test_job_id_prefix("job-");
+
+        for log_name in ["../../etc/cron", "..", "", ".hidden", "a/b"] {
+            let err = fs.append_log_line("job-logs", log_name, "x").expect_err(log_name);
+            assert!(matches!(err, CircuitFsError::InvalidLogName { .. }), "{log_name}: {err}");
+            assert!(fs.job_log_path("job-logs", log_name).is_err());
+        }
+        assert!(!tempdir.path().join("etc").exists());
+        fs.append_log_line("job-logs", "kernel.v2", "ok").expect("valid log name");
+    }
+
+    #[test]
+    fn job_ids_must_be_uuids_or_kernel_ids_by_default() {
         let tempdir = tempdir().expect("tempdir");
         let fs = CircuitFsLocal::new(tempdir.path());
 
+        for job_id in [
+            "123e4567-e89b-12d3-a456-426614174000",
+            "job-00f067aa0ba902b7",
+            "job-4bf92f3577b34da6a3ce929d0e0e4736",
+        ] {
+            assert!(!fs.job_exists(job_id).expect("valid job id"), "{job_id}");
+        }
+        assert!(matches!(fs.job_exists("job-1"), Err(CircuitFsError::InvalidJobId { .. })));
+    }
+
+    #[test]
+    fn job_ids_with_the_configured_test_prefix_are_accepted() {
+        let tempdir = tempdir().expect("tempdir");
+        let fs = CircuitFsLocal::new(tempdir.path()).with_test_job_id_prefix("job-");
+
+        fs.ensure_job_layout("job-1").expect("layout");
+        assert!(fs.job_exists("job-1").expect("job exists"));
+        assert!(matches!(fs.job_exists("job-"), Err(CircuitFsError::InvalidJobId { .. })));
+    }
+
+    #[test]
+    fn garbage_job_ids_are_rejected() {
+        let tempdir = tempdir().expect("tempdir");
+        let fs = CircuitFsLocal::new(tempdir.path()).with_test_job_id_prefix("job-");
+
+        let uppercase_uuid = "123E4567-E89B-12D3-A456-426614174000";
+        for job_id in ["", "jbo-1", "job 1", "../job-1", uppercase_uuid, "123e4567e89b12d3a456426614174000"] {
+            assert!(matches!(fs.ensure_job_layout(job_id), Err(CircuitFsError::InvalidJobId { .. })), "{job_id:?}");
+        }
+    }
+
+    #[test]
+    fn store_compiled_artifacts_uses_canonical_paths_and_metadata() {
+        let tempdir = tempdir().expect("tempdir");
+        let fs = CircuitFsLocal::new(tempdir.path()).with_test_job_id_prefix("job-");
+
         let provenance = CompiledArtifactProvenance {
             producer_identity: "compiler-service".to_string(),
             contract_version: "1.0.0".to_string(),
@@ -1161,7 +1947,7 @@ mod tests {
     #[test]
     fn store_release_evidence_bundle_writes_bundle_manifest_and_provenance() {
         let tempdir = tempdir().expect("tempdir");
-        let fs = CircuitFsLocal::new(tempdir.path());
+        let fs = CircuitFsLocal::new(tempdir.path()).with_test_job_id_prefix("job-");
 
         let bundle = ReleaseEvidenceBundle {
             artifact_version: "1.0.0".to_string(),
@@ -1253,7 +2039,7 @@ mod tests {
     #[test]
     fn store_results_bundle_writes_canonical_results_parquet_and_sidecars() {
         let tempdir = tempdir().expect("tempdir");
-        let fs = CircuitFsLocal::new(tempdir.path());
+        let fs = CircuitFsLocal::new(tempdir.path()).with_test_job_id_prefix("job-");
 
         let envelope = ResultEnvelope {
             artifact_version: "1.0.0".to_string(),
@@ -1336,6 +2122,8 @@ mod tests {
             .collect();
         assert!(artifact_paths.contains("results.parquet"));
         assert!(artifact_paths.contains("results/result.json"));
+        assert!(artifact_paths.contains("results/metadata.json"));
+        assert_eq!(fs.load_result_metadata("job-789").expect("load metadata"), envelope.summary);
 
         let parquet_path = job_root.join("results.parquet");
         let parquet_bytes = fs::read(&parquet_path).expect("read parquet");
@@ -1352,7 +2140,7 @@ mod tests {
     #[test]
     fn replay_safe_job_ids_reject_path_traversal_and_invalid_segments() {
         let tempdir = tempdir().expect("tempdir");
-        let fs = CircuitFsLocal::new(tempdir.path());
+        let fs = CircuitFsLocal::new(tempdir.path()).with_test_job_id_prefix("job-");
 
         for job_id in ["", ".", "..", "bad/segment", "bad..segment", "bad\\segment"] {
             let err = fs.ensure_job_layout(job_id).expect_err("invalid job id must fail");
@@ -1370,6 +2158,187 @@ mod tests {
             other => panic!("unexpected error: {other:?}"),
         }
     }
+
+    #[test]
+    fn source_bundle_round_trips_and_is_immutable() {
+        let tempdir = tempdir().expect("tempdir");
+        let fs = CircuitFsLocal::new(tempdir.path()).with_test_job_id_prefix("job-");
+        let bundle = SourceBundle {
+            job_yaml: "apiVersion: eigen/v1\nkind: QuantumJob\n".to_string(),
+            program_eigen_py: b"def main():\n    pass\n".to_vec(),
+        };
+
+        fs.store_source_bundle("job-src", &bundle).expect("store source bundle");
+        assert!(tempdir.path().join("jobs/job-src/input/job.yaml").exists());
+        assert!(tempdir.path().join("jobs/job-src/input/program.eigen.py").exists());
+        assert_eq!(fs.load_source_bundle("job-src").expect("load source bundle"), bundle);
+
+        let err = fs
+            .store_source_bundle("job-src", &bundle)
+            .expect_err("source bundle must be immutable");
+        assert!(matches!(err, CircuitFsError::AlreadyExists { .. }));
+
+        let yaml_only = SourceBundle { job_yaml: "kind: QuantumJob\n".to_string(), program_eigen_py: Vec::new() };
+        fs.store_source_bundle("job-yaml-only", &yaml_only).expect("store yaml-only bundle");
+        assert!(!tempdir.path().join("jobs/job-yaml-only/input/program.eigen.py").exists());
+        assert_eq!(fs.load_source_bundle("job-yaml-only").expect("load yaml-only bundle"), yaml_only);
+    }
+
+    fn job_meta(job_id: &str) -> JobMeta {
+        JobMeta {
+            schema_version: JOB_META_SCHEMA_VERSION.to_string(),
+            job_id: job_id.to_string(),
+            name: "bell".to_string(),
+            tenant_id: Some("tenant-a".to_string()),
+            tags: BTreeMap::from([("team".to_string(), "qa".to_string())]),
+            shots: 1024,
+            state: "Error".to_string(),
+            priority: "High".to_string(),
+            created_at_unix_ms: 1_718_181_234_000,
+            updated_at_unix_ms: 1_718_181_235_500,
+            attempts: 2,
+            error_code: Some("RUNTIME_FAILED".to_string()),
+            error_summary: Some("backend rejected circuit".to_string()),
+            error_details_ref: None,
+            extra: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn list_jobs_returns_only_job_directories_with_metadata() {
+        let tempdir = tempdir().expect("tempdir");
+        let fs = CircuitFsLocal::new(tempdir.path()).with_test_job_id_prefix("job-");
+        assert!(fs.list_jobs().expect("list without jobs dir").is_empty());
+
+        for job_id in ["job-c", "job-a", "job-b"] {
+            fs.store_job_meta(&job_meta(job_id)).expect("store job meta");
+        }
+        fs.ensure_job_layout("job-layout-only").expect("layout");
+        std::fs::write(tempdir.path().join("jobs/job-file"), b"x").expect("stray file");
+        let stray = tempdir.path().join("jobs/not a job/meta");
+        std::fs::create_dir_all(&stray).expect("stray dir");
+        std::fs::write(stray.join("job.json"), b"{}").expect("stray meta");
+
+        assert_eq!(fs.list_jobs().expect("list jobs"), ["job-a", "job-b", "job-c"]);
+        assert_eq!(fs.list_job_ids().expect("list job ids"), ["job-a", "job-b", "job-c", "job-layout-only"]);
+    }
+
+    #[test]
+    fn job_meta_round_trips_and_is_rewritable() {
+        let tempdir = tempdir().expect("tempdir");
+        let fs = CircuitFsLocal::new(tempdir.path()).with_test_job_id_prefix("job-");
+        let mut meta = job_meta("job-meta");
+        assert!(matches!(fs.load_job_meta("job-meta"), Err(CircuitFsError::NotFound { .. })));
+
+        fs.store_job_meta(&meta).expect("store job meta");
+        assert_eq!(fs.load_job_meta("job-meta").expect("load job meta"), meta);
+        let stored = read_json(&tempdir.path().join("jobs/job-meta/meta/job.json"));
+        assert!(stored.get("error_details_ref").is_none());
+
+        meta.state = "Retrying".to_string();
+        meta.attempts = 3;
+        fs.store_job_meta(&meta).expect("rewrite job meta");
+        assert_eq!(fs.load_job_meta("job-meta").expect("reload job meta"), meta);
+    }
+
+    #[test]
+    fn job_meta_keeps_fields_from_newer_producers() {
+        let tempdir = tempdir().expect("tempdir");
+        let fs = CircuitFsLocal::new(tempdir.path()).with_test_job_id_prefix("job-");
+        let path = tempdir.path().join("jobs/job-newer/meta/job.json");
+        fs.ensure_job_layout("job-newer").expect("layout");
+        let document = serde_json::json!({
+            "schema_version": JOB_META_SCHEMA_VERSION,
+            "job_id": "job-newer",
+            "state": "Running",
+            "priority": "Low",
+            "device": {"id": "sim-1", "kind": "Simulator"},
+            "history": [],
+        });
+        atomic_write_checksummed(&path, &serde_json::to_vec(&document).expect("json")).expect("write meta");
+
+        let mut meta = fs.load_job_meta("job-newer").expect("load newer meta");
+        assert_eq!((meta.state.as_str(), meta.priority.as_str()), ("Running", "Low"));
+        assert_eq!(meta.extra["device"]["id"], "sim-1");
+        meta.state = "Done".to_string();
+        fs.store_job_meta(&meta).expect("rewrite meta");
+        let stored = read_json(&path);
+        assert_eq!((&stored["device"], &stored["history"]), (&document["device"], &document["history"]));
+        assert_eq!(stored["state"], "Done");
+
+        let legacy = serde_json::json!({"job_id": "job-newer", "state": "Pending"});
+        let legacy: JobMeta = serde_json::from_value(legacy).expect("meta without a schema version");
+        assert_eq!(legacy.schema_version, JOB_META_SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn job_meta_with_an_unknown_schema_version_is_rejected() {
+        let tempdir = tempdir().expect("tempdir");
+        let fs = CircuitFsLocal::new(tempdir.path()).with_test_job_id_prefix("job-");
+        let meta = JobMeta { schema_version: "99".to_string(), ..job_meta("job-future") };
+        fs.store_job_meta(&meta).expect("store is not version-checked");
+
+        match fs.load_job_meta("job-future") {
+            Err(CircuitFsError::UnsupportedSchema { path, found, supported }) => {
+                assert!(path.ends_with("jobs/job-future/meta/job.json"), "{}", path.display());
+                assert_eq!((found.as_str(), supported), ("99", JOB_META_SCHEMA_VERSION));
+            }
+            other => panic!("expected UnsupportedSchema, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn error_details_are_rewritable_and_resolve_by_ref() {
+        let tempdir = tempdir().expect("tempdir");
+        let fs = CircuitFsLocal::new(tempdir.path()).with_test_job_id_prefix("job-");
+
+        fs.store_error_details_json("job-failed", br#"{"error_code":"COMPILER_STAGE_FAILED"}"#)
+            .expect("store error details");
+        fs.store_error_details_json("job-failed", br#"{"error_code":"EXECUTION_STAGE_FAILED"}"#)
+            .expect("rewrite error details");
+        assert_eq!(
+            fs.load_error_details_json("job-failed").expect("load error details"),
+            br#"{"error_code":"EXECUTION_STAGE_FAILED"}"#
+        );
+        assert_eq!(
+            fs.read_bytes("qfs://jobs/job-failed/results/error.json").expect("read by ref"),
+            br#"{"error_code":"EXECUTION_STAGE_FAILED"}"#
+        );
+    }
+
+    #[test]
+    fn load_job_meta_reports_missing_file_as_not_found() {
+        let tempdir = tempdir().expect("tempdir");
+        let fs = CircuitFsLocal::new(tempdir.path()).with_test_job_id_prefix("job-");
+        assert!(matches!(fs.load_job_meta("job-none"), Err(CircuitFsError::NotFound { .. })));
+    }
+
+    #[test]
+    fn load_job_meta_ignores_unknown_fields() {
+        let tempdir = tempdir().expect("tempdir");
+        let fs = CircuitFsLocal::new(tempdir.path()).with_test_job_id_prefix("job-");
+        let record = serde_json::json!({
+            "job_id": "job-record",
+            "name": "bell",
+            "state": "Done",
+            "created_at_unix_ms": 1,
+            "updated_at_unix_ms": 2,
+            "error_code": null,
+            "counts": { "00": 512, "11": 512 },
+            "history": [{ "from": "Pending", "to": "Pending", "event": null, "at_unix_ms": 1, "reason": null }]
+        });
+        fs.write_bytes(
+            fs.meta_json_path("job-record").expect("meta path"),
+            &serde_json::to_vec(&record).expect("encode record"),
+        )
+        .expect("write record");
+
+        let meta = fs.load_job_meta("job-record").expect("load record as job meta");
+        assert_eq!(meta.state, "Done");
+        assert_eq!(meta.attempts, 0);
+        assert_eq!((meta.tenant_id, meta.tags.len(), meta.shots), (None, 0, 0));
+        assert_eq!(meta.error_code, None);
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -0,0 +1,352 @@
+//! Retention policy enforcement (garbage collection) for CircuitFS job directories.
+//!
+//! Jobs are ranked by their most recent modification time (the newest mtime of any
+//! file under `jobs/<job_id>/`). The newest `keep_last` jobs are always kept; the
+//! rest are removed oldest-first when they exceed `max_age`, and then until both
+//! the job count fits under `max_jobs` and the total footprint fits under
+//! `max_total_bytes`.
+//!
+//! Jobs without `meta/job.json` are never removed: they may still be being laid out,
+//! so they are reported as skipped instead. Removal first renames the job directory
+//! into `<root>/.trash/` and only then deletes it, so readers never observe a
+//! half-deleted job.
+//!
+//! Only the local filesystem is pruned; mirrored MinIO objects are left to the
+//! bucket's own lifecycle rules.
+
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::local_circuit_fs::{CircuitFsError, CircuitFsLocal};
+
+/// Job states (as recorded in `meta/job.json`) that are safe to prune.
+const TERMINAL_STATES: [&str; 4] = ["done", "error", "cancelled", "timeout"];
+
+/// Staging directory (under the CircuitFS root) for jobs being removed.
+const TRASH_DIR: &str = ".trash";
+
+/// Limits applied by [`CircuitFsLocal::garbage_collect`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    /// Remove jobs whose last modification is older than this.
+    pub max_age: Option<Duration>,
+    /// Remove the oldest jobs until at most this many remain.
+    pub max_jobs: Option<usize>,
+    /// Remove the oldest jobs until the total size of all jobs fits under this.
+    pub max_total_bytes: Option<u64>,
+    /// Never remove the N most recently modified jobs.
+    pub keep_last: Option<usize>,
+    /// Never remove jobs whose `meta/job.json` is unreadable or records a
+    /// non-terminal state.
+    pub protect_non_terminal: bool,
+    /// Never remove jobs in these states (case-insensitive), terminal or not.
+    pub protect_states: Vec<String>,
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        Self {
+            max_age: None,
+            max_jobs: None,
+            max_total_bytes: None,
+            keep_last: None,
+            protect_non_terminal: true,
+            protect_states: Vec::new(),
+        }
+    }
+}
+
+/// Outcome of a garbage collection pass.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GcReport {
+    /// Removed job ids, oldest first.
+    pub removed_job_ids: Vec<String>,
+    /// Job directories left alone because they have no `meta/job.json`, sorted.
+    pub skipped_job_ids: Vec<String>,
+    /// Bytes freed by the removed jobs.
+    pub bytes_reclaimed: u64,
+    /// Number of job directories left in place, including skipped ones.
+    pub retained_jobs: usize,
+    /// Total size of the job directories left in place.
+    pub retained_bytes: u64,
+}
+
+#[derive(Debug)]
+struct JobFootprint {
+    job_id: String,
+    size_bytes: u64,
+    modified_at: SystemTime,
+    protected: bool,
+}
+
+impl CircuitFsLocal {
+    /// Deletes job directories that fall outside `policy` and reports what was removed.
+    pub fn garbage_collect(&self, policy: &RetentionPolicy) -> Result<GcReport, CircuitFsError> {
+        self.garbage_collect_at(policy, SystemTime::now())
+    }
+
+    pub(crate) fn garbage_collect_at(
+        &self,
+        policy: &RetentionPolicy,
+        now: SystemTime,
+    ) -> Result<GcReport, CircuitFsError> {
+        let mut report = GcReport::default();
+        let mut jobs = Vec::new();
+        for job_id in self.list_job_ids()? {
+            if !self.meta_json_path(&job_id)?.is_file() {
+                report.skipped_job_ids.push(job_id);
+                continue;
+            }
+            jobs.push(self.job_footprint(job_id, policy)?);
+        }
+        let skipped_bytes = report
+            .skipped_job_ids
+            .iter()
+            .map(|job_id| dir_footprint(&self.root_path().join("jobs").join(job_id)).map(|(size, _)| size))
+            .sum::<Result<u64, CircuitFsError>>()?;
+        // Newest first, so `keep_last` is a prefix and eviction walks from the back.
+        jobs.sort_by(|a, b| b.modified_at.cmp(&a.modified_at).then_with(|| a.job_id.cmp(&b.job_id)));
+
+        let keep_last = policy.keep_last.unwrap_or(0).min(jobs.len());
+        let mut total_bytes: u64 = jobs.iter().map(|job| job.size_bytes).sum::<u64>() + skipped_bytes;
+        let mut retained_jobs = jobs.len() + report.skipped_job_ids.len();
+
+        for job in jobs[keep_last..].iter().rev() {
+            if job.protected {
+                continue;
+            }
+            let expired = policy.max_age.is_some_and(|max_age| {
+                now.duration_since(job.modified_at).unwrap_or_default() > max_age
+            });
+            let over_count = policy.max_jobs.is_some_and(|limit| retained_jobs > limit);
+            let over_budget = policy.max_total_bytes.is_some_and(|limit| total_bytes > limit);
+            if !(expired || over_count || over_budget) {
+                continue;
+            }
+            self.remove_job_dir(&job.job_id)?;
+            total_bytes = total_bytes.saturating_sub(job.size_bytes);
+            retained_jobs -= 1;
+            report.bytes_reclaimed += job.size_bytes;
+            report.removed_job_ids.push(job.job_id.clone());
+        }
+
+        report.retained_jobs = retained_jobs;
+        report.retained_bytes = total_bytes;
+        Ok(report)
+    }
+
+    /// Moves `jobs/<job_id>` into the trash directory, then deletes it there.
+    fn remove_job_dir(&self, job_id: &str) -> Result<(), CircuitFsError> {
+        let trash_dir = self.root_path().join(TRASH_DIR);
+        fs::create_dir_all(&trash_dir)?;
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_nanos();
+        let staged = trash_dir.join(format!("{job_id}.{nanos}"));
+        fs::rename(self.root_path().join("jobs").join(job_id), &staged)?;
+        fs::remove_dir_all(&staged)?;
+        Ok(())
+    }
+
+    fn job_footprint(&self, job_id: String, policy: &RetentionPolicy) -> Result<JobFootprint, CircuitFsError> {
+        let job_root = self.root_path().join("jobs").join(&job_id);
+        let (size_bytes, modified_at) = dir_footprint(&job_root)?;
+        let state = self.job_state(&job_id);
+        let terminal = state
+            .as_deref()
+            .is_some_and(|state| TERMINAL_STATES.contains(&state));
+        let protected_state = state.as_deref().is_some_and(|state| {
+            policy
+                .protect_states
+                .iter()
+                .any(|protected| protected.eq_ignore_ascii_case(state))
+        });
+        let protected = protected_state || (policy.protect_non_terminal && !terminal);
+        Ok(JobFootprint {
+            job_id,
+            size_bytes,
+            modified_at,
+            protected,
+        })
+    }
+
+    /// Lower-cased `state` from `meta/job.json`, if readable.
+    fn job_state(&self, job_id: &str) -> Option<String> {
+        let bytes = fs::read(self.meta_json_path(job_id).ok()?).ok()?;
+        serde_json::from_slice::<serde_json::Value>(&bytes)
+            .ok()?
+            .get("state")?
+            .as_str()
+            .map(str::to_ascii_lowercase)
+    }
+}
+
+/// Total file size under `dir` and the newest mtime of any entry in it.
+fn dir_footprint(dir: &Path) -> Result<(u64, SystemTime), CircuitFsError> {
+    let mut size_bytes = 0u64;
+    let mut modified_at = fs::metadata(dir)?.modified()?;
+    let mut stack = vec![dir.to_path_buf()];
+    let mut first = true;
+    while let Some(current) = stack.pop() {
+        for entry in fs::read_dir(&current)? {
+            let entry = entry?;
+            let metadata = entry.metadata()?;
+            if metadata.is_dir() {
+                stack.push(entry.path());
+                continue;
+            }
+            size_bytes += metadata.len();
+            let file_modified = metadata.modified()?;
+            // Files carry the meaningful timestamps; directory mtimes only count for empty jobs.
+            if first || file_modified > modified_at {
+                modified_at = file_modified;
+                first = false;
+            }
+        }
+    }
+    Ok((size_bytes, modified_at))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+    use tempfile::tempdir;
+
+    const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+    fn write_job(fs: &CircuitFsLocal, job_id: &str, state: &str, size: usize, age: Duration, now: SystemTime) {
+        let meta_path = fs.meta_json_path(job_id).expect("meta path");
+        std::fs::create_dir_all(meta_path.parent().expect("meta dir")).expect("create meta dir");
+        std::fs::write(&meta_path, format!(r#"{{"job_id":"{job_id}","state":"{state}"}}"#)).expect("write meta");
+        let payload_path = fs.root_path().join("jobs").join(job_id).join("results.parquet");
+        std::fs::write(&payload_path, vec![0u8; size]).expect("write payload");
+        for path in [meta_path, payload_path] {
+            File::options()
+                .write(true)
+                .open(&path)
+                .and_then(|file| file.set_modified(now - age))
+                .expect("set mtime");
+        }
+    }
+
+    fn job_size(fs: &CircuitFsLocal, job_id: &str) -> u64 {
+        dir_footprint(&fs.root_path().join("jobs").join(job_id)).expect("footprint").0
+    }
+
+    #[test]
+    fn max_age_removes_only_expired_terminal_jobs() {
+        let tempdir = tempdir().expect("tempdir");
+        let fs = CircuitFsLocal::new(tempdir.path()).with_test_job_id_prefix("job-");
+        let now = SystemTime::now();
+        write_job(&fs, "job-old-done", "Done", 10, 10 * DAY, now);
+        write_job(&fs, "job-old-running", "Running", 10, 10 * DAY, now);
+        write_job(&fs, "job-fresh-done", "Done", 10, DAY, now);
+        let reclaimed = job_size(&fs, "job-old-done");
+
+        let policy = RetentionPolicy { max_age: Some(7 * DAY), ..RetentionPolicy::default() };
+        let report = fs.garbage_collect_at(&policy, now).expect("garbage collect");
+
+        assert_eq!(report.removed_job_ids, vec!["job-old-done".to_string()]);
+        assert_eq!(report.bytes_reclaimed, reclaimed);
+        assert_eq!(report.retained_jobs, 2);
+        assert_eq!(fs.list_job_ids().expect("list"), vec!["job-fresh-done", "job-old-running"]);
+
+        let unprotected = RetentionPolicy { protect_non_terminal: false, ..policy };
+        let report = fs.garbage_collect_at(&unprotected, now).expect("garbage collect");
+        assert_eq!(report.removed_job_ids, vec!["job-old-running".to_string()]);
+    }
+
+    #[test]
+    fn keep_last_n_survives_age_and_size_limits() {
+        let tempdir = tempdir().expect("tempdir");
+        let fs = CircuitFsLocal::new(tempdir.path()).with_test_job_id_prefix("job-");
+        let now = SystemTime::now();
+        for (idx, job_id) in ["job-a", "job-b", "job-c", "job-d"].iter().enumerate() {
+            write_job(&fs, job_id, "DONE", 100, (30 - idx as u32) * DAY, now);
+        }
+
+        let policy = RetentionPolicy {
+            max_age: Some(DAY),
+            max_jobs: Some(0),
+            max_total_bytes: Some(0),
+            keep_last: Some(2),
+            protect_non_terminal: true,
+            protect_states: Vec::new(),
+        };
+        let report = fs.garbage_collect_at(&policy, now).expect("garbage collect");
+
+        assert_eq!(report.removed_job_ids, vec!["job-a".to_string(), "job-b".to_string()]);
+        assert_eq!(report.retained_jobs, 2);
+        assert_eq!(fs.list_job_ids().expect("list"), vec!["job-c", "job-d"]);
+    }
+
+    #[test]
+    fn max_total_bytes_evicts_oldest_first_until_under_budget() {
+        let tempdir = tempdir().expect("tempdir");
+        let fs = CircuitFsLocal::new(tempdir.path()).with_test_job_id_prefix("job-");
+        let now = SystemTime::now();
+        write_job(&fs, "job-oldest", "Done", 1_000, 3 * DAY, now);
+        write_job(&fs, "job-middle", "Done", 1_000, 2 * DAY, now);
+        write_job(&fs, "job-newest", "Done", 1_000, DAY, now);
+        let per_job = job_size(&fs, "job-newest");
+
+        let policy = RetentionPolicy { max_total_bytes: Some(per_job * 2), ..RetentionPolicy::default() };
+        let report = fs.garbage_collect_at(&policy, now).expect("garbage collect");
+
+        assert_eq!(report.removed_job_ids, vec!["job-oldest".to_string()]);
+        assert_eq!(report.retained_bytes, per_job * 2);
+        assert_eq!(fs.list_job_ids().expect("list"), vec!["job-middle", "job-newest"]);
+    }
+
+    #[test]
+    fn max_jobs_caps_count_and_protects_in_flight_states() {
+        let tempdir = tempdir().expect("tempdir");
+        let fs = CircuitFsLocal::new(tempdir.path()).with_test_job_id_prefix("job-");
+        let now = SystemTime::now();
+        write_job(&fs, "job-1", "Done", 10, 5 * DAY, now);
+        write_job(&fs, "job-2", "Running", 10, 4 * DAY, now);
+        write_job(&fs, "job-3", "Error", 10, 3 * DAY, now);
+        write_job(&fs, "job-4", "Done", 10, 2 * DAY, now);
+        write_job(&fs, "job-5", "Done", 10, DAY, now);
+
+        let policy = RetentionPolicy {
+            max_jobs: Some(2),
+            protect_non_terminal: false,
+            protect_states: vec!["running".to_string(), "ERROR".to_string()],
+            ..RetentionPolicy::default()
+        };
+        let report = fs.garbage_collect_at(&policy, now).expect("garbage collect");
+
+        // Protected jobs still count towards the cap, so every unprotected job goes.
+        assert_eq!(
+            report.removed_job_ids,
+            vec!["job-1".to_string(), "job-4".to_string(), "job-5".to_string()]
+        );
+        assert_eq!(report.retained_jobs, 2);
+        assert_eq!(fs.list_job_ids().expect("list"), vec!["job-2", "job-3"]);
+    }
+
+    #[test]
+    fn jobs_without_meta_are_skipped_and_trash_is_emptied() {
+        let tempdir = tempdir().expect("tempdir");
+        let fs = CircuitFsLocal::new(tempdir.path()).with_test_job_id_prefix("job-");
+        let now = SystemTime::now();
+        write_job(&fs, "job-expired", "Done", 10, 30 * DAY, now);
+        fs.ensure_job_layout("job-no-meta").expect("layout without meta");
+
+        let policy = RetentionPolicy {
+            max_age: Some(DAY),
+            max_jobs: Some(0),
+            protect_non_terminal: false,
+            ..RetentionPolicy::default()
+        };
+        let report = fs.garbage_collect_at(&policy, now).expect("garbage collect");
+
+        assert_eq!(report.removed_job_ids, vec!["job-expired".to_string()]);
+        assert_eq!(report.skipped_job_ids, vec!["job-no-meta".to_string()]);
+        assert_eq!(report.retained_jobs, 1);
+        assert_eq!(fs.list_job_ids().expect("list"), vec!["job-no-meta"]);
+        let trash = tempdir.path().join(TRASH_DIR);
+        assert_eq!(std::fs::read_dir(trash).expect("trash dir").count(), 0);
+    }
+}
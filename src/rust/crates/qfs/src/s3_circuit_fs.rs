@@ -0,0 +1,233 @@
+//! [`CircuitStore`] over an S3-compatible object store (`s3` feature).
+//!
+//! Objects use the keys of the local layout, `jobs/<job_id>/input/job.yaml` and so
+//! on, so a bucket can be synced with a CircuitFS root. Artifacts are stored
+//! uncompressed and without checksum sidecars. Write-once artifacts are put with
+//! `If-None-Match`, and log appends are read-modify-write guarded by the object's
+//! ETag, so concurrent writers never silently overwrite each other.
+
+use std::env;
+use std::io;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use object_store::aws::{AmazonS3Builder, S3ConditionalPut};
+use object_store::memory::InMemory;
+use object_store::path::Path as ObjectPath;
+use object_store::{ObjectStore, PutMode, UpdateVersion};
+
+use crate::backend::{job_key, json_error, log_key, results_bundle_objects};
+use crate::local_circuit_fs::{
+    CircuitFsError, CompiledArtifactProvenance, CompiledMetadata, ResultEnvelope, ResultsBundle, SourceBundle,
+};
+use crate::store::CircuitStore;
+
+/// Bucket holding the job objects; defaults to `eigen-qfs`, like the MinIO mirror.
+pub const S3_BUCKET_ENV: &str = "EIGEN_QFS_S3_BUCKET";
+/// Endpoint of an S3-compatible service such as MinIO; AWS when unset.
+pub const S3_ENDPOINT_ENV: &str = "EIGEN_QFS_S3_ENDPOINT";
+
+const DEFAULT_S3_BUCKET: &str = "eigen-qfs";
+
+/// Appends retried after losing an ETag race before giving up with
+/// [`CircuitFsError::Locked`].
+const LOG_APPEND_MAX_ATTEMPTS: usize = 8;
+
+/// Cheaply cloneable handle to job artifacts in an object store.
+#[derive(Debug, Clone)]
+pub struct CircuitFsS3 {
+    store: Arc<dyn ObjectStore>,
+}
+
+impl CircuitFsS3 {
+    pub fn new(store: Arc<dyn ObjectStore>) -> Self {
+        Self { store }
+    }
+
+    /// Backed by a fresh in-memory store, for tests.
+    pub fn in_memory() -> Self {
+        Self::new(Arc::new(InMemory::new()))
+    }
+
+    /// Bucket [`S3_BUCKET_ENV`] at [`S3_ENDPOINT_ENV`], with credentials and region
+    /// from the usual `AWS_*` variables.
+    pub fn from_env() -> Result<Self, CircuitFsError> {
+        let bucket = env::var(S3_BUCKET_ENV).unwrap_or_else(|_| DEFAULT_S3_BUCKET.to_string());
+        let mut builder = AmazonS3Builder::from_env()
+            .with_bucket_name(bucket)
+            .with_conditional_put(S3ConditionalPut::ETagMatch);
+        if let Ok(endpoint) = env::var(S3_ENDPOINT_ENV) {
+            builder = builder.with_allow_http(endpoint.starts_with("http://")).with_endpoint(endpoint);
+        }
+        let store = builder.build().map_err(io::Error::other)?;
+        Ok(Self::new(Arc::new(store)))
+    }
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>, CircuitFsError> {
+        let location = ObjectPath::from(key);
+        let object = self.store.get(&location).await.map_err(|err| object_error(key, err))?;
+        let bytes = object.bytes().await.map_err(|err| object_error(key, err))?;
+        Ok(bytes.to_vec())
+    }
+
+    async fn get_json<T: serde::de::DeserializeOwned>(&self, key: &str) -> Result<T, CircuitFsError> {
+        serde_json::from_slice(&self.get(key).await?).map_err(json_error)
+    }
+
+    async fn put(&self, key: &str, bytes: Vec<u8>, mode: PutMode) -> Result<(), CircuitFsError> {
+        self.store
+            .put_opts(&ObjectPath::from(key), bytes.into(), mode.into())
+            .await
+            .map(|_| ())
+            .map_err(|err| object_error(key, err))
+    }
+
+    /// Puts each object unless it already exists, in order, stopping at the first
+    /// conflict. The first object is the one loads read, so a conflicting store
+    /// leaves the earlier artifact untouched.
+    async fn put_new(&self, objects: Vec<(String, Vec<u8>)>) -> Result<(), CircuitFsError> {
+        for (key, bytes) in objects {
+            self.put(&key, bytes, PutMode::Create).await?;
+        }
+        Ok(())
+    }
+}
+
+impl CircuitStore for CircuitFsS3 {
+    async fn store_source_bundle(&self, job_id: &str, bundle: SourceBundle) -> Result<(), CircuitFsError> {
+        let mut objects = vec![(job_key(job_id, "input/job.yaml")?, bundle.job_yaml.into_bytes())];
+        if !bundle.program_eigen_py.is_empty() {
+            objects.push((job_key(job_id, "input/program.eigen.py")?, bundle.program_eigen_py));
+        }
+        self.put_new(objects).await
+    }
+
+    async fn load_source_bundle(&self, job_id: &str) -> Result<SourceBundle, CircuitFsError> {
+        let job_yaml = String::from_utf8(self.get(&job_key(job_id, "input/job.yaml")?).await?)
+            .map_err(|err| CircuitFsError::Io(io::Error::new(io::ErrorKind::InvalidData, err)))?;
+        let program_eigen_py = match self.get(&job_key(job_id, "input/program.eigen.py")?).await {
+            Ok(program) => program,
+            Err(CircuitFsError::NotFound { .. }) => Vec::new(),
+            Err(err) => return Err(err),
+        };
+        Ok(SourceBundle { job_yaml, program_eigen_py })
+    }
+
+    async fn store_compiled_artifacts_v1(
+        &self,
+        job_id: &str,
+        aqo_json: Vec<u8>,
+        qasm: Option<Vec<u8>>,
+        compile_report_json: Option<Vec<u8>>,
+        provenance: CompiledArtifactProvenance,
+    ) -> Result<(), CircuitFsError> {
+        let metadata = CompiledMetadata::v1(&aqo_json, qasm.as_deref(), compile_report_json.as_deref(), provenance);
+        let mut objects = vec![
+            (job_key(job_id, "compiled/circuit.aqo.json")?, aqo_json),
+            (job_key(job_id, "compiled/metadata.json")?, serde_json::to_vec_pretty(&metadata).map_err(json_error)?),
+        ];
+        if let Some(qasm) = qasm {
+            objects.push((job_key(job_id, "compiled/circuit.qasm")?, qasm));
+        }
+        if let Some(report) = compile_report_json {
+            objects.push((job_key(job_id, "compiled/compile_report.json")?, report));
+        }
+        self.put_new(objects).await
+    }
+
+    async fn load_compiled_aqo_json(&self, job_id: &str) -> Result<Vec<u8>, CircuitFsError> {
+        self.get(&job_key(job_id, "compiled/circuit.aqo.json")?).await
+    }
+
+    async fn store_results_bundle(
+        &self,
+        job_id: &str,
+        envelope: ResultEnvelope,
+        producer_version: &str,
+    ) -> Result<(), CircuitFsError> {
+        let objects = results_bundle_objects(&envelope, producer_version)?
+            .into_iter()
+            .map(|(rel, bytes)| Ok((job_key(job_id, rel)?, bytes)))
+            .collect::<Result<_, CircuitFsError>>()?;
+        self.put_new(objects).await
+    }
+
+    async fn load_results_bundle(&self, job_id: &str) -> Result<ResultsBundle, CircuitFsError> {
+        Ok(ResultsBundle { envelope: self.get_json(&job_key(job_id, "results/result.json")?).await? })
+    }
+
+    async fn store_error_details_json(&self, job_id: &str, details: Vec<u8>) -> Result<(), CircuitFsError> {
+        self.put(&job_key(job_id, "results/error.json")?, details, PutMode::Overwrite).await
+    }
+
+    async fn load_error_details_json(&self, job_id: &str) -> Result<Vec<u8>, CircuitFsError> {
+        self.get(&job_key(job_id, "results/error.json")?).await
+    }
+
+    async fn append_log_line(&self, job_id: &str, stream: &str, line: &str) -> Result<(), CircuitFsError> {
+        let key = log_key(job_id, stream)?;
+        let location = ObjectPath::from(key.as_str());
+        for _ in 0..LOG_APPEND_MAX_ATTEMPTS {
+            let (mut log, mode) = match self.store.get(&location).await {
+                Ok(current) => {
+                    let version =
+                        UpdateVersion { e_tag: current.meta.e_tag.clone(), version: current.meta.version.clone() };
+                    let bytes = current.bytes().await.map_err(|err| object_error(&key, err))?;
+                    (bytes.to_vec(), PutMode::Update(version))
+                }
+                Err(object_store::Error::NotFound { .. }) => (Vec::new(), PutMode::Create),
+                Err(err) => return Err(object_error(&key, err)),
+            };
+            log.extend_from_slice(line.trim_end_matches('\n').as_bytes());
+            log.push(b'\n');
+            match self.store.put_opts(&location, log.into(), mode.into()).await {
+                Ok(_) => return Ok(()),
+                Err(object_store::Error::AlreadyExists { .. } | object_store::Error::Precondition { .. }) => continue,
+                Err(err) => return Err(object_error(&key, err)),
+            }
+        }
+        Err(CircuitFsError::Locked { path: ref_path(&key) })
+    }
+
+    async fn load_log(&self, job_id: &str, stream: &str) -> Result<Vec<u8>, CircuitFsError> {
+        self.get(&log_key(job_id, stream)?).await
+    }
+}
+
+/// Objects are reported by their `qfs://` ref, which every CircuitFS API accepts as a path.
+fn ref_path(key: &str) -> PathBuf {
+    PathBuf::from(format!("qfs://{key}"))
+}
+
+/// Keeps the missing/existing distinctions callers branch on; everything else is I/O.
+fn object_error(key: &str, err: object_store::Error) -> CircuitFsError {
+    match err {
+        object_store::Error::NotFound { .. } => CircuitFsError::NotFound { path: ref_path(key) },
+        object_store::Error::AlreadyExists { .. } => CircuitFsError::AlreadyExists { path: ref_path(key) },
+        err => CircuitFsError::Io(io::Error::other(err)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn unreadable_objects_are_io_errors_not_missing_ones() {
+        let memory = Arc::new(InMemory::new());
+        let store = CircuitFsS3::new(memory.clone());
+        memory
+            .put(&ObjectPath::from("jobs/job-s3/results/result.json"), b"not json".to_vec().into())
+            .await
+            .expect("seed object");
+
+        let err = store.load_results_bundle("job-s3").await.expect_err("corrupt result");
+        assert!(matches!(err, CircuitFsError::Io(_)), "{err}");
+        match store.load_error_details_json("job-s3").await {
+            Err(CircuitFsError::NotFound { path }) => {
+                assert_eq!(path, PathBuf::from("qfs://jobs/job-s3/results/error.json"));
+            }
+            other => panic!("expected NotFound, got {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,214 @@
+//! Async per-job artifact storage, independent of where the bytes live.
+//!
+//! [`CircuitStore`] covers what the job pipeline writes and reads back: the source
+//! bundle, the compiled AQO, the results bundle, the error report and the job logs.
+//! [`CircuitFsAsync`] implements it over a local CircuitFS root; with the `s3`
+//! feature, [`crate::CircuitFsS3`] implements it over an object store using the same
+//! `jobs/<job_id>/...` keys. Every implementation reports a missing artifact as
+//! [`CircuitFsError::NotFound`] and storage failures as [`CircuitFsError::Io`].
+
+use std::fmt;
+use std::future::Future;
+
+use crate::async_circuit_fs::CircuitFsAsync;
+use crate::local_circuit_fs::{
+    CircuitFsError, CompiledArtifactProvenance, ResultEnvelope, ResultsBundle, SourceBundle,
+};
+
+/// Per-job artifact storage with the layout described in `qfs-layout.md`.
+///
+/// Source, compiled and results artifacts are written once: storing them again fails
+/// with [`CircuitFsError::AlreadyExists`]. The error report is rewritable.
+pub trait CircuitStore: Send + Sync + fmt::Debug {
+    fn store_source_bundle(
+        &self,
+        job_id: &str,
+        bundle: SourceBundle,
+    ) -> impl Future<Output = Result<(), CircuitFsError>> + Send;
+
+    fn load_source_bundle(&self, job_id: &str) -> impl Future<Output = Result<SourceBundle, CircuitFsError>> + Send;
+
+    fn store_compiled_artifacts_v1(
+        &self,
+        job_id: &str,
+        aqo_json: Vec<u8>,
+        qasm: Option<Vec<u8>>,
+        compile_report_json: Option<Vec<u8>>,
+        provenance: CompiledArtifactProvenance,
+    ) -> impl Future<Output = Result<(), CircuitFsError>> + Send;
+
+    fn load_compiled_aqo_json(&self, job_id: &str) -> impl Future<Output = Result<Vec<u8>, CircuitFsError>> + Send;
+
+    fn store_results_bundle(
+        &self,
+        job_id: &str,
+        envelope: ResultEnvelope,
+        producer_version: &str,
+    ) -> impl Future<Output = Result<(), CircuitFsError>> + Send;
+
+    fn load_results_bundle(&self, job_id: &str) -> impl Future<Output = Result<ResultsBundle, CircuitFsError>> + Send;
+
+    fn store_error_details_json(
+        &self,
+        job_id: &str,
+        details: Vec<u8>,
+    ) -> impl Future<Output = Result<(), CircuitFsError>> + Send;
+
+    fn load_error_details_json(&self, job_id: &str) -> impl Future<Output = Result<Vec<u8>, CircuitFsError>> + Send;
+
+    /// Appends `line` and a newline to `logs/<stream>.jsonl`, creating it when needed.
+    fn append_log_line(
+        &self,
+        job_id: &str,
+        stream: &str,
+        line: &str,
+    ) -> impl Future<Output = Result<(), CircuitFsError>> + Send;
+
+    /// Contents of `logs/<stream>.jsonl`.
+    fn load_log(&self, job_id: &str, stream: &str) -> impl Future<Output = Result<Vec<u8>, CircuitFsError>> + Send;
+}
+
+impl CircuitStore for CircuitFsAsync {
+    async fn store_source_bundle(&self, job_id: &str, bundle: SourceBundle) -> Result<(), CircuitFsError> {
+        CircuitFsAsync::store_source_bundle(self, job_id, bundle).await
+    }
+
+    async fn load_source_bundle(&self, job_id: &str) -> Result<SourceBundle, CircuitFsError> {
+        CircuitFsAsync::load_source_bundle(self, job_id).await
+    }
+
+    async fn store_compiled_artifacts_v1(
+        &self,
+        job_id: &str,
+        aqo_json: Vec<u8>,
+        qasm: Option<Vec<u8>>,
+        compile_report_json: Option<Vec<u8>>,
+        provenance: CompiledArtifactProvenance,
+    ) -> Result<(), CircuitFsError> {
+        CircuitFsAsync::store_compiled_artifacts_v1(self, job_id, aqo_json, qasm, compile_report_json, provenance)
+            .await
+    }
+
+    async fn load_compiled_aqo_json(&self, job_id: &str) -> Result<Vec<u8>, CircuitFsError> {
+        let job_id = job_id.to_string();
+        self.run(move |fs| fs.load_compiled_aqo_json(&job_id)).await
+    }
+
+    async fn store_results_bundle(
+        &self,
+        job_id: &str,
+        envelope: ResultEnvelope,
+        producer_version: &str,
+    ) -> Result<(), CircuitFsError> {
+        CircuitFsAsync::store_results_bundle(self, job_id, envelope, producer_version).await
+    }
+
+    async fn load_results_bundle(&self, job_id: &str) -> Result<ResultsBundle, CircuitFsError> {
+        CircuitFsAsync::load_results_bundle(self, job_id).await
+    }
+
+    async fn store_error_details_json(&self, job_id: &str, details: Vec<u8>) -> Result<(), CircuitFsError> {
+        CircuitFsAsync::store_error_details_json(self, job_id, details).await
+    }
+
+    async fn load_error_details_json(&self, job_id: &str) -> Result<Vec<u8>, CircuitFsError> {
+        CircuitFsAsync::load_error_details_json(self, job_id).await
+    }
+
+    async fn append_log_line(&self, job_id: &str, stream: &str, line: &str) -> Result<(), CircuitFsError> {
+        CircuitFsAsync::append_log_line(self, job_id, stream, line).await
+    }
+
+    async fn load_log(&self, job_id: &str, stream: &str) -> Result<Vec<u8>, CircuitFsError> {
+        let path = self.blocking().job_log_path(job_id, stream)?;
+        self.read_bytes(path).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::local_circuit_fs::CircuitFsLocal;
+    use crate::CompiledArtifactLineage;
+    use tempfile::tempdir;
+
+    fn envelope(job_id: &str) -> ResultEnvelope {
+        ResultEnvelope {
+            artifact_version: "1.0.0".to_string(),
+            schema_version: "scientific_result_bundle.v1".to_string(),
+            producer_version: "1.0.0".to_string(),
+            job_id: job_id.to_string(),
+            workload_kind: "QuantumJob".to_string(),
+            result_ref: "results/result.json".to_string(),
+            manifest_ref: "results/manifest.json".to_string(),
+            created_at_epoch_ms: 1_718_181_234_000,
+            retention_policy: "default".to_string(),
+            lineage: CompiledArtifactLineage::default(),
+            context: Default::default(),
+            summary: [("shots".to_string(), "1024".to_string())].into(),
+            measurements: Vec::new(),
+        }
+    }
+
+    fn provenance() -> CompiledArtifactProvenance {
+        CompiledArtifactProvenance {
+            producer_identity: "eigen-compiler".to_string(),
+            contract_version: "1.0.0".to_string(),
+            compiler_version: "0.1.0".to_string(),
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+            lineage: CompiledArtifactLineage::default(),
+        }
+    }
+
+    /// Round-trips every artifact kind through `store`, checking write-once and
+    /// missing-artifact behaviour along the way.
+    async fn round_trip(store: &impl CircuitStore) {
+        let job_id = "job-store";
+        let bundle = SourceBundle { job_yaml: "name: bell\n".to_string(), program_eigen_py: b"h(0)\n".to_vec() };
+        assert!(matches!(store.load_source_bundle(job_id).await, Err(CircuitFsError::NotFound { .. })));
+        store.store_source_bundle(job_id, bundle.clone()).await.expect("store source");
+        assert_eq!(store.load_source_bundle(job_id).await.expect("load source"), bundle);
+        let err = store.store_source_bundle(job_id, bundle).await.expect_err("source is immutable");
+        assert!(matches!(err, CircuitFsError::AlreadyExists { .. }), "{err}");
+
+        assert!(matches!(store.load_compiled_aqo_json(job_id).await, Err(CircuitFsError::NotFound { .. })));
+        let aqo = br#"{"ops":[]}"#.to_vec();
+        store
+            .store_compiled_artifacts_v1(job_id, aqo.clone(), Some(b"OPENQASM 3;".to_vec()), None, provenance())
+            .await
+            .expect("store compiled");
+        assert_eq!(store.load_compiled_aqo_json(job_id).await.expect("load aqo"), aqo);
+
+        assert!(matches!(store.load_results_bundle(job_id).await, Err(CircuitFsError::NotFound { .. })));
+        store.store_results_bundle(job_id, envelope(job_id), "1.0.0").await.expect("store results");
+        assert_eq!(store.load_results_bundle(job_id).await.expect("load results").envelope, envelope(job_id));
+        let err = store.store_results_bundle(job_id, envelope(job_id), "1.0.0").await.expect_err("results once");
+        assert!(matches!(err, CircuitFsError::AlreadyExists { .. }), "{err}");
+
+        store.store_error_details_json(job_id, b"{\"code\":1}".to_vec()).await.expect("store error");
+        store.store_error_details_json(job_id, b"{\"code\":2}".to_vec()).await.expect("error is rewritable");
+        assert_eq!(store.load_error_details_json(job_id).await.expect("load error"), b"{\"code\":2}");
+
+        assert!(matches!(store.load_log(job_id, "kernel").await, Err(CircuitFsError::NotFound { .. })));
+        store.append_log_line(job_id, "kernel", "one").await.expect("append");
+        store.append_log_line(job_id, "kernel", "two\n").await.expect("append");
+        assert_eq!(store.load_log(job_id, "kernel").await.expect("load log"), b"one\ntwo\n");
+
+        let err = store.load_source_bundle("../escape").await.expect_err("path traversal");
+        assert!(matches!(err, CircuitFsError::InvalidJobId { .. }), "{err}");
+        let err = store.append_log_line(job_id, "../escape", "x").await.expect_err("bad log name");
+        assert!(matches!(err, CircuitFsError::InvalidLogName { .. }), "{err}");
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn local_store_round_trips() {
+        let tempdir = tempdir().expect("tempdir");
+        round_trip(&CircuitFsAsync::from(CircuitFsLocal::new(tempdir.path()).with_test_job_id_prefix("job-"))).await;
+    }
+
+    #[cfg(feature = "s3")]
+    #[tokio::test]
+    async fn s3_store_round_trips() {
+        round_trip(&crate::CircuitFsS3::in_memory()).await;
+    }
+}
@@ -2,7 +2,9 @@
 //!
 //! This crate provides:
 //! - Deterministic job lifecycle state machine (`state_machine.rs`)
+//! - Retry-budgeted lifecycle wrapper around the state machine (`lifecycle.rs`)
 //! - Event-sourced audit trail for deterministic replay (`event_log.rs`)
+//! - Job priorities and the priority-ordered, aging dispatch queue (`queue.rs`)
 //!
 //! Described in:
 //! - `docs/architecture/components/qrtx.md`
@@ -11,4 +13,6 @@
 #![forbid(unsafe_code)]
 
 pub mod event_log;
+pub mod lifecycle;
+pub mod queue;
 pub mod state_machine;
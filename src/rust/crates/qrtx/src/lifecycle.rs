@@ -0,0 +1,160 @@
+//! Retry-aware job lifecycle on top of the pure state machine.
+//!
+//! [`crate::state_machine::transition`] stays a pure function of `(state, event)`.
+//! [`Lifecycle`] adds the one piece of memory retries need — how many run attempts
+//! have been made — and uses a [`RetryPolicy`] to turn a `Fail` while `Running`
+//! into `RetryRequested` for as long as the budget allows.
+
+use std::time::Duration;
+
+use crate::state_machine::{JobEvent, JobState, TransitionError, transition};
+
+/// Bounded retry budget for a job's run attempts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct RetryPolicy {
+    /// Total run attempts allowed, including the first one. `0` and `1` both mean
+    /// "no retries".
+    pub max_attempts: u32,
+    /// Delay the executor should wait in `Retrying` before the next attempt.
+    pub backoff_ms: u64,
+}
+
+impl RetryPolicy {
+    /// A policy that never retries.
+    pub const fn no_retry() -> Self {
+        Self {
+            max_attempts: 1,
+            backoff_ms: 0,
+        }
+    }
+
+    pub fn backoff(&self) -> Duration {
+        Duration::from_millis(self.backoff_ms)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::no_retry()
+    }
+}
+
+/// Job lifecycle that tracks run attempts against a [`RetryPolicy`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lifecycle {
+    state: JobState,
+    policy: RetryPolicy,
+    attempts: u32,
+}
+
+impl Lifecycle {
+    pub fn new(policy: RetryPolicy) -> Self {
+        Self {
+            state: JobState::Pending,
+            policy,
+            attempts: 0,
+        }
+    }
+
+    pub fn state(&self) -> JobState {
+        self.state
+    }
+
+    pub fn policy(&self) -> RetryPolicy {
+        self.policy
+    }
+
+    /// Number of run attempts started so far.
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    /// Run attempts still available after the current one.
+    pub fn retries_remaining(&self) -> u32 {
+        self.policy.max_attempts.max(1).saturating_sub(self.attempts)
+    }
+
+    /// Applies `event` and returns the new state.
+    ///
+    /// A `Fail` while `Running` becomes `RetryRequested` if the budget allows another
+    /// attempt, otherwise it fails the job. On error the lifecycle is left unchanged.
+    pub fn apply(&mut self, event: JobEvent) -> Result<JobState, TransitionError> {
+        let event = match (self.state, event) {
+            (JobState::Running, JobEvent::Fail) if self.retries_remaining() > 0 => JobEvent::RetryRequested,
+            _ => event,
+        };
+        let next = transition(self.state, event)?;
+        if event == JobEvent::StartRunning {
+            self.attempts = self.attempts.saturating_add(1);
+        }
+        self.state = next;
+        Ok(next)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn running(policy: RetryPolicy) -> Lifecycle {
+        let mut lifecycle = Lifecycle::new(policy);
+        lifecycle.apply(JobEvent::StartCompiling).unwrap();
+        lifecycle.apply(JobEvent::StartRunning).unwrap();
+        lifecycle
+    }
+
+    #[test]
+    fn failures_are_retried_until_the_budget_is_exhausted() {
+        let mut lifecycle = running(RetryPolicy {
+            max_attempts: 3,
+            backoff_ms: 25,
+        });
+
+        for attempt in 1..3 {
+            assert_eq!(lifecycle.attempts(), attempt);
+            assert_eq!(lifecycle.apply(JobEvent::Fail).unwrap(), JobState::Retrying);
+            assert_eq!(lifecycle.apply(JobEvent::StartRunning).unwrap(), JobState::Running);
+        }
+
+        assert_eq!(lifecycle.attempts(), 3);
+        assert_eq!(lifecycle.retries_remaining(), 0);
+        assert_eq!(lifecycle.apply(JobEvent::Fail).unwrap(), JobState::Error);
+        assert!(lifecycle.apply(JobEvent::StartRunning).is_err());
+        assert_eq!(lifecycle.policy().backoff(), Duration::from_millis(25));
+    }
+
+    #[test]
+    fn default_policy_fails_without_retrying() {
+        let mut lifecycle = running(RetryPolicy::default());
+        assert_eq!(lifecycle.apply(JobEvent::Fail).unwrap(), JobState::Error);
+    }
+
+    #[test]
+    fn retry_exhausted_fails_a_retrying_job() {
+        let mut lifecycle = running(RetryPolicy {
+            max_attempts: 5,
+            backoff_ms: 0,
+        });
+        lifecycle.apply(JobEvent::Fail).unwrap();
+        assert_eq!(lifecycle.apply(JobEvent::RetryExhausted).unwrap(), JobState::Error);
+    }
+
+    #[test]
+    fn cancel_from_retrying_is_accepted() {
+        let mut lifecycle = running(RetryPolicy {
+            max_attempts: 2,
+            backoff_ms: 0,
+        });
+        assert_eq!(lifecycle.apply(JobEvent::Fail).unwrap(), JobState::Retrying);
+        assert_eq!(lifecycle.apply(JobEvent::Cancel).unwrap(), JobState::Cancelled);
+        assert_eq!(lifecycle.attempts(), 1);
+    }
+
+    #[test]
+    fn rejected_events_leave_the_lifecycle_unchanged() {
+        let mut lifecycle = Lifecycle::new(RetryPolicy::default());
+        let before = lifecycle.clone();
+        assert!(lifecycle.apply(JobEvent::Complete).is_err());
+        assert_eq!(lifecycle, before);
+    }
+}
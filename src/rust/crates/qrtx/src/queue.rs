@@ -0,0 +1,280 @@
+//! Job priorities and the dispatch queue the kernel scheduler pulls from.
+//!
+//! [`JobQueue`] hands out waiting jobs by [`Priority`] first and enqueue time
+//! second, so interactive work jumps ahead of batch work without reordering jobs
+//! of the same class. With [`JobQueue::with_aging`], a job that keeps being
+//! overtaken is promoted a class at a time, so `Low` jobs cannot starve. Aging
+//! counts dispatches rather than wall time, so the order only depends on the
+//! sequence of pushes, pops and removals.
+
+use std::cmp::Ordering;
+
+/// Scheduling class of a job. Variants are ordered from lowest to highest.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub enum Priority {
+    Low,
+    #[default]
+    Normal,
+    High,
+}
+
+impl Priority {
+    /// Maps the `0..=100` wire priority onto a class: `0` (unset) is `Normal`,
+    /// `1..=33` is `Low`, `34..=66` is `Normal` and anything above is `High`.
+    pub fn from_level(level: i32) -> Self {
+        match level {
+            0 => Self::Normal,
+            i32::MIN..=33 => Self::Low,
+            34..=66 => Self::Normal,
+            _ => Self::High,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Low => "low",
+            Self::Normal => "normal",
+            Self::High => "high",
+        }
+    }
+
+    /// The next class up; `High` stays `High`.
+    fn promoted(self) -> Self {
+        match self {
+            Self::Low => Self::Normal,
+            Self::Normal | Self::High => Self::High,
+        }
+    }
+}
+
+/// A job waiting in a [`JobQueue`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueuedJob {
+    pub job_id: String,
+    pub priority: Priority,
+    /// Unix timestamp (milliseconds) when the job entered the queue.
+    pub enqueued_at_ms: i64,
+}
+
+#[derive(Debug)]
+struct Entry {
+    job: QueuedJob,
+    /// Class the job is dispatched at: its own, raised by aging.
+    effective: Priority,
+    /// Dispatches of later-enqueued jobs since the last promotion.
+    overtaken: u32,
+    /// Insertion order, breaking ties between equal timestamps.
+    seq: u64,
+}
+
+impl Entry {
+    /// Whether `self` was enqueued before `other`.
+    fn is_older_than(&self, other: &Self) -> bool {
+        (self.job.enqueued_at_ms, self.seq) < (other.job.enqueued_at_ms, other.seq)
+    }
+}
+
+impl Ord for Entry {
+    // The greatest entry is dispatched first: highest class, then the oldest.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.effective
+            .cmp(&other.effective)
+            .then_with(|| other.job.enqueued_at_ms.cmp(&self.job.enqueued_at_ms))
+            .then_with(|| other.seq.cmp(&self.seq))
+    }
+}
+
+impl PartialOrd for Entry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Entry {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Entry {}
+
+/// Jobs waiting for dispatch, ordered by priority then enqueue time.
+#[derive(Debug, Default)]
+pub struct JobQueue {
+    entries: Vec<Entry>,
+    next_seq: u64,
+    /// Promote a job after this many later jobs were dispatched ahead of it.
+    aging_threshold: Option<u32>,
+}
+
+impl JobQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Promotes a waiting job one class each time `threshold` jobs enqueued after
+    /// it have been dispatched first. A `Low` job is therefore overtaken at most
+    /// `2 * threshold` times. `0` disables aging.
+    pub fn with_aging(mut self, threshold: u32) -> Self {
+        self.aging_threshold = (threshold > 0).then_some(threshold);
+        self
+    }
+
+    pub fn push(&mut self, job_id: impl Into<String>, priority: Priority, enqueued_at_ms: i64) {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        self.entries.push(Entry {
+            job: QueuedJob {
+                job_id: job_id.into(),
+                priority,
+                enqueued_at_ms,
+            },
+            effective: priority,
+            overtaken: 0,
+            seq,
+        });
+    }
+
+    /// The job [`JobQueue::pop`] would return next.
+    pub fn peek(&self) -> Option<&QueuedJob> {
+        self.head().map(|index| &self.entries[index].job)
+    }
+
+    pub fn pop(&mut self) -> Option<QueuedJob> {
+        let head = self.entries.swap_remove(self.head()?);
+        if let Some(threshold) = self.aging_threshold {
+            for entry in self.entries.iter_mut().filter(|entry| entry.is_older_than(&head)) {
+                entry.overtaken += 1;
+                if entry.overtaken >= threshold {
+                    entry.effective = entry.effective.promoted();
+                    entry.overtaken = 0;
+                }
+            }
+        }
+        Some(head.job)
+    }
+
+    /// The job with the highest class that waited longest, as an index into `entries`.
+    fn head(&self) -> Option<usize> {
+        self.entries.iter().enumerate().max_by(|(_, a), (_, b)| a.cmp(b)).map(|(index, _)| index)
+    }
+
+    /// Removes `job_id` (e.g. a job cancelled while waiting), returning its entry.
+    pub fn remove(&mut self, job_id: &str) -> Option<QueuedJob> {
+        let mut removed = None;
+        self.entries.retain(|entry| {
+            if removed.is_none() && entry.job.job_id == job_id {
+                removed = Some(entry.job.clone());
+                return false;
+            }
+            true
+        });
+        removed
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn drain(queue: &mut JobQueue) -> Vec<String> {
+        std::iter::from_fn(|| queue.pop().map(|job| job.job_id)).collect()
+    }
+
+    #[test]
+    fn high_priority_job_is_dispatched_before_an_earlier_low_one() {
+        let mut queue = JobQueue::new();
+        queue.push("job-low", Priority::Low, 1_000);
+        queue.push("job-high", Priority::High, 2_000);
+
+        assert_eq!(queue.peek().map(|job| job.job_id.as_str()), Some("job-high"));
+        assert_eq!(drain(&mut queue), vec!["job-high", "job-low"]);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn same_priority_jobs_keep_enqueue_order() {
+        let mut queue = JobQueue::new();
+        queue.push("job-b", Priority::Normal, 2_000);
+        queue.push("job-a", Priority::Normal, 1_000);
+        queue.push("job-c", Priority::Normal, 2_000);
+        queue.push("job-urgent", Priority::High, 3_000);
+
+        assert_eq!(drain(&mut queue), vec!["job-urgent", "job-a", "job-b", "job-c"]);
+    }
+
+    #[test]
+    fn removed_jobs_are_never_dispatched() {
+        let mut queue = JobQueue::new();
+        queue.push("job-1", Priority::High, 1);
+        queue.push("job-2", Priority::Low, 2);
+
+        let removed = queue.remove("job-1").expect("queued job");
+        assert_eq!(removed.priority, Priority::High);
+        assert!(queue.remove("job-1").is_none());
+        assert_eq!(queue.len(), 1);
+        assert_eq!(drain(&mut queue), vec!["job-2"]);
+    }
+
+    #[test]
+    fn interleaved_pushes_dequeue_in_priority_order() {
+        let mut queue = JobQueue::new();
+        for (job_id, priority) in [
+            ("job-1", Priority::Low),
+            ("job-2", Priority::High),
+            ("job-3", Priority::Normal),
+            ("job-4", Priority::Low),
+            ("job-5", Priority::High),
+            ("job-6", Priority::Normal),
+        ] {
+            queue.push(job_id, priority, 1_000);
+        }
+
+        assert_eq!(drain(&mut queue), vec!["job-2", "job-5", "job-3", "job-6", "job-1", "job-4"]);
+    }
+
+    #[test]
+    fn aging_promotes_a_starved_low_job_after_the_threshold() {
+        let mut queue = JobQueue::new().with_aging(2);
+        queue.push("job-low", Priority::Low, 0);
+        // A steady stream of newer Normal jobs would starve job-low forever.
+        for n in 1..=4 {
+            queue.push(format!("job-normal-{n}"), Priority::Normal, n);
+        }
+
+        assert_eq!(queue.pop().map(|job| job.job_id), Some("job-normal-1".to_string()));
+        assert_eq!(queue.pop().map(|job| job.job_id), Some("job-normal-2".to_string()));
+        // Overtaken twice: job-low now ranks as Normal and is the oldest of them.
+        let promoted = queue.pop().expect("promoted job");
+        assert_eq!(promoted.job_id, "job-low");
+        assert_eq!(promoted.priority, Priority::Low, "the job keeps its own class");
+        assert_eq!(drain(&mut queue), vec!["job-normal-3", "job-normal-4"]);
+
+        let mut unaged = JobQueue::new().with_aging(0);
+        unaged.push("job-low", Priority::Low, 0);
+        for n in 1..=4 {
+            unaged.push(format!("job-normal-{n}"), Priority::Normal, n);
+        }
+        assert_eq!(drain(&mut unaged).last().map(String::as_str), Some("job-low"));
+    }
+
+    #[test]
+    fn wire_levels_map_onto_classes() {
+        assert_eq!(Priority::from_level(0), Priority::Normal);
+        assert_eq!(Priority::from_level(10), Priority::Low);
+        assert_eq!(Priority::from_level(50), Priority::Normal);
+        assert_eq!(Priority::from_level(90), Priority::High);
+        assert_eq!(Priority::default(), Priority::Normal);
+        assert!(Priority::High > Priority::Normal && Priority::Normal > Priority::Low);
+    }
+}
@@ -3,30 +3,222 @@
 //! Source of truth:
 //! - docs/architecture/components/qrtx.md
 //! - RFC 0007 (QRTX MVP)
+//!
+//! # Wire names
+//!
+//! [`JobState`] and [`JobEvent`] are written as SCREAMING_SNAKE_CASE strings
+//! (`PENDING`, `START_COMPILING`, ...) by serde, [`Display`](fmt::Display) and
+//! [`as_str`](JobState::as_str), and parsed back by serde and [`FromStr`]. These
+//! names are a stable contract: they end up in persisted job records, event logs
+//! and structured logs, and `tests/fixtures/wire_names_v1.json` pins them. Adding
+//! a variant is fine; renaming one is a breaking change. Serde still reads the
+//! variant names written before this contract (`Pending`, `StartCompiling`, ...).
+
+use std::fmt;
+use std::str::FromStr;
 
 use thiserror::Error;
 
 /// Canonical MVP-3 job lifecycle states.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum JobState {
+    #[serde(alias = "Pending")]
     Pending,
+    #[serde(alias = "Compiling")]
     Compiling,
+    #[serde(alias = "Running")]
     Running,
+    /// A run attempt failed and the job is waiting to be re-run.
+    #[serde(alias = "Retrying")]
+    Retrying,
+    /// Held out of dispatch by an operator; resumes as `Pending`.
+    #[serde(alias = "Paused")]
+    Paused,
+    #[serde(alias = "Done")]
     Done,
+    #[serde(alias = "Error")]
     Error,
+    #[serde(alias = "Cancelled")]
     Cancelled,
+    #[serde(alias = "Timeout")]
     Timeout,
 }
 
+impl JobState {
+    /// Every state, in declaration order.
+    pub const ALL: [JobState; 9] = [
+        JobState::Pending,
+        JobState::Compiling,
+        JobState::Running,
+        JobState::Retrying,
+        JobState::Paused,
+        JobState::Done,
+        JobState::Error,
+        JobState::Cancelled,
+        JobState::Timeout,
+    ];
+
+    /// Wire name of the state, e.g. `PENDING`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pending => "PENDING",
+            Self::Compiling => "COMPILING",
+            Self::Running => "RUNNING",
+            Self::Retrying => "RETRYING",
+            Self::Paused => "PAUSED",
+            Self::Done => "DONE",
+            Self::Error => "ERROR",
+            Self::Cancelled => "CANCELLED",
+            Self::Timeout => "TIMEOUT",
+        }
+    }
+
+    /// Whether the job is finished; terminal states accept no further events.
+    pub fn is_terminal(self) -> bool {
+        matches!(self, Self::Done | Self::Error | Self::Cancelled | Self::Timeout)
+    }
+}
+
+impl fmt::Display for JobState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for JobState {
+    type Err = UnknownWireName;
+
+    /// Parses a wire name; the legacy variant names are only accepted by serde.
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|state| state.as_str() == raw)
+            .ok_or_else(|| UnknownWireName { kind: "job state", value: raw.to_string() })
+    }
+}
+
 /// Events that can cause a state transition.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum JobEvent {
+    #[serde(alias = "StartCompiling")]
     StartCompiling,
+    #[serde(alias = "StartRunning")]
     StartRunning,
+    #[serde(alias = "Complete")]
     Complete,
+    /// A compile-only job is finished once it has compiled; it never runs.
+    #[serde(alias = "FinishCompilingOnly")]
+    FinishCompilingOnly,
+    #[serde(alias = "Fail")]
     Fail,
+    #[serde(alias = "Cancel")]
     Cancel,
+    #[serde(alias = "TimeOut")]
     TimeOut,
+    /// A run attempt failed but the retry budget allows another attempt.
+    #[serde(alias = "RetryRequested")]
+    RetryRequested,
+    /// The retry budget is spent; the job fails for good.
+    #[serde(alias = "RetryExhausted")]
+    RetryExhausted,
+    /// An operator holds a queued job without cancelling it.
+    #[serde(alias = "Pause")]
+    Pause,
+    /// A paused job goes back to the queue.
+    #[serde(alias = "Resume")]
+    Resume,
+}
+
+impl JobEvent {
+    /// Every event, in declaration order.
+    pub const ALL: [JobEvent; 11] = [
+        JobEvent::StartCompiling,
+        JobEvent::StartRunning,
+        JobEvent::Complete,
+        JobEvent::FinishCompilingOnly,
+        JobEvent::Fail,
+        JobEvent::Cancel,
+        JobEvent::TimeOut,
+        JobEvent::RetryRequested,
+        JobEvent::RetryExhausted,
+        JobEvent::Pause,
+        JobEvent::Resume,
+    ];
+
+    /// Wire name of the event, e.g. `START_COMPILING`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::StartCompiling => "START_COMPILING",
+            Self::StartRunning => "START_RUNNING",
+            Self::Complete => "COMPLETE",
+            Self::FinishCompilingOnly => "FINISH_COMPILING_ONLY",
+            Self::Fail => "FAIL",
+            Self::Cancel => "CANCEL",
+            Self::TimeOut => "TIME_OUT",
+            Self::RetryRequested => "RETRY_REQUESTED",
+            Self::RetryExhausted => "RETRY_EXHAUSTED",
+            Self::Pause => "PAUSE",
+            Self::Resume => "RESUME",
+        }
+    }
+}
+
+impl fmt::Display for JobEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for JobEvent {
+    type Err = UnknownWireName;
+
+    /// Parses a wire name; the legacy variant names are only accepted by serde.
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|event| event.as_str() == raw)
+            .ok_or_else(|| UnknownWireName { kind: "job event", value: raw.to_string() })
+    }
+}
+
+/// A string that is not the wire name of any [`JobState`] or [`JobEvent`].
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+#[error("unknown {kind} {value:?}")]
+pub struct UnknownWireName {
+    /// `job state` or `job event`.
+    pub kind: &'static str,
+    pub value: String,
+}
+
+/// A [`JobEvent`] together with the data a store records alongside the
+/// transition, so a failed job gets its error in the same write as its state.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum JobEventWithReason {
+    /// [`JobEvent::Fail`] with the error reported for the job.
+    Fail { code: String, summary: String },
+    /// [`JobEvent::Cancel`] with who asked for the cancellation.
+    Cancel { requested_by: String },
+    /// Any event without a payload.
+    Plain(JobEvent),
+}
+
+impl JobEventWithReason {
+    /// The event the state machine sees; [`transition`] only looks at this.
+    pub fn event(&self) -> JobEvent {
+        match self {
+            Self::Fail { .. } => JobEvent::Fail,
+            Self::Cancel { .. } => JobEvent::Cancel,
+            Self::Plain(event) => *event,
+        }
+    }
+}
+
+impl From<JobEvent> for JobEventWithReason {
+    fn from(event: JobEvent) -> Self {
+        Self::Plain(event)
+    }
 }
 
 #[derive(Debug, Error, Clone, PartialEq, Eq)]
@@ -47,14 +239,28 @@ pub fn transition(from: JobState, event: JobEvent) -> Result<JobState, Transitio
         (S::Pending, E::StartCompiling) => S::Compiling,
         (S::Compiling, E::StartRunning) => S::Running,
         (S::Running, E::Complete) => S::Done,
+        (S::Compiling, E::FinishCompilingOnly) => S::Done,
 
-        // Cancellation/failure/timeout are allowed from non-terminal states.
-        (S::Pending | S::Compiling | S::Running, E::Cancel) => S::Cancelled,
-        (S::Pending | S::Compiling | S::Running, E::Fail) => S::Error,
-        (S::Pending | S::Compiling | S::Running, E::TimeOut) => S::Timeout,
+        // Retry loop. Whether a failure is retried is decided by the caller's
+        // retry budget (see `lifecycle::Lifecycle`), not here.
+        (S::Running, E::RetryRequested) => S::Retrying,
+        (S::Retrying, E::StartRunning) => S::Running,
+        (S::Retrying, E::RetryExhausted) => S::Error,
+
+        // Only a job still waiting in the queue can be held; it keeps its place
+        // in the lifecycle and resumes where it was. A held job runs nothing, so
+        // it cannot fail or time out; it can only be resumed or cancelled.
+        (S::Pending, E::Pause) => S::Paused,
+        (S::Paused, E::Resume) => S::Pending,
+        (S::Paused, E::Cancel) => S::Cancelled,
+
+        // Cancellation/failure/timeout are allowed from the other non-terminal states.
+        (S::Pending | S::Compiling | S::Running | S::Retrying, E::Cancel) => S::Cancelled,
+        (S::Pending | S::Compiling | S::Running | S::Retrying, E::Fail) => S::Error,
+        (S::Pending | S::Compiling | S::Running | S::Retrying, E::TimeOut) => S::Timeout,
 
         // Terminal states do not accept transitions.
-        (S::Done | S::Error | S::Cancelled | S::Timeout, _) => {
+        _ if from.is_terminal() => {
             return Err(TransitionError::Invalid { from, event });
         }
 
@@ -82,13 +288,20 @@ mod tests {
 
     #[test]
     fn cancellation_failure_timeout_are_allowed_from_non_terminal_states() {
-        let non_terminal = [JobState::Pending, JobState::Compiling, JobState::Running];
+        let non_terminal = [
+            JobState::Pending,
+            JobState::Compiling,
+            JobState::Running,
+            JobState::Retrying,
+        ];
 
         for s in non_terminal {
             assert_eq!(
                 transition(s, JobEvent::Cancel).unwrap(),
                 JobState::Cancelled
             );
+            assert_eq!(transition(s, JobEvent::Fail).unwrap(), JobState::Error);
+            assert_eq!(transition(s, JobEvent::TimeOut).unwrap(), JobState::Timeout);
         }
     }
 
@@ -98,9 +311,14 @@ mod tests {
             JobEvent::StartCompiling,
             JobEvent::StartRunning,
             JobEvent::Complete,
+            JobEvent::FinishCompilingOnly,
             JobEvent::Fail,
             JobEvent::Cancel,
             JobEvent::TimeOut,
+            JobEvent::RetryRequested,
+            JobEvent::RetryExhausted,
+            JobEvent::Pause,
+            JobEvent::Resume,
         ];
 
         for s in [
@@ -115,4 +333,113 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn retry_loop_transitions() {
+        let retrying = transition(JobState::Running, JobEvent::RetryRequested).unwrap();
+        assert_eq!(retrying, JobState::Retrying);
+        assert_eq!(transition(retrying, JobEvent::StartRunning).unwrap(), JobState::Running);
+        assert_eq!(transition(retrying, JobEvent::RetryExhausted).unwrap(), JobState::Error);
+
+        for s in [JobState::Pending, JobState::Compiling] {
+            assert!(transition(s, JobEvent::RetryRequested).is_err());
+            assert!(transition(s, JobEvent::RetryExhausted).is_err());
+        }
+    }
+
+    #[test]
+    fn pause_and_resume_only_apply_to_queued_and_paused_jobs() {
+        let states = [
+            JobState::Pending,
+            JobState::Compiling,
+            JobState::Running,
+            JobState::Retrying,
+            JobState::Paused,
+            JobState::Done,
+            JobState::Error,
+            JobState::Cancelled,
+            JobState::Timeout,
+        ];
+        for s in states {
+            let paused = transition(s, JobEvent::Pause);
+            let resumed = transition(s, JobEvent::Resume);
+            match s {
+                JobState::Pending => {
+                    assert_eq!(paused, Ok(JobState::Paused));
+                    assert!(resumed.is_err());
+                }
+                JobState::Paused => {
+                    assert_eq!(paused, Err(TransitionError::Invalid { from: s, event: JobEvent::Pause }));
+                    assert_eq!(resumed, Ok(JobState::Pending));
+                }
+                _ => {
+                    assert_eq!(paused, Err(TransitionError::Invalid { from: s, event: JobEvent::Pause }));
+                    assert_eq!(resumed, Err(TransitionError::Invalid { from: s, event: JobEvent::Resume }));
+                }
+            }
+        }
+
+        // A paused job does not progress until it is resumed.
+        for event in [JobEvent::StartCompiling, JobEvent::StartRunning, JobEvent::Complete, JobEvent::RetryRequested] {
+            assert!(transition(JobState::Paused, event).is_err(), "{event:?}");
+        }
+        let resumed = transition(transition(JobState::Pending, JobEvent::Pause).unwrap(), JobEvent::Resume).unwrap();
+        assert_eq!(transition(resumed, JobEvent::StartCompiling), Ok(JobState::Compiling));
+    }
+
+    #[test]
+    fn paused_jobs_can_only_be_resumed_or_cancelled() {
+        for event in JobEvent::ALL {
+            let next = transition(JobState::Paused, event);
+            match event {
+                JobEvent::Resume => assert_eq!(next, Ok(JobState::Pending)),
+                JobEvent::Cancel => assert_eq!(next, Ok(JobState::Cancelled)),
+                _ => assert_eq!(next, Err(TransitionError::Invalid { from: JobState::Paused, event }), "{event:?}"),
+            }
+        }
+        for event in [JobEvent::Fail, JobEvent::TimeOut] {
+            assert!(transition(JobState::Paused, event).is_err(), "{event:?}");
+        }
+    }
+
+    #[test]
+    fn wire_names_round_trip_through_serde_and_from_str() {
+        for state in JobState::ALL {
+            let json = serde_json::to_string(&state).unwrap();
+            assert_eq!(json, format!("\"{state}\""));
+            assert_eq!(serde_json::from_str::<JobState>(&json).unwrap(), state);
+            assert_eq!(state.as_str().parse::<JobState>(), Ok(state));
+        }
+        for event in JobEvent::ALL {
+            let json = serde_json::to_string(&event).unwrap();
+            assert_eq!(json, format!("\"{event}\""));
+            assert_eq!(serde_json::from_str::<JobEvent>(&json).unwrap(), event);
+            assert_eq!(event.as_str().parse::<JobEvent>(), Ok(event));
+        }
+
+        // Records written before the wire names were fixed still load.
+        assert_eq!(serde_json::from_str::<JobState>("\"Cancelled\"").unwrap(), JobState::Cancelled);
+        assert_eq!(serde_json::from_str::<JobEvent>("\"StartCompiling\"").unwrap(), JobEvent::StartCompiling);
+        let err = "Pending".parse::<JobState>().unwrap_err();
+        assert_eq!(err.to_string(), "unknown job state \"Pending\"");
+        assert!("TIMEOUT".parse::<JobEvent>().is_err());
+    }
+
+    #[test]
+    fn only_done_error_cancelled_and_timeout_are_terminal() {
+        let terminal: Vec<JobState> = JobState::ALL.into_iter().filter(|state| state.is_terminal()).collect();
+        assert_eq!(terminal, [JobState::Done, JobState::Error, JobState::Cancelled, JobState::Timeout]);
+        for state in JobState::ALL {
+            let accepts_some_event = JobEvent::ALL.into_iter().any(|event| transition(state, event).is_ok());
+            assert_eq!(accepts_some_event, !state.is_terminal(), "{state}");
+        }
+    }
+
+    #[test]
+    fn compile_only_jobs_finish_from_compiling() {
+        assert_eq!(transition(JobState::Compiling, JobEvent::FinishCompilingOnly), Ok(JobState::Done));
+        for s in [JobState::Pending, JobState::Running, JobState::Retrying, JobState::Paused] {
+            assert!(transition(s, JobEvent::FinishCompilingOnly).is_err(), "{s:?}");
+        }
+    }
 }
@@ -0,0 +1,47 @@
+use std::fs;
+use std::path::PathBuf;
+
+use qrtx::state_machine::{JobEvent, JobState};
+
+fn fixture() -> serde_json::Value {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join("wire_names_v1.json");
+    let raw = fs::read_to_string(path).expect("fixture file must exist");
+    serde_json::from_str(&raw).expect("fixture must be valid json")
+}
+
+fn names(key: &str) -> Vec<String> {
+    serde_json::from_value(fixture()[key].clone()).expect("fixture must list names")
+}
+
+#[test]
+fn job_state_wire_names_match_the_v1_fixture() {
+    let serialized: Vec<String> = JobState::ALL
+        .iter()
+        .map(|state| serde_json::to_value(state).unwrap().as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(serialized, names("job_states"), "renaming a JobState variant breaks the wire contract");
+
+    for (name, state) in names("job_states").iter().zip(JobState::ALL) {
+        assert_eq!(serde_json::from_value::<JobState>(name.as_str().into()).unwrap(), state);
+        assert_eq!(name.parse::<JobState>(), Ok(state));
+        assert_eq!(state.to_string(), *name);
+    }
+}
+
+#[test]
+fn job_event_wire_names_match_the_v1_fixture() {
+    let serialized: Vec<String> = JobEvent::ALL
+        .iter()
+        .map(|event| serde_json::to_value(event).unwrap().as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(serialized, names("job_events"), "renaming a JobEvent variant breaks the wire contract");
+
+    for (name, event) in names("job_events").iter().zip(JobEvent::ALL) {
+        assert_eq!(serde_json::from_value::<JobEvent>(name.as_str().into()).unwrap(), event);
+        assert_eq!(name.parse::<JobEvent>(), Ok(event));
+        assert_eq!(event.to_string(), *name);
+    }
+}
@@ -0,0 +1,196 @@
+//! Registered execution devices.
+//!
+//! A [`DevicePool`] holds the simulators and QPUs a node can dispatch to. A job
+//! takes one device of the type it needs with [`DevicePool::acquire`] and has it
+//! to itself until the [`DeviceHandle`] is released or dropped.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Kind of device a job can run on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DeviceType {
+    Simulator,
+    Qpu,
+}
+
+/// A device known to the pool.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Device {
+    pub id: String,
+    pub device_type: DeviceType,
+    pub backend_name: String,
+    /// Whether the device can be handed out; the pool clears it while a handle holds
+    /// the device. Devices registered unavailable are never handed out.
+    pub is_available: bool,
+}
+
+/// Devices of one type. `free` has one permit per available device, so a permit
+/// always finds a device to take.
+#[derive(Debug)]
+struct TypePool {
+    free: Arc<Semaphore>,
+    devices: Mutex<Vec<Device>>,
+}
+
+impl TypePool {
+    fn new() -> Self {
+        Self { free: Arc::new(Semaphore::new(0)), devices: Mutex::new(Vec::new()) }
+    }
+
+    fn set_available(&self, id: &str, is_available: bool) {
+        let mut devices = self.devices.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(device) = devices.iter_mut().find(|device| device.id == id) {
+            device.is_available = is_available;
+        }
+    }
+}
+
+/// Devices of every type, handed out one job at a time.
+#[derive(Debug, Default)]
+pub struct DevicePool {
+    types: HashMap<DeviceType, Arc<TypePool>>,
+}
+
+/// Exclusive use of one device, given back to its pool on drop.
+#[derive(Debug)]
+#[must_use = "the device is released as soon as the handle is dropped"]
+pub struct DeviceHandle {
+    id: String,
+    device_type: DeviceType,
+    backend_name: String,
+    pool: Arc<TypePool>,
+    _permit: OwnedSemaphorePermit,
+}
+
+impl DeviceHandle {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn device_type(&self) -> DeviceType {
+        self.device_type
+    }
+
+    pub fn backend_name(&self) -> &str {
+        &self.backend_name
+    }
+}
+
+impl Drop for DeviceHandle {
+    fn drop(&mut self) {
+        // Runs before `_permit` is dropped, so the device is free before its permit is.
+        self.pool.set_available(&self.id, true);
+    }
+}
+
+impl DevicePool {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `device` to the pool. A device whose id is already registered is ignored.
+    pub fn register_device(&mut self, device: Device) {
+        if self.device(&device.id).is_some() {
+            return;
+        }
+        let pool = self.types.entry(device.device_type).or_insert_with(|| Arc::new(TypePool::new()));
+        if device.is_available {
+            pool.free.add_permits(1);
+        }
+        pool.devices.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).push(device);
+    }
+
+    /// Takes a free device of `device_type`, or returns `None` without waiting when
+    /// every one is in use or none is registered.
+    pub async fn acquire(&self, device_type: DeviceType) -> Option<DeviceHandle> {
+        let pool = self.types.get(&device_type)?;
+        let permit = pool.free.clone().try_acquire_owned().ok()?;
+        let mut devices = pool.devices.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let device = devices
+            .iter_mut()
+            .find(|device| device.is_available)
+            .expect("a free permit means a device is available");
+        device.is_available = false;
+        Some(DeviceHandle {
+            id: device.id.clone(),
+            device_type,
+            backend_name: device.backend_name.clone(),
+            pool: pool.clone(),
+            _permit: permit,
+        })
+    }
+
+    /// Gives the device back to the pool; the same as dropping `handle`.
+    pub fn release(&self, handle: DeviceHandle) {
+        drop(handle);
+    }
+
+    /// Snapshot of the device registered as `id`.
+    pub fn device(&self, id: &str) -> Option<Device> {
+        self.types.values().find_map(|pool| {
+            let devices = pool.devices.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            devices.iter().find(|device| device.id == id).cloned()
+        })
+    }
+
+    /// Devices of `device_type` that [`DevicePool::acquire`] could hand out now.
+    pub fn available(&self, device_type: DeviceType) -> usize {
+        self.types.get(&device_type).map_or(0, |pool| pool.free.available_permits())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn device(id: &str, device_type: DeviceType, is_available: bool) -> Device {
+        Device { id: id.to_string(), device_type, backend_name: format!("{id}-backend"), is_available }
+    }
+
+    fn pool() -> DevicePool {
+        let mut pool = DevicePool::new();
+        pool.register_device(device("sim-1", DeviceType::Simulator, true));
+        pool.register_device(device("sim-2", DeviceType::Simulator, true));
+        pool.register_device(device("sim-offline", DeviceType::Simulator, false));
+        pool.register_device(device("qpu-1", DeviceType::Qpu, true));
+        pool
+    }
+
+    #[tokio::test]
+    async fn simulators_are_handed_out_once_until_released() {
+        let pool = pool();
+        let first = pool.acquire(DeviceType::Simulator).await.expect("first simulator");
+        let second = pool.acquire(DeviceType::Simulator).await.expect("second simulator");
+        assert_ne!(first.id(), second.id());
+        assert_eq!(second.backend_name(), format!("{}-backend", second.id()));
+        assert!(pool.acquire(DeviceType::Simulator).await.is_none(), "offline device must not be handed out");
+        assert_eq!(pool.device(first.id()).map(|device| device.is_available), Some(false));
+
+        let released = first.id().to_string();
+        pool.release(first);
+        assert_eq!(pool.available(DeviceType::Simulator), 1);
+        let again = pool.acquire(DeviceType::Simulator).await.expect("released simulator");
+        assert_eq!(again.id(), released);
+
+        let qpu = pool.acquire(DeviceType::Qpu).await.expect("QPUs are pooled separately");
+        assert_eq!(qpu.device_type(), DeviceType::Qpu);
+    }
+
+    #[tokio::test]
+    async fn dropped_handles_release_their_device() {
+        let mut pool = pool();
+        {
+            let _qpu = pool.acquire(DeviceType::Qpu).await.expect("qpu");
+            assert_eq!(pool.available(DeviceType::Qpu), 0);
+        }
+        assert_eq!(pool.available(DeviceType::Qpu), 1);
+        assert_eq!(pool.device("qpu-1").map(|device| device.is_available), Some(true));
+
+        pool.register_device(device("qpu-1", DeviceType::Simulator, true));
+        assert_eq!(pool.available(DeviceType::Simulator), 2, "duplicate id is ignored");
+        assert!(DevicePool::new().acquire(DeviceType::Qpu).await.is_none());
+    }
+}
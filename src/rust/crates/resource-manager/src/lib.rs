@@ -2,6 +2,9 @@
 //!
 //! This module implements Scheduler Core v2 with:
 //! - configurable admission control with per-tenant and per-project quotas
+//! - per-tenant concurrency leases ([`QuotaManager`])
+//! - node-wide execution slots ([`SlotPool`])
+//! - exclusive use of registered simulators and QPUs ([`DevicePool`])
 //! - weighted fairness dispatch across tenants/projects
 //! - starvation prevention guardrails
 //! - observable scheduler decisions and health/metrics snapshots
@@ -11,6 +14,14 @@
 use std::cmp::Ordering;
 use std::collections::{BTreeMap, HashMap, VecDeque};
 
+mod device_pool;
+mod quota;
+mod slot_pool;
+
+pub use device_pool::{Device, DeviceHandle, DevicePool, DeviceType};
+pub use quota::{QuotaError, QuotaLease, QuotaManager, QuotaPolicy};
+pub use slot_pool::{SlotLease, SlotPool};
+
 /// SemVer version for scheduler decision DTOs/contracts.
 ///
 /// Any breaking change to queue semantics, quota semantics,
@@ -0,0 +1,212 @@
+//! Per-tenant concurrency quotas.
+//!
+//! Unlike the scheduler's admission policy, which bounds queue depth, a
+//! [`QuotaManager`] bounds how much work a tenant has in flight: a job takes a
+//! [`QuotaLease`] when it is accepted and gives it back once it is terminal.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::sync::{Mutex, PoisonError};
+
+/// Concurrency limits enforced by [`QuotaManager`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuotaPolicy {
+    /// Maximum cost (one per job) a tenant may hold at once.
+    pub max_concurrent_jobs_per_tenant: u64,
+    /// Per-tenant overrides of `max_concurrent_jobs_per_tenant`.
+    pub tenant_limits: BTreeMap<String, u64>,
+}
+
+impl Default for QuotaPolicy {
+    fn default() -> Self {
+        Self {
+            max_concurrent_jobs_per_tenant: 64,
+            tenant_limits: BTreeMap::new(),
+        }
+    }
+}
+
+impl QuotaPolicy {
+    pub fn limit_for(&self, tenant_id: &str) -> u64 {
+        self.tenant_limits
+            .get(tenant_id)
+            .copied()
+            .unwrap_or(self.max_concurrent_jobs_per_tenant)
+    }
+}
+
+/// Quota acquisition errors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QuotaError {
+    EmptyTenantId,
+    /// Granting `requested` would take the tenant past its limit.
+    Exhausted {
+        tenant_id: String,
+        requested: u64,
+        in_use: u64,
+        limit: u64,
+    },
+}
+
+impl fmt::Display for QuotaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EmptyTenantId => f.write_str("tenant id is required"),
+            Self::Exhausted { tenant_id, requested, in_use, limit } => write!(
+                f,
+                "tenant {tenant_id} is over its quota ({in_use} of {limit} in use, {requested} requested)"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for QuotaError {}
+
+/// Capacity held by one tenant until handed back through [`QuotaManager::release`].
+#[derive(Debug, PartialEq, Eq)]
+#[must_use = "a dropped lease keeps its capacity until released"]
+pub struct QuotaLease {
+    tenant_id: String,
+    cost: u64,
+}
+
+impl QuotaLease {
+    pub fn tenant_id(&self) -> &str {
+        &self.tenant_id
+    }
+
+    pub fn cost(&self) -> u64 {
+        self.cost
+    }
+}
+
+/// Thread-safe per-tenant quota accounting.
+#[derive(Debug, Default)]
+pub struct QuotaManager {
+    policy: QuotaPolicy,
+    in_use: Mutex<HashMap<String, u64>>,
+}
+
+impl QuotaManager {
+    pub fn new(policy: QuotaPolicy) -> Self {
+        Self {
+            policy,
+            in_use: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn policy(&self) -> &QuotaPolicy {
+        &self.policy
+    }
+
+    /// Reserves `cost` for `tenant_id`, or fails without reserving anything when
+    /// the tenant's limit would be exceeded.
+    pub fn try_acquire(&self, tenant_id: &str, cost: u64) -> Result<QuotaLease, QuotaError> {
+        if tenant_id.trim().is_empty() {
+            return Err(QuotaError::EmptyTenantId);
+        }
+        let limit = self.policy.limit_for(tenant_id);
+        let mut in_use = self.in_use.lock().unwrap_or_else(PoisonError::into_inner);
+        let used = in_use.get(tenant_id).copied().unwrap_or(0);
+        if used.saturating_add(cost) > limit {
+            return Err(QuotaError::Exhausted {
+                tenant_id: tenant_id.to_string(),
+                requested: cost,
+                in_use: used,
+                limit,
+            });
+        }
+        in_use.insert(tenant_id.to_string(), used + cost);
+        Ok(QuotaLease {
+            tenant_id: tenant_id.to_string(),
+            cost,
+        })
+    }
+
+    /// Returns the lease's capacity to its tenant.
+    pub fn release(&self, lease: QuotaLease) {
+        let mut in_use = self.in_use.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(used) = in_use.get_mut(&lease.tenant_id) {
+            *used = used.saturating_sub(lease.cost);
+            if *used == 0 {
+                in_use.remove(&lease.tenant_id);
+            }
+        }
+    }
+
+    /// Capacity currently leased to `tenant_id`.
+    pub fn in_use(&self, tenant_id: &str) -> u64 {
+        self.in_use
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(tenant_id)
+            .copied()
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manager(limit: u64) -> QuotaManager {
+        QuotaManager::new(QuotaPolicy {
+            max_concurrent_jobs_per_tenant: limit,
+            ..QuotaPolicy::default()
+        })
+    }
+
+    #[test]
+    fn acquires_up_to_the_limit() {
+        let quotas = manager(3);
+        let leases: Vec<QuotaLease> = (0..3)
+            .map(|_| quotas.try_acquire("tenant-a", 1).expect("within quota"))
+            .collect();
+        assert_eq!(quotas.in_use("tenant-a"), 3);
+        assert!(leases.iter().all(|lease| lease.tenant_id() == "tenant-a" && lease.cost() == 1));
+    }
+
+    #[test]
+    fn rejects_at_the_limit_without_touching_other_tenants() {
+        let quotas = manager(2);
+        let _first = quotas.try_acquire("tenant-a", 1).expect("first");
+        let _second = quotas.try_acquire("tenant-a", 1).expect("second");
+
+        assert_eq!(
+            quotas.try_acquire("tenant-a", 1),
+            Err(QuotaError::Exhausted {
+                tenant_id: "tenant-a".to_string(),
+                requested: 1,
+                in_use: 2,
+                limit: 2,
+            })
+        );
+        assert_eq!(quotas.in_use("tenant-a"), 2);
+        let err = quotas.try_acquire("tenant-a", 1).expect_err("over quota");
+        assert_eq!(err.to_string(), "tenant tenant-a is over its quota (2 of 2 in use, 1 requested)");
+        assert!(quotas.try_acquire("tenant-b", 2).is_ok());
+        assert_eq!(quotas.try_acquire(" ", 1), Err(QuotaError::EmptyTenantId));
+    }
+
+    #[test]
+    fn release_frees_capacity() {
+        let quotas = manager(1);
+        let lease = quotas.try_acquire("tenant-a", 1).expect("first");
+        assert!(quotas.try_acquire("tenant-a", 1).is_err());
+
+        quotas.release(lease);
+        assert_eq!(quotas.in_use("tenant-a"), 0);
+        let lease = quotas.try_acquire("tenant-a", 1).expect("capacity is back");
+        quotas.release(lease);
+    }
+
+    #[test]
+    fn tenant_overrides_replace_the_default_limit() {
+        let quotas = QuotaManager::new(QuotaPolicy {
+            max_concurrent_jobs_per_tenant: 1,
+            tenant_limits: BTreeMap::from([("tenant-big".to_string(), 3)]),
+        });
+        assert!(quotas.try_acquire("tenant-big", 3).is_ok());
+        assert!(quotas.try_acquire("tenant-small", 2).is_err());
+    }
+}
@@ -0,0 +1,130 @@
+//! Simulator execution slots.
+//!
+//! A [`SlotPool`] bounds how many jobs execute at once on the local node. A job
+//! holds a [`SlotLease`] while it runs; the slot goes back to the pool when the
+//! lease is dropped, on every exit path of the job.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Fixed number of execution slots shared by the jobs of one node.
+///
+/// Waiters in [`SlotPool::acquire`] are served in arrival order. Cloning gives
+/// another handle to the same slots.
+#[derive(Debug, Clone)]
+pub struct SlotPool {
+    capacity: usize,
+    slots: Arc<Semaphore>,
+    pending: Arc<AtomicUsize>,
+}
+
+/// One execution slot held by `job_id`, returned to its pool on drop.
+#[derive(Debug)]
+#[must_use = "the slot is released as soon as the lease is dropped"]
+pub struct SlotLease {
+    job_id: String,
+    _permit: OwnedSemaphorePermit,
+}
+
+impl SlotLease {
+    pub fn job_id(&self) -> &str {
+        &self.job_id
+    }
+}
+
+/// Counts a caller of [`SlotPool::acquire`] as pending until it gets its slot
+/// or stops waiting.
+struct PendingGuard<'a>(&'a AtomicUsize);
+
+impl Drop for PendingGuard<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+impl SlotPool {
+    /// A pool of `capacity` slots; a capacity of zero is raised to one so that
+    /// jobs can always make progress.
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            capacity,
+            slots: Arc::new(Semaphore::new(capacity)),
+            pending: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Waits for a free slot. Dropping the future before it resolves (e.g. when
+    /// the job is cancelled) abandons the wait without taking a slot.
+    pub async fn acquire(&self, job_id: &str) -> SlotLease {
+        self.pending.fetch_add(1, Ordering::Relaxed);
+        let _pending = PendingGuard(&self.pending);
+        let permit = self.slots.clone().acquire_owned().await.expect("slot pool semaphore is never closed");
+        SlotLease { job_id: job_id.to_string(), _permit: permit }
+    }
+
+    /// Takes a free slot without waiting, or returns `None` when all are in use.
+    pub fn try_acquire(&self, job_id: &str) -> Option<SlotLease> {
+        let permit = self.slots.clone().try_acquire_owned().ok()?;
+        Some(SlotLease { job_id: job_id.to_string(), _permit: permit })
+    }
+
+    /// Callers currently waiting in [`SlotPool::acquire`].
+    pub fn pending(&self) -> usize {
+        self.pending.load(Ordering::Relaxed)
+    }
+
+    /// Slots currently leased.
+    pub fn in_use(&self) -> usize {
+        self.capacity - self.slots.available_permits()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn leases_are_bounded_by_capacity_and_released_on_drop() {
+        let pool = SlotPool::new(2);
+        let first = pool.acquire("job-1").await;
+        let second = pool.try_acquire("job-2").expect("second slot");
+        assert_eq!(second.job_id(), "job-2");
+        assert_eq!(pool.in_use(), 2);
+        assert!(pool.try_acquire("job-3").is_none());
+
+        drop(first);
+        assert_eq!(pool.in_use(), 1);
+        let _third = pool.try_acquire("job-3").expect("slot freed by the drop");
+        assert_eq!(SlotPool::new(0).capacity(), 1);
+    }
+
+    #[tokio::test]
+    async fn waiters_are_served_in_order_and_abandoned_waits_take_nothing() {
+        let pool = SlotPool::new(1);
+        let busy = pool.acquire("job-busy").await;
+
+        let abandoned = tokio::time::timeout(Duration::from_millis(20), pool.acquire("job-cancelled")).await;
+        assert!(abandoned.is_err());
+        assert_eq!(pool.pending(), 0);
+
+        let waiter = tokio::spawn({
+            let pool = pool.clone();
+            async move { pool.acquire("job-next").await.job_id().to_string() }
+        });
+        while pool.pending() == 0 {
+            tokio::task::yield_now().await;
+        }
+        assert_eq!(pool.in_use(), 1);
+        drop(busy);
+        assert_eq!(waiter.await.expect("waiter"), "job-next");
+        assert_eq!((pool.in_use(), pool.pending()), (0, 0));
+    }
+}
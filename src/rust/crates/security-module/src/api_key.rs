@@ -0,0 +1,96 @@
+//! API-key authentication.
+
+use std::fmt;
+
+use crate::token::{AuthError, TokenValidator, constant_time_eq};
+
+/// Accepts any key of a fixed set.
+#[derive(Clone, Default)]
+pub struct ApiKeySet {
+    keys: Vec<String>,
+}
+
+impl ApiKeySet {
+    /// Blank keys are dropped, so an empty entry cannot make every empty key valid.
+    pub fn new<I, K>(keys: I) -> Self
+    where
+        I: IntoIterator<Item = K>,
+        K: Into<String>,
+    {
+        let keys = keys
+            .into_iter()
+            .map(|key| key.into().trim().to_string())
+            .filter(|key| !key.is_empty())
+            .collect();
+        Self { keys }
+    }
+
+    /// Parses a comma-separated list, e.g. the value of an environment variable.
+    pub fn parse_list(list: &str) -> Self {
+        Self::new(list.split(','))
+    }
+
+    /// Parses a keys file: one key per line; blank lines and lines starting with
+    /// `#` are ignored.
+    pub fn parse_file(contents: &str) -> Self {
+        Self::new(contents.lines().filter(|line| !line.trim_start().starts_with('#')))
+    }
+
+    /// Adds the keys of `other`.
+    pub fn extend(&mut self, other: ApiKeySet) {
+        self.keys.extend(other.keys);
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+}
+
+// Keep the keys out of logs.
+impl fmt::Debug for ApiKeySet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ApiKeySet").field("len", &self.keys.len()).finish_non_exhaustive()
+    }
+}
+
+impl TokenValidator for ApiKeySet {
+    /// Compares `key` against every configured key, so the time taken does not
+    /// reveal which key (if any) matched.
+    fn validate(&self, key: &str) -> Result<(), AuthError> {
+        let matched = self
+            .keys
+            .iter()
+            .fold(false, |matched, candidate| constant_time_eq(key.as_bytes(), candidate.as_bytes()) | matched);
+        if matched { Ok(()) } else { Err(AuthError::InvalidApiKey) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_set_accepts_only_configured_keys() {
+        let keys = ApiKeySet::parse_list("key-a, ,key-b,");
+        assert_eq!(keys.len(), 2);
+        assert_eq!(keys.validate("key-a"), Ok(()));
+        assert_eq!(keys.validate("key-b"), Ok(()));
+        assert_eq!(keys.validate("key-c"), Err(AuthError::InvalidApiKey));
+        assert_eq!(keys.validate(""), Err(AuthError::InvalidApiKey));
+        assert!(!format!("{keys:?}").contains("key-a"));
+    }
+
+    #[test]
+    fn keys_file_skips_comments_and_blank_lines() {
+        let mut keys = ApiKeySet::parse_file("# ci runners\nkey-ci\n\n  key-ops  \n");
+        keys.extend(ApiKeySet::parse_list("key-env"));
+        assert_eq!(keys.len(), 3);
+        assert_eq!(keys.validate("key-ops"), Ok(()));
+        assert_eq!(keys.validate("# ci runners"), Err(AuthError::InvalidApiKey));
+        assert!(ApiKeySet::parse_list(" , ").is_empty());
+    }
+}
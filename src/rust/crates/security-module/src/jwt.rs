@@ -0,0 +1,262 @@
+//! JWT bearer-token authentication.
+//!
+//! [`JwtValidator`] accepts HS256 tokens signed with a shared secret whose issuer
+//! and audience match a [`JwtAuthConfig`], and hands back their [`Claims`].
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use jsonwebtoken::errors::ErrorKind;
+use jsonwebtoken::{Algorithm, DecodingKey, Validation, decode};
+use serde::{Deserialize, Serialize};
+
+use crate::token::{AuthError, TokenValidator};
+
+/// Required `iss` claim; not checked when empty.
+pub const JWT_ISSUER_ENV: &str = "EIGEN_AUTH_JWT_ISSUER";
+/// Required `aud` claim; not checked when empty.
+pub const JWT_AUDIENCE_ENV: &str = "EIGEN_AUTH_JWT_AUDIENCE";
+/// JWKS endpoint for asymmetric tokens (not supported yet).
+pub const JWT_JWKS_URL_ENV: &str = "EIGEN_AUTH_JWKS_URL";
+/// Name of the variable holding the HS256 secret, [`DEFAULT_JWT_SECRET_ENV`] when unset.
+pub const JWT_SECRET_ENV_VAR_ENV: &str = "EIGEN_AUTH_JWT_SECRET_ENV";
+/// Variable holding the HS256 secret unless [`JWT_SECRET_ENV_VAR_ENV`] names another.
+pub const DEFAULT_JWT_SECRET_ENV: &str = "EIGEN_AUTH_JWT_SECRET";
+
+/// Claim holding the caller's OAuth scopes.
+pub const SCOPE_CLAIM: &str = "scope";
+/// Scope letting a caller see every job, not only the ones it submitted.
+pub const ADMIN_SCOPE: &str = "eigen:admin";
+
+/// What a token must carry to be accepted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JwtAuthConfig {
+    pub issuer: String,
+    pub audience: String,
+    pub jwks_url: Option<String>,
+    /// Environment variable holding the HS256 signing secret.
+    pub secret_env_var: String,
+}
+
+impl JwtAuthConfig {
+    /// Reads [`JWT_ISSUER_ENV`], [`JWT_AUDIENCE_ENV`], [`JWT_JWKS_URL_ENV`] and
+    /// [`JWT_SECRET_ENV_VAR_ENV`].
+    pub fn from_env() -> Self {
+        Self::from_vars(|key| std::env::var(key).ok())
+    }
+
+    /// Like [`JwtAuthConfig::from_env`], reading variables through `env`.
+    pub fn from_vars(env: impl Fn(&str) -> Option<String>) -> Self {
+        let var = |key: &str| env(key).map(|value| value.trim().to_string()).filter(|value| !value.is_empty());
+        Self {
+            issuer: var(JWT_ISSUER_ENV).unwrap_or_default(),
+            audience: var(JWT_AUDIENCE_ENV).unwrap_or_default(),
+            jwks_url: var(JWT_JWKS_URL_ENV),
+            secret_env_var: var(JWT_SECRET_ENV_VAR_ENV).unwrap_or_else(|| DEFAULT_JWT_SECRET_ENV.to_string()),
+        }
+    }
+}
+
+/// Claims of an accepted token.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Claims {
+    pub sub: String,
+    #[serde(default)]
+    pub iss: String,
+    pub exp: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iat: Option<u64>,
+    /// Every other claim, including `aud`.
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, serde_json::Value>,
+}
+
+impl Claims {
+    /// Scopes in [`SCOPE_CLAIM`], given either as one space-separated string (RFC 8693)
+    /// or as an array of strings.
+    pub fn scopes(&self) -> Vec<&str> {
+        match self.extra.get(SCOPE_CLAIM) {
+            Some(serde_json::Value::String(scopes)) => scopes.split_whitespace().collect(),
+            Some(serde_json::Value::Array(scopes)) => scopes.iter().filter_map(serde_json::Value::as_str).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scopes().contains(&scope)
+    }
+}
+
+/// Why a [`JwtValidator`] could not be built.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JwtConfigError {
+    /// The variable named by [`JwtAuthConfig::secret_env_var`] is unset or blank.
+    MissingSecret { env: String },
+    /// Only HS256 shared secrets are supported; JWKS key sets are not.
+    JwksUnsupported { url: String },
+}
+
+impl fmt::Display for JwtConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingSecret { env } => write!(f, "JWT authentication needs a signing secret in {env}"),
+            Self::JwksUnsupported { url } => {
+                write!(f, "JWKS key sets are not supported ({JWT_JWKS_URL_ENV}={url}); use an HS256 secret")
+            }
+        }
+    }
+}
+
+impl std::error::Error for JwtConfigError {}
+
+/// Verifies HS256 tokens: signature, expiry, and issuer/audience when configured.
+#[derive(Clone)]
+pub struct JwtValidator {
+    key: DecodingKey,
+    validation: Validation,
+}
+
+// Keep the signing secret out of logs.
+impl fmt::Debug for JwtValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JwtValidator")
+            .field("issuer", &self.validation.iss)
+            .field("audience", &self.validation.aud)
+            .finish_non_exhaustive()
+    }
+}
+
+impl JwtValidator {
+    /// Builds the validator for `config`, reading the secret from the variable it names.
+    pub fn from_config(config: &JwtAuthConfig) -> Result<Self, JwtConfigError> {
+        let secret = std::env::var(&config.secret_env_var).unwrap_or_default();
+        Self::hs256(config, &secret)
+    }
+
+    /// Validator for tokens signed with `secret`. Fails when `secret` is blank, so
+    /// an unset variable cannot make every token signed with an empty key valid.
+    pub fn hs256(config: &JwtAuthConfig, secret: &str) -> Result<Self, JwtConfigError> {
+        if let Some(url) = &config.jwks_url {
+            return Err(JwtConfigError::JwksUnsupported { url: url.clone() });
+        }
+        if secret.trim().is_empty() {
+            return Err(JwtConfigError::MissingSecret { env: config.secret_env_var.clone() });
+        }
+        let mut validation = Validation::new(Algorithm::HS256);
+        if !config.issuer.is_empty() {
+            validation.set_issuer(&[config.issuer.as_str()]);
+        }
+        if config.audience.is_empty() {
+            validation.validate_aud = false;
+        } else {
+            validation.set_audience(&[config.audience.as_str()]);
+        }
+        Ok(Self { key: DecodingKey::from_secret(secret.as_bytes()), validation })
+    }
+
+    /// Claims of `token`, or why it is not accepted.
+    pub fn verify(&self, token: &str) -> Result<Claims, AuthError> {
+        decode::<Claims>(token, &self.key, &self.validation)
+            .map(|data| data.claims)
+            .map_err(|err| match err.kind() {
+                ErrorKind::ExpiredSignature => AuthError::ExpiredToken,
+                _ => AuthError::InvalidToken,
+            })
+    }
+}
+
+impl TokenValidator for JwtValidator {
+    fn validate(&self, token: &str) -> Result<(), AuthError> {
+        self.verify(token).map(|_| ())
+    }
+
+    fn claims(&self, token: &str) -> Result<Option<Claims>, AuthError> {
+        self.verify(token).map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use jsonwebtoken::{EncodingKey, Header, encode};
+
+    const SECRET: &str = "kernel-jwt-secret";
+
+    fn config() -> JwtAuthConfig {
+        JwtAuthConfig {
+            issuer: "https://auth.eigen.local".to_string(),
+            audience: "eigen-kernel".to_string(),
+            jwks_url: None,
+            secret_env_var: DEFAULT_JWT_SECRET_ENV.to_string(),
+        }
+    }
+
+    fn token(secret: &str, exp_offset_secs: i64) -> String {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).expect("clock").as_secs() as i64;
+        let claims = serde_json::json!({
+            "sub": "alice",
+            "iss": "https://auth.eigen.local",
+            "aud": "eigen-kernel",
+            "iat": now,
+            "exp": now + exp_offset_secs,
+            "tenant_id": "tenant-a",
+        });
+        encode(&Header::default(), &claims, &EncodingKey::from_secret(secret.as_bytes())).expect("encode")
+    }
+
+    #[test]
+    fn valid_hs256_token_yields_its_claims() {
+        let validator = JwtValidator::hs256(&config(), SECRET).expect("validator");
+        let claims = validator.verify(&token(SECRET, 300)).expect("valid token");
+        assert_eq!((claims.sub.as_str(), claims.iss.as_str()), ("alice", "https://auth.eigen.local"));
+        assert_eq!(claims.extra["tenant_id"], "tenant-a");
+        assert!(!format!("{validator:?}").contains(SECRET));
+    }
+
+    #[test]
+    fn expired_tampered_or_foreign_tokens_are_rejected() {
+        let validator = JwtValidator::hs256(&config(), SECRET).expect("validator");
+        assert_eq!(validator.verify(&token(SECRET, -600)), Err(AuthError::ExpiredToken));
+        assert_eq!(validator.verify(&token("another-secret", 300)), Err(AuthError::InvalidToken));
+
+        let valid = token(SECRET, 300);
+        let (signed, signature) = valid.rsplit_once('.').expect("three segments");
+        let flipped = if signature.starts_with('A') { 'B' } else { 'A' };
+        let tampered = format!("{signed}.{flipped}{}", &signature[1..]);
+        assert_eq!(validator.verify(&tampered), Err(AuthError::InvalidToken));
+
+        let other_audience = JwtAuthConfig { audience: "another-service".to_string(), ..config() };
+        let validator = JwtValidator::hs256(&other_audience, SECRET).expect("validator");
+        assert_eq!(validator.verify(&valid), Err(AuthError::InvalidToken));
+    }
+
+    #[test]
+    fn scopes_come_from_a_string_or_an_array() {
+        let claims = |scope: serde_json::Value| Claims {
+            sub: "alice".to_string(),
+            iss: String::new(),
+            exp: 0,
+            iat: None,
+            extra: BTreeMap::from([(SCOPE_CLAIM.to_string(), scope)]),
+        };
+        let spaced = claims(serde_json::json!("jobs:read  eigen:admin"));
+        assert_eq!(spaced.scopes(), ["jobs:read", "eigen:admin"]);
+        assert!(spaced.has_scope(ADMIN_SCOPE));
+        assert!(claims(serde_json::json!(["eigen:admin", 7])).has_scope(ADMIN_SCOPE));
+        assert!(!claims(serde_json::json!("eigen:administrator")).has_scope(ADMIN_SCOPE));
+        assert!(claims(serde_json::json!(null)).scopes().is_empty());
+    }
+
+    #[test]
+    fn config_requires_a_secret_and_rejects_jwks() {
+        let env = |key: &str| (key == JWT_JWKS_URL_ENV).then(|| "https://auth.eigen.local/jwks".to_string());
+        let config = JwtAuthConfig::from_vars(env);
+        assert_eq!(config.secret_env_var, DEFAULT_JWT_SECRET_ENV);
+        assert!(matches!(JwtValidator::hs256(&config, SECRET), Err(JwtConfigError::JwksUnsupported { .. })));
+
+        let err = JwtValidator::hs256(&JwtAuthConfig::from_vars(|_| None), " ").expect_err("blank secret");
+        assert_eq!(err, JwtConfigError::MissingSecret { env: DEFAULT_JWT_SECRET_ENV.to_string() });
+    }
+}
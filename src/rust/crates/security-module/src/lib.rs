@@ -1,13 +1,30 @@
-//! Security module (MVP placeholder).
+//! Security module.
+//!
+//! Provides:
+//! - bearer-token authentication ([`TokenValidator`], [`StaticTokenValidator`])
+//! - signed JWT bearer tokens ([`JwtValidator`])
+//! - API-key authentication ([`ApiKeySet`])
+//! - role-based authorization of JWT callers ([`RbacPolicy`])
+//! - tenant- and owner-scoped policy rules ([`PolicyEngine`])
 //!
 //! Future responsibilities:
-//! - authentication / authorization enforcement
 //! - secret management
-//! - policy evaluation
 
 #![forbid(unsafe_code)]
 
-/// Returns a stable placeholder value.
-pub fn hello_security_module() -> &'static str {
-    "security-module"
-}
+mod api_key;
+mod jwt;
+mod policy;
+mod rbac;
+mod token;
+
+pub use api_key::ApiKeySet;
+pub use jwt::{
+    ADMIN_SCOPE, Claims, DEFAULT_JWT_SECRET_ENV, JWT_AUDIENCE_ENV, JWT_ISSUER_ENV, JWT_JWKS_URL_ENV,
+    JWT_SECRET_ENV_VAR_ENV, JwtAuthConfig, JwtConfigError, JwtValidator, SCOPE_CLAIM,
+};
+pub use policy::{
+    Action, DEFAULT_RULE, Decision, Effect, PolicyEngine, PolicyError, Principal, Resource, Rule, Scope, TENANT_CLAIM,
+};
+pub use rbac::{Permission, RbacError, RbacPolicy, ROLE_CLAIM, role};
+pub use token::{AuthError, StaticTokenValidator, TokenValidator, bearer_token};
@@ -0,0 +1,312 @@
+//! Attribute-based policy evaluation.
+//!
+//! Where [`RbacPolicy`](crate::RbacPolicy) only asks what a role may do, a
+//! [`PolicyEngine`] also looks at whose job it is: rules can be limited to jobs of
+//! the caller's own tenant or to jobs the caller submitted. Rules are tried in
+//! file order and the first one matching decides; when none matches the engine's
+//! default effect applies.
+//!
+//! ```toml
+//! default = "deny"
+//!
+//! [[rules]]
+//! name = "admins"
+//! effect = "allow"
+//! actions = ["submit", "status", "cancel", "results"]
+//! roles = ["admin"]
+//!
+//! [[rules]]
+//! name = "own-jobs"
+//! effect = "allow"
+//! actions = ["submit", "status", "cancel", "results"]
+//! scope = "owner"
+//!
+//! [[rules]]
+//! name = "tenant-read"
+//! effect = "allow"
+//! actions = ["status", "results"]
+//! scope = "tenant"
+//! ```
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::jwt::Claims;
+use crate::rbac::role;
+
+/// JWT claim naming the caller's tenant.
+pub const TENANT_CLAIM: &str = "tenant_id";
+
+/// Who is asking.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Principal {
+    pub subject: String,
+    pub tenant_id: String,
+    pub roles: Vec<String>,
+}
+
+impl Principal {
+    /// The caller described by a JWT: `sub`, [`TENANT_CLAIM`] and the `role` claim.
+    pub fn from_claims(claims: &Claims) -> Self {
+        Self {
+            subject: claims.sub.clone(),
+            tenant_id: claims.extra.get(TENANT_CLAIM).and_then(serde_json::Value::as_str).unwrap_or("").to_string(),
+            roles: role(claims).map(str::to_string).into_iter().collect(),
+        }
+    }
+}
+
+/// What the principal wants to do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    Submit,
+    Status,
+    Cancel,
+    Results,
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Submit => "submit",
+            Self::Status => "status",
+            Self::Cancel => "cancel",
+            Self::Results => "results",
+        })
+    }
+}
+
+/// The job acted on; for [`Action::Submit`], the job about to be created.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Resource {
+    pub tenant_id: String,
+    /// Subject that submitted the job.
+    pub owner: String,
+}
+
+/// Whether a rule allows or denies what it matches.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Effect {
+    Allow,
+    #[default]
+    Deny,
+}
+
+/// Which jobs a rule applies to, relative to the principal.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Scope {
+    /// Every job.
+    #[default]
+    Any,
+    /// Jobs of the principal's tenant.
+    Tenant,
+    /// Jobs of the principal's tenant that the principal submitted.
+    Owner,
+}
+
+/// One entry of the rule set.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Rule {
+    pub name: String,
+    pub effect: Effect,
+    pub actions: Vec<Action>,
+    /// Roles the rule applies to; empty for every principal.
+    #[serde(default)]
+    pub roles: Vec<String>,
+    #[serde(default)]
+    pub scope: Scope,
+}
+
+impl Rule {
+    fn matches(&self, principal: &Principal, action: Action, resource: &Resource) -> bool {
+        let same_tenant = !principal.tenant_id.is_empty() && principal.tenant_id == resource.tenant_id;
+        self.actions.contains(&action)
+            && (self.roles.is_empty() || principal.roles.iter().any(|role| self.roles.contains(role)))
+            && match self.scope {
+                Scope::Any => true,
+                Scope::Tenant => same_tenant,
+                Scope::Owner => same_tenant && !principal.subject.is_empty() && principal.subject == resource.owner,
+            }
+    }
+}
+
+/// Outcome of [`PolicyEngine::check`], naming the rule that decided it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Decision {
+    Allow { rule: String },
+    /// `rule` is [`DEFAULT_RULE`] when no rule matched.
+    Deny { rule: String },
+}
+
+/// Rule name reported when the default effect decided.
+pub const DEFAULT_RULE: &str = "default";
+
+impl Decision {
+    pub fn is_allowed(&self) -> bool {
+        matches!(self, Self::Allow { .. })
+    }
+
+    pub fn rule(&self) -> &str {
+        match self {
+            Self::Allow { rule } | Self::Deny { rule } => rule,
+        }
+    }
+}
+
+/// Why a policy file could not be loaded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PolicyError {
+    Read { path: PathBuf, message: String },
+    Parse { message: String },
+}
+
+impl fmt::Display for PolicyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Read { path, message } => write!(f, "cannot read policy {}: {message}", path.display()),
+            Self::Parse { message } => write!(f, "invalid policy: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for PolicyError {}
+
+/// Ordered rules with a default effect.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PolicyEngine {
+    #[serde(default)]
+    default: Effect,
+    #[serde(default)]
+    rules: Vec<Rule>,
+}
+
+impl PolicyEngine {
+    /// Denies whatever `rules` do not allow.
+    pub fn new(rules: Vec<Rule>) -> Self {
+        Self { default: Effect::Deny, rules }
+    }
+
+    /// Reads the policy file at `path`.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, PolicyError> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .map_err(|err| PolicyError::Read { path: path.to_path_buf(), message: err.to_string() })?;
+        Self::from_toml_str(&contents)
+    }
+
+    pub fn from_toml_str(contents: &str) -> Result<Self, PolicyError> {
+        toml::from_str(contents).map_err(|err| PolicyError::Parse { message: err.to_string() })
+    }
+
+    /// Decision of the first rule matching, else of the default effect.
+    pub fn check(&self, principal: &Principal, action: Action, resource: &Resource) -> Decision {
+        let (effect, rule) = self
+            .rules
+            .iter()
+            .find(|rule| rule.matches(principal, action, resource))
+            .map_or((self.default, DEFAULT_RULE), |rule| (rule.effect, rule.name.as_str()));
+        match effect {
+            Effect::Allow => Decision::Allow { rule: rule.to_string() },
+            Effect::Deny => Decision::Deny { rule: rule.to_string() },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POLICY: &str = r#"
+        default = "deny"
+
+        [[rules]]
+        name = "suspended"
+        effect = "deny"
+        actions = ["submit"]
+        roles = ["suspended"]
+
+        [[rules]]
+        name = "admins"
+        effect = "allow"
+        actions = ["submit", "status", "cancel", "results"]
+        roles = ["admin"]
+
+        [[rules]]
+        name = "own-jobs"
+        effect = "allow"
+        actions = ["submit", "status", "cancel", "results"]
+        scope = "owner"
+
+        [[rules]]
+        name = "tenant-read"
+        effect = "allow"
+        actions = ["status", "results"]
+        scope = "tenant"
+    "#;
+
+    fn principal(subject: &str, tenant_id: &str, role: Option<&str>) -> Principal {
+        Principal {
+            subject: subject.to_string(),
+            tenant_id: tenant_id.to_string(),
+            roles: role.map(str::to_string).into_iter().collect(),
+        }
+    }
+
+    fn job(tenant_id: &str, owner: &str) -> Resource {
+        Resource { tenant_id: tenant_id.to_string(), owner: owner.to_string() }
+    }
+
+    #[test]
+    fn first_matching_rule_allows_or_denies() {
+        let engine = PolicyEngine::from_toml_str(POLICY).expect("policy");
+        let admin = principal("root", "tenant-ops", Some("admin"));
+        assert_eq!(engine.check(&admin, Action::Cancel, &job("tenant-b", "bob")), Decision::Allow {
+            rule: "admins".to_string()
+        });
+        let suspended = principal("eve", "tenant-a", Some("suspended"));
+        assert_eq!(engine.check(&suspended, Action::Submit, &job("tenant-a", "eve")), Decision::Deny {
+            rule: "suspended".to_string()
+        });
+    }
+
+    #[test]
+    fn tenants_may_cancel_only_their_own_jobs() {
+        let engine = PolicyEngine::from_toml_str(POLICY).expect("policy");
+        let alice = principal("alice", "tenant-a", None);
+
+        let own = engine.check(&alice, Action::Cancel, &job("tenant-a", "alice"));
+        assert_eq!(own.rule(), "own-jobs");
+        assert!(own.is_allowed());
+        let colleague = job("tenant-a", "carol");
+        assert!(engine.check(&alice, Action::Status, &colleague).is_allowed(), "tenant members may read");
+        assert_eq!(engine.check(&alice, Action::Cancel, &colleague), Decision::Deny { rule: DEFAULT_RULE.to_string() });
+        let other_tenant = job("tenant-b", "alice");
+        assert!(!engine.check(&alice, Action::Status, &other_tenant).is_allowed(), "same subject, other tenant");
+        assert!(!engine.check(&principal("alice", "", None), Action::Status, &job("", "alice")).is_allowed());
+    }
+
+    #[test]
+    fn principal_comes_from_jwt_claims_and_bad_policies_are_rejected() {
+        let extra = [("tenant_id", "tenant-a"), ("role", "admin")]
+            .into_iter()
+            .map(|(key, value)| (key.to_string(), serde_json::Value::from(value)))
+            .collect();
+        let claims = Claims { sub: "alice".to_string(), iss: String::new(), exp: 0, iat: None, extra };
+        assert_eq!(Principal::from_claims(&claims), principal("alice", "tenant-a", Some("admin")));
+
+        let err = PolicyEngine::from_toml_str("[[rules]]\nname = \"x\"\neffect = \"allow\"\nactions = [\"purge\"]\n")
+            .expect_err("unknown action");
+        assert!(matches!(err, PolicyError::Parse { .. }), "{err}");
+        let open = PolicyEngine::from_toml_str("default = \"allow\"\n").expect("policy");
+        assert!(open.check(&Principal::default(), Action::Submit, &Resource::default()).is_allowed());
+        let closed = PolicyEngine::new(Vec::new());
+        assert!(!closed.check(&Principal::default(), Action::Status, &Resource::default()).is_allowed());
+    }
+}
@@ -0,0 +1,166 @@
+//! Role-based authorization.
+//!
+//! An [`RbacPolicy`] maps role names to the [`Permission`]s they grant. A caller's
+//! role is the `role` claim of their JWT; callers without one are granted nothing.
+//!
+//! Policies are TOML files with one `[roles]` entry per role:
+//!
+//! ```toml
+//! [roles]
+//! viewer = ["read_job_status", "read_job_results", "list_jobs"]
+//! operator = ["submit_job", "cancel_job", "read_job_status", "read_job_results", "list_jobs"]
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::jwt::Claims;
+
+/// JWT claim naming the caller's role.
+pub const ROLE_CLAIM: &str = "role";
+
+/// Something a role may be allowed to do with jobs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Permission {
+    SubmitJob,
+    CancelJob,
+    ReadJobStatus,
+    ReadJobResults,
+    ListJobs,
+    DeleteJob,
+}
+
+impl Permission {
+    /// The name used for this permission in policy files.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::SubmitJob => "submit_job",
+            Self::CancelJob => "cancel_job",
+            Self::ReadJobStatus => "read_job_status",
+            Self::ReadJobResults => "read_job_results",
+            Self::ListJobs => "list_jobs",
+            Self::DeleteJob => "delete_job",
+        }
+    }
+}
+
+impl fmt::Display for Permission {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Why a policy could not be loaded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RbacError {
+    Read { path: PathBuf, message: String },
+    Parse { message: String },
+}
+
+impl fmt::Display for RbacError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Read { path, message } => write!(f, "cannot read RBAC policy {}: {message}", path.display()),
+            Self::Parse { message } => write!(f, "invalid RBAC policy: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for RbacError {}
+
+/// Permissions granted to each role.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RbacPolicy {
+    #[serde(default)]
+    roles: BTreeMap<String, Vec<Permission>>,
+}
+
+impl RbacPolicy {
+    /// Reads the policy file at `path`.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, RbacError> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .map_err(|err| RbacError::Read { path: path.to_path_buf(), message: err.to_string() })?;
+        Self::from_toml_str(&contents)
+    }
+
+    /// Parses a policy; unknown permission names are an error rather than ignored.
+    pub fn from_toml_str(contents: &str) -> Result<Self, RbacError> {
+        toml::from_str(contents).map_err(|err| RbacError::Parse { message: err.to_string() })
+    }
+
+    /// Grants `permissions` to `role`, replacing what it had.
+    pub fn with_role(mut self, role: impl Into<String>, permissions: impl IntoIterator<Item = Permission>) -> Self {
+        self.roles.insert(role.into(), permissions.into_iter().collect());
+        self
+    }
+
+    /// Whether `role` is granted `permission`.
+    pub fn grants(&self, role: &str, permission: Permission) -> bool {
+        self.roles.get(role).is_some_and(|permissions| permissions.contains(&permission))
+    }
+
+    /// Whether the caller holding `claims` is granted `permission` through its
+    /// [`ROLE_CLAIM`]. A missing, non-string or unknown role is granted nothing.
+    pub fn is_allowed(&self, claims: &Claims, permission: Permission) -> bool {
+        role(claims).is_some_and(|role| self.grants(role, permission))
+    }
+}
+
+/// The caller's [`ROLE_CLAIM`], when it is a string.
+pub fn role(claims: &Claims) -> Option<&str> {
+    claims.extra.get(ROLE_CLAIM).and_then(serde_json::Value::as_str)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POLICY: &str = r#"
+        [roles]
+        viewer = ["read_job_status", "read_job_results", "list_jobs"]
+        operator = ["submit_job", "cancel_job", "read_job_status", "read_job_results", "list_jobs"]
+    "#;
+
+    fn claims(role: Option<serde_json::Value>) -> Claims {
+        let mut claims =
+            Claims { sub: "alice".to_string(), iss: String::new(), exp: 0, iat: None, extra: BTreeMap::new() };
+        if let Some(role) = role {
+            claims.extra.insert(ROLE_CLAIM.to_string(), role);
+        }
+        claims
+    }
+
+    #[test]
+    fn viewer_may_read_status_but_not_cancel() {
+        let policy = RbacPolicy::from_toml_str(POLICY).expect("policy");
+        let viewer = claims(Some("viewer".into()));
+        assert!(policy.is_allowed(&viewer, Permission::ReadJobStatus));
+        assert!(!policy.is_allowed(&viewer, Permission::CancelJob));
+        assert!(!policy.is_allowed(&viewer, Permission::SubmitJob));
+        assert!(policy.is_allowed(&claims(Some("operator".into())), Permission::CancelJob));
+    }
+
+    #[test]
+    fn missing_or_unknown_roles_are_granted_nothing() {
+        let policy = RbacPolicy::from_toml_str(POLICY).expect("policy");
+        for claims in [claims(None), claims(Some("auditor".into())), claims(Some(serde_json::json!(["viewer"])))] {
+            assert!(!policy.is_allowed(&claims, Permission::ReadJobStatus), "{claims:?}");
+        }
+    }
+
+    #[test]
+    fn unknown_permissions_and_unreadable_files_are_rejected() {
+        let err = RbacPolicy::from_toml_str("[roles]\nviewer = [\"delete_everything\"]\n").expect_err("unknown");
+        assert!(matches!(err, RbacError::Parse { .. }), "{err}");
+        let err = RbacPolicy::load("/nonexistent/rbac.toml").expect_err("missing file");
+        assert!(matches!(err, RbacError::Read { .. }), "{err}");
+        let policy = RbacPolicy::default().with_role("viewer", [Permission::ListJobs]);
+        assert!(policy.grants("viewer", Permission::ListJobs));
+    }
+}
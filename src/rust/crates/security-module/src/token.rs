@@ -0,0 +1,124 @@
+//! Bearer-token authentication.
+
+use std::fmt;
+
+use crate::jwt::Claims;
+
+/// Why a request failed authentication.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuthError {
+    /// No `authorization` header, or one without a `Bearer` token.
+    MissingToken,
+    /// A token was presented but is not accepted.
+    InvalidToken,
+    /// A signed token was presented after its `exp` time.
+    ExpiredToken,
+    /// No API key was presented.
+    MissingApiKey,
+    /// An API key was presented but is not one of the configured keys.
+    InvalidApiKey,
+}
+
+impl fmt::Display for AuthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingToken => f.write_str("missing bearer token"),
+            Self::InvalidToken => f.write_str("invalid bearer token"),
+            Self::ExpiredToken => f.write_str("expired bearer token"),
+            Self::MissingApiKey => f.write_str("missing api key"),
+            Self::InvalidApiKey => f.write_str("invalid api key"),
+        }
+    }
+}
+
+impl std::error::Error for AuthError {}
+
+/// Decides whether a bearer token may call a service.
+pub trait TokenValidator: Send + Sync {
+    fn validate(&self, token: &str) -> Result<(), AuthError>;
+
+    /// Validates `token` like [`TokenValidator::validate`] and returns the claims it
+    /// carries; `None` for opaque tokens.
+    fn claims(&self, token: &str) -> Result<Option<Claims>, AuthError> {
+        self.validate(token).map(|()| None)
+    }
+}
+
+/// Accepts exactly one shared secret.
+#[derive(Clone)]
+pub struct StaticTokenValidator {
+    secret: String,
+}
+
+impl StaticTokenValidator {
+    /// Fails with [`AuthError::MissingToken`] when `secret` is blank, so an unset
+    /// env var cannot turn into "every empty token is valid".
+    pub fn new(secret: impl Into<String>) -> Result<Self, AuthError> {
+        let secret = secret.into();
+        if secret.trim().is_empty() {
+            return Err(AuthError::MissingToken);
+        }
+        Ok(Self { secret })
+    }
+}
+
+// Keep the secret out of logs.
+impl fmt::Debug for StaticTokenValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StaticTokenValidator").finish_non_exhaustive()
+    }
+}
+
+impl TokenValidator for StaticTokenValidator {
+    fn validate(&self, token: &str) -> Result<(), AuthError> {
+        if constant_time_eq(token.as_bytes(), self.secret.as_bytes()) {
+            Ok(())
+        } else {
+            Err(AuthError::InvalidToken)
+        }
+    }
+}
+
+/// Extracts the token from an `authorization` header value of the form
+/// `Bearer <token>` (scheme is case-insensitive).
+pub fn bearer_token(header_value: &str) -> Option<&str> {
+    let (scheme, token) = header_value.trim().split_once(' ')?;
+    let token = token.trim();
+    (scheme.eq_ignore_ascii_case("bearer") && !token.is_empty()).then_some(token)
+}
+
+/// Compares without short-circuiting on the first differing byte.
+pub(crate) fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn static_validator_accepts_only_the_secret() {
+        let validator = StaticTokenValidator::new("s3cret").expect("validator");
+        assert_eq!(validator.validate("s3cret"), Ok(()));
+        assert_eq!(validator.validate("s3cret2"), Err(AuthError::InvalidToken));
+        assert_eq!(validator.validate(""), Err(AuthError::InvalidToken));
+        assert!(!format!("{validator:?}").contains("s3cret"));
+    }
+
+    #[test]
+    fn blank_secret_is_rejected() {
+        assert_eq!(StaticTokenValidator::new("  ").unwrap_err(), AuthError::MissingToken);
+    }
+
+    #[test]
+    fn bearer_token_parses_scheme_case_insensitively() {
+        assert_eq!(bearer_token("Bearer abc"), Some("abc"));
+        assert_eq!(bearer_token("bearer  abc "), Some("abc"));
+        assert_eq!(bearer_token("Basic abc"), None);
+        assert_eq!(bearer_token("Bearer "), None);
+        assert_eq!(bearer_token("abc"), None);
+    }
+}